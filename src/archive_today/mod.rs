@@ -2,12 +2,55 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{Context, Result};
+use chrono::Utc;
 use reqwest::Client;
 use tokio::sync::Semaphore;
 use tokio::time::sleep;
 use tracing::{debug, info, warn};
 
 use crate::constants::ARCHIVAL_USER_AGENT;
+use crate::retry_after::parse_retry_after;
+
+/// Default base URL for Archive.today submissions and lookups.
+pub const ARCHIVE_TODAY_BASE_URL: &str = "https://archive.today";
+
+/// Domains Archive.today serves from. The canonical one rotates, so snapshot
+/// URLs on any of them are recognised.
+pub const ARCHIVE_TODAY_DOMAINS: [&str; 6] = [
+    "archive.today",
+    "archive.ph",
+    "archive.is",
+    "archive.li",
+    "archive.vn",
+    "archive.md",
+];
+
+/// Normalize a configured base (`archive.ph` or `https://archive.ph/`) to a
+/// base URL without a trailing slash.
+///
+/// Returns `None` unless the host is one of [`ARCHIVE_TODAY_DOMAINS`] and the
+/// URL has no path or query.
+#[must_use]
+pub fn normalize_base_url(base: &str) -> Option<String> {
+    let base = base.trim();
+    let with_scheme = if base.contains("://") {
+        base.to_string()
+    } else {
+        format!("https://{base}")
+    };
+    let parsed = url::Url::parse(&with_scheme).ok()?;
+    let host = parsed.host_str()?.to_ascii_lowercase();
+    let known = ARCHIVE_TODAY_DOMAINS.contains(&host.as_str());
+    let bare = parsed.path() == "/" && parsed.query().is_none() && parsed.port().is_none();
+    (matches!(parsed.scheme(), "http" | "https") && known && bare)
+        .then(|| format!("{}://{host}", parsed.scheme()))
+}
+
+/// How many times an in-progress snapshot is re-checked before giving up.
+const SNAPSHOT_POLL_ATTEMPTS: u32 = 5;
+
+/// Delay before the first re-check; doubles after each attempt.
+const SNAPSHOT_POLL_DELAY: Duration = Duration::from_secs(10);
 
 /// Rate-limited Archive.today client.
 pub struct ArchiveTodayClient {
@@ -16,6 +59,10 @@ pub struct ArchiveTodayClient {
     rate_limiter: Arc<Semaphore>,
     /// Interval between permit releases.
     permit_interval: Duration,
+    /// Base URL requests are sent to.
+    base_url: String,
+    /// Delay before the first in-progress snapshot re-check.
+    poll_delay: Duration,
 }
 
 impl ArchiveTodayClient {
@@ -24,8 +71,16 @@ impl ArchiveTodayClient {
     /// # Arguments
     ///
     /// * `rate_limit_per_min` - Maximum submissions per minute (default 3).
+    /// * `base_url` - Mirror used for submissions and lookups (`ARCHIVE_TODAY_BASE`).
+    #[must_use]
+    pub fn new(rate_limit_per_min: u32, base_url: &str) -> Self {
+        Self::with_base_url(rate_limit_per_min, base_url, SNAPSHOT_POLL_DELAY)
+    }
+
+    /// Create a client that talks to `base_url` and first re-checks
+    /// in-progress snapshots after `poll_delay`.
     #[must_use]
-    pub fn new(rate_limit_per_min: u32) -> Self {
+    pub fn with_base_url(rate_limit_per_min: u32, base_url: &str, poll_delay: Duration) -> Self {
         let rate_limit = rate_limit_per_min.max(1) as usize;
         let permit_interval = Duration::from_secs(60) / rate_limit as u32;
 
@@ -55,12 +110,18 @@ impl ArchiveTodayClient {
             client,
             rate_limiter,
             permit_interval,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            poll_delay,
         }
     }
 
     /// Submit a URL to Archive.today for archiving.
     ///
-    /// Returns the archive URL if successful.
+    /// Returns the snapshot URL once Archive.today confirms it. A "snapshot
+    /// is being created" interstitial is re-checked with backoff; if the
+    /// snapshot never appears (or no snapshot URL can be found) nothing is
+    /// returned, so the archive keeps no Archive.today link rather than a
+    /// search page.
     ///
     /// # Errors
     ///
@@ -87,58 +148,60 @@ impl ArchiveTodayClient {
 
         // Submit the URL for archiving
         // Archive.today's submission endpoint
-        let submit_url = "https://archive.today/submit/";
+        let submit_url = self.submit_url();
 
         let form = [("url", url)];
 
         let response = self
             .client
-            .post(submit_url)
+            .post(&submit_url)
             .form(&form)
             .send()
             .await
             .context("Failed to submit to Archive.today")?;
 
         let status = response.status();
+        let retry_after = parse_retry_after(response.headers(), Utc::now());
         let final_url = response.url().to_string();
+        // The interstitial points at the work-in-progress page via a Refresh header
+        let refresh_url = response
+            .headers()
+            .get("refresh")
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_refresh_url);
 
         // Archive.today typically redirects to the archived page
         if status.is_success() || status.is_redirection() {
             // Read the response body once
             let body = response.text().await.unwrap_or_default();
 
-            // Check if we got redirected to an archive page
-            if final_url.contains("archive.today/") || final_url.contains("archive.ph/") {
-                // Look for the canonical archive URL in the response
-                if let Some(archive_url) = extract_archive_url(&body) {
-                    info!(url = %url, archive = %archive_url, "Archive.today snapshot created");
-                    return Ok(Some(archive_url));
-                }
-
-                // If we're on an archive.today URL, return it
-                if final_url.starts_with("https://archive.today/")
-                    || final_url.starts_with("https://archive.ph/")
-                    || final_url.starts_with("https://archive.is/")
-                {
-                    info!(url = %url, archive = %final_url, "Archive.today snapshot created");
-                    return Ok(Some(final_url));
-                }
-            }
-
-            // Try to get archive URL from response body
-            if let Some(archive_url) = extract_archive_url(&body) {
+            if let Some(archive_url) = resolved_snapshot_url(&final_url, &body) {
                 info!(url = %url, archive = %archive_url, "Archive.today snapshot created");
                 return Ok(Some(archive_url));
             }
 
-            // Return generic search URL if we can't determine exact archive
-            let search_url = format!("https://archive.today/{}", urlencoding::encode(url));
-            info!(url = %url, "Archive.today submission accepted");
-            Ok(Some(search_url))
+            if refresh_url.is_none() && !is_in_progress_page(&final_url, &body) {
+                warn!(url = %url, final_url = %final_url, "Archive.today submission returned no snapshot URL");
+                return Ok(None);
+            }
+
+            let poll_url = refresh_url.unwrap_or(final_url);
+            debug!(url = %url, poll_url = %poll_url, "Archive.today snapshot in progress");
+            let snapshot = self.poll_snapshot(&poll_url).await?;
+            match &snapshot {
+                Some(archive_url) => {
+                    info!(url = %url, archive = %archive_url, "Archive.today snapshot created");
+                }
+                None => {
+                    warn!(url = %url, "Archive.today snapshot still in progress, leaving for retry");
+                }
+            }
+            Ok(snapshot)
         } else if status.as_u16() == 429 {
-            warn!(url = %url, "Archive.today rate limited, will retry later");
-            // Wait extra time on rate limit
-            sleep(self.permit_interval * 3).await;
+            // Wait as long as the server asks, or extra time if it doesn't say
+            let wait = retry_after.unwrap_or(self.permit_interval * 3);
+            warn!(url = %url, wait_secs = wait.as_secs(), "Archive.today rate limited, will retry later");
+            sleep(wait).await;
             Ok(None)
         } else {
             warn!(url = %url, status = %status, "Archive.today submission failed");
@@ -146,11 +209,54 @@ impl ArchiveTodayClient {
         }
     }
 
+    /// Re-check an in-progress snapshot until it resolves.
+    ///
+    /// Waits `poll_delay` before the first check and doubles the wait after
+    /// each one, giving up after [`SNAPSHOT_POLL_ATTEMPTS`] checks.
+    async fn poll_snapshot(&self, poll_url: &str) -> Result<Option<String>> {
+        let mut delay = self.poll_delay;
+
+        for attempt in 1..=SNAPSHOT_POLL_ATTEMPTS {
+            sleep(delay).await;
+            delay *= 2;
+
+            let response = self
+                .client
+                .get(poll_url)
+                .send()
+                .await
+                .context("Failed to poll Archive.today snapshot")?;
+            if !response.status().is_success() {
+                debug!(attempt, status = %response.status(), "Archive.today snapshot not ready");
+                continue;
+            }
+
+            let final_url = response.url().to_string();
+            let body = response.text().await.unwrap_or_default();
+            if let Some(archive_url) = resolved_snapshot_url(&final_url, &body) {
+                return Ok(Some(archive_url));
+            }
+            debug!(attempt, "Archive.today snapshot still in progress");
+        }
+
+        Ok(None)
+    }
+
+    /// Form endpoint new snapshots are submitted to.
+    fn submit_url(&self) -> String {
+        format!("{}/submit/", self.base_url)
+    }
+
+    /// Lookup URL that redirects to the newest snapshot of `url`, if any.
+    fn check_url(&self, url: &str) -> String {
+        format!("{}/{}", self.base_url, urlencoding::encode(url))
+    }
+
     /// Check if a URL has been archived on Archive.today.
     ///
     /// Returns the most recent archive URL if available.
     pub async fn check_existing(&self, url: &str) -> Result<Option<String>> {
-        let check_url = format!("https://archive.today/{}", urlencoding::encode(url));
+        let check_url = self.check_url(url);
 
         let response = self
             .client
@@ -181,24 +287,50 @@ impl ArchiveTodayClient {
     }
 }
 
+/// Detect Archive.today's "your snapshot is being created" interstitial.
+///
+/// While archiving, submissions land on a `/wip/<hash>` page that refreshes
+/// itself until the snapshot is ready.
+fn is_in_progress_page(final_url: &str, body: &str) -> bool {
+    let body_lower = body.to_ascii_lowercase();
+
+    final_url.contains("/wip/")
+        || body_lower.contains("snapshot is being created")
+        || body_lower.contains("archiving in progress")
+}
+
+/// The snapshot URL for a finished page, or `None` while it's in progress.
+fn resolved_snapshot_url(final_url: &str, body: &str) -> Option<String> {
+    if is_in_progress_page(final_url, body) {
+        return None;
+    }
+    if is_archive_url(final_url) {
+        return Some(final_url.to_string());
+    }
+    extract_archive_url(body)
+}
+
+/// Extract the target URL from a `Refresh: <seconds>;url=<url>` header.
+fn parse_refresh_url(value: &str) -> Option<String> {
+    let (_, rest) = value.split_once(';')?;
+    let (key, url) = rest.trim().split_once('=')?;
+    if !key.trim().eq_ignore_ascii_case("url") {
+        return None;
+    }
+    let url = url.trim().trim_matches(|c| c == '\'' || c == '"');
+    (!url.is_empty()).then(|| url.to_string())
+}
+
 /// Check if a URL is an Archive.today archive URL.
 fn is_archive_url(url: &str) -> bool {
     // Known non-archive paths that should not be matched
     const EXCLUDED_PATHS: [&str; 5] = ["submit", "search", "about", "faq", "timegate"];
 
-    let patterns = [
-        "archive.today/",
-        "archive.ph/",
-        "archive.is/",
-        "archive.li/",
-        "archive.vn/",
-        "archive.md/",
-    ];
-
-    for pattern in &patterns {
-        if url.contains(pattern) {
+    for domain in ARCHIVE_TODAY_DOMAINS {
+        let pattern = format!("{domain}/");
+        if url.contains(&pattern) {
             // Check that it's not just a search/submit page
-            if let Some(after_domain) = url.split(pattern).nth(1) {
+            if let Some(after_domain) = url.split(&pattern).nth(1) {
                 // Archive URLs have a short hash after the domain
                 let first_part = after_domain.split('/').next().unwrap_or("");
                 // Exclude known non-archive paths
@@ -244,12 +376,8 @@ fn extract_archive_url(body: &str) -> Option<String> {
     }
 
     // Look for archive links in the body
-    for pattern in &[
-        "https://archive.today/",
-        "https://archive.ph/",
-        "https://archive.is/",
-    ] {
-        if let Some(start) = body.find(pattern) {
+    for domain in ARCHIVE_TODAY_DOMAINS {
+        if let Some(start) = body.find(&format!("https://{domain}/")) {
             let after_start = &body[start..];
             // Find the end of the URL (quote, space, or angle bracket)
             let end = after_start
@@ -270,6 +398,11 @@ fn extract_archive_url(body: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const WIP_BODY: &str =
+        "<html><body>Your snapshot is being created. Please refresh in a few seconds.</body></html>";
 
     #[test]
     fn test_is_archive_url() {
@@ -281,6 +414,38 @@ mod tests {
         assert!(!is_archive_url("https://archive.today/")); // Just root URL
     }
 
+    #[test]
+    fn test_normalize_base_url() {
+        assert_eq!(
+            normalize_base_url("archive.ph"),
+            Some("https://archive.ph".to_string())
+        );
+        assert_eq!(
+            normalize_base_url("https://Archive.IS/"),
+            Some("https://archive.is".to_string())
+        );
+        assert_eq!(
+            normalize_base_url(ARCHIVE_TODAY_BASE_URL),
+            Some(ARCHIVE_TODAY_BASE_URL.to_string())
+        );
+        assert_eq!(normalize_base_url("https://example.com"), None);
+        assert_eq!(normalize_base_url("https://archive.ph.evil.com"), None);
+        assert_eq!(normalize_base_url("https://archive.ph/submit/"), None);
+        assert_eq!(normalize_base_url("ftp://archive.ph"), None);
+    }
+
+    #[tokio::test]
+    async fn test_urls_use_configured_base() {
+        let client = ArchiveTodayClient::new(3, "https://archive.ph");
+        assert_eq!(client.submit_url(), "https://archive.ph/submit/");
+        assert_eq!(
+            client.check_url("https://example.com/a?b=1"),
+            "https://archive.ph/https%3A%2F%2Fexample.com%2Fa%3Fb%3D1"
+        );
+        // Snapshots on other mirrors are still recognised
+        assert!(is_archive_url("https://archive.md/AbCd1"));
+    }
+
     #[test]
     fn test_extract_archive_url() {
         let html = r#"<link rel="canonical" href="https://archive.today/AbCd1">"#;
@@ -292,4 +457,104 @@ mod tests {
         let html_no_match = r#"<link rel="canonical" href="https://example.com">"#;
         assert_eq!(extract_archive_url(html_no_match), None);
     }
+
+    #[test]
+    fn test_is_in_progress_page() {
+        assert!(is_in_progress_page("https://archive.ph/wip/AbCd1", ""));
+        assert!(is_in_progress_page("https://archive.ph/submit/", WIP_BODY));
+        assert!(!is_in_progress_page(
+            "https://archive.ph/AbCd1",
+            r#"<link rel="canonical" href="https://archive.ph/AbCd1">"#
+        ));
+
+        // In-progress pages never count as a snapshot, even if they link one
+        assert_eq!(
+            resolved_snapshot_url(
+                "https://archive.ph/wip/AbCd1",
+                "See https://archive.ph/AbCd1 when ready"
+            ),
+            None
+        );
+        assert_eq!(
+            resolved_snapshot_url("https://archive.ph/AbCd1", ""),
+            Some("https://archive.ph/AbCd1".to_string())
+        );
+        assert_eq!(
+            resolved_snapshot_url("https://archive.today/https%3A%2F%2Fexample.com", ""),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_refresh_url() {
+        assert_eq!(
+            parse_refresh_url("0;url=https://archive.ph/wip/AbCd1"),
+            Some("https://archive.ph/wip/AbCd1".to_string())
+        );
+        assert_eq!(
+            parse_refresh_url("5; URL='https://archive.ph/wip/AbCd1'"),
+            Some("https://archive.ph/wip/AbCd1".to_string())
+        );
+        assert_eq!(parse_refresh_url("5"), None);
+    }
+
+    async fn mount_wip_submission(server: &MockServer) {
+        Mock::given(method("POST"))
+            .and(path("/submit/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("refresh", format!("0;url={}/wip/AbCd1", server.uri()))
+                    .set_body_string(WIP_BODY),
+            )
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_submit_polls_until_snapshot_resolves() {
+        let server = MockServer::start().await;
+        mount_wip_submission(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/wip/AbCd1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(WIP_BODY))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        // Once done, the work-in-progress page redirects to the snapshot
+        Mock::given(method("GET"))
+            .and(path("/wip/AbCd1"))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", "/AbCd1"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/AbCd1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"<link rel="canonical" href="https://archive.ph/AbCd1">"#),
+            )
+            .mount(&server)
+            .await;
+
+        let client = ArchiveTodayClient::with_base_url(60, &server.uri(), Duration::from_millis(1));
+        let snapshot = client.submit("https://example.com/page").await.unwrap();
+
+        assert_eq!(snapshot, Some("https://archive.ph/AbCd1".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_submit_unresolved_snapshot_stores_nothing() {
+        let server = MockServer::start().await;
+        mount_wip_submission(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/wip/AbCd1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(WIP_BODY))
+            .expect(u64::from(SNAPSHOT_POLL_ATTEMPTS))
+            .mount(&server)
+            .await;
+
+        let client = ArchiveTodayClient::with_base_url(60, &server.uri(), Duration::from_millis(1));
+        let snapshot = client.submit("https://example.com/page").await.unwrap();
+
+        assert_eq!(snapshot, None);
+    }
 }
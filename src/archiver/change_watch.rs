@@ -0,0 +1,272 @@
+//! Background worker that re-checks watched links for content changes.
+//!
+//! Admins can mark a link "watch for changes". Each due link is re-fetched,
+//! its visible text normalized and hashed, and compared with the last stored
+//! content version. When the hash differs a new version is recorded (linked
+//! to the previous one) and the link's archive is queued for re-archiving.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use scraper::{Html, Node, Selector};
+use sha2::{Digest, Sha256};
+use tracing::{debug, error, info, warn};
+
+use crate::config::Config;
+use crate::db::{
+    get_archive_by_link_id, get_due_watched_links, get_latest_content_version, get_link,
+    insert_content_version, mark_watched_link_checked,
+    reset_archive_for_rearchive_preserve_metadata, Database, WatchedLink,
+};
+use crate::handlers::archival_client;
+use crate::s3::S3Client;
+
+use super::versions::preserve_current_capture;
+
+/// How often to look for watched links that are due for a check.
+const POLL_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Maximum number of watched links checked per poll.
+const BATCH_SIZE: i64 = 10;
+
+/// Outcome of checking a single watched link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckOutcome {
+    /// First check; the current content was stored as the baseline.
+    Baseline,
+    /// The content matches the last stored version.
+    Unchanged,
+    /// The content changed and a new version was recorded.
+    Changed,
+}
+
+/// Extract the visible text of an HTML page, one line per text node.
+///
+/// Whitespace inside each text node is collapsed to single spaces. Text inside `script`, `style`, `noscript` and `template` elements is ignored.
+pub fn extract_page_text(html: &str) -> String {
+    let document = Html::parse_document(html);
+    let Ok(body_selector) = Selector::parse("body") else {
+        return String::new();
+    };
+    let Some(body) = document.select(&body_selector).next() else {
+        return String::new();
+    };
+
+    let mut lines = Vec::new();
+    for node in body.descendants() {
+        let Node::Text(text) = node.value() else {
+            continue;
+        };
+        let hidden = node.ancestors().any(|a| {
+            a.value()
+                .as_element()
+                .is_some_and(|e| matches!(e.name(), "script" | "style" | "noscript" | "template"))
+        });
+        if !hidden {
+            lines.push(text.split_whitespace().collect::<Vec<_>>().join(" "));
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Normalize page text so that whitespace-only edits don't count as changes.
+///
+/// Runs of whitespace within a line collapse to a single space and blank
+/// lines are dropped.
+pub fn normalize_text(text: &str) -> String {
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// SHA256 hex digest of normalized text.
+pub fn content_hash(normalized: &str) -> String {
+    hex::encode(Sha256::digest(normalized.as_bytes()))
+}
+
+/// Whether a newly computed hash represents a change from the previous one.
+///
+/// A missing previous hash is a baseline, not a change.
+pub fn has_changed(previous_hash: Option<&str>, new_hash: &str) -> bool {
+    previous_hash.is_some_and(|prev| prev != new_hash)
+}
+
+/// Resolve the check interval for a watched link.
+///
+/// Falls back to `default_hours` when no interval was requested and never
+/// returns less than `min_hours`, so a link can't be re-fetched too often.
+pub fn bounded_interval_hours(requested: Option<i64>, default_hours: u64, min_hours: u64) -> i64 {
+    let min = i64::try_from(min_hours).unwrap_or(i64::MAX);
+    let default = i64::try_from(default_hours).unwrap_or(i64::MAX);
+    requested.unwrap_or(default).max(min)
+}
+
+/// Run the change watch loop.
+///
+/// This function runs forever, checking watched links whose next check is
+/// due. It should be spawned as a background task.
+pub async fn run(db: Database, s3: S3Client, config: &Config) {
+    info!("Change watch worker started");
+
+    let client = match archival_client(config, Duration::from_secs(30)) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Failed to build HTTP client for change watch: {e}");
+            return;
+        }
+    };
+
+    loop {
+        match get_due_watched_links(db.pool(), BATCH_SIZE).await {
+            Ok(watched) => {
+                for watch in watched {
+                    match check_link(&client, &db, &s3, config, &watch).await {
+                        Ok(outcome) => {
+                            debug!(link_id = watch.link_id, ?outcome, "Checked watched link");
+                        }
+                        Err(e) => {
+                            warn!(
+                                link_id = watch.link_id,
+                                "Failed to check watched link: {e:#}"
+                            );
+                        }
+                    }
+                    // Reschedule even on failure so a broken link doesn't spin
+                    if let Err(e) = mark_watched_link_checked(db.pool(), watch.link_id).await {
+                        error!(
+                            link_id = watch.link_id,
+                            "Failed to reschedule watched link: {e:#}"
+                        );
+                    }
+                }
+            }
+            Err(e) => error!("Failed to fetch due watched links: {e:#}"),
+        }
+
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Fetch a watched link and record a new content version if it changed.
+async fn check_link(
+    client: &reqwest::Client,
+    db: &Database,
+    s3: &S3Client,
+    config: &Config,
+    watch: &WatchedLink,
+) -> Result<CheckOutcome> {
+    let link = get_link(db.pool(), watch.link_id)
+        .await?
+        .context("Watched link not found")?;
+    let url = link.final_url.as_deref().unwrap_or(&link.normalized_url);
+
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .context("Failed to fetch watched URL")?;
+    if !response.status().is_success() {
+        anyhow::bail!("HTTP request failed with status {}", response.status());
+    }
+    let body = response
+        .text()
+        .await
+        .context("Failed to read response body")?;
+
+    let text = normalize_text(&extract_page_text(&body));
+    let hash = content_hash(&text);
+
+    let previous = get_latest_content_version(db.pool(), link.id).await?;
+    let archive = get_archive_by_link_id(db.pool(), link.id).await?;
+    let archive_id = archive.as_ref().map(|a| a.id);
+
+    let Some(previous) = previous else {
+        insert_content_version(db.pool(), link.id, archive_id, None, &hash, &text).await?;
+        return Ok(CheckOutcome::Baseline);
+    };
+
+    if !has_changed(Some(&previous.content_hash), &hash) {
+        return Ok(CheckOutcome::Unchanged);
+    }
+
+    // Before recording the change, so a failed copy is retried on the next check
+    if let Some(ref archive) = archive {
+        preserve_current_capture(db, s3, &config.s3_prefix, archive).await?;
+    }
+
+    let version_id = insert_content_version(
+        db.pool(),
+        link.id,
+        archive_id,
+        Some(previous.id),
+        &hash,
+        &text,
+    )
+    .await?;
+    info!(
+        link_id = link.id,
+        version_id,
+        previous_version_id = previous.id,
+        "Watched link content changed"
+    );
+
+    if let Some(archive) = archive {
+        reset_archive_for_rearchive_preserve_metadata(db.pool(), archive.id).await?;
+    }
+
+    Ok(CheckOutcome::Changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_of(html: &str) -> String {
+        content_hash(&normalize_text(&extract_page_text(html)))
+    }
+
+    #[test]
+    fn test_extract_page_text_skips_scripts() {
+        let html = "<html><head><title>T</title></head><body><p>Hello</p>\
+            <script>var x = 1;</script><style>p{}</style><div>World</div></body></html>";
+        assert_eq!(normalize_text(&extract_page_text(html)), "Hello\nWorld");
+    }
+
+    #[test]
+    fn test_whitespace_only_edits_are_not_changes() {
+        let before = hash_of("<body><p>Some   article text</p>\n<p>Second</p></body>");
+        let after = hash_of("<body>\n  <p>Some article\n text</p><p>Second</p>\n\n</body>");
+        assert!(!has_changed(Some(&before), &after));
+    }
+
+    #[test]
+    fn test_script_edits_are_not_changes() {
+        let before = hash_of("<body><p>Text</p><script>t=1</script></body>");
+        let after = hash_of("<body><p>Text</p><script>t=2</script></body>");
+        assert!(!has_changed(Some(&before), &after));
+    }
+
+    #[test]
+    fn test_text_edits_are_changes() {
+        let before = hash_of("<body><p>The vote passed.</p></body>");
+        let after = hash_of("<body><p>The vote failed.</p></body>");
+        assert!(has_changed(Some(&before), &after));
+    }
+
+    #[test]
+    fn test_bounded_interval_hours() {
+        assert_eq!(bounded_interval_hours(None, 24, 6), 24);
+        assert_eq!(bounded_interval_hours(Some(48), 24, 6), 48);
+        assert_eq!(bounded_interval_hours(Some(1), 24, 6), 6);
+        assert_eq!(bounded_interval_hours(Some(-5), 24, 6), 6);
+        assert_eq!(bounded_interval_hours(None, 2, 6), 6);
+    }
+
+    #[test]
+    fn test_first_check_is_baseline() {
+        assert!(!has_changed(None, &hash_of("<body>x</body>")));
+    }
+}
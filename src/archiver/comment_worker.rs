@@ -1,109 +1,272 @@
 //! Background worker for extracting comments from archived videos and social media.
 //!
-//! This module runs a loop that polls for pending comment extraction jobs
-//! and processes them one at a time, avoiding rate limits by serializing comment downloads.
+//! This module runs a loop that polls for pending comment extraction jobs and
+//! runs up to `comments_concurrency` of them at once, oldest first. Jobs whose
+//! domain is busy in the archive worker's [`DomainRateLimiter`] are deferred to
+//! a later poll, and yt-dlp extractions share the global yt-dlp semaphore with
+//! downloads, so comment jobs never add to a platform's rate limits.
 
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
-use tracing::{error, info, trace, warn};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::task::JoinSet;
+use tracing::{debug, error, info, trace, warn};
 
-use crate::archiver::{tiktok_comments, ytdlp, CookieOptions};
+use crate::archiver::rate_limiter::DomainPermit;
+use crate::archiver::{reddit_comments, tiktok_comments, ytdlp, CookieOptions, DomainRateLimiter};
 use crate::config::Config;
 use crate::db::{
-    get_archive, get_pending_comment_extraction_jobs, set_job_completed, set_job_failed,
-    set_job_running, Database,
+    get_archive, get_link, get_pending_comment_extraction_jobs, set_job_completed, set_job_failed,
+    set_job_running, set_job_skipped, Archive, ArchiveJob, Database,
 };
 use crate::s3::S3Client;
 
+/// How long to wait between polls when no job has finished.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
 /// Run the comment extraction worker loop.
 ///
 /// This function runs forever, polling for pending comment extraction jobs
-/// and processing them one at a time. It should be spawned as a background
-/// task.
+/// and running up to `comments_concurrency` of them at a time. It should be
+/// spawned as a background task.
 ///
-/// Only one comment extraction runs at a time to avoid rate limiting from platforms like YouTube.
-pub async fn run(config: Config, db: Database, s3: S3Client) {
-    info!("Comment extraction worker started");
+/// `domain_limiter` is the archive worker's per-domain limiter; a job is only
+/// started when its domain has a free permit.
+pub async fn run(
+    config: Config,
+    db: Database,
+    s3: S3Client,
+    domain_limiter: Arc<DomainRateLimiter>,
+) {
+    info!(
+        concurrency = config.comments_concurrency,
+        "Comment extraction worker started"
+    );
+
+    let slots = Arc::new(Semaphore::new(config.comments_concurrency));
+    let mut running = JoinSet::new();
 
     loop {
-        // Check for pending jobs
-        match get_pending_comment_extraction_jobs(db.pool(), 1).await {
-            Ok(jobs) if !jobs.is_empty() => {
-                let job = &jobs[0];
-                info!(
-                    job_id = job.id,
-                    archive_id = job.archive_id,
-                    "Processing comment extraction job"
-                );
-
-                // Get the archive details
-                let archive = match get_archive(db.pool(), job.archive_id).await {
-                    Ok(Some(archive)) => archive,
-                    Ok(None) => {
-                        error!(job_id = job.id, "Archive not found, skipping job");
-                        if let Err(e) = set_job_failed(db.pool(), job.id, "Archive not found").await
-                        {
-                            error!(job_id = job.id, "Failed to mark job failed: {e}");
-                        }
-                        continue;
-                    }
-                    Err(e) => {
-                        error!(job_id = job.id, error = %e, "Failed to fetch archive");
-                        tokio::time::sleep(Duration::from_secs(30)).await;
-                        continue;
-                    }
-                };
-
-                // Mark job as running
-                if let Err(e) = set_job_running(db.pool(), job.id).await {
-                    error!(job_id = job.id, "Failed to mark job running: {e}");
-                    tokio::time::sleep(Duration::from_secs(30)).await;
-                    continue;
+        match claim_jobs(&db, &slots, &domain_limiter).await {
+            Ok(claimed) if claimed.is_empty() => {
+                trace!("No comment extraction jobs to start");
+            }
+            Ok(claimed) => {
+                for claimed in claimed {
+                    let config = config.clone();
+                    let db = db.clone();
+                    let s3 = s3.clone();
+                    running.spawn(async move { run_job(&config, &db, &s3, claimed).await });
                 }
+            }
+            Err(e) => {
+                error!("Failed to fetch pending comment extraction jobs: {e}");
+            }
+        }
 
-                // Extract comments
-                match extract_comments_for_archive(&config, &db, &s3, &archive).await {
-                    Ok(comment_count) => {
-                        info!(
-                            job_id = job.id,
-                            archive_id = archive.id,
-                            comments = comment_count,
-                            "Comment extraction completed successfully"
-                        );
-                        let metadata = serde_json::json!({
-                            "comment_count": comment_count,
-                            "platform": archive.content_type.as_deref().unwrap_or("unknown"),
-                        });
-                        if let Err(e) =
-                            set_job_completed(db.pool(), job.id, Some(&metadata.to_string())).await
-                        {
-                            error!(job_id = job.id, "Failed to mark job complete: {e}");
-                        }
-                    }
-                    Err(e) => {
-                        error!(job_id = job.id, error = %e, "Comment extraction failed");
-                        let error_msg = format!("{e:#}");
-                        if let Err(e) = set_job_failed(db.pool(), job.id, &error_msg).await {
-                            error!(job_id = job.id, "Failed to mark job failed: {e}");
-                        }
-                    }
+        // Wait before checking again; a finished job frees a slot early
+        tokio::select! {
+            () = tokio::time::sleep(POLL_INTERVAL) => {}
+            Some(result) = running.join_next(), if !running.is_empty() => {
+                if let Err(e) = result {
+                    error!("Comment extraction task panicked: {e}");
                 }
             }
-            Ok(_) => {
-                // No pending jobs, just wait
-                trace!("No pending comment extraction jobs");
+        }
+    }
+}
+
+/// A job that has been marked running and holds its concurrency permits.
+struct ClaimedJob {
+    job: ArchiveJob,
+    archive: Archive,
+    _slot: OwnedSemaphorePermit,
+    _domain_permit: DomainPermit,
+}
+
+/// Claim as many pending jobs as there are free slots, oldest first.
+///
+/// Jobs whose domain has no free permit in `domain_limiter` are left pending
+/// and picked up on a later poll. Claimed jobs are marked running.
+async fn claim_jobs(
+    db: &Database,
+    slots: &Arc<Semaphore>,
+    domain_limiter: &DomainRateLimiter,
+) -> Result<Vec<ClaimedJob>> {
+    let free = slots.available_permits();
+    if free == 0 {
+        return Ok(Vec::new());
+    }
+
+    // Look past the first few jobs so deferred domains don't block the queue
+    let jobs = get_pending_comment_extraction_jobs(db.pool(), (free * 4) as i64).await?;
+    let mut claimed = Vec::new();
+
+    for job in jobs {
+        let Ok(slot) = Arc::clone(slots).try_acquire_owned() else {
+            break;
+        };
+
+        let archive = match get_archive(db.pool(), job.archive_id).await {
+            Ok(Some(archive)) => archive,
+            Ok(None) => {
+                error!(job_id = job.id, "Archive not found, skipping job");
+                if let Err(e) = set_job_failed(db.pool(), job.id, "Archive not found").await {
+                    error!(job_id = job.id, "Failed to mark job failed: {e}");
+                }
+                continue;
             }
             Err(e) => {
-                error!("Failed to fetch pending comment extraction jobs: {e}");
+                error!(job_id = job.id, error = %e, "Failed to fetch archive");
+                break;
+            }
+        };
+
+        let link = match get_link(db.pool(), archive.link_id).await {
+            Ok(Some(link)) => link,
+            Ok(None) => {
+                error!(job_id = job.id, "Link not found, skipping job");
+                if let Err(e) = set_job_failed(db.pool(), job.id, "Link not found").await {
+                    error!(job_id = job.id, "Failed to mark job failed: {e}");
+                }
+                continue;
+            }
+            Err(e) => {
+                error!(job_id = job.id, error = %e, "Failed to fetch link");
+                break;
             }
+        };
+
+        let Some(domain_permit) = domain_limiter.try_acquire(&link.domain).await else {
+            debug!(
+                job_id = job.id,
+                domain = %link.domain,
+                "Domain busy, deferring comment extraction"
+            );
+            continue;
+        };
+
+        if let Err(e) = set_job_running(db.pool(), job.id).await {
+            error!(job_id = job.id, "Failed to mark job running: {e}");
+            break;
         }
 
-        // Wait before checking again
-        tokio::time::sleep(Duration::from_secs(30)).await;
+        claimed.push(ClaimedJob {
+            job,
+            archive,
+            _slot: slot,
+            _domain_permit: domain_permit,
+        });
+    }
+
+    Ok(claimed)
+}
+
+/// Extract comments for a claimed job and record the outcome.
+async fn run_job(config: &Config, db: &Database, s3: &S3Client, claimed: ClaimedJob) {
+    let job = &claimed.job;
+    let archive = &claimed.archive;
+    info!(
+        job_id = job.id,
+        archive_id = job.archive_id,
+        "Processing comment extraction job"
+    );
+
+    let started = Instant::now();
+    let outcome = extract_comments_for_archive(config, db, s3, archive).await;
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    match outcome {
+        Ok(CommentExtraction::Extracted {
+            comment_count,
+            truncated,
+        }) => {
+            info!(
+                job_id = job.id,
+                archive_id = archive.id,
+                comments = comment_count,
+                truncated,
+                duration_ms,
+                "Comment extraction completed successfully"
+            );
+            let metadata = serde_json::json!({
+                "comment_count": comment_count,
+                "truncated": truncated,
+                "platform": archive.content_type.as_deref().unwrap_or("unknown"),
+                "duration_ms": duration_ms,
+            });
+            if let Err(e) = set_job_completed(db.pool(), job.id, Some(&metadata.to_string())).await
+            {
+                error!(job_id = job.id, "Failed to mark job complete: {e}");
+            }
+        }
+        Ok(CommentExtraction::Disabled) => {
+            info!(
+                job_id = job.id,
+                archive_id = archive.id,
+                duration_ms,
+                "Comments are disabled, skipping"
+            );
+            if let Err(e) = set_job_skipped(db.pool(), job.id, Some("Comments are disabled")).await
+            {
+                error!(job_id = job.id, "Failed to mark job skipped: {e}");
+            }
+        }
+        Ok(CommentExtraction::RateLimited) => {
+            info!(
+                job_id = job.id,
+                archive_id = archive.id,
+                duration_ms,
+                "Rate limited by platform, keeping the post without comments"
+            );
+            if let Err(e) = set_job_skipped(
+                db.pool(),
+                job.id,
+                Some("Rate limited; archived without comments"),
+            )
+            .await
+            {
+                error!(job_id = job.id, "Failed to mark job skipped: {e}");
+            }
+        }
+        Err(e) => {
+            error!(job_id = job.id, error = %e, duration_ms, "Comment extraction failed");
+            let error_msg = format!("{e:#}");
+            if let Err(e) = set_job_failed(db.pool(), job.id, &error_msg).await {
+                error!(job_id = job.id, "Failed to mark job failed: {e}");
+            }
+        }
     }
 }
 
+/// Outcome of a comment extraction attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentExtraction {
+    /// Comments were extracted. `truncated` is set when the configured
+    /// `comments_max_count` cap stopped extraction before all comments were
+    /// fetched.
+    Extracted {
+        comment_count: usize,
+        truncated: bool,
+    },
+    /// The platform reports that comments are disabled for this content.
+    Disabled,
+    /// The platform rate-limited the comment request; the archive keeps just
+    /// the post.
+    RateLimited,
+}
+
+/// Check whether a platform or yt-dlp message says comments are turned off.
+pub fn is_comments_disabled_message(msg: &str) -> bool {
+    let msg = msg.to_lowercase();
+    msg.contains("comments are turned off")
+        || msg.contains("comments are disabled")
+        || msg.contains("comments disabled")
+        || msg.contains("comment is disabled")
+}
+
 /// Check if URL is a TikTok URL.
 fn is_tiktok_url(url: &str) -> bool {
     url.contains("://tiktok.com/")
@@ -113,6 +276,11 @@ fn is_tiktok_url(url: &str) -> bool {
         || url.contains("://vt.tiktok.com/")
 }
 
+/// Check if URL is a Reddit URL.
+fn is_reddit_url(url: &str) -> bool {
+    url.contains("reddit.com/")
+}
+
 /// Check if URL is a Twitter/X URL.
 /// Twitter comment extraction via yt-dlp is disabled because it causes account locks.
 fn is_twitter_url(url: &str) -> bool {
@@ -129,10 +297,10 @@ async fn extract_comments_for_archive(
     config: &Config,
     db: &Database,
     s3: &S3Client,
-    archive: &crate::db::Archive,
-) -> Result<usize> {
+    archive: &Archive,
+) -> Result<CommentExtraction> {
     // Get the link for this archive
-    let link = crate::db::get_link(db.pool(), archive.link_id)
+    let link = get_link(db.pool(), archive.link_id)
         .await
         .context("Failed to fetch link")?
         .ok_or_else(|| anyhow::anyhow!("Link not found for archive"))?;
@@ -149,6 +317,7 @@ async fn extract_comments_for_archive(
 
     let comments_json_path = work_dir.join("comments.json");
     let comment_count: usize;
+    let truncated: bool;
 
     // Extract comments based on platform
     if is_twitter_url(url) {
@@ -164,7 +333,43 @@ async fn extract_comments_for_archive(
             warn!(work_dir = %work_dir.display(), error = %e, "Failed to clean up work directory");
         }
 
-        return Ok(0);
+        return Ok(CommentExtraction::Extracted {
+            comment_count: 0,
+            truncated: false,
+        });
+    } else if is_reddit_url(url) {
+        // Reddit: Use the post's .json endpoint
+        info!(
+            archive_id = archive.id,
+            "Using Reddit JSON endpoint for comment extraction"
+        );
+
+        let Some(comments_json) =
+            reddit_comments::fetch_reddit_comments(reddit_comments::REDDIT_JSON_BASE, url, config)
+                .await
+                .context("Failed to extract comments from Reddit")?
+        else {
+            if let Err(e) = tokio::fs::remove_dir_all(&work_dir).await {
+                warn!(work_dir = %work_dir.display(), error = %e, "Failed to clean up work directory");
+            }
+            return Ok(CommentExtraction::RateLimited);
+        };
+
+        comment_count = comments_json
+            .get("stats")
+            .and_then(|s| s.get("extracted_comments"))
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0) as usize;
+        truncated = comments_json
+            .get("truncated")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+
+        let json_str = serde_json::to_string_pretty(&comments_json)
+            .context("Failed to serialize comments JSON")?;
+        tokio::fs::write(&comments_json_path, json_str)
+            .await
+            .context("Failed to write comments.json")?;
     } else if is_tiktok_url(url) {
         // TikTok: Use direct API extraction
         info!(
@@ -187,6 +392,21 @@ async fn extract_comments_for_archive(
             .and_then(|s| s.get("extracted_comments"))
             .and_then(|c| c.as_u64())
             .unwrap_or(0) as usize;
+        truncated = comments_json
+            .get("truncated")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+
+        let disabled = comments_json
+            .get("comments_disabled")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        if disabled && comment_count == 0 {
+            if let Err(e) = tokio::fs::remove_dir_all(&work_dir).await {
+                warn!(work_dir = %work_dir.display(), error = %e, "Failed to clean up work directory");
+            }
+            return Ok(CommentExtraction::Disabled);
+        }
 
         // Write comments.json to work directory
         let json_str = serde_json::to_string_pretty(&comments_json)
@@ -207,7 +427,7 @@ async fn extract_comments_for_archive(
             screenshot_service: None,
         };
 
-        comment_count = ytdlp::extract_comments_only(
+        match ytdlp::extract_comments_only(
             url,
             &work_dir,
             &cookies,
@@ -216,7 +436,22 @@ async fn extract_comments_for_archive(
             Some(db.pool()),
         )
         .await
-        .context("Failed to extract comments with yt-dlp")?;
+        .context("Failed to extract comments with yt-dlp")?
+        {
+            CommentExtraction::Extracted {
+                comment_count: count,
+                truncated: was_truncated,
+            } => {
+                comment_count = count;
+                truncated = was_truncated;
+            }
+            outcome @ (CommentExtraction::Disabled | CommentExtraction::RateLimited) => {
+                if let Err(e) = tokio::fs::remove_dir_all(&work_dir).await {
+                    warn!(work_dir = %work_dir.display(), error = %e, "Failed to clean up work directory");
+                }
+                return Ok(outcome);
+            }
+        }
     }
 
     // Upload comments.json to S3 if it exists
@@ -236,6 +471,7 @@ async fn extract_comments_for_archive(
         let metadata = Some(
             serde_json::json!({
                 "comment_count": comment_count as i64,
+                "truncated": truncated,
                 "platform": crate::archiver::worker::extract_platform_name(&link.domain),
             })
             .to_string(),
@@ -268,5 +504,117 @@ async fn extract_comments_for_archive(
         warn!(work_dir = %work_dir.display(), error = %e, "Failed to clean up work directory");
     }
 
-    Ok(comment_count)
+    Ok(CommentExtraction::Extracted {
+        comment_count,
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{
+        create_archive_job, create_pending_archive, get_jobs_for_archive, get_or_create_link,
+        ArchiveJobType, NewLink,
+    };
+
+    /// Create an archive for `url` with a pending comment extraction job.
+    async fn pending_comment_job(db: &Database, url: &str, domain: &str) -> i64 {
+        let link_id = get_or_create_link(
+            db.pool(),
+            &NewLink {
+                original_url: url.to_string(),
+                normalized_url: url.to_string(),
+                canonical_url: None,
+                domain: domain.to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        let archive_id = create_pending_archive(db.pool(), link_id, None)
+            .await
+            .unwrap();
+        create_archive_job(db.pool(), archive_id, ArchiveJobType::CommentExtraction)
+            .await
+            .unwrap();
+        archive_id
+    }
+
+    async fn job_status(db: &Database, archive_id: i64) -> String {
+        get_jobs_for_archive(db.pool(), archive_id)
+            .await
+            .unwrap()
+            .remove(0)
+            .status
+    }
+
+    #[tokio::test]
+    async fn test_claim_jobs_respects_concurrency_cap() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(&temp_dir.path().join("test.sqlite"))
+            .await
+            .unwrap();
+        let first = pending_comment_job(&db, "https://youtube.com/watch?v=a", "youtube.com").await;
+        let second =
+            pending_comment_job(&db, "https://reddit.com/r/x/comments/b", "reddit.com").await;
+        let third = pending_comment_job(&db, "https://tiktok.com/@c/video/3", "tiktok.com").await;
+
+        let slots = Arc::new(Semaphore::new(2));
+        let limiter = DomainRateLimiter::new(1);
+        let claimed = claim_jobs(&db, &slots, &limiter).await.unwrap();
+
+        // The two oldest jobs are started and the third waits for a free slot
+        let ids: Vec<i64> = claimed.iter().map(|c| c.archive.id).collect();
+        assert_eq!(ids, vec![first, second]);
+        assert_eq!(job_status(&db, first).await, "running");
+        assert_eq!(job_status(&db, third).await, "pending");
+        assert!(claim_jobs(&db, &slots, &limiter).await.unwrap().is_empty());
+
+        drop(claimed);
+        let claimed = claim_jobs(&db, &slots, &limiter).await.unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].archive.id, third);
+    }
+
+    #[tokio::test]
+    async fn test_claim_jobs_defers_rate_limited_domain() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(&temp_dir.path().join("test.sqlite"))
+            .await
+            .unwrap();
+        let busy = pending_comment_job(&db, "https://youtube.com/watch?v=a", "youtube.com").await;
+        let free =
+            pending_comment_job(&db, "https://reddit.com/r/x/comments/b", "reddit.com").await;
+
+        // The archive worker holds the only youtube.com permit
+        let slots = Arc::new(Semaphore::new(2));
+        let limiter = DomainRateLimiter::new(1);
+        let held = limiter.acquire("youtube.com").await;
+
+        let claimed = claim_jobs(&db, &slots, &limiter).await.unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].archive.id, free);
+        assert_eq!(job_status(&db, busy).await, "pending");
+        // The deferred job gave its slot back
+        assert_eq!(slots.available_permits(), 1);
+
+        drop(held);
+        let claimed = claim_jobs(&db, &slots, &limiter).await.unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].archive.id, busy);
+        assert_eq!(job_status(&db, busy).await, "running");
+    }
+
+    #[test]
+    fn test_is_comments_disabled_message() {
+        assert!(is_comments_disabled_message(
+            "WARNING: [youtube] abc123: Comments are turned off"
+        ));
+        assert!(is_comments_disabled_message(
+            "TikTok API error (status 10204): Comments disabled"
+        ));
+        assert!(!is_comments_disabled_message(
+            "ERROR: [youtube] abc123: Video unavailable"
+        ));
+    }
 }
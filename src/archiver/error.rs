@@ -0,0 +1,296 @@
+//! Structured archive failure categories.
+//!
+//! Handlers return an [`ArchiveError`] inside their `anyhow::Error` when they
+//! know why a capture failed. The worker stores the error's kind in
+//! `archives.error_kind` next to the human-readable `error_message`; failures
+//! that weren't typed get a kind inferred from their text, so every failed
+//! archive can be counted and filtered by category.
+
+use serde::Serialize;
+use thiserror::Error;
+
+/// A categorized archive failure. The message is what `error_message` shows.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ArchiveError {
+    /// Content needs a login (or better cookies) to view.
+    #[error("{0}")]
+    AuthRequired(String),
+    /// Content is gone: deleted, removed, 404/410 or a soft 404.
+    #[error("{0}")]
+    NotFound(String),
+    /// The site throttled us.
+    #[error("{0}")]
+    RateLimited(String),
+    /// The download exceeds `MAX_ARTIFACT_BYTES`.
+    #[error("{0}")]
+    TooLarge(String),
+    /// The request timed out.
+    #[error("{0}")]
+    Timeout(String),
+    /// The page can't or may not be archived (e.g. it asked not to be).
+    #[error("{0}")]
+    Unsupported(String),
+    /// Anything else.
+    #[error("{0}")]
+    Other(String),
+}
+
+impl ArchiveError {
+    /// Error for an unsuccessful HTTP response, categorized by status code.
+    #[must_use]
+    pub fn from_status(status: reqwest::StatusCode, message: String) -> Self {
+        match status.as_u16() {
+            401 | 403 => Self::AuthRequired(message),
+            404 | 410 => Self::NotFound(message),
+            429 => Self::RateLimited(message),
+            408 | 504 => Self::Timeout(message),
+            413 => Self::TooLarge(message),
+            _ => Self::Other(message),
+        }
+    }
+
+    /// The failure category.
+    #[must_use]
+    pub const fn kind(&self) -> ArchiveErrorKind {
+        match self {
+            Self::AuthRequired(_) => ArchiveErrorKind::AuthRequired,
+            Self::NotFound(_) => ArchiveErrorKind::NotFound,
+            Self::RateLimited(_) => ArchiveErrorKind::RateLimited,
+            Self::TooLarge(_) => ArchiveErrorKind::TooLarge,
+            Self::Timeout(_) => ArchiveErrorKind::Timeout,
+            Self::Unsupported(_) => ArchiveErrorKind::Unsupported,
+            Self::Other(_) => ArchiveErrorKind::Other,
+        }
+    }
+}
+
+/// Failure category stored in `archives.error_kind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveErrorKind {
+    AuthRequired,
+    NotFound,
+    RateLimited,
+    TooLarge,
+    Timeout,
+    Unsupported,
+    Other,
+}
+
+impl ArchiveErrorKind {
+    /// Every kind, in display order.
+    pub const ALL: [Self; 7] = [
+        Self::AuthRequired,
+        Self::NotFound,
+        Self::RateLimited,
+        Self::TooLarge,
+        Self::Timeout,
+        Self::Unsupported,
+        Self::Other,
+    ];
+
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::AuthRequired => "auth_required",
+            Self::NotFound => "not_found",
+            Self::RateLimited => "rate_limited",
+            Self::TooLarge => "too_large",
+            Self::Timeout => "timeout",
+            Self::Unsupported => "unsupported",
+            Self::Other => "other",
+        }
+    }
+
+    #[must_use]
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == s)
+    }
+
+    /// Human-readable label for the debug page.
+    #[must_use]
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::AuthRequired => "Auth required",
+            Self::NotFound => "Not found",
+            Self::RateLimited => "Rate limited",
+            Self::TooLarge => "Too large",
+            Self::Timeout => "Timeout",
+            Self::Unsupported => "Unsupported",
+            Self::Other => "Other",
+        }
+    }
+
+    /// How a typed failure of this kind is retried.
+    ///
+    /// `Other` has no fixed policy; its error text is classified instead.
+    #[must_use]
+    pub const fn failure_class(&self) -> Option<FailureClass> {
+        match self {
+            Self::AuthRequired => Some(FailureClass::AuthRequired),
+            Self::NotFound => Some(FailureClass::Permanent("Not found")),
+            Self::TooLarge => Some(FailureClass::Permanent("Exceeds MAX_ARTIFACT_BYTES")),
+            Self::Unsupported => Some(FailureClass::Permanent("Unsupported")),
+            Self::RateLimited => Some(FailureClass::Transient("Rate limited")),
+            Self::Timeout => Some(FailureClass::Transient("Timeout")),
+            Self::Other => None,
+        }
+    }
+}
+
+/// Classification of an archive failure, deciding whether it is retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureClass {
+    /// Content needs login; retried once cookies are configured.
+    AuthRequired,
+    /// Content is gone or unreachable for good; skipped without retries.
+    Permanent(&'static str),
+    /// Temporary problem (timeout, 5xx, rate limit); retried with backoff.
+    Transient(&'static str),
+}
+
+impl FailureClass {
+    /// Short human-readable reason for the classification.
+    #[must_use]
+    pub const fn reason(&self) -> &'static str {
+        match self {
+            Self::AuthRequired => "Authentication required",
+            Self::Permanent(reason) | Self::Transient(reason) => reason,
+        }
+    }
+
+    /// Whether an archive with this failure should be retried automatically.
+    #[must_use]
+    pub const fn is_retryable(&self) -> bool {
+        !matches!(self, Self::Permanent(_))
+    }
+}
+
+/// Why an archive was marked `skipped`, stored in `archives.skip_reason`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// The link's domain is on the excluded domains list.
+    ExcludedDomain,
+    /// The page asked not to be archived (X-No-Archive, noarchive meta).
+    NoArchive,
+    /// The download exceeds `MAX_ARTIFACT_BYTES`.
+    SizeCap,
+    /// No handler can archive the content.
+    Unsupported,
+    /// The content is gone.
+    NotFound,
+    /// Any other failure that isn't worth retrying.
+    PermanentFailure,
+    /// Retries ran out.
+    MaxRetries,
+    /// `ARCHIVE_DRY_RUN` recorded the planned work instead.
+    DryRun,
+}
+
+impl SkipReason {
+    /// Every reason, in display order.
+    pub const ALL: [Self; 8] = [
+        Self::ExcludedDomain,
+        Self::NoArchive,
+        Self::SizeCap,
+        Self::Unsupported,
+        Self::NotFound,
+        Self::PermanentFailure,
+        Self::MaxRetries,
+        Self::DryRun,
+    ];
+
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::ExcludedDomain => "excluded_domain",
+            Self::NoArchive => "noarchive",
+            Self::SizeCap => "size_cap",
+            Self::Unsupported => "unsupported",
+            Self::NotFound => "not_found",
+            Self::PermanentFailure => "permanent_failure",
+            Self::MaxRetries => "max_retries",
+            Self::DryRun => "dry_run",
+        }
+    }
+
+    #[must_use]
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|reason| reason.as_str() == s)
+    }
+
+    /// Human-readable label for the debug page.
+    #[must_use]
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::ExcludedDomain => "Excluded domain",
+            Self::NoArchive => "No-archive requested",
+            Self::SizeCap => "Too large",
+            Self::Unsupported => "Unsupported",
+            Self::NotFound => "Not found",
+            Self::PermanentFailure => "Permanent failure",
+            Self::MaxRetries => "Out of retries",
+            Self::DryRun => "Dry run",
+        }
+    }
+
+    /// Reason for skipping after a permanent failure of `kind`, where
+    /// `reason` is the [`FailureClass::Permanent`] reason.
+    #[must_use]
+    pub fn for_permanent_failure(kind: ArchiveErrorKind, reason: &str) -> Self {
+        if reason == crate::handlers::NOARCHIVE_REQUESTED {
+            return Self::NoArchive;
+        }
+        match kind {
+            ArchiveErrorKind::TooLarge => Self::SizeCap,
+            ArchiveErrorKind::Unsupported => Self::Unsupported,
+            ArchiveErrorKind::NotFound => Self::NotFound,
+            _ => Self::PermanentFailure,
+        }
+    }
+}
+
+/// The first [`ArchiveError`] in an error's chain, if a handler typed it.
+#[must_use]
+pub fn find_archive_error(err: &anyhow::Error) -> Option<&ArchiveError> {
+    err.chain()
+        .find_map(|cause| cause.downcast_ref::<ArchiveError>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use reqwest::StatusCode;
+
+    #[test]
+    fn test_kind_round_trip() {
+        for kind in ArchiveErrorKind::ALL {
+            assert_eq!(ArchiveErrorKind::from_str(kind.as_str()), Some(kind));
+        }
+        assert_eq!(ArchiveErrorKind::from_str("bogus"), None);
+    }
+
+    #[test]
+    fn test_skip_reason_round_trip() {
+        for reason in SkipReason::ALL {
+            assert_eq!(SkipReason::from_str(reason.as_str()), Some(reason));
+        }
+        assert_eq!(SkipReason::from_str("bogus"), None);
+    }
+
+    #[test]
+    fn test_from_status() {
+        let kind = |code: u16| {
+            ArchiveError::from_status(StatusCode::from_u16(code).unwrap(), String::new()).kind()
+        };
+        assert_eq!(kind(401), ArchiveErrorKind::AuthRequired);
+        assert_eq!(kind(403), ArchiveErrorKind::AuthRequired);
+        assert_eq!(kind(404), ArchiveErrorKind::NotFound);
+        assert_eq!(kind(410), ArchiveErrorKind::NotFound);
+        assert_eq!(kind(429), ArchiveErrorKind::RateLimited);
+        assert_eq!(kind(504), ArchiveErrorKind::Timeout);
+        assert_eq!(kind(500), ArchiveErrorKind::Other);
+    }
+}
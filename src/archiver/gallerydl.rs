@@ -28,6 +28,22 @@ pub async fn download(
     work_dir: &Path,
     cookies: &CookieOptions<'_>,
     config: &crate::config::Config,
+) -> Result<ArchiveResult> {
+    download_with_options(url, work_dir, cookies, config, &[]).await
+}
+
+/// Download content using gallery-dl with extra command-line arguments
+/// (e.g. extractor `-o key=value` options) appended after the defaults.
+///
+/// # Errors
+///
+/// Returns an error if gallery-dl fails or times out.
+pub async fn download_with_options(
+    url: &str,
+    work_dir: &Path,
+    cookies: &CookieOptions<'_>,
+    config: &crate::config::Config,
+    extra_args: &[&str],
 ) -> Result<ArchiveResult> {
     // Acquire semaphore permit to ensure only 1 gallery-dl operation at a time
     let _permit = GALLERYDL_SEMAPHORE
@@ -47,6 +63,7 @@ pub async fn download(
         "{category}_{filename}.{extension}".to_string(),
         "--no-mtime".to_string(),
     ];
+    args.extend(extra_args.iter().map(|arg| (*arg).to_string()));
 
     // gallery-dl only supports cookies files, not browser profiles
     if let Some(cookies_path) = cookies.cookies_file {
@@ -0,0 +1,206 @@
+//! Lightweight language detection for subtitle text.
+//!
+//! yt-dlp sometimes names subtitle files without a usable language code
+//! (e.g. `video.NA.vtt` or `video.und.vtt`). In that case the language is
+//! guessed from the subtitle text by counting common function words. This is
+//! deliberately simple: it only needs to tell apart the languages we commonly
+//! see in archived videos, and it declines to guess when the signal is weak.
+
+use super::transcript::parse_vtt_content;
+
+/// Minimum number of words before content detection is attempted.
+const MIN_WORDS: usize = 8;
+
+/// Minimum share of words that must be stopwords of the winning language.
+const MIN_SCORE_RATIO: f64 = 0.08;
+
+/// Stopword lists keyed by ISO 639-1 code.
+///
+/// Words that are very common in more than one of these languages (such as
+/// "a", "de", "la", "en") are left out to keep the scores distinct.
+const STOPWORDS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "the", "and", "is", "are", "was", "were", "to", "of", "that", "this", "it", "you",
+            "with", "for", "have", "not", "what", "they", "we", "be", "on", "just", "so", "but",
+            "like", "there", "all", "your", "can", "do", "know", "going",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "el", "los", "las", "que", "y", "es", "por", "con", "para", "una", "pero", "como",
+            "muy", "esto", "eso", "está", "qué", "más", "yo", "tu", "lo", "del", "sí", "también",
+            "hay", "porque", "cuando", "estoy",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "le", "les", "et", "est", "une", "des", "que", "qui", "pas", "je", "vous", "nous",
+            "il", "elle", "ce", "c'est", "dans", "sur", "avec", "mais", "pour", "très", "du", "au",
+            "j'ai", "oui",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "ich", "du", "wir", "sie", "ein", "eine",
+            "mit", "auf", "für", "auch", "aber", "wie", "was", "den", "dem", "zu", "sind", "noch",
+            "jetzt", "hier",
+        ],
+    ),
+    (
+        "pt",
+        &[
+            "o", "os", "as", "não", "uma", "um", "para", "com", "mas", "você", "isso", "muito",
+            "também", "ele", "ela", "eu", "está", "são", "aqui", "então", "porque", "mais", "do",
+            "da",
+        ],
+    ),
+    (
+        "it",
+        &[
+            "il", "gli", "che", "non", "sono", "una", "per", "con", "ma", "questo", "anche",
+            "molto", "io", "lui", "lei", "è", "della", "perché", "ci", "allora", "cosa", "sì",
+        ],
+    ),
+];
+
+/// Check whether a subtitle language code is missing or a placeholder.
+///
+/// Codes derived from filenames like `video.NA.vtt` or `video.und.vtt` carry
+/// no information and should be replaced by header or content detection.
+#[must_use]
+pub fn is_missing_language_code(code: &str) -> bool {
+    let code = code.trim();
+    code.is_empty()
+        || code.len() > 10
+        || code.eq_ignore_ascii_case("unknown")
+        || code.eq_ignore_ascii_case("na")
+        || code.eq_ignore_ascii_case("und")
+        || code.eq_ignore_ascii_case("none")
+}
+
+/// Detect the language of plain text.
+///
+/// Returns an ISO 639-1 code, or `None` if the text is too short or no
+/// language is a clear winner.
+#[must_use]
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let words: Vec<String> = text
+        .split(|c: char| !(c.is_alphabetic() || c == '\''))
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    if words.len() < MIN_WORDS {
+        return None;
+    }
+
+    let mut scores: Vec<(&'static str, usize)> = STOPWORDS
+        .iter()
+        .map(|(code, list)| {
+            let hits = words.iter().filter(|w| list.contains(&w.as_str())).count();
+            (*code, hits)
+        })
+        .collect();
+    scores.sort_by_key(|s| std::cmp::Reverse(s.1));
+
+    let (best_code, best) = scores[0];
+    let runner_up = scores.get(1).map_or(0, |s| s.1);
+
+    let ratio = best as f64 / words.len() as f64;
+    if ratio < MIN_SCORE_RATIO || best <= runner_up {
+        return None;
+    }
+
+    Some(best_code)
+}
+
+/// Detect the language of a subtitle file's contents.
+///
+/// Returns `None` when `current` is already a usable language code, so that
+/// codes from the filename or VTT header always take precedence.
+#[must_use]
+pub fn language_from_content(current: &str, content: &str, format: &str) -> Option<&'static str> {
+    if !is_missing_language_code(current) {
+        return None;
+    }
+
+    let text = if format == "vtt" {
+        parse_vtt_content(content)
+            .iter()
+            .map(|cue| cue.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    } else {
+        // SRT cue numbers and timestamps contain no letters, so they are
+        // dropped by the word splitter in `detect_language`.
+        content.to_string()
+    };
+
+    detect_language(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENGLISH_VTT: &str = "WEBVTT
+
+00:00:00.000 --> 00:00:03.000
+Hey everyone, welcome back to the channel.
+
+00:00:03.000 --> 00:00:07.000
+Today we are going to look at what happened with the new update.
+
+00:00:07.000 --> 00:00:11.000
+I know a lot of you have been asking about this, so let's get into it.
+";
+
+    const SPANISH_VTT: &str = "WEBVTT
+
+00:00:00.000 --> 00:00:03.000
+Hola a todos, bienvenidos otra vez al canal.
+
+00:00:03.000 --> 00:00:07.000
+Hoy vamos a ver qué pasó con la nueva actualización, porque hay muchas preguntas.
+
+00:00:07.000 --> 00:00:11.000
+Yo sé que muchos de ustedes están esperando esto, pero también es muy importante.
+";
+
+    #[test]
+    fn test_is_missing_language_code() {
+        assert!(is_missing_language_code("unknown"));
+        assert!(is_missing_language_code("NA"));
+        assert!(is_missing_language_code("und"));
+        assert!(is_missing_language_code(""));
+        assert!(is_missing_language_code("some-very-long-title-fragment"));
+        assert!(!is_missing_language_code("en"));
+        assert!(!is_missing_language_code("pt-BR"));
+    }
+
+    #[test]
+    fn test_detects_english_vtt() {
+        assert_eq!(language_from_content("und", ENGLISH_VTT, "vtt"), Some("en"));
+    }
+
+    #[test]
+    fn test_detects_spanish_vtt() {
+        assert_eq!(language_from_content("NA", SPANISH_VTT, "vtt"), Some("es"));
+    }
+
+    #[test]
+    fn test_filename_code_wins_over_content() {
+        assert_eq!(language_from_content("en", SPANISH_VTT, "vtt"), None);
+    }
+
+    #[test]
+    fn test_short_text_not_classified() {
+        assert_eq!(detect_language("hello there"), None);
+        assert_eq!(detect_language(""), None);
+    }
+}
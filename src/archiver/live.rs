@@ -0,0 +1,219 @@
+//! Live streams, premieres and Twitter Spaces that can't be archived yet.
+//!
+//! yt-dlp reports a `live_status` for YouTube streams and premieres and for
+//! Twitter Spaces and broadcasts. While the content is upcoming, live, or
+//! still being turned into a VOD, the download is refused with a
+//! [`LiveNotReady`] error. The worker then defers the archive (it stays
+//! `pending` with a future `next_retry_at`) instead of failing it, for up to
+//! `LIVE_DEFER_MAX_HOURS` after the archive was queued.
+
+use thiserror::Error;
+
+/// Where live content is in its lifecycle, from yt-dlp's `live_status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiveStatus {
+    /// Ordinary uploaded content.
+    NotLive,
+    /// A scheduled stream or premiere that hasn't started.
+    Upcoming,
+    /// Currently streaming.
+    Live,
+    /// The stream ended but the VOD is still being processed.
+    PostLive,
+    /// A finished stream with its VOD available.
+    WasLive,
+}
+
+impl LiveStatus {
+    /// Status from yt-dlp's `live_status` field, falling back to `is_live`
+    /// for extractors that only set the latter.
+    #[must_use]
+    pub fn from_ytdlp(live_status: Option<&str>, is_live: Option<bool>) -> Self {
+        match live_status {
+            Some("is_upcoming") => Self::Upcoming,
+            Some("is_live") => Self::Live,
+            Some("post_live") => Self::PostLive,
+            Some("was_live") => Self::WasLive,
+            Some(_) => Self::NotLive,
+            None if is_live == Some(true) => Self::Live,
+            None => Self::NotLive,
+        }
+    }
+
+    /// Status implied by a yt-dlp error for content that hasn't started.
+    ///
+    /// yt-dlp refuses to dump metadata for scheduled streams and premieres.
+    #[must_use]
+    pub fn from_error_message(msg: &str) -> Option<Self> {
+        let msg = msg.to_lowercase();
+        (msg.contains("this live event will begin")
+            || msg.contains("premieres in")
+            || msg.contains("premiere will begin"))
+        .then_some(Self::Upcoming)
+    }
+
+    /// Whether the content can be downloaded as a finished recording.
+    #[must_use]
+    pub const fn is_ready(self) -> bool {
+        matches!(self, Self::NotLive | Self::WasLive)
+    }
+
+    const fn describe(self) -> &'static str {
+        match self {
+            Self::Upcoming => "has not started yet",
+            Self::Live => "is still live",
+            Self::PostLive => "has ended but its recording is still processing",
+            Self::NotLive | Self::WasLive => "is available",
+        }
+    }
+}
+
+/// Live content that can't be downloaded yet; the archive should be retried
+/// after `retry_in_secs`.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{message}")]
+pub struct LiveNotReady {
+    pub status: LiveStatus,
+    pub message: String,
+    pub retry_in_secs: i64,
+}
+
+impl LiveNotReady {
+    /// Error for `url` in `status`, scheduled with [`deferral_delay_secs`].
+    #[must_use]
+    pub fn new(
+        url: &str,
+        status: LiveStatus,
+        release_timestamp: Option<i64>,
+        now: i64,
+        interval_secs: i64,
+    ) -> Self {
+        Self {
+            status,
+            message: format!(
+                "Live content {}; archive deferred until it is available ({url})",
+                status.describe()
+            ),
+            retry_in_secs: deferral_delay_secs(status, release_timestamp, now, interval_secs),
+        }
+    }
+}
+
+/// Seconds to wait before checking live content again.
+///
+/// Live and processing streams are polled every `interval_secs`. Upcoming
+/// content with a known start time waits until it starts, but never less than
+/// one interval.
+#[must_use]
+pub fn deferral_delay_secs(
+    status: LiveStatus,
+    release_timestamp: Option<i64>,
+    now: i64,
+    interval_secs: i64,
+) -> i64 {
+    match (status, release_timestamp) {
+        (LiveStatus::Upcoming, Some(release)) => (release - now).max(interval_secs),
+        _ => interval_secs,
+    }
+}
+
+/// The first [`LiveNotReady`] in an error's chain.
+#[must_use]
+pub fn find_live_not_ready(err: &anyhow::Error) -> Option<&LiveNotReady> {
+    err.chain()
+        .find_map(|cause| cause.downcast_ref::<LiveNotReady>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_live_status_from_ytdlp() {
+        assert_eq!(
+            LiveStatus::from_ytdlp(Some("is_upcoming"), Some(false)),
+            LiveStatus::Upcoming
+        );
+        assert_eq!(
+            LiveStatus::from_ytdlp(Some("is_live"), None),
+            LiveStatus::Live
+        );
+        assert_eq!(
+            LiveStatus::from_ytdlp(Some("post_live"), Some(false)),
+            LiveStatus::PostLive
+        );
+        assert_eq!(
+            LiveStatus::from_ytdlp(Some("was_live"), Some(false)),
+            LiveStatus::WasLive
+        );
+        assert_eq!(
+            LiveStatus::from_ytdlp(Some("not_live"), None),
+            LiveStatus::NotLive
+        );
+        // Extractors without live_status only set is_live
+        assert_eq!(LiveStatus::from_ytdlp(None, Some(true)), LiveStatus::Live);
+        assert_eq!(LiveStatus::from_ytdlp(None, None), LiveStatus::NotLive);
+
+        assert!(LiveStatus::WasLive.is_ready());
+        assert!(LiveStatus::NotLive.is_ready());
+        assert!(!LiveStatus::PostLive.is_ready());
+    }
+
+    #[test]
+    fn test_live_status_from_error_message() {
+        assert_eq!(
+            LiveStatus::from_error_message(
+                "ERROR: [youtube] abc123: This live event will begin in 3 hours."
+            ),
+            Some(LiveStatus::Upcoming)
+        );
+        assert_eq!(
+            LiveStatus::from_error_message("ERROR: [youtube] abc123: Premieres in 20 minutes"),
+            Some(LiveStatus::Upcoming)
+        );
+        assert_eq!(
+            LiveStatus::from_error_message("ERROR: [youtube] abc123: Video unavailable"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_deferral_delay_secs() {
+        let now = 1_700_000_000;
+        assert_eq!(deferral_delay_secs(LiveStatus::Live, None, now, 1800), 1800);
+        assert_eq!(
+            deferral_delay_secs(LiveStatus::PostLive, Some(now - 60), now, 1800),
+            1800
+        );
+        // Upcoming content waits for its start time...
+        assert_eq!(
+            deferral_delay_secs(LiveStatus::Upcoming, Some(now + 7200), now, 1800),
+            7200
+        );
+        // ...but is never polled faster than the interval
+        assert_eq!(
+            deferral_delay_secs(LiveStatus::Upcoming, Some(now + 60), now, 1800),
+            1800
+        );
+        assert_eq!(
+            deferral_delay_secs(LiveStatus::Upcoming, None, now, 1800),
+            1800
+        );
+    }
+
+    #[test]
+    fn test_find_live_not_ready_in_chain() {
+        let err = anyhow::Error::new(LiveNotReady::new(
+            "https://x.com/i/spaces/1abc",
+            LiveStatus::Live,
+            None,
+            0,
+            600,
+        ))
+        .context("Handler archive failed");
+        let found = find_live_not_ready(&err).unwrap();
+        assert_eq!(found.retry_in_secs, 600);
+        assert!(found.message.contains("is still live"));
+        assert!(find_live_not_ready(&anyhow::anyhow!("boom")).is_none());
+    }
+}
@@ -1,21 +1,36 @@
 use sha2::{Digest, Sha256};
 use std::path::Path;
 
+pub mod change_watch;
 pub mod comment_worker;
+pub mod error;
 pub mod gallerydl;
+pub mod langdetect;
+pub mod live;
 pub mod monolith;
+pub mod nsfw;
+pub mod playlist;
 pub mod rate_limiter;
+pub mod reddit_comments;
+pub mod redirects;
+pub mod retention;
 pub mod screenshot;
+pub mod size_cap;
+pub mod storage_sizes;
 pub mod tiktok_comments;
 pub mod transcript;
+pub mod versions;
+pub mod whisper;
 pub mod worker;
 pub mod ytdlp;
 
+pub use error::{ArchiveError, ArchiveErrorKind, FailureClass, SkipReason};
 pub use monolith::{create_complete_html, MonolithConfig};
 pub use rate_limiter::DomainRateLimiter;
 pub use screenshot::{MhtmlConfig, PdfConfig, ScreenshotConfig, ScreenshotService};
 pub use worker::{
-    extract_platform_name, is_comments_supported_platform, process_subtitle_files, ArchiveWorker,
+    classify_error, classify_failure, error_kind, extract_platform_name,
+    is_comments_supported_platform, process_subtitle_files, ArchiveWorker,
 };
 
 /// Sanitize a filename to be URL-safe and filesystem-safe.
@@ -0,0 +1,97 @@
+//! Optional automatic NSFW classification of downloaded media.
+//!
+//! The configured command is run as `<command> <file>` on an image or video
+//! and must print a score between 0.0 (safe) and 1.0 (explicit) on the last
+//! line of stdout. Scores at or above the configured threshold mark the
+//! archive NSFW with `nsfw_source = 'auto'`.
+
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use tokio::process::Command;
+use tokio::sync::Semaphore;
+use tracing::debug;
+
+use crate::config::Config;
+
+/// Classifiers usually load a model per run; one at a time keeps the workers responsive.
+static CLASSIFIER_SEMAPHORE: LazyLock<Semaphore> = LazyLock::new(|| Semaphore::new(1));
+
+/// Parse the classifier score from its stdout.
+///
+/// Uses the last non-empty line so commands can log progress before the score.
+fn parse_score(stdout: &str) -> Option<f64> {
+    stdout
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .and_then(|line| line.parse::<f64>().ok())
+        .filter(|score| (0.0..=1.0).contains(score))
+}
+
+/// Score a media file with the configured classifier command.
+///
+/// Returns `Ok(None)` when the command is not installed, so callers can skip
+/// classification gracefully.
+///
+/// # Errors
+///
+/// Returns an error if the command fails, times out, or prints no valid score.
+pub async fn classify(path: &Path, config: &Config) -> Result<Option<f64>> {
+    let _permit = CLASSIFIER_SEMAPHORE
+        .acquire()
+        .await
+        .context("Failed to acquire NSFW classifier semaphore")?;
+
+    debug!(file = %path.display(), command = %config.nsfw_classifier_command, "Running NSFW classifier");
+
+    let child = match Command::new(&config.nsfw_classifier_command)
+        .arg(path)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).context("Failed to spawn NSFW classifier"),
+    };
+
+    let timeout = Duration::from_secs(config.nsfw_classifier_timeout_secs);
+    let output = tokio::time::timeout(timeout, child.wait_with_output())
+        .await
+        .with_context(|| format!("NSFW classifier timed out after {}s", timeout.as_secs()))?
+        .context("Failed to wait for NSFW classifier")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!(
+            "NSFW classifier failed with status {}: {stderr}",
+            output.status
+        );
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    parse_score(&stdout)
+        .map(Some)
+        .with_context(|| format!("NSFW classifier printed no score between 0 and 1: {stdout}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_score() {
+        assert_eq!(parse_score("0.93\n"), Some(0.93));
+        assert_eq!(parse_score("loading model...\n0.12\n\n"), Some(0.12));
+        assert_eq!(parse_score("1"), Some(1.0));
+        assert_eq!(parse_score("1.5"), None);
+        assert_eq!(parse_score("-0.1"), None);
+        assert_eq!(parse_score("nsfw"), None);
+        assert_eq!(parse_score(""), None);
+    }
+}
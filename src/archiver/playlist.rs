@@ -46,15 +46,24 @@ pub struct PlaylistInfo {
 
 /// Extract playlist metadata using yt-dlp.
 ///
+/// Only the first `max_items` entries are enumerated so very large playlists
+/// don't flood the archive queue.
+///
 /// # Errors
 ///
 /// Returns an error if yt-dlp fails or the response cannot be parsed.
-async fn get_playlist_metadata(url: &str, cookies: &CookieOptions<'_>) -> Result<PlaylistInfo> {
+async fn get_playlist_metadata(
+    url: &str,
+    cookies: &CookieOptions<'_>,
+    config: &Config,
+) -> Result<PlaylistInfo> {
     let mut args = vec![
         "--dump-json".to_string(),
         "--flat-playlist".to_string(),
         "--no-warnings".to_string(),
         "--quiet".to_string(),
+        "--playlist-end".to_string(),
+        config.youtube_playlist_max_items.to_string(),
     ];
 
     // Add cookie options
@@ -73,7 +82,7 @@ async fn get_playlist_metadata(url: &str, cookies: &CookieOptions<'_>) -> Result
 
     debug!(url = %url, "Fetching YouTube playlist metadata");
 
-    let output = Command::new("yt-dlp")
+    let output = Command::new(&config.yt_dlp_path)
         .args(&args)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -119,13 +128,13 @@ async fn get_playlist_metadata(url: &str, cookies: &CookieOptions<'_>) -> Result
     let playlist_url = first_entry
         .get("playlist_webpage_url")
         .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
+        .map(ToString::to_string);
 
     let uploader = first_entry
         .get("playlist_uploader")
         .or_else(|| first_entry.get("uploader"))
         .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
+        .map(ToString::to_string);
 
     // Extract videos from all entries
     let mut videos = Vec::new();
@@ -145,16 +154,15 @@ async fn get_playlist_metadata(url: &str, cookies: &CookieOptions<'_>) -> Result
             .unwrap_or("Untitled Video")
             .to_string();
 
-        let video_url = entry
-            .get("url")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| format!("https://www.youtube.com/watch?v={}", video_id));
+        let video_url = entry.get("url").and_then(|v| v.as_str()).map_or_else(
+            || format!("https://www.youtube.com/watch?v={video_id}"),
+            ToString::to_string,
+        );
 
         let uploader_name = entry
             .get("uploader")
             .and_then(|v| v.as_str())
-            .map(|s| s.to_string());
+            .map(ToString::to_string);
 
         let upload_date = entry.get("upload_date").and_then(|v| v.as_str()).map(|s| {
             // Convert YYYYMMDD format to ISO format YYYY-MM-DD
@@ -167,7 +175,7 @@ async fn get_playlist_metadata(url: &str, cookies: &CookieOptions<'_>) -> Result
 
         let duration = entry
             .get("duration")
-            .and_then(|v| v.as_i64())
+            .and_then(serde_json::Value::as_i64)
             .map(|d| d as i32);
 
         videos.push(PlaylistVideoInfo {
@@ -208,7 +216,8 @@ fn maybe_adjust_chromium_user_data_dir_spec(spec: &str) -> String {
 /// Archive a YouTube playlist.
 ///
 /// Extracts playlist metadata without downloading any videos and stores
-/// the information as JSON in the archive result.
+/// the information as JSON in the archive result. The worker enqueues each
+/// listed video as its own archive and records playlist membership.
 ///
 /// # Errors
 ///
@@ -217,13 +226,13 @@ pub async fn archive_playlist(
     url: &str,
     _work_dir: &Path,
     cookies: &CookieOptions<'_>,
-    _config: &Config,
+    config: &Config,
     playlist_id: &str,
 ) -> Result<ArchiveResult> {
     debug!(url = %url, playlist_id = %playlist_id, "Archiving YouTube playlist");
 
     // Fetch playlist metadata
-    let playlist_info = match get_playlist_metadata(url, cookies).await {
+    let playlist_info = match get_playlist_metadata(url, cookies, config).await {
         Ok(info) => info,
         Err(e) => {
             warn!("Failed to fetch playlist metadata: {e}");
@@ -49,7 +49,6 @@ impl DomainRateLimiter {
     /// Try to acquire a permit for the given domain without blocking.
     ///
     /// Returns `None` if no permit is immediately available.
-    #[allow(dead_code)]
     pub async fn try_acquire(&self, domain: &str) -> Option<DomainPermit> {
         let semaphore = self.get_or_create_semaphore(domain).await;
         semaphore
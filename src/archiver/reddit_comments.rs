@@ -0,0 +1,360 @@
+//! Reddit comment extraction using Reddit's public `.json` endpoint.
+//!
+//! Appending `.json` to a post URL returns the submission and its comment
+//! listing as nested `t1` things. The tree is flattened into the standard
+//! comment schema (one entry per comment, linked by `parent_id`) so the archive
+//! page renders it threaded like the TikTok/YouTube comments. Removed and
+//! deleted comments are kept as placeholders so reply chains stay intact.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use reqwest::StatusCode;
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::constants::ARCHIVAL_USER_AGENT;
+use crate::handlers::reddit::extract_post_id;
+
+/// Base URL for Reddit's JSON endpoint.
+pub const REDDIT_JSON_BASE: &str = "https://old.reddit.com";
+
+/// Reddit returns at most this many comments per listing request.
+const REDDIT_MAX_LIMIT: usize = 500;
+
+/// Comments flattened from a Reddit comment listing.
+#[derive(Debug, Default)]
+struct FlattenedComments {
+    comments: Vec<Value>,
+    /// Set when the depth/count cap or a "load more" stub left comments out.
+    truncated: bool,
+}
+
+/// Fetch the comment tree for a Reddit post.
+///
+/// Keeps at most `comments_max_count` comments and `comments_max_depth`
+/// levels (only top-level comments when `comments_include_replies` is off).
+///
+/// # Returns
+/// The comments in the standard schema, or `None` when Reddit rate-limited
+/// the request so the archive keeps just the post.
+///
+/// # Errors
+/// Returns an error if the URL has no post ID, the request fails, or the
+/// response isn't a comment listing.
+pub async fn fetch_reddit_comments(
+    base_url: &str,
+    url: &str,
+    config: &Config,
+) -> Result<Option<Value>> {
+    let post_id = extract_post_id(url)
+        .ok_or_else(|| anyhow::anyhow!("Could not extract post ID from URL: {url}"))?;
+
+    let max_count = config.comments_max_count;
+    let max_depth = if config.comments_include_replies {
+        config.comments_max_depth.max(1)
+    } else {
+        1
+    };
+
+    info!(post_id = %post_id, max_count, max_depth, "Extracting Reddit comments");
+
+    let endpoint = format!(
+        "{base_url}/comments/{post_id}.json?raw_json=1&limit={}&depth={max_depth}",
+        max_count.clamp(1, REDDIT_MAX_LIMIT)
+    );
+    let response = reqwest::Client::new()
+        .get(&endpoint)
+        .header("User-Agent", ARCHIVAL_USER_AGENT)
+        .timeout(Duration::from_secs(30))
+        .send()
+        .await
+        .context("Failed to send request to Reddit")?;
+
+    if response.status() == StatusCode::TOO_MANY_REQUESTS {
+        warn!(post_id = %post_id, "Reddit rate-limited the comment request");
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        anyhow::bail!("Reddit returned status {}", response.status());
+    }
+
+    let data: Value = response
+        .json()
+        .await
+        .context("Failed to parse Reddit response as JSON")?;
+
+    // The response is [submission listing, comment listing]
+    let post = data
+        .get(0)
+        .and_then(|l| l.pointer("/data/children/0/data"))
+        .cloned()
+        .unwrap_or(Value::Null);
+    let children = data
+        .get(1)
+        .and_then(|l| l.pointer("/data/children"))
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow::anyhow!("Reddit response has no comment listing"))?;
+
+    let flattened = flatten_comment_tree(children, max_depth, max_count);
+    let extracted = flattened.comments.len();
+    let top_level = flattened
+        .comments
+        .iter()
+        .filter(|c| c["depth"] == 0)
+        .count();
+    let deepest = flattened
+        .comments
+        .iter()
+        .filter_map(|c| c["depth"].as_u64())
+        .max()
+        .unwrap_or(0);
+
+    info!(
+        post_id = %post_id,
+        comments = extracted,
+        truncated = flattened.truncated,
+        "Reddit comment extraction completed"
+    );
+
+    Ok(Some(serde_json::json!({
+        "platform": "reddit",
+        "extraction_method": "json",
+        "extracted_at": chrono::Utc::now().to_rfc3339(),
+        "content_url": url,
+        "content_id": post_id,
+        "limited": flattened.truncated,
+        "truncated": flattened.truncated,
+        "comments_disabled": false,
+        "limit_applied": max_count,
+        "stats": {
+            "total_comments": post.get("num_comments").and_then(Value::as_u64).unwrap_or(extracted as u64),
+            "extracted_comments": extracted,
+            "top_level_comments": top_level,
+            "max_depth": deepest,
+        },
+        "comments": flattened.comments,
+    })))
+}
+
+/// Flatten a Reddit comment listing into parent-linked comments.
+///
+/// Comments are emitted parent-first. Anything at or below `max_depth`
+/// levels, past `max_count` comments, or behind a "load more" stub is left
+/// out and marks the result truncated.
+fn flatten_comment_tree(
+    children: &[Value],
+    max_depth: usize,
+    max_count: usize,
+) -> FlattenedComments {
+    let mut out = FlattenedComments::default();
+    flatten_into(children, 0, max_depth, max_count, &mut out);
+    out
+}
+
+fn flatten_into(
+    children: &[Value],
+    depth: usize,
+    max_depth: usize,
+    max_count: usize,
+    out: &mut FlattenedComments,
+) {
+    for child in children {
+        if child["kind"] != "t1" {
+            // "more" stubs stand for comments the listing didn't include
+            if child["kind"] == "more" {
+                out.truncated = true;
+            }
+            continue;
+        }
+        if depth >= max_depth || out.comments.len() >= max_count {
+            out.truncated = true;
+            return;
+        }
+
+        let data = &child["data"];
+        out.comments.push(comment_from_reddit(data, depth));
+
+        if let Some(replies) = data
+            .pointer("/replies/data/children")
+            .and_then(Value::as_array)
+        {
+            flatten_into(replies, depth + 1, max_depth, max_count, out);
+        }
+    }
+}
+
+/// Convert one Reddit `t1` comment into the standard comment schema.
+fn comment_from_reddit(data: &Value, depth: usize) -> Value {
+    let text = data["body"].as_str().unwrap_or_default();
+    let author = data["author"].as_str().unwrap_or("[deleted]");
+    let is_removed = matches!(text, "[removed]" | "[deleted]") || author == "[deleted]";
+    let parent_id = match data["parent_id"].as_str() {
+        Some(parent) if parent.starts_with("t1_") => parent.trim_start_matches("t1_"),
+        _ => "root",
+    };
+
+    serde_json::json!({
+        "id": data["id"].as_str().unwrap_or_default(),
+        "author": author,
+        "author_id": data["author_fullname"],
+        "text": text,
+        "timestamp": data["created_utc"].as_f64().map(|t| t as i64),
+        "likes": data["score"].as_i64().unwrap_or(0),
+        "is_pinned": data["stickied"].as_bool().unwrap_or(false),
+        "is_creator": data["is_submitter"].as_bool().unwrap_or(false),
+        "is_removed": is_removed,
+        "parent_id": parent_id,
+        "depth": depth,
+        "replies": [],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn comment(id: &str, parent: &str, body: &str, replies: &[Value]) -> Value {
+        let replies = if replies.is_empty() {
+            Value::String(String::new())
+        } else {
+            serde_json::json!({"kind": "Listing", "data": {"children": replies}})
+        };
+        serde_json::json!({
+            "kind": "t1",
+            "data": {
+                "id": id,
+                "parent_id": parent,
+                "author": if body == "[deleted]" { "[deleted]" } else { "someone" },
+                "body": body,
+                "created_utc": 1_700_000_000.0,
+                "score": 5,
+                "replies": replies,
+            }
+        })
+    }
+
+    fn sample_tree() -> Vec<Value> {
+        vec![
+            comment(
+                "a",
+                "t3_post",
+                "top one",
+                &[comment(
+                    "b",
+                    "t1_a",
+                    "[removed]",
+                    &[comment("c", "t1_b", "deep reply", &[])],
+                )],
+            ),
+            comment("d", "t3_post", "[deleted]", &[]),
+        ]
+    }
+
+    #[test]
+    fn test_flatten_comment_tree() {
+        let flat = flatten_comment_tree(&sample_tree(), 10, 100);
+
+        let ids: Vec<&str> = flat
+            .comments
+            .iter()
+            .map(|c| c["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["a", "b", "c", "d"]);
+        assert_eq!(flat.comments[0]["parent_id"], "root");
+        assert_eq!(flat.comments[1]["parent_id"], "a");
+        assert_eq!(flat.comments[2]["parent_id"], "b");
+        assert_eq!(flat.comments[2]["depth"], 2);
+        assert!(!flat.truncated);
+
+        // Removed/deleted placeholders are kept and flagged
+        assert_eq!(flat.comments[1]["is_removed"], true);
+        assert_eq!(flat.comments[3]["is_removed"], true);
+        assert_eq!(flat.comments[3]["author"], "[deleted]");
+        assert_eq!(flat.comments[0]["is_removed"], false);
+    }
+
+    #[test]
+    fn test_flatten_depth_cap() {
+        let flat = flatten_comment_tree(&sample_tree(), 2, 100);
+
+        let ids: Vec<&str> = flat
+            .comments
+            .iter()
+            .map(|c| c["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["a", "b", "d"]);
+        assert!(flat.truncated);
+
+        let top_only = flatten_comment_tree(&sample_tree(), 1, 100);
+        assert_eq!(top_only.comments.len(), 2);
+        assert!(top_only.truncated);
+    }
+
+    #[test]
+    fn test_flatten_count_cap_and_more_stub() {
+        let flat = flatten_comment_tree(&sample_tree(), 10, 2);
+        assert_eq!(flat.comments.len(), 2);
+        assert!(flat.truncated);
+
+        let mut tree = sample_tree();
+        tree.push(serde_json::json!({"kind": "more", "data": {"count": 12, "children": ["x"]}}));
+        let flat = flatten_comment_tree(&tree, 10, 100);
+        assert_eq!(flat.comments.len(), 4);
+        assert!(flat.truncated);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_reddit_comments() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/comments/abc123.json"))
+            .and(query_param("depth", "3"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"kind": "Listing", "data": {"children": [
+                    {"kind": "t3", "data": {"id": "abc123", "num_comments": 4}}
+                ]}},
+                {"kind": "Listing", "data": {"children": sample_tree()}},
+            ])))
+            .mount(&server)
+            .await;
+
+        let json = fetch_reddit_comments(
+            &server.uri(),
+            "https://old.reddit.com/r/rust/comments/abc123/title/",
+            &Config::for_testing(),
+        )
+        .await
+        .unwrap()
+        .expect("comments should be fetched");
+
+        assert_eq!(json["platform"], "reddit");
+        assert_eq!(json["stats"]["extracted_comments"], 4);
+        assert_eq!(json["stats"]["top_level_comments"], 2);
+        assert_eq!(json["stats"]["max_depth"], 2);
+        assert_eq!(json["truncated"], false);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_reddit_comments_rate_limited() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/comments/abc123.json"))
+            .respond_with(ResponseTemplate::new(429))
+            .mount(&server)
+            .await;
+
+        let result = fetch_reddit_comments(
+            &server.uri(),
+            "https://old.reddit.com/r/rust/comments/abc123/title/",
+            &Config::for_testing(),
+        )
+        .await
+        .unwrap();
+
+        assert!(result.is_none());
+    }
+}
@@ -0,0 +1,190 @@
+//! Redirect chain capture for archived links.
+//!
+//! Before a link is archived its redirects are followed one hop at a time and
+//! each hop's URL and status is recorded, so a failed archive shows whether a
+//! shortener broke or the final host blocked the request (301 → 302 → 403).
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use reqwest::{header, StatusCode};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::config::Config;
+use crate::handlers::archival_client_builder;
+
+/// Maximum number of hops recorded; resolution stops after this many.
+pub const MAX_REDIRECT_HOPS: usize = 10;
+
+/// One request in a redirect chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedirectHop {
+    pub url: String,
+    pub status: u16,
+}
+
+/// Serialize a chain for the `archives.redirect_chain` column.
+#[must_use]
+pub fn chain_to_json(chain: &[RedirectHop]) -> String {
+    serde_json::to_string(chain).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Parse a stored chain, returning an empty chain for malformed JSON.
+#[must_use]
+pub fn parse_chain(json: &str) -> Vec<RedirectHop> {
+    serde_json::from_str(json).unwrap_or_default()
+}
+
+/// Follow `url`'s redirects manually and record every hop.
+///
+/// Uses HEAD requests, falling back to GET for servers that reject HEAD.
+/// The last hop is the final response (or the hop where the cap was hit).
+///
+/// # Errors
+///
+/// Returns an error if the first request can't be sent; later network errors
+/// end the chain early instead.
+pub async fn resolve_redirect_chain(config: &Config, url: &str) -> Result<Vec<RedirectHop>> {
+    let client = archival_client_builder(config, Duration::from_secs(10))?
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .context("Failed to build HTTP client")?;
+
+    let mut chain = Vec::new();
+    let mut current = Url::parse(url).context("Failed to parse URL")?;
+
+    while chain.len() < MAX_REDIRECT_HOPS {
+        let response = match send_probe(&client, &current).await {
+            Ok(response) => response,
+            Err(e) if chain.is_empty() => return Err(e),
+            Err(_) => break,
+        };
+        let status = response.status();
+        chain.push(RedirectHop {
+            url: current.to_string(),
+            status: status.as_u16(),
+        });
+
+        if !status.is_redirection() {
+            break;
+        }
+        let Some(next) = response
+            .headers()
+            .get(header::LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|location| current.join(location).ok())
+        else {
+            break;
+        };
+        current = next;
+    }
+
+    Ok(chain)
+}
+
+async fn send_probe(client: &reqwest::Client, url: &Url) -> Result<reqwest::Response> {
+    let response = client
+        .head(url.clone())
+        .send()
+        .await
+        .context("Failed to resolve redirects")?;
+
+    if matches!(
+        response.status(),
+        StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED
+    ) {
+        return client
+            .get(url.clone())
+            .send()
+            .await
+            .context("Failed to resolve redirects");
+    }
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_chain_json_round_trip() {
+        let chain = vec![
+            RedirectHop {
+                url: "https://short.example/abc".to_string(),
+                status: 301,
+            },
+            RedirectHop {
+                url: "https://example.com/page".to_string(),
+                status: 403,
+            },
+        ];
+        let json = chain_to_json(&chain);
+        assert_eq!(
+            json,
+            r#"[{"url":"https://short.example/abc","status":301},{"url":"https://example.com/page","status":403}]"#
+        );
+        assert_eq!(parse_chain(&json), chain);
+        assert!(parse_chain("not json").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_multi_hop_chain() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/short"))
+            .respond_with(ResponseTemplate::new(301).insert_header("location", "/middle?x=1"))
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/middle"))
+            .respond_with(
+                ResponseTemplate::new(302)
+                    .insert_header("location", format!("{}/final", server.uri())),
+            )
+            .mount(&server)
+            .await;
+        // Final host rejects HEAD, so the GET fallback supplies the status
+        Mock::given(method("HEAD"))
+            .and(path("/final"))
+            .respond_with(ResponseTemplate::new(405))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/final"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let chain =
+            resolve_redirect_chain(&Config::for_testing(), &format!("{}/short", server.uri()))
+                .await
+                .unwrap();
+
+        let statuses: Vec<u16> = chain.iter().map(|hop| hop.status).collect();
+        assert_eq!(statuses, vec![301, 302, 403]);
+        assert_eq!(chain[1].url, format!("{}/middle?x=1", server.uri()));
+        assert_eq!(chain[2].url, format!("{}/final", server.uri()));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_chain_is_capped() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/loop"))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", "/loop"))
+            .mount(&server)
+            .await;
+
+        let chain =
+            resolve_redirect_chain(&Config::for_testing(), &format!("{}/loop", server.uri()))
+                .await
+                .unwrap();
+
+        assert_eq!(chain.len(), MAX_REDIRECT_HOPS);
+        assert!(chain.iter().all(|hop| hop.status == 302));
+    }
+}
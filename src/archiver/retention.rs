@@ -0,0 +1,151 @@
+//! Background worker that prunes old failed and skipped archives.
+//!
+//! Enabled by `ARCHIVE_RETENTION_DAYS`. Failed archives that have exhausted
+//! their retries (and optionally skipped ones) are deleted once their last
+//! attempt is older than the retention period, together with their S3
+//! artifacts and IPFS pins.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use tracing::{error, info, warn};
+
+use crate::config::Config;
+use crate::db::{
+    delete_archive, get_archive_versions, get_archives_eligible_for_pruning,
+    get_artifacts_for_archive, Archive, ArchiveArtifact, Database,
+};
+use crate::ipfs::IpfsClient;
+use crate::s3::S3Client;
+
+/// How often to look for archives past the retention period.
+const POLL_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Maximum number of archives pruned per run.
+const BATCH_SIZE: i64 = 500;
+
+/// S3 keys of the artifacts stored under this archive's own prefix.
+///
+/// Deduplicated artifacts may point at another archive's object, so only keys
+/// under `prefix` are removed.
+pub(crate) fn owned_s3_keys(
+    archive: &Archive,
+    artifacts: &[ArchiveArtifact],
+    prefix: &str,
+) -> Vec<String> {
+    let mut keys: Vec<String> = artifacts
+        .iter()
+        .map(|a| a.s3_key.clone())
+        .chain(archive.s3_key_primary.clone())
+        .chain(archive.s3_key_thumb.clone())
+        .filter(|key| key.starts_with(prefix))
+        .collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+/// Run the retention loop.
+///
+/// This function runs forever, pruning eligible archives every few hours. It
+/// should be spawned as a background task.
+pub async fn run(db: Database, s3: S3Client, ipfs: IpfsClient, config: Config) {
+    info!(
+        retention_days = config.archive_retention_days,
+        prune_skipped = config.archive_retention_prune_skipped,
+        dry_run = config.archive_retention_dry_run,
+        "Archive retention worker started"
+    );
+
+    loop {
+        if let Err(e) = prune_once(&db, &s3, &ipfs, &config).await {
+            error!("Archive retention run failed: {e:#}");
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Prune one batch of eligible archives, returning how many were deleted.
+async fn prune_once(
+    db: &Database,
+    s3: &S3Client,
+    ipfs: &IpfsClient,
+    config: &Config,
+) -> Result<usize> {
+    let eligible = get_archives_eligible_for_pruning(
+        db.pool(),
+        config.archive_retention_days,
+        config.archive_max_retries,
+        config.archive_retention_prune_skipped,
+        BATCH_SIZE,
+    )
+    .await?;
+
+    if eligible.is_empty() {
+        return Ok(0);
+    }
+
+    info!(
+        count = eligible.len(),
+        dry_run = config.archive_retention_dry_run,
+        "Archives eligible for retention pruning"
+    );
+    if config.archive_retention_dry_run {
+        for archive in &eligible {
+            info!(
+                archive_id = archive.id,
+                status = %archive.status,
+                last_attempt_at = ?archive.last_attempt_at,
+                "Would prune archive (dry run)"
+            );
+        }
+        return Ok(0);
+    }
+
+    let mut deleted = 0;
+    for archive in &eligible {
+        match prune_archive(db, s3, ipfs, config, archive).await {
+            Ok(()) => deleted += 1,
+            Err(e) => warn!(archive_id = archive.id, "Failed to prune archive: {e:#}"),
+        }
+    }
+
+    info!(deleted, "Archive retention run complete");
+    Ok(deleted)
+}
+
+/// Delete one archive's S3 objects, IPFS pin and database rows.
+async fn prune_archive(
+    db: &Database,
+    s3: &S3Client,
+    ipfs: &IpfsClient,
+    config: &Config,
+    archive: &Archive,
+) -> Result<()> {
+    let artifacts = get_artifacts_for_archive(db.pool(), archive.id).await?;
+    let prefix = format!("{}{}/", config.s3_prefix, archive.link_id);
+    for key in owned_s3_keys(archive, &artifacts, &prefix) {
+        if let Err(e) = s3.delete_object(&key).await {
+            warn!(archive_id = archive.id, key = %key, "Failed to delete S3 object: {e:#}");
+        }
+    }
+    for version in get_archive_versions(db.pool(), archive.id).await? {
+        for key in s3.list_objects(&version.s3_prefix).await? {
+            if let Err(e) = s3.delete_object(&key).await {
+                warn!(archive_id = archive.id, key = %key, "Failed to delete S3 object: {e:#}");
+            }
+        }
+    }
+
+    if let Some(cid) = archive.ipfs_cid.as_deref() {
+        if ipfs.is_enabled() {
+            if let Err(e) = ipfs.unpin(cid).await {
+                warn!(archive_id = archive.id, cid = %cid, "Failed to unpin from IPFS: {e:#}");
+            }
+        }
+    }
+
+    delete_archive(db.pool(), archive.id).await?;
+    info!(archive_id = archive.id, status = %archive.status, "Pruned archive");
+    Ok(())
+}
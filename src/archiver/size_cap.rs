@@ -0,0 +1,133 @@
+//! Early enforcement of the `MAX_ARTIFACT_BYTES` download cap.
+//!
+//! Downloads are checked against the size the server (or yt-dlp) reports
+//! before any content is fetched. When no size is reported, the cap is
+//! enforced while streaming instead.
+
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, CONTENT_LENGTH};
+
+use super::error::ArchiveError;
+
+/// Error text used when a download is larger than `MAX_ARTIFACT_BYTES`.
+pub const ARTIFACT_TOO_LARGE: &str = "artifact too large";
+
+/// Outcome of checking a reported size against the cap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeCheck {
+    /// No cap, or the reported size fits.
+    Within,
+    /// The reported size exceeds the cap; skip the download.
+    TooLarge(u64),
+    /// No size was reported; enforce the cap while streaming.
+    Unknown,
+}
+
+/// Compare a reported size with `cap` (0 disables the cap).
+#[must_use]
+pub const fn check_reported_size(reported: Option<u64>, cap: u64) -> SizeCheck {
+    if cap == 0 {
+        return SizeCheck::Within;
+    }
+    match reported {
+        Some(size) if size > cap => SizeCheck::TooLarge(size),
+        Some(_) => SizeCheck::Within,
+        None => SizeCheck::Unknown,
+    }
+}
+
+/// Read `Content-Length` from response headers.
+///
+/// Used instead of `Response::content_length`, which reports the (empty)
+/// body of a HEAD response rather than the header.
+#[must_use]
+pub fn header_content_length(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(CONTENT_LENGTH)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Error for a download whose reported or streamed size exceeds `cap`.
+#[must_use]
+pub fn too_large(size: u64, cap: u64) -> anyhow::Error {
+    ArchiveError::TooLarge(format!(
+        "{ARTIFACT_TOO_LARGE}: {size} bytes exceeds MAX_ARTIFACT_BYTES ({cap} bytes)"
+    ))
+    .into()
+}
+
+/// Read a response body, stopping once it grows past `cap` (0 disables the cap).
+///
+/// # Errors
+///
+/// Returns an error if reading fails or the body exceeds the cap.
+pub async fn read_body_capped(mut response: reqwest::Response, cap: u64) -> Result<Vec<u8>> {
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .context("Failed to read response body")?
+    {
+        body.extend_from_slice(&chunk);
+        if cap > 0 && body.len() as u64 > cap {
+            return Err(too_large(body.len() as u64, cap).context("size limit hit while streaming"));
+        }
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_reported_size() {
+        // Cap disabled
+        assert_eq!(check_reported_size(Some(u64::MAX), 0), SizeCheck::Within);
+        assert_eq!(check_reported_size(None, 0), SizeCheck::Within);
+
+        assert_eq!(check_reported_size(Some(100), 100), SizeCheck::Within);
+        assert_eq!(
+            check_reported_size(Some(101), 100),
+            SizeCheck::TooLarge(101)
+        );
+        // Chunked responses report nothing; enforce while streaming
+        assert_eq!(check_reported_size(None, 100), SizeCheck::Unknown);
+    }
+
+    #[test]
+    fn test_header_content_length() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(header_content_length(&headers), None);
+        headers.insert(CONTENT_LENGTH, "2048".parse().unwrap());
+        assert_eq!(header_content_length(&headers), Some(2048));
+        headers.insert(CONTENT_LENGTH, "lots".parse().unwrap());
+        assert_eq!(header_content_length(&headers), None);
+    }
+
+    #[tokio::test]
+    async fn test_read_body_capped_without_length() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![b'x'; 4096]))
+            .mount(&server)
+            .await;
+
+        let fetch = || async { reqwest::get(server.uri()).await.unwrap() };
+
+        let body = read_body_capped(fetch().await, 0).await.unwrap();
+        assert_eq!(body.len(), 4096);
+        let body = read_body_capped(fetch().await, 4096).await.unwrap();
+        assert_eq!(body.len(), 4096);
+
+        let err = read_body_capped(fetch().await, 1000).await.unwrap_err();
+        assert!(format!("{err:#}").contains(ARTIFACT_TOO_LARGE));
+    }
+}
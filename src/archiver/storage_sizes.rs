@@ -0,0 +1,227 @@
+//! Recompute stored artifact sizes from S3.
+//!
+//! Storage stats sum `archive_artifacts.size_bytes`, which can drift from the
+//! real objects after manual S3 changes or failed uploads. A recompute pass
+//! heads each artifact's object and overwrites the stored size when it
+//! differs. Passes work through artifacts in ID order in batches, so a sweep
+//! can be resumed after the last artifact a batch checked.
+
+use anyhow::Result;
+use futures_util::{stream, StreamExt};
+use tracing::{info, warn};
+
+use crate::db::{get_artifacts_after, set_artifact_size_bytes, ArchiveArtifact, Database};
+use crate::s3::S3Client;
+
+/// Maximum number of S3 HEAD requests in flight at once.
+const HEAD_CONCURRENCY: usize = 8;
+
+/// An artifact whose stored size didn't match its S3 object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeDiscrepancy {
+    pub artifact_id: i64,
+    pub s3_key: String,
+    pub stored: Option<i64>,
+    pub actual: i64,
+}
+
+/// Outcome of one recompute batch.
+#[derive(Debug, Default)]
+pub struct SizeRecomputeReport {
+    /// Artifacts checked in this batch
+    pub checked: usize,
+    /// Stored sizes that were corrected
+    pub discrepancies: Vec<SizeDiscrepancy>,
+    /// Artifacts whose S3 object no longer exists
+    pub missing: usize,
+    /// Artifacts that couldn't be checked because the HEAD request failed
+    pub errors: usize,
+    /// ID of the last artifact in the batch, to resume after
+    pub last_id: Option<i64>,
+}
+
+/// Whether a stored size disagrees with the size S3 reports.
+///
+/// An unknown stored size counts as a discrepancy so it gets filled in.
+#[must_use]
+pub fn size_differs(stored: Option<i64>, actual: i64) -> bool {
+    stored != Some(actual)
+}
+
+/// Head the next `limit` artifacts after `after_id` and correct their sizes.
+///
+/// Deduplicated artifacts share an object with their original, so each row
+/// is still checked against its own `s3_key`. Missing objects are reported
+/// but left untouched.
+///
+/// # Errors
+///
+/// Returns an error if the batch can't be read or a size can't be written.
+pub async fn recompute_artifact_sizes(
+    db: &Database,
+    s3: &S3Client,
+    after_id: i64,
+    limit: i64,
+) -> Result<SizeRecomputeReport> {
+    let artifacts = get_artifacts_after(db.read_pool(), after_id, limit).await?;
+    let mut report = SizeRecomputeReport {
+        checked: artifacts.len(),
+        last_id: artifacts.last().map(|a| a.id),
+        ..SizeRecomputeReport::default()
+    };
+
+    let heads: Vec<(ArchiveArtifact, Result<Option<i64>>)> = stream::iter(artifacts)
+        .map(|artifact| async move {
+            let size = s3.object_size(&artifact.s3_key).await;
+            (artifact, size)
+        })
+        .buffered(HEAD_CONCURRENCY)
+        .collect()
+        .await;
+
+    for (artifact, size) in heads {
+        match size {
+            Ok(Some(actual)) if size_differs(artifact.size_bytes, actual) => {
+                set_artifact_size_bytes(db.pool(), artifact.id, actual).await?;
+                info!(
+                    artifact_id = artifact.id,
+                    s3_key = %artifact.s3_key,
+                    stored = ?artifact.size_bytes,
+                    actual,
+                    "Corrected artifact size"
+                );
+                report.discrepancies.push(SizeDiscrepancy {
+                    artifact_id: artifact.id,
+                    s3_key: artifact.s3_key,
+                    stored: artifact.size_bytes,
+                    actual,
+                });
+            }
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                warn!(
+                    artifact_id = artifact.id,
+                    s3_key = %artifact.s3_key,
+                    "Artifact object missing from S3"
+                );
+                report.missing += 1;
+            }
+            Err(e) => {
+                warn!(
+                    artifact_id = artifact.id,
+                    s3_key = %artifact.s3_key,
+                    error = %e,
+                    "Failed to head artifact object"
+                );
+                report.errors += 1;
+            }
+        }
+    }
+
+    info!(
+        after_id,
+        last_id = ?report.last_id,
+        checked = report.checked,
+        fixed = report.discrepancies.len(),
+        missing = report.missing,
+        errors = report.errors,
+        "Recomputed artifact sizes"
+    );
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::db::{
+        create_pending_archive, get_artifact, get_or_create_link, insert_artifact, NewLink,
+    };
+    use wiremock::matchers::{method, path_regex};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_size_differs() {
+        assert!(!size_differs(Some(100), 100));
+        assert!(size_differs(Some(100), 120));
+        assert!(size_differs(None, 0));
+    }
+
+    #[tokio::test]
+    async fn test_recompute_artifact_sizes_fixes_discrepancies() {
+        let storage = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path_regex("/archives/1/raw.html$"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0u8; 100]))
+            .mount(&storage)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path_regex("/archives/1/video.mp4$"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0u8; 2048]))
+            .mount(&storage)
+            .await;
+        // Anything else is unmatched and gets a 404
+
+        std::env::set_var("AWS_ACCESS_KEY_ID", "test");
+        std::env::set_var("AWS_SECRET_ACCESS_KEY", "test");
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            s3_endpoint: Some(storage.uri()),
+            ..Config::for_testing()
+        };
+        let db = Database::new(&temp_dir.path().join("test.sqlite"))
+            .await
+            .unwrap();
+        let s3 = S3Client::new(&config).await.unwrap();
+
+        let link_id = get_or_create_link(
+            db.pool(),
+            &NewLink {
+                original_url: "https://example.com/a".to_string(),
+                normalized_url: "https://example.com/a".to_string(),
+                canonical_url: None,
+                domain: "example.com".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        let archive_id = create_pending_archive(db.pool(), link_id, None)
+            .await
+            .unwrap();
+        let mut ids = Vec::new();
+        for (kind, key, size) in [
+            ("raw_html", "archives/1/raw.html", Some(100)),
+            ("video", "archives/1/video.mp4", Some(1024)),
+            ("screenshot", "archives/1/screenshot.webp", Some(500)),
+        ] {
+            let id = insert_artifact(db.pool(), archive_id, kind, key, None, size, None)
+                .await
+                .unwrap();
+            ids.push(id);
+        }
+
+        let report = recompute_artifact_sizes(&db, &s3, 0, 2).await.unwrap();
+        assert_eq!(report.checked, 2);
+        assert_eq!(
+            report.discrepancies,
+            vec![SizeDiscrepancy {
+                artifact_id: ids[1],
+                s3_key: "archives/1/video.mp4".to_string(),
+                stored: Some(1024),
+                actual: 2048,
+            }]
+        );
+        assert_eq!(report.last_id, Some(ids[1]));
+        let video = get_artifact(db.pool(), ids[1]).await.unwrap().unwrap();
+        assert_eq!(video.size_bytes, Some(2048));
+
+        // Resuming picks up the rest; a missing object is reported, not changed
+        let report = recompute_artifact_sizes(&db, &s3, ids[1], 2).await.unwrap();
+        assert_eq!(report.checked, 1);
+        assert!(report.discrepancies.is_empty());
+        assert_eq!(report.missing, 1);
+        let screenshot = get_artifact(db.pool(), ids[2]).await.unwrap().unwrap();
+        assert_eq!(screenshot.size_bytes, Some(500));
+    }
+}
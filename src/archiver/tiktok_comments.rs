@@ -10,6 +10,7 @@ use std::collections::HashSet;
 use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
+use super::comment_worker::is_comments_disabled_message;
 use crate::constants::ARCHIVAL_USER_AGENT;
 
 // Note: We use serde_json::Value instead of strict structs because TikTok's API
@@ -105,6 +106,48 @@ async fn fetch_comments_batch(
     Ok(data)
 }
 
+/// What the pagination loop should do after processing a page of comments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PageAction {
+    /// Fetch the next page.
+    Continue,
+    /// The platform has no more comments to return.
+    Exhausted,
+    /// The comment cap was reached while more comments were available.
+    Truncated,
+}
+
+/// Decide whether to keep paginating after a page has been processed.
+///
+/// `taken` is how many comments from the page were kept; fewer than
+/// `batch_size` means the cap cut the page short. A cursor that does not
+/// advance is treated as the end of the list so a misbehaving API can't
+/// keep the loop spinning.
+fn next_page_action(
+    collected: usize,
+    limit: usize,
+    batch_size: usize,
+    taken: usize,
+    has_more: bool,
+    cursor: u64,
+    next_cursor: u64,
+) -> PageAction {
+    if batch_size == 0 {
+        return PageAction::Exhausted;
+    }
+    if collected >= limit {
+        return if has_more || taken < batch_size {
+            PageAction::Truncated
+        } else {
+            PageAction::Exhausted
+        };
+    }
+    if !has_more || next_cursor <= cursor {
+        return PageAction::Exhausted;
+    }
+    PageAction::Continue
+}
+
 /// Deduplicate comments by ID.
 ///
 /// TikTok API may return duplicate comments at page boundaries.
@@ -152,16 +195,12 @@ pub async fn fetch_tiktok_comments(
     let mut all_comments = Vec::new();
     let mut cursor = 0u64;
     let mut request_count = 0;
+    let mut truncated = false;
+    let mut comments_disabled = false;
     let mut last_progress_update = Instant::now();
     let progress_update_interval = Duration::from_secs(10);
 
     loop {
-        // Check if we've reached the limit
-        if all_comments.len() >= limit {
-            info!("Reached comment limit of {}", limit);
-            break;
-        }
-
         // Fetch a batch of comments
         request_count += 1;
         debug!(
@@ -174,7 +213,12 @@ pub async fn fetch_tiktok_comments(
         let response = match fetch_comments_batch(&client, &video_id, cursor).await {
             Ok(r) => r,
             Err(e) => {
-                warn!(error = %e, "Failed to fetch comment batch, stopping extraction");
+                if request_count == 1 && is_comments_disabled_message(&e.to_string()) {
+                    info!(video_id = %video_id, "Comments are disabled for this video");
+                    comments_disabled = true;
+                } else {
+                    warn!(error = %e, "Failed to fetch comment batch, stopping extraction");
+                }
                 break;
             }
         };
@@ -197,15 +241,12 @@ pub async fn fetch_tiktok_comments(
             has_more, next_cursor, batch_size
         );
 
-        if batch_size == 0 {
-            debug!("No comments in batch, reached end");
-            break;
-        }
-
+        let mut taken = 0;
         for comment in comments_array {
             if all_comments.len() >= limit {
                 break;
             }
+            taken += 1;
 
             // Extract comment fields
             let cid = comment
@@ -262,6 +303,7 @@ pub async fn fetch_tiktok_comments(
                 "is_pinned": false,  // TikTok API doesn't provide this easily
                 "is_creator": false, // TikTok API doesn't provide this easily
                 "parent_id": "root", // TikTok comments are flat in this API
+                "depth": 0,
                 "replies": [],       // Not fetching nested replies
             });
 
@@ -303,10 +345,25 @@ pub async fn fetch_tiktok_comments(
             }
         }
 
-        // Check if there are more comments
-        if !has_more {
-            debug!("API reports no more comments available");
-            break;
+        match next_page_action(
+            all_comments.len(),
+            limit,
+            batch_size,
+            taken,
+            has_more,
+            cursor,
+            next_cursor,
+        ) {
+            PageAction::Continue => {}
+            PageAction::Exhausted => {
+                debug!("No more comments available");
+                break;
+            }
+            PageAction::Truncated => {
+                info!("Reached comment limit of {}", limit);
+                truncated = true;
+                break;
+            }
         }
 
         // Update cursor for next batch
@@ -329,7 +386,6 @@ pub async fn fetch_tiktok_comments(
     }
 
     let extracted_count = deduplicated.len();
-    let limited = extracted_count >= limit;
 
     // Build output JSON in standard schema
     let output = serde_json::json!({
@@ -338,7 +394,9 @@ pub async fn fetch_tiktok_comments(
         "extracted_at": chrono::Utc::now().to_rfc3339(),
         "content_url": url,
         "content_id": video_id,
-        "limited": limited,
+        "limited": truncated,
+        "truncated": truncated,
+        "comments_disabled": comments_disabled,
         "limit_applied": limit,
         "stats": {
             "total_comments": extracted_count,
@@ -408,6 +466,56 @@ mod tests {
         assert_eq!(deduped[2]["id"], "3");
     }
 
+    #[test]
+    fn test_next_page_action_continues_while_more_available() {
+        assert_eq!(
+            next_page_action(50, 1000, 50, 50, true, 0, 50),
+            PageAction::Continue
+        );
+    }
+
+    #[test]
+    fn test_next_page_action_stops_when_exhausted() {
+        // Empty page
+        assert_eq!(
+            next_page_action(100, 1000, 0, 0, true, 50, 100),
+            PageAction::Exhausted
+        );
+        // API reports no more pages
+        assert_eq!(
+            next_page_action(120, 1000, 20, 20, false, 100, 120),
+            PageAction::Exhausted
+        );
+        // Cursor did not advance
+        assert_eq!(
+            next_page_action(100, 1000, 50, 50, true, 50, 50),
+            PageAction::Exhausted
+        );
+    }
+
+    #[test]
+    fn test_next_page_action_truncates_at_cap() {
+        // Cap hit mid-page: leftover comments in this page
+        assert_eq!(
+            next_page_action(75, 75, 50, 25, false, 50, 100),
+            PageAction::Truncated
+        );
+        // Cap hit exactly at page end, but the API has more pages
+        assert_eq!(
+            next_page_action(100, 100, 50, 50, true, 50, 100),
+            PageAction::Truncated
+        );
+    }
+
+    #[test]
+    fn test_next_page_action_cap_on_last_page_is_not_truncated() {
+        // Exactly `limit` comments exist: nothing was left behind
+        assert_eq!(
+            next_page_action(100, 100, 50, 50, false, 50, 100),
+            PageAction::Exhausted
+        );
+    }
+
     /// Integration test: Download comments from a real TikTok video.
     ///
     /// This test is ignored by default and only runs when explicitly requested with:
@@ -0,0 +1,105 @@
+//! Preserving prior captures when an archive is re-archived.
+//!
+//! Archives opt in individually (`keep_versions`) or through a versioned
+//! domain. Before such an archive is reset, its artifacts are copied to
+//! `{s3_prefix}{link_id}/versions/{n}/` and an `archive_versions` row records
+//! the capture, so the fresh capture can overwrite the original keys.
+
+use anyhow::{Context, Result};
+use tracing::info;
+
+use crate::db::{
+    archive_versioning_enabled, get_artifacts_for_archive, insert_archive_version,
+    next_archive_version, Archive, Database,
+};
+use crate::s3::S3Client;
+
+use super::retention::owned_s3_keys;
+
+/// S3 prefix holding version `version` of the archive stored under `link_prefix`.
+#[must_use]
+pub fn version_prefix(link_prefix: &str, version: i64) -> String {
+    format!("{link_prefix}versions/{version}/")
+}
+
+/// Where `key` is copied to for a version, or `None` if the archive doesn't own it.
+#[must_use]
+pub fn versioned_key(link_prefix: &str, version_prefix: &str, key: &str) -> Option<String> {
+    key.strip_prefix(link_prefix)
+        .map(|rest| format!("{version_prefix}{rest}"))
+}
+
+/// Preserve the archive's current capture if versioning is enabled for it.
+///
+/// Returns the new version number, or `None` when versioning is off or there
+/// is no completed capture to keep. Fails without recording a version if any
+/// artifact copy fails, so callers should not reset the archive in that case.
+pub async fn preserve_current_capture(
+    db: &Database,
+    s3: &S3Client,
+    s3_prefix: &str,
+    archive: &Archive,
+) -> Result<Option<i64>> {
+    if archive.status != "complete" || archive.s3_key_primary.is_none() {
+        return Ok(None);
+    }
+    if !archive_versioning_enabled(db.pool(), archive.id).await? {
+        return Ok(None);
+    }
+
+    let link_prefix = format!("{s3_prefix}{}/", archive.link_id);
+    let version = next_archive_version(db.pool(), archive.id).await?;
+    let prefix = version_prefix(&link_prefix, version);
+
+    let artifacts = get_artifacts_for_archive(db.pool(), archive.id).await?;
+    for key in owned_s3_keys(archive, &artifacts, &link_prefix) {
+        if let Some(dest) = versioned_key(&link_prefix, &prefix, &key) {
+            s3.copy_object(&key, &dest)
+                .await
+                .with_context(|| format!("Failed to copy {key} to {dest}"))?;
+        }
+    }
+
+    // Deduplicated primaries live under another archive's prefix and weren't copied
+    let primary = archive
+        .s3_key_primary
+        .as_deref()
+        .map(|key| versioned_key(&link_prefix, &prefix, key).unwrap_or_else(|| key.to_string()));
+
+    insert_archive_version(
+        db.pool(),
+        archive.id,
+        version,
+        archive.archived_at.as_deref(),
+        &prefix,
+        primary.as_deref(),
+        archive.content_title.as_deref(),
+        archive.content_text.as_deref(),
+    )
+    .await?;
+
+    info!(archive_id = archive.id, version, prefix = %prefix, "Preserved archive version");
+    Ok(Some(version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versioned_key() {
+        let link_prefix = "archives/42/";
+        let prefix = version_prefix(link_prefix, 3);
+        assert_eq!(prefix, "archives/42/versions/3/");
+
+        assert_eq!(
+            versioned_key(link_prefix, &prefix, "archives/42/media/page.html"),
+            Some("archives/42/versions/3/media/page.html".to_string())
+        );
+        // Deduplicated artifacts belong to another link
+        assert_eq!(
+            versioned_key(link_prefix, &prefix, "archives/7/media/video.mp4"),
+            None
+        );
+    }
+}
@@ -0,0 +1,256 @@
+//! Local speech-to-text fallback using Whisper.
+//!
+//! When a downloaded video has no platform subtitles, its audio can be
+//! transcribed locally with either the openai-whisper CLI or a whisper.cpp
+//! binary. Both are asked for WebVTT output, which is then turned into a
+//! transcript the same way as platform subtitles.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use tokio::process::Command;
+use tokio::sync::Semaphore;
+use tracing::{debug, info};
+
+use crate::config::Config;
+
+/// Semaphore to limit concurrent Whisper runs to 1 at a time.
+/// Transcription is CPU/GPU heavy; running several at once starves the workers.
+static WHISPER_SEMAPHORE: LazyLock<Semaphore> = LazyLock::new(|| Semaphore::new(1));
+
+/// Output of a successful Whisper run.
+#[derive(Debug, Clone)]
+pub struct WhisperTranscript {
+    /// Path to the generated WebVTT file.
+    pub vtt_path: PathBuf,
+    /// Language code, either configured or detected by Whisper.
+    pub language: Option<String>,
+}
+
+/// Check if the configured binary is whisper.cpp rather than openai-whisper.
+fn is_whisper_cpp(path: &str) -> bool {
+    let name = Path::new(path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(path)
+        .to_lowercase();
+    name.starts_with("whisper-cli")
+        || name.starts_with("whisper-cpp")
+        || name.starts_with("whisper.cpp")
+        || name == "main"
+}
+
+/// Map a language name printed by openai-whisper to its ISO 639-1 code.
+fn language_code_from_name(name: &str) -> String {
+    let name = name.trim().to_lowercase();
+    let code = match name.as_str() {
+        "english" => "en",
+        "spanish" => "es",
+        "french" => "fr",
+        "german" => "de",
+        "italian" => "it",
+        "portuguese" => "pt",
+        "dutch" => "nl",
+        "russian" => "ru",
+        "ukrainian" => "uk",
+        "polish" => "pl",
+        "japanese" => "ja",
+        "korean" => "ko",
+        "chinese" => "zh",
+        "arabic" => "ar",
+        "hindi" => "hi",
+        "turkish" => "tr",
+        "swedish" => "sv",
+        _ => return name,
+    };
+    code.to_string()
+}
+
+/// Extract the detected language from Whisper's console output.
+///
+/// - openai-whisper: `Detected language: English`
+/// - whisper.cpp: `auto-detected language: en (p = 0.97)`
+fn parse_detected_language(output: &str) -> Option<String> {
+    for line in output.lines() {
+        let lower = line.to_lowercase();
+        if let Some(idx) = lower.find("auto-detected language:") {
+            let rest = &line[idx + "auto-detected language:".len()..];
+            return rest.split_whitespace().next().map(str::to_lowercase);
+        }
+        if let Some(idx) = lower.find("detected language:") {
+            let rest = line[idx + "detected language:".len()..].trim();
+            if !rest.is_empty() {
+                return Some(language_code_from_name(rest));
+            }
+        }
+    }
+    None
+}
+
+/// Run a prepared command with the Whisper timeout.
+///
+/// Returns `Ok(None)` if the binary does not exist.
+async fn run_command(
+    mut cmd: Command,
+    timeout: Duration,
+    what: &str,
+) -> Result<Option<std::process::Output>> {
+    cmd.stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to spawn {what}")),
+    };
+
+    let output = tokio::time::timeout(timeout, child.wait_with_output())
+        .await
+        .with_context(|| format!("{what} timed out after {}s", timeout.as_secs()))?
+        .with_context(|| format!("Failed to wait for {what}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("{what} failed with status {}: {stderr}", output.status);
+    }
+
+    Ok(Some(output))
+}
+
+/// Transcribe a downloaded media file with the configured Whisper binary.
+///
+/// Returns `Ok(None)` when the Whisper binary (or ffmpeg, for whisper.cpp)
+/// is not installed, so callers can skip transcription gracefully.
+///
+/// # Errors
+///
+/// Returns an error if Whisper fails, times out, or produces no VTT output.
+pub async fn transcribe(
+    input: &Path,
+    work_dir: &Path,
+    config: &Config,
+) -> Result<Option<WhisperTranscript>> {
+    let _permit = WHISPER_SEMAPHORE
+        .acquire()
+        .await
+        .context("Failed to acquire whisper semaphore")?;
+
+    let output_dir = work_dir.join("whisper");
+    tokio::fs::create_dir_all(&output_dir)
+        .await
+        .context("Failed to create whisper output directory")?;
+
+    let timeout = Duration::from_secs(config.whisper_timeout_secs);
+    let language = config.whisper_language.as_deref();
+
+    info!(input = %input.display(), model = %config.whisper_model, "Transcribing with Whisper");
+
+    let output = if is_whisper_cpp(&config.whisper_path) {
+        // whisper.cpp only reads 16 kHz mono WAV, so extract the audio first.
+        let wav_path = output_dir.join("audio.wav");
+        let mut ffmpeg = Command::new("ffmpeg");
+        ffmpeg
+            .arg("-y")
+            .arg("-i")
+            .arg(input)
+            .args(["-vn", "-ar", "16000", "-ac", "1", "-c:a", "pcm_s16le"])
+            .arg(&wav_path);
+        if run_command(ffmpeg, timeout, "ffmpeg").await?.is_none() {
+            info!("ffmpeg not found, skipping Whisper transcription");
+            return Ok(None);
+        }
+
+        let mut cmd = Command::new(&config.whisper_path);
+        cmd.arg("-m")
+            .arg(&config.whisper_model)
+            .arg("-f")
+            .arg(&wav_path)
+            .arg("-ovtt")
+            .arg("-of")
+            .arg(output_dir.join("transcript"))
+            .arg("-l")
+            .arg(language.unwrap_or("auto"));
+        run_command(cmd, timeout, "whisper.cpp").await?
+    } else {
+        let mut cmd = Command::new(&config.whisper_path);
+        cmd.arg(input)
+            .arg("--model")
+            .arg(&config.whisper_model)
+            .arg("--output_format")
+            .arg("vtt")
+            .arg("--output_dir")
+            .arg(&output_dir);
+        if let Some(lang) = language {
+            cmd.arg("--language").arg(lang);
+        }
+        run_command(cmd, timeout, "whisper").await?
+    };
+
+    let Some(output) = output else {
+        info!(path = %config.whisper_path, "Whisper binary not found, skipping transcription");
+        return Ok(None);
+    };
+
+    let mut vtt_path = None;
+    let mut entries = tokio::fs::read_dir(&output_dir)
+        .await
+        .context("Failed to read whisper output directory")?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) == Some("vtt") {
+            vtt_path = Some(path);
+            break;
+        }
+    }
+    let vtt_path = vtt_path.context("Whisper produced no VTT output")?;
+
+    let detected = parse_detected_language(&format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    ));
+    let language = language.map(ToString::to_string).or(detected);
+
+    debug!(vtt = %vtt_path.display(), language = ?language, "Whisper transcription complete");
+
+    Ok(Some(WhisperTranscript { vtt_path, language }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_whisper_cpp() {
+        assert!(is_whisper_cpp("/usr/local/bin/whisper-cli"));
+        assert!(is_whisper_cpp("whisper-cpp"));
+        assert!(is_whisper_cpp("/opt/whisper.cpp/main"));
+        assert!(!is_whisper_cpp("whisper"));
+        assert!(!is_whisper_cpp("/home/user/.local/bin/whisper"));
+    }
+
+    #[test]
+    fn test_parse_detected_language_openai() {
+        let output = "Detecting language using up to the first 30 seconds.\nDetected language: English\n[00:00.000 --> 00:02.000]  Hello";
+        assert_eq!(parse_detected_language(output), Some("en".to_string()));
+    }
+
+    #[test]
+    fn test_parse_detected_language_whisper_cpp() {
+        let output = "whisper_full_with_state: auto-detected language: de (p = 0.981)";
+        assert_eq!(parse_detected_language(output), Some("de".to_string()));
+    }
+
+    #[test]
+    fn test_parse_detected_language_unknown_name_passthrough() {
+        assert_eq!(
+            parse_detected_language("Detected language: Welsh"),
+            Some("welsh".to_string())
+        );
+        assert_eq!(parse_detected_language("no language line"), None);
+    }
+}
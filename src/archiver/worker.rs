@@ -8,30 +8,76 @@ use tokio::sync::Semaphore;
 use tracing::{debug, error, info, warn};
 use url::Url;
 
+use super::error::{find_archive_error, ArchiveError, ArchiveErrorKind, FailureClass, SkipReason};
+use super::live::find_live_not_ready;
 use super::monolith::create_complete_html;
+use super::playlist::PlaylistInfo;
 use super::rate_limiter::DomainRateLimiter;
 use super::screenshot::ScreenshotService;
+use crate::archive_today::ArchiveTodayClient;
 use crate::config::Config;
 use crate::db::{
-    create_archive_job, find_artifact_by_perceptual_hash, find_video_file, get_archive,
-    get_failed_archives_for_retry, get_link, get_or_create_video_file, get_pending_archives,
-    has_artifact_kind, insert_artifact, insert_artifact_with_hash, insert_artifact_with_metadata,
-    insert_artifact_with_video_file, is_domain_excluded, mark_og_extraction_attempted,
-    reset_archive_for_retry, reset_stuck_processing_archives, reset_todays_failed_archives,
-    set_archive_auth_required, set_archive_complete, set_archive_failed, set_archive_ipfs_cid,
-    set_archive_nsfw, set_archive_processing, set_archive_skipped, set_job_completed,
-    set_job_failed, set_job_running, set_job_skipped, update_archive_og_metadata,
-    update_link_final_url, update_link_last_archived, update_video_file_metadata_key,
-    ArchiveJobType, ArtifactKind, Database, VideoFile,
+    clear_ipfs_pin_pending, create_archive_job, create_pending_archive, defer_archive,
+    external_services_for, find_artifact_by_perceptual_hash, find_video_file, get_archive,
+    get_archive_by_link_id, get_artifacts_for_archive, get_failed_archives_for_retry, get_link,
+    get_or_create_link, get_or_create_video_file, get_pending_archives, get_pending_ipfs_pins,
+    get_previous_primary, has_artifact_kind, insert_artifact, insert_artifact_with_encoding,
+    insert_artifact_with_hash, insert_artifact_with_metadata, insert_artifact_with_video_file,
+    insert_playlist_item, is_domain_excluded, is_post_snapshot_archive, mark_ipfs_pin_pending,
+    mark_og_extraction_attempted, reset_archive_for_retry, reset_dry_run_archives,
+    reset_stuck_processing_archives, reset_todays_failed_archives, set_archive_archive_today_url,
+    set_archive_auth_required, set_archive_complete, set_archive_dry_run, set_archive_error_kind,
+    set_archive_failed, set_archive_ipfs_cid, set_archive_nsfw, set_archive_nsfw_auto,
+    set_archive_processing, set_archive_quoted_link, set_archive_redirect_chain,
+    set_archive_reply_link, set_archive_skipped, set_archive_wayback_url, set_artifact_placeholder,
+    set_job_completed, set_job_failed, set_job_running, set_job_skipped,
+    update_archive_og_metadata, update_link_final_url, update_link_last_archived,
+    update_video_file_metadata_key, ArchiveJobType, ArtifactKind, Database, ExternalServices, Link,
+    NewLink, VideoFile,
 };
 use crate::dedup;
 use crate::handlers::youtube::extract_video_id;
-use crate::handlers::HANDLERS;
-use crate::ipfs::IpfsClient;
+use crate::handlers::{normalize_link_url, SiteHandler, ARTICLE_FILE, HANDLERS};
+use crate::ipfs::{IpfsClient, PinReadiness};
 use crate::og_extractor;
-use crate::s3::S3Client;
+use crate::placeholder;
+use crate::s3::{precompress, S3Client, PRECOMPRESSED_ENCODING};
+use crate::wayback::WaybackClient;
+use crate::webhook::chat::ChatNotifier;
+use crate::webhook::{WebhookEvent, WebhookNotifier, WebhookPayload};
 
-const MAX_RETRIES: i32 = 3;
+/// Upload complete.html zstd-compressed so the S3 proxy can serve it as stored.
+///
+/// Falls back to the plain file if compression fails. Returns the stored size
+/// and the `Content-Encoding` the object was stored with, if any.
+async fn upload_complete_html(
+    s3: &S3Client,
+    path: &Path,
+    s3_key: &str,
+    archive_id: i64,
+) -> Result<(Option<i64>, Option<&'static str>)> {
+    let html = tokio::fs::read(path)
+        .await
+        .context("Failed to read complete.html")?;
+    let compressed = tokio::task::spawn_blocking(move || precompress(&html))
+        .await
+        .context("complete.html compression task panicked")
+        .and_then(std::convert::identity);
+
+    match compressed {
+        Ok(data) => {
+            s3.upload_encoded_bytes(&data, s3_key, "text/html", PRECOMPRESSED_ENCODING)
+                .await?;
+            Ok((i64::try_from(data.len()).ok(), Some(PRECOMPRESSED_ENCODING)))
+        }
+        Err(e) => {
+            warn!(archive_id, error = %e, "Failed to compress complete.html, storing it uncompressed");
+            s3.upload_file(path, s3_key, Some(archive_id)).await?;
+            let size_bytes = tokio::fs::metadata(path).await.ok().map(|m| m.len() as i64);
+            Ok((size_bytes, None))
+        }
+    }
+}
 
 /// Check if domain is in comments-supported platforms
 pub fn is_comments_supported_platform(domain: &str, config: &Config) -> bool {
@@ -44,6 +90,7 @@ pub fn is_comments_supported_platform(domain: &str, config: &Config) -> bool {
         ),
         ("twitter", vec!["x.com", "twitter.com"]),
         ("instagram", vec!["instagram.com", "instagr.am"]),
+        ("reddit", vec!["reddit.com"]),
     ];
 
     // Check if domain matches any supported platform
@@ -69,20 +116,32 @@ pub fn extract_platform_name(domain: &str) -> &'static str {
         "twitter"
     } else if domain.contains("instagram.com") {
         "instagram"
+    } else if domain.contains("reddit.com") {
+        "reddit"
     } else {
         "unknown"
     }
 }
 
+/// Clients for the external services archives can be sent to.
+#[derive(Clone)]
+struct ExternalClients {
+    ipfs: IpfsClient,
+    wayback: Arc<WaybackClient>,
+    archive_today: Arc<ArchiveTodayClient>,
+}
+
 /// Archive worker pool.
 pub struct ArchiveWorker {
     config: Config,
     db: Database,
     s3: S3Client,
-    ipfs: IpfsClient,
+    external: ExternalClients,
     screenshot: Arc<ScreenshotService>,
     semaphore: Arc<Semaphore>,
     domain_limiter: Arc<DomainRateLimiter>,
+    webhooks: WebhookNotifier,
+    chat: ChatNotifier,
 }
 
 impl ArchiveWorker {
@@ -112,22 +171,52 @@ impl ArchiveWorker {
         if monolith_config.enabled {
             info!("Monolith self-contained HTML enabled");
         }
+        let webhooks = WebhookNotifier::new(&config);
+        if config.webhook_url.is_some() {
+            info!(events = ?config.webhook_events, "Webhook notifications enabled");
+        }
+        let chat = ChatNotifier::new(&config);
+        if chat.is_enabled() {
+            info!(format = ?config.chat_webhook_format, "Chat notifications enabled");
+        }
+        if config.archive_dry_run {
+            warn!("Archive dry run enabled: archives are routed and logged, nothing is downloaded, uploaded or submitted");
+        }
+        // Built even when globally disabled: external service rules can enable
+        // Wayback and Archive.today for specific domains or content types.
+        let external = ExternalClients {
+            ipfs,
+            wayback: Arc::new(WaybackClient::new(config.wayback_rate_limit_per_min)),
+            archive_today: Arc::new(ArchiveTodayClient::new(
+                config.archive_today_rate_limit_per_min,
+                &config.archive_today_base,
+            )),
+        };
 
         Self {
             config,
             db,
             s3,
-            ipfs,
+            external,
             screenshot,
             semaphore,
             domain_limiter,
+            webhooks,
+            chat,
         }
     }
 
+    /// Per-domain limiter shared with the comment extraction worker.
+    #[must_use]
+    pub fn domain_limiter(&self) -> Arc<DomainRateLimiter> {
+        Arc::clone(&self.domain_limiter)
+    }
+
     /// Recover from a previous unclean shutdown.
     ///
     /// This resets archives that were stuck in "processing" state (interrupted
-    /// mid-processing) and gives today's failed archives another chance.
+    /// mid-processing), requeues archives an earlier dry run skipped when dry
+    /// run is now off, and gives today's failed archives another chance.
     pub async fn recover_on_startup(&self) -> Result<()> {
         // Reset archives that were mid-processing when we shut down
         let stuck = reset_stuck_processing_archives(self.db.pool()).await?;
@@ -135,8 +224,16 @@ impl ArchiveWorker {
             info!(count = stuck, "Reset stuck processing archives to pending");
         }
 
+        if !self.config.archive_dry_run {
+            let dry_run = reset_dry_run_archives(self.db.pool()).await?;
+            if dry_run > 0 {
+                info!(count = dry_run, "Requeued archives skipped by a dry run");
+            }
+        }
+
         // Give today's failed archives another chance
-        let failed = reset_todays_failed_archives(self.db.pool(), MAX_RETRIES).await?;
+        let failed =
+            reset_todays_failed_archives(self.db.pool(), self.config.archive_max_retries).await?;
         if failed > 0 {
             info!(
                 count = failed,
@@ -511,10 +608,12 @@ impl ArchiveWorker {
             let permit = self.semaphore.clone().acquire_owned().await?;
             let db = self.db.clone();
             let s3 = self.s3.clone();
-            let ipfs = self.ipfs.clone();
+            let external = self.external.clone();
             let screenshot = Arc::clone(&self.screenshot);
             let config = self.config.clone();
             let domain_limiter = Arc::clone(&self.domain_limiter);
+            let webhooks = self.webhooks.clone();
+            let chat = self.chat.clone();
 
             let handle = tokio::spawn(async move {
                 let _global_permit = permit;
@@ -524,9 +623,11 @@ impl ArchiveWorker {
                 process_archive(
                     &db,
                     &s3,
-                    &ipfs,
+                    &external,
                     &screenshot,
                     &config,
+                    &webhooks,
+                    &chat,
                     archive.id,
                     archive.link_id,
                 )
@@ -551,12 +652,15 @@ impl ArchiveWorker {
     async fn process_failed(&self) -> Result<()> {
         // The query already filters by retry_count and next_retry_at,
         // so archives returned here are ready for retry
-        let failed = get_failed_archives_for_retry(self.db.pool(), 10, MAX_RETRIES).await?;
+        let max_retries = self.config.archive_max_retries;
+        let failed = get_failed_archives_for_retry(self.db.pool(), 10, max_retries).await?;
 
         for archive in failed {
-            if archive.retry_count >= MAX_RETRIES {
+            if archive.retry_count >= max_retries {
                 // Mark as permanently skipped (shouldn't happen due to query filter, but be safe)
-                set_archive_skipped(self.db.pool(), archive.id).await?;
+                set_archive_skipped(self.db.pool(), archive.id, SkipReason::MaxRetries.as_str())
+                    .await?;
+                remove_archive_work_dir(&self.config, archive.id).await;
                 warn!(
                     archive_id = archive.id,
                     "Archive marked as skipped after max retries"
@@ -580,9 +684,11 @@ impl ArchiveWorker {
 async fn process_archive(
     db: &Database,
     s3: &S3Client,
-    ipfs: &IpfsClient,
+    external: &ExternalClients,
     screenshot: &ScreenshotService,
     config: &Config,
+    webhooks: &WebhookNotifier,
+    chat: &ChatNotifier,
     archive_id: i64,
     link_id: i64,
 ) {
@@ -593,43 +699,351 @@ async fn process_archive(
         Err(_) => "unknown".to_string(),
     };
 
-    if let Err(e) =
-        process_archive_inner(db, s3, ipfs, screenshot, config, archive_id, link_id).await
-    {
+    if let Err(e) = run_archive(db, s3, external, screenshot, config, archive_id, link_id).await {
         let error_msg = format!("{e:#}");
-        error!(archive_id, domain = %domain, "Archive failed: {error_msg}");
 
-        // Check if this is an authentication error that can be retried with cookies
-        if is_auth_required_failure(&error_msg) {
-            warn!(
+        // Live content isn't a failure until the deferral window runs out
+        if let Some(live) = find_live_not_ready(&e) {
+            let window_secs = (config.live_defer_max_hours * 3600) as i64;
+            match defer_archive(
+                db.pool(),
                 archive_id,
-                domain = %domain,
-                "Authentication required, marking as auth_required (can retry with cookies)"
-            );
-            if let Err(e2) = set_archive_auth_required(db.pool(), archive_id, &error_msg).await {
-                error!(archive_id, domain = %domain, "Failed to mark archive as auth_required: {e2:#}");
+                &live.message,
+                live.retry_in_secs,
+                window_secs,
+            )
+            .await
+            {
+                Ok(true) => {
+                    info!(
+                        archive_id,
+                        domain = %domain,
+                        status = ?live.status,
+                        retry_in_secs = live.retry_in_secs,
+                        "Live content not ready, archive deferred"
+                    );
+                    return;
+                }
+                Ok(false) => {
+                    warn!(
+                        archive_id,
+                        domain = %domain,
+                        "Live content still not ready after LIVE_DEFER_MAX_HOURS, failing archive"
+                    );
+                }
+                Err(e2) => {
+                    error!(archive_id, domain = %domain, "Failed to defer archive: {e2:#}");
+                }
+            }
+        }
+
+        error!(archive_id, domain = %domain, "Archive failed: {error_msg}");
+
+        let http_status = match get_archive(db.pool(), archive_id).await {
+            Ok(Some(archive)) => archive.http_status_code,
+            _ => None,
+        };
+        let has_cookies =
+            config.cookies_file_path.is_some() || config.yt_dlp_cookies_from_browser.is_some();
+
+        let (error_kind, failure_class) = classify_error(&e, http_status, has_cookies);
+        match failure_class {
+            // Authentication error that can be retried with cookies
+            FailureClass::AuthRequired => {
+                warn!(
+                    archive_id,
+                    domain = %domain,
+                    "Authentication required, marking as auth_required (can retry with cookies)"
+                );
+                if let Err(e2) = set_archive_auth_required(db.pool(), archive_id, &error_msg).await
+                {
+                    error!(archive_id, domain = %domain, "Failed to mark archive as auth_required: {e2:#}");
+                }
+            }
+            // Permanent failure (404, deleted, geo-blocked) that shouldn't be retried
+            FailureClass::Permanent(reason) => {
+                warn!(
+                    archive_id,
+                    domain = %domain,
+                    reason,
+                    "Permanent failure detected, marking as skipped (no retry)"
+                );
+                // Store the error message first; it also sets status = 'failed'
+                if let Err(e2) = set_archive_failed(
+                    db.pool(),
+                    archive_id,
+                    &error_msg,
+                    config.archive_max_retries,
+                )
+                .await
+                {
+                    error!(archive_id, domain = %domain, "Failed to store error message: {e2:#}");
+                }
+                let skip_reason = SkipReason::for_permanent_failure(error_kind, reason);
+                if let Err(e2) =
+                    set_archive_skipped(db.pool(), archive_id, skip_reason.as_str()).await
+                {
+                    error!(archive_id, domain = %domain, "Failed to mark archive as skipped: {e2:#}");
+                }
+                remove_archive_work_dir(config, archive_id).await;
+            }
+            // Transient failure - mark as failed for retry with backoff
+            FailureClass::Transient(reason) => {
+                debug!(archive_id, domain = %domain, reason, "Transient failure, will retry");
+                if let Err(e2) = set_archive_failed(
+                    db.pool(),
+                    archive_id,
+                    &error_msg,
+                    config.archive_max_retries,
+                )
+                .await
+                {
+                    error!(archive_id, domain = %domain, "Failed to mark archive as failed: {e2:#}");
+                }
+                // Out of retries: nothing will resume the partial download
+                if matches!(
+                    get_archive(db.pool(), archive_id).await,
+                    Ok(Some(a)) if a.retry_count >= config.archive_max_retries
+                ) {
+                    remove_archive_work_dir(config, archive_id).await;
+                }
             }
         }
-        // Check if this is a permanent failure (404, deleted) that shouldn't be retried
-        else if is_permanent_failure(&error_msg) {
+        if let Err(e2) = set_archive_error_kind(db.pool(), archive_id, error_kind.as_str()).await {
+            error!(archive_id, domain = %domain, "Failed to store error kind: {e2:#}");
+        }
+        notify_archive_event(db, webhooks, chat, WebhookEvent::ArchiveFailed, archive_id).await;
+    } else {
+        notify_archive_event(
+            db,
+            webhooks,
+            chat,
+            WebhookEvent::ArchiveComplete,
+            archive_id,
+        )
+        .await;
+    }
+}
+
+/// Queue webhook and chat notifications for an archive that just finished processing.
+///
+/// Completion is only reported if the archive actually reached `complete`
+/// (archives skipped by prevention signals also finish without error). Chat
+/// notifications are only sent for completions.
+async fn notify_archive_event(
+    db: &Database,
+    webhooks: &WebhookNotifier,
+    chat: &ChatNotifier,
+    event: WebhookEvent,
+    archive_id: i64,
+) {
+    let send_webhook = webhooks.is_subscribed(event);
+    let send_chat = event == WebhookEvent::ArchiveComplete && chat.is_enabled();
+    if !send_webhook && !send_chat {
+        return;
+    }
+    let archive = match get_archive(db.pool(), archive_id).await {
+        Ok(Some(archive)) => archive,
+        Ok(None) => return,
+        Err(e) => {
             warn!(
                 archive_id,
-                domain = %domain,
-                "Permanent failure detected, marking as skipped (no retry)"
+                "Failed to load archive for notifications: {e:#}"
+            );
+            return;
+        }
+    };
+    if event == WebhookEvent::ArchiveComplete && archive.status != "complete" {
+        return;
+    }
+    let link = get_link(db.pool(), archive.link_id).await.ok().flatten();
+
+    if send_webhook {
+        let url = link.as_ref().map_or("", |l| l.original_url.as_str());
+        let artifacts = get_artifacts_for_archive(db.pool(), archive_id)
+            .await
+            .unwrap_or_default();
+        webhooks.notify(WebhookPayload::from_archive(
+            event, &archive, url, &artifacts,
+        ));
+    }
+    if send_chat {
+        if let Some(link) = &link {
+            chat.notify_archive(&archive, link);
+        }
+    }
+}
+
+/// Classify an archive failure from its error text and HTTP status code.
+///
+/// The HTTP status recorded for the archive takes precedence over the error
+/// text. Geo-blocks are only treated as permanent when no cookies are
+/// configured. Anything unrecognized is assumed to be transient.
+#[must_use]
+pub fn classify_failure(
+    error_msg: &str,
+    http_status: Option<i32>,
+    has_cookies: bool,
+) -> (ArchiveErrorKind, FailureClass) {
+    let (kind, class) = classify_failure_inner(error_msg, http_status, has_cookies);
+    // yt-dlp has no extractor for the URL, whatever else the message says
+    if error_msg.to_lowercase().contains("unsupported url") {
+        return (ArchiveErrorKind::Unsupported, class);
+    }
+    (kind, class)
+}
+
+/// [`classify_failure`] without the unsupported-URL override.
+fn classify_failure_inner(
+    error_msg: &str,
+    http_status: Option<i32>,
+    has_cookies: bool,
+) -> (ArchiveErrorKind, FailureClass) {
+    use ArchiveErrorKind as Kind;
+
+    // Opt-out honored under RESPECT_NOARCHIVE; retrying would hit the same directive
+    if error_msg.contains(crate::handlers::NOARCHIVE_REQUESTED) {
+        return (
+            Kind::Unsupported,
+            FailureClass::Permanent(crate::handlers::NOARCHIVE_REQUESTED),
+        );
+    }
+    // Over MAX_ARTIFACT_BYTES; the same download would be skipped again
+    if error_msg.contains(super::size_cap::ARTIFACT_TOO_LARGE) {
+        return (
+            Kind::TooLarge,
+            FailureClass::Permanent("Exceeds MAX_ARTIFACT_BYTES"),
+        );
+    }
+    // Soft 404 / paywall page; the URL has already gone to Wayback/Archive.today
+    if error_msg.contains(crate::handlers::SOFT_UNAVAILABLE) {
+        return (
+            Kind::NotFound,
+            FailureClass::Permanent("Content unavailable (soft 404 or paywall)"),
+        );
+    }
+
+    match http_status {
+        Some(404) => {
+            return (
+                Kind::NotFound,
+                FailureClass::Permanent("Not found (HTTP 404)"),
+            )
+        }
+        Some(410) => return (Kind::NotFound, FailureClass::Permanent("Gone (HTTP 410)")),
+        Some(429) => {
+            return (
+                Kind::RateLimited,
+                FailureClass::Transient("Rate limited (HTTP 429)"),
+            )
+        }
+        Some(code) if (500..600).contains(&code) => {
+            return (
+                Kind::Other,
+                FailureClass::Transient("Server error (HTTP 5xx)"),
             );
-            if let Err(e2) = set_archive_skipped(db.pool(), archive_id).await {
-                error!(archive_id, domain = %domain, "Failed to mark archive as skipped: {e2:#}");
-            }
-            // Also store the error message
-            if let Err(e2) = set_archive_failed(db.pool(), archive_id, &error_msg).await {
-                error!(archive_id, domain = %domain, "Failed to store error message: {e2:#}");
-            }
         }
-        // Transient failure - mark as failed for retry with backoff
-        else if let Err(e2) = set_archive_failed(db.pool(), archive_id, &error_msg).await {
-            error!(archive_id, domain = %domain, "Failed to mark archive as failed: {e2:#}");
+        _ => {}
+    }
+
+    let error_lower = error_msg.to_lowercase();
+
+    if is_geo_blocked_failure(&error_lower) {
+        return if has_cookies {
+            (Kind::Other, FailureClass::Transient("Geo-restricted"))
+        } else {
+            (Kind::Other, FailureClass::Permanent("Geo-restricted"))
+        };
+    }
+
+    if is_auth_required_failure(error_msg) {
+        return (Kind::AuthRequired, FailureClass::AuthRequired);
+    }
+
+    if is_permanent_failure(error_msg) {
+        let (kind, reason) = if error_lower.contains("410") || error_lower.contains("gone") {
+            (Kind::NotFound, "Gone")
+        } else if error_lower.contains("404") || error_lower.contains("not found") {
+            (Kind::NotFound, "Not found")
+        } else if error_lower.contains("deleted") || error_lower.contains("removed") {
+            (Kind::NotFound, "Content removed")
+        } else if error_lower.contains("unavailable") || error_lower.contains("no longer available")
+        {
+            (Kind::NotFound, "Content unavailable")
+        } else {
+            (Kind::Other, "Permanently blocked")
+        };
+        return (kind, FailureClass::Permanent(reason));
+    }
+
+    let (kind, reason) = if error_lower.contains("429")
+        || error_lower.contains("rate limit")
+        || error_lower.contains("too many requests")
+    {
+        (Kind::RateLimited, "Rate limited")
+    } else if error_lower.contains("timed out") || error_lower.contains("timeout") {
+        (Kind::Timeout, "Timeout")
+    } else if [
+        "500",
+        "502",
+        "503",
+        "504",
+        "bad gateway",
+        "service unavailable",
+    ]
+    .iter()
+    .any(|needle| error_lower.contains(needle))
+    {
+        (Kind::Other, "Server error")
+    } else if error_lower.contains("connection") || error_lower.contains("dns") {
+        (Kind::Other, "Network error")
+    } else {
+        (Kind::Other, "Unknown error")
+    };
+    (kind, FailureClass::Transient(reason))
+}
+
+/// Categorize a failed archive and decide how it is retried.
+///
+/// A typed [`ArchiveError`] decides both, then a timed-out request anywhere in
+/// the chain; otherwise the error text is classified as before.
+#[must_use]
+pub fn classify_error(
+    err: &anyhow::Error,
+    http_status: Option<i32>,
+    has_cookies: bool,
+) -> (ArchiveErrorKind, FailureClass) {
+    let typed = find_archive_error(err).map(ArchiveError::kind).or_else(|| {
+        err.chain()
+            .filter_map(|cause| cause.downcast_ref::<reqwest::Error>())
+            .any(reqwest::Error::is_timeout)
+            .then_some(ArchiveErrorKind::Timeout)
+    });
+    if let Some(kind) = typed {
+        if let Some(class) = kind.failure_class() {
+            return (kind, class);
         }
     }
+
+    let (kind, class) = classify_failure(&format!("{err:#}"), http_status, has_cookies);
+    (typed.unwrap_or(kind), class)
+}
+
+/// The category an error would be stored under, ignoring HTTP status and cookies.
+#[must_use]
+pub fn error_kind(err: &anyhow::Error) -> ArchiveErrorKind {
+    classify_error(err, None, false).0
+}
+
+/// Check if an (already lowercased) error indicates a geo-restriction.
+fn is_geo_blocked_failure(error_lower: &str) -> bool {
+    error_lower.contains("not available in your country")
+        || error_lower.contains("not made this video available in your country")
+        || error_lower.contains("geo restricted")
+        || error_lower.contains("geo-restricted")
+        || error_lower.contains("georestricted")
+        || error_lower.contains("geo-blocked")
+        || error_lower.contains("blocked in your country")
 }
 
 /// Check if an error indicates authentication is required.
@@ -678,7 +1092,7 @@ fn is_auth_required_failure(error_msg: &str) -> bool {
 
 /// Check if an error indicates a permanent failure that shouldn't be retried.
 ///
-/// Returns true for HTTP 404 (Not Found) and content that is deleted or removed.
+/// Returns true for HTTP 404/410 and content that is deleted, removed or unavailable.
 /// These errors indicate the content is truly unavailable and retrying will not help.
 ///
 /// Note: Authentication errors (401/403) are NOT considered permanent failures,
@@ -688,9 +1102,14 @@ fn is_permanent_failure(error_msg: &str) -> bool {
 
     // Only true permanent failures: content that no longer exists
     error_lower.contains("404")
+        || error_lower.contains("410 gone")
+        || error_lower.contains("http error 410")
         || error_lower.contains("not found")
         || error_lower.contains("deleted")
         || error_lower.contains("removed")
+        || error_lower.contains("content unavailable")
+        || error_lower.contains("video unavailable")
+        || error_lower.contains("no longer available")
         // Permanent blocks (not auth-related)
         || error_lower.contains("permanently")
 }
@@ -702,8 +1121,13 @@ fn is_permanent_failure(error_msg: &str) -> bool {
 /// 2. If the URL has X-No-Archive HTTP header or x-no-archive meta tag
 /// 3. If allowArchive=1 query parameter is present (bypass signal)
 ///
-/// Returns true if the URL should be skipped (i.e., it has archive prevention signals).
-async fn should_skip_due_to_archive_prevention(db: &Database, url: &str) -> Result<bool> {
+/// Returns the reason to skip the URL if it has archive prevention signals.
+async fn should_skip_due_to_archive_prevention(
+    db: &Database,
+    config: &Config,
+    url: &str,
+    allow_excluded_domain: bool,
+) -> Result<Option<SkipReason>> {
     // Parse URL to extract domain and query params
     let parsed = Url::parse(url).context("Failed to parse URL")?;
     let domain = parsed
@@ -718,18 +1142,18 @@ async fn should_skip_due_to_archive_prevention(db: &Database, url: &str) -> Resu
 
     if has_allow_archive {
         debug!(url = %url, "URL has allowArchive=1, will archive despite any signals");
-        return Ok(false);
+        return Ok(None);
     }
 
-    // Check if domain is in excluded list
-    if is_domain_excluded(db.pool(), &domain).await? {
+    // Check if domain is in excluded list (post snapshots opt out of the forum's self-exclusion)
+    if !allow_excluded_domain && is_domain_excluded(db.pool(), &domain).await? {
         warn!(url = %url, domain = %domain, "Domain is in excluded list, skipping archive");
-        return Ok(true);
+        return Ok(Some(SkipReason::ExcludedDomain));
     }
 
     // Fetch the URL and check for archive prevention headers/meta tags
     // Use a short timeout for this check to avoid blocking the worker
-    match fetch_url_for_signals(url).await {
+    match fetch_url_for_signals(config, url).await {
         Ok(signals) => {
             if signals.has_no_archive_header || signals.has_no_archive_meta {
                 warn!(
@@ -738,7 +1162,7 @@ async fn should_skip_due_to_archive_prevention(db: &Database, url: &str) -> Resu
                     has_meta = signals.has_no_archive_meta,
                     "URL has archive prevention signals, skipping archive"
                 );
-                return Ok(true);
+                return Ok(Some(SkipReason::NoArchive));
             }
         }
         Err(e) => {
@@ -748,7 +1172,7 @@ async fn should_skip_due_to_archive_prevention(db: &Database, url: &str) -> Resu
         }
     }
 
-    Ok(false)
+    Ok(None)
 }
 
 /// Archive prevention signals found on a page.
@@ -763,13 +1187,12 @@ struct ArchivePreventionSignals {
 /// Looks for:
 /// - X-No-Archive HTTP header
 /// - x-no-archive or robots: noarchive meta tags
-async fn fetch_url_for_signals(url: &str) -> Result<ArchivePreventionSignals> {
-    let client = reqwest::Client::new();
-
+async fn fetch_url_for_signals(config: &Config, url: &str) -> Result<ArchivePreventionSignals> {
     // Use a short timeout for this probe
+    let client = crate::handlers::archival_client(config, Duration::from_secs(10))?;
+
     let response = client
         .head(url)
-        .timeout(Duration::from_secs(10))
         .send()
         .await
         .context("Failed to fetch URL for signals check")?;
@@ -784,12 +1207,7 @@ async fn fetch_url_for_signals(url: &str) -> Result<ArchivePreventionSignals> {
     // For meta tag check, we need to GET the page (HEAD won't include body)
     // Only do this if we didn't find a header
     if !signals.has_no_archive_header {
-        if let Ok(full_response) = client
-            .get(url)
-            .timeout(Duration::from_secs(10))
-            .send()
-            .await
-        {
+        if let Ok(full_response) = client.get(url).send().await {
             if let Ok(html_text) = full_response.text().await {
                 // Parse HTML and check only the <head> section
                 let document = Html::parse_document(&html_text);
@@ -892,15 +1310,22 @@ fn inject_archive_banner(html: &str, banner: &str) -> String {
     }
 }
 
-async fn process_archive_inner(
+/// An archive claimed for processing that passed the prevention checks.
+struct RoutedArchive {
+    link: Link,
+    is_post_snapshot: bool,
+    handler: &'static dyn SiteHandler,
+}
+
+/// Claim an archive and find the handler for its link.
+///
+/// Returns `None` when prevention signals skipped the archive.
+async fn route_archive(
     db: &Database,
-    s3: &S3Client,
-    ipfs: &IpfsClient,
-    screenshot: &ScreenshotService,
     config: &Config,
     archive_id: i64,
     link_id: i64,
-) -> Result<()> {
+) -> Result<Option<RoutedArchive>> {
     // Mark as processing
     set_archive_processing(db.pool(), archive_id).await?;
 
@@ -911,18 +1336,94 @@ async fn process_archive_inner(
 
     debug!(archive_id, url = %link.normalized_url, "Processing archive");
 
+    let is_post_snapshot =
+        config.archive_post_snapshots && is_post_snapshot_archive(db.pool(), archive_id).await?;
+
     // Check for archive prevention signals (excluded domains, X-No-Archive header, meta tags)
-    if should_skip_due_to_archive_prevention(db, &link.normalized_url).await? {
-        info!(archive_id, url = %link.normalized_url, "Skipping archive due to prevention signals");
-        set_archive_skipped(db.pool(), archive_id).await?;
-        return Ok(());
+    if let Some(skip_reason) =
+        should_skip_due_to_archive_prevention(db, config, &link.normalized_url, is_post_snapshot)
+            .await?
+    {
+        info!(archive_id, url = %link.normalized_url, reason = skip_reason.as_str(), "Skipping archive due to prevention signals");
+        set_archive_skipped(db.pool(), archive_id, skip_reason.as_str()).await?;
+        return Ok(None);
     }
 
+    // Record redirect hops so a failure can be traced to the hop that broke
+    record_redirect_chain(db, config, archive_id, &link.normalized_url).await;
+
     // Find handler
     let handler = HANDLERS
         .find_handler(&link.normalized_url)
         .context("No handler found for URL")?;
 
+    Ok(Some(RoutedArchive {
+        link,
+        is_post_snapshot,
+        handler,
+    }))
+}
+
+/// Route an archive under `ARCHIVE_DRY_RUN` and record what archiving it would do.
+async fn dry_run_archive(
+    db: &Database,
+    external: &ExternalClients,
+    config: &Config,
+    archive_id: i64,
+    link_id: i64,
+) -> Result<()> {
+    let Some(routed) = route_archive(db, config, archive_id, link_id).await? else {
+        return Ok(());
+    };
+    record_dry_run(
+        db,
+        external,
+        config,
+        archive_id,
+        &routed.link,
+        routed.handler.site_id(),
+    )
+    .await
+}
+
+/// Archive a link, or only record what would be done under `ARCHIVE_DRY_RUN`.
+async fn run_archive(
+    db: &Database,
+    s3: &S3Client,
+    external: &ExternalClients,
+    screenshot: &ScreenshotService,
+    config: &Config,
+    archive_id: i64,
+    link_id: i64,
+) -> Result<()> {
+    if config.archive_dry_run {
+        return dry_run_archive(db, external, config, archive_id, link_id).await;
+    }
+    // Boxed: the pipeline's state machine is too large to keep on the stack
+    Box::pin(process_archive_inner(
+        db, s3, external, screenshot, config, archive_id, link_id,
+    ))
+    .await
+}
+
+async fn process_archive_inner(
+    db: &Database,
+    s3: &S3Client,
+    external: &ExternalClients,
+    screenshot: &ScreenshotService,
+    config: &Config,
+    archive_id: i64,
+    link_id: i64,
+) -> Result<()> {
+    let Some(RoutedArchive {
+        link,
+        is_post_snapshot,
+        handler,
+    }) = route_archive(db, config, archive_id, link_id).await?
+    else {
+        return Ok(());
+    };
+
     // Check for existing video before downloading (supports all platforms)
     let platform = handler.site_id();
     let is_youtube = platform == "youtube";
@@ -1026,11 +1527,20 @@ async fn process_archive_inner(
         }
     }
 
-    // Create work directory
-    let work_dir = config.work_dir.join(format!("archive_{archive_id}"));
+    // Create (or reuse) the work directory; a previous interrupted attempt may
+    // have left partial downloads for yt-dlp to resume
+    let work_dir = archive_work_dir(&config.work_dir, archive_id);
     tokio::fs::create_dir_all(&work_dir)
         .await
         .context("Failed to create work directory")?;
+    let partial = partial_downloads(&work_dir);
+    if !partial.is_empty() {
+        info!(
+            archive_id,
+            files = partial.len(),
+            "Resuming interrupted download from partial files"
+        );
+    }
 
     let mut primary_key: Option<String> = None;
     let mut thumb_key: Option<String> = None;
@@ -1242,7 +1752,11 @@ async fn process_archive_inner(
                 res
             }
             Err(e) => {
-                fail_job(db.pool(), main_job, &format!("{e:#}")).await;
+                let error_msg = format!("{e:#}");
+                fail_job(db.pool(), main_job, &error_msg).await;
+                if error_msg.contains(crate::handlers::SOFT_UNAVAILABLE) && !is_post_snapshot {
+                    preserve_soft_unavailable(db, external, config, archive_id, &link).await;
+                }
                 return Err(e.context("Handler archive failed"));
             }
         };
@@ -1274,8 +1788,12 @@ async fn process_archive_inner(
             // Determine artifact kind based on content type
             let kind = if result.content_type == "video" {
                 ArtifactKind::Video
+            } else if result.content_type == "audio" {
+                ArtifactKind::Audio
             } else if result.content_type == "image" || result.content_type == "gallery" {
                 ArtifactKind::Image
+            } else if result.content_type == "document" {
+                ArtifactKind::Document
             } else if result.content_type == "pdf" {
                 ArtifactKind::Pdf
             } else if primary == "raw.html" {
@@ -1321,9 +1839,36 @@ async fn process_archive_inner(
                 (None, None)
             };
 
-            // Upload to S3 only if not a duplicate
+            let sha256 = match compute_file_sha256(&local_path).await {
+                Ok(hash) => Some(hash),
+                Err(e) => {
+                    debug!(archive_id, error = %e, "Failed to compute primary sha256");
+                    None
+                }
+            };
+
+            // Upload to S3 only if not a duplicate and not identical to the
+            // object a previous archive run left at the same key
             if duplicate_of.is_none() {
-                s3.upload_file(&local_path, &key, Some(archive_id)).await?;
+                let previous = match get_previous_primary(db.pool(), archive_id).await {
+                    Ok(previous) => previous,
+                    Err(e) => {
+                        debug!(archive_id, error = %e, "Failed to fetch previous primary");
+                        None
+                    }
+                };
+                let previous = previous
+                    .as_ref()
+                    .map(|(key, hash)| (key.as_str(), hash.as_str()));
+                if primary_upload_unchanged(previous, &key, sha256.as_deref()) {
+                    info!(
+                        archive_id,
+                        key = %key,
+                        "Primary artifact unchanged since last archive, skipping upload"
+                    );
+                } else {
+                    s3.upload_file(&local_path, &key, Some(archive_id)).await?;
+                }
                 primary_key = Some(key.clone());
                 primary_local_path = Some(local_path.clone());
             }
@@ -1336,7 +1881,7 @@ async fn process_archive_inner(
                 primary_key.as_deref().unwrap_or(&key),
                 Some(&content_type),
                 size_bytes,
-                None,
+                sha256.as_deref(),
                 perceptual_hash.as_deref(),
                 duplicate_of,
             )
@@ -1600,30 +2145,36 @@ async fn process_archive_inner(
                 {
                     Ok(()) => {
                         let complete_key = format!("{s3_prefix}media/complete.html");
-                        let metadata = tokio::fs::metadata(&complete_path).await.ok();
-                        let size_bytes = metadata.map(|m| m.len() as i64);
-                        if let Err(e) = s3
-                            .upload_file(&complete_path, &complete_key, Some(archive_id))
+                        match upload_complete_html(s3, &complete_path, &complete_key, archive_id)
                             .await
                         {
-                            warn!(archive_id, error = %e, "Failed to upload complete.html");
-                        } else {
-                            debug!(archive_id, key = %complete_key, "Uploaded complete.html");
-                            if let Err(e) = insert_artifact(
-                                db.pool(),
-                                archive_id,
-                                ArtifactKind::CompleteHtml.as_str(),
-                                &complete_key,
-                                Some("text/html"),
-                                size_bytes,
-                                None,
-                            )
-                            .await
-                            {
-                                warn!(archive_id, error = %e, "Failed to insert complete.html artifact record");
-                            } else {
-                                let size_meta = size_bytes.map(|s| format!("{s} bytes"));
-                                complete_job(db.pool(), monolith_job, size_meta.as_deref()).await;
+                            Err(e) => {
+                                warn!(archive_id, error = %e, "Failed to upload complete.html");
+                                fail_job(db.pool(), monolith_job, &e.to_string()).await;
+                            }
+                            Ok((size_bytes, content_encoding)) => {
+                                debug!(archive_id, key = %complete_key, content_encoding = ?content_encoding, "Uploaded complete.html");
+                                match insert_artifact_with_encoding(
+                                    db.pool(),
+                                    archive_id,
+                                    ArtifactKind::CompleteHtml.as_str(),
+                                    &complete_key,
+                                    Some("text/html"),
+                                    size_bytes,
+                                    content_encoding,
+                                )
+                                .await
+                                {
+                                    Err(e) => {
+                                        warn!(archive_id, error = %e, "Failed to insert complete.html artifact record");
+                                        fail_job(db.pool(), monolith_job, &e.to_string()).await;
+                                    }
+                                    Ok(_) => {
+                                        let size_meta = size_bytes.map(|s| format!("{s} bytes"));
+                                        complete_job(db.pool(), monolith_job, size_meta.as_deref())
+                                            .await;
+                                    }
+                                }
                             }
                         }
                     }
@@ -1696,7 +2247,7 @@ async fn process_archive_inner(
             }
 
             // Insert thumbnail artifact record with hash info
-            if let Err(e) = insert_artifact_with_hash(
+            match insert_artifact_with_hash(
                 db.pool(),
                 archive_id,
                 ArtifactKind::Thumb.as_str(),
@@ -1709,7 +2260,12 @@ async fn process_archive_inner(
             )
             .await
             {
-                warn!(archive_id, error = %e, "Failed to insert thumbnail artifact record");
+                Ok(artifact_id) => {
+                    store_thumbnail_placeholder(db, archive_id, artifact_id, &local_path).await;
+                }
+                Err(e) => {
+                    warn!(archive_id, error = %e, "Failed to insert thumbnail artifact record");
+                }
             }
         }
     }
@@ -1847,7 +2403,9 @@ async fn process_archive_inner(
             debug!(archive_id, file = %extra_file, "Uploaded extra file");
 
             // Determine artifact kind based on content type and filename
-            let kind = if extra_file.starts_with("tiktok_music.") {
+            let kind = if extra_file == ARTICLE_FILE {
+                ArtifactKind::Article
+            } else if extra_file.starts_with("tiktok_music.") {
                 ArtifactKind::Music
             } else if content_type.starts_with("image/") {
                 ArtifactKind::Image
@@ -1881,12 +2439,29 @@ async fn process_archive_inner(
     // Process subtitle files with metadata tracking
     if !subtitle_files.is_empty() {
         process_subtitle_files(db, s3, archive_id, &subtitle_files, &work_dir, &s3_prefix).await;
+    } else if config.whisper_enabled && result.content_type == "video" {
+        // No platform subtitles: fall back to local transcription of the download
+        if let Some(ref primary) = result.primary_file {
+            let local_path = work_dir.join(primary);
+            if local_path.exists() {
+                transcribe_with_whisper(
+                    db,
+                    s3,
+                    config,
+                    archive_id,
+                    &local_path,
+                    &work_dir,
+                    &s3_prefix,
+                )
+                .await;
+            }
+        }
     }
 
     // Capture screenshot if enabled (non-fatal if it fails)
     // Skip screenshots for direct PDF files - they're already archived
-    let is_pdf = result.content_type == "pdf";
-    let skip_browser_captures = is_pdf || is_youtube_video;
+    let is_document = matches!(result.content_type.as_str(), "document" | "pdf");
+    let skip_browser_captures = is_document || is_youtube_video;
 
     if screenshot.is_enabled() && !skip_browser_captures {
         let screenshot_job = start_job(db.pool(), archive_id, ArchiveJobType::Screenshot).await;
@@ -1902,10 +2477,15 @@ async fn process_archive_inner(
                 } else {
                     debug!(archive_id, key = %screenshot_key, "Screenshot uploaded");
                     // Insert screenshot artifact record
+                    let screenshot_kind = if is_post_snapshot {
+                        ArtifactKind::PostSnapshot
+                    } else {
+                        ArtifactKind::Screenshot
+                    };
                     if let Err(e) = insert_artifact(
                         db.pool(),
                         archive_id,
-                        ArtifactKind::Screenshot.as_str(),
+                        screenshot_kind.as_str(),
                         &screenshot_key,
                         Some("image/webp"),
                         size_bytes,
@@ -2030,13 +2610,53 @@ async fn process_archive_inner(
         .await;
     }
 
-    // Pin to IPFS if enabled
-    let ipfs_cid = if ipfs.is_enabled() {
-        // Try to pin the primary file to IPFS
-        if let Some(ref local_path) = primary_local_path {
-            match ipfs.pin_file(local_path).await {
-                Ok(cid) => {
-                    info!(archive_id, cid = %cid, "Pinned to IPFS");
+    // Decide which external services this archive goes to
+    let ipfs = &external.ipfs;
+    let default_services = ExternalServices {
+        wayback: config.wayback_enabled,
+        archive_today: config.archive_today_enabled,
+        ipfs: ipfs.is_enabled(),
+    };
+    let services = match external_services_for(
+        db.pool(),
+        &link.domain,
+        &result.content_type,
+        default_services,
+    )
+    .await
+    {
+        Ok(services) => services,
+        Err(e) => {
+            warn!(archive_id, error = %e, "Failed to load external service rules, using defaults");
+            default_services
+        }
+    };
+
+    // Pin to IPFS if enabled. Rules can't enable pinning without a configured daemon.
+    let ipfs_cid = if services.ipfs && ipfs.is_enabled() {
+        let readiness = ipfs.pin_readiness().await;
+        if readiness == PinReadiness::Recovered {
+            let (db, s3, ipfs) = (db.clone(), s3.clone(), ipfs.clone());
+            tokio::spawn(async move {
+                match backfill_pending_pins(&db, &s3, &ipfs).await {
+                    Ok(count) => info!(count, "Backfilled pending IPFS pins"),
+                    Err(e) => warn!(error = %e, "Failed to backfill pending IPFS pins"),
+                }
+            });
+        }
+
+        if readiness == PinReadiness::Unavailable {
+            // Daemon is down; pin from S3 once it's back instead of waiting on it
+            debug!(archive_id, "IPFS unavailable, queueing pin for later");
+            if let Err(e) = mark_ipfs_pin_pending(db.pool(), archive_id).await {
+                warn!(archive_id, error = %e, "Failed to queue pending IPFS pin");
+            }
+            None
+        } else if let Some(ref local_path) = primary_local_path {
+            // Try to pin the primary file to IPFS
+            match ipfs.pin_file(local_path).await {
+                Ok(cid) => {
+                    info!(archive_id, cid = %cid, "Pinned to IPFS");
                     Some(cid)
                 }
                 Err(e) => {
@@ -2091,14 +2711,35 @@ async fn process_archive_inner(
         }
     }
 
+    // Enqueue playlist members as individual archives grouped under this one
+    if result.content_type == "playlist" {
+        if let Some(ref metadata_json) = result.metadata_json {
+            match enqueue_playlist_members(db, config, archive_id, metadata_json).await {
+                Ok(count) => info!(archive_id, count, "Enqueued playlist members"),
+                Err(e) => warn!(archive_id, error = %e, "Failed to enqueue playlist members"),
+            }
+        }
+    }
+
+    // Archive the reply parent / quoted post / self-thread and link them to this archive
+    if let Some(ref metadata_json) = result.metadata_json {
+        if let Err(e) = enqueue_thread_context(db, archive_id, metadata_json).await {
+            warn!(archive_id, error = %e, "Failed to enqueue thread context");
+        }
+    }
+
     // Queue comment extraction job if this is a comments-supported platform
     // Skip comment extraction for playlists and YouTube channels (they have no individual video comments)
     let is_playlist = result.content_type == "playlist";
     let url_for_check = link.final_url.as_deref().unwrap_or(&link.normalized_url);
     let is_youtube_channel = crate::handlers::youtube::is_channel_url(url_for_check);
+    // Reddit comments are per post; subreddit and user pages have none
+    let is_reddit_non_post = extract_platform_name(&link.domain) == "reddit"
+        && crate::handlers::reddit::extract_post_id(url_for_check).is_none();
     if config.comments_enabled
         && !is_playlist
         && !is_youtube_channel
+        && !is_reddit_non_post
         && is_comments_supported_platform(&link.domain, config)
     {
         match create_archive_job(db.pool(), archive_id, ArchiveJobType::CommentExtraction).await {
@@ -2144,6 +2785,16 @@ async fn process_archive_inner(
         }
     }
 
+    // Classify media the handler didn't already flag
+    if config.nsfw_classifier_enabled
+        && result.is_nsfw != Some(true)
+        && matches!(result.content_type.as_str(), "image" | "video")
+    {
+        if let Some(ref local_path) = primary_local_path {
+            classify_nsfw(db, config, archive_id, local_path).await;
+        }
+    }
+
     // Store IPFS CID if we have one
     if let Some(ref cid) = ipfs_cid {
         set_archive_ipfs_cid(db.pool(), archive_id, cid).await?;
@@ -2163,6 +2814,19 @@ async fn process_archive_inner(
     // Update link last archived timestamp
     update_link_last_archived(db.pool(), link_id).await?;
 
+    // Forum post snapshots stay out of third-party archives
+    if !is_post_snapshot {
+        spawn_external_submissions(
+            db,
+            external,
+            archive_id,
+            &link.normalized_url,
+            services,
+            default_services,
+        )
+        .await;
+    }
+
     // Extract Open Graph metadata from raw.html if available
     let raw_html_path = work_dir.join("raw.html");
     if raw_html_path.exists() {
@@ -2226,12 +2890,226 @@ async fn process_archive_inner(
     Ok(())
 }
 
-/// Create a work directory for an archive job.
-#[allow(dead_code)]
-fn create_work_dir(base: &Path, archive_id: i64) -> PathBuf {
+/// Work directory for an archive job.
+///
+/// The path depends only on the archive ID and is kept across failed
+/// attempts and restarts, so a retried yt-dlp download resumes its `.part`
+/// files instead of starting over. It is removed once the archive completes
+/// or fails permanently.
+pub(crate) fn archive_work_dir(base: &Path, archive_id: i64) -> PathBuf {
     base.join(format!("archive_{archive_id}"))
 }
 
+/// Partial downloads (`*.part`) left in a work directory by an interrupted attempt.
+fn partial_downloads(work_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(work_dir) else {
+        return Vec::new();
+    };
+    let mut parts: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "part"))
+        .collect();
+    parts.sort();
+    parts
+}
+
+/// Remove an archive's work directory once it will not be retried.
+async fn remove_archive_work_dir(config: &Config, archive_id: i64) {
+    let work_dir = archive_work_dir(&config.work_dir, archive_id);
+    match tokio::fs::remove_dir_all(&work_dir).await {
+        Ok(()) => debug!(archive_id, "Removed work directory"),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => warn!(archive_id, "Failed to clean up work directory: {e}"),
+    }
+}
+
+/// Enqueue each video of an archived playlist and record its membership.
+///
+/// Videos are normalized the same way as links found in forum posts, so a
+/// video that was already archived on its own is linked rather than
+/// re-archived. The item count is capped by `youtube_playlist_max_items`.
+async fn enqueue_playlist_members(
+    db: &Database,
+    config: &Config,
+    playlist_archive_id: i64,
+    metadata_json: &str,
+) -> Result<usize> {
+    let playlist: PlaylistInfo =
+        serde_json::from_str(metadata_json).context("Failed to parse playlist metadata")?;
+    let post_date = get_archive(db.pool(), playlist_archive_id)
+        .await?
+        .and_then(|a| a.post_date);
+
+    let mut enqueued = 0;
+    for (index, video) in playlist
+        .videos
+        .iter()
+        .take(config.youtube_playlist_max_items)
+        .enumerate()
+    {
+        let normalized = normalize_link_url(&video.url);
+        let Some(domain) = Url::parse(&normalized)
+            .ok()
+            .and_then(|u| u.host_str().map(ToString::to_string))
+        else {
+            warn!(playlist_archive_id, url = %video.url, "Skipping playlist entry with invalid URL");
+            continue;
+        };
+
+        let new_link = NewLink {
+            original_url: video.url.clone(),
+            normalized_url: normalized.clone(),
+            canonical_url: None,
+            domain,
+        };
+        let link_id = get_or_create_link(db.pool(), &new_link).await?;
+
+        let member_archive_id =
+            create_pending_archive(db.pool(), link_id, post_date.as_deref()).await?;
+        insert_playlist_item(
+            db.pool(),
+            playlist_archive_id,
+            member_archive_id,
+            index as i64 + 1,
+        )
+        .await?;
+        enqueued += 1;
+    }
+
+    Ok(enqueued)
+}
+
+/// Archive the posts a social post replies to or quotes, and link them.
+///
+/// Reads `reply_parent_url` / `quoted_post_url` from the handler metadata
+/// (set by the Bluesky handler). Each target reuses its link's existing
+/// archive or gets a new pending one; when that archive is processed its own
+/// parent is queued in turn, so the whole reply chain is captured.
+///
+/// `self_thread_urls` (set by the Twitter handler) lists the author's
+/// follow-up tweets; each is archived and linked as a reply to the one
+/// before it. Only the head of a thread expands it, so members that list the
+/// rest of the thread again don't re-link it.
+async fn enqueue_thread_context(db: &Database, archive_id: i64, metadata_json: &str) -> Result<()> {
+    let Ok(metadata) = serde_json::from_str::<serde_json::Value>(metadata_json) else {
+        return Ok(());
+    };
+    let Some(archive) = get_archive(db.pool(), archive_id).await? else {
+        return Ok(());
+    };
+    let post_date = archive.post_date.as_deref();
+
+    for (key, is_quote) in [("reply_parent_url", false), ("quoted_post_url", true)] {
+        let Some(url) = metadata.get(key).and_then(serde_json::Value::as_str) else {
+            continue;
+        };
+        let Some(target_id) = resolve_context_archive(db, url, post_date).await? else {
+            continue;
+        };
+        if target_id == archive_id {
+            continue;
+        }
+
+        if is_quote {
+            set_archive_quoted_link(db.pool(), archive_id, target_id).await?;
+        } else {
+            set_archive_reply_link(db.pool(), archive_id, target_id).await?;
+        }
+        debug!(archive_id, target_id, key, "Linked thread context archive");
+    }
+
+    if archive.reply_to_archive_id.is_none() {
+        let thread_urls = metadata
+            .get("self_thread_urls")
+            .and_then(serde_json::Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let mut previous_id = archive_id;
+        for url in thread_urls.iter().filter_map(serde_json::Value::as_str) {
+            let Some(target_id) = resolve_context_archive(db, url, post_date).await? else {
+                continue;
+            };
+            if target_id == archive_id || target_id == previous_id {
+                continue;
+            }
+            set_archive_reply_link(db.pool(), target_id, previous_id).await?;
+            debug!(
+                archive_id,
+                target_id, previous_id, "Linked self-thread archive"
+            );
+            previous_id = target_id;
+        }
+    }
+
+    Ok(())
+}
+
+/// Find or create the archive for a thread context URL.
+///
+/// Returns `None` when the URL has no host.
+async fn resolve_context_archive(
+    db: &Database,
+    url: &str,
+    post_date: Option<&str>,
+) -> Result<Option<i64>> {
+    let normalized = normalize_link_url(url);
+    let Some(domain) = Url::parse(&normalized)
+        .ok()
+        .and_then(|u| u.host_str().map(ToString::to_string))
+    else {
+        return Ok(None);
+    };
+
+    let link_id = get_or_create_link(
+        db.pool(),
+        &NewLink {
+            original_url: url.to_string(),
+            normalized_url: normalized,
+            canonical_url: None,
+            domain,
+        },
+    )
+    .await?;
+    let target_id = match get_archive_by_link_id(db.pool(), link_id).await? {
+        Some(existing) => existing.id,
+        None => create_pending_archive(db.pool(), link_id, post_date).await?,
+    };
+
+    Ok(Some(target_id))
+}
+
+/// Pending IPFS pins uploaded per backfill run.
+const IPFS_BACKFILL_BATCH: i64 = 100;
+
+/// Pin archives that were queued while the IPFS daemon was unavailable.
+///
+/// Each archive's primary S3 object is pinned. Stops at the first failed pin
+/// so a daemon that went down again isn't hammered; the rest stay queued.
+async fn backfill_pending_pins(db: &Database, s3: &S3Client, ipfs: &IpfsClient) -> Result<usize> {
+    let pending = get_pending_ipfs_pins(db.pool(), IPFS_BACKFILL_BATCH).await?;
+    let mut pinned = 0;
+
+    for archive in pending {
+        let Some(ref s3_key) = archive.s3_key_primary else {
+            // Nothing stored to pin
+            clear_ipfs_pin_pending(db.pool(), archive.id).await?;
+            continue;
+        };
+        let Some((data, _content_type)) = s3.get_object(s3_key).await? else {
+            clear_ipfs_pin_pending(db.pool(), archive.id).await?;
+            continue;
+        };
+        let filename = s3_key.rsplit('/').next().unwrap_or(s3_key);
+
+        let cid = ipfs.pin_bytes(&data, filename).await?;
+        set_archive_ipfs_cid(db.pool(), archive.id, &cid).await?;
+        debug!(archive_id = archive.id, cid = %cid, "Backfilled IPFS pin");
+        pinned += 1;
+    }
+
+    Ok(pinned)
+}
+
 /// Check if a YouTube video already exists on S3.
 ///
 /// Returns the existing S3 key if found, along with file extension.
@@ -2284,6 +3162,239 @@ async fn copy_video_to_predictable_path(
     Ok(target_key)
 }
 
+/// Resolve and store the redirect chain for an archive's URL. Failures are only logged.
+async fn record_redirect_chain(db: &Database, config: &Config, archive_id: i64, url: &str) {
+    match super::redirects::resolve_redirect_chain(config, url).await {
+        Ok(chain) if !chain.is_empty() => {
+            debug!(archive_id, hops = chain.len(), "Resolved redirect chain");
+            let json = super::redirects::chain_to_json(&chain);
+            if let Err(e) = set_archive_redirect_chain(db.pool(), archive_id, &json).await {
+                warn!(archive_id, error = %e, "Failed to store redirect chain");
+            }
+        }
+        Ok(_) => {}
+        Err(e) => debug!(archive_id, error = %e, "Could not resolve redirect chain"),
+    }
+}
+
+/// Run the NSFW classifier on an archive's primary media file.
+///
+/// Failures are logged and leave the NSFW status unchanged.
+async fn classify_nsfw(db: &Database, config: &Config, archive_id: i64, path: &Path) {
+    let score = match super::nsfw::classify(path, config).await {
+        Ok(Some(score)) => score,
+        Ok(None) => {
+            warn!(
+                archive_id,
+                command = %config.nsfw_classifier_command,
+                "NSFW classifier not found, skipping classification"
+            );
+            return;
+        }
+        Err(e) => {
+            warn!(archive_id, error = %e, "NSFW classification failed");
+            return;
+        }
+    };
+
+    let is_nsfw = score >= config.nsfw_classifier_threshold;
+    match set_archive_nsfw_auto(db.pool(), archive_id, is_nsfw).await {
+        Ok(true) => {
+            if is_nsfw {
+                info!(archive_id, score, "Archive auto-classified as NSFW");
+            } else {
+                debug!(archive_id, score, "Archive auto-classified as safe");
+            }
+        }
+        Ok(false) => {
+            debug!(
+                archive_id,
+                score, "Keeping existing NSFW status over auto classification"
+            );
+        }
+        Err(e) => warn!(archive_id, error = %e, "Failed to store NSFW classification"),
+    }
+}
+
+/// Content type a handler is expected to produce, known before downloading.
+///
+/// Only used to describe a dry run; real archives take the type from the
+/// handler's result.
+fn expected_content_type(platform: &str, url: &str) -> &'static str {
+    match platform {
+        "youtube" if extract_video_id(url).is_some() => "video",
+        "tiktok" | "streamable" | "facebook" | "instagram" => "video",
+        "soundcloud" | "bandcamp" => "audio",
+        "imgur" => "image",
+        "reddit" => "thread",
+        _ => "text",
+    }
+}
+
+/// Log what archiving a link would do and mark it skipped with that plan.
+///
+/// Nothing is downloaded, uploaded or submitted. External services are
+/// resolved against the rules for the expected content type.
+async fn record_dry_run(
+    db: &Database,
+    external: &ExternalClients,
+    config: &Config,
+    archive_id: i64,
+    link: &Link,
+    platform: &str,
+) -> Result<()> {
+    let content_type = expected_content_type(platform, &link.normalized_url);
+    let defaults = ExternalServices {
+        wayback: config.wayback_enabled,
+        archive_today: config.archive_today_enabled,
+        ipfs: external.ipfs.is_enabled(),
+    };
+    let services = external_services_for(db.pool(), &link.domain, content_type, defaults)
+        .await
+        .unwrap_or(defaults);
+
+    let planned: Vec<&str> = [
+        (services.wayback, "wayback"),
+        (services.archive_today, "archive.today"),
+        (services.ipfs, "ipfs"),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, name)| enabled.then_some(name))
+    .collect();
+    let external = if planned.is_empty() {
+        "none".to_string()
+    } else {
+        planned.join(", ")
+    };
+
+    info!(
+        archive_id,
+        url = %link.normalized_url,
+        handler = platform,
+        content_type,
+        external = %external,
+        "Dry run: would archive"
+    );
+    let note =
+        format!("Dry run: handler {platform}, expected {content_type}, external: {external}");
+    set_archive_dry_run(db.pool(), archive_id, &note).await
+}
+
+/// Send a soft-404 or paywalled page to Wayback and Archive.today.
+///
+/// Our own capture would only show the unavailable page, so the external
+/// snapshots become the primary preservation. IPFS is skipped since nothing
+/// is stored.
+async fn preserve_soft_unavailable(
+    db: &Database,
+    external: &ExternalClients,
+    config: &Config,
+    archive_id: i64,
+    link: &Link,
+) {
+    let defaults = ExternalServices {
+        wayback: config.wayback_enabled,
+        archive_today: config.archive_today_enabled,
+        ipfs: false,
+    };
+    let services = external_services_for(db.pool(), &link.domain, "text", defaults)
+        .await
+        .unwrap_or(defaults);
+    info!(
+        archive_id,
+        url = %link.normalized_url,
+        wayback = services.wayback,
+        archive_today = services.archive_today,
+        "Page looks unavailable, relying on external archives"
+    );
+    spawn_external_submissions(
+        db,
+        external,
+        archive_id,
+        &link.normalized_url,
+        services,
+        defaults,
+    )
+    .await;
+}
+
+/// Submit a completed archive's URL to Wayback and Archive.today.
+///
+/// Submissions are rate limited and can wait for minutes, so they run in the
+/// background instead of holding the worker permit. Services a rule turned off
+/// are recorded as skipped jobs.
+async fn spawn_external_submissions(
+    db: &Database,
+    external: &ExternalClients,
+    archive_id: i64,
+    url: &str,
+    services: ExternalServices,
+    defaults: ExternalServices,
+) {
+    if services.wayback {
+        let db = db.clone();
+        let client = Arc::clone(&external.wayback);
+        let url = url.to_string();
+        tokio::spawn(async move {
+            let job = start_job(db.pool(), archive_id, ArchiveJobType::Wayback).await;
+            match client.submit(&url).await {
+                Ok(Some(snapshot)) => {
+                    if let Err(e) = set_archive_wayback_url(db.pool(), archive_id, &snapshot).await
+                    {
+                        warn!(archive_id, error = %e, "Failed to store Wayback URL");
+                    }
+                    complete_job(db.pool(), job, Some(&snapshot)).await;
+                }
+                Ok(None) => complete_job(db.pool(), job, None).await,
+                Err(e) => {
+                    warn!(archive_id, error = %e, "Wayback submission failed");
+                    fail_job(db.pool(), job, &format!("{e:#}")).await;
+                }
+            }
+        });
+    } else if defaults.wayback {
+        skip_job(
+            db.pool(),
+            archive_id,
+            ArchiveJobType::Wayback,
+            "Disabled by external service rule",
+        )
+        .await;
+    }
+
+    if services.archive_today {
+        let db = db.clone();
+        let client = Arc::clone(&external.archive_today);
+        let url = url.to_string();
+        tokio::spawn(async move {
+            let job = start_job(db.pool(), archive_id, ArchiveJobType::ArchiveToday).await;
+            match client.submit(&url).await {
+                Ok(Some(snapshot)) => {
+                    if let Err(e) =
+                        set_archive_archive_today_url(db.pool(), archive_id, &snapshot).await
+                    {
+                        warn!(archive_id, error = %e, "Failed to store Archive.today URL");
+                    }
+                    complete_job(db.pool(), job, Some(&snapshot)).await;
+                }
+                Ok(None) => complete_job(db.pool(), job, None).await,
+                Err(e) => {
+                    warn!(archive_id, error = %e, "Archive.today submission failed");
+                    fail_job(db.pool(), job, &format!("{e:#}")).await;
+                }
+            }
+        });
+    } else if defaults.archive_today {
+        skip_job(
+            db.pool(),
+            archive_id,
+            ArchiveJobType::ArchiveToday,
+            "Disabled by external service rule",
+        )
+        .await;
+    }
+}
+
 /// Create a job record and mark it running. Returns the job ID when successful.
 async fn start_job(
     pool: &sqlx::SqlitePool,
@@ -2340,6 +3451,47 @@ async fn skip_job(
     }
 }
 
+/// Compute the hex sha256 of a file, reading it in chunks.
+async fn compute_file_sha256(path: &Path) -> Result<String> {
+    use sha2::{Digest, Sha256};
+    use tokio::io::AsyncReadExt;
+
+    let mut file = tokio::fs::File::open(path)
+        .await
+        .context("Failed to open file for hashing")?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file
+            .read(&mut buf)
+            .await
+            .context("Failed to read file for hashing")?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Whether a re-archived primary file matches what is already stored at `key`.
+///
+/// `previous` is the S3 key and sha256 recorded when the archive was reset.
+/// Uploads are only skipped when both the key and the hash match, since a
+/// different key means the old object cannot be reused as is.
+fn primary_upload_unchanged(
+    previous: Option<(&str, &str)>,
+    key: &str,
+    sha256: Option<&str>,
+) -> bool {
+    match (previous, sha256) {
+        (Some((previous_key, previous_hash)), Some(hash)) => {
+            previous_key == key && previous_hash.eq_ignore_ascii_case(hash)
+        }
+        _ => false,
+    }
+}
+
 /// Compute perceptual hash for an image or video file.
 async fn compute_perceptual_hash(path: &Path) -> Result<String> {
     let data = tokio::fs::read(path)
@@ -2348,6 +3500,37 @@ async fn compute_perceptual_hash(path: &Path) -> Result<String> {
     dedup::compute_image_hash(&data).context("Failed to compute perceptual hash")
 }
 
+/// Compute and store a thumbnail's placeholder colour and size.
+///
+/// Failures are only logged: cards fall back to a default size without one.
+async fn store_thumbnail_placeholder(
+    db: &Database,
+    archive_id: i64,
+    artifact_id: i64,
+    path: &Path,
+) {
+    let placeholder = match tokio::fs::read(path).await {
+        Ok(data) => placeholder::compute_placeholder(&data),
+        Err(e) => Err(e.into()),
+    };
+    match placeholder {
+        Ok(p) => {
+            if let Err(e) = set_artifact_placeholder(
+                db.pool(),
+                artifact_id,
+                &p.color,
+                i64::from(p.width),
+                i64::from(p.height),
+            )
+            .await
+            {
+                warn!(archive_id, error = %e, "Failed to store thumbnail placeholder");
+            }
+        }
+        Err(e) => debug!(archive_id, error = %e, "Failed to compute thumbnail placeholder"),
+    }
+}
+
 /// Check if a file is a duplicate of an existing artifact.
 ///
 /// Returns the original artifact if a duplicate is found, or None if unique.
@@ -2450,9 +3633,9 @@ pub async fn process_subtitle_files(
         let (mut language, is_auto, format) = parse_subtitle_info(subtitle_file);
         let mut detected_from = "filename";
 
-        // If language is unknown or looks like a placeholder (NA, etc.),
+        // If language is unknown or looks like a placeholder (NA, und, etc.),
         // try to read it from the VTT file header
-        if (language == "unknown" || language == "NA" || language.len() > 10) && format == "vtt" {
+        if super::langdetect::is_missing_language_code(&language) && format == "vtt" {
             if let Some(header_lang) = parse_vtt_language_from_file(&local_path).await {
                 debug!(
                     archive_id,
@@ -2465,6 +3648,25 @@ pub async fn process_subtitle_files(
                 detected_from = "vtt_header";
             }
         }
+
+        // Still no usable code: guess from the subtitle text itself
+        if super::langdetect::is_missing_language_code(&language) {
+            if let Ok(content) = tokio::fs::read_to_string(&local_path).await {
+                if let Some(content_lang) =
+                    super::langdetect::language_from_content(&language, &content, &format)
+                {
+                    debug!(
+                        archive_id,
+                        file = %subtitle_file,
+                        filename_lang = %language,
+                        content_lang,
+                        "Detected language from subtitle content"
+                    );
+                    language = content_lang.to_string();
+                    detected_from = "content";
+                }
+            }
+        }
         let key = format!("{s3_prefix}subtitles/{subtitle_file}");
         let metadata_result = tokio::fs::metadata(&local_path).await.ok();
         let size_bytes = metadata_result.map(|m| m.len() as i64);
@@ -2636,6 +3838,99 @@ pub async fn process_subtitle_files(
     }
 }
 
+/// Transcribe a downloaded video with Whisper and store it as a transcript artifact.
+///
+/// Used when the platform provided no subtitles. Failures are logged and do
+/// not affect the archive.
+async fn transcribe_with_whisper(
+    db: &Database,
+    s3: &S3Client,
+    config: &Config,
+    archive_id: i64,
+    media_path: &Path,
+    work_dir: &Path,
+    s3_prefix: &str,
+) {
+    use super::transcript::build_transcript_from_file;
+
+    let whisper_output = match super::whisper::transcribe(media_path, work_dir, config).await {
+        Ok(Some(output)) => output,
+        Ok(None) => return,
+        Err(e) => {
+            warn!(archive_id, error = %e, "Whisper transcription failed");
+            return;
+        }
+    };
+
+    let transcript = match build_transcript_from_file(&whisper_output.vtt_path).await {
+        Ok(t) if !t.is_empty() => t,
+        Ok(_) => {
+            debug!(archive_id, "Whisper transcript was empty, skipping upload");
+            return;
+        }
+        Err(e) => {
+            warn!(archive_id, error = %e, "Failed to build transcript from Whisper output");
+            return;
+        }
+    };
+
+    let transcript_key = format!("{s3_prefix}subtitles/transcript.txt");
+    let size_bytes = transcript.len() as i64;
+    if let Err(e) = s3
+        .upload_bytes(transcript.as_bytes(), &transcript_key, "text/plain")
+        .await
+    {
+        warn!(archive_id, error = %e, "Failed to upload Whisper transcript");
+        return;
+    }
+
+    let language = whisper_output
+        .language
+        .unwrap_or_else(|| "unknown".to_string());
+    let transcript_metadata = serde_json::json!({
+        "source": "whisper",
+        "language": language,
+        "model": config.whisper_model,
+    });
+
+    match crate::db::insert_artifact_with_metadata(
+        db.pool(),
+        archive_id,
+        ArtifactKind::Transcript.as_str(),
+        &transcript_key,
+        Some("text/plain"),
+        Some(size_bytes),
+        None,
+        Some(&transcript_metadata.to_string()),
+    )
+    .await
+    {
+        Ok(artifact_id) => {
+            if let Err(e) = crate::db::upsert_subtitle_language(
+                db.pool(),
+                artifact_id,
+                &language,
+                "whisper",
+                true,
+            )
+            .await
+            {
+                warn!(archive_id, artifact_id, error = %e, "Failed to insert transcript language");
+            }
+        }
+        Err(e) => {
+            warn!(archive_id, error = %e, "Failed to insert Whisper transcript artifact");
+        }
+    }
+
+    if let Err(e) = crate::db::set_archive_transcript_text(db.pool(), archive_id, &transcript).await
+    {
+        warn!(archive_id, error = %e, "Failed to store transcript text for search");
+    }
+
+    info!(archive_id, language = %language, size = size_bytes, "Stored Whisper transcript");
+}
+
 async fn check_for_duplicate(
     db: &Database,
     path: &Path,
@@ -2665,6 +3960,454 @@ async fn check_for_duplicate(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_primary_upload_unchanged() {
+        let key = "archives/7/media/video.mp4";
+        let hash = "ab12";
+
+        assert!(primary_upload_unchanged(Some((key, hash)), key, Some(hash)));
+        assert!(primary_upload_unchanged(
+            Some((key, "AB12")),
+            key,
+            Some(hash)
+        ));
+
+        // Content changed
+        assert!(!primary_upload_unchanged(
+            Some((key, "cd34")),
+            key,
+            Some(hash)
+        ));
+        // Same content under a new key still needs uploading
+        assert!(!primary_upload_unchanged(
+            Some(("archives/7/media/old.mp4", hash)),
+            key,
+            Some(hash)
+        ));
+        // Nothing to compare against
+        assert!(!primary_upload_unchanged(None, key, Some(hash)));
+        assert!(!primary_upload_unchanged(Some((key, hash)), key, None));
+    }
+
+    #[tokio::test]
+    async fn test_compute_file_sha256() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("file.bin");
+        tokio::fs::write(&path, b"hello").await.unwrap();
+        assert_eq!(
+            compute_file_sha256(&path).await.unwrap(),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_post_snapshot_bypasses_excluded_domain() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(&temp_dir.path().join("test.sqlite"))
+            .await
+            .unwrap();
+        crate::db::add_excluded_domain(db.pool(), "localhost", "Self-archive exclusion", None)
+            .await
+            .unwrap();
+
+        let url = format!("http://localhost:{}/t/topic/1/1", server.address().port());
+        let config = Config::for_testing();
+
+        // Regular links to the (self-excluded) forum are skipped...
+        assert_eq!(
+            should_skip_due_to_archive_prevention(&db, &config, &url, false)
+                .await
+                .unwrap(),
+            Some(SkipReason::ExcludedDomain)
+        );
+        // ...but an opted-in post snapshot of the same page is archived
+        assert_eq!(
+            should_skip_due_to_archive_prevention(&db, &config, &url, true)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_archive_header_skip_reason() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(200).insert_header("X-No-Archive", "yes"))
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(&temp_dir.path().join("test.sqlite"))
+            .await
+            .unwrap();
+        let url = format!("{}/private", server.uri());
+
+        assert_eq!(
+            should_skip_due_to_archive_prevention(&db, &Config::for_testing(), &url, false)
+                .await
+                .unwrap(),
+            Some(SkipReason::NoArchive)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_thread_context_links_parent_and_quote() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(&temp_dir.path().join("test.sqlite"))
+            .await
+            .unwrap();
+
+        let new_link = |url: &str| NewLink {
+            original_url: url.to_string(),
+            normalized_url: url.to_string(),
+            canonical_url: None,
+            domain: "bsky.app".to_string(),
+        };
+        let child_link = get_or_create_link(
+            db.pool(),
+            &new_link("https://bsky.app/profile/bob.dev/post/3child"),
+        )
+        .await
+        .unwrap();
+        let child_id = create_pending_archive(db.pool(), child_link, None)
+            .await
+            .unwrap();
+
+        // The quoted post was already archived and is reused
+        let quoted_link = get_or_create_link(
+            db.pool(),
+            &new_link("https://bsky.app/profile/carol.example.com/post/3quoted"),
+        )
+        .await
+        .unwrap();
+        let quoted_id = create_pending_archive(db.pool(), quoted_link, None)
+            .await
+            .unwrap();
+
+        let metadata = serde_json::json!({
+            "reply_parent_url": "https://bsky.app/profile/Alice.bsky.social/post/3parent",
+            "quoted_post_url": "https://bsky.app/profile/carol.example.com/post/3quoted",
+        });
+        enqueue_thread_context(&db, child_id, &metadata.to_string())
+            .await
+            .unwrap();
+
+        let child = get_archive(db.pool(), child_id).await.unwrap().unwrap();
+        assert_eq!(child.quoted_archive_id, Some(quoted_id));
+
+        let parent_id = child.reply_to_archive_id.expect("parent should be linked");
+        let parent = get_archive(db.pool(), parent_id).await.unwrap().unwrap();
+        assert_eq!(parent.status, "pending");
+        let parent_link = get_link(db.pool(), parent.link_id).await.unwrap().unwrap();
+        assert_eq!(
+            parent_link.normalized_url,
+            "https://bsky.app/profile/alice.bsky.social/post/3parent"
+        );
+
+        // Metadata without thread context links nothing
+        enqueue_thread_context(&db, quoted_id, "{}").await.unwrap();
+        let quoted = get_archive(db.pool(), quoted_id).await.unwrap().unwrap();
+        assert_eq!(quoted.quoted_archive_id, None);
+        assert_eq!(quoted.reply_to_archive_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_thread_context_chains_self_thread() {
+        use crate::db::get_self_thread;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(&temp_dir.path().join("test.sqlite"))
+            .await
+            .unwrap();
+
+        let head_link = get_or_create_link(
+            db.pool(),
+            &NewLink {
+                original_url: "https://x.com/author/status/100".to_string(),
+                normalized_url: "https://x.com/author/status/100".to_string(),
+                canonical_url: None,
+                domain: "x.com".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        let head_id = create_pending_archive(db.pool(), head_link, None)
+            .await
+            .unwrap();
+
+        let metadata = serde_json::json!({
+            "self_thread_urls": [
+                "https://x.com/author/status/101",
+                "https://twitter.com/author/status/102",
+            ],
+        });
+        enqueue_thread_context(&db, head_id, &metadata.to_string())
+            .await
+            .unwrap();
+
+        let thread = get_self_thread(db.pool(), head_id).await.unwrap();
+        let ids: Vec<i64> = thread.iter().map(|a| a.id).collect();
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids[0], head_id);
+        assert_eq!(thread[1].reply_to_archive_id, Some(head_id));
+        assert_eq!(thread[2].reply_to_archive_id, Some(ids[1]));
+        assert!(thread[1..].iter().all(|a| a.status == "pending"));
+
+        // Any member resolves to the same root-first thread
+        let from_middle: Vec<i64> = get_self_thread(db.pool(), ids[1])
+            .await
+            .unwrap()
+            .iter()
+            .map(|a| a.id)
+            .collect();
+        assert_eq!(from_middle, ids);
+
+        // Thread members don't expand the thread again
+        let member_metadata = serde_json::json!({
+            "self_thread_urls": ["https://x.com/author/status/103"],
+        });
+        enqueue_thread_context(&db, ids[1], &member_metadata.to_string())
+            .await
+            .unwrap();
+        assert_eq!(get_self_thread(db.pool(), head_id).await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_makes_no_storage_calls() {
+        use wiremock::matchers::any;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        // The linked site answers exclusion probes; S3, IPFS and Archive.today
+        // share a server with no mocks, so any call to them would be recorded there
+        let site = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&site)
+            .await;
+        let storage = MockServer::start().await;
+
+        std::env::set_var("AWS_ACCESS_KEY_ID", "test");
+        std::env::set_var("AWS_SECRET_ACCESS_KEY", "test");
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            archive_dry_run: true,
+            ipfs_enabled: true,
+            ipfs_api_url: storage.uri(),
+            s3_endpoint: Some(storage.uri()),
+            work_dir: temp_dir.path().join("work"),
+            ..Config::for_testing()
+        };
+        let db = Database::new(&temp_dir.path().join("test.sqlite"))
+            .await
+            .unwrap();
+        let s3 = S3Client::new(&config).await.unwrap();
+        let screenshot = ScreenshotService::new(config.screenshot_config());
+        let external = ExternalClients {
+            ipfs: IpfsClient::new(&config),
+            wayback: Arc::new(WaybackClient::new(config.wayback_rate_limit_per_min)),
+            archive_today: Arc::new(ArchiveTodayClient::with_base_url(
+                config.archive_today_rate_limit_per_min,
+                &storage.uri(),
+                Duration::ZERO,
+            )),
+        };
+
+        for path in ["/blog/post", "/files/report.pdf"] {
+            let url = format!("{}{path}", site.uri());
+            let link_id = get_or_create_link(
+                db.pool(),
+                &NewLink {
+                    original_url: url.clone(),
+                    normalized_url: url,
+                    canonical_url: None,
+                    domain: "127.0.0.1".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+            let archive_id = create_pending_archive(db.pool(), link_id, None)
+                .await
+                .unwrap();
+
+            run_archive(
+                &db,
+                &s3,
+                &external,
+                &screenshot,
+                &config,
+                archive_id,
+                link_id,
+            )
+            .await
+            .unwrap();
+
+            let archive = get_archive(db.pool(), archive_id).await.unwrap().unwrap();
+            assert_eq!(archive.status, "skipped");
+            assert_eq!(
+                archive.error_message.as_deref(),
+                Some("Dry run: handler generic, expected text, external: ipfs")
+            );
+            assert!(archive.s3_key_primary.is_none());
+            assert!(get_artifacts_for_archive(db.pool(), archive_id)
+                .await
+                .unwrap()
+                .is_empty());
+        }
+
+        assert!(storage.received_requests().await.unwrap().is_empty());
+        assert!(!config.work_dir.exists());
+    }
+
+    #[tokio::test]
+    async fn test_soft_unavailable_page_goes_to_external_archives() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let site = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/story"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                "<html><head><title>Page not found</title></head><body>Oops</body></html>",
+                "text/html",
+            ))
+            .mount(&site)
+            .await;
+        let archive_today = MockServer::start().await;
+
+        std::env::set_var("AWS_ACCESS_KEY_ID", "test");
+        std::env::set_var("AWS_SECRET_ACCESS_KEY", "test");
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            wayback_enabled: false,
+            archive_today_enabled: true,
+            s3_endpoint: Some(archive_today.uri()),
+            work_dir: temp_dir.path().join("work"),
+            ..Config::for_testing()
+        };
+        let db = Database::new(&temp_dir.path().join("test.sqlite"))
+            .await
+            .unwrap();
+        let s3 = S3Client::new(&config).await.unwrap();
+        let external = ExternalClients {
+            ipfs: IpfsClient::new(&config),
+            wayback: Arc::new(WaybackClient::new(config.wayback_rate_limit_per_min)),
+            archive_today: Arc::new(ArchiveTodayClient::with_base_url(
+                config.archive_today_rate_limit_per_min,
+                &archive_today.uri(),
+                Duration::ZERO,
+            )),
+        };
+        let screenshot = ScreenshotService::new(config.screenshot_config());
+
+        let url = format!("{}/story", site.uri());
+        let link_id = get_or_create_link(
+            db.pool(),
+            &NewLink {
+                original_url: url.clone(),
+                normalized_url: url,
+                canonical_url: None,
+                domain: "127.0.0.1".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        let archive_id = create_pending_archive(db.pool(), link_id, None)
+            .await
+            .unwrap();
+
+        let err = process_archive_inner(
+            &db,
+            &s3,
+            &external,
+            &screenshot,
+            &config,
+            archive_id,
+            link_id,
+        )
+        .await
+        .unwrap_err();
+        assert!(format!("{err:#}").contains(crate::handlers::SOFT_UNAVAILABLE));
+        assert!(get_artifacts_for_archive(db.pool(), archive_id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // The Archive.today submission runs in the background
+        let mut requests = Vec::new();
+        for _ in 0..50 {
+            requests = archive_today.received_requests().await.unwrap();
+            if !requests.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert!(!requests.is_empty());
+        assert!(requests
+            .iter()
+            .all(|r| r.method.as_str() != "PUT" && !r.url.path().contains("/media/")));
+    }
+
+    #[test]
+    fn test_expected_content_type() {
+        assert_eq!(
+            expected_content_type("youtube", "https://www.youtube.com/watch?v=dQw4w9WgXcQ"),
+            "video"
+        );
+        assert_eq!(
+            expected_content_type("youtube", "https://www.youtube.com/@channel"),
+            "text"
+        );
+        assert_eq!(
+            expected_content_type("bandcamp", "https://a.bandcamp.com/track/x"),
+            "audio"
+        );
+        assert_eq!(
+            expected_content_type("generic", "https://example.com/"),
+            "text"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_archive_work_dir_reuses_partial_downloads() {
+        let base = tempfile::TempDir::new().unwrap();
+        let config = Config {
+            work_dir: base.path().to_path_buf(),
+            ..Config::for_testing()
+        };
+
+        // An interrupted attempt leaves a partial download behind
+        let first = archive_work_dir(base.path(), 42);
+        std::fs::create_dir_all(&first).unwrap();
+        let part = first.join("Some_Video.NA.mp4.part");
+        std::fs::write(&part, b"partial").unwrap();
+        std::fs::write(first.join("Some_Video.NA.info.json"), b"{}").unwrap();
+
+        // The retry gets the same directory and finds the part file to resume
+        let retry = archive_work_dir(base.path(), 42);
+        assert_eq!(retry, first);
+        assert_eq!(partial_downloads(&retry), vec![part]);
+        assert_ne!(archive_work_dir(base.path(), 43), first);
+        assert!(partial_downloads(&archive_work_dir(base.path(), 43)).is_empty());
+
+        remove_archive_work_dir(&config, 42).await;
+        assert!(!first.exists());
+        // Removing an already-cleaned directory is a no-op
+        remove_archive_work_dir(&config, 42).await;
+    }
+
     #[test]
     fn test_is_auth_required_failure_tiktok() {
         // TikTok-specific sensitive content message
@@ -2733,6 +4476,169 @@ mod tests {
         assert!(!is_auth_required_failure("Private video deleted by user"));
     }
 
+    #[test]
+    fn test_classify_failure_http_status() {
+        assert_eq!(
+            classify_failure("Request failed", Some(404), false),
+            (
+                ArchiveErrorKind::NotFound,
+                FailureClass::Permanent("Not found (HTTP 404)")
+            )
+        );
+        assert_eq!(
+            classify_failure("Request failed", Some(410), false),
+            (
+                ArchiveErrorKind::NotFound,
+                FailureClass::Permanent("Gone (HTTP 410)")
+            )
+        );
+        assert_eq!(
+            classify_failure("Request failed", Some(503), false),
+            (
+                ArchiveErrorKind::Other,
+                FailureClass::Transient("Server error (HTTP 5xx)")
+            )
+        );
+        assert_eq!(
+            classify_failure("Request failed", Some(429), false),
+            (
+                ArchiveErrorKind::RateLimited,
+                FailureClass::Transient("Rate limited (HTTP 429)")
+            )
+        );
+    }
+
+    #[test]
+    fn test_classify_failure_permanent_text() {
+        for msg in [
+            "ERROR: [youtube] abc: Video unavailable",
+            "HTTP Error 410: Gone",
+            "This content is no longer available",
+            "Post removed by moderators",
+            "HTTP Error 404: Not Found",
+        ] {
+            let (kind, class) = classify_failure(msg, None, false);
+            assert_eq!(kind, ArchiveErrorKind::NotFound, "{msg}");
+            assert!(
+                matches!(class, FailureClass::Permanent(_)),
+                "expected '{msg}' to be permanent, got {class:?}"
+            );
+            assert!(!class.is_retryable());
+        }
+    }
+
+    #[test]
+    fn test_classify_failure_noarchive_requested() {
+        let (kind, class) = classify_failure(
+            "noarchive requested (X-Robots-Tag header)",
+            Some(200),
+            false,
+        );
+        assert_eq!(kind, ArchiveErrorKind::Unsupported);
+        assert_eq!(class, FailureClass::Permanent("noarchive requested"));
+        assert!(!class.is_retryable());
+    }
+
+    #[test]
+    fn test_classify_failure_artifact_too_large() {
+        let (kind, class) = classify_failure(
+            "Handler archive failed: artifact too large: 5000 bytes exceeds MAX_ARTIFACT_BYTES (1000 bytes)",
+            Some(200),
+            false,
+        );
+        assert_eq!(kind, ArchiveErrorKind::TooLarge);
+        assert_eq!(class, FailureClass::Permanent("Exceeds MAX_ARTIFACT_BYTES"));
+    }
+
+    #[test]
+    fn test_classify_failure_soft_unavailable() {
+        // Marker text that would otherwise look like an auth wall is still a skip
+        let (kind, class) = classify_failure(
+            "Handler archive failed: soft_unavailable: title matches \"sign in to continue reading\"",
+            Some(200),
+            false,
+        );
+        assert_eq!(kind, ArchiveErrorKind::NotFound);
+        assert_eq!(
+            class,
+            FailureClass::Permanent("Content unavailable (soft 404 or paywall)")
+        );
+        assert!(!class.is_retryable());
+    }
+
+    #[test]
+    fn test_classify_failure_geo_block_depends_on_cookies() {
+        let msg = "ERROR: The uploader has not made this video available in your country";
+        assert_eq!(
+            classify_failure(msg, None, false),
+            (
+                ArchiveErrorKind::Other,
+                FailureClass::Permanent("Geo-restricted")
+            )
+        );
+        assert_eq!(
+            classify_failure(msg, None, true),
+            (
+                ArchiveErrorKind::Other,
+                FailureClass::Transient("Geo-restricted")
+            )
+        );
+    }
+
+    #[test]
+    fn test_classify_failure_transient_text() {
+        assert_eq!(
+            classify_failure("operation timed out", None, false),
+            (
+                ArchiveErrorKind::Timeout,
+                FailureClass::Transient("Timeout")
+            )
+        );
+        assert_eq!(
+            classify_failure("HTTP Error 429: Too Many Requests", None, false),
+            (
+                ArchiveErrorKind::RateLimited,
+                FailureClass::Transient("Rate limited")
+            )
+        );
+        assert_eq!(
+            classify_failure("HTTP Error 502: Bad Gateway", None, false),
+            (
+                ArchiveErrorKind::Other,
+                FailureClass::Transient("Server error")
+            )
+        );
+        assert_eq!(
+            classify_failure("503 Service Unavailable", None, false),
+            (
+                ArchiveErrorKind::Other,
+                FailureClass::Transient("Server error")
+            )
+        );
+        assert_eq!(
+            classify_failure("error sending request: connection refused", None, false),
+            (
+                ArchiveErrorKind::Other,
+                FailureClass::Transient("Network error")
+            )
+        );
+        assert_eq!(
+            classify_failure("something odd happened", None, false),
+            (
+                ArchiveErrorKind::Other,
+                FailureClass::Transient("Unknown error")
+            )
+        );
+    }
+
+    #[test]
+    fn test_classify_failure_auth_required() {
+        let (kind, class) = classify_failure("Sign in to confirm your age", None, false);
+        assert_eq!(kind, ArchiveErrorKind::AuthRequired);
+        assert_eq!(class, FailureClass::AuthRequired);
+        assert!(class.is_retryable());
+    }
+
     #[test]
     fn test_is_permanent_failure_404() {
         // 404 errors should be permanent
@@ -2884,4 +4790,123 @@ mod tests {
             "HTTP Error 403 Forbidden; reason given: gated"
         ));
     }
+
+    #[test]
+    fn test_skip_reason_for_permanent_failures() {
+        let skip_reason = |err: anyhow::Error| match classify_error(&err, None, false) {
+            (kind, FailureClass::Permanent(reason)) => {
+                SkipReason::for_permanent_failure(kind, reason)
+            }
+            other => panic!("not permanent: {other:?}"),
+        };
+        assert_eq!(
+            skip_reason(crate::archiver::size_cap::too_large(10, 5)),
+            SkipReason::SizeCap
+        );
+        assert_eq!(
+            skip_reason(ArchiveError::Unsupported("Unsupported URL".to_string()).into()),
+            SkipReason::Unsupported
+        );
+        assert_eq!(
+            skip_reason(ArchiveError::NotFound("Deleted".to_string()).into()),
+            SkipReason::NotFound
+        );
+        assert_eq!(
+            skip_reason(anyhow::anyhow!(
+                "Page asked not to be archived: {}",
+                crate::handlers::NOARCHIVE_REQUESTED
+            )),
+            SkipReason::NoArchive
+        );
+    }
+
+    #[test]
+    fn test_typed_error_survives_context() {
+        let err = anyhow::Error::from(ArchiveError::NotFound("Album gone".to_string()))
+            .context("Failed to archive album");
+        assert_eq!(format!("{err:#}"), "Failed to archive album: Album gone");
+        assert_eq!(
+            classify_error(&err, None, false),
+            (
+                ArchiveErrorKind::NotFound,
+                FailureClass::Permanent("Not found")
+            )
+        );
+    }
+
+    #[test]
+    fn test_typed_other_keeps_text_classification() {
+        let err = anyhow::Error::from(ArchiveError::Other("HTTP Error 404".to_string()));
+        assert_eq!(
+            classify_error(&err, None, false),
+            (
+                ArchiveErrorKind::Other,
+                FailureClass::Permanent("Not found")
+            )
+        );
+    }
+
+    #[test]
+    fn test_untyped_errors_inferred_from_text() {
+        let cases = [
+            (
+                "Sign in to confirm your age",
+                ArchiveErrorKind::AuthRequired,
+            ),
+            ("HTTP Error 404: Not Found", ArchiveErrorKind::NotFound),
+            ("This video has been removed", ArchiveErrorKind::NotFound),
+            (
+                "HTTP Error 429: Too Many Requests",
+                ArchiveErrorKind::RateLimited,
+            ),
+            ("Read timed out", ArchiveErrorKind::Timeout),
+            ("artifact too large: 5 bytes", ArchiveErrorKind::TooLarge),
+            (
+                "noarchive requested (robots meta tag)",
+                ArchiveErrorKind::Unsupported,
+            ),
+            (
+                "ERROR: Unsupported URL: https://x",
+                ArchiveErrorKind::Unsupported,
+            ),
+            ("Something odd happened", ArchiveErrorKind::Other),
+        ];
+        for (msg, expected) in cases {
+            assert_eq!(error_kind(&anyhow::anyhow!("{msg}")), expected, "{msg}");
+        }
+
+        // The recorded HTTP status still counts
+        let (kind, _) = classify_error(&anyhow::anyhow!("fetch failed"), Some(404), false);
+        assert_eq!(kind, ArchiveErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_reqwest_timeout_is_timeout() {
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(wiremock::matchers::any())
+            .respond_with(ResponseTemplate::new(200).set_delay(std::time::Duration::from_secs(5)))
+            .mount(&server)
+            .await;
+
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_millis(50))
+            .build()
+            .unwrap();
+        let err = client
+            .get(server.uri())
+            .send()
+            .await
+            .context("Failed to fetch URL")
+            .unwrap_err();
+
+        assert_eq!(
+            classify_error(&err, None, false),
+            (
+                ArchiveErrorKind::Timeout,
+                FailureClass::Transient("Timeout")
+            )
+        );
+    }
 }
@@ -12,6 +12,9 @@ use tokio::process::Command;
 use tokio::sync::Semaphore;
 use tracing::{debug, error, info, warn};
 
+use super::comment_worker::{is_comments_disabled_message, CommentExtraction};
+use super::live::{LiveNotReady, LiveStatus};
+use super::size_cap::{check_reported_size, too_large, SizeCheck};
 use super::CookieOptions;
 use crate::config::Config;
 use crate::handlers::ArchiveResult;
@@ -52,6 +55,12 @@ struct VideoMetadata {
     width: Option<u32>,
     #[serde(default)]
     height: Option<u32>,
+    #[serde(default)]
+    live_status: Option<String>,
+    #[serde(default)]
+    is_live: Option<bool>,
+    #[serde(default)]
+    release_timestamp: Option<i64>,
 }
 
 /// TikTok content format detection result
@@ -457,6 +466,24 @@ async fn get_video_metadata(url: &str, cookies: &CookieOptions<'_>) -> Result<Vi
     Ok(metadata)
 }
 
+/// Error deferring live content that isn't downloadable yet.
+fn live_not_ready(
+    url: &str,
+    status: LiveStatus,
+    release_timestamp: Option<i64>,
+    config: &Config,
+) -> anyhow::Error {
+    info!(url = %url, status = ?status, "Live content not ready for download");
+    LiveNotReady::new(
+        url,
+        status,
+        release_timestamp,
+        chrono::Utc::now().timestamp(),
+        (config.live_defer_interval_minutes * 60) as i64,
+    )
+    .into()
+}
+
 /// Download content using yt-dlp.
 ///
 /// If both browser_profile and cookies_file are provided, browser_profile is preferred
@@ -478,6 +505,61 @@ pub async fn download(
     archive_id: Option<i64>,
     pool: Option<&SqlitePool>,
     skip_subtitles: bool,
+) -> Result<ArchiveResult> {
+    let progress = archive_id.zip(pool);
+    run_download(
+        url,
+        work_dir,
+        cookies,
+        config,
+        progress,
+        skip_subtitles,
+        None,
+    )
+    .await
+}
+
+/// Download the best available audio stream using yt-dlp.
+///
+/// Used for audio-only sites (e.g. SoundCloud, Bandcamp). Subtitles are
+/// skipped and the result has content type `audio`, with title, artist and
+/// duration taken from yt-dlp's info.json.
+///
+/// # Errors
+///
+/// Returns an error if yt-dlp fails or times out.
+pub async fn download_audio(
+    url: &str,
+    work_dir: &Path,
+    cookies: &CookieOptions<'_>,
+    config: &Config,
+) -> Result<ArchiveResult> {
+    run_download(
+        url,
+        work_dir,
+        cookies,
+        config,
+        None,
+        true,
+        Some(AUDIO_FORMAT),
+    )
+    .await
+}
+
+/// Format string for audio-only downloads, preferring browser-playable containers.
+const AUDIO_FORMAT: &str = "bestaudio[ext=m4a]/bestaudio[ext=mp3]/bestaudio/best";
+
+/// Shared yt-dlp download implementation.
+///
+/// `format_override` replaces the adaptive video format selection.
+async fn run_download(
+    url: &str,
+    work_dir: &Path,
+    cookies: &CookieOptions<'_>,
+    config: &Config,
+    progress_target: Option<(i64, &SqlitePool)>,
+    skip_subtitles: bool,
+    format_override: Option<&str>,
 ) -> Result<ArchiveResult> {
     // Acquire yt-dlp semaphore - only one yt-dlp operation at a time to avoid 429s
     let _permit = YTDLP_SEMAPHORE
@@ -486,10 +568,22 @@ pub async fn download(
         .context("Failed to acquire yt-dlp semaphore")?;
 
     // Pre-flight check: get video metadata to check duration limits and select quality
-    let mut format_string = select_format_string(None); // Default
+    let mut format_string =
+        format_override.map_or_else(|| select_format_string(None), str::to_string); // Default
 
     match get_video_metadata(url, cookies).await {
         Ok(metadata) => {
+            // Streams and Spaces can only be downloaded once they have a recording
+            let live = LiveStatus::from_ytdlp(metadata.live_status.as_deref(), metadata.is_live);
+            if !live.is_ready() {
+                return Err(live_not_ready(
+                    url,
+                    live,
+                    metadata.release_timestamp,
+                    config,
+                ));
+            }
+
             // Check duration limit
             if let Some(max_duration) = config.youtube_max_duration_seconds {
                 if let Some(duration) = metadata.duration {
@@ -504,10 +598,23 @@ pub async fn download(
                 }
             }
 
+            // Skip oversized downloads before fetching anything
+            let reported = metadata.filesize.or(metadata.filesize_approx);
+            if let SizeCheck::TooLarge(size) =
+                check_reported_size(reported, config.max_artifact_bytes)
+            {
+                return Err(too_large(size, config.max_artifact_bytes));
+            }
+
             // Select format based on metadata
-            format_string = select_format_string(Some(&metadata));
+            if format_override.is_none() {
+                format_string = select_format_string(Some(&metadata));
+            }
         }
         Err(e) => {
+            if let Some(live) = LiveStatus::from_error_message(&e.to_string()) {
+                return Err(live_not_ready(url, live, None, config));
+            }
             // Log warning but continue - metadata fetch can fail for some videos
             warn!("Failed to fetch video metadata for pre-flight checks: {e}");
         }
@@ -548,6 +655,10 @@ pub async fn download(
         output_template.to_string_lossy().to_string(),
         // Sanitize filenames to prevent filesystem issues with special characters
         "--restrict-filenames".to_string(),
+        // Download into .part files and resume them if a previous attempt in
+        // this (per-archive, persistent) work directory was interrupted
+        "--continue".to_string(),
+        "--part".to_string(),
         // Use --newline for parseable progress output (each update on a new line)
         "--newline".to_string(),
         // Format selection: adaptive based on video characteristics
@@ -560,6 +671,12 @@ pub async fn download(
         // "ejs:github".to_string(),
     ]);
 
+    // Sizes yt-dlp only learns while downloading are capped there too
+    if config.max_artifact_bytes > 0 {
+        args.push("--max-filesize".to_string());
+        args.push(config.max_artifact_bytes.to_string());
+    }
+
     // Skip comment extraction during main download - comments will be extracted
     // by the dedicated comment worker to avoid blocking other archives
     // Comment extraction is now done as a separate background job
@@ -619,6 +736,9 @@ pub async fn download(
     let mut last_update = std::time::Instant::now();
     let update_interval = Duration::from_secs(2); // Update DB every 2 seconds max
 
+    // Reported size when yt-dlp aborts a download for exceeding --max-filesize
+    let mut oversized: Option<u64> = None;
+
     // Wrap the streaming in a timeout
     let timeout_duration = Duration::from_secs(config.youtube_download_timeout_seconds);
     let streaming_future = async {
@@ -629,10 +749,14 @@ pub async fn download(
                         Ok(Some(line)) => {
                             debug!("yt-dlp: {}", line);
 
+                            if let Some(size) = parse_max_filesize_abort(&line) {
+                                oversized = Some(size);
+                            }
+
                             // Try to parse progress
                             if let Some(progress) = parse_ytdlp_progress(&line) {
                                 // Only update DB if enough time has passed and we have pool + archive_id
-                                if let (Some(id), Some(db_pool)) = (archive_id, pool) {
+                                if let Some((id, db_pool)) = progress_target {
                                     if last_update.elapsed() >= update_interval {
                                         let progress_json = serde_json::to_string(&progress)
                                             .unwrap_or_else(|_| "{}".to_string());
@@ -695,12 +819,17 @@ pub async fn download(
         ))??;
 
     // Clear progress from database when done
-    if let (Some(id), Some(db_pool)) = (archive_id, pool) {
+    if let Some((id, db_pool)) = progress_target {
         if let Err(e) = crate::db::clear_archive_progress(db_pool, id).await {
             warn!("Failed to clear progress: {}", e);
         }
     }
 
+    // yt-dlp exits successfully after skipping an oversized file
+    if let Some(size) = oversized {
+        return Err(too_large(size, config.max_artifact_bytes));
+    }
+
     if !status.success() {
         let (has_video_error, has_subtitle_error, stderr) = classify_ytdlp_errors(&stderr_lines);
 
@@ -740,6 +869,14 @@ pub async fn download(
     Ok(metadata)
 }
 
+/// Parse yt-dlp's `--max-filesize` abort line, returning the reported file size.
+///
+/// yt-dlp prints e.g. `[download] File is larger than max-filesize (5242880 bytes > 1048576 bytes). Aborting.`
+fn parse_max_filesize_abort(line: &str) -> Option<u64> {
+    let rest = line.split("larger than max-filesize (").nth(1)?;
+    rest.split_whitespace().next()?.parse().ok()
+}
+
 /// Download supplementary artifacts (subtitles, comments) without re-downloading the video.
 ///
 /// This is used during re-archiving when the video file already exists but
@@ -991,6 +1128,7 @@ async fn extract_comments_from_info_json(
             "is_pinned": comment.get("is_pinned").and_then(|v| v.as_bool()).unwrap_or(false),
             "is_creator": comment.get("author_is_uploader").and_then(|v| v.as_bool()).unwrap_or(false),
             "parent_id": comment.get("parent").and_then(|v| v.as_str()).unwrap_or("root"),
+            "depth": i32::from(comment.get("parent").and_then(|v| v.as_str()).is_some_and(|p| p != "root")),
             "replies": [],  // yt-dlp typically doesn't nest replies in the JSON
         });
 
@@ -998,7 +1136,15 @@ async fn extract_comments_from_info_json(
     }
 
     let extracted_count = processed_comments.len();
-    let limited = total_comments > config.comments_max_count;
+    // yt-dlp stops at max_comments itself, so compare against the platform's
+    // reported total when available.
+    let limited = metadata
+        .get("comment_count")
+        .and_then(serde_json::Value::as_u64)
+        .map_or(
+            total_comments > config.comments_max_count,
+            |platform_total| platform_total > extracted_count as u64,
+        );
 
     // Calculate basic stats
     let top_level_count = processed_comments
@@ -1014,6 +1160,7 @@ async fn extract_comments_from_info_json(
         "content_url": metadata.get("webpage_url").and_then(|v| v.as_str()).unwrap_or(""),
         "content_id": metadata.get("id").and_then(|v| v.as_str()).unwrap_or(""),
         "limited": limited,
+        "truncated": limited,
         "limit_applied": config.comments_max_count,
         "stats": {
             "total_comments": total_comments,
@@ -1051,6 +1198,7 @@ async fn find_and_parse_metadata(work_dir: &Path, _config: &Config) -> Result<Ar
 
     let mut info_file = None;
     let mut video_file = None;
+    let mut audio_file = None;
     let mut thumb_file = None;
     let mut extra_files = Vec::new();
     let mut subtitle_files = Vec::new();
@@ -1063,6 +1211,8 @@ async fn find_and_parse_metadata(work_dir: &Path, _config: &Config) -> Result<Ar
             info_file = Some(path);
         } else if is_video_file(&name) {
             video_file = Some(name.to_string());
+        } else if is_audio_file(&name) {
+            audio_file = Some(name.to_string());
         } else if is_thumbnail(&name) {
             thumb_file = Some(name.to_string());
         } else if is_subtitle_file(&name) {
@@ -1072,6 +1222,12 @@ async fn find_and_parse_metadata(work_dir: &Path, _config: &Config) -> Result<Ar
         }
     }
 
+    // Audio-only downloads (e.g. SoundCloud) have no video file
+    let is_audio = video_file.is_none() && audio_file.is_some();
+    if is_audio {
+        video_file = audio_file;
+    }
+
     // Sanitize and rename video file if found
     if let Some(ref orig_name) = video_file {
         let sanitized = crate::archiver::sanitize_filename(orig_name);
@@ -1150,7 +1306,7 @@ async fn find_and_parse_metadata(work_dir: &Path, _config: &Config) -> Result<Ar
     let extra_files = sanitized_extra_files;
 
     let mut result = ArchiveResult {
-        content_type: "video".to_string(),
+        content_type: if is_audio { "audio" } else { "video" }.to_string(),
         primary_file: video_file,
         thumbnail: thumb_file,
         extra_files,
@@ -1165,8 +1321,14 @@ async fn find_and_parse_metadata(work_dir: &Path, _config: &Config) -> Result<Ar
 
         if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
             result.title = json.get("title").and_then(|v| v.as_str()).map(String::from);
-            result.author = json
-                .get("uploader")
+            // Music sites report the performer as `artist` or `creator`
+            let artist = if is_audio {
+                json.get("artist").or_else(|| json.get("creator"))
+            } else {
+                None
+            };
+            result.author = artist
+                .or_else(|| json.get("uploader"))
                 .or_else(|| json.get("channel"))
                 .and_then(|v| v.as_str())
                 .map(String::from);
@@ -1209,6 +1371,11 @@ fn is_video_file(name: &str) -> bool {
     video_exts.iter().any(|ext| name.ends_with(ext))
 }
 
+fn is_audio_file(name: &str) -> bool {
+    let audio_exts = [".mp3", ".m4a", ".opus", ".ogg", ".flac", ".wav", ".aac"];
+    audio_exts.iter().any(|ext| name.ends_with(ext))
+}
+
 fn is_thumbnail(name: &str) -> bool {
     let thumb_exts = [".jpg", ".jpeg", ".png", ".webp"];
     thumb_exts.iter().any(|ext| name.ends_with(ext))
@@ -1461,7 +1628,9 @@ pub async fn fetch_metadata_only(url: &str, cookies: &CookieOptions<'_>) -> Resu
 /// This function downloads only the comments for a video/post using yt-dlp's
 /// --skip-download flag. Useful for background comment extraction jobs.
 ///
-/// Returns the number of comments extracted.
+/// Returns the number of comments extracted and whether the configured cap
+/// truncated them, or [`CommentExtraction::Disabled`] when yt-dlp reports the
+/// video has comments turned off.
 ///
 /// # Errors
 ///
@@ -1473,7 +1642,13 @@ pub async fn extract_comments_only(
     config: &Config,
     archive_id: Option<i64>,
     pool: Option<&SqlitePool>,
-) -> Result<usize> {
+) -> Result<CommentExtraction> {
+    // Share the yt-dlp semaphore with downloads so comment jobs don't add to 429s
+    let _permit = YTDLP_SEMAPHORE
+        .acquire()
+        .await
+        .context("Failed to acquire yt-dlp semaphore")?;
+
     let mut args = vec![
         "-4".to_string(),
         "--no-playlist".to_string(),
@@ -1536,12 +1711,15 @@ pub async fn extract_comments_only(
     let stderr_reader = BufReader::new(stderr);
     let stdout_reader = BufReader::new(stdout);
 
-    // Stream stderr for log output
+    // Stream stderr for log output, noting whether comments are turned off
     let stderr_handle = tokio::spawn(async move {
+        let mut comments_disabled = false;
         let mut lines = stderr_reader.lines();
         while let Ok(Some(line)) = lines.next_line().await {
             debug!("yt-dlp: {line}");
+            comments_disabled |= is_comments_disabled_message(&line);
         }
+        comments_disabled
     });
 
     // Stream stdout for progress tracking
@@ -1597,7 +1775,8 @@ pub async fn extract_comments_only(
         .context("Comment extraction timed out")??;
 
     // Wait for stream handlers to finish
-    let _ = tokio::join!(stderr_handle, stdout_handle);
+    let (comments_disabled, _) = tokio::join!(stderr_handle, stdout_handle);
+    let comments_disabled = comments_disabled.unwrap_or(false);
 
     if !status.success() {
         anyhow::bail!("yt-dlp comment extraction failed with status: {status}");
@@ -1605,6 +1784,7 @@ pub async fn extract_comments_only(
 
     // Find and parse the comments.json file
     let mut comment_count = 0;
+    let mut truncated = false;
     for entry in std::fs::read_dir(work_dir).context("Failed to read work directory")? {
         let entry = entry?;
         let path = entry.path();
@@ -1630,6 +1810,10 @@ pub async fn extract_comments_only(
                                 comment_count = count as usize;
                                 info!("Extracted {comment_count} comments from {}", path.display());
                             }
+                            truncated = json
+                                .get("truncated")
+                                .and_then(serde_json::Value::as_bool)
+                                .unwrap_or(false);
                         }
                     }
                 }
@@ -1639,10 +1823,17 @@ pub async fn extract_comments_only(
     }
 
     if comment_count == 0 {
+        if comments_disabled {
+            info!("Comments are disabled for {url}");
+            return Ok(CommentExtraction::Disabled);
+        }
         warn!("No comments were extracted from {url}");
     }
 
-    Ok(comment_count)
+    Ok(CommentExtraction::Extracted {
+        comment_count,
+        truncated,
+    })
 }
 
 /// Periodically update yt-dlp and gallery-dl to keep them fresh.
@@ -1754,6 +1945,20 @@ pub async fn run_update_loop() {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_max_filesize_abort() {
+        assert_eq!(
+            parse_max_filesize_abort(
+                "[download] File is larger than max-filesize (5242880 bytes > 1048576 bytes). Aborting."
+            ),
+            Some(5_242_880)
+        );
+        assert_eq!(
+            parse_max_filesize_abort("[download]  42.0% of 10.00MiB at 1.00MiB/s ETA 00:06"),
+            None
+        );
+    }
+
     #[test]
     fn test_detect_tiktok_video() {
         // TikTok video with h264 video codec
@@ -1956,4 +2161,29 @@ mod tests {
         assert!(!has_video_error, "Should not detect video error");
         assert!(!has_subtitle_error, "Should not detect subtitle error");
     }
+
+    #[tokio::test]
+    async fn test_find_and_parse_metadata_audio_only() {
+        let dir = tempfile::TempDir::new().unwrap();
+        tokio::fs::write(dir.path().join("Song.NA.m4a"), b"audio")
+            .await
+            .unwrap();
+        tokio::fs::write(
+            dir.path().join("Song.NA.info.json"),
+            r#"{"title":"Song","uploader":"label","artist":"Band","duration":215.0}"#,
+        )
+        .await
+        .unwrap();
+
+        let result = find_and_parse_metadata(dir.path(), &Config::for_testing())
+            .await
+            .unwrap();
+        assert_eq!(result.content_type, "audio");
+        assert_eq!(result.primary_file.as_deref(), Some("Song_NA.m4a"));
+        assert_eq!(result.title.as_deref(), Some("Song"));
+        assert_eq!(result.author.as_deref(), Some("Band"));
+        assert!(result
+            .metadata_json
+            .is_some_and(|m| m.contains("\"duration\":215.0")));
+    }
 }
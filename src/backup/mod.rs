@@ -2,12 +2,17 @@
 //!
 //! Provides functionality to backup the `SQLite` database, compress it with zstd,
 //! and upload to S3 with retention policies.
+//!
+//! Backup keys embed a prefix of the snapshot's sha256, so a snapshot identical
+//! to the most recent backup can be detected from the S3 listing alone and its
+//! upload skipped.
 
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
 use tokio::fs;
 use tracing::{debug, error, info, warn};
 
@@ -22,8 +27,21 @@ pub struct BackupManager {
     s3_client: S3Client,
     s3_prefix: String,
     retention_count: usize,
+    skip_unchanged: bool,
+}
+
+/// Result of a single backup run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackupOutcome {
+    /// A new backup was uploaded to this S3 key.
+    Uploaded(String),
+    /// The database matched the latest backup at this S3 key; nothing was uploaded.
+    Unchanged(String),
 }
 
+/// Number of hex characters of the snapshot sha256 stored in backup keys.
+const KEY_HASH_LEN: usize = 16;
+
 impl BackupManager {
     /// Create a new backup manager.
     #[must_use]
@@ -34,6 +52,7 @@ impl BackupManager {
             s3_client,
             s3_prefix: format!("{}backups/", config.s3_prefix),
             retention_count: config.backup_retention_count,
+            skip_unchanged: config.backup_skip_unchanged,
         }
     }
 
@@ -50,27 +69,27 @@ impl BackupManager {
 
             // Run backup
             match self.run_backup().await {
-                Ok(key) => info!(s3_key = %key, "Database backup completed successfully"),
+                Ok(BackupOutcome::Uploaded(key)) => {
+                    info!(s3_key = %key, "Database backup completed successfully");
+                }
+                Ok(BackupOutcome::Unchanged(key)) => {
+                    info!(latest = %key, "Backup unchanged, skipped upload");
+                }
                 Err(e) => error!("Database backup failed: {e:#}"),
             }
         }
     }
 
-    /// Perform a database backup: VACUUM INTO, compress, upload, cleanup.
+    /// Perform a database backup: VACUUM INTO, hash, compress, upload, cleanup.
     ///
-    /// # Returns
-    ///
-    /// Returns the S3 key of the uploaded backup on success.
+    /// If `skip_unchanged` is enabled and the snapshot hash matches the most
+    /// recent backup in S3, the upload is skipped.
     ///
     /// # Errors
     ///
     /// Returns an error if any step fails.
-    pub async fn run_backup(&self) -> Result<String> {
+    pub async fn run_backup(&self) -> Result<BackupOutcome> {
         let timestamp = Utc::now();
-        let backup_name = format!(
-            "archive-backup-{}.sqlite.zst",
-            timestamp.format("%Y%m%d-%H%M%S")
-        );
 
         // Create temp directory for this backup
         let backup_dir = self.work_dir.join("backup");
@@ -79,16 +98,38 @@ impl BackupManager {
             .context("Failed to create backup directory")?;
 
         let raw_backup_path = backup_dir.join(format!("backup-{}.sqlite", timestamp.timestamp()));
-        let compressed_path = backup_dir.join(&backup_name);
 
         info!(db_path = ?self.db_path, "Starting database backup");
 
         // Step 1: VACUUM INTO to create a consistent backup
-        self.vacuum_into(&raw_backup_path)
+        vacuum_into(&self.db_path, &raw_backup_path)
             .await
             .context("VACUUM INTO failed")?;
 
-        // Step 2: Compress with zstd
+        // Step 2: Hash the snapshot and compare against the latest backup
+        let hash = sha256_file(&raw_backup_path)
+            .await
+            .context("Failed to hash backup")?;
+
+        if self.skip_unchanged {
+            match self.list_backups().await {
+                Ok(backups) => {
+                    if let Some(latest) = find_unchanged_backup(&backups, &hash) {
+                        info!(latest = %latest.key, "Backup unchanged, skipped upload");
+                        if let Err(e) = fs::remove_file(&raw_backup_path).await {
+                            warn!(path = ?raw_backup_path, "Failed to remove raw backup file: {e}");
+                        }
+                        return Ok(BackupOutcome::Unchanged(latest.key.clone()));
+                    }
+                }
+                Err(e) => warn!("Failed to list backups, uploading anyway: {e:#}"),
+            }
+        }
+
+        let backup_name = backup_file_name(timestamp, &hash);
+        let compressed_path = backup_dir.join(&backup_name);
+
+        // Step 3: Compress with zstd
         self.compress_zstd(&raw_backup_path, &compressed_path)
             .await
             .context("Compression failed")?;
@@ -98,7 +139,7 @@ impl BackupManager {
             warn!(path = ?raw_backup_path, "Failed to remove raw backup file: {e}");
         }
 
-        // Step 3: Upload to S3
+        // Step 4: Upload to S3
         let s3_key = format!("{}{}", self.s3_prefix, backup_name);
         self.s3_client
             .upload_file(&compressed_path, &s3_key, None)
@@ -110,44 +151,12 @@ impl BackupManager {
             warn!(path = ?compressed_path, "Failed to remove compressed backup file: {e}");
         }
 
-        // Step 4: Apply retention policy
+        // Step 5: Apply retention policy
         if let Err(e) = self.apply_retention().await {
             warn!("Failed to apply backup retention: {e}");
         }
 
-        Ok(s3_key)
-    }
-
-    /// Use VACUUM INTO to create a consistent backup of the database.
-    async fn vacuum_into(&self, output_path: &Path) -> Result<()> {
-        let db_path_str = self.db_path.to_string_lossy().to_string();
-        let output_path_str = output_path.to_string_lossy().to_string();
-
-        debug!(db = %db_path_str, output = %output_path_str, "Running VACUUM INTO");
-
-        // We need to run VACUUM INTO via a separate SQLite connection
-        // to avoid locking the main database for too long
-        let conn = sqlx::sqlite::SqlitePoolOptions::new()
-            .max_connections(1)
-            .connect(&format!("sqlite://{db_path_str}?mode=ro"))
-            .await
-            .context("Failed to open database for backup")?;
-
-        // VACUUM INTO creates a complete copy of the database
-        let query = format!("VACUUM INTO '{}'", output_path_str.replace('\'', "''"));
-        sqlx::query(&query)
-            .execute(&conn)
-            .await
-            .context("VACUUM INTO query failed")?;
-
-        conn.close().await;
-
-        let metadata = fs::metadata(output_path).await?;
-        #[allow(clippy::cast_precision_loss)]
-        let size_mb = metadata.len() as f64 / 1_048_576.0;
-        info!(size_mb, "VACUUM INTO completed");
-
-        Ok(())
+        Ok(BackupOutcome::Uploaded(s3_key))
     }
 
     /// Compress a file using zstd.
@@ -273,20 +282,34 @@ impl BackupManager {
 struct BackupInfo {
     key: String,
     timestamp: DateTime<Utc>,
+    /// Leading hex characters of the snapshot sha256, if present in the key.
+    content_hash: Option<String>,
 }
 
 /// Parse backup key to extract timestamp.
 fn parse_backup_key(key: &str) -> Option<BackupInfo> {
-    // Expected format: prefix/archive-backup-YYYYMMDD-HHMMSS.sqlite.zst
+    // Expected format: prefix/archive-backup-YYYYMMDD-HHMMSS[-HASH].sqlite.zst
     let filename = key.rsplit('/').next()?;
     if !filename.starts_with("archive-backup-") || !filename.ends_with(".sqlite.zst") {
         return None;
     }
 
-    let timestamp_str = filename
+    let stem = filename
         .strip_prefix("archive-backup-")?
         .strip_suffix(".sqlite.zst")?;
 
+    // Older backups have no hash suffix
+    let (timestamp_str, content_hash) = match stem.get(15..) {
+        Some(rest) if !rest.is_empty() => {
+            let hash = rest.strip_prefix('-')?;
+            if hash.is_empty() || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                return None;
+            }
+            (&stem[..15], Some(hash.to_string()))
+        }
+        _ => (stem, None),
+    };
+
     // Parse YYYYMMDD-HHMMSS
     let timestamp = chrono::NaiveDateTime::parse_from_str(timestamp_str, "%Y%m%d-%H%M%S").ok()?;
     let timestamp = timestamp.and_utc();
@@ -294,7 +317,71 @@ fn parse_backup_key(key: &str) -> Option<BackupInfo> {
     Some(BackupInfo {
         key: key.to_string(),
         timestamp,
+        content_hash,
+    })
+}
+
+/// Use VACUUM INTO to create a consistent backup of the database.
+async fn vacuum_into(db_path: &Path, output_path: &Path) -> Result<()> {
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let output_path_str = output_path.to_string_lossy().to_string();
+
+    debug!(db = %db_path_str, output = %output_path_str, "Running VACUUM INTO");
+
+    // We need to run VACUUM INTO via a separate SQLite connection
+    // to avoid locking the main database for too long
+    let conn = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect(&format!("sqlite://{db_path_str}?mode=ro"))
+        .await
+        .context("Failed to open database for backup")?;
+
+    // VACUUM INTO creates a complete copy of the database
+    let query = format!("VACUUM INTO '{}'", output_path_str.replace('\'', "''"));
+    sqlx::query(&query)
+        .execute(&conn)
+        .await
+        .context("VACUUM INTO query failed")?;
+
+    conn.close().await;
+
+    let metadata = fs::metadata(output_path).await?;
+    #[allow(clippy::cast_precision_loss)]
+    let size_mb = metadata.len() as f64 / 1_048_576.0;
+    info!(size_mb, "VACUUM INTO completed");
+
+    Ok(())
+}
+
+/// Compute the hex-encoded sha256 of a file.
+async fn sha256_file(path: &Path) -> Result<String> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let mut file = std::fs::File::open(&path).context("Failed to open file for hashing")?;
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut file, &mut hasher).context("Failed to read file for hashing")?;
+        Ok::<_, anyhow::Error>(hex::encode(hasher.finalize()))
     })
+    .await
+    .context("Hashing task panicked")?
+}
+
+/// Build the backup file name for a snapshot taken at `timestamp` with `hash`.
+fn backup_file_name(timestamp: DateTime<Utc>, hash: &str) -> String {
+    format!(
+        "archive-backup-{}-{}.sqlite.zst",
+        timestamp.format("%Y%m%d-%H%M%S"),
+        &hash[..KEY_HASH_LEN.min(hash.len())]
+    )
+}
+
+/// Return the most recent backup if its content hash matches `hash`.
+///
+/// Backups from before hashes were stored in keys never match.
+fn find_unchanged_backup<'a>(backups: &'a [BackupInfo], hash: &str) -> Option<&'a BackupInfo> {
+    let latest = backups.iter().max_by_key(|b| b.timestamp)?;
+    let latest_hash = latest.content_hash.as_deref()?;
+    hash.starts_with(latest_hash).then_some(latest)
 }
 
 impl std::fmt::Debug for BackupManager {
@@ -303,6 +390,7 @@ impl std::fmt::Debug for BackupManager {
             .field("db_path", &self.db_path)
             .field("s3_prefix", &self.s3_prefix)
             .field("retention_count", &self.retention_count)
+            .field("skip_unchanged", &self.skip_unchanged)
             .finish_non_exhaustive()
     }
 }
@@ -325,6 +413,91 @@ mod tests {
         assert_eq!(info.timestamp.second(), 22);
     }
 
+    #[test]
+    fn test_parse_backup_key_with_hash() {
+        let key = "archives/backups/archive-backup-20240115-143022-0123456789abcdef.sqlite.zst";
+        let info = parse_backup_key(key).unwrap();
+        assert_eq!(info.timestamp.year(), 2024);
+        assert_eq!(info.timestamp.second(), 22);
+        assert_eq!(info.content_hash.as_deref(), Some("0123456789abcdef"));
+
+        let legacy = parse_backup_key("archive-backup-20240115-143022.sqlite.zst").unwrap();
+        assert!(legacy.content_hash.is_none());
+    }
+
+    /// Run the snapshot/compare half of `run_backup`, recording an "upload"
+    /// in `uploaded` unless the snapshot matches the latest one.
+    async fn simulate_backup(
+        db_path: &Path,
+        dir: &Path,
+        timestamp: DateTime<Utc>,
+        uploaded: &mut Vec<String>,
+    ) {
+        let raw = dir.join(format!("backup-{}.sqlite", timestamp.timestamp()));
+        vacuum_into(db_path, &raw).await.unwrap();
+        let hash = sha256_file(&raw).await.unwrap();
+        std::fs::remove_file(&raw).unwrap();
+
+        let backups: Vec<BackupInfo> = uploaded
+            .iter()
+            .filter_map(|k| parse_backup_key(k))
+            .collect();
+        if find_unchanged_backup(&backups, &hash).is_none() {
+            uploaded.push(format!("backups/{}", backup_file_name(timestamp, &hash)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_database_uploaded_once() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db_path = dir.path().join("test.db");
+
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(
+                sqlx::sqlite::SqliteConnectOptions::new()
+                    .filename(&db_path)
+                    .create_if_missing(true),
+            )
+            .await
+            .unwrap();
+        sqlx::query("CREATE TABLE t (id INTEGER PRIMARY KEY, v TEXT)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO t (v) VALUES ('a')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let start = Utc::now();
+        let mut uploaded = Vec::new();
+        simulate_backup(&db_path, dir.path(), start, &mut uploaded).await;
+        simulate_backup(
+            &db_path,
+            dir.path(),
+            start + chrono::Duration::hours(1),
+            &mut uploaded,
+        )
+        .await;
+        assert_eq!(uploaded.len(), 1);
+
+        sqlx::query("INSERT INTO t (v) VALUES ('b')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        simulate_backup(
+            &db_path,
+            dir.path(),
+            start + chrono::Duration::hours(2),
+            &mut uploaded,
+        )
+        .await;
+        assert_eq!(uploaded.len(), 2);
+
+        pool.close().await;
+    }
+
     #[test]
     fn test_parse_backup_key_invalid() {
         assert!(parse_backup_key("random-file.txt").is_none());
@@ -0,0 +1,217 @@
+//! Dry-run configuration check (`--check-config`).
+//!
+//! Loads and validates the configuration and probes the external services it
+//! points at, without opening the database, binding ports or starting workers.
+//! The report is meant for CI/CD pipelines and first-time deployers.
+
+use std::fmt;
+
+use crate::config::Config;
+use crate::ipfs::IpfsClient;
+use crate::s3::S3Client;
+
+/// Command-line flag that runs the check instead of the service.
+pub const CHECK_CONFIG_FLAG: &str = "--check-config";
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+    Skipped,
+}
+
+impl CheckStatus {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Warn => "warn",
+            Self::Fail => "FAIL",
+            Self::Skipped => "skip",
+        }
+    }
+}
+
+/// One line of the report.
+#[derive(Debug, Clone)]
+pub struct CheckItem {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+/// Results of a configuration check.
+#[derive(Debug, Clone, Default)]
+pub struct CheckReport {
+    pub items: Vec<CheckItem>,
+    /// Optional features that are switched on.
+    pub enabled: Vec<&'static str>,
+    /// Optional features that are switched off.
+    pub disabled: Vec<&'static str>,
+}
+
+impl CheckReport {
+    fn push(&mut self, name: &'static str, status: CheckStatus, detail: impl Into<String>) {
+        self.items.push(CheckItem {
+            name,
+            status,
+            detail: detail.into(),
+        });
+    }
+
+    /// Number of failed checks.
+    pub fn failures(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.status == CheckStatus::Fail)
+            .count()
+    }
+
+    /// Whether every check passed (warnings allowed).
+    pub fn is_ok(&self) -> bool {
+        self.failures() == 0
+    }
+}
+
+impl fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Configuration check")?;
+        for item in &self.items {
+            writeln!(
+                f,
+                "  [{:<4}] {}: {}",
+                item.status.label(),
+                item.name,
+                item.detail
+            )?;
+        }
+        if !self.enabled.is_empty() {
+            writeln!(f, "Enabled: {}", self.enabled.join(", "))?;
+        }
+        if !self.disabled.is_empty() {
+            writeln!(f, "Disabled: {}", self.disabled.join(", "))?;
+        }
+        match self.failures() {
+            0 => writeln!(f, "Result: OK"),
+            n => writeln!(f, "Result: FAILED ({n} problem(s))"),
+        }
+    }
+}
+
+/// Load the configuration from the environment and check it.
+pub async fn run() -> CheckReport {
+    let mut report = CheckReport::default();
+
+    let config = match Config::from_env() {
+        Ok(config) => config,
+        Err(e) => {
+            report.push("config", CheckStatus::Fail, e.to_string());
+            return report;
+        }
+    };
+    report.push("config", CheckStatus::Ok, "loaded from environment");
+
+    match config.validate() {
+        Ok(()) => report.push("validate", CheckStatus::Ok, "all values in range"),
+        Err(e) => report.push("validate", CheckStatus::Fail, e.to_string()),
+    }
+
+    check_services(&config, &mut report).await;
+    list_features(&config, &mut report);
+    report
+}
+
+/// Probe S3 (read-only) and, when enabled, the IPFS daemon.
+async fn check_services(config: &Config, report: &mut CheckReport) {
+    let target = config.s3_endpoint.as_deref().map_or_else(
+        || format!("bucket {} ({})", config.s3_bucket, config.s3_region),
+        |endpoint| format!("bucket {} at {endpoint}", config.s3_bucket),
+    );
+    match S3Client::new(config).await {
+        Ok(s3) => match s3.check_connectivity().await {
+            Ok(()) => report.push("s3", CheckStatus::Ok, format!("{target} reachable")),
+            Err(e) => report.push("s3", CheckStatus::Fail, format!("{target}: {e:#}")),
+        },
+        Err(e) => report.push("s3", CheckStatus::Fail, format!("{e:#}")),
+    }
+
+    if config.ipfs_enabled {
+        let ipfs = IpfsClient::new(config);
+        match ipfs.health_check().await {
+            Ok(true) => report.push(
+                "ipfs",
+                CheckStatus::Ok,
+                format!("daemon reachable at {}", config.ipfs_api_url),
+            ),
+            Ok(false) | Err(_) => report.push(
+                "ipfs",
+                CheckStatus::Fail,
+                format!("daemon not reachable at {}", config.ipfs_api_url),
+            ),
+        }
+    } else {
+        report.push("ipfs", CheckStatus::Skipped, "disabled");
+    }
+
+    if let Some(ref path) = config.cookies_file_path {
+        if path.exists() {
+            report.push("cookies", CheckStatus::Ok, path.display().to_string());
+        } else {
+            report.push(
+                "cookies",
+                CheckStatus::Warn,
+                format!("{} not found", path.display()),
+            );
+        }
+    }
+}
+
+/// Record which optional features are switched on.
+fn list_features(config: &Config, report: &mut CheckReport) {
+    let features = [
+        ("wayback", config.wayback_enabled),
+        ("archive_today", config.archive_today_enabled),
+        ("ipfs", config.ipfs_enabled),
+        ("backups", config.backup_enabled),
+        ("submissions", config.submission_enabled),
+        ("screenshots", config.screenshot_enabled),
+        ("pdf", config.pdf_enabled),
+        ("mhtml", config.mhtml_enabled),
+        ("monolith", config.monolith_enabled),
+        ("comments", config.comments_enabled),
+        ("dedup", config.dedup_enabled),
+        ("whisper", config.whisper_enabled),
+        ("nsfw_classifier", config.nsfw_classifier_enabled),
+        ("change_watch", config.change_watch_enabled),
+        ("tls", config.tls_enabled),
+        ("dry_run", config.archive_dry_run),
+    ];
+    for (name, enabled) in features {
+        if enabled {
+            report.enabled.push(name);
+        } else {
+            report.disabled.push(name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_result_line() {
+        let mut report = CheckReport::default();
+        report.push("config", CheckStatus::Ok, "loaded from environment");
+        report.push("cookies", CheckStatus::Warn, "cookies.txt not found");
+        assert!(report.is_ok());
+        let text = report.to_string();
+        assert!(text.contains("[ok  ] config: loaded from environment"));
+        assert!(text.ends_with("Result: OK\n"));
+
+        report.push("s3", CheckStatus::Fail, "access denied");
+        assert!(!report.is_ok());
+        assert!(report.to_string().contains("Result: FAILED (1 problem(s))"));
+    }
+}
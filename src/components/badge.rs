@@ -201,7 +201,7 @@ impl MediaTypeVariant {
             "text" => Self::Text,
             "thread" => Self::Thread,
             "playlist" => Self::Playlist,
-            "pdf" => Self::Pdf,
+            "pdf" | "document" => Self::Pdf,
             "mixed" => Self::Mixed,
             _ => Self::Unknown,
         }
@@ -371,8 +371,8 @@ impl ArtifactKindVariant {
             "completehtml" => Self::CompleteHtml,
             "viewhtml" => Self::ViewHtml,
             "rawhtml" => Self::RawHtml,
-            "screenshot" => Self::Screenshot,
-            "pdf" => Self::Pdf,
+            "screenshot" | "postsnapshot" => Self::Screenshot,
+            "pdf" | "document" => Self::Pdf,
             "video" => Self::Video,
             "thumb" | "thumbnail" => Self::Thumb,
             "metadata" | "meta" => Self::Metadata,
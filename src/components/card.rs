@@ -2,10 +2,15 @@
 //!
 //! This module provides maud components for rendering archive cards and grids.
 
+use std::collections::HashMap;
+
 use maud::{html, Markup, Render};
 
 use crate::components::badge::{DomainBadge, MediaTypeBadge, NsfwBadge, SizeBadge, StatusBadge};
-use crate::db::ArchiveDisplay;
+use crate::db::{ArchiveArtifact, ArchiveDisplay};
+
+/// Thumbnail size assumed when an artifact has no stored dimensions (16:9).
+const DEFAULT_THUMB_SIZE: (i64, i64) = (320, 180);
 
 /// An archive card component for displaying archive summaries.
 ///
@@ -130,6 +135,8 @@ impl Render for ArchiveCard<'_> {
 pub struct ArchiveGrid<'a> {
     pub archives: &'a [ArchiveDisplay],
     pub show_post_links: bool,
+    /// Thumbnail artifacts keyed by archive ID
+    pub thumbnails: Option<&'a HashMap<i64, ArchiveArtifact>>,
 }
 
 impl<'a> ArchiveGrid<'a> {
@@ -139,6 +146,7 @@ impl<'a> ArchiveGrid<'a> {
         Self {
             archives,
             show_post_links: false,
+            thumbnails: None,
         }
     }
 
@@ -148,6 +156,16 @@ impl<'a> ArchiveGrid<'a> {
         self.show_post_links = true;
         self
     }
+
+    /// Show thumbnails for archives that have one.
+    #[must_use]
+    pub const fn with_thumbnails(
+        mut self,
+        thumbnails: Option<&'a HashMap<i64, ArchiveArtifact>>,
+    ) -> Self {
+        self.thumbnails = thumbnails;
+        self
+    }
 }
 
 impl Render for ArchiveGrid<'_> {
@@ -155,7 +173,9 @@ impl Render for ArchiveGrid<'_> {
         html! {
             div class="archive-grid" {
                 @for archive in self.archives {
-                    @if self.show_post_links {
+                    @if let Some(thumb) = self.thumbnails.and_then(|t| t.get(&archive.id)) {
+                        (ArchiveCardWithThumb::new(archive).with_thumb_artifact(thumb))
+                    } @else if self.show_post_links {
                         (ArchiveCard::new(archive).with_post_link())
                     } @else {
                         (ArchiveCard::new(archive))
@@ -205,10 +225,18 @@ impl Render for EmptyState<'_> {
 }
 
 /// A card with thumbnail support for archives with images.
+///
+/// Thumbnails are lazily loaded and given explicit dimensions, with the
+/// stored placeholder colour painted behind them, so the card keeps a stable
+/// layout until the image arrives.
 #[derive(Debug, Clone)]
 pub struct ArchiveCardWithThumb<'a> {
     pub archive: &'a ArchiveDisplay,
-    pub thumb_url: Option<&'a str>,
+    pub thumb_url: Option<String>,
+    /// Placeholder colour (`#rrggbb`) shown until the thumbnail loads
+    pub placeholder_color: Option<&'a str>,
+    /// Thumbnail width and height in pixels
+    pub thumb_size: Option<(i64, i64)>,
 }
 
 impl<'a> ArchiveCardWithThumb<'a> {
@@ -218,15 +246,37 @@ impl<'a> ArchiveCardWithThumb<'a> {
         Self {
             archive,
             thumb_url: None,
+            placeholder_color: None,
+            thumb_size: None,
         }
     }
 
     /// Set the thumbnail URL.
     #[must_use]
-    pub const fn with_thumb(mut self, url: &'a str) -> Self {
-        self.thumb_url = Some(url);
+    pub fn with_thumb(mut self, url: &str) -> Self {
+        self.thumb_url = Some(url.to_string());
         self
     }
+
+    /// Use a stored thumbnail artifact, including its placeholder and size.
+    #[must_use]
+    pub fn with_thumb_artifact(mut self, artifact: &'a ArchiveArtifact) -> Self {
+        self.thumb_url = Some(format!("/s3/{}", artifact.s3_key));
+        self.placeholder_color = artifact.placeholder_color.as_deref();
+        self.thumb_size = artifact.width.zip(artifact.height);
+        self
+    }
+
+    /// Inline style painting the placeholder colour, if it is a valid `#rrggbb`.
+    fn placeholder_style(&self) -> Option<String> {
+        self.placeholder_color
+            .filter(|c| {
+                c.len() == 7
+                    && c.starts_with('#')
+                    && c[1..].chars().all(|ch| ch.is_ascii_hexdigit())
+            })
+            .map(|c| format!("background-color: {c}"))
+    }
 }
 
 impl Render for ArchiveCardWithThumb<'_> {
@@ -241,6 +291,10 @@ impl Render for ArchiveCardWithThumb<'_> {
         let type_badge = MediaTypeBadge::from_content_type(content_type);
         let domain_badge = DomainBadge::new(&archive.domain);
         let size_bytes = archive.total_size_bytes.unwrap_or(0);
+        let (thumb_width, thumb_height) = self
+            .thumb_size
+            .filter(|(w, h)| *w > 0 && *h > 0)
+            .unwrap_or(DEFAULT_THUMB_SIZE);
 
         html! {
             article class="archive-card" data-nsfw=[archive.is_nsfw.then_some("true")] {
@@ -262,8 +316,9 @@ impl Render for ArchiveCardWithThumb<'_> {
 
                 // Main card content - hidden when NSFW filter is active
                 div class="archive-card-content" {
-                    @if let Some(thumb) = self.thumb_url {
-                        img class="archive-thumb" src=(thumb) alt=(title) loading="lazy";
+                    @if let Some(thumb) = &self.thumb_url {
+                        img class="archive-thumb" src=(thumb) alt=(title) loading="lazy" decoding="async"
+                            width=(thumb_width) height=(thumb_height) style=[self.placeholder_style()];
                     }
                     h3 {
                         @if archive.is_nsfw {
@@ -510,6 +565,87 @@ mod tests {
 
         assert!(html.contains("archive-thumb"));
         assert!(html.contains("/thumbs/1.jpg"));
+        assert!(html.contains("loading=\"lazy\""));
+        assert!(html.contains("width=\"320\""));
+        assert!(html.contains("height=\"180\""));
+    }
+
+    #[test]
+    fn test_archive_card_with_thumb_artifact_placeholder() {
+        let archive = sample_archive();
+        let artifact = ArchiveArtifact {
+            id: 9,
+            archive_id: archive.id,
+            kind: "thumb".to_string(),
+            s3_key: "archives/1/thumb.jpg".to_string(),
+            content_type: Some("image/jpeg".to_string()),
+            size_bytes: Some(2048),
+            sha256: None,
+            created_at: "2024-01-01 00:00:00".to_string(),
+            perceptual_hash: None,
+            duplicate_of_artifact_id: None,
+            video_file_id: None,
+            metadata: None,
+            placeholder_color: Some("#2080ff".to_string()),
+            width: Some(640),
+            height: Some(480),
+            content_encoding: None,
+        };
+        let html = ArchiveCardWithThumb::new(&archive)
+            .with_thumb_artifact(&artifact)
+            .render()
+            .into_string();
+
+        assert!(html.contains("src=\"/s3/archives/1/thumb.jpg\""));
+        assert!(html.contains("loading=\"lazy\""));
+        assert!(html.contains("width=\"640\""));
+        assert!(html.contains("height=\"480\""));
+        assert!(html.contains("style=\"background-color: #2080ff\""));
+    }
+
+    #[test]
+    fn test_archive_card_rejects_invalid_placeholder_color() {
+        let archive = sample_archive();
+        let mut card = ArchiveCardWithThumb::new(&archive).with_thumb("/thumbs/1.jpg");
+        card.placeholder_color = Some("red; background-image: url(x)");
+        let html = card.render().into_string();
+        assert!(!html.contains("style="));
+    }
+
+    #[test]
+    fn test_archive_grid_uses_thumbnails() {
+        let archives = vec![sample_archive()];
+        let mut thumbs = HashMap::new();
+        thumbs.insert(
+            archives[0].id,
+            ArchiveArtifact {
+                id: 2,
+                archive_id: archives[0].id,
+                kind: "thumb".to_string(),
+                s3_key: "archives/1/thumb.webp".to_string(),
+                content_type: None,
+                size_bytes: None,
+                sha256: None,
+                created_at: String::new(),
+                perceptual_hash: None,
+                duplicate_of_artifact_id: None,
+                video_file_id: None,
+                metadata: None,
+                placeholder_color: None,
+                width: None,
+                height: None,
+                content_encoding: None,
+            },
+        );
+
+        let with = ArchiveGrid::new(&archives)
+            .with_thumbnails(Some(&thumbs))
+            .render()
+            .into_string();
+        assert!(with.contains("archives/1/thumb.webp"));
+
+        let without = ArchiveGrid::new(&archives).render().into_string();
+        assert!(!without.contains("archive-thumb"));
     }
 
     #[test]
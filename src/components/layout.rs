@@ -132,6 +132,7 @@ impl<'a> BaseLayout<'a> {
                         li { a href="/archives/all" { "All Archives" } }
                         li { a href="/threads" { "Threads" } }
                         li { a href="/search" { "Search" } }
+                        li { a href="/random" { "Random" } }
                         li { a href="/submit" { "Submit" } }
                         li { a href="/stats" { "Stats" } }
                         (self.render_auth_nav())
@@ -253,6 +254,7 @@ mod tests {
         assert!(html.contains(r#"<a href="/">Home</a>"#));
         assert!(html.contains(r#"<a href="/threads">Threads</a>"#));
         assert!(html.contains(r#"<a href="/search">Search</a>"#));
+        assert!(html.contains(r#"<a href="/random">Random</a>"#));
         assert!(html.contains(r#"<a href="/submit">Submit</a>"#));
         assert!(html.contains(r#"<a href="/stats">Stats</a>"#));
     }
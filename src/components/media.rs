@@ -115,6 +115,7 @@ impl<'a> AudioPlayer<'a> {
             "m4a" => "audio/mp4",
             "flac" => "audio/flac",
             "aac" => "audio/aac",
+            "opus" => "audio/ogg",
             "webm" => "audio/webm",
             _ => "audio/mpeg", // default
         }
@@ -423,6 +424,7 @@ fn audio_mime_type(extension: &str) -> &'static str {
         "m4a" => "audio/mp4",
         "flac" => "audio/flac",
         "aac" => "audio/aac",
+        "opus" => "audio/ogg",
         _ => "audio/mpeg",
     }
 }
@@ -26,6 +26,8 @@ pub struct OpenGraphMetadata {
     pub twitter_card: String,
     /// Whether this is NSFW content (adds [NSFW] prefix to title)
     pub is_nsfw: bool,
+    /// oEmbed discovery endpoint for this page, if any
+    pub oembed_url: Option<String>,
 }
 
 impl Default for OpenGraphMetadata {
@@ -39,6 +41,7 @@ impl Default for OpenGraphMetadata {
             site_name: "CF Archive".to_string(),
             twitter_card: "summary".to_string(),
             is_nsfw: false,
+            oembed_url: None,
         }
     }
 }
@@ -97,6 +100,13 @@ impl OpenGraphMetadata {
         self
     }
 
+    /// Set the oEmbed discovery URL.
+    #[must_use]
+    pub fn with_oembed_url(mut self, oembed_url: impl Into<String>) -> Self {
+        self.oembed_url = Some(oembed_url.into());
+        self
+    }
+
     /// Get the final title with NSFW prefix if needed.
     fn formatted_title(&self) -> String {
         if self.is_nsfw {
@@ -136,6 +146,10 @@ impl OpenGraphMetadata {
 
             // Standard meta description
             meta name="description" content=(description);
+
+            @if let Some(ref oembed_url) = self.oembed_url {
+                link rel="alternate" type="application/json+oembed" href=(oembed_url) title=(&title);
+            }
         }
     }
 }
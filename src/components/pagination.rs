@@ -22,6 +22,10 @@ pub struct Pagination {
     pub content_type_filter: Option<String>,
     /// Source filter to preserve in links
     pub source_filter: Option<String>,
+    /// Additional query parameters to preserve in links
+    pub extra_params: Vec<(String, String)>,
+    /// Query parameter carrying the page number
+    pub page_param: String,
 }
 
 impl Pagination {
@@ -39,9 +43,19 @@ impl Pagination {
             base_url: base_url.to_string(),
             content_type_filter: None,
             source_filter: None,
+            extra_params: Vec::new(),
+            page_param: "page".to_string(),
         }
     }
 
+    /// Use a different query parameter for the page number, so several
+    /// paginated lists can share one page.
+    #[must_use]
+    pub fn with_page_param(mut self, name: &str) -> Self {
+        self.page_param = name.to_string();
+        self
+    }
+
     /// Add a content type filter to preserve in pagination links.
     #[must_use]
     pub fn with_content_type_filter(mut self, filter: Option<&str>) -> Self {
@@ -56,12 +70,21 @@ impl Pagination {
         self
     }
 
+    /// Add an arbitrary query parameter to preserve in pagination links.
+    #[must_use]
+    pub fn with_param(mut self, key: &str, value: Option<&str>) -> Self {
+        if let Some(value) = value {
+            self.extra_params.push((key.to_string(), value.to_string()));
+        }
+        self
+    }
+
     /// Build URL for a specific page number with all filters preserved.
     fn build_url(&self, page_num: usize) -> String {
         let mut params = Vec::new();
 
         if page_num > 0 {
-            params.push(format!("page={page_num}"));
+            params.push(format!("{}={page_num}", self.page_param));
         }
 
         if let Some(ref ct) = self.content_type_filter {
@@ -74,6 +97,10 @@ impl Pagination {
             params.push(format!("source={encoded}"));
         }
 
+        for (key, value) in &self.extra_params {
+            params.push(format!("{}={}", encode(key), encode(value)));
+        }
+
         if params.is_empty() {
             self.base_url.clone()
         } else {
@@ -185,6 +212,15 @@ mod tests {
         assert_eq!(pagination.build_url(5), "/archives?page=5");
     }
 
+    #[test]
+    fn test_build_url_with_page_param() {
+        let pagination = Pagination::new(0, 10, "/admin")
+            .with_page_param("users_page")
+            .with_param("tab", Some("users"));
+        assert_eq!(pagination.build_url(0), "/admin?tab=users");
+        assert_eq!(pagination.build_url(3), "/admin?users_page=3&tab=users");
+    }
+
     #[test]
     fn test_build_url_with_filters() {
         let pagination = Pagination::new(0, 10, "/")
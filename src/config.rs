@@ -1,9 +1,17 @@
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+use ipnet::IpNet;
+use regex::Regex;
 use serde::Deserialize;
 use thiserror::Error;
 
+use crate::archive_today::{normalize_base_url, ARCHIVE_TODAY_BASE_URL, ARCHIVE_TODAY_DOMAINS};
+use crate::constants::ARCHIVAL_USER_AGENT;
+use crate::webhook::WebhookEvent;
+
 #[derive(Debug, Error)]
 pub enum ConfigError {
     #[error("missing required configuration: {0}")]
@@ -37,11 +45,28 @@ pub struct Config {
     // RSS Feed
     pub rss_url: String,
     pub poll_interval: Duration,
+    /// Per-feed poll interval overrides, keyed by feed URL.
+    pub poll_interval_overrides: BTreeMap<String, Duration>,
+    /// Upper bound on the random delay added to each poll cycle.
+    pub poll_jitter: Duration,
     pub cache_window: Duration,
     pub rss_max_pages: usize,
+    /// Ingest a feed's full history on its first poll, ignoring `rss_max_pages`.
+    pub rss_backfill: bool,
+    /// Upper bound on the pages one poll backfills; later polls resume.
+    pub rss_backfill_max_pages: usize,
+    /// Extracts the forum username from a post's raw author string (the
+    /// `handle` group, or the first group); unmatched authors use the default rules.
+    pub forum_author_pattern: Option<Regex>,
 
     // Database
     pub database_path: PathBuf,
+    /// Connections in each of the read-write and read-only pools.
+    pub db_max_connections: u32,
+    /// How long a connection waits on a locked database before `SQLITE_BUSY`.
+    pub db_busy_timeout_secs: u64,
+    /// Page cache per connection, in KiB (`PRAGMA cache_size = -N`).
+    pub db_cache_size_kb: u32,
 
     // S3 Storage
     pub s3_bucket: String,
@@ -49,6 +74,15 @@ pub struct Config {
     pub s3_endpoint: Option<String>,
     pub s3_prefix: String,
     pub s3_public_url_base: Option<String>,
+    /// Storage class for large media uploads (e.g. `STANDARD_IA`, `GLACIER_IR`).
+    /// Only applied on AWS S3; custom endpoints (R2, MinIO) ignore it.
+    pub s3_storage_class: Option<String>,
+    /// Retries for S3 uploads that fail with a transient error (5xx,
+    /// throttling, timeouts, dropped connections). Permanent errors fail at once.
+    pub s3_upload_max_retries: u32,
+    /// Hash artifacts fetched by the S3 proxy and refuse (502) any whose
+    /// SHA-256 no longer matches the stored one. Range requests are not checked.
+    pub verify_downloads: bool,
 
     // Archive Workers
     pub worker_concurrency: usize,
@@ -63,15 +97,62 @@ pub struct Config {
     pub youtube_max_duration_seconds: Option<u32>,
     pub youtube_download_timeout_seconds: u64,
     pub youtube_request_delay_ms: u64,
+    pub youtube_playlist_max_items: usize,
+    /// Minutes between checks of a live stream or Space that isn't downloadable yet.
+    pub live_defer_interval_minutes: u64,
+    /// Hours after queueing during which live content is deferred rather than failed (0 = never defer).
+    pub live_defer_max_hours: u64,
 
     // Archive Policy
     pub archive_mode: ArchiveMode,
+    /// Archive links whose first occurrence is inside a quote; overridable per domain.
     pub archive_quote_only_links: bool,
+    /// Forum usernames whose posts are archived; empty means everyone.
+    pub archive_author_allowlist: Vec<String>,
+    /// Also archive each new forum post's own page, even if the forum domain is excluded.
+    pub archive_post_snapshots: bool,
+    /// Skip generic pages whose `X-Robots-Tag` header or robots meta tag says `noarchive`.
+    pub respect_noarchive: bool,
+    /// Extract the main article of generic pages into an `article` artifact and use its text for search.
+    pub reader_mode: bool,
+    /// Attempts a failed archive gets before it stops being retried.
+    pub archive_max_retries: i32,
+    /// Largest download (bytes) an archive may store; larger ones are skipped. 0 disables.
+    pub max_artifact_bytes: u64,
+    /// Phrases marking a 200 page as a soft 404 when found in its title or short body.
+    pub soft_unavailable_markers: Vec<String>,
+    /// Class name fragments marking a page as paywalled when its text is only a teaser.
+    pub soft_unavailable_paywall_classes: Vec<String>,
+    /// Route and log pending archives without downloading, uploading or submitting anything.
+    pub archive_dry_run: bool,
+    /// Delete failed archives (retries exhausted) older than this many days; 0 disables.
+    pub archive_retention_days: u32,
+    /// Also prune `skipped` archives older than the retention period.
+    pub archive_retention_prune_skipped: bool,
+    /// Log what retention would delete without deleting anything.
+    pub archive_retention_dry_run: bool,
+    /// User agent for generic page fetches (generic handler, noarchive probe,
+    /// change watch). Wayback, Archive.today and site handlers keep their own.
+    pub archival_user_agent: String,
+    /// Extra headers (e.g. `Accept-Language`) sent with generic page fetches.
+    pub archival_extra_headers: Vec<(String, String)>,
 
     // Web Server
+    /// One or more comma-separated IP addresses to listen on (e.g. `0.0.0.0,::`).
     pub web_host: String,
     pub web_port: u16,
     pub public_base_url: String,
+    /// Per-request timeout for handlers (large download routes are exempt).
+    pub web_request_timeout_secs: u64,
+    /// Maximum request body size in bytes (large download routes are exempt).
+    pub web_max_body_bytes: usize,
+    /// Static files directory, checked before the built-in candidates.
+    pub static_dir: Option<PathBuf>,
+    /// Peers whose `Forwarded`/`X-Forwarded-For` headers are believed.
+    /// Empty means only loopback peers are trusted.
+    pub trusted_proxies: Vec<IpNet>,
+    /// Bearer token accepted by `/api/queue.json` in place of an admin session.
+    pub queue_api_token: Option<String>,
 
     // TLS / Let's Encrypt
     pub tls_enabled: bool,
@@ -80,6 +161,10 @@ pub struct Config {
     pub tls_cache_dir: PathBuf,
     pub tls_use_staging: bool,
     pub tls_https_port: u16,
+    /// PEM certificate chain to serve instead of obtaining one via ACME.
+    pub tls_cert_file: Option<PathBuf>,
+    /// PEM private key matching `tls_cert_file`.
+    pub tls_key_file: Option<PathBuf>,
 
     // Wayback Machine
     pub wayback_enabled: bool,
@@ -88,11 +173,14 @@ pub struct Config {
     // Archive.today
     pub archive_today_enabled: bool,
     pub archive_today_rate_limit_per_min: u32,
+    /// Archive.today mirror used for submissions and lookups
+    pub archive_today_base: String,
 
     // Backup
     pub backup_enabled: bool,
     pub backup_interval_hours: u64,
     pub backup_retention_count: usize,
+    pub backup_skip_unchanged: bool,
 
     // Logging
     pub log_format: LogFormat,
@@ -101,6 +189,10 @@ pub struct Config {
     pub ipfs_enabled: bool,
     pub ipfs_api_url: String,
     pub ipfs_gateway_urls: Vec<String>,
+    pub ipfs_pin_timeout_secs: u64,
+    pub ipfs_max_concurrent_pins: usize,
+    pub ipfs_failure_threshold: u32,
+    pub ipfs_cooldown_secs: u64,
 
     // Manual Submission
     pub submission_enabled: bool,
@@ -137,6 +229,7 @@ pub struct Config {
     pub twitter_archive_quoted: bool,
     pub twitter_html_snapshot: bool,
     pub twitter_max_quote_depth: u32,
+    pub twitter_max_thread_length: u32,
 
     // Comment extraction settings
     pub comments_enabled: bool,
@@ -145,6 +238,39 @@ pub struct Config {
     pub comments_platforms: Vec<String>,
     pub comments_max_depth: usize,
     pub comments_request_delay_ms: u64,
+    pub comments_concurrency: usize,
+
+    // Local Whisper transcription fallback
+    pub whisper_enabled: bool,
+    pub whisper_path: String,
+    pub whisper_model: String,
+    pub whisper_language: Option<String>,
+    pub whisper_timeout_secs: u64,
+
+    // Automatic NSFW classification of image/video archives
+    pub nsfw_classifier_enabled: bool,
+    pub nsfw_classifier_command: String,
+    pub nsfw_classifier_threshold: f64,
+    pub nsfw_classifier_timeout_secs: u64,
+
+    // Webhook notifications
+    pub webhook_url: Option<String>,
+    pub webhook_secret: Option<String>,
+    pub webhook_events: Vec<String>,
+
+    // Chat (Discord/Slack) notifications
+    pub chat_webhook_url: Option<String>,
+    pub chat_webhook_format: ChatFormat,
+    pub chat_batch_window_secs: u64,
+    pub chat_notify_feed: bool,
+    pub chat_notify_submissions: bool,
+
+    // Content change watching
+    pub change_watch_enabled: bool,
+    /// Default re-check interval for newly watched links.
+    pub change_watch_default_interval_hours: u64,
+    /// Lower bound on any link's re-check interval.
+    pub change_watch_min_interval_hours: u64,
 }
 
 /// Configuration file structure (all fields optional, loaded from TOML).
@@ -191,6 +317,16 @@ pub struct FileConfig {
     pub twitter: TwitterConfig,
     #[serde(default)]
     pub comments: CommentsConfig,
+    #[serde(default)]
+    pub whisper: WhisperConfig,
+    #[serde(default)]
+    pub nsfw_classifier: NsfwClassifierConfig,
+    #[serde(default)]
+    pub webhook: WebhookConfig,
+    #[serde(default)]
+    pub chat: ChatConfig,
+    #[serde(default)]
+    pub change_watch: ChangeWatchConfig,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -198,14 +334,22 @@ pub struct FileConfig {
 pub struct RssConfig {
     pub url: Option<String>,
     pub poll_interval_secs: Option<u64>,
+    pub poll_interval_overrides: Option<BTreeMap<String, u64>>,
+    pub poll_jitter_secs: Option<u64>,
     pub cache_window_secs: Option<u64>,
     pub max_pages: Option<usize>,
+    pub backfill: Option<bool>,
+    pub backfill_max_pages: Option<usize>,
+    pub author_pattern: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
 #[serde(default)]
 pub struct DatabaseConfig {
     pub path: Option<String>,
+    pub max_connections: Option<u32>,
+    pub busy_timeout_secs: Option<u64>,
+    pub cache_size_kb: Option<u32>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -216,6 +360,9 @@ pub struct S3Config {
     pub endpoint: Option<String>,
     pub prefix: Option<String>,
     pub public_url_base: Option<String>,
+    pub storage_class: Option<String>,
+    pub upload_max_retries: Option<u32>,
+    pub verify_downloads: Option<bool>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -231,6 +378,9 @@ pub struct WorkersConfig {
     pub youtube_max_duration_seconds: Option<u32>,
     pub youtube_download_timeout_seconds: Option<u64>,
     pub youtube_request_delay_ms: Option<u64>,
+    pub youtube_playlist_max_items: Option<usize>,
+    pub live_defer_interval_minutes: Option<u64>,
+    pub live_defer_max_hours: Option<u64>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -238,6 +388,20 @@ pub struct WorkersConfig {
 pub struct ArchiveConfig {
     pub mode: Option<String>,
     pub quote_only_links: Option<bool>,
+    pub author_allowlist: Option<Vec<String>>,
+    pub post_snapshots: Option<bool>,
+    pub respect_noarchive: Option<bool>,
+    pub reader_mode: Option<bool>,
+    pub max_retries: Option<i32>,
+    pub max_artifact_bytes: Option<u64>,
+    pub soft_unavailable_markers: Option<Vec<String>>,
+    pub soft_unavailable_paywall_classes: Option<Vec<String>>,
+    pub dry_run: Option<bool>,
+    pub retention_days: Option<u32>,
+    pub retention_prune_skipped: Option<bool>,
+    pub retention_dry_run: Option<bool>,
+    pub user_agent: Option<String>,
+    pub extra_headers: Option<BTreeMap<String, String>>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -246,6 +410,11 @@ pub struct WebConfig {
     pub host: Option<String>,
     pub port: Option<u16>,
     pub public_base_url: Option<String>,
+    pub request_timeout_secs: Option<u64>,
+    pub max_body_bytes: Option<usize>,
+    pub static_dir: Option<String>,
+    pub trusted_proxies: Option<Vec<String>>,
+    pub queue_api_token: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -257,6 +426,8 @@ pub struct TlsConfig {
     pub cache_dir: Option<String>,
     pub use_staging: Option<bool>,
     pub https_port: Option<u16>,
+    pub cert_file: Option<String>,
+    pub key_file: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -271,6 +442,7 @@ pub struct WaybackConfig {
 pub struct ArchiveTodayConfig {
     pub enabled: Option<bool>,
     pub rate_limit_per_min: Option<u32>,
+    pub base: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -279,6 +451,7 @@ pub struct BackupConfig {
     pub enabled: Option<bool>,
     pub interval_hours: Option<u64>,
     pub retention_count: Option<usize>,
+    pub skip_unchanged: Option<bool>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -293,6 +466,10 @@ pub struct IpfsConfig {
     pub enabled: Option<bool>,
     pub api_url: Option<String>,
     pub gateway_urls: Option<Vec<String>>,
+    pub pin_timeout_secs: Option<u64>,
+    pub max_concurrent_pins: Option<usize>,
+    pub failure_threshold: Option<u32>,
+    pub cooldown_secs: Option<u64>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -350,6 +527,7 @@ pub struct TwitterConfig {
     pub archive_quoted: Option<bool>,
     pub html_snapshot: Option<bool>,
     pub max_quote_depth: Option<u32>,
+    pub max_thread_length: Option<u32>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -361,6 +539,52 @@ pub struct CommentsConfig {
     pub platforms: Option<Vec<String>>,
     pub max_depth: Option<usize>,
     pub request_delay_ms: Option<u64>,
+    pub concurrency: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct WhisperConfig {
+    pub enabled: Option<bool>,
+    pub path: Option<String>,
+    pub model: Option<String>,
+    pub language: Option<String>,
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct NsfwClassifierConfig {
+    pub enabled: Option<bool>,
+    pub command: Option<String>,
+    pub threshold: Option<f64>,
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    pub url: Option<String>,
+    pub secret: Option<String>,
+    pub events: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ChatConfig {
+    pub webhook_url: Option<String>,
+    pub format: Option<String>,
+    pub batch_window_secs: Option<u64>,
+    pub notify_feed: Option<bool>,
+    pub notify_submissions: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ChangeWatchConfig {
+    pub enabled: Option<bool>,
+    pub default_interval_hours: Option<u64>,
+    pub min_interval_hours: Option<u64>,
 }
 
 /// Log output format.
@@ -373,6 +597,16 @@ pub enum LogFormat {
     Json,
 }
 
+/// Message format for chat notifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChatFormat {
+    /// Discord incoming webhook (embeds)
+    #[default]
+    Discord,
+    /// Slack incoming webhook (Block Kit)
+    Slack,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ArchiveMode {
     /// Only archive content from sites known for deletable content
@@ -473,11 +707,35 @@ impl Config {
                 "POLL_INTERVAL_SECS",
                 fc.rss.poll_interval_secs.unwrap_or(60),
             )?),
+            poll_interval_overrides: match optional_env("POLL_INTERVAL_OVERRIDES") {
+                Some(value) => parse_poll_interval_overrides(&value)?,
+                None => fc
+                    .rss
+                    .poll_interval_overrides
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(url, secs)| (url, Duration::from_secs(secs)))
+                    .collect(),
+            },
+            poll_jitter: Duration::from_secs(parse_env_u64(
+                "POLL_JITTER_SECS",
+                fc.rss.poll_jitter_secs.unwrap_or(5),
+            )?),
             cache_window: Duration::from_secs(parse_env_u64(
                 "CACHE_WINDOW_SECS",
                 fc.rss.cache_window_secs.unwrap_or(3600),
             )?),
             rss_max_pages: parse_env_usize("RSS_MAX_PAGES", fc.rss.max_pages.unwrap_or(4))?,
+            rss_backfill: parse_env_bool("RSS_BACKFILL", fc.rss.backfill.unwrap_or(false))?,
+            rss_backfill_max_pages: parse_env_usize(
+                "RSS_BACKFILL_MAX_PAGES",
+                fc.rss.backfill_max_pages.unwrap_or(1000),
+            )?,
+            forum_author_pattern: optional_env("FORUM_AUTHOR_PATTERN")
+                .or(fc.rss.author_pattern)
+                .filter(|p| !p.trim().is_empty())
+                .map(|p| parse_author_pattern(&p))
+                .transpose()?,
 
             // Database
             database_path: PathBuf::from(get_string(
@@ -485,6 +743,18 @@ impl Config {
                 fc.database.path,
                 "./data/archive.sqlite",
             )),
+            db_max_connections: parse_env_u32(
+                "DB_MAX_CONNECTIONS",
+                fc.database.max_connections.unwrap_or(5),
+            )?,
+            db_busy_timeout_secs: parse_env_u64(
+                "DB_BUSY_TIMEOUT_SECS",
+                fc.database.busy_timeout_secs.unwrap_or(10),
+            )?,
+            db_cache_size_kb: parse_env_u32(
+                "DB_CACHE_SIZE_KB",
+                fc.database.cache_size_kb.unwrap_or(2000),
+            )?,
 
             // S3 Storage
             s3_bucket: get_string_required("S3_BUCKET", fc.s3.bucket)?,
@@ -492,6 +762,15 @@ impl Config {
             s3_endpoint: optional_env("S3_ENDPOINT").or(fc.s3.endpoint),
             s3_prefix: get_string("S3_PREFIX", fc.s3.prefix, "archives/"),
             s3_public_url_base: optional_env("S3_PUBLIC_URL_BASE").or(fc.s3.public_url_base),
+            s3_storage_class: optional_env("S3_STORAGE_CLASS").or(fc.s3.storage_class),
+            s3_upload_max_retries: parse_env_u32(
+                "S3_UPLOAD_MAX_RETRIES",
+                fc.s3.upload_max_retries.unwrap_or(3),
+            )?,
+            verify_downloads: parse_env_bool(
+                "VERIFY_DOWNLOADS",
+                fc.s3.verify_downloads.unwrap_or(false),
+            )?,
 
             // Archive Workers
             worker_concurrency: parse_env_usize(
@@ -528,6 +807,18 @@ impl Config {
                 "YOUTUBE_REQUEST_DELAY_MS",
                 fc.workers.youtube_request_delay_ms.unwrap_or(5000), // Default: 5 seconds
             )?,
+            youtube_playlist_max_items: parse_env_usize(
+                "YOUTUBE_PLAYLIST_MAX_ITEMS",
+                fc.workers.youtube_playlist_max_items.unwrap_or(50),
+            )?,
+            live_defer_interval_minutes: parse_env_u64(
+                "LIVE_DEFER_INTERVAL_MINUTES",
+                fc.workers.live_defer_interval_minutes.unwrap_or(30),
+            )?,
+            live_defer_max_hours: parse_env_u64(
+                "LIVE_DEFER_MAX_HOURS",
+                fc.workers.live_defer_max_hours.unwrap_or(48),
+            )?,
 
             // Archive Policy
             archive_mode: parse_archive_mode(&get_string(
@@ -537,8 +828,67 @@ impl Config {
             ))?,
             archive_quote_only_links: parse_env_bool(
                 "ARCHIVE_QUOTE_ONLY_LINKS",
-                fc.archive.quote_only_links.unwrap_or(false),
+                fc.archive.quote_only_links.unwrap_or(true),
+            )?,
+            archive_author_allowlist: optional_env("ARCHIVE_AUTHOR_ALLOWLIST")
+                .map(|s| parse_comma_separated_list(&s))
+                .or(fc.archive.author_allowlist)
+                .unwrap_or_default(),
+            archive_post_snapshots: parse_env_bool(
+                "ARCHIVE_POST_SNAPSHOTS",
+                fc.archive.post_snapshots.unwrap_or(false),
+            )?,
+            respect_noarchive: parse_env_bool(
+                "RESPECT_NOARCHIVE",
+                fc.archive.respect_noarchive.unwrap_or(false),
+            )?,
+            reader_mode: parse_env_bool("READER_MODE", fc.archive.reader_mode.unwrap_or(true))?,
+            archive_max_retries: parse_env_i32(
+                "ARCHIVE_MAX_RETRIES",
+                fc.archive.max_retries.unwrap_or(3),
+            )?,
+            max_artifact_bytes: parse_env_u64(
+                "MAX_ARTIFACT_BYTES",
+                fc.archive.max_artifact_bytes.unwrap_or(0),
+            )?,
+            soft_unavailable_markers: optional_env("SOFT_UNAVAILABLE_MARKERS")
+                .map(|s| parse_comma_separated_list(&s))
+                .or(fc.archive.soft_unavailable_markers)
+                .unwrap_or_else(default_soft_unavailable_markers),
+            soft_unavailable_paywall_classes: optional_env("SOFT_UNAVAILABLE_PAYWALL_CLASSES")
+                .map(|s| parse_comma_separated_list(&s))
+                .or(fc.archive.soft_unavailable_paywall_classes)
+                .unwrap_or_else(default_soft_unavailable_paywall_classes),
+            archive_dry_run: parse_env_bool(
+                "ARCHIVE_DRY_RUN",
+                fc.archive.dry_run.unwrap_or(false),
             )?,
+            archive_retention_days: parse_env_u32(
+                "ARCHIVE_RETENTION_DAYS",
+                fc.archive.retention_days.unwrap_or(0),
+            )?,
+            archive_retention_prune_skipped: parse_env_bool(
+                "ARCHIVE_RETENTION_PRUNE_SKIPPED",
+                fc.archive.retention_prune_skipped.unwrap_or(false),
+            )?,
+            archive_retention_dry_run: parse_env_bool(
+                "ARCHIVE_RETENTION_DRY_RUN",
+                fc.archive.retention_dry_run.unwrap_or(false),
+            )?,
+            archival_user_agent: get_string(
+                "ARCHIVAL_USER_AGENT",
+                fc.archive.user_agent,
+                ARCHIVAL_USER_AGENT,
+            ),
+            archival_extra_headers: match optional_env("ARCHIVAL_EXTRA_HEADERS") {
+                Some(value) => parse_header_list(&value)?,
+                None => fc
+                    .archive
+                    .extra_headers
+                    .unwrap_or_default()
+                    .into_iter()
+                    .collect(),
+            },
 
             // Web Server
             web_host: get_string("WEB_HOST", fc.web.host, "0.0.0.0"),
@@ -548,6 +898,22 @@ impl Config {
                 fc.web.public_base_url,
                 "https://cf-archiver.xk.io",
             ),
+            web_request_timeout_secs: parse_env_u64(
+                "WEB_REQUEST_TIMEOUT_SECS",
+                fc.web.request_timeout_secs.unwrap_or(30),
+            )?,
+            web_max_body_bytes: parse_env_usize(
+                "WEB_MAX_BODY_BYTES",
+                fc.web.max_body_bytes.unwrap_or(1024 * 1024),
+            )?,
+            static_dir: optional_env("STATIC_DIR")
+                .or(fc.web.static_dir)
+                .map(PathBuf::from),
+            trusted_proxies: match optional_env("TRUSTED_PROXIES") {
+                Some(value) => parse_trusted_proxies(&parse_comma_separated_list(&value))?,
+                None => parse_trusted_proxies(&fc.web.trusted_proxies.unwrap_or_default())?,
+            },
+            queue_api_token: optional_env("QUEUE_API_TOKEN").or(fc.web.queue_api_token),
 
             // TLS / Let's Encrypt
             tls_enabled: parse_env_bool("TLS_ENABLED", fc.tls.enabled.unwrap_or(false))?,
@@ -566,6 +932,12 @@ impl Config {
                 fc.tls.use_staging.unwrap_or(false),
             )?,
             tls_https_port: parse_env_u16("TLS_HTTPS_PORT", fc.tls.https_port.unwrap_or(443))?,
+            tls_cert_file: optional_env("TLS_CERT_FILE")
+                .or(fc.tls.cert_file)
+                .map(PathBuf::from),
+            tls_key_file: optional_env("TLS_KEY_FILE")
+                .or(fc.tls.key_file)
+                .map(PathBuf::from),
 
             // Wayback Machine
             wayback_enabled: parse_env_bool("WAYBACK_ENABLED", fc.wayback.enabled.unwrap_or(true))?,
@@ -583,6 +955,14 @@ impl Config {
                 "ARCHIVE_TODAY_RATE_LIMIT_PER_MIN",
                 fc.archive_today.rate_limit_per_min.unwrap_or(3),
             )?,
+            archive_today_base: {
+                let base = get_string(
+                    "ARCHIVE_TODAY_BASE",
+                    fc.archive_today.base,
+                    ARCHIVE_TODAY_BASE_URL,
+                );
+                normalize_base_url(&base).unwrap_or(base)
+            },
 
             // Backup
             backup_enabled: parse_env_bool("BACKUP_ENABLED", fc.backup.enabled.unwrap_or(true))?,
@@ -594,6 +974,10 @@ impl Config {
                 "BACKUP_RETENTION_COUNT",
                 fc.backup.retention_count.unwrap_or(30),
             )?,
+            backup_skip_unchanged: parse_env_bool(
+                "BACKUP_SKIP_UNCHANGED",
+                fc.backup.skip_unchanged.unwrap_or(true),
+            )?,
 
             // Logging
             log_format: parse_log_format(&get_string("LOG_FORMAT", fc.logging.format, "pretty"))?,
@@ -611,6 +995,22 @@ impl Config {
                         "https://gateway.pinata.cloud/ipfs/".to_string(),
                     ]
                 }),
+            ipfs_pin_timeout_secs: parse_env_u64(
+                "IPFS_PIN_TIMEOUT_SECS",
+                fc.ipfs.pin_timeout_secs.unwrap_or(300),
+            )?,
+            ipfs_max_concurrent_pins: parse_env_usize(
+                "IPFS_MAX_CONCURRENT_PINS",
+                fc.ipfs.max_concurrent_pins.unwrap_or(2),
+            )?,
+            ipfs_failure_threshold: parse_env_u32(
+                "IPFS_FAILURE_THRESHOLD",
+                fc.ipfs.failure_threshold.unwrap_or(3),
+            )?,
+            ipfs_cooldown_secs: parse_env_u64(
+                "IPFS_COOLDOWN_SECS",
+                fc.ipfs.cooldown_secs.unwrap_or(300),
+            )?,
 
             // Manual Submission
             submission_enabled: parse_env_bool(
@@ -696,6 +1096,10 @@ impl Config {
                 "TWITTER_MAX_QUOTE_DEPTH",
                 fc.twitter.max_quote_depth.unwrap_or(10),
             )?,
+            twitter_max_thread_length: parse_env_u32(
+                "TWITTER_MAX_THREAD_LENGTH",
+                fc.twitter.max_thread_length.unwrap_or(25),
+            )?,
 
             // Comment extraction settings
             comments_enabled: parse_env_bool(
@@ -729,9 +1133,132 @@ impl Config {
                 "COMMENTS_REQUEST_DELAY_MS",
                 fc.comments.request_delay_ms.unwrap_or(1000),
             )?,
+            comments_concurrency: parse_env_usize(
+                "COMMENTS_CONCURRENCY",
+                fc.comments.concurrency.unwrap_or(1),
+            )?,
+
+            // Local Whisper transcription fallback
+            whisper_enabled: parse_env_bool(
+                "WHISPER_ENABLED",
+                fc.whisper.enabled.unwrap_or(false),
+            )?,
+            whisper_path: get_string("WHISPER_PATH", fc.whisper.path, "whisper"),
+            whisper_model: get_string("WHISPER_MODEL", fc.whisper.model, "base"),
+            whisper_language: optional_env("WHISPER_LANGUAGE").or(fc.whisper.language),
+            whisper_timeout_secs: parse_env_u64(
+                "WHISPER_TIMEOUT_SECS",
+                fc.whisper.timeout_secs.unwrap_or(3600), // Default: 1 hour
+            )?,
+
+            // Automatic NSFW classification
+            nsfw_classifier_enabled: parse_env_bool(
+                "NSFW_CLASSIFIER_ENABLED",
+                fc.nsfw_classifier.enabled.unwrap_or(false),
+            )?,
+            nsfw_classifier_command: get_string(
+                "NSFW_CLASSIFIER_COMMAND",
+                fc.nsfw_classifier.command,
+                "nsfw-classify",
+            ),
+            nsfw_classifier_threshold: parse_env_f64(
+                "NSFW_CLASSIFIER_THRESHOLD",
+                fc.nsfw_classifier.threshold.unwrap_or(0.8),
+            )?,
+            nsfw_classifier_timeout_secs: parse_env_u64(
+                "NSFW_CLASSIFIER_TIMEOUT_SECS",
+                fc.nsfw_classifier.timeout_secs.unwrap_or(120),
+            )?,
+
+            // Webhook notifications
+            webhook_url: optional_env("WEBHOOK_URL").or(fc.webhook.url),
+            webhook_secret: optional_env("WEBHOOK_SECRET").or(fc.webhook.secret),
+            webhook_events: optional_env("WEBHOOK_EVENTS")
+                .map(|s| parse_comma_separated_list(&s))
+                .or(fc.webhook.events)
+                .unwrap_or_else(|| {
+                    WebhookEvent::ALL
+                        .iter()
+                        .map(|e| e.as_str().to_string())
+                        .collect()
+                }),
+
+            // Chat (Discord/Slack) notifications
+            chat_webhook_url: optional_env("CHAT_WEBHOOK_URL").or(fc.chat.webhook_url),
+            chat_webhook_format: parse_chat_format(&get_string(
+                "CHAT_WEBHOOK_FORMAT",
+                fc.chat.format,
+                "discord",
+            ))?,
+            chat_batch_window_secs: parse_env_u64(
+                "CHAT_BATCH_WINDOW_SECS",
+                fc.chat.batch_window_secs.unwrap_or(30),
+            )?,
+            chat_notify_feed: parse_env_bool(
+                "CHAT_NOTIFY_FEED",
+                fc.chat.notify_feed.unwrap_or(true),
+            )?,
+            chat_notify_submissions: parse_env_bool(
+                "CHAT_NOTIFY_SUBMISSIONS",
+                fc.chat.notify_submissions.unwrap_or(false),
+            )?,
+
+            // Content change watching
+            change_watch_enabled: parse_env_bool(
+                "CHANGE_WATCH_ENABLED",
+                fc.change_watch.enabled.unwrap_or(false),
+            )?,
+            change_watch_default_interval_hours: parse_env_u64(
+                "CHANGE_WATCH_DEFAULT_INTERVAL_HOURS",
+                fc.change_watch.default_interval_hours.unwrap_or(24),
+            )?,
+            change_watch_min_interval_hours: parse_env_u64(
+                "CHANGE_WATCH_MIN_INTERVAL_HOURS",
+                fc.change_watch.min_interval_hours.unwrap_or(6),
+            )?,
         })
     }
 
+    /// Poll interval for `feed_url`, preferring a per-feed override.
+    #[must_use]
+    pub fn poll_interval_for(&self, feed_url: &str) -> Duration {
+        self.poll_interval_overrides
+            .get(feed_url)
+            .copied()
+            .unwrap_or(self.poll_interval)
+    }
+
+    /// Addresses to listen on for `port`, one per `web_host` entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `web_host` is empty or an entry is not an IP address.
+    pub fn bind_addrs(&self, port: u16) -> Result<Vec<SocketAddr>, ConfigError> {
+        let mut addrs = Vec::new();
+        for host in parse_comma_separated_list(&self.web_host) {
+            let ip = host
+                .strip_prefix('[')
+                .and_then(|h| h.strip_suffix(']'))
+                .unwrap_or(&host)
+                .parse::<IpAddr>()
+                .map_err(|_| ConfigError::InvalidValue {
+                    name: "web_host".to_string(),
+                    message: format!("'{host}' is not an IP address"),
+                })?;
+            let addr = SocketAddr::new(ip, port);
+            if !addrs.contains(&addr) {
+                addrs.push(addr);
+            }
+        }
+        if addrs.is_empty() {
+            return Err(ConfigError::InvalidValue {
+                name: "web_host".to_string(),
+                message: "at least one address required".to_string(),
+            });
+        }
+        Ok(addrs)
+    }
+
     /// Create a ScreenshotConfig from this config.
     #[must_use]
     pub fn screenshot_config(&self) -> crate::archiver::ScreenshotConfig {
@@ -782,36 +1309,153 @@ impl Config {
     ///
     /// Returns an error if the configuration is invalid.
     pub fn validate(&self) -> Result<(), ConfigError> {
+        if !(1..=100).contains(&self.db_max_connections) {
+            return Err(ConfigError::InvalidValue {
+                name: "db_max_connections".to_string(),
+                message: "must be between 1 and 100".to_string(),
+            });
+        }
+        if self.db_busy_timeout_secs == 0 {
+            return Err(ConfigError::InvalidValue {
+                name: "db_busy_timeout_secs".to_string(),
+                message: "must be at least 1".to_string(),
+            });
+        }
+        if self.db_cache_size_kb < 128 {
+            return Err(ConfigError::InvalidValue {
+                name: "db_cache_size_kb".to_string(),
+                message: "must be at least 128".to_string(),
+            });
+        }
         if self.worker_concurrency == 0 {
             return Err(ConfigError::InvalidValue {
                 name: "worker_concurrency".to_string(),
                 message: "must be at least 1".to_string(),
             });
         }
+        if self.rss_backfill_max_pages == 0 {
+            return Err(ConfigError::InvalidValue {
+                name: "rss_backfill_max_pages".to_string(),
+                message: "must be at least 1".to_string(),
+            });
+        }
+        if normalize_base_url(&self.archive_today_base).is_none() {
+            return Err(ConfigError::InvalidValue {
+                name: "archive_today_base".to_string(),
+                message: format!(
+                    "must be one of the archive.today domains: {}",
+                    ARCHIVE_TODAY_DOMAINS.join(", ")
+                ),
+            });
+        }
         if self.per_domain_concurrency == 0 {
             return Err(ConfigError::InvalidValue {
                 name: "per_domain_concurrency".to_string(),
                 message: "must be at least 1".to_string(),
             });
         }
+        if self.live_defer_interval_minutes == 0 {
+            return Err(ConfigError::InvalidValue {
+                name: "live_defer_interval_minutes".to_string(),
+                message: "must be at least 1".to_string(),
+            });
+        }
+        if self.comments_concurrency == 0 {
+            return Err(ConfigError::InvalidValue {
+                name: "comments_concurrency".to_string(),
+                message: "must be at least 1".to_string(),
+            });
+        }
+        if self.web_request_timeout_secs == 0 {
+            return Err(ConfigError::InvalidValue {
+                name: "web_request_timeout_secs".to_string(),
+                message: "must be at least 1".to_string(),
+            });
+        }
+        if self.web_max_body_bytes == 0 {
+            return Err(ConfigError::InvalidValue {
+                name: "web_max_body_bytes".to_string(),
+                message: "must be at least 1".to_string(),
+            });
+        }
+        self.bind_addrs(self.web_port)?;
+        if self.archive_max_retries < 0 {
+            return Err(ConfigError::InvalidValue {
+                name: "archive_max_retries".to_string(),
+                message: "cannot be negative".to_string(),
+            });
+        }
         if self.rss_url.is_empty() {
             return Err(ConfigError::InvalidValue {
                 name: "rss_url".to_string(),
                 message: "cannot be empty".to_string(),
             });
         }
+        if self.poll_interval.is_zero() {
+            return Err(ConfigError::InvalidValue {
+                name: "poll_interval".to_string(),
+                message: "must be at least 1 second".to_string(),
+            });
+        }
+        for (url, interval) in &self.poll_interval_overrides {
+            if url::Url::parse(url).is_err() {
+                return Err(ConfigError::InvalidValue {
+                    name: "poll_interval_overrides".to_string(),
+                    message: format!("'{url}' is not a valid feed URL"),
+                });
+            }
+            if interval.is_zero() {
+                return Err(ConfigError::InvalidValue {
+                    name: "poll_interval_overrides".to_string(),
+                    message: format!("interval for '{url}' must be at least 1 second"),
+                });
+            }
+        }
+        let shortest_interval = self
+            .poll_interval_overrides
+            .values()
+            .copied()
+            .fold(self.poll_interval, Duration::min);
+        if self.poll_jitter >= shortest_interval {
+            return Err(ConfigError::InvalidValue {
+                name: "poll_jitter".to_string(),
+                message: "must be shorter than every poll interval".to_string(),
+            });
+        }
         if self.s3_bucket.is_empty() {
             return Err(ConfigError::InvalidValue {
                 name: "s3_bucket".to_string(),
                 message: "cannot be empty".to_string(),
             });
         }
-        if self.tls_enabled && self.tls_domains.is_empty() {
+        if let Some(ref class) = self.s3_storage_class {
+            let known = aws_sdk_s3::types::StorageClass::values();
+            if !known.contains(&class.as_str()) {
+                return Err(ConfigError::InvalidValue {
+                    name: "s3_storage_class".to_string(),
+                    message: format!("unknown storage class '{class}'"),
+                });
+            }
+        }
+        if reqwest::header::HeaderValue::from_str(&self.archival_user_agent).is_err() {
             return Err(ConfigError::InvalidValue {
-                name: "tls_domains".to_string(),
-                message: "at least one domain required when TLS is enabled".to_string(),
+                name: "archival_user_agent".to_string(),
+                message: "is not a valid header value".to_string(),
             });
         }
+        for (name, value) in &self.archival_extra_headers {
+            if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err()
+                || reqwest::header::HeaderValue::from_str(value).is_err()
+            {
+                return Err(ConfigError::InvalidValue {
+                    name: "archival_extra_headers".to_string(),
+                    message: format!("invalid header '{name}: {value}'"),
+                });
+            }
+        }
+        if self.tls_enabled {
+            self.validate_tls()?;
+        }
         if let Some(ref cookies_path) = self.cookies_file_path {
             if cookies_path.is_dir() {
                 return Err(ConfigError::InvalidValue {
@@ -823,6 +1467,84 @@ impl Config {
                 });
             }
         }
+        if let Some(unknown) = self
+            .webhook_events
+            .iter()
+            .find(|name| WebhookEvent::parse(name).is_none())
+        {
+            return Err(ConfigError::InvalidValue {
+                name: "webhook_events".to_string(),
+                message: format!("unknown event '{unknown}'"),
+            });
+        }
+        if self.change_watch_min_interval_hours == 0 {
+            return Err(ConfigError::InvalidValue {
+                name: "change_watch_min_interval_hours".to_string(),
+                message: "must be at least 1".to_string(),
+            });
+        }
+        if !(0.0..=1.0).contains(&self.nsfw_classifier_threshold) {
+            return Err(ConfigError::InvalidValue {
+                name: "nsfw_classifier_threshold".to_string(),
+                message: "must be between 0.0 and 1.0".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Validate the TLS settings, catching mistakes that would otherwise only
+    /// surface as ACME errors at runtime.
+    ///
+    /// Domain and contact email checks only apply to ACME; they are skipped
+    /// when certificate files are provided.
+    fn validate_tls(&self) -> Result<(), ConfigError> {
+        if self.web_port == self.tls_https_port {
+            return Err(ConfigError::InvalidValue {
+                name: "tls_https_port".to_string(),
+                message: format!(
+                    "must differ from web_port ({}), which serves HTTP redirects when TLS is enabled",
+                    self.web_port
+                ),
+            });
+        }
+        match (&self.tls_cert_file, &self.tls_key_file) {
+            (Some(_), Some(_)) => return Ok(()),
+            (Some(_), None) => {
+                return Err(ConfigError::InvalidValue {
+                    name: "tls_key_file".to_string(),
+                    message: "required when tls_cert_file is set".to_string(),
+                });
+            }
+            (None, Some(_)) => {
+                return Err(ConfigError::InvalidValue {
+                    name: "tls_cert_file".to_string(),
+                    message: "required when tls_key_file is set".to_string(),
+                });
+            }
+            (None, None) => {}
+        }
+        if self.tls_domains.iter().all(|d| d.trim().is_empty()) {
+            return Err(ConfigError::InvalidValue {
+                name: "tls_domains".to_string(),
+                message: "at least one domain required when TLS is enabled".to_string(),
+            });
+        }
+        let has_email = self
+            .tls_contact_email
+            .as_deref()
+            .is_some_and(|e| !e.trim().is_empty());
+        if !has_email && !self.tls_use_staging {
+            return Err(ConfigError::InvalidValue {
+                name: "tls_contact_email".to_string(),
+                message: "required when TLS is enabled with the production ACME environment"
+                    .to_string(),
+            });
+        }
+        if self.tls_use_staging {
+            tracing::warn!(
+                "TLS uses the Let's Encrypt staging environment; certificates will not be trusted by browsers"
+            );
+        }
         Ok(())
     }
 
@@ -838,10 +1560,12 @@ impl Config {
             name: "rss_url".to_string(),
             message: format!("Cannot parse RSS URL: {}", e),
         })?;
+        let port = url.port().map(|p| format!(":{p}")).unwrap_or_default();
         Ok(format!(
-            "{}://{}",
+            "{}://{}{}",
             url.scheme(),
-            url.host_str().unwrap_or("")
+            url.host_str().unwrap_or(""),
+            port
         ))
     }
 }
@@ -870,6 +1594,16 @@ fn parse_env_u32(name: &str, default: u32) -> Result<u32, ConfigError> {
     }
 }
 
+fn parse_env_i32(name: &str, default: i32) -> Result<i32, ConfigError> {
+    match std::env::var(name) {
+        Ok(val) if !val.is_empty() => val.parse().map_err(|e| ConfigError::ParseInt {
+            name: name.to_string(),
+            source: e,
+        }),
+        _ => Ok(default),
+    }
+}
+
 fn parse_env_u16(name: &str, default: u16) -> Result<u16, ConfigError> {
     match std::env::var(name) {
         Ok(val) if !val.is_empty() => val.parse().map_err(|e| ConfigError::ParseInt {
@@ -936,6 +1670,17 @@ fn parse_log_format(value: &str) -> Result<LogFormat, ConfigError> {
     }
 }
 
+fn parse_chat_format(value: &str) -> Result<ChatFormat, ConfigError> {
+    match value.to_lowercase().as_str() {
+        "discord" => Ok(ChatFormat::Discord),
+        "slack" => Ok(ChatFormat::Slack),
+        _ => Err(ConfigError::InvalidValue {
+            name: "chat_webhook_format".to_string(),
+            message: format!("must be 'discord' or 'slack', got '{value}'"),
+        }),
+    }
+}
+
 fn parse_gateway_urls(value: &str) -> Vec<String> {
     value
         .split(',')
@@ -954,6 +1699,77 @@ fn parse_domain_list(value: &str) -> Vec<String> {
         .collect()
 }
 
+/// Parse `Name: value` header pairs separated by `|`.
+///
+/// `|` is used because header values such as `Accept-Language` contain commas
+/// and semicolons.
+fn parse_header_list(value: &str) -> Result<Vec<(String, String)>, ConfigError> {
+    value
+        .split('|')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|pair| {
+            pair.split_once(':')
+                .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+                .ok_or_else(|| ConfigError::InvalidValue {
+                    name: "ARCHIVAL_EXTRA_HEADERS".to_string(),
+                    message: format!("expected 'Name: value', got '{pair}'"),
+                })
+        })
+        .collect()
+}
+
+/// Parse `POLL_INTERVAL_OVERRIDES` entries of the form `url=secs`, comma-separated.
+fn parse_poll_interval_overrides(value: &str) -> Result<BTreeMap<String, Duration>, ConfigError> {
+    parse_comma_separated_list(value)
+        .into_iter()
+        .map(|entry| {
+            entry
+                .rsplit_once('=')
+                .and_then(|(url, secs)| {
+                    let secs = secs.trim().parse::<u64>().ok()?;
+                    Some((url.trim().to_string(), Duration::from_secs(secs)))
+                })
+                .ok_or_else(|| ConfigError::InvalidValue {
+                    name: "POLL_INTERVAL_OVERRIDES".to_string(),
+                    message: format!("expected 'url=secs', got '{entry}'"),
+                })
+        })
+        .collect()
+}
+
+/// Parse `TRUSTED_PROXIES` entries: CIDR ranges, or bare IPs for a single host.
+fn parse_trusted_proxies(entries: &[String]) -> Result<Vec<IpNet>, ConfigError> {
+    entries
+        .iter()
+        .map(|entry| {
+            let entry = entry.trim();
+            entry
+                .parse::<IpNet>()
+                .or_else(|_| entry.parse::<IpAddr>().map(IpNet::from))
+                .map_err(|_| ConfigError::InvalidValue {
+                    name: "TRUSTED_PROXIES".to_string(),
+                    message: format!("expected a CIDR range or IP address, got '{entry}'"),
+                })
+        })
+        .collect()
+}
+
+/// Compile `FORUM_AUTHOR_PATTERN`, which must capture the username in a group.
+fn parse_author_pattern(pattern: &str) -> Result<Regex, ConfigError> {
+    let invalid = |message: String| ConfigError::InvalidValue {
+        name: "FORUM_AUTHOR_PATTERN".to_string(),
+        message,
+    };
+    let regex = Regex::new(pattern).map_err(|e| invalid(e.to_string()))?;
+    if regex.captures_len() < 2 {
+        return Err(invalid(
+            "must have a capture group for the username".to_string(),
+        ));
+    }
+    Ok(regex)
+}
+
 fn parse_comma_separated_list(value: &str) -> Vec<String> {
     value
         .split(',')
@@ -971,6 +1787,30 @@ fn default_nitter_instances() -> Vec<String> {
     ]
 }
 
+fn default_soft_unavailable_markers() -> Vec<String> {
+    [
+        "page not found",
+        "404 not found",
+        "content unavailable",
+        "no longer available",
+        "this page isn't available",
+        "this content isn't available",
+        "has been removed",
+        "has been deleted",
+        "subscribe to continue reading",
+        "subscribe to read",
+        "sign in to continue reading",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+fn default_soft_unavailable_paywall_classes() -> Vec<String> {
+    ["paywall", "regwall", "subscriber-only", "subscription-wall"]
+        .map(String::from)
+        .to_vec()
+}
+
 impl Config {
     /// Create a Config instance with sensible defaults for testing.
     ///
@@ -990,14 +1830,25 @@ impl Config {
         Self {
             rss_url: "https://example.com/posts.rss".to_string(),
             poll_interval: Duration::from_secs(60),
+            poll_interval_overrides: BTreeMap::new(),
+            poll_jitter: Duration::ZERO,
             cache_window: Duration::from_secs(3600),
             rss_max_pages: 1,
+            rss_backfill: false,
+            rss_backfill_max_pages: 100,
+            forum_author_pattern: None,
             database_path: PathBuf::from("./test.db"),
+            db_max_connections: 5,
+            db_busy_timeout_secs: 10,
+            db_cache_size_kb: 2000,
             s3_bucket: "test-bucket".to_string(),
             s3_region: "us-east-1".to_string(),
             s3_endpoint: None,
             s3_prefix: "archives/".to_string(),
             s3_public_url_base: None,
+            s3_storage_class: None,
+            s3_upload_max_retries: 3,
+            verify_downloads: false,
             worker_concurrency: 4,
             per_domain_concurrency: 1,
             work_dir: PathBuf::from("./tmp"),
@@ -1008,28 +1859,58 @@ impl Config {
             youtube_max_duration_seconds: Some(3600),
             youtube_download_timeout_seconds: 7200,
             youtube_request_delay_ms: 5000,
+            youtube_playlist_max_items: 50,
+            live_defer_interval_minutes: 30,
+            live_defer_max_hours: 48,
             archive_mode: ArchiveMode::All,
-            archive_quote_only_links: false,
+            archive_quote_only_links: true,
+            archive_author_allowlist: vec![],
+            archive_post_snapshots: false,
+            respect_noarchive: false,
+            reader_mode: true,
+            archive_max_retries: 3,
+            max_artifact_bytes: 0,
+            soft_unavailable_markers: default_soft_unavailable_markers(),
+            soft_unavailable_paywall_classes: default_soft_unavailable_paywall_classes(),
+            archive_dry_run: false,
+            archive_retention_days: 0,
+            archive_retention_prune_skipped: false,
+            archive_retention_dry_run: false,
+            archival_user_agent: ARCHIVAL_USER_AGENT.to_string(),
+            archival_extra_headers: Vec::new(),
             web_host: "0.0.0.0".to_string(),
             web_port: 8080,
             public_base_url: "https://cf-archiver.xk.io".to_string(),
+            web_request_timeout_secs: 30,
+            web_max_body_bytes: 1024 * 1024,
+            static_dir: None,
+            trusted_proxies: Vec::new(),
+            queue_api_token: None,
             tls_enabled: false,
             tls_domains: vec![],
             tls_contact_email: None,
             tls_cache_dir: PathBuf::from("./acme_cache"),
             tls_use_staging: false,
             tls_https_port: 443,
+            tls_cert_file: None,
+            tls_key_file: None,
             wayback_enabled: false,
             wayback_rate_limit_per_min: 5,
             archive_today_enabled: false,
             archive_today_rate_limit_per_min: 3,
+            archive_today_base: ARCHIVE_TODAY_BASE_URL.to_string(),
             backup_enabled: false,
             backup_interval_hours: 24,
             backup_retention_count: 30,
+            backup_skip_unchanged: true,
             log_format: LogFormat::Pretty,
             ipfs_enabled: false,
             ipfs_api_url: "http://127.0.0.1:5001".to_string(),
             ipfs_gateway_urls: vec![],
+            ipfs_pin_timeout_secs: 300,
+            ipfs_max_concurrent_pins: 2,
+            ipfs_failure_threshold: 3,
+            ipfs_cooldown_secs: 300,
             submission_enabled: false,
             submission_rate_limit_per_hour: 10,
             screenshot_enabled: false,
@@ -1052,6 +1933,7 @@ impl Config {
             twitter_archive_quoted: true,
             twitter_html_snapshot: true,
             twitter_max_quote_depth: 10,
+            twitter_max_thread_length: 25,
             comments_enabled: true,
             comments_max_count: 1000,
             comments_include_replies: true,
@@ -1064,6 +1946,30 @@ impl Config {
             ],
             comments_max_depth: 3,
             comments_request_delay_ms: 1000,
+            comments_concurrency: 1,
+            whisper_enabled: false,
+            whisper_path: "whisper".to_string(),
+            whisper_model: "base".to_string(),
+            whisper_language: None,
+            whisper_timeout_secs: 3600,
+            nsfw_classifier_enabled: false,
+            nsfw_classifier_command: "nsfw-classify".to_string(),
+            nsfw_classifier_threshold: 0.8,
+            nsfw_classifier_timeout_secs: 120,
+            webhook_url: None,
+            webhook_secret: None,
+            webhook_events: WebhookEvent::ALL
+                .iter()
+                .map(|e| e.as_str().to_string())
+                .collect(),
+            chat_webhook_url: None,
+            chat_webhook_format: ChatFormat::Discord,
+            chat_batch_window_secs: 30,
+            chat_notify_feed: true,
+            chat_notify_submissions: false,
+            change_watch_enabled: false,
+            change_watch_default_interval_hours: 24,
+            change_watch_min_interval_hours: 6,
         }
     }
 }
@@ -1089,6 +1995,244 @@ mod tests {
         assert!(parse_archive_mode("invalid").is_err());
     }
 
+    #[test]
+    fn test_parse_chat_format() {
+        assert_eq!(parse_chat_format("discord").unwrap(), ChatFormat::Discord);
+        assert_eq!(parse_chat_format("Slack").unwrap(), ChatFormat::Slack);
+        assert!(parse_chat_format("teams").is_err());
+    }
+
+    #[test]
+    fn test_parse_header_list() {
+        assert_eq!(
+            parse_header_list("Accept-Language: en-US,en;q=0.9 | DNT: 1").unwrap(),
+            vec![
+                ("Accept-Language".to_string(), "en-US,en;q=0.9".to_string()),
+                ("DNT".to_string(), "1".to_string()),
+            ]
+        );
+        assert!(parse_header_list("").unwrap().is_empty());
+        assert!(parse_header_list("no-colon").is_err());
+    }
+
+    #[test]
+    fn test_parse_poll_interval_overrides() {
+        let parsed = parse_poll_interval_overrides(
+            "https://a.example/posts.rss=30, https://b.example/latest.rss?x=1=600",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.get("https://a.example/posts.rss"),
+            Some(&Duration::from_secs(30))
+        );
+        // Splits on the last '=' so query strings survive
+        assert_eq!(
+            parsed.get("https://b.example/latest.rss?x=1"),
+            Some(&Duration::from_secs(600))
+        );
+        assert!(parse_poll_interval_overrides("").unwrap().is_empty());
+        assert!(parse_poll_interval_overrides("https://a.example/posts.rss").is_err());
+        assert!(parse_poll_interval_overrides("https://a.example/posts.rss=soon").is_err());
+    }
+
+    #[test]
+    fn test_parse_trusted_proxies() {
+        let entries = parse_comma_separated_list("10.0.0.0/8, 192.168.1.5, ::1, fd00::/8");
+        let parsed: Vec<String> = parse_trusted_proxies(&entries)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            parsed,
+            vec!["10.0.0.0/8", "192.168.1.5/32", "::1/128", "fd00::/8"]
+        );
+        assert!(parse_trusted_proxies(&[]).unwrap().is_empty());
+        assert!(parse_trusted_proxies(&["proxy.internal".to_string()]).is_err());
+        assert!(parse_trusted_proxies(&["10.0.0.0/33".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_parse_author_pattern() {
+        let regex = parse_author_pattern(r"^(?P<handle>\w+) via").unwrap();
+        assert_eq!(
+            &regex.captures("jane via Example").unwrap()["handle"],
+            "jane"
+        );
+        // The username must be captured
+        assert!(parse_author_pattern(r"^\w+").is_err());
+        assert!(parse_author_pattern(r"(unclosed").is_err());
+    }
+
+    #[test]
+    fn test_poll_interval_override_takes_precedence() {
+        let mut config = Config::for_testing();
+        assert_eq!(
+            config.poll_interval_for(&config.rss_url.clone()),
+            Duration::from_secs(60)
+        );
+
+        config
+            .poll_interval_overrides
+            .insert(config.rss_url.clone(), Duration::from_secs(15));
+        assert_eq!(
+            config.poll_interval_for(&config.rss_url.clone()),
+            Duration::from_secs(15)
+        );
+        assert_eq!(
+            config.poll_interval_for("https://other.example/posts.rss"),
+            Duration::from_secs(60)
+        );
+    }
+
+    #[test]
+    fn test_validate_poll_jitter_and_overrides() {
+        let config = Config {
+            poll_jitter: Duration::from_secs(60),
+            ..Config::for_testing()
+        };
+        assert_eq!(invalid_field(&config), "poll_jitter");
+
+        // Jitter must also fit inside the shortest override
+        let mut config = Config {
+            poll_jitter: Duration::from_secs(10),
+            ..Config::for_testing()
+        };
+        config.poll_interval_overrides.insert(
+            "https://a.example/posts.rss".to_string(),
+            Duration::from_secs(5),
+        );
+        assert_eq!(invalid_field(&config), "poll_jitter");
+
+        let mut config = Config::for_testing();
+        config
+            .poll_interval_overrides
+            .insert("not a url".to_string(), Duration::from_secs(30));
+        assert_eq!(invalid_field(&config), "poll_interval_overrides");
+
+        let mut config = Config::for_testing();
+        config
+            .poll_interval_overrides
+            .insert("https://a.example/posts.rss".to_string(), Duration::ZERO);
+        assert_eq!(invalid_field(&config), "poll_interval_overrides");
+
+        let config = Config {
+            poll_jitter: Duration::from_secs(5),
+            ..Config::for_testing()
+        };
+        assert!(config.validate().is_ok());
+
+        let config = Config {
+            rss_backfill_max_pages: 0,
+            ..Config::for_testing()
+        };
+        assert_eq!(invalid_field(&config), "rss_backfill_max_pages");
+    }
+
+    #[test]
+    fn test_bind_addrs() {
+        let config = Config {
+            web_host: "0.0.0.0, ::, [::1], 0.0.0.0".to_string(),
+            ..Config::for_testing()
+        };
+        let addrs: Vec<String> = config
+            .bind_addrs(8080)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(addrs, vec!["0.0.0.0:8080", "[::]:8080", "[::1]:8080"]);
+        assert!(config.validate().is_ok());
+
+        for web_host in ["", " , ", "example.com", "0.0.0.0,localhost"] {
+            let config = Config {
+                web_host: web_host.to_string(),
+                ..Config::for_testing()
+            };
+            assert_eq!(invalid_field(&config), "web_host", "{web_host:?}");
+        }
+    }
+
+    #[test]
+    fn test_validate_database_settings() {
+        let config = Config {
+            db_max_connections: 0,
+            ..Config::for_testing()
+        };
+        assert_eq!(invalid_field(&config), "db_max_connections");
+
+        let config = Config {
+            db_max_connections: 101,
+            ..Config::for_testing()
+        };
+        assert_eq!(invalid_field(&config), "db_max_connections");
+
+        let config = Config {
+            db_busy_timeout_secs: 0,
+            ..Config::for_testing()
+        };
+        assert_eq!(invalid_field(&config), "db_busy_timeout_secs");
+
+        let config = Config {
+            db_cache_size_kb: 64,
+            ..Config::for_testing()
+        };
+        assert_eq!(invalid_field(&config), "db_cache_size_kb");
+
+        assert!(Config {
+            db_max_connections: 32,
+            db_cache_size_kb: 64 * 1024,
+            ..Config::for_testing()
+        }
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn test_validate_archive_max_retries() {
+        let config = Config {
+            archive_max_retries: -1,
+            ..Config::for_testing()
+        };
+        assert_eq!(invalid_field(&config), "archive_max_retries");
+
+        let config = Config {
+            archive_max_retries: 0,
+            ..Config::for_testing()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_archive_today_base() {
+        let config = Config {
+            archive_today_base: "https://example.com".to_string(),
+            ..Config::for_testing()
+        };
+        assert_eq!(invalid_field(&config), "archive_today_base");
+
+        let config = Config {
+            archive_today_base: "https://archive.ph".to_string(),
+            ..Config::for_testing()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_archival_headers() {
+        let config = Config {
+            archival_extra_headers: vec![("Bad Name".to_string(), "x".to_string())],
+            ..Config::for_testing()
+        };
+        assert_eq!(invalid_field(&config), "archival_extra_headers");
+
+        let config = Config {
+            archival_user_agent: "bad\nagent".to_string(),
+            ..Config::for_testing()
+        };
+        assert_eq!(invalid_field(&config), "archival_user_agent");
+    }
+
     #[test]
     fn test_parse_bool() {
         assert!(parse_env_bool("NONEXISTENT_VAR", true).unwrap());
@@ -1151,4 +2295,109 @@ bucket = "test-bucket"
         assert_eq!(config.workers.concurrency, None);
         assert_eq!(config.backup.enabled, None);
     }
+
+    fn tls_config() -> Config {
+        Config {
+            tls_enabled: true,
+            tls_domains: vec!["archive.example.com".to_string()],
+            tls_contact_email: Some("admin@example.com".to_string()),
+            web_port: 80,
+            tls_https_port: 443,
+            ..Config::for_testing()
+        }
+    }
+
+    fn invalid_field(config: &Config) -> String {
+        match config.validate() {
+            Err(ConfigError::InvalidValue { name, .. }) => name,
+            other => panic!("expected InvalidValue, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_validate_tls_valid() {
+        assert!(tls_config().validate().is_ok());
+        assert!(Config {
+            tls_contact_email: None,
+            tls_use_staging: true,
+            ..tls_config()
+        }
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn test_validate_tls_requires_domains() {
+        let config = Config {
+            tls_domains: vec![],
+            ..tls_config()
+        };
+        assert_eq!(invalid_field(&config), "tls_domains");
+
+        let config = Config {
+            tls_domains: vec!["  ".to_string()],
+            ..tls_config()
+        };
+        assert_eq!(invalid_field(&config), "tls_domains");
+    }
+
+    #[test]
+    fn test_validate_tls_requires_contact_email_in_production() {
+        let config = Config {
+            tls_contact_email: None,
+            ..tls_config()
+        };
+        assert_eq!(invalid_field(&config), "tls_contact_email");
+
+        let config = Config {
+            tls_contact_email: Some(String::new()),
+            ..tls_config()
+        };
+        assert_eq!(invalid_field(&config), "tls_contact_email");
+    }
+
+    #[test]
+    fn test_validate_tls_port_conflict() {
+        let config = Config {
+            web_port: 443,
+            ..tls_config()
+        };
+        assert_eq!(invalid_field(&config), "tls_https_port");
+    }
+
+    #[test]
+    fn test_validate_tls_ignored_when_disabled() {
+        let config = Config {
+            tls_enabled: false,
+            tls_domains: vec![],
+            tls_contact_email: None,
+            web_port: 443,
+            ..tls_config()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_tls_cert_files() {
+        let config = Config {
+            tls_domains: vec![],
+            tls_contact_email: None,
+            tls_cert_file: Some(PathBuf::from("/etc/ssl/cert.pem")),
+            tls_key_file: Some(PathBuf::from("/etc/ssl/key.pem")),
+            ..tls_config()
+        };
+        assert!(config.validate().is_ok());
+
+        let config = Config {
+            tls_cert_file: Some(PathBuf::from("/etc/ssl/cert.pem")),
+            ..tls_config()
+        };
+        assert_eq!(invalid_field(&config), "tls_key_file");
+
+        let config = Config {
+            tls_key_file: Some(PathBuf::from("/etc/ssl/key.pem")),
+            ..tls_config()
+        };
+        assert_eq!(invalid_field(&config), "tls_cert_file");
+    }
 }
@@ -0,0 +1,622 @@
+//! Composable archive listing queries.
+//!
+//! [`ArchiveQuery`] collects every listing filter (NSFW, content type,
+//! source/domain, status, time window), pagination and sort order, and renders them into a
+//! single parameterized SQL statement. Listing pages and API endpoints build a
+//! query instead of picking between near-duplicate functions that each
+//! support a different subset of filters.
+
+use anyhow::{Context, Result};
+use sqlx::SqlitePool;
+
+use super::models::{Archive, ArchiveDisplay};
+use super::queries::get_domain_filter;
+
+/// Columns selected for [`ArchiveDisplay`] rows (also the `GROUP BY` list).
+const DISPLAY_COLUMNS: &str = "a.id, a.link_id, a.status, a.archived_at,
+            a.content_title, a.content_author, a.content_type,
+            a.is_nsfw, a.error_message, a.retry_count,
+            l.original_url, l.domain";
+
+/// Timestamp used by time-window filters and the [`ArchiveSort::Recent`] order.
+const RECENT_EXPRESSION: &str = "COALESCE(a.post_date, a.archived_at, a.created_at)";
+
+/// Sort order for archive listings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArchiveSort {
+    /// By original post date, falling back to archive time.
+    #[default]
+    Recent,
+    /// By archiving activity (completed or last attempted).
+    Activity,
+    /// By archive ID.
+    Id,
+    /// By total artifact size.
+    Size,
+    /// By link domain.
+    Domain,
+}
+
+impl ArchiveSort {
+    /// Parse a `sort_by` query parameter. Unknown keys return `None`.
+    #[must_use]
+    pub fn from_param(value: &str) -> Option<Self> {
+        match value {
+            "recent" => Some(Self::Recent),
+            "date" => Some(Self::Activity),
+            "id" => Some(Self::Id),
+            "size" => Some(Self::Size),
+            "domain" => Some(Self::Domain),
+            _ => None,
+        }
+    }
+
+    /// The `sort_by` query parameter for this order.
+    #[must_use]
+    pub const fn as_param(self) -> &'static str {
+        match self {
+            Self::Recent => "recent",
+            Self::Activity => "date",
+            Self::Id => "id",
+            Self::Size => "size",
+            Self::Domain => "domain",
+        }
+    }
+
+    /// Direction used when a listing is first sorted by this key.
+    #[must_use]
+    pub const fn default_direction(self) -> SortDirection {
+        match self {
+            Self::Domain => SortDirection::Asc,
+            _ => SortDirection::Desc,
+        }
+    }
+
+    /// `ORDER BY` expression for this key.
+    ///
+    /// Display queries group by archive and can order by the
+    /// `total_size_bytes` aggregate; other shapes sum artifact sizes in a
+    /// subquery instead.
+    const fn expression(self, aggregated: bool) -> &'static str {
+        match self {
+            Self::Recent => RECENT_EXPRESSION,
+            Self::Activity => "COALESCE(a.archived_at, a.last_attempt_at, a.created_at)",
+            Self::Id => "a.id",
+            Self::Size if aggregated => "total_size_bytes",
+            Self::Size => {
+                "(SELECT COALESCE(SUM(size_bytes), 0) FROM archive_artifacts WHERE archive_id = a.id)"
+            }
+            Self::Domain => "l.domain",
+        }
+    }
+
+    /// Full `ORDER BY` list, with the archive ID as a tie-breaker.
+    fn order_by(self, direction: SortDirection, aggregated: bool) -> String {
+        let dir = direction.as_sql();
+        match self {
+            Self::Id => format!("a.id {dir}"),
+            _ => format!("{} {dir}, a.id {dir}", self.expression(aggregated)),
+        }
+    }
+}
+
+/// Direction of an [`ArchiveSort`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortDirection {
+    /// Parse a `sort_dir` query parameter (`asc` or `desc`).
+    #[must_use]
+    pub fn from_param(value: &str) -> Option<Self> {
+        match value {
+            "asc" => Some(Self::Asc),
+            "desc" => Some(Self::Desc),
+            _ => None,
+        }
+    }
+
+    /// The `sort_dir` query parameter for this direction.
+    #[must_use]
+    pub const fn as_param(self) -> &'static str {
+        match self {
+            Self::Asc => "asc",
+            Self::Desc => "desc",
+        }
+    }
+
+    /// The opposite direction.
+    #[must_use]
+    pub const fn reversed(self) -> Self {
+        match self {
+            Self::Asc => Self::Desc,
+            Self::Desc => Self::Asc,
+        }
+    }
+
+    const fn as_sql(self) -> &'static str {
+        match self {
+            Self::Asc => "ASC",
+            Self::Desc => "DESC",
+        }
+    }
+}
+
+/// Builder for filtered, paginated archive listings.
+///
+/// Every filter is optional and all of them can be combined. Queries always
+/// join `links` as `l`, so source and domain filters work with any output
+/// shape.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchiveQuery<'a> {
+    nsfw: Option<bool>,
+    content_type: Option<&'a str>,
+    source: Option<&'a str>,
+    domain: Option<&'a str>,
+    statuses: Vec<&'a str>,
+    since: Option<&'a str>,
+    before: Option<&'a str>,
+    limit: Option<i64>,
+    offset: i64,
+    sort: ArchiveSort,
+    direction: SortDirection,
+}
+
+impl<'a> ArchiveQuery<'a> {
+    /// A query over all archives with no filters.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `Some(true)` keeps only NSFW archives, `Some(false)` hides them.
+    #[must_use]
+    pub const fn nsfw(mut self, nsfw: Option<bool>) -> Self {
+        self.nsfw = nsfw;
+        self
+    }
+
+    /// Keep only archives with this content type (e.g. "video").
+    #[must_use]
+    pub const fn content_type(mut self, content_type: Option<&'a str>) -> Self {
+        self.content_type = content_type;
+        self
+    }
+
+    /// Keep only archives from a source (e.g. "reddit"), including subdomains.
+    #[must_use]
+    pub const fn source(mut self, source: Option<&'a str>) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Keep only archives whose link domain matches exactly.
+    #[must_use]
+    pub const fn domain(mut self, domain: Option<&'a str>) -> Self {
+        self.domain = domain;
+        self
+    }
+
+    /// Keep only archives with this status. Can be called more than once.
+    #[must_use]
+    pub fn status(mut self, status: &'a str) -> Self {
+        self.statuses.push(status);
+        self
+    }
+
+    /// Keep only archives posted (or archived) at or after this timestamp.
+    ///
+    /// Timestamps are compared through SQLite's `datetime()`, so RFC 3339
+    /// values with an offset and `YYYY-MM-DD HH:MM:SS` values mix correctly.
+    #[must_use]
+    pub const fn since(mut self, since: Option<&'a str>) -> Self {
+        self.since = since;
+        self
+    }
+
+    /// Keep only archives posted (or archived) strictly before this timestamp.
+    #[must_use]
+    pub const fn before(mut self, before: Option<&'a str>) -> Self {
+        self.before = before;
+        self
+    }
+
+    /// Maximum number of rows returned.
+    #[must_use]
+    pub const fn limit(mut self, limit: i64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Number of rows to skip.
+    #[must_use]
+    pub const fn offset(mut self, offset: i64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sort order (defaults to [`ArchiveSort::Recent`]).
+    #[must_use]
+    pub const fn sort(mut self, sort: ArchiveSort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Sort direction (defaults to descending).
+    #[must_use]
+    pub const fn direction(mut self, direction: SortDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// The `WHERE` clause (empty when unfiltered) and its bind values in order.
+    #[must_use]
+    pub fn where_clause(&self) -> (String, Vec<String>) {
+        let mut clauses = Vec::new();
+        let mut values = Vec::new();
+
+        if !self.statuses.is_empty() {
+            let placeholders = vec!["?"; self.statuses.len()].join(", ");
+            clauses.push(format!("a.status IN ({placeholders})"));
+            values.extend(self.statuses.iter().map(ToString::to_string));
+        }
+
+        match self.nsfw {
+            Some(true) => clauses.push("a.is_nsfw = 1".to_string()),
+            Some(false) => clauses.push("(a.is_nsfw = 0 OR a.is_nsfw IS NULL)".to_string()),
+            None => {}
+        }
+
+        if let Some(ct) = self.content_type {
+            clauses.push("a.content_type = ?".to_string());
+            values.push(ct.to_string());
+        }
+
+        if let Some(source) = self.source {
+            let filter = get_domain_filter(source);
+            clauses.push(filter.sql);
+            values.extend(filter.values);
+        }
+
+        if let Some(domain) = self.domain {
+            clauses.push("l.domain = ?".to_string());
+            values.push(domain.to_string());
+        }
+
+        if let Some(since) = self.since {
+            clauses.push(format!("datetime({RECENT_EXPRESSION}) >= datetime(?)"));
+            values.push(since.to_string());
+        }
+
+        if let Some(before) = self.before {
+            clauses.push(format!("datetime({RECENT_EXPRESSION}) < datetime(?)"));
+            values.push(before.to_string());
+        }
+
+        if clauses.is_empty() {
+            (String::new(), values)
+        } else {
+            (format!("WHERE {}", clauses.join(" AND ")), values)
+        }
+    }
+
+    /// `ORDER BY ... LIMIT ? OFFSET ?` suffix; a missing limit binds -1 (no limit).
+    fn page_clause(&self, aggregated: bool) -> String {
+        format!(
+            "ORDER BY {} LIMIT ? OFFSET ?",
+            self.sort.order_by(self.direction, aggregated)
+        )
+    }
+
+    fn archives_sql(&self) -> (String, Vec<String>) {
+        let (where_clause, values) = self.where_clause();
+        let sql = format!(
+            "SELECT a.* FROM archives a JOIN links l ON a.link_id = l.id {where_clause} {}",
+            self.page_clause(false)
+        );
+        (sql, values)
+    }
+
+    fn display_sql(&self) -> (String, Vec<String>) {
+        let (where_clause, values) = self.where_clause();
+        let sql = format!(
+            r"
+            SELECT
+                {DISPLAY_COLUMNS},
+                COALESCE(SUM(aa.size_bytes), 0) as total_size_bytes
+            FROM archives a
+            JOIN links l ON a.link_id = l.id
+            LEFT JOIN archive_artifacts aa ON a.id = aa.archive_id
+            {where_clause}
+            GROUP BY {DISPLAY_COLUMNS}
+            {}
+            ",
+            self.page_clause(true)
+        );
+        (sql, values)
+    }
+
+    fn count_sql(&self) -> (String, Vec<String>) {
+        let (where_clause, values) = self.where_clause();
+        let sql = format!(
+            "SELECT COUNT(*) FROM archives a JOIN links l ON a.link_id = l.id {where_clause}"
+        );
+        (sql, values)
+    }
+
+    /// Fetch full archive rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn fetch_archives(&self, pool: &SqlitePool) -> Result<Vec<Archive>> {
+        let (sql, values) = self.archives_sql();
+        let mut query = sqlx::query_as(&sql);
+        for value in values {
+            query = query.bind(value);
+        }
+        query
+            .bind(self.limit.unwrap_or(-1))
+            .bind(self.offset)
+            .fetch_all(pool)
+            .await
+            .context("Failed to fetch archives")
+    }
+
+    /// Fetch archives joined with link info and artifact sizes for display.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn fetch_display(&self, pool: &SqlitePool) -> Result<Vec<ArchiveDisplay>> {
+        let (sql, values) = self.display_sql();
+        let mut query = sqlx::query_as(&sql);
+        for value in values {
+            query = query.bind(value);
+        }
+        query
+            .bind(self.limit.unwrap_or(-1))
+            .bind(self.offset)
+            .fetch_all(pool)
+            .await
+            .context("Failed to fetch archives for display")
+    }
+
+    /// Count matching archives, ignoring pagination.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn count(&self, pool: &SqlitePool) -> Result<i64> {
+        let (sql, values) = self.count_sql();
+        let mut query = sqlx::query_scalar(&sql);
+        for value in values {
+            query = query.bind(value);
+        }
+        query
+            .fetch_one(pool)
+            .await
+            .context("Failed to count archives")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{explain_query_plan, Database};
+
+    #[tokio::test]
+    async fn test_recent_archives_uses_index() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(&temp_dir.path().join("test.sqlite"))
+            .await
+            .unwrap();
+        let (sql, _) = ArchiveQuery::new()
+            .status("complete")
+            .limit(20)
+            .archives_sql();
+        let plan = explain_query_plan(db.pool(), &sql).await.unwrap();
+        assert!(
+            plan.iter()
+                .any(|step| step.starts_with("SEARCH a USING INDEX idx_archives_status_")),
+            "recent archives should search archives by status: {plan:?}"
+        );
+        assert!(
+            !plan.iter().any(|step| step.starts_with("SCAN a")),
+            "recent archives should not scan archives: {plan:?}"
+        );
+    }
+
+    #[test]
+    fn test_unfiltered_has_no_where_clause() {
+        let (clause, values) = ArchiveQuery::new().where_clause();
+        assert_eq!(clause, "");
+        assert!(values.is_empty());
+    }
+
+    #[test]
+    fn test_nsfw_filters() {
+        let (clause, values) = ArchiveQuery::new().nsfw(Some(true)).where_clause();
+        assert_eq!(clause, "WHERE a.is_nsfw = 1");
+        assert!(values.is_empty());
+
+        let (clause, _) = ArchiveQuery::new().nsfw(Some(false)).where_clause();
+        assert_eq!(clause, "WHERE (a.is_nsfw = 0 OR a.is_nsfw IS NULL)");
+    }
+
+    #[test]
+    fn test_status_filter() {
+        let (clause, values) = ArchiveQuery::new()
+            .status("pending")
+            .status("complete")
+            .where_clause();
+        assert_eq!(clause, "WHERE a.status IN (?, ?)");
+        assert_eq!(values, vec!["pending", "complete"]);
+    }
+
+    #[test]
+    fn test_content_type_and_source() {
+        let (clause, values) = ArchiveQuery::new()
+            .content_type(Some("video"))
+            .source(Some("reddit"))
+            .where_clause();
+        assert_eq!(
+            clause,
+            "WHERE a.content_type = ? AND (l.domain = ? OR l.domain LIKE ?)"
+        );
+        assert_eq!(values, vec!["video", "reddit.com", "%.reddit.com"]);
+    }
+
+    #[test]
+    fn test_source_with_nsfw() {
+        let (clause, values) = ArchiveQuery::new()
+            .status("complete")
+            .nsfw(Some(false))
+            .source(Some("twitter"))
+            .where_clause();
+        assert_eq!(
+            clause,
+            "WHERE a.status IN (?) AND (a.is_nsfw = 0 OR a.is_nsfw IS NULL) \
+             AND (l.domain = ? OR l.domain LIKE ? OR l.domain = ? OR l.domain LIKE ?)"
+        );
+        assert_eq!(
+            values,
+            vec![
+                "complete",
+                "x.com",
+                "%.x.com",
+                "twitter.com",
+                "%.twitter.com"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_all_filters_bind_in_placeholder_order() {
+        let query = ArchiveQuery::new()
+            .status("complete")
+            .nsfw(Some(true))
+            .content_type(Some("image"))
+            .source(Some("example.org"))
+            .domain(Some("www.example.org"));
+        let (clause, values) = query.where_clause();
+        assert_eq!(
+            clause,
+            "WHERE a.status IN (?) AND a.is_nsfw = 1 AND a.content_type = ? \
+             AND (l.domain = ? OR l.domain LIKE ?) AND l.domain = ?"
+        );
+        assert_eq!(
+            values,
+            vec![
+                "complete",
+                "image",
+                "example.org",
+                "%.example.org",
+                "www.example.org"
+            ]
+        );
+        assert_eq!(clause.matches('?').count(), values.len());
+    }
+
+    #[test]
+    fn test_time_window_filters() {
+        let (clause, values) = ArchiveQuery::new()
+            .status("complete")
+            .since(Some("2024-01-01T00:00:00Z"))
+            .before(Some("2024-02-01 00:00:00"))
+            .where_clause();
+        assert_eq!(
+            clause,
+            "WHERE a.status IN (?) \
+             AND datetime(COALESCE(a.post_date, a.archived_at, a.created_at)) >= datetime(?) \
+             AND datetime(COALESCE(a.post_date, a.archived_at, a.created_at)) < datetime(?)"
+        );
+        assert_eq!(
+            values,
+            vec!["complete", "2024-01-01T00:00:00Z", "2024-02-01 00:00:00"]
+        );
+    }
+
+    #[test]
+    fn test_sort_and_pagination_suffix() {
+        let (sql, _) = ArchiveQuery::new().archives_sql();
+        assert!(sql.ends_with(
+            "ORDER BY COALESCE(a.post_date, a.archived_at, a.created_at) DESC, a.id DESC \
+             LIMIT ? OFFSET ?"
+        ));
+
+        let (sql, _) = ArchiveQuery::new()
+            .sort(ArchiveSort::Id)
+            .limit(10)
+            .offset(20)
+            .display_sql();
+        assert!(sql.contains("ORDER BY a.id DESC LIMIT ? OFFSET ?"));
+
+        let (sql, _) = ArchiveQuery::new()
+            .sort(ArchiveSort::Activity)
+            .display_sql();
+        assert!(sql.contains(
+            "ORDER BY COALESCE(a.archived_at, a.last_attempt_at, a.created_at) DESC, a.id DESC"
+        ));
+    }
+
+    #[test]
+    fn test_table_sort_keys_map_to_order_by() {
+        for (param, dir, expected) in [
+            ("id", SortDirection::Asc, "a.id ASC"),
+            ("id", SortDirection::Desc, "a.id DESC"),
+            (
+                "size",
+                SortDirection::Desc,
+                "total_size_bytes DESC, a.id DESC",
+            ),
+            ("domain", SortDirection::Asc, "l.domain ASC, a.id ASC"),
+            (
+                "date",
+                SortDirection::Asc,
+                "COALESCE(a.archived_at, a.last_attempt_at, a.created_at) ASC, a.id ASC",
+            ),
+        ] {
+            let sort = ArchiveSort::from_param(param).unwrap();
+            assert_eq!(sort.as_param(), param);
+            let (sql, _) = ArchiveQuery::new().sort(sort).direction(dir).display_sql();
+            assert!(
+                sql.contains(&format!("ORDER BY {expected} LIMIT")),
+                "{param}: {sql}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_size_sort_without_aggregate_uses_subquery() {
+        let (sql, _) = ArchiveQuery::new().sort(ArchiveSort::Size).archives_sql();
+        assert!(sql.contains(
+            "ORDER BY (SELECT COALESCE(SUM(size_bytes), 0) FROM archive_artifacts \
+             WHERE archive_id = a.id) DESC"
+        ));
+    }
+
+    #[test]
+    fn test_sort_params_reject_unknown_values() {
+        assert_eq!(ArchiveSort::from_param("title; DROP TABLE archives"), None);
+        assert_eq!(SortDirection::from_param("ASC"), None);
+        assert_eq!(SortDirection::from_param("asc"), Some(SortDirection::Asc));
+        assert_eq!(SortDirection::Asc.reversed(), SortDirection::Desc);
+    }
+
+    #[test]
+    fn test_count_ignores_pagination() {
+        let (sql, values) = ArchiveQuery::new()
+            .content_type(Some("text"))
+            .limit(5)
+            .count_sql();
+        assert_eq!(
+            sql,
+            "SELECT COUNT(*) FROM archives a JOIN links l ON a.link_id = l.id WHERE a.content_type = ?"
+        );
+        assert_eq!(values, vec!["text"]);
+    }
+}
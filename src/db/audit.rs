@@ -0,0 +1,694 @@
+//! Typed audit events.
+//!
+//! Every audit log entry is described by an [`AuditAction`] variant. The
+//! variant determines the stored `event_type`, the target (`target_type` /
+//! `target_id`) and the JSON `metadata`, so the same event always produces
+//! the same shape. Call sites record events through [`record_audit`] rather
+//! than building metadata strings by hand.
+//!
+//! Metadata is a flat JSON object of the variant's fields (fields that are
+//! already stored as `target_id` are left out). Variants without extra
+//! fields store no metadata.
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use sqlx::SqliteExecutor;
+
+use super::queries::create_audit_event;
+
+/// Serde tag carrying the event type; stripped from the stored metadata.
+const EVENT_TYPE_TAG: &str = "event_type";
+
+/// An auditable action, with its documented metadata shape.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event_type", rename_all = "snake_case")]
+pub enum AuditAction {
+    /// `registration`, no metadata.
+    Registration,
+    /// `login_failed`, no metadata.
+    LoginFailed,
+    /// `login_success`, no metadata.
+    LoginSuccess,
+    /// `logout`, no metadata.
+    Logout,
+    /// `display_name_changed` on `user`: `{"old_value": str|null, "new_value": str|null}`.
+    DisplayNameChanged {
+        #[serde(skip)]
+        user_id: i64,
+        old_value: Option<String>,
+        new_value: Option<String>,
+    },
+    /// `user_approved` on `user`, no metadata.
+    UserApproved {
+        #[serde(skip)]
+        user_id: i64,
+    },
+    /// `user_approval_revoked` on `user`, no metadata.
+    UserApprovalRevoked {
+        #[serde(skip)]
+        user_id: i64,
+    },
+    /// `user_promoted_admin` on `user`, no metadata.
+    UserPromotedAdmin {
+        #[serde(skip)]
+        user_id: i64,
+    },
+    /// `user_demoted_admin` on `user`, no metadata.
+    UserDemotedAdmin {
+        #[serde(skip)]
+        user_id: i64,
+    },
+    /// `user_deactivated` on `user`, no metadata.
+    UserDeactivated {
+        #[serde(skip)]
+        user_id: i64,
+    },
+    /// `user_reactivated` on `user`, no metadata.
+    UserReactivated {
+        #[serde(skip)]
+        user_id: i64,
+    },
+    /// `admin_password_reset` on `user`, no metadata.
+    AdminPasswordReset {
+        #[serde(skip)]
+        user_id: i64,
+    },
+    /// `admin_add_excluded_domain` on `excluded_domain`: `{"domain": str, "reason": str}`.
+    AdminAddExcludedDomain { domain: String, reason: String },
+    /// `admin_toggle_excluded_domain` on `excluded_domain`: `{"domain": str, "active": bool}`.
+    AdminToggleExcludedDomain { domain: String, active: bool },
+    /// `admin_delete_excluded_domain` on `excluded_domain`: `{"domain": str}`.
+    AdminDeleteExcludedDomain { domain: String },
+    /// `admin_set_domain_quote_policy` on `domain_quote_policy`:
+    /// `{"domain": str, "archive_quote_only": bool}`.
+    AdminSetDomainQuotePolicy {
+        domain: String,
+        archive_quote_only: bool,
+    },
+    /// `admin_delete_domain_quote_policy` on `domain_quote_policy`: `{"domain": str}`.
+    AdminDeleteDomainQuotePolicy { domain: String },
+    /// `admin_add_priority_domain` on `priority_domain`: `{"domain": str, "reason": str}`.
+    AdminAddPriorityDomain { domain: String, reason: String },
+    /// `admin_delete_priority_domain` on `priority_domain`: `{"domain": str}`.
+    AdminDeletePriorityDomain { domain: String },
+    /// `admin_add_versioned_domain` on `versioned_domain`: `{"domain": str, "reason": str}`.
+    AdminAddVersionedDomain { domain: String, reason: String },
+    /// `admin_delete_versioned_domain` on `versioned_domain`: `{"domain": str}`.
+    AdminDeleteVersionedDomain { domain: String },
+    /// `admin_set_archive_versioning` on `archive`: `{"keep_versions": bool}`.
+    AdminSetArchiveVersioning {
+        #[serde(skip)]
+        archive_id: i64,
+        keep_versions: bool,
+    },
+    /// `admin_set_external_service_rule` on `external_service_rule`:
+    /// `{"scope": str, "pattern": str, "wayback": bool?, "archive_today": bool?, "ipfs": bool?}`.
+    AdminSetExternalServiceRule {
+        scope: String,
+        pattern: String,
+        wayback: Option<bool>,
+        archive_today: Option<bool>,
+        ipfs: Option<bool>,
+    },
+    /// `admin_delete_external_service_rule` on `external_service_rule`:
+    /// `{"scope": str, "pattern": str}`.
+    AdminDeleteExternalServiceRule { scope: String, pattern: String },
+    /// `admin_delete_forum_link` on `forum_link`: `{"forum_username": str, "user_id": int}`.
+    AdminDeleteForumLink {
+        #[serde(skip)]
+        link_id: i64,
+        forum_username: String,
+        user_id: i64,
+    },
+    /// `admin_reprocess_missing_artifacts`:
+    /// `{"after_id": int, "archives": int, "supplementary_jobs": int, "comment_jobs": int}`.
+    AdminReprocessMissingArtifacts {
+        after_id: i64,
+        archives: usize,
+        supplementary_jobs: usize,
+        comment_jobs: usize,
+    },
+    /// `admin_recompute_sizes`:
+    /// `{"after_id": int, "checked": int, "fixed": int, "missing": int}`.
+    AdminRecomputeSizes {
+        after_id: i64,
+        checked: usize,
+        fixed: usize,
+        missing: usize,
+    },
+    /// `admin_merge_links` on `link`: `{"merged_link_ids": [int], "archives_merged": int}`.
+    AdminMergeLinks {
+        #[serde(skip)]
+        keep_id: i64,
+        merged_link_ids: Vec<i64>,
+        archives_merged: usize,
+    },
+    /// `admin_bulk_import`:
+    /// `{"queued": int, "excluded": int, "already_archived": int, "invalid": int}`.
+    AdminBulkImport {
+        queued: usize,
+        excluded: usize,
+        already_archived: usize,
+        invalid: usize,
+    },
+    /// `admin_reset_og_extraction` on `archive` (single) or no target (bulk):
+    /// `{"archives_reset": int}`.
+    AdminResetOgExtraction {
+        #[serde(skip)]
+        archive_id: Option<i64>,
+        archives_reset: u64,
+    },
+    /// `admin_delete_subtitle_language` on `subtitle_language`, no metadata.
+    AdminDeleteSubtitleLanguage {
+        #[serde(skip)]
+        subtitle_language_id: i64,
+    },
+    /// `nsfw_enabled` on `archive`, no metadata.
+    NsfwEnabled {
+        #[serde(skip)]
+        archive_id: i64,
+    },
+    /// `nsfw_disabled` on `archive`, no metadata.
+    NsfwDisabled {
+        #[serde(skip)]
+        archive_id: i64,
+    },
+    /// `post_author_not_allowed` on `post`: `{"post_guid": str, "author": str|null}`.
+    ///
+    /// Recorded when a new post's links are skipped because its author is not
+    /// on the archive author allowlist.
+    PostAuthorNotAllowed {
+        #[serde(skip)]
+        post_id: i64,
+        post_guid: String,
+        author: Option<String>,
+    },
+    /// `forum_account_linked` on `user`:
+    /// `{"forum_username": str, "post_guid": str, "post_url": str}`.
+    ForumAccountLinked {
+        #[serde(skip)]
+        user_id: i64,
+        forum_username: String,
+        post_guid: String,
+        post_url: String,
+    },
+}
+
+impl AuditAction {
+    /// Serialize to a JSON object (including the event type tag).
+    fn to_map(&self) -> Map<String, Value> {
+        match serde_json::to_value(self) {
+            Ok(Value::Object(map)) => map,
+            _ => Map::new(),
+        }
+    }
+
+    /// The stored `event_type` string, e.g. `user_approved`.
+    #[must_use]
+    pub fn event_type(&self) -> String {
+        self.to_map()
+            .get(EVENT_TYPE_TAG)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// The metadata object for this event, or `None` if it has no fields.
+    #[must_use]
+    pub fn metadata(&self) -> Option<Value> {
+        let mut map = self.to_map();
+        map.remove(EVENT_TYPE_TAG);
+        (!map.is_empty()).then_some(Value::Object(map))
+    }
+
+    /// The `(target_type, target_id)` this event applies to.
+    #[must_use]
+    pub const fn target(&self) -> (Option<&'static str>, Option<i64>) {
+        match self {
+            Self::Registration | Self::LoginFailed | Self::LoginSuccess | Self::Logout => {
+                (None, None)
+            }
+            Self::DisplayNameChanged { user_id, .. }
+            | Self::UserApproved { user_id }
+            | Self::UserApprovalRevoked { user_id }
+            | Self::UserPromotedAdmin { user_id }
+            | Self::UserDemotedAdmin { user_id }
+            | Self::UserDeactivated { user_id }
+            | Self::UserReactivated { user_id }
+            | Self::AdminPasswordReset { user_id }
+            | Self::ForumAccountLinked { user_id, .. } => (Some("user"), Some(*user_id)),
+            Self::AdminAddExcludedDomain { .. }
+            | Self::AdminToggleExcludedDomain { .. }
+            | Self::AdminDeleteExcludedDomain { .. } => (Some("excluded_domain"), None),
+            Self::AdminSetDomainQuotePolicy { .. } | Self::AdminDeleteDomainQuotePolicy { .. } => {
+                (Some("domain_quote_policy"), None)
+            }
+            Self::AdminAddPriorityDomain { .. } | Self::AdminDeletePriorityDomain { .. } => {
+                (Some("priority_domain"), None)
+            }
+            Self::AdminAddVersionedDomain { .. } | Self::AdminDeleteVersionedDomain { .. } => {
+                (Some("versioned_domain"), None)
+            }
+            Self::AdminSetExternalServiceRule { .. }
+            | Self::AdminDeleteExternalServiceRule { .. } => (Some("external_service_rule"), None),
+            Self::AdminDeleteForumLink { link_id, .. } => (Some("forum_link"), Some(*link_id)),
+            Self::AdminReprocessMissingArtifacts { .. }
+            | Self::AdminRecomputeSizes { .. }
+            | Self::AdminBulkImport { .. }
+            | Self::AdminResetOgExtraction {
+                archive_id: None, ..
+            } => (None, None),
+            Self::AdminMergeLinks { keep_id, .. } => (Some("link"), Some(*keep_id)),
+            Self::AdminResetOgExtraction {
+                archive_id: Some(archive_id),
+                ..
+            }
+            | Self::AdminSetArchiveVersioning { archive_id, .. } => {
+                (Some("archive"), Some(*archive_id))
+            }
+            Self::AdminDeleteSubtitleLanguage {
+                subtitle_language_id,
+            } => (Some("subtitle_language"), Some(*subtitle_language_id)),
+            Self::NsfwEnabled { archive_id } | Self::NsfwDisabled { archive_id } => {
+                (Some("archive"), Some(*archive_id))
+            }
+            Self::PostAuthorNotAllowed { post_id, .. } => (Some("post"), Some(*post_id)),
+        }
+    }
+}
+
+/// Who performed an audited action, and from where.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AuditActor<'a> {
+    /// Acting user; `None` for system actions.
+    pub user_id: Option<i64>,
+    pub ip_address: Option<&'a str>,
+    pub forwarded_for: Option<&'a str>,
+    pub user_agent: Option<&'a str>,
+}
+
+impl<'a> AuditActor<'a> {
+    /// An actor with no request context (e.g. actions triggered by the RSS poller).
+    #[must_use]
+    pub const fn new(user_id: Option<i64>) -> Self {
+        Self {
+            user_id,
+            ip_address: None,
+            forwarded_for: None,
+            user_agent: None,
+        }
+    }
+
+    /// An actor acting through an HTTP request.
+    #[must_use]
+    pub const fn from_request(
+        user_id: Option<i64>,
+        ip_address: &'a str,
+        forwarded_for: Option<&'a str>,
+    ) -> Self {
+        Self {
+            user_id,
+            ip_address: Some(ip_address),
+            forwarded_for,
+            user_agent: None,
+        }
+    }
+
+    /// Attach the request's user agent.
+    #[must_use]
+    pub const fn with_user_agent(mut self, user_agent: Option<&'a str>) -> Self {
+        self.user_agent = user_agent;
+        self
+    }
+}
+
+/// Record a typed audit event.
+///
+/// # Errors
+///
+/// Returns an error if the insert fails.
+pub async fn record_audit(
+    executor: impl SqliteExecutor<'_>,
+    actor: &AuditActor<'_>,
+    action: &AuditAction,
+) -> Result<i64> {
+    let (target_type, target_id) = action.target();
+    let metadata = action.metadata().map(|m| m.to_string());
+
+    create_audit_event(
+        executor,
+        actor.user_id,
+        &action.event_type(),
+        target_type,
+        target_id,
+        metadata.as_deref(),
+        actor.ip_address,
+        actor.forwarded_for,
+        actor.user_agent,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_unit_events_have_no_metadata() {
+        for (action, event_type) in [
+            (AuditAction::Registration, "registration"),
+            (AuditAction::LoginFailed, "login_failed"),
+            (AuditAction::LoginSuccess, "login_success"),
+            (AuditAction::Logout, "logout"),
+        ] {
+            assert_eq!(action.event_type(), event_type);
+            assert_eq!(action.metadata(), None);
+            assert_eq!(action.target(), (None, None));
+        }
+    }
+
+    #[test]
+    fn test_user_target_events() {
+        for (action, event_type) in [
+            (AuditAction::UserApproved { user_id: 7 }, "user_approved"),
+            (
+                AuditAction::UserApprovalRevoked { user_id: 7 },
+                "user_approval_revoked",
+            ),
+            (
+                AuditAction::UserPromotedAdmin { user_id: 7 },
+                "user_promoted_admin",
+            ),
+            (
+                AuditAction::UserDemotedAdmin { user_id: 7 },
+                "user_demoted_admin",
+            ),
+            (
+                AuditAction::UserDeactivated { user_id: 7 },
+                "user_deactivated",
+            ),
+            (
+                AuditAction::UserReactivated { user_id: 7 },
+                "user_reactivated",
+            ),
+            (
+                AuditAction::AdminPasswordReset { user_id: 7 },
+                "admin_password_reset",
+            ),
+        ] {
+            assert_eq!(action.event_type(), event_type);
+            assert_eq!(action.metadata(), None);
+            assert_eq!(action.target(), (Some("user"), Some(7)));
+        }
+    }
+
+    #[test]
+    fn test_display_name_changed_shape() {
+        let action = AuditAction::DisplayNameChanged {
+            user_id: 3,
+            old_value: None,
+            new_value: Some("New Name".to_string()),
+        };
+        assert_eq!(action.event_type(), "display_name_changed");
+        assert_eq!(
+            action.metadata(),
+            Some(json!({"old_value": null, "new_value": "New Name"}))
+        );
+        assert_eq!(action.target(), (Some("user"), Some(3)));
+    }
+
+    #[test]
+    fn test_excluded_domain_shapes() {
+        let add = AuditAction::AdminAddExcludedDomain {
+            domain: "example.com".to_string(),
+            reason: "spam".to_string(),
+        };
+        assert_eq!(add.event_type(), "admin_add_excluded_domain");
+        assert_eq!(
+            add.metadata(),
+            Some(json!({"domain": "example.com", "reason": "spam"}))
+        );
+        assert_eq!(add.target(), (Some("excluded_domain"), None));
+
+        let toggle = AuditAction::AdminToggleExcludedDomain {
+            domain: "example.com".to_string(),
+            active: false,
+        };
+        assert_eq!(toggle.event_type(), "admin_toggle_excluded_domain");
+        assert_eq!(
+            toggle.metadata(),
+            Some(json!({"domain": "example.com", "active": false}))
+        );
+
+        let delete = AuditAction::AdminDeleteExcludedDomain {
+            domain: "example.com".to_string(),
+        };
+        assert_eq!(delete.event_type(), "admin_delete_excluded_domain");
+        assert_eq!(delete.metadata(), Some(json!({"domain": "example.com"})));
+    }
+
+    #[test]
+    fn test_domain_quote_policy_shapes() {
+        let set = AuditAction::AdminSetDomainQuotePolicy {
+            domain: "courtlistener.com".to_string(),
+            archive_quote_only: true,
+        };
+        assert_eq!(set.event_type(), "admin_set_domain_quote_policy");
+        assert_eq!(
+            set.metadata(),
+            Some(json!({"domain": "courtlistener.com", "archive_quote_only": true}))
+        );
+        assert_eq!(set.target(), (Some("domain_quote_policy"), None));
+
+        let delete = AuditAction::AdminDeleteDomainQuotePolicy {
+            domain: "courtlistener.com".to_string(),
+        };
+        assert_eq!(delete.event_type(), "admin_delete_domain_quote_policy");
+        assert_eq!(
+            delete.metadata(),
+            Some(json!({"domain": "courtlistener.com"}))
+        );
+        assert_eq!(delete.target(), (Some("domain_quote_policy"), None));
+    }
+
+    #[test]
+    fn test_priority_domain_shapes() {
+        let add = AuditAction::AdminAddPriorityDomain {
+            domain: "*.gov.au".to_string(),
+            reason: "Pages get pulled quickly".to_string(),
+        };
+        assert_eq!(add.event_type(), "admin_add_priority_domain");
+        assert_eq!(
+            add.metadata(),
+            Some(json!({"domain": "*.gov.au", "reason": "Pages get pulled quickly"}))
+        );
+        assert_eq!(add.target(), (Some("priority_domain"), None));
+
+        let delete = AuditAction::AdminDeletePriorityDomain {
+            domain: "*.gov.au".to_string(),
+        };
+        assert_eq!(delete.event_type(), "admin_delete_priority_domain");
+        assert_eq!(delete.metadata(), Some(json!({"domain": "*.gov.au"})));
+        assert_eq!(delete.target(), (Some("priority_domain"), None));
+    }
+
+    #[test]
+    fn test_archive_versioning_shapes() {
+        let add = AuditAction::AdminAddVersionedDomain {
+            domain: "*.gov.au".to_string(),
+            reason: "Media releases get edited".to_string(),
+        };
+        assert_eq!(add.event_type(), "admin_add_versioned_domain");
+        assert_eq!(
+            add.metadata(),
+            Some(json!({"domain": "*.gov.au", "reason": "Media releases get edited"}))
+        );
+        assert_eq!(add.target(), (Some("versioned_domain"), None));
+
+        let delete = AuditAction::AdminDeleteVersionedDomain {
+            domain: "*.gov.au".to_string(),
+        };
+        assert_eq!(delete.event_type(), "admin_delete_versioned_domain");
+        assert_eq!(delete.metadata(), Some(json!({"domain": "*.gov.au"})));
+        assert_eq!(delete.target(), (Some("versioned_domain"), None));
+
+        let toggle = AuditAction::AdminSetArchiveVersioning {
+            archive_id: 7,
+            keep_versions: true,
+        };
+        assert_eq!(toggle.event_type(), "admin_set_archive_versioning");
+        assert_eq!(toggle.metadata(), Some(json!({"keep_versions": true})));
+        assert_eq!(toggle.target(), (Some("archive"), Some(7)));
+    }
+
+    #[test]
+    fn test_external_service_rule_shapes() {
+        let set = AuditAction::AdminSetExternalServiceRule {
+            scope: "content_type".to_string(),
+            pattern: "video".to_string(),
+            wayback: Some(false),
+            archive_today: None,
+            ipfs: Some(true),
+        };
+        assert_eq!(set.event_type(), "admin_set_external_service_rule");
+        assert_eq!(
+            set.metadata(),
+            Some(json!({
+                "scope": "content_type",
+                "pattern": "video",
+                "wayback": false,
+                "archive_today": null,
+                "ipfs": true
+            }))
+        );
+        assert_eq!(set.target(), (Some("external_service_rule"), None));
+
+        let delete = AuditAction::AdminDeleteExternalServiceRule {
+            scope: "domain".to_string(),
+            pattern: "*.example.com".to_string(),
+        };
+        assert_eq!(delete.event_type(), "admin_delete_external_service_rule");
+        assert_eq!(
+            delete.metadata(),
+            Some(json!({"scope": "domain", "pattern": "*.example.com"}))
+        );
+        assert_eq!(delete.target(), (Some("external_service_rule"), None));
+    }
+
+    #[test]
+    fn test_forum_link_shapes() {
+        let deleted = AuditAction::AdminDeleteForumLink {
+            link_id: 4,
+            forum_username: "@alice".to_string(),
+            user_id: 9,
+        };
+        assert_eq!(deleted.event_type(), "admin_delete_forum_link");
+        assert_eq!(
+            deleted.metadata(),
+            Some(json!({"forum_username": "@alice", "user_id": 9}))
+        );
+        assert_eq!(deleted.target(), (Some("forum_link"), Some(4)));
+
+        let linked = AuditAction::ForumAccountLinked {
+            user_id: 9,
+            forum_username: "@alice".to_string(),
+            post_guid: "guid-1".to_string(),
+            post_url: "https://forum.example.com/t/x/1".to_string(),
+        };
+        assert_eq!(linked.event_type(), "forum_account_linked");
+        assert_eq!(
+            linked.metadata(),
+            Some(json!({
+                "forum_username": "@alice",
+                "post_guid": "guid-1",
+                "post_url": "https://forum.example.com/t/x/1",
+            }))
+        );
+        assert_eq!(linked.target(), (Some("user"), Some(9)));
+    }
+
+    #[test]
+    fn test_post_author_not_allowed_shape() {
+        let action = AuditAction::PostAuthorNotAllowed {
+            post_id: 11,
+            post_guid: "forum.example.com-post-11".to_string(),
+            author: Some("@mallory".to_string()),
+        };
+        assert_eq!(action.event_type(), "post_author_not_allowed");
+        assert_eq!(
+            action.metadata(),
+            Some(json!({"post_guid": "forum.example.com-post-11", "author": "@mallory"}))
+        );
+        assert_eq!(action.target(), (Some("post"), Some(11)));
+    }
+
+    #[test]
+    fn test_reprocess_missing_artifacts_shape() {
+        let action = AuditAction::AdminReprocessMissingArtifacts {
+            after_id: 100,
+            archives: 12,
+            supplementary_jobs: 10,
+            comment_jobs: 2,
+        };
+        assert_eq!(action.event_type(), "admin_reprocess_missing_artifacts");
+        assert_eq!(
+            action.metadata(),
+            Some(json!({
+                "after_id": 100,
+                "archives": 12,
+                "supplementary_jobs": 10,
+                "comment_jobs": 2,
+            }))
+        );
+        assert_eq!(action.target(), (None, None));
+    }
+
+    #[test]
+    fn test_merge_links_shape() {
+        let action = AuditAction::AdminMergeLinks {
+            keep_id: 3,
+            merged_link_ids: vec![8, 9],
+            archives_merged: 1,
+        };
+        assert_eq!(action.event_type(), "admin_merge_links");
+        assert_eq!(
+            action.metadata(),
+            Some(json!({"merged_link_ids": [8, 9], "archives_merged": 1}))
+        );
+        assert_eq!(action.target(), (Some("link"), Some(3)));
+    }
+
+    #[test]
+    fn test_bulk_import_shape() {
+        let action = AuditAction::AdminBulkImport {
+            queued: 10,
+            excluded: 1,
+            already_archived: 2,
+            invalid: 3,
+        };
+        assert_eq!(action.event_type(), "admin_bulk_import");
+        assert_eq!(
+            action.metadata(),
+            Some(json!({"queued": 10, "excluded": 1, "already_archived": 2, "invalid": 3}))
+        );
+        assert_eq!(action.target(), (None, None));
+    }
+
+    #[test]
+    fn test_reset_og_extraction_shape() {
+        let single = AuditAction::AdminResetOgExtraction {
+            archive_id: Some(7),
+            archives_reset: 1,
+        };
+        assert_eq!(single.event_type(), "admin_reset_og_extraction");
+        assert_eq!(single.metadata(), Some(json!({"archives_reset": 1})));
+        assert_eq!(single.target(), (Some("archive"), Some(7)));
+
+        let bulk = AuditAction::AdminResetOgExtraction {
+            archive_id: None,
+            archives_reset: 40,
+        };
+        assert_eq!(bulk.metadata(), Some(json!({"archives_reset": 40})));
+        assert_eq!(bulk.target(), (None, None));
+    }
+
+    #[test]
+    fn test_archive_and_subtitle_targets() {
+        let enabled = AuditAction::NsfwEnabled { archive_id: 5 };
+        assert_eq!(enabled.event_type(), "nsfw_enabled");
+        assert_eq!(enabled.metadata(), None);
+        assert_eq!(enabled.target(), (Some("archive"), Some(5)));
+
+        let disabled = AuditAction::NsfwDisabled { archive_id: 5 };
+        assert_eq!(disabled.event_type(), "nsfw_disabled");
+
+        let subtitle = AuditAction::AdminDeleteSubtitleLanguage {
+            subtitle_language_id: 8,
+        };
+        assert_eq!(subtitle.event_type(), "admin_delete_subtitle_language");
+        assert_eq!(subtitle.metadata(), None);
+        assert_eq!(subtitle.target(), (Some("subtitle_language"), Some(8)));
+    }
+}
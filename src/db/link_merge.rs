@@ -0,0 +1,423 @@
+//! Finding and merging duplicate links.
+//!
+//! Links are unique by `normalized_url`, but URLs stored before a change to
+//! the normalization rules can still point at the same page as a newer link.
+//! [`find_duplicate_links`] re-normalizes a page of stored URLs at a time to
+//! find such groups, and [`merge_links`] folds a group into a single link.
+//!
+//! Because each link has at most one archive, merging links also merges their
+//! archives: the best archive is kept, and artifacts, comments and other rows
+//! from the rest are moved onto it before they are deleted. Artifacts dropped
+//! as duplicates of a kept file leave their S3 object behind; its key is
+//! recorded in `orphaned_s3_objects` so it can be cleaned up later.
+
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Context, Result};
+use sqlx::{Sqlite, SqlitePool, Transaction};
+
+use crate::db::{get_link_by_normalized_url, Link};
+use crate::handlers::normalize_link_url;
+
+/// Links that normalize to the same URL under the current rules.
+#[derive(Debug, Clone)]
+pub struct DuplicateLinkGroup {
+    /// The URL every link in the group normalizes to.
+    pub normalized_url: String,
+    /// The duplicate links, oldest first.
+    pub links: Vec<Link>,
+}
+
+impl DuplicateLinkGroup {
+    /// The oldest link, which is kept when the group is merged.
+    #[must_use]
+    pub fn keep(&self) -> &Link {
+        &self.links[0]
+    }
+
+    /// IDs of the links merged into [`Self::keep`].
+    #[must_use]
+    pub fn merge_ids(&self) -> Vec<i64> {
+        self.links[1..].iter().map(|l| l.id).collect()
+    }
+}
+
+/// Counts of what a [`merge_links`] call changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinkMergeSummary {
+    /// Link rows deleted after their data was moved.
+    pub links_removed: usize,
+    /// Link occurrences moved to the kept link.
+    pub occurrences_moved: u64,
+    /// Archives folded into the kept archive and deleted.
+    pub archives_merged: usize,
+    /// Artifacts moved to the kept archive.
+    pub artifacts_moved: usize,
+    /// Artifacts dropped because the kept archive already had the same file.
+    pub artifacts_deduplicated: usize,
+    /// S3 objects left without any artifact and recorded for cleanup.
+    pub objects_orphaned: usize,
+}
+
+/// Duplicate groups found in one page of links.
+#[derive(Debug, Clone, Default)]
+pub struct DuplicateLinkScan {
+    pub groups: Vec<DuplicateLinkGroup>,
+    /// Links checked in this page
+    pub scanned: usize,
+    /// ID of the last link checked, to continue after
+    pub last_id: Option<i64>,
+}
+
+/// Find duplicates among the next `limit` links after `after_id`.
+///
+/// Stored URLs are re-normalized with the current rules, including the site
+/// handler's, and each stale URL is looked up under its current form, so a
+/// link on this page is grouped with the link already stored under that URL
+/// wherever it is. Two stale links on different pages that share no current
+/// link aren't grouped until one of them is re-normalized.
+pub async fn find_duplicate_links(
+    pool: &SqlitePool,
+    after_id: i64,
+    limit: i64,
+) -> Result<DuplicateLinkScan> {
+    let links: Vec<Link> = sqlx::query_as("SELECT * FROM links WHERE id > ? ORDER BY id LIMIT ?")
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .context("Failed to fetch links")?;
+    let mut scan = DuplicateLinkScan {
+        scanned: links.len(),
+        last_id: links.last().map(|l| l.id),
+        ..DuplicateLinkScan::default()
+    };
+
+    let mut by_url: BTreeMap<String, Vec<Link>> = BTreeMap::new();
+    for link in links {
+        by_url
+            .entry(normalize_link_url(&link.normalized_url))
+            .or_default()
+            .push(link);
+    }
+
+    for (normalized_url, mut links) in by_url {
+        if links.iter().any(|l| l.normalized_url != normalized_url) {
+            if let Some(current) = get_link_by_normalized_url(pool, &normalized_url).await? {
+                if links.iter().all(|l| l.id != current.id) {
+                    links.push(current);
+                    links.sort_by_key(|l| l.id);
+                }
+            }
+        }
+        if links.len() > 1 {
+            scan.groups.push(DuplicateLinkGroup {
+                normalized_url,
+                links,
+            });
+        }
+    }
+
+    Ok(scan)
+}
+
+/// Merge `merge_ids` into the link `keep_id` and delete the merged links.
+///
+/// Occurrences, submissions, watches and content versions are moved to the
+/// kept link. The archives of all the links are folded into one, preferring
+/// a complete archive and then the kept link's own. The kept link's
+/// `normalized_url` is then brought up to the current rules. Everything runs
+/// in one transaction, so a failure leaves the data untouched.
+pub async fn merge_links(
+    pool: &SqlitePool,
+    keep_id: i64,
+    merge_ids: &[i64],
+) -> Result<LinkMergeSummary> {
+    let mut tx = pool
+        .begin()
+        .await
+        .context("Failed to begin link merge transaction")?;
+    let summary = merge_links_in(&mut tx, keep_id, merge_ids).await?;
+
+    let stored: String = sqlx::query_scalar("SELECT normalized_url FROM links WHERE id = ?")
+        .bind(keep_id)
+        .fetch_one(&mut *tx)
+        .await
+        .context("Failed to fetch kept link")?;
+    let current = normalize_link_url(&stored);
+    if current != stored {
+        sqlx::query("UPDATE links SET normalized_url = ? WHERE id = ?")
+            .bind(&current)
+            .bind(keep_id)
+            .execute(&mut *tx)
+            .await
+            .with_context(|| {
+                format!("Failed to update kept link to {current}; another link may already use it")
+            })?;
+    }
+
+    tx.commit().await.context("Failed to commit link merge")?;
+
+    Ok(summary)
+}
+
+/// [`merge_links`] inside a transaction owned by the caller.
+async fn merge_links_in(
+    tx: &mut Transaction<'_, Sqlite>,
+    keep_id: i64,
+    merge_ids: &[i64],
+) -> Result<LinkMergeSummary> {
+    if merge_ids.is_empty() {
+        bail!("No links to merge");
+    }
+    if merge_ids.contains(&keep_id) {
+        bail!("Cannot merge link {keep_id} into itself");
+    }
+
+    let mut summary = LinkMergeSummary::default();
+
+    let mut all_ids = vec![keep_id];
+    all_ids.extend_from_slice(merge_ids);
+    let placeholders = vec!["?"; all_ids.len()].join(",");
+
+    let count_sql = format!("SELECT COUNT(*) FROM links WHERE id IN ({placeholders})");
+    let mut count_query = sqlx::query_scalar::<_, i64>(&count_sql);
+    for id in &all_ids {
+        count_query = count_query.bind(id);
+    }
+    let found = count_query
+        .fetch_one(&mut **tx)
+        .await
+        .context("Failed to look up links")?;
+    if usize::try_from(found).unwrap_or_default() != all_ids.len() {
+        bail!("One or more links to merge do not exist");
+    }
+
+    // Keep a complete archive if there is one, then the kept link's own
+    let archives_sql = format!(
+        r"
+        SELECT id FROM archives WHERE link_id IN ({placeholders})
+        ORDER BY status = 'complete' DESC, link_id = ? DESC, id
+        "
+    );
+    let mut archives_query = sqlx::query_scalar::<_, i64>(&archives_sql);
+    for id in &all_ids {
+        archives_query = archives_query.bind(id);
+    }
+    let archive_ids = archives_query
+        .bind(keep_id)
+        .fetch_all(&mut **tx)
+        .await
+        .context("Failed to fetch archives of merged links")?;
+
+    if let Some((&primary, others)) = archive_ids.split_first() {
+        for &other in others {
+            let folded = fold_archive(tx, primary, other).await?;
+            summary.artifacts_moved += folded.moved;
+            summary.artifacts_deduplicated += folded.deduplicated;
+            summary.objects_orphaned += folded.orphaned;
+            summary.archives_merged += 1;
+        }
+        sqlx::query("UPDATE archives SET link_id = ? WHERE id = ?")
+            .bind(keep_id)
+            .bind(primary)
+            .execute(&mut **tx)
+            .await
+            .context("Failed to move archive to kept link")?;
+    }
+
+    for id in merge_ids {
+        // An occurrence in a post that already links the kept URL is redundant
+        summary.occurrences_moved += sqlx::query(
+            r"
+            UPDATE link_occurrences SET link_id = ?
+            WHERE link_id = ? AND post_id NOT IN (
+                SELECT post_id FROM link_occurrences WHERE link_id = ?
+            )
+            ",
+        )
+        .bind(keep_id)
+        .bind(id)
+        .bind(keep_id)
+        .execute(&mut **tx)
+        .await
+        .context("Failed to move link occurrences")?
+        .rows_affected();
+
+        for sql in [
+            "UPDATE submissions SET link_id = ? WHERE link_id = ?",
+            "UPDATE content_versions SET link_id = ? WHERE link_id = ?",
+            "UPDATE OR IGNORE watched_links SET link_id = ? WHERE link_id = ?",
+        ] {
+            sqlx::query(sql)
+                .bind(keep_id)
+                .bind(id)
+                .execute(&mut **tx)
+                .await
+                .context("Failed to move rows to kept link")?;
+        }
+
+        sqlx::query("DELETE FROM links WHERE id = ?")
+            .bind(id)
+            .execute(&mut **tx)
+            .await
+            .context("Failed to delete merged link")?;
+        summary.links_removed += 1;
+    }
+
+    Ok(summary)
+}
+
+/// The artifact columns needed to spot the same file in two archives.
+#[derive(sqlx::FromRow)]
+struct MergeArtifact {
+    id: i64,
+    kind: String,
+    s3_key: String,
+    sha256: Option<String>,
+}
+
+/// Counts from folding one archive into another.
+struct FoldedArchive {
+    moved: usize,
+    deduplicated: usize,
+    orphaned: usize,
+}
+
+async fn fetch_merge_artifacts(
+    tx: &mut Transaction<'_, Sqlite>,
+    archive_id: i64,
+) -> Result<Vec<MergeArtifact>> {
+    sqlx::query_as("SELECT id, kind, s3_key, sha256 FROM archive_artifacts WHERE archive_id = ?")
+        .bind(archive_id)
+        .fetch_all(&mut **tx)
+        .await
+        .context("Failed to fetch archive artifacts")
+}
+
+/// Record `s3_key` for cleanup if no artifact refers to it any more.
+///
+/// Returns whether the key was newly recorded.
+async fn record_orphaned_object(tx: &mut Transaction<'_, Sqlite>, s3_key: &str) -> Result<bool> {
+    let still_used: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM archive_artifacts WHERE s3_key = ?")
+            .bind(s3_key)
+            .fetch_one(&mut **tx)
+            .await
+            .context("Failed to check artifact references")?;
+    if still_used > 0 {
+        return Ok(false);
+    }
+
+    let inserted = sqlx::query(
+        "INSERT OR IGNORE INTO orphaned_s3_objects (s3_key, reason) VALUES (?, 'link_merge')",
+    )
+    .bind(s3_key)
+    .execute(&mut **tx)
+    .await
+    .context("Failed to record orphaned S3 object")?
+    .rows_affected();
+    Ok(inserted > 0)
+}
+
+/// Move everything attached to archive `from` onto archive `into`, then delete `from`.
+async fn fold_archive(
+    tx: &mut Transaction<'_, Sqlite>,
+    into: i64,
+    from: i64,
+) -> Result<FoldedArchive> {
+    let existing = fetch_merge_artifacts(tx, into).await?;
+    let incoming = fetch_merge_artifacts(tx, from).await?;
+
+    let mut folded = FoldedArchive {
+        moved: 0,
+        deduplicated: 0,
+        orphaned: 0,
+    };
+    for artifact in incoming {
+        let same_file = existing.iter().find(|e| {
+            e.kind == artifact.kind
+                && (e.s3_key == artifact.s3_key
+                    || (e.sha256.is_some() && e.sha256 == artifact.sha256))
+        });
+        if let Some(kept) = same_file {
+            sqlx::query(
+                "UPDATE archive_artifacts SET duplicate_of_artifact_id = ? WHERE duplicate_of_artifact_id = ?",
+            )
+            .bind(kept.id)
+            .bind(artifact.id)
+            .execute(&mut **tx)
+            .await
+            .context("Failed to repoint duplicate artifacts")?;
+            sqlx::query("DELETE FROM archive_artifacts WHERE id = ?")
+                .bind(artifact.id)
+                .execute(&mut **tx)
+                .await
+                .context("Failed to delete duplicate artifact")?;
+            folded.deduplicated += 1;
+            if kept.s3_key != artifact.s3_key
+                && record_orphaned_object(tx, &artifact.s3_key).await?
+            {
+                folded.orphaned += 1;
+            }
+        } else {
+            sqlx::query("UPDATE archive_artifacts SET archive_id = ? WHERE id = ?")
+                .bind(into)
+                .bind(artifact.id)
+                .execute(&mut **tx)
+                .await
+                .context("Failed to move artifact")?;
+            folded.moved += 1;
+        }
+    }
+
+    for sql in [
+        "UPDATE comments SET archive_id = ? WHERE archive_id = ?",
+        "UPDATE content_versions SET archive_id = ? WHERE archive_id = ?",
+        "UPDATE posts SET snapshot_archive_id = ? WHERE snapshot_archive_id = ?",
+        "UPDATE archives SET quoted_archive_id = ? WHERE quoted_archive_id = ?",
+        "UPDATE archives SET reply_to_archive_id = ? WHERE reply_to_archive_id = ?",
+        "UPDATE OR IGNORE playlist_items SET playlist_archive_id = ? WHERE playlist_archive_id = ?",
+        "UPDATE OR IGNORE playlist_items SET member_archive_id = ? WHERE member_archive_id = ?",
+    ] {
+        sqlx::query(sql)
+            .bind(into)
+            .bind(from)
+            .execute(&mut **tx)
+            .await
+            .context("Failed to move rows to kept archive")?;
+    }
+
+    // Append the merged archive's captures after the kept archive's own
+    let last_version: i64 = sqlx::query_scalar(
+        "SELECT COALESCE(MAX(version), 0) FROM archive_versions WHERE archive_id = ?",
+    )
+    .bind(into)
+    .fetch_one(&mut **tx)
+    .await
+    .context("Failed to fetch kept archive versions")?;
+    sqlx::query(
+        "UPDATE archive_versions SET archive_id = ?, version = version + ? WHERE archive_id = ?",
+    )
+    .bind(into)
+    .bind(last_version)
+    .bind(from)
+    .execute(&mut **tx)
+    .await
+    .context("Failed to move archive versions")?;
+    sqlx::query("UPDATE OR IGNORE ipfs_pending_pins SET archive_id = ? WHERE archive_id = ?")
+        .bind(into)
+        .bind(from)
+        .execute(&mut **tx)
+        .await
+        .context("Failed to move pending IPFS pin")?;
+
+    // Remaining jobs and clashing playlist entries go with the archive
+    sqlx::query("DELETE FROM archives WHERE id = ?")
+        .bind(from)
+        .execute(&mut **tx)
+        .await
+        .context("Failed to delete merged archive")?;
+
+    Ok(folded)
+}
@@ -1,6 +1,6 @@
 use anyhow::{Context, Result};
-use sqlx::SqlitePool;
-use tracing::debug;
+use sqlx::{Sqlite, SqlitePool, Transaction};
+use tracing::{debug, info};
 
 /// Run all pending migrations.
 pub async fn run(pool: &SqlitePool) -> Result<()> {
@@ -175,6 +175,120 @@ pub async fn run(pool: &SqlitePool) -> Result<()> {
         set_schema_version(pool, 28).await?;
     }
 
+    if current_version < 29 {
+        debug!("Running migration v29");
+        run_migration_v29(pool).await?;
+        set_schema_version(pool, 29).await?;
+    }
+
+    if current_version < 30 {
+        debug!("Running migration v30");
+        run_migration_v30(pool).await?;
+        set_schema_version(pool, 30).await?;
+    }
+
+    if current_version < 31 {
+        debug!("Running migration v31");
+        run_migration_v31(pool).await?;
+        set_schema_version(pool, 31).await?;
+    }
+
+    if current_version < 32 {
+        debug!("Running migration v32");
+        run_migration_v32(pool).await?;
+        set_schema_version(pool, 32).await?;
+    }
+
+    if current_version < 33 {
+        debug!("Running migration v33");
+        run_migration_v33(pool).await?;
+        set_schema_version(pool, 33).await?;
+    }
+
+    if current_version < 34 {
+        debug!("Running migration v34");
+        run_migration_v34(pool).await?;
+        set_schema_version(pool, 34).await?;
+    }
+
+    if current_version < 35 {
+        debug!("Running migration v35");
+        run_migration_v35(pool).await?;
+        set_schema_version(pool, 35).await?;
+    }
+
+    if current_version < 36 {
+        debug!("Running migration v36");
+        run_migration_v36(pool).await?;
+        set_schema_version(pool, 36).await?;
+    }
+
+    if current_version < 37 {
+        debug!("Running migration v37");
+        run_migration_v37(pool).await?;
+        set_schema_version(pool, 37).await?;
+    }
+
+    if current_version < 38 {
+        debug!("Running migration v38");
+        run_migration_v38(pool).await?;
+        set_schema_version(pool, 38).await?;
+    }
+
+    if current_version < 39 {
+        debug!("Running migration v39");
+        run_migration_v39(pool).await?;
+        set_schema_version(pool, 39).await?;
+    }
+
+    if current_version < 40 {
+        debug!("Running migration v40");
+        run_migration_v40(pool).await?;
+        set_schema_version(pool, 40).await?;
+    }
+
+    if current_version < 41 {
+        debug!("Running migration v41");
+        run_migration_v41(pool).await?;
+        set_schema_version(pool, 41).await?;
+    }
+
+    if current_version < 42 {
+        debug!("Running migration v42");
+        run_migration_v42(pool).await?;
+        set_schema_version(pool, 42).await?;
+    }
+
+    if current_version < 43 {
+        debug!("Running migration v43");
+        run_migration_v43(pool).await?;
+        set_schema_version(pool, 43).await?;
+    }
+
+    if current_version < 44 {
+        debug!("Running migration v44");
+        run_migration_v44(pool).await?;
+        set_schema_version(pool, 44).await?;
+    }
+
+    if current_version < 45 {
+        debug!("Running migration v45");
+        run_migration_v45(pool).await?;
+        set_schema_version(pool, 45).await?;
+    }
+
+    if current_version < 46 {
+        debug!("Running migration v46");
+        run_migration_v46(pool).await?;
+        set_schema_version(pool, 46).await?;
+    }
+
+    if current_version < 47 {
+        debug!("Running migration v47");
+        run_migration_v47(pool).await?;
+        set_schema_version(pool, 47).await?;
+    }
+
     Ok(())
 }
 
@@ -1536,3 +1650,860 @@ async fn run_migration_v28(pool: &SqlitePool) -> Result<()> {
 
     Ok(())
 }
+
+async fn run_migration_v29(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v29: adding playlist_items table");
+
+    // Membership of individual video archives within a playlist archive.
+    // Position is the 1-based index of the entry in the playlist.
+    sqlx::query(
+        r"
+        CREATE TABLE IF NOT EXISTS playlist_items (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            playlist_archive_id INTEGER NOT NULL REFERENCES archives(id) ON DELETE CASCADE,
+            member_archive_id INTEGER NOT NULL REFERENCES archives(id) ON DELETE CASCADE,
+            position INTEGER NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            UNIQUE(playlist_archive_id, member_archive_id)
+        )
+        ",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create playlist_items table")?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_playlist_items_playlist ON playlist_items(playlist_archive_id, position)",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create playlist_items playlist index")?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_playlist_items_member ON playlist_items(member_archive_id)",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create playlist_items member index")?;
+
+    Ok(())
+}
+
+async fn run_migration_v30(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v30: adding domain_quote_policies table");
+
+    // Per-domain override of the global ARCHIVE_QUOTE_ONLY_LINKS setting.
+    // Domain patterns follow the same rules as excluded_domains.
+    sqlx::query(
+        r"
+        CREATE TABLE IF NOT EXISTS domain_quote_policies (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            domain TEXT NOT NULL UNIQUE,
+            archive_quote_only INTEGER NOT NULL,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            created_by_user_id INTEGER,
+            updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )
+        ",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create domain_quote_policies table")?;
+
+    Ok(())
+}
+
+async fn run_migration_v31(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v31: adding posts.snapshot_archive_id");
+
+    // Archive of the Discourse post page itself (ARCHIVE_POST_SNAPSHOTS).
+    sqlx::query(
+        "ALTER TABLE posts ADD COLUMN snapshot_archive_id INTEGER REFERENCES archives(id) ON DELETE SET NULL",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to add snapshot_archive_id column")?;
+
+    Ok(())
+}
+
+async fn run_migration_v32(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v32: adding watched_links and content_versions tables");
+
+    // Links an admin asked to re-check periodically for content changes.
+    sqlx::query(
+        r"
+        CREATE TABLE IF NOT EXISTS watched_links (
+            link_id INTEGER PRIMARY KEY REFERENCES links(id) ON DELETE CASCADE,
+            check_interval_hours INTEGER NOT NULL,
+            last_checked_at TEXT,
+            next_check_at TEXT,
+            created_by_user_id INTEGER,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )
+        ",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create watched_links table")?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_watched_links_next_check ON watched_links(next_check_at)",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create watched_links next_check index")?;
+
+    // Normalized text snapshots of a watched link, each pointing at the one before it.
+    sqlx::query(
+        r"
+        CREATE TABLE IF NOT EXISTS content_versions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            link_id INTEGER NOT NULL REFERENCES links(id) ON DELETE CASCADE,
+            archive_id INTEGER REFERENCES archives(id) ON DELETE SET NULL,
+            previous_version_id INTEGER REFERENCES content_versions(id) ON DELETE SET NULL,
+            content_hash TEXT NOT NULL,
+            content_text TEXT NOT NULL,
+            captured_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )
+        ",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create content_versions table")?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_content_versions_link ON content_versions(link_id, id)",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create content_versions link index")?;
+
+    Ok(())
+}
+
+async fn run_migration_v33(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v33: adding thumbnail placeholder columns to archive_artifacts");
+
+    // Dominant colour and pixel size of image artifacts, so cards can reserve
+    // space and show a placeholder before the thumbnail loads.
+    for (column, sql) in [
+        (
+            "placeholder_color",
+            "ALTER TABLE archive_artifacts ADD COLUMN placeholder_color TEXT",
+        ),
+        (
+            "width",
+            "ALTER TABLE archive_artifacts ADD COLUMN width INTEGER",
+        ),
+        (
+            "height",
+            "ALTER TABLE archive_artifacts ADD COLUMN height INTEGER",
+        ),
+    ] {
+        sqlx::query(sql)
+            .execute(pool)
+            .await
+            .with_context(|| format!("Failed to add archive_artifacts.{column} column"))?;
+    }
+
+    Ok(())
+}
+
+async fn run_migration_v34(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v34: add unique constraint on links.normalized_url");
+
+    let mut tx = pool
+        .begin()
+        .await
+        .context("Failed to begin migration v34 transaction")?;
+
+    // S3 objects no artifact refers to any more, kept for later cleanup.
+    // Merging duplicates below can leave some behind.
+    sqlx::query(
+        r"
+        CREATE TABLE IF NOT EXISTS orphaned_s3_objects (
+            s3_key TEXT PRIMARY KEY,
+            reason TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )
+        ",
+    )
+    .execute(&mut *tx)
+    .await
+    .context("Failed to create orphaned_s3_objects table")?;
+
+    // Concurrent ingest could insert the same normalized URL twice. Merge each
+    // duplicate into the oldest link before adding the unique index, keeping
+    // the best archive and its artifacts.
+    let duplicates: Vec<(i64, String)> = sqlx::query_as(
+        r"
+        SELECT id, normalized_url FROM links
+        WHERE normalized_url IN (
+            SELECT normalized_url FROM links
+            GROUP BY normalized_url
+            HAVING COUNT(*) > 1
+        )
+        ORDER BY normalized_url, id
+        ",
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .context("Failed to find duplicate links")?;
+
+    let mut groups: Vec<(String, Vec<i64>)> = Vec::new();
+    for (id, normalized_url) in duplicates {
+        match groups.last_mut() {
+            Some((url, ids)) if *url == normalized_url => ids.push(id),
+            _ => groups.push((normalized_url, vec![id])),
+        }
+    }
+    for (normalized_url, ids) in &groups {
+        let orphaned = v34_merge_links(&mut tx, ids[0], &ids[1..])
+            .await
+            .with_context(|| format!("Failed to merge duplicate links for {normalized_url}"))?;
+        info!(
+            normalized_url,
+            kept_link_id = ids[0],
+            links_removed = ids.len() - 1,
+            objects_orphaned = orphaned,
+            "Merged duplicate links"
+        );
+    }
+
+    sqlx::query("DROP INDEX IF EXISTS idx_links_normalized_url")
+        .execute(&mut *tx)
+        .await
+        .context("Failed to drop links.normalized_url index")?;
+    sqlx::query("CREATE UNIQUE INDEX idx_links_normalized_url ON links(normalized_url)")
+        .execute(&mut *tx)
+        .await
+        .context("Failed to create unique index on links.normalized_url")?;
+
+    tx.commit()
+        .await
+        .context("Failed to commit migration v34")?;
+
+    Ok(())
+}
+
+/// Fold the links `merge_ids` into `keep_id` against the v34 schema.
+///
+/// Kept as it was at v34 rather than calling `link_merge`, whose queries
+/// follow the current schema. Returns how many S3 objects were orphaned.
+async fn v34_merge_links(
+    tx: &mut Transaction<'_, Sqlite>,
+    keep_id: i64,
+    merge_ids: &[i64],
+) -> Result<usize> {
+    let mut all_ids = vec![keep_id];
+    all_ids.extend_from_slice(merge_ids);
+    let placeholders = vec!["?"; all_ids.len()].join(",");
+
+    // Keep a complete archive if there is one, then the kept link's own
+    let archives_sql = format!(
+        r"
+        SELECT id FROM archives WHERE link_id IN ({placeholders})
+        ORDER BY status = 'complete' DESC, link_id = ? DESC, id
+        "
+    );
+    let mut archives_query = sqlx::query_scalar::<_, i64>(&archives_sql);
+    for id in &all_ids {
+        archives_query = archives_query.bind(id);
+    }
+    let archive_ids = archives_query
+        .bind(keep_id)
+        .fetch_all(&mut **tx)
+        .await
+        .context("Failed to fetch archives of merged links")?;
+
+    let mut orphaned = 0;
+    if let Some((&primary, others)) = archive_ids.split_first() {
+        for &other in others {
+            orphaned += v34_fold_archive(tx, primary, other).await?;
+        }
+        sqlx::query("UPDATE archives SET link_id = ? WHERE id = ?")
+            .bind(keep_id)
+            .bind(primary)
+            .execute(&mut **tx)
+            .await
+            .context("Failed to move archive to kept link")?;
+    }
+
+    for id in merge_ids {
+        for sql in [
+            r"
+            UPDATE link_occurrences SET link_id = ?1
+            WHERE link_id = ?2 AND post_id NOT IN (
+                SELECT post_id FROM link_occurrences WHERE link_id = ?1
+            )
+            ",
+            "UPDATE submissions SET link_id = ?1 WHERE link_id = ?2",
+            "UPDATE content_versions SET link_id = ?1 WHERE link_id = ?2",
+            "UPDATE OR IGNORE watched_links SET link_id = ?1 WHERE link_id = ?2",
+            "DELETE FROM links WHERE id = ?2",
+        ] {
+            sqlx::query(sql)
+                .bind(keep_id)
+                .bind(id)
+                .execute(&mut **tx)
+                .await
+                .context("Failed to move rows to kept link")?;
+        }
+    }
+
+    Ok(orphaned)
+}
+
+/// Move everything attached to archive `from` onto `into`, then delete `from`.
+///
+/// An artifact of the same kind and file as one `into` already has is
+/// dropped, and its S3 object recorded if nothing else uses it. Returns how
+/// many objects were recorded.
+async fn v34_fold_archive(tx: &mut Transaction<'_, Sqlite>, into: i64, from: i64) -> Result<usize> {
+    let artifacts_sql =
+        "SELECT id, kind, s3_key, sha256 FROM archive_artifacts WHERE archive_id = ?";
+    let existing: Vec<(i64, String, String, Option<String>)> = sqlx::query_as(artifacts_sql)
+        .bind(into)
+        .fetch_all(&mut **tx)
+        .await
+        .context("Failed to fetch archive artifacts")?;
+    let incoming: Vec<(i64, String, String, Option<String>)> = sqlx::query_as(artifacts_sql)
+        .bind(from)
+        .fetch_all(&mut **tx)
+        .await
+        .context("Failed to fetch archive artifacts")?;
+
+    let mut orphaned = 0;
+    for (id, kind, s3_key, sha256) in incoming {
+        let same_file = existing.iter().find(|(_, k, key, sha)| {
+            *k == kind && (*key == s3_key || (sha.is_some() && *sha == sha256))
+        });
+        let Some((kept_id, _, kept_key, _)) = same_file else {
+            sqlx::query("UPDATE archive_artifacts SET archive_id = ? WHERE id = ?")
+                .bind(into)
+                .bind(id)
+                .execute(&mut **tx)
+                .await
+                .context("Failed to move artifact")?;
+            continue;
+        };
+
+        sqlx::query(
+            "UPDATE archive_artifacts SET duplicate_of_artifact_id = ? WHERE duplicate_of_artifact_id = ?",
+        )
+        .bind(kept_id)
+        .bind(id)
+        .execute(&mut **tx)
+        .await
+        .context("Failed to repoint duplicate artifacts")?;
+        sqlx::query("DELETE FROM archive_artifacts WHERE id = ?")
+            .bind(id)
+            .execute(&mut **tx)
+            .await
+            .context("Failed to delete duplicate artifact")?;
+        if *kept_key != s3_key {
+            orphaned += sqlx::query(
+                r"
+                INSERT OR IGNORE INTO orphaned_s3_objects (s3_key, reason)
+                SELECT ?1, 'link_merge'
+                WHERE NOT EXISTS (SELECT 1 FROM archive_artifacts WHERE s3_key = ?1)
+                ",
+            )
+            .bind(&s3_key)
+            .execute(&mut **tx)
+            .await
+            .context("Failed to record orphaned S3 object")?
+            .rows_affected();
+        }
+    }
+
+    for sql in [
+        "UPDATE comments SET archive_id = ? WHERE archive_id = ?",
+        "UPDATE content_versions SET archive_id = ? WHERE archive_id = ?",
+        "UPDATE posts SET snapshot_archive_id = ? WHERE snapshot_archive_id = ?",
+        "UPDATE archives SET quoted_archive_id = ? WHERE quoted_archive_id = ?",
+        "UPDATE archives SET reply_to_archive_id = ? WHERE reply_to_archive_id = ?",
+        "UPDATE OR IGNORE playlist_items SET playlist_archive_id = ? WHERE playlist_archive_id = ?",
+        "UPDATE OR IGNORE playlist_items SET member_archive_id = ? WHERE member_archive_id = ?",
+    ] {
+        sqlx::query(sql)
+            .bind(into)
+            .bind(from)
+            .execute(&mut **tx)
+            .await
+            .context("Failed to move rows to kept archive")?;
+    }
+
+    // Remaining jobs and clashing playlist entries go with the archive
+    sqlx::query("DELETE FROM archives WHERE id = ?")
+        .bind(from)
+        .execute(&mut **tx)
+        .await
+        .context("Failed to delete merged archive")?;
+
+    Ok(usize::try_from(orphaned).unwrap_or(usize::MAX))
+}
+
+async fn run_migration_v35(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v35: adding external_service_rules table");
+
+    // Domain- or content-type-scoped overrides of the global Wayback,
+    // Archive.today and IPFS switches. NULL leaves a service to the next rule.
+    sqlx::query(
+        r"
+        CREATE TABLE IF NOT EXISTS external_service_rules (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            scope TEXT NOT NULL,
+            pattern TEXT NOT NULL,
+            wayback INTEGER,
+            archive_today INTEGER,
+            ipfs INTEGER,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            created_by_user_id INTEGER,
+            updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            UNIQUE(scope, pattern)
+        )
+        ",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create external_service_rules table")?;
+
+    Ok(())
+}
+
+async fn run_migration_v36(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v36: adding redirect_chain column to archives");
+
+    // Skip if present so re-running from an older schema version is harmless
+    let exists: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM pragma_table_info('archives') WHERE name = 'redirect_chain'",
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to inspect archives columns")?;
+
+    // JSON array of {url, status} hops seen while resolving the link
+    if exists == 0 {
+        sqlx::query("ALTER TABLE archives ADD COLUMN redirect_chain TEXT")
+            .execute(pool)
+            .await
+            .context("Failed to add redirect_chain column")?;
+    }
+
+    Ok(())
+}
+
+async fn run_migration_v37(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v37: adding ipfs_pending_pins table");
+
+    // Archives whose IPFS pin was skipped while the daemon was unavailable;
+    // they're pinned from S3 once it recovers
+    sqlx::query(
+        r"
+        CREATE TABLE IF NOT EXISTS ipfs_pending_pins (
+            archive_id INTEGER PRIMARY KEY REFERENCES archives(id) ON DELETE CASCADE,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )
+        ",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create ipfs_pending_pins table")?;
+
+    Ok(())
+}
+
+async fn run_migration_v38(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v38: adding short_code column to archives");
+
+    let exists: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM pragma_table_info('archives') WHERE name = 'short_code'",
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to inspect archives columns")?;
+
+    if exists == 0 {
+        sqlx::query("ALTER TABLE archives ADD COLUMN short_code TEXT")
+            .execute(pool)
+            .await
+            .context("Failed to add short_code column")?;
+    }
+
+    // Give existing archives a code before the unique index goes on
+    let ids: Vec<i64> = sqlx::query_scalar("SELECT id FROM archives WHERE short_code IS NULL")
+        .fetch_all(pool)
+        .await
+        .context("Failed to list archives without short codes")?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .context("Failed to begin migration v38 transaction")?;
+    let mut used = std::collections::HashSet::new();
+    for id in ids {
+        let mut code = super::generate_short_code();
+        while !used.insert(code.clone()) {
+            code = super::generate_short_code();
+        }
+        sqlx::query("UPDATE archives SET short_code = ? WHERE id = ?")
+            .bind(&code)
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to backfill archive short code")?;
+    }
+
+    sqlx::query(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_archives_short_code ON archives(short_code)",
+    )
+    .execute(&mut *tx)
+    .await
+    .context("Failed to create unique index on archives.short_code")?;
+
+    tx.commit()
+        .await
+        .context("Failed to commit migration v38")?;
+
+    Ok(())
+}
+
+async fn run_migration_v39(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v39: remembering the previous primary artifact across re-archives");
+
+    for column in ["previous_primary_s3_key", "previous_primary_sha256"] {
+        let exists: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM pragma_table_info('archives') WHERE name = ?")
+                .bind(column)
+                .fetch_one(pool)
+                .await
+                .context("Failed to inspect archives columns")?;
+
+        if exists == 0 {
+            sqlx::query(&format!("ALTER TABLE archives ADD COLUMN {column} TEXT"))
+                .execute(pool)
+                .await
+                .with_context(|| format!("Failed to add {column} column"))?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_migration_v40(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v40: adding priority_domains table");
+
+    // Links on these domains are processed and retried ahead of others.
+    // Domain patterns follow the same rules as excluded_domains.
+    sqlx::query(
+        r"
+        CREATE TABLE IF NOT EXISTS priority_domains (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            domain TEXT NOT NULL UNIQUE,
+            reason TEXT NOT NULL DEFAULT '',
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            created_by_user_id INTEGER
+        )
+        ",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create priority_domains table")?;
+
+    Ok(())
+}
+
+async fn run_migration_v41(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v41: adding archive versioning");
+
+    // Prior captures preserved when a versioned archive is re-archived.
+    // Artifacts are copied under s3_prefix; content text is kept for diffing.
+    sqlx::query(
+        r"
+        CREATE TABLE IF NOT EXISTS archive_versions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            archive_id INTEGER NOT NULL REFERENCES archives(id) ON DELETE CASCADE,
+            version INTEGER NOT NULL,
+            archived_at TEXT,
+            s3_prefix TEXT NOT NULL,
+            s3_key_primary TEXT,
+            content_title TEXT,
+            content_text TEXT,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            UNIQUE(archive_id, version)
+        )
+        ",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create archive_versions table")?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_archive_versions_archive ON archive_versions(archive_id)",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create archive_versions index")?;
+
+    let exists: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM pragma_table_info('archives') WHERE name = 'keep_versions'",
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to check for keep_versions column")?;
+    if exists == 0 {
+        sqlx::query("ALTER TABLE archives ADD COLUMN keep_versions INTEGER NOT NULL DEFAULT 0")
+            .execute(pool)
+            .await
+            .context("Failed to add keep_versions column")?;
+    }
+
+    // Archives on these domains keep versions without a per-archive opt-in.
+    // Domain patterns follow the same rules as excluded_domains.
+    sqlx::query(
+        r"
+        CREATE TABLE IF NOT EXISTS versioned_domains (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            domain TEXT NOT NULL UNIQUE,
+            reason TEXT NOT NULL DEFAULT '',
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            created_by_user_id INTEGER
+        )
+        ",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create versioned_domains table")?;
+
+    Ok(())
+}
+
+async fn run_migration_v42(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v42: adding feed poll state");
+
+    // Per-feed polling state. A feed with no row has never been polled, which
+    // is when RSS_BACKFILL ingests its full history; `backfill_cursor` is where
+    // a backfill cut short by its page limit picks up again.
+    sqlx::query(
+        r"
+        CREATE TABLE IF NOT EXISTS feed_state (
+            feed_url TEXT PRIMARY KEY,
+            newest_post_id INTEGER,
+            backfill_cursor INTEGER,
+            backfilled_at TEXT,
+            last_polled_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )
+        ",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create feed_state table")?;
+
+    Ok(())
+}
+
+async fn run_migration_v43(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v43: adding archive error kinds");
+
+    // Failure category (see archiver::ArchiveErrorKind), alongside error_message
+    let exists: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM pragma_table_info('archives') WHERE name = 'error_kind'",
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to check for error_kind column")?;
+    if exists == 0 {
+        sqlx::query("ALTER TABLE archives ADD COLUMN error_kind TEXT")
+            .execute(pool)
+            .await
+            .context("Failed to add error_kind column")?;
+    }
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_archives_error_kind ON archives(error_kind)")
+        .execute(pool)
+        .await
+        .context("Failed to create error_kind index")?;
+
+    // The only category older failures can be given without their error chain
+    sqlx::query(
+        "UPDATE archives SET error_kind = 'auth_required' WHERE status = 'auth_required' AND error_kind IS NULL",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to backfill auth_required error kinds")?;
+
+    Ok(())
+}
+
+async fn run_migration_v44(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v44: normalizing forum authors");
+
+    // Canonical forum username next to the raw author string
+    let exists: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM pragma_table_info('posts') WHERE name = 'author_handle'",
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to check for author_handle column")?;
+    if exists == 0 {
+        sqlx::query("ALTER TABLE posts ADD COLUMN author_handle TEXT")
+            .execute(pool)
+            .await
+            .context("Failed to add author_handle column")?;
+    }
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_posts_author_handle ON posts(author_handle COLLATE NOCASE)",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create author_handle index")?;
+
+    // Existing rows get the default rules; FORUM_AUTHOR_PATTERN applies from the next ingest
+    let posts: Vec<(i64, String)> = sqlx::query_as(
+        "SELECT id, author FROM posts WHERE author IS NOT NULL AND author_handle IS NULL",
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to list posts without author handles")?;
+    let links: Vec<(i64, String)> =
+        sqlx::query_as("SELECT id, forum_username FROM forum_account_links")
+            .fetch_all(pool)
+            .await
+            .context("Failed to list forum account links")?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .context("Failed to begin migration v44 transaction")?;
+    for (id, author) in posts {
+        sqlx::query("UPDATE posts SET author_handle = ? WHERE id = ?")
+            .bind(super::forum_author_handle(&author))
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to backfill post author handle")?;
+    }
+    // Links stored the raw author as their username; keep it as forum_author_raw.
+    // A link whose handle is already taken keeps its raw username.
+    for (id, raw) in links {
+        let Some(handle) = super::forum_author_handle(&raw).filter(|h| *h != raw) else {
+            continue;
+        };
+        sqlx::query(
+            "UPDATE OR IGNORE forum_account_links SET forum_username = ?, forum_author_raw = ? WHERE id = ?",
+        )
+        .bind(handle)
+        .bind(&raw)
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to normalize forum account link username")?;
+    }
+
+    tx.commit()
+        .await
+        .context("Failed to commit migration v44")?;
+
+    Ok(())
+}
+
+async fn run_migration_v45(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v45: adding artifact content encoding");
+
+    // Precompressed artifacts (e.g. zstd complete.html) record their encoding
+    let exists: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM pragma_table_info('archive_artifacts') WHERE name = 'content_encoding'",
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to check for content_encoding column")?;
+    if exists == 0 {
+        sqlx::query("ALTER TABLE archive_artifacts ADD COLUMN content_encoding TEXT")
+            .execute(pool)
+            .await
+            .context("Failed to add content_encoding column")?;
+    }
+
+    Ok(())
+}
+
+async fn run_migration_v46(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v46: adding archive skip reason");
+
+    let exists: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM pragma_table_info('archives') WHERE name = 'skip_reason'",
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to check for skip_reason column")?;
+    if exists == 0 {
+        sqlx::query("ALTER TABLE archives ADD COLUMN skip_reason TEXT")
+            .execute(pool)
+            .await
+            .context("Failed to add skip_reason column")?;
+    }
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_archives_skip_reason ON archives(skip_reason)")
+        .execute(pool)
+        .await
+        .context("Failed to create skip_reason index")?;
+
+    // Best-effort reasons for archives skipped before they were recorded
+    sqlx::query(
+        r"
+        UPDATE archives
+        SET skip_reason = CASE
+            WHEN error_message LIKE '%noarchive requested%' THEN 'noarchive'
+            WHEN error_kind = 'too_large' THEN 'size_cap'
+            WHEN error_kind = 'unsupported' THEN 'unsupported'
+            WHEN error_kind = 'not_found' THEN 'not_found'
+        END
+        WHERE status = 'skipped' AND skip_reason IS NULL
+        ",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to backfill skip reasons")?;
+
+    Ok(())
+}
+
+async fn run_migration_v47(pool: &SqlitePool) -> Result<()> {
+    debug!("Running migration v47: adding composite indexes for hot archive queries");
+
+    // Status listings ordered by age (home page, pending queue)
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_archives_status_created_at ON archives(status, created_at)",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create status/created_at index")?;
+
+    // Retry picker: failed archives whose next_retry_at has passed
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_archives_status_next_retry_at ON archives(status, next_retry_at)",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create status/next_retry_at index")?;
+
+    // Both composites lead with status, so the single-column index is redundant
+    sqlx::query("DROP INDEX IF EXISTS idx_archives_status")
+        .execute(pool)
+        .await
+        .context("Failed to drop status index")?;
+
+    // Thread and post pages join occurrences by post, then link
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_link_occurrences_post_link ON link_occurrences(post_id, link_id)",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to create link_occurrences post/link index")?;
+
+    Ok(())
+}
@@ -1,12 +1,22 @@
+mod archive_query;
+mod audit;
 pub mod backfill;
 mod fts;
+mod link_merge;
 mod migrations;
 mod models;
 mod queries;
+mod short_code;
 
+pub use archive_query::{ArchiveQuery, ArchiveSort, SortDirection};
+pub use audit::*;
 pub use fts::*;
+pub use link_merge::{
+    find_duplicate_links, merge_links, DuplicateLinkGroup, DuplicateLinkScan, LinkMergeSummary,
+};
 pub use models::*;
 pub use queries::*;
+pub use short_code::{generate_short_code, is_valid_short_code, SHORT_CODE_LEN};
 
 use std::path::Path;
 use std::time::Duration;
@@ -15,18 +25,65 @@ use anyhow::{Context, Result};
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
 use tracing::info;
 
+use crate::config::Config;
+
+/// Connection pool size and SQLite tuning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DatabaseOptions {
+    /// Connections in each of the read-write and read-only pools.
+    pub max_connections: u32,
+    /// How long a connection waits on a locked database before `SQLITE_BUSY`.
+    pub busy_timeout: Duration,
+    /// Page cache per connection, in KiB.
+    pub cache_size_kb: u32,
+}
+
+impl Default for DatabaseOptions {
+    fn default() -> Self {
+        Self {
+            max_connections: 5,
+            busy_timeout: Duration::from_secs(10),
+            // SQLite's own default
+            cache_size_kb: 2000,
+        }
+    }
+}
+
+impl From<&Config> for DatabaseOptions {
+    fn from(config: &Config) -> Self {
+        Self {
+            max_connections: config.db_max_connections,
+            busy_timeout: Duration::from_secs(config.db_busy_timeout_secs),
+            cache_size_kb: config.db_cache_size_kb,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Database {
     pool: SqlitePool,
+    /// Read-only connections to the same file, so page loads don't queue
+    /// behind writers. Under WAL, readers see every committed write.
+    read_pool: SqlitePool,
 }
 
 impl Database {
-    /// Create a new database connection, running migrations if needed.
+    /// Create a new database connection with default options, running
+    /// migrations if needed.
     ///
     /// # Errors
     ///
     /// Returns an error if the connection fails or migrations fail.
     pub async fn new(path: &Path) -> Result<Self> {
+        Self::with_options(path, &DatabaseOptions::default()).await
+    }
+
+    /// Create a new database connection, running migrations if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection fails or migrations fail.
+    pub async fn with_options(path: &Path, db_options: &DatabaseOptions) -> Result<Self> {
         let options = SqliteConnectOptions::new()
             .filename(path)
             .create_if_missing(true)
@@ -36,16 +93,34 @@ impl Database {
             // Without a busy timeout, concurrent writers can cause immediate SQLITE_BUSY
             // errors (e.g. when resetting/deleting archives from the web UI while the
             // worker is writing). WAL helps, but writes are still serialized.
-            .busy_timeout(Duration::from_secs(10));
+            .busy_timeout(db_options.busy_timeout)
+            // Negative values are KiB rather than pages
+            .pragma("cache_size", format!("-{}", db_options.cache_size_kb));
+
+        // Migrations drop and recreate schema objects, so run them on a single
+        // connection. Other pooled connections may hold a stale schema cache and
+        // reject statements like CREATE TRIGGER right after a DROP elsewhere.
+        let migration_pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options.clone())
+            .await
+            .context("Failed to connect to SQLite database")?;
+        Self::run_migrations(&migration_pool).await?;
+        migration_pool.close().await;
 
         let pool = SqlitePoolOptions::new()
-            .max_connections(5)
-            .connect_with(options)
+            .max_connections(db_options.max_connections)
+            .connect_with(options.clone())
             .await
             .context("Failed to connect to SQLite database")?;
 
-        let db = Self { pool };
-        db.run_migrations().await?;
+        let read_pool = SqlitePoolOptions::new()
+            .max_connections(db_options.max_connections)
+            .connect_with(options.read_only(true))
+            .await
+            .context("Failed to open read-only SQLite connection")?;
+
+        let db = Self { pool, read_pool };
         db.verify_writable(path).await?;
 
         Ok(db)
@@ -76,8 +151,8 @@ impl Database {
     }
 
     /// Run all pending migrations.
-    async fn run_migrations(&self) -> Result<()> {
-        migrations::run(&self.pool).await?;
+    async fn run_migrations(pool: &SqlitePool) -> Result<()> {
+        migrations::run(pool).await?;
         info!("Database migrations complete");
         Ok(())
     }
@@ -87,4 +162,13 @@ impl Database {
     pub const fn pool(&self) -> &SqlitePool {
         &self.pool
     }
+
+    /// Get a reference to the read-only connection pool.
+    ///
+    /// Use this for queries that only read, such as page renders in the web
+    /// server. Any write through it fails with a read-only database error.
+    #[must_use]
+    pub const fn read_pool(&self) -> &SqlitePool {
+        &self.read_pool
+    }
 }
@@ -12,6 +12,8 @@ pub struct Post {
     pub content_hash: Option<String>,
     pub published_at: Option<String>,
     pub processed_at: String,
+    /// Canonical forum username extracted from `author`.
+    pub author_handle: Option<String>,
 }
 
 /// A URL found in a post.
@@ -136,6 +138,8 @@ pub struct Archive {
     pub last_attempt_at: Option<String>,
     /// HTTP status code from the original page fetch (200, 404, 401, etc.).
     pub http_status_code: Option<i32>,
+    /// JSON array of `{url, status}` redirect hops seen before archiving.
+    pub redirect_chain: Option<String>,
     /// Publication date of the Discourse post containing this link.
     /// Used for sorting archives by when the post was made, not when archived.
     pub post_date: Option<String>,
@@ -181,6 +185,12 @@ pub struct Archive {
     pub save_count: Option<i64>,
     /// Tracks which backfill version has been applied to this row.
     pub metrics_backfill_version: Option<i64>,
+    /// Random code for the `/a/<code>` permalink (see `generate_short_code`).
+    pub short_code: Option<String>,
+    /// Failure category of the last error (see `archiver::ArchiveErrorKind`).
+    pub error_kind: Option<String>,
+    /// Why the archive was skipped (see `archiver::SkipReason`).
+    pub skip_reason: Option<String>,
 }
 
 impl Archive {
@@ -201,6 +211,8 @@ pub enum ArtifactKind {
     Screenshot,
     Pdf,
     Video,
+    /// Primary audio file from an audio-only site (e.g. SoundCloud).
+    Audio,
     Thumb,
     Metadata,
     Image,
@@ -208,6 +220,12 @@ pub enum ArtifactKind {
     Transcript,
     Comments,
     Music,
+    /// Original document file (e.g. a directly linked PDF).
+    Document,
+    /// Screenshot of the Discourse post page itself.
+    PostSnapshot,
+    /// Reader-mode extraction of a page's main article.
+    Article,
 }
 
 impl ArtifactKind {
@@ -221,6 +239,7 @@ impl ArtifactKind {
             Self::Screenshot => "screenshot",
             Self::Pdf => "pdf",
             Self::Video => "video",
+            Self::Audio => "audio",
             Self::Thumb => "thumb",
             Self::Metadata => "metadata",
             Self::Image => "image",
@@ -228,6 +247,9 @@ impl ArtifactKind {
             Self::Transcript => "transcript",
             Self::Comments => "comments",
             Self::Music => "music",
+            Self::Document => "document",
+            Self::PostSnapshot => "post_snapshot",
+            Self::Article => "article",
         }
     }
 }
@@ -251,6 +273,14 @@ pub struct ArchiveArtifact {
     pub video_file_id: Option<i64>,
     /// Structured metadata (JSON) for artifact-specific data (e.g., subtitle language, transcript source)
     pub metadata: Option<String>,
+    /// Dominant colour of an image artifact (`#rrggbb`), shown while it loads
+    pub placeholder_color: Option<String>,
+    /// Pixel width of an image artifact
+    pub width: Option<i64>,
+    /// Pixel height of an image artifact
+    pub height: Option<i64>,
+    /// `Content-Encoding` the object is stored with in S3 (e.g. `zstd`), if precompressed
+    pub content_encoding: Option<String>,
 }
 
 /// Artifact kinds used as internal backfill markers with no real S3 file.
@@ -324,7 +354,10 @@ pub struct VideoFile {
 pub struct NewPost {
     pub guid: String,
     pub discourse_url: String,
+    /// Author string as the feed gave it.
     pub author: Option<String>,
+    /// Canonical forum username (see [`normalize_forum_author`](crate::db::normalize_forum_author)).
+    pub author_handle: Option<String>,
     pub title: Option<String>,
     pub body_html: Option<String>,
     pub content_hash: Option<String>,
@@ -356,6 +389,14 @@ pub struct ArchiveWithLink {
     pub link: Link,
 }
 
+/// Number of links and archives found in a single post.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PostLinkCounts {
+    pub post_id: i64,
+    pub link_count: i64,
+    pub archive_count: i64,
+}
+
 /// Flattened archive data for list display (includes link info).
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct ArchiveDisplay {
@@ -749,6 +790,151 @@ pub struct ExcludedDomain {
     pub updated_at: String,
 }
 
+impl ExcludedDomain {
+    /// Whether this entry also excludes subdomains (`*.example.com` or `.example.com`).
+    #[must_use]
+    pub fn applies_to_subdomains(&self) -> bool {
+        self.domain.starts_with("*.") || self.domain.starts_with('.')
+    }
+}
+
+/// A link an admin marked "watch for changes".
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct WatchedLink {
+    pub link_id: i64,
+    pub check_interval_hours: i64,
+    pub last_checked_at: Option<String>,
+    pub next_check_at: Option<String>,
+    pub created_by_user_id: Option<i64>,
+    pub created_at: String,
+}
+
+/// A normalized text snapshot of a watched link's content.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ContentVersion {
+    pub id: i64,
+    pub link_id: i64,
+    pub archive_id: Option<i64>,
+    pub previous_version_id: Option<i64>,
+    pub content_hash: String,
+    pub content_text: String,
+    pub captured_at: String,
+}
+
+/// Per-domain override for archiving links that only appear inside quotes.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DomainQuotePolicy {
+    pub id: i64,
+    pub domain: String,
+    pub archive_quote_only: bool,
+    pub created_at: String,
+    pub created_by_user_id: Option<i64>,
+    pub updated_at: String,
+}
+
+/// A domain whose links jump the archive queue and retry on a shorter backoff.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PriorityDomain {
+    pub id: i64,
+    pub domain: String,
+    pub reason: String,
+    pub created_at: String,
+    pub created_by_user_id: Option<i64>,
+}
+
+/// A domain whose archives keep prior captures when re-archived.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct VersionedDomain {
+    pub id: i64,
+    pub domain: String,
+    pub reason: String,
+    pub created_at: String,
+    pub created_by_user_id: Option<i64>,
+}
+
+/// Polling state of a forum feed.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FeedState {
+    pub feed_url: String,
+    /// Highest post ID ingested from the feed; older pages are already seen.
+    pub newest_post_id: Option<i64>,
+    /// Post ID an unfinished backfill resumes paging before.
+    pub backfill_cursor: Option<i64>,
+    /// When the feed's full history was ingested, if it ever was.
+    pub backfilled_at: Option<String>,
+    pub last_polled_at: String,
+    pub created_at: String,
+}
+
+/// A prior capture of an archive, preserved when it was re-archived.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ArchiveVersion {
+    pub id: i64,
+    pub archive_id: i64,
+    /// 1 for the first preserved capture, counting up.
+    pub version: i64,
+    /// When the preserved capture was originally archived.
+    pub archived_at: Option<String>,
+    /// S3 prefix holding the copied artifacts.
+    pub s3_prefix: String,
+    pub s3_key_primary: Option<String>,
+    pub content_title: Option<String>,
+    pub content_text: Option<String>,
+    pub created_at: String,
+}
+
+/// What an external service rule is matched against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalServiceScope {
+    /// Link domain, using the same patterns as excluded domains.
+    Domain,
+    /// Archive content type (`video`, `text`, `image`, ...).
+    ContentType,
+}
+
+impl ExternalServiceScope {
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Domain => "domain",
+            Self::ContentType => "content_type",
+        }
+    }
+
+    #[must_use]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "domain" => Some(Self::Domain),
+            "content_type" => Some(Self::ContentType),
+            _ => None,
+        }
+    }
+}
+
+/// Domain- or content-type-scoped override of which external services an
+/// archive is sent to. `None` leaves that service to the next matching rule.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ExternalServiceRule {
+    pub id: i64,
+    pub scope: String,
+    pub pattern: String,
+    pub wayback: Option<bool>,
+    pub archive_today: Option<bool>,
+    pub ipfs: Option<bool>,
+    pub created_at: String,
+    pub created_by_user_id: Option<i64>,
+    pub updated_at: String,
+}
+
+/// External services an archive should be sent to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExternalServices {
+    pub wayback: bool,
+    pub archive_today: bool,
+    pub ipfs: bool,
+}
+
 /// Status of a thread archive job.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -757,6 +943,7 @@ pub enum ThreadArchiveJobStatus {
     Processing,
     Complete,
     Failed,
+    Cancelled,
 }
 
 impl ThreadArchiveJobStatus {
@@ -767,6 +954,7 @@ impl ThreadArchiveJobStatus {
             Self::Processing => "processing",
             Self::Complete => "complete",
             Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
         }
     }
 
@@ -777,6 +965,7 @@ impl ThreadArchiveJobStatus {
             "processing" => Some(Self::Processing),
             "complete" => Some(Self::Complete),
             "failed" => Some(Self::Failed),
+            "cancelled" => Some(Self::Cancelled),
             _ => None,
         }
     }
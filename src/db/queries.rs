@@ -1,26 +1,28 @@
 use anyhow::{Context, Result};
-use sqlx::{Row, SqlitePool};
+use regex::Regex;
+use sqlx::{Row, SqliteConnection, SqliteExecutor, SqlitePool};
 use std::collections::{HashMap, HashSet};
 
+use super::archive_query::{ArchiveQuery, ArchiveSort, SortDirection};
 use super::models::{
-    Archive, ArchiveArtifact, ArchiveDisplay, ArchiveJob, ArchiveJobType, AuditEvent, Link,
-    LinkOccurrence, NewLink, NewLinkOccurrence, NewPost, NewSubmission, Post, Session, Submission,
-    SubtitleLanguage, ThreadArchiveJob, ThreadDisplay, User, VideoFile,
+    Archive, ArchiveArtifact, ArchiveDisplay, ArchiveJob, ArchiveJobType, ArtifactKind, AuditEvent,
+    Link, LinkOccurrence, NewLink, NewLinkOccurrence, NewPost, NewSubmission, Post, PostLinkCounts,
+    Session, Submission, SubtitleLanguage, ThreadArchiveJob, ThreadDisplay, User, VideoFile,
 };
 
 // ========== Source Filter Helpers ==========
 
 /// Represents a domain filter condition with its SQL clause and bind values.
-struct DomainFilter {
+pub(super) struct DomainFilter {
     /// SQL WHERE clause fragment (e.g., "(l.domain = ? OR l.domain LIKE ?)")
-    sql: String,
+    pub(super) sql: String,
     /// Values to bind in order
-    values: Vec<String>,
+    pub(super) values: Vec<String>,
 }
 
 /// Get domain filter SQL and bind values for a source name.
 /// Returns a proper domain match that handles exact domain and subdomains.
-fn get_domain_filter(source: &str) -> DomainFilter {
+pub(super) fn get_domain_filter(source: &str) -> DomainFilter {
     // Map source names to their domains
     // For most sources, match exact domain or subdomains (e.g., "reddit.com" or "*.reddit.com")
     // For twitter, match both x.com and twitter.com for backwards compatibility
@@ -46,6 +48,19 @@ fn get_domain_filter(source: &str) -> DomainFilter {
             sql: "(l.domain = ? OR l.domain LIKE ?)".to_string(),
             values: vec!["tiktok.com".to_string(), "%.tiktok.com".to_string()],
         },
+        "soundcloud" => DomainFilter {
+            sql: "(l.domain = ? OR l.domain LIKE ?)".to_string(),
+            values: vec!["soundcloud.com".to_string(), "%.soundcloud.com".to_string()],
+        },
+        "streamable" => DomainFilter {
+            sql: "(l.domain = ? OR l.domain LIKE ?)".to_string(),
+            values: vec!["streamable.com".to_string(), "%.streamable.com".to_string()],
+        },
+        // Bandcamp tracks live on per-artist subdomains (e.g. artist.bandcamp.com)
+        "bandcamp" => DomainFilter {
+            sql: "(l.domain = ? OR l.domain LIKE ?)".to_string(),
+            values: vec!["bandcamp.com".to_string(), "%.bandcamp.com".to_string()],
+        },
         // For unknown sources, try to match as a domain directly
         _ => DomainFilter {
             sql: "(l.domain = ? OR l.domain LIKE ?)".to_string(),
@@ -57,30 +72,34 @@ fn get_domain_filter(source: &str) -> DomainFilter {
 // ========== Posts ==========
 
 /// Get a post by its GUID.
-pub async fn get_post_by_guid(pool: &SqlitePool, guid: &str) -> Result<Option<Post>> {
+pub async fn get_post_by_guid(
+    executor: impl SqliteExecutor<'_>,
+    guid: &str,
+) -> Result<Option<Post>> {
     sqlx::query_as("SELECT * FROM posts WHERE guid = ?")
         .bind(guid)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await
         .context("Failed to fetch post by guid")
 }
 
 /// Insert a new post, returning its ID.
-pub async fn insert_post(pool: &SqlitePool, post: &NewPost) -> Result<i64> {
+pub async fn insert_post(executor: impl SqliteExecutor<'_>, post: &NewPost) -> Result<i64> {
     let result = sqlx::query(
         r"
-        INSERT INTO posts (guid, discourse_url, author, title, body_html, content_hash, published_at)
-        VALUES (?, ?, ?, ?, ?, ?, ?)
+        INSERT INTO posts (guid, discourse_url, author, author_handle, title, body_html, content_hash, published_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
         ",
     )
     .bind(&post.guid)
     .bind(&post.discourse_url)
     .bind(&post.author)
+    .bind(&post.author_handle)
     .bind(&post.title)
     .bind(&post.body_html)
     .bind(&post.content_hash)
     .bind(&post.published_at)
-    .execute(pool)
+    .execute(executor)
     .await
     .context("Failed to insert post")?;
 
@@ -88,23 +107,24 @@ pub async fn insert_post(pool: &SqlitePool, post: &NewPost) -> Result<i64> {
 }
 
 /// Update an existing post's content.
-pub async fn update_post(pool: &SqlitePool, id: i64, post: &NewPost) -> Result<()> {
+pub async fn update_post(executor: impl SqliteExecutor<'_>, id: i64, post: &NewPost) -> Result<()> {
     sqlx::query(
         r"
         UPDATE posts
-        SET discourse_url = ?, author = ?, title = ?, body_html = ?,
+        SET discourse_url = ?, author = ?, author_handle = ?, title = ?, body_html = ?,
             content_hash = ?, published_at = ?, processed_at = datetime('now')
         WHERE id = ?
         ",
     )
     .bind(&post.discourse_url)
     .bind(&post.author)
+    .bind(&post.author_handle)
     .bind(&post.title)
     .bind(&post.body_html)
     .bind(&post.content_hash)
     .bind(&post.published_at)
     .bind(id)
-    .execute(pool)
+    .execute(executor)
     .await
     .context("Failed to update post")?;
 
@@ -215,6 +235,71 @@ pub async fn count_all_threads(pool: &SqlitePool) -> Result<i64> {
     Ok(thread_keys.len() as i64)
 }
 
+/// Extract the forum handle (username without `@`) from a post author string.
+///
+/// Authors are stored as `@username` (JSON poller) or `@username Full Name`
+/// (RSS poller), so both map to the same handle. An `@mention` anywhere in
+/// the string wins, so `Jane Doe (@jane)` is `jane`; otherwise the first word
+/// is taken as a bare username.
+#[must_use]
+pub fn forum_author_handle(author: &str) -> Option<&str> {
+    normalize_forum_author(author, None)
+}
+
+/// Extract the canonical forum username from a raw post author string.
+///
+/// A `pattern` (`FORUM_AUTHOR_PATTERN`) is tried first, taking its `handle`
+/// group or else its first group; authors it doesn't match fall back to the
+/// rules of [`forum_author_handle`].
+#[must_use]
+pub fn normalize_forum_author<'a>(author: &'a str, pattern: Option<&Regex>) -> Option<&'a str> {
+    // Discourse usernames are letters, digits, `_`, `-` and `.`
+    let clean = |s: &'a str| {
+        let handle = s.trim_matches(|c: char| !(c.is_alphanumeric() || "_-.".contains(c)));
+        (!handle.is_empty()).then_some(handle)
+    };
+
+    if let Some(captures) = pattern.and_then(|p| p.captures(author)) {
+        if let Some(handle) = captures
+            .name("handle")
+            .or_else(|| captures.get(1))
+            .and_then(|m| clean(m.as_str()))
+        {
+            return Some(handle);
+        }
+    }
+
+    let mut words = author.split_whitespace();
+    let mention = words
+        .clone()
+        .find(|w| w.trim_start_matches(['(', '[', '<']).starts_with('@'));
+    mention.or_else(|| words.next()).and_then(clean)
+}
+
+/// Fetch posts authored by a forum user, newest first.
+///
+/// Matches the normalized `author_handle` stored at ingest, case-insensitively,
+/// so every decorated form of the same author is grouped together.
+pub async fn get_posts_by_forum_author(
+    pool: &SqlitePool,
+    handle: &str,
+    limit: i64,
+) -> Result<Vec<Post>> {
+    sqlx::query_as(
+        r"
+        SELECT * FROM posts
+        WHERE author_handle = ? COLLATE NOCASE
+        ORDER BY published_at IS NULL, published_at DESC, processed_at DESC
+        LIMIT ?
+        ",
+    )
+    .bind(handle)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch posts by forum author")
+}
+
 /// Fetch all posts that belong to the given thread key (host + topic id/path).
 pub async fn get_posts_by_thread_key(pool: &SqlitePool, thread_key: &str) -> Result<Vec<Post>> {
     let (pattern_base, pattern_with_post) = build_post_url_patterns(thread_key);
@@ -403,6 +488,43 @@ pub async fn insert_link(pool: &SqlitePool, link: &NewLink) -> Result<i64> {
     Ok(result.last_insert_rowid())
 }
 
+/// Get the ID of the link for a normalized URL, inserting it if missing.
+///
+/// Safe to call concurrently for the same URL: the unique index on
+/// `normalized_url` makes the insert a no-op for all but one caller.
+pub async fn get_or_create_link(pool: &SqlitePool, link: &NewLink) -> Result<i64> {
+    let mut conn = pool
+        .acquire()
+        .await
+        .context("Failed to acquire connection")?;
+    get_or_create_link_on(&mut conn, link).await
+}
+
+/// [`get_or_create_link`] on a given connection, e.g. inside a transaction.
+pub async fn get_or_create_link_on(conn: &mut SqliteConnection, link: &NewLink) -> Result<i64> {
+    // Insert or ignore (handles race conditions)
+    sqlx::query(
+        r"
+        INSERT OR IGNORE INTO links (original_url, normalized_url, canonical_url, domain)
+        VALUES (?, ?, ?, ?)
+        ",
+    )
+    .bind(&link.original_url)
+    .bind(&link.normalized_url)
+    .bind(&link.canonical_url)
+    .bind(&link.domain)
+    .execute(&mut *conn)
+    .await
+    .context("Failed to insert link")?;
+
+    // Fetch the ID (either newly inserted or existing)
+    sqlx::query_scalar("SELECT id FROM links WHERE normalized_url = ?")
+        .bind(&link.normalized_url)
+        .fetch_one(&mut *conn)
+        .await
+        .context("Failed to fetch link ID")
+}
+
 /// Update the final URL after redirect resolution.
 pub async fn update_link_final_url(pool: &SqlitePool, id: i64, final_url: &str) -> Result<()> {
     sqlx::query("UPDATE links SET final_url = ? WHERE id = ?")
@@ -429,19 +551,26 @@ pub async fn update_link_last_archived(pool: &SqlitePool, id: i64) -> Result<()>
 // ========== Link Occurrences ==========
 
 /// Check if a link occurrence exists for a post.
-pub async fn link_occurrence_exists(pool: &SqlitePool, link_id: i64, post_id: i64) -> Result<bool> {
+pub async fn link_occurrence_exists(
+    executor: impl SqliteExecutor<'_>,
+    link_id: i64,
+    post_id: i64,
+) -> Result<bool> {
     let row: (i64,) =
         sqlx::query_as("SELECT COUNT(*) FROM link_occurrences WHERE link_id = ? AND post_id = ?")
             .bind(link_id)
             .bind(post_id)
-            .fetch_one(pool)
+            .fetch_one(executor)
             .await?;
 
     Ok(row.0 > 0)
 }
 
 /// Insert a new link occurrence.
-pub async fn insert_link_occurrence(pool: &SqlitePool, occ: &NewLinkOccurrence) -> Result<i64> {
+pub async fn insert_link_occurrence(
+    executor: impl SqliteExecutor<'_>,
+    occ: &NewLinkOccurrence,
+) -> Result<i64> {
     let result = sqlx::query(
         r"
         INSERT INTO link_occurrences (link_id, post_id, in_quote, context_snippet)
@@ -452,7 +581,7 @@ pub async fn insert_link_occurrence(pool: &SqlitePool, occ: &NewLinkOccurrence)
     .bind(occ.post_id)
     .bind(occ.in_quote)
     .bind(&occ.context_snippet)
-    .execute(pool)
+    .execute(executor)
     .await
     .context("Failed to insert link occurrence")?;
 
@@ -460,11 +589,14 @@ pub async fn insert_link_occurrence(pool: &SqlitePool, occ: &NewLinkOccurrence)
 }
 
 /// Check if a link has any non-quote occurrences.
-pub async fn link_has_non_quote_occurrence(pool: &SqlitePool, link_id: i64) -> Result<bool> {
+pub async fn link_has_non_quote_occurrence(
+    executor: impl SqliteExecutor<'_>,
+    link_id: i64,
+) -> Result<bool> {
     let row: (i64,) =
         sqlx::query_as("SELECT COUNT(*) FROM link_occurrences WHERE link_id = ? AND in_quote = 0")
             .bind(link_id)
-            .fetch_one(pool)
+            .fetch_one(executor)
             .await?;
 
     Ok(row.0 > 0)
@@ -482,15 +614,29 @@ pub async fn get_archive(pool: &SqlitePool, id: i64) -> Result<Option<Archive>>
 }
 
 /// Get the archive for a link.
-pub async fn get_archive_by_link_id(pool: &SqlitePool, link_id: i64) -> Result<Option<Archive>> {
+pub async fn get_archive_by_link_id(
+    executor: impl SqliteExecutor<'_>,
+    link_id: i64,
+) -> Result<Option<Archive>> {
     sqlx::query_as("SELECT * FROM archives WHERE link_id = ?")
         .bind(link_id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await
         .context("Failed to fetch archive by link")
 }
 
-/// Create a pending archive for a link.
+/// Get an archive by its permalink short code.
+pub async fn get_archive_by_short_code(pool: &SqlitePool, code: &str) -> Result<Option<Archive>> {
+    sqlx::query_as("SELECT * FROM archives WHERE short_code = ?")
+        .bind(code)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to fetch archive by short code")
+}
+
+/// Attempts at finding an unused short code before giving up.
+const SHORT_CODE_ATTEMPTS: usize = 5;
+
 /// Create a pending archive for a link.
 ///
 /// Uses INSERT OR IGNORE to handle race conditions where multiple threads
@@ -499,29 +645,51 @@ pub async fn get_archive_by_link_id(pool: &SqlitePool, link_id: i64) -> Result<O
 ///
 /// This is safe due to the UNIQUE constraint on archives(link_id) added in
 /// migration v23, following the same pattern as video_files deduplication.
+/// New archives get a random short code; the insert is also ignored if that
+/// code is taken, in which case another code is tried.
 pub async fn create_pending_archive(
     pool: &SqlitePool,
     link_id: i64,
     post_date: Option<&str>,
 ) -> Result<i64> {
-    // Try to insert (will be ignored if archive already exists for this link_id)
-    sqlx::query(
-        "INSERT OR IGNORE INTO archives (link_id, status, post_date) VALUES (?, 'pending', ?)",
-    )
-    .bind(link_id)
-    .bind(post_date)
-    .execute(pool)
-    .await
-    .context("Failed to insert pending archive")?;
+    let mut conn = pool
+        .acquire()
+        .await
+        .context("Failed to acquire connection")?;
+    create_pending_archive_on(&mut conn, link_id, post_date).await
+}
 
-    // Fetch the archive (either newly created or existing)
-    // This is guaranteed to exist due to the INSERT OR IGNORE above
-    let archive = get_archive_by_link_id(pool, link_id)
+/// [`create_pending_archive`] on a given connection, e.g. inside a transaction.
+pub async fn create_pending_archive_on(
+    conn: &mut SqliteConnection,
+    link_id: i64,
+    post_date: Option<&str>,
+) -> Result<i64> {
+    for _ in 0..SHORT_CODE_ATTEMPTS {
+        // Try to insert (will be ignored if archive already exists for this link_id)
+        sqlx::query(
+            r"
+            INSERT OR IGNORE INTO archives (link_id, status, post_date, short_code)
+            VALUES (?, 'pending', ?, ?)
+            ",
+        )
+        .bind(link_id)
+        .bind(post_date)
+        .bind(super::generate_short_code())
+        .execute(&mut *conn)
         .await
-        .context("Failed to fetch archive after insert")?
-        .expect("Archive should exist after INSERT OR IGNORE");
+        .context("Failed to insert pending archive")?;
+
+        // Fetch the archive (either newly created or existing)
+        if let Some(archive) = get_archive_by_link_id(&mut *conn, link_id)
+            .await
+            .context("Failed to fetch archive after insert")?
+        {
+            return Ok(archive.id);
+        }
+    }
 
-    Ok(archive.id)
+    anyhow::bail!("Failed to find an unused short code for link {link_id}")
 }
 
 /// Update archive status to processing.
@@ -706,30 +874,105 @@ pub async fn set_archive_nsfw(
     Ok(())
 }
 
+/// Record the result of automatic NSFW classification.
+///
+/// Archives whose NSFW status was set manually or detected by a handler keep
+/// it; only unset or previously auto-classified archives are updated.
+/// Returns whether the archive was updated.
+pub async fn set_archive_nsfw_auto(pool: &SqlitePool, id: i64, is_nsfw: bool) -> Result<bool> {
+    let result = sqlx::query(
+        r"
+        UPDATE archives SET is_nsfw = ?, nsfw_source = 'auto'
+        WHERE id = ? AND (nsfw_source IS NULL OR nsfw_source = 'auto')
+        ",
+    )
+    .bind(is_nsfw)
+    .bind(id)
+    .execute(pool)
+    .await
+    .context("Failed to set automatic NSFW status")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Base delay before the first retry of a failed archive.
+const RETRY_BASE_DELAY_SECS: i64 = 5 * 60;
+
+/// Cap on the backoff exponent, so the shift can't overflow.
+const RETRY_MAX_EXPONENT: i64 = 10;
+
+/// Lower backoff cap for priority domains (~40 minutes between retries).
+const PRIORITY_RETRY_MAX_EXPONENT: i64 = 3;
+
+/// Fraction of the backoff delay used as random jitter in either direction.
+const RETRY_JITTER: f64 = 0.25;
+
+/// Compute the jittered backoff delay in seconds for the given retry count.
+///
+/// The base delay is `5 min * 2^retry_count`, randomized by ±25% so archives
+/// that failed together (e.g. during an outage) don't all retry at once.
+/// Priority domains stop doubling sooner, so they keep retrying often.
+#[must_use]
+pub fn jittered_retry_delay_secs(retry_count: i64, priority: bool) -> i64 {
+    use rand::Rng;
+
+    let max_exponent = if priority {
+        PRIORITY_RETRY_MAX_EXPONENT
+    } else {
+        RETRY_MAX_EXPONENT
+    };
+    let exponent = retry_count.clamp(0, max_exponent);
+    let base = RETRY_BASE_DELAY_SECS << exponent;
+    let factor = rand::thread_rng().gen_range((1.0 - RETRY_JITTER)..=(1.0 + RETRY_JITTER));
+
+    #[allow(clippy::cast_possible_truncation)]
+    let delay = (base as f64 * factor).round() as i64;
+    delay
+}
+
 /// Update archive as failed with exponential backoff for retry.
 ///
-/// The `next_retry_at` is calculated as: now + (base_delay * 2^retry_count)
+/// The `next_retry_at` is calculated as: now + (base_delay * 2^retry_count) ± 25%
 /// With base_delay = 5 minutes:
-/// - retry 0: 5 minutes
-/// - retry 1: 10 minutes
-/// - retry 2: 20 minutes
-/// - retry 3: 40 minutes (but this is the last retry)
-pub async fn set_archive_failed(pool: &SqlitePool, id: i64, error: &str) -> Result<()> {
-    // Calculate exponential backoff delay in minutes: 5 * 2^retry_count
+/// - retry 0: ~5 minutes
+/// - retry 1: ~10 minutes
+/// - retry 2: ~20 minutes
+///
+/// No retry is scheduled once this failure uses up the archive's
+/// `max_retries` attempts; `next_retry_at` is cleared instead.
+pub async fn set_archive_failed(
+    pool: &SqlitePool,
+    id: i64,
+    error: &str,
+    max_retries: i32,
+) -> Result<()> {
     // We use the current retry_count before incrementing, so:
-    // retry_count=0 -> 5*2^0 = 5 min, retry_count=1 -> 5*2^1 = 10 min, etc.
+    // retry_count=0 -> ~5 min, retry_count=1 -> ~10 min, etc.
+    let sql = format!(
+        "SELECT a.retry_count, {PRIORITY_DOMAIN_MATCH} FROM archives a JOIN links l ON l.id = a.link_id WHERE a.id = ?"
+    );
+    let (retry_count, priority): (i64, bool) = sqlx::query_as(&sql)
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to get archive retry count")?
+        .unwrap_or((0, false));
+    let delay_secs = retry_delay_secs(retry_count, max_retries, priority);
+
+    // A NULL delay makes the datetime() modifier NULL, clearing next_retry_at
     sqlx::query(
         r"
         UPDATE archives
         SET status = 'failed',
             error_message = ?,
             last_attempt_at = datetime('now'),
-            next_retry_at = datetime('now', '+' || (5 * (1 << retry_count)) || ' minutes'),
+            next_retry_at = datetime('now', '+' || ? || ' seconds'),
             retry_count = retry_count + 1
         WHERE id = ?
         ",
     )
     .bind(error)
+    .bind(delay_secs)
     .bind(id)
     .execute(pool)
     .await
@@ -738,6 +981,53 @@ pub async fn set_archive_failed(pool: &SqlitePool, id: i64, error: &str) -> Resu
     Ok(())
 }
 
+/// Backoff delay before the next retry, or `None` when the failure at
+/// `retry_count` leaves no retries under `max_retries`.
+#[must_use]
+pub fn retry_delay_secs(retry_count: i64, max_retries: i32, priority: bool) -> Option<i64> {
+    (retry_count + 1 < i64::from(max_retries))
+        .then(|| jittered_retry_delay_secs(retry_count, priority))
+}
+
+/// Defer an archive whose content isn't available yet (e.g. a live stream).
+///
+/// The archive goes back to `pending` with `next_retry_at` in `delay_secs`,
+/// clamped to `max_window_secs` after the archive was created. Returns `false`
+/// (and changes nothing) once that window has passed. Deferrals don't count
+/// against `retry_count`.
+pub async fn defer_archive(
+    pool: &SqlitePool,
+    id: i64,
+    reason: &str,
+    delay_secs: i64,
+    max_window_secs: i64,
+) -> Result<bool> {
+    let result = sqlx::query(
+        r"
+        UPDATE archives
+        SET status = 'pending',
+            error_message = ?,
+            last_attempt_at = datetime('now'),
+            next_retry_at = MIN(
+                datetime('now', '+' || ? || ' seconds'),
+                datetime(created_at, '+' || ? || ' seconds')
+            )
+        WHERE id = ?
+          AND datetime('now') < datetime(created_at, '+' || ? || ' seconds')
+        ",
+    )
+    .bind(reason)
+    .bind(delay_secs)
+    .bind(max_window_secs)
+    .bind(id)
+    .bind(max_window_secs)
+    .execute(pool)
+    .await
+    .context("Failed to defer archive")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
 /// Reset a failed archive to pending for retry.
 pub async fn reset_archive_for_retry(pool: &SqlitePool, id: i64) -> Result<()> {
     sqlx::query("UPDATE archives SET status = 'pending' WHERE id = ?")
@@ -749,9 +1039,53 @@ pub async fn reset_archive_for_retry(pool: &SqlitePool, id: i64) -> Result<()> {
     Ok(())
 }
 
-/// Mark archive as permanently skipped.
-pub async fn set_archive_skipped(pool: &SqlitePool, id: i64) -> Result<()> {
-    sqlx::query("UPDATE archives SET status = 'skipped' WHERE id = ?")
+/// Mark an archive skipped by a dry run, recording the planned work as its note.
+///
+/// Dry-run skips are requeued by [`reset_dry_run_archives`] once dry run is off.
+pub async fn set_archive_dry_run(pool: &SqlitePool, id: i64, note: &str) -> Result<()> {
+    sqlx::query(
+        r"
+        UPDATE archives
+        SET status = 'skipped',
+            skip_reason = 'dry_run',
+            error_message = ?,
+            last_attempt_at = datetime('now')
+        WHERE id = ?
+        ",
+    )
+    .bind(note)
+    .bind(id)
+    .execute(pool)
+    .await
+    .context("Failed to record archive dry run")?;
+
+    Ok(())
+}
+
+/// Requeue archives a dry run skipped, returning how many were reset.
+///
+/// Called on startup with dry run off, so turning it off archives them for real.
+pub async fn reset_dry_run_archives(pool: &SqlitePool) -> Result<u64> {
+    let result = sqlx::query(
+        r"
+        UPDATE archives
+        SET status = 'pending',
+            error_message = NULL,
+            skip_reason = NULL
+        WHERE status = 'skipped' AND skip_reason = 'dry_run'
+        ",
+    )
+    .execute(pool)
+    .await
+    .context("Failed to reset dry run archives")?;
+
+    Ok(result.rows_affected())
+}
+
+/// Mark archive as permanently skipped, recording why (see `archiver::SkipReason`).
+pub async fn set_archive_skipped(pool: &SqlitePool, id: i64, skip_reason: &str) -> Result<()> {
+    sqlx::query("UPDATE archives SET status = 'skipped', skip_reason = ? WHERE id = ?")
+        .bind(skip_reason)
         .bind(id)
         .execute(pool)
         .await
@@ -780,6 +1114,18 @@ pub async fn set_archive_auth_required(pool: &SqlitePool, id: i64, error: &str)
     Ok(())
 }
 
+/// Record the failure category of an archive's last error.
+pub async fn set_archive_error_kind(pool: &SqlitePool, id: i64, error_kind: &str) -> Result<()> {
+    sqlx::query("UPDATE archives SET error_kind = ? WHERE id = ?")
+        .bind(error_kind)
+        .bind(id)
+        .execute(pool)
+        .await
+        .context("Failed to set archive error kind")?;
+
+    Ok(())
+}
+
 /// Set the Wayback URL for an archive.
 pub async fn set_archive_wayback_url(pool: &SqlitePool, id: i64, wayback_url: &str) -> Result<()> {
     sqlx::query("UPDATE archives SET wayback_url = ? WHERE id = ?")
@@ -792,20 +1138,41 @@ pub async fn set_archive_wayback_url(pool: &SqlitePool, id: i64, wayback_url: &s
     Ok(())
 }
 
-/// Get pending archives for processing.
+/// Set the Archive.today URL for an archive.
+pub async fn set_archive_archive_today_url(
+    pool: &SqlitePool,
+    id: i64,
+    archive_today_url: &str,
+) -> Result<()> {
+    sqlx::query("UPDATE archives SET archive_today_url = ? WHERE id = ?")
+        .bind(archive_today_url)
+        .bind(id)
+        .execute(pool)
+        .await
+        .context("Failed to set Archive.today URL")?;
+
+    Ok(())
+}
+
+/// Get pending archives for processing, priority domains first.
+///
+/// Archives deferred until a future `next_retry_at` (live content) are left out.
 pub async fn get_pending_archives(pool: &SqlitePool, limit: i64) -> Result<Vec<Archive>> {
-    sqlx::query_as(
+    let sql = format!(
         r"
-        SELECT * FROM archives
-        WHERE status = 'pending'
-        ORDER BY created_at ASC
+        SELECT a.* FROM archives a
+        JOIN links l ON l.id = a.link_id
+        WHERE a.status = 'pending'
+          AND (a.next_retry_at IS NULL OR a.next_retry_at <= datetime('now'))
+        ORDER BY {PRIORITY_DOMAIN_MATCH} DESC, a.created_at ASC
         LIMIT ?
-        ",
-    )
-    .bind(limit)
-    .fetch_all(pool)
-    .await
-    .context("Failed to fetch pending archives")
+        "
+    );
+    sqlx::query_as(&sql)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .context("Failed to fetch pending archives")
 }
 
 /// Get failed archives eligible for retry.
@@ -814,26 +1181,34 @@ pub async fn get_pending_archives(pool: &SqlitePool, limit: i64) -> Result<Vec<A
 /// - status = 'failed'
 /// - retry_count < max_retries
 /// - next_retry_at <= now (or is null for legacy data)
+///
+/// Archives on priority domains come first.
 pub async fn get_failed_archives_for_retry(
     pool: &SqlitePool,
     limit: i64,
     max_retries: i32,
 ) -> Result<Vec<Archive>> {
-    sqlx::query_as(
+    sqlx::query_as(&failed_archives_for_retry_sql())
+        .bind(max_retries)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .context("Failed to fetch failed archives")
+}
+
+/// SQL for [`get_failed_archives_for_retry`], binding `max_retries` and `limit`.
+fn failed_archives_for_retry_sql() -> String {
+    format!(
         r"
-        SELECT * FROM archives
-        WHERE status = 'failed'
-          AND retry_count < ?
-          AND (next_retry_at IS NULL OR next_retry_at <= datetime('now'))
-        ORDER BY next_retry_at ASC NULLS FIRST, created_at ASC
+        SELECT a.* FROM archives a
+        JOIN links l ON l.id = a.link_id
+        WHERE a.status = 'failed'
+          AND a.retry_count < ?
+          AND (a.next_retry_at IS NULL OR a.next_retry_at <= datetime('now'))
+        ORDER BY {PRIORITY_DOMAIN_MATCH} DESC, a.next_retry_at ASC NULLS FIRST, a.created_at ASC
         LIMIT ?
-        ",
+        "
     )
-    .bind(max_retries)
-    .bind(limit)
-    .fetch_all(pool)
-    .await
-    .context("Failed to fetch failed archives")
 }
 
 /// Get archives requiring authentication.
@@ -858,18 +1233,11 @@ pub async fn get_archives_needing_auth(pool: &SqlitePool, limit: i64) -> Result<
 
 /// Get recent archives for the home page.
 pub async fn get_recent_archives(pool: &SqlitePool, limit: i64) -> Result<Vec<Archive>> {
-    sqlx::query_as(
-        r"
-        SELECT * FROM archives
-        WHERE status = 'complete'
-        ORDER BY COALESCE(post_date, archived_at, created_at) DESC
-        LIMIT ?
-        ",
-    )
-    .bind(limit)
-    .fetch_all(pool)
-    .await
-    .context("Failed to fetch recent archives")
+    ArchiveQuery::new()
+        .status("complete")
+        .limit(limit)
+        .fetch_archives(pool)
+        .await
 }
 
 /// Get recent archives with NSFW filter and pagination.
@@ -890,63 +1258,23 @@ pub async fn get_recent_archives_filtered_full(
     nsfw_filter: Option<bool>,
     content_type: Option<&str>,
 ) -> Result<Vec<Archive>> {
-    // Build WHERE clause dynamically based on filters
-    let mut where_clauses = vec!["status = 'complete'".to_string()];
-
-    match nsfw_filter {
-        Some(true) => where_clauses.push("is_nsfw = 1".to_string()),
-        Some(false) => where_clauses.push("(is_nsfw = 0 OR is_nsfw IS NULL)".to_string()),
-        None => {}
-    }
-
-    if content_type.is_some() {
-        where_clauses.push("content_type = ?".to_string());
-    }
-
-    let where_clause = where_clauses.join(" AND ");
-    let sql = format!(
-        "SELECT * FROM archives WHERE {} ORDER BY COALESCE(post_date, archived_at, created_at) DESC LIMIT ? OFFSET ?",
-        where_clause
-    );
-
-    let mut query = sqlx::query_as(&sql);
-
-    // Bind content_type if present (bind in order of ? placeholders)
-    if let Some(ct) = content_type {
-        query = query.bind(ct);
-    }
-
-    query
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(pool)
+    ArchiveQuery::new()
+        .status("complete")
+        .nsfw(nsfw_filter)
+        .content_type(content_type)
+        .limit(limit)
+        .offset(offset)
+        .fetch_archives(pool)
         .await
-        .context("Failed to fetch filtered archives")
 }
 
 /// Get total count of archives with NSFW filter.
 pub async fn get_archives_count(pool: &SqlitePool, nsfw_filter: Option<bool>) -> Result<i64> {
-    let count: (i64,) = match nsfw_filter {
-        Some(true) => {
-            sqlx::query_as("SELECT COUNT(*) FROM archives WHERE status = 'complete' AND is_nsfw = 1")
-                .fetch_one(pool)
-                .await
-                .context("Failed to count NSFW archives")?
-        }
-        Some(false) => {
-            sqlx::query_as("SELECT COUNT(*) FROM archives WHERE status = 'complete' AND (is_nsfw = 0 OR is_nsfw IS NULL)")
-                .fetch_one(pool)
-                .await
-                .context("Failed to count SFW archives")?
-        }
-        None => {
-            sqlx::query_as("SELECT COUNT(*) FROM archives WHERE status = 'complete'")
-                .fetch_one(pool)
-                .await
-                .context("Failed to count archives")?
-        }
-    };
-    Ok(count.0)
+    ArchiveQuery::new()
+        .status("complete")
+        .nsfw(nsfw_filter)
+        .count(pool)
+        .await
 }
 
 /// Get recent archives with domain and content_type filters.
@@ -957,93 +1285,88 @@ pub async fn get_recent_archives_with_filters(
     domain: Option<&str>,
     content_type: Option<&str>,
 ) -> Result<Vec<Archive>> {
-    match (domain, content_type) {
-        (Some(d), Some(ct)) => {
-            // Filter by both domain and content_type
-            sqlx::query_as(
-                r"
-                SELECT a.* FROM archives a
-                JOIN links l ON a.link_id = l.id
-                WHERE a.status = 'complete' AND l.domain = ? AND a.content_type = ?
-                ORDER BY COALESCE(a.post_date, a.archived_at, a.created_at) DESC
-                LIMIT ?
-                ",
-            )
-            .bind(d)
-            .bind(ct)
-            .bind(limit)
-            .fetch_all(pool)
-            .await
-            .context("Failed to fetch archives with domain and content_type filter")
-        }
-        (Some(d), None) => {
-            // Filter by domain only
-            sqlx::query_as(
-                r"
-                SELECT a.* FROM archives a
-                JOIN links l ON a.link_id = l.id
-                WHERE a.status = 'complete' AND l.domain = ?
-                ORDER BY COALESCE(a.post_date, a.archived_at, a.created_at) DESC
-                LIMIT ?
-                ",
-            )
-            .bind(d)
-            .bind(limit)
-            .fetch_all(pool)
-            .await
-            .context("Failed to fetch archives with domain filter")
-        }
-        (None, Some(ct)) => {
-            // Filter by content_type only
-            sqlx::query_as(
-                r"
-                SELECT * FROM archives
-                WHERE status = 'complete' AND content_type = ?
-                ORDER BY COALESCE(post_date, archived_at, created_at) DESC
-                LIMIT ?
-                ",
-            )
-            .bind(ct)
-            .bind(limit)
-            .fetch_all(pool)
-            .await
-            .context("Failed to fetch archives with content_type filter")
-        }
-        (None, None) => {
-            // No filters, use existing function
-            get_recent_archives(pool, limit).await
-        }
-    }
+    ArchiveQuery::new()
+        .status("complete")
+        .domain(domain)
+        .content_type(content_type)
+        .limit(limit)
+        .fetch_archives(pool)
+        .await
 }
 
-/// Get recent archives with link info for display (all statuses).
-pub async fn get_recent_archives_display(
+/// Get complete archives at or after `since`, oldest first.
+///
+/// Ordered ascending by post date (falling back to archive time), so a
+/// consumer can resume from the last item it saw without missing archives
+/// that arrived between polls. The boundary is inclusive; items sharing the
+/// `since` timestamp are returned again and should be deduplicated by ID.
+pub async fn get_archives_since(
     pool: &SqlitePool,
+    since: &str,
     limit: i64,
-) -> Result<Vec<ArchiveDisplay>> {
+) -> Result<Vec<Archive>> {
+    ArchiveQuery::new()
+        .status("complete")
+        .since(Some(since))
+        .direction(SortDirection::Asc)
+        .limit(limit)
+        .fetch_archives(pool)
+        .await
+}
+
+/// Get `(id, archived_at)` for every archive listed in the sitemap.
+///
+/// Only complete, non-NSFW archives are public enough to advertise to
+/// search engines.
+pub async fn get_sitemap_archives(pool: &SqlitePool) -> Result<Vec<(i64, Option<String>)>> {
     sqlx::query_as(
+        "SELECT id, archived_at FROM archives WHERE status = 'complete' AND is_nsfw = 0 ORDER BY id",
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch sitemap archives")
+}
+
+/// Pick a random complete, non-NSFW archive id for `/random`.
+///
+/// Draws a random id between the smallest and largest eligible ids and takes
+/// the first eligible archive at or after it, which walks the primary key
+/// instead of sorting the whole table. Archives after gaps in the id sequence
+/// are slightly more likely to be picked.
+pub async fn get_random_archive_id(pool: &SqlitePool) -> Result<Option<i64>> {
+    sqlx::query_scalar(
         r"
-        SELECT
-            a.id, a.link_id, a.status, a.archived_at,
-            a.content_title, a.content_author, a.content_type,
-            a.is_nsfw, a.error_message, a.retry_count,
-            l.original_url, l.domain,
-            COALESCE(SUM(aa.size_bytes), 0) as total_size_bytes
-        FROM archives a
-        JOIN links l ON a.link_id = l.id
-        LEFT JOIN archive_artifacts aa ON a.id = aa.archive_id
-        GROUP BY a.id, a.link_id, a.status, a.archived_at,
-                 a.content_title, a.content_author, a.content_type,
-                 a.is_nsfw, a.error_message, a.retry_count,
-                 l.original_url, l.domain
-        ORDER BY COALESCE(a.archived_at, a.last_attempt_at, a.created_at) DESC
-        LIMIT ?
+        WITH bounds AS (
+            SELECT MIN(id) AS lo, MAX(id) AS hi
+            FROM archives
+            WHERE status = 'complete' AND is_nsfw = 0
+        ),
+        target AS (
+            SELECT lo + (random() & 9223372036854775807) % (hi - lo + 1) AS id
+            FROM bounds
+            WHERE lo IS NOT NULL
+        )
+        SELECT archives.id FROM archives, target
+        WHERE archives.id >= target.id AND status = 'complete' AND is_nsfw = 0
+        ORDER BY archives.id
+        LIMIT 1
         ",
     )
-    .bind(limit)
-    .fetch_all(pool)
+    .fetch_optional(pool)
     .await
-    .context("Failed to fetch recent archives with links")
+    .context("Failed to pick a random archive")
+}
+
+/// Get recent archives with link info for display (all statuses).
+pub async fn get_recent_archives_display(
+    pool: &SqlitePool,
+    limit: i64,
+) -> Result<Vec<ArchiveDisplay>> {
+    ArchiveQuery::new()
+        .sort(ArchiveSort::Activity)
+        .limit(limit)
+        .fetch_display(pool)
+        .await
 }
 
 /// Search archives with link info for display.
@@ -1166,133 +1489,35 @@ pub async fn get_recent_archives_display_filtered(
     content_type: Option<&str>,
     source: Option<&str>,
 ) -> Result<Vec<ArchiveDisplay>> {
-    // Build WHERE clause dynamically based on filters
-    let mut where_clauses: Vec<String> = Vec::new();
-    let domain_filter = source.map(get_domain_filter);
-
-    if content_type.is_some() {
-        where_clauses.push("a.content_type = ?".to_string());
-    }
-
-    if let Some(ref df) = domain_filter {
-        where_clauses.push(df.sql.clone());
-    }
-
-    if where_clauses.is_empty() {
-        return get_recent_archives_display(pool, limit).await;
-    }
-
-    let where_clause = where_clauses.join(" AND ");
-    let sql = format!(
-        r"
-        SELECT
-            a.id, a.link_id, a.status, a.archived_at,
-            a.content_title, a.content_author, a.content_type,
-            a.is_nsfw, a.error_message, a.retry_count,
-            l.original_url, l.domain,
-            COALESCE(SUM(aa.size_bytes), 0) as total_size_bytes
-        FROM archives a
-        JOIN links l ON a.link_id = l.id
-        LEFT JOIN archive_artifacts aa ON a.id = aa.archive_id
-        WHERE {}
-        GROUP BY a.id, a.link_id, a.status, a.archived_at,
-                 a.content_title, a.content_author, a.content_type,
-                 a.is_nsfw, a.error_message, a.retry_count,
-                 l.original_url, l.domain
-        ORDER BY COALESCE(a.archived_at, a.last_attempt_at, a.created_at) DESC
-        LIMIT ?
-        ",
-        where_clause
-    );
-
-    let mut query = sqlx::query_as(&sql);
-
-    // Bind parameters in order
-    if let Some(ct) = content_type {
-        query = query.bind(ct);
-    }
-
-    if let Some(ref df) = domain_filter {
-        for value in &df.values {
-            query = query.bind(value);
-        }
-    }
-
-    query
-        .bind(limit)
-        .fetch_all(pool)
+    ArchiveQuery::new()
+        .content_type(content_type)
+        .source(source)
+        .sort(ArchiveSort::Activity)
+        .limit(limit)
+        .fetch_display(pool)
         .await
-        .context("Failed to fetch recent archives with filters")
 }
 
 /// Get all archives for table view with pagination support.
-/// Orders by archive ID descending (newest first).
+/// Orders by `sort_by` in `sort_dir` direction (the table defaults to ID descending).
 pub async fn get_all_archives_table_view(
     pool: &SqlitePool,
     limit: i64,
     offset: i64,
     content_type: Option<&str>,
     source: Option<&str>,
+    sort_by: ArchiveSort,
+    sort_dir: SortDirection,
 ) -> Result<Vec<ArchiveDisplay>> {
-    // Build WHERE clause dynamically based on filters
-    let mut where_clauses: Vec<String> = Vec::new();
-    let domain_filter = source.map(get_domain_filter);
-
-    if content_type.is_some() {
-        where_clauses.push("a.content_type = ?".to_string());
-    }
-
-    if let Some(ref df) = domain_filter {
-        where_clauses.push(df.sql.clone());
-    }
-
-    let where_clause = if where_clauses.is_empty() {
-        String::new()
-    } else {
-        format!("WHERE {}", where_clauses.join(" AND "))
-    };
-
-    let sql = format!(
-        r"
-        SELECT
-            a.id, a.link_id, a.status, a.archived_at,
-            a.content_title, a.content_author, a.content_type,
-            a.is_nsfw, a.error_message, a.retry_count,
-            l.original_url, l.domain,
-            COALESCE(SUM(aa.size_bytes), 0) as total_size_bytes
-        FROM archives a
-        JOIN links l ON a.link_id = l.id
-        LEFT JOIN archive_artifacts aa ON a.id = aa.archive_id
-        {}
-        GROUP BY a.id, a.link_id, a.status, a.archived_at,
-                 a.content_title, a.content_author, a.content_type,
-                 a.is_nsfw, a.error_message, a.retry_count,
-                 l.original_url, l.domain
-        ORDER BY a.id DESC
-        LIMIT ? OFFSET ?
-        ",
-        where_clause
-    );
-
-    let mut query = sqlx::query_as(&sql);
-
-    // Bind parameters in order
-    if let Some(ct) = content_type {
-        query = query.bind(ct);
-    }
-
-    if let Some(ref df) = domain_filter {
-        for value in &df.values {
-            query = query.bind(value);
-        }
-    }
-
-    query
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(pool)
+    ArchiveQuery::new()
+        .content_type(content_type)
+        .source(source)
+        .sort(sort_by)
+        .direction(sort_dir)
+        .limit(limit)
+        .offset(offset)
+        .fetch_display(pool)
         .await
-        .context("Failed to fetch archives for table view")
 }
 
 /// Count all archives with optional filters for pagination calculation.
@@ -1301,51 +1526,11 @@ pub async fn count_all_archives_filtered(
     content_type: Option<&str>,
     source: Option<&str>,
 ) -> Result<i64> {
-    // Build WHERE clause dynamically based on filters
-    let mut where_clauses: Vec<String> = Vec::new();
-    let domain_filter = source.map(get_domain_filter);
-
-    if content_type.is_some() {
-        where_clauses.push("a.content_type = ?".to_string());
-    }
-
-    if let Some(ref df) = domain_filter {
-        where_clauses.push(df.sql.clone());
-    }
-
-    let where_clause = if where_clauses.is_empty() {
-        String::new()
-    } else {
-        format!("WHERE {}", where_clauses.join(" AND "))
-    };
-
-    let sql = format!(
-        r"
-        SELECT COUNT(DISTINCT a.id)
-        FROM archives a
-        JOIN links l ON a.link_id = l.id
-        {}
-        ",
-        where_clause
-    );
-
-    let mut query = sqlx::query_scalar(&sql);
-
-    // Bind parameters in order
-    if let Some(ct) = content_type {
-        query = query.bind(ct);
-    }
-
-    if let Some(ref df) = domain_filter {
-        for value in &df.values {
-            query = query.bind(value);
-        }
-    }
-
-    query
-        .fetch_one(pool)
+    ArchiveQuery::new()
+        .content_type(content_type)
+        .source(source)
+        .count(pool)
         .await
-        .context("Failed to count archives")
 }
 
 /// Search archives with link info for display, with optional content_type and source filters.
@@ -1523,13 +1708,17 @@ pub async fn search_archives_display_filtered(
 }
 
 /// Get archives by domain with link info for display (all statuses).
+///
+/// `domain` is matched like [`get_domain_filter`]: subdomains are included and
+/// source names such as `twitter` map to their domains.
 pub async fn get_archives_by_domain_display(
     pool: &SqlitePool,
     domain: &str,
     limit: i64,
     offset: i64,
 ) -> Result<Vec<ArchiveDisplay>> {
-    sqlx::query_as(
+    let domain_filter = get_domain_filter(domain);
+    let query = format!(
         r"
         SELECT
             a.id, a.link_id, a.status, a.archived_at,
@@ -1540,7 +1729,7 @@ pub async fn get_archives_by_domain_display(
         FROM archives a
         JOIN links l ON a.link_id = l.id
         LEFT JOIN archive_artifacts aa ON a.id = aa.archive_id
-        WHERE l.domain = ?
+        WHERE {}
         GROUP BY a.id, a.link_id, a.status, a.archived_at,
                  a.content_title, a.content_author, a.content_type,
                  a.is_nsfw, a.error_message, a.retry_count,
@@ -1548,13 +1737,19 @@ pub async fn get_archives_by_domain_display(
         ORDER BY COALESCE(a.archived_at, a.last_attempt_at, a.created_at) DESC
         LIMIT ? OFFSET ?
         ",
-    )
-    .bind(domain)
-    .bind(limit)
-    .bind(offset)
-    .fetch_all(pool)
-    .await
-    .context("Failed to fetch archives by domain with links")
+        domain_filter.sql
+    );
+
+    let mut query_builder = sqlx::query_as(&query);
+    for value in &domain_filter.values {
+        query_builder = query_builder.bind(value);
+    }
+    query_builder
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+        .context("Failed to fetch archives by domain with links")
 }
 
 /// Get archives for a post with link info for display.
@@ -1610,6 +1805,45 @@ pub async fn get_archives_for_posts_display(
         .context("Failed to fetch archives for posts with links")
 }
 
+/// Count links and archives per post.
+///
+/// Posts without any links are omitted from the result.
+pub async fn get_link_counts_for_posts(
+    pool: &SqlitePool,
+    post_ids: &[i64],
+) -> Result<Vec<PostLinkCounts>> {
+    if post_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let placeholders = std::iter::repeat_n("?", post_ids.len())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let query = format!(
+        r#"
+        SELECT
+            lo.post_id,
+            COUNT(DISTINCT lo.link_id) as link_count,
+            COUNT(DISTINCT a.id) as archive_count
+        FROM link_occurrences lo
+        LEFT JOIN archives a ON a.link_id = lo.link_id
+        WHERE lo.post_id IN ({placeholders})
+        GROUP BY lo.post_id
+        "#
+    );
+
+    let mut query = sqlx::query_as(&query);
+    for id in post_ids {
+        query = query.bind(id);
+    }
+
+    query
+        .fetch_all(pool)
+        .await
+        .context("Failed to count links for posts")
+}
+
 /// Get all archives created for a thread archive job.
 ///
 /// This queries archives through the relationship:
@@ -1832,28 +2066,35 @@ pub async fn search_archives_filtered_full(
         .context("Failed to search filtered archives")
 }
 
-/// Get archives by domain.
+/// Get complete archives by domain, matched like [`get_domain_filter`].
 pub async fn get_archives_by_domain(
     pool: &SqlitePool,
     domain: &str,
     limit: i64,
     offset: i64,
 ) -> Result<Vec<Archive>> {
-    sqlx::query_as(
+    let domain_filter = get_domain_filter(domain);
+    let query = format!(
         r"
-        SELECT archives.* FROM archives
-        JOIN links ON archives.link_id = links.id
-        WHERE links.domain = ? AND archives.status = 'complete'
-        ORDER BY archives.archived_at DESC
+        SELECT a.* FROM archives a
+        JOIN links l ON a.link_id = l.id
+        WHERE {} AND a.status = 'complete'
+        ORDER BY a.archived_at DESC
         LIMIT ? OFFSET ?
         ",
-    )
-    .bind(domain)
-    .bind(limit)
-    .bind(offset)
-    .fetch_all(pool)
-    .await
-    .context("Failed to fetch archives by domain")
+        domain_filter.sql
+    );
+
+    let mut query_builder = sqlx::query_as(&query);
+    for value in &domain_filter.values {
+        query_builder = query_builder.bind(value);
+    }
+    query_builder
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+        .context("Failed to fetch archives by domain")
 }
 
 /// Get link by ID.
@@ -1896,10 +2137,39 @@ pub async fn insert_artifact(
     Ok(result.last_insert_rowid())
 }
 
-/// Insert a new archive artifact with metadata.
-///
-/// # Errors
-///
+/// Insert an archive artifact whose S3 object was stored with `content_encoding`.
+pub async fn insert_artifact_with_encoding(
+    pool: &SqlitePool,
+    archive_id: i64,
+    kind: &str,
+    s3_key: &str,
+    content_type: Option<&str>,
+    size_bytes: Option<i64>,
+    content_encoding: Option<&str>,
+) -> Result<i64> {
+    let result = sqlx::query(
+        r"
+        INSERT INTO archive_artifacts (archive_id, kind, s3_key, content_type, size_bytes, content_encoding)
+        VALUES (?, ?, ?, ?, ?, ?)
+        ",
+    )
+    .bind(archive_id)
+    .bind(kind)
+    .bind(s3_key)
+    .bind(content_type)
+    .bind(size_bytes)
+    .bind(content_encoding)
+    .execute(pool)
+    .await
+    .context("Failed to insert artifact with encoding")?;
+
+    Ok(result.last_insert_rowid())
+}
+
+/// Insert a new archive artifact with metadata.
+///
+/// # Errors
+///
 /// Returns an error if the database insert fails.
 pub async fn insert_artifact_with_metadata(
     pool: &SqlitePool,
@@ -1988,12 +2258,39 @@ pub async fn has_artifact_kind(pool: &SqlitePool, archive_id: i64, kind: &str) -
     Ok(row.0 > 0)
 }
 
+/// Determine which expected artifact kinds an archive is missing.
+///
+/// Video content should have subtitles and a transcript, plus comments on
+/// platforms that support comment extraction (YouTube, TikTok). Other content
+/// types have no expectations and never report anything missing.
+#[must_use]
+pub fn missing_artifact_kinds<S: std::hash::BuildHasher>(
+    content_type: Option<&str>,
+    url: &str,
+    existing_kinds: &HashSet<String, S>,
+) -> Vec<ArtifactKind> {
+    if content_type != Some("video") {
+        return Vec::new();
+    }
+
+    let is_comments_platform =
+        url.contains("youtube.com") || url.contains("youtu.be") || url.contains("tiktok.com");
+
+    let mut expected = vec![ArtifactKind::Subtitles, ArtifactKind::Transcript];
+    if is_comments_platform {
+        expected.push(ArtifactKind::Comments);
+    }
+
+    expected
+        .into_iter()
+        .filter(|kind| !existing_kinds.contains(kind.as_str()))
+        .collect()
+}
+
 /// Check if an archive has missing artifacts based on its content type.
 /// For video content (e.g., YouTube), checks for subtitles and transcripts.
 /// Returns true if any expected artifacts are missing.
 pub async fn has_missing_artifacts(pool: &SqlitePool, archive_id: i64) -> Result<bool> {
-    use crate::db::models::ArtifactKind;
-
     // Get the archive to check its content type and URL
     let archive = get_archive(pool, archive_id)
         .await?
@@ -2011,21 +2308,87 @@ pub async fn has_missing_artifacts(pool: &SqlitePool, archive_id: i64) -> Result
         return Ok(false);
     }
 
-    // Video content should have subtitles, transcript, and comments (platform-dependent)
-    let has_subtitles =
-        has_artifact_kind(pool, archive_id, ArtifactKind::Subtitles.as_str()).await?;
-    let has_transcript =
-        has_artifact_kind(pool, archive_id, ArtifactKind::Transcript.as_str()).await?;
-    let has_comments = has_artifact_kind(pool, archive_id, ArtifactKind::Comments.as_str()).await?;
+    let existing = get_existing_artifact_kinds(pool, archive_id).await?;
+    Ok(!missing_artifact_kinds(archive.content_type.as_deref(), url, &existing).is_empty())
+}
 
-    // Check if comments are missing on supported platforms (YouTube, TikTok)
-    let is_comments_platform =
-        url.contains("youtube.com") || url.contains("youtu.be") || url.contains("tiktok.com");
+/// A completed archive that is missing some expected artifacts.
+#[derive(Debug, Clone)]
+pub struct ArchiveMissingArtifacts {
+    pub archive_id: i64,
+    pub missing: Vec<ArtifactKind>,
+}
+
+/// SQL condition matching complete video archives that lack expected artifacts.
+///
+/// Mirrors [`missing_artifact_kinds`] so counts and scans agree. Expects the
+/// archive aliased as `a` and its link as `l`.
+const MISSING_ARTIFACTS_CONDITION: &str = r"
+    a.status = 'complete'
+    AND a.content_type = 'video'
+    AND (
+        NOT EXISTS (SELECT 1 FROM archive_artifacts x WHERE x.archive_id = a.id AND x.kind = 'subtitles')
+        OR NOT EXISTS (SELECT 1 FROM archive_artifacts x WHERE x.archive_id = a.id AND x.kind = 'transcript')
+        OR (
+            (COALESCE(l.final_url, l.normalized_url) LIKE '%youtube.com%'
+             OR COALESCE(l.final_url, l.normalized_url) LIKE '%youtu.be%'
+             OR COALESCE(l.final_url, l.normalized_url) LIKE '%tiktok.com%')
+            AND NOT EXISTS (SELECT 1 FROM archive_artifacts x WHERE x.archive_id = a.id AND x.kind = 'comments')
+        )
+    )
+";
+
+/// Count completed archives that are missing expected artifacts.
+pub async fn count_archives_missing_artifacts(pool: &SqlitePool) -> Result<i64> {
+    let sql = format!(
+        "SELECT COUNT(*) FROM archives a JOIN links l ON l.id = a.link_id WHERE {MISSING_ARTIFACTS_CONDITION}"
+    );
+    let row: (i64,) = sqlx::query_as(&sql)
+        .fetch_one(pool)
+        .await
+        .context("Failed to count archives missing artifacts")?;
+    Ok(row.0)
+}
+
+/// Find completed archives missing expected artifacts, in ID order.
+///
+/// Returns at most `limit` archives with ID greater than `after_id`, so a
+/// sweep can resume from the last ID it processed.
+pub async fn get_archives_missing_artifacts(
+    pool: &SqlitePool,
+    after_id: i64,
+    limit: i64,
+) -> Result<Vec<ArchiveMissingArtifacts>> {
+    let sql = format!(
+        r"
+        SELECT a.id, a.content_type, COALESCE(l.final_url, l.normalized_url)
+        FROM archives a
+        JOIN links l ON l.id = a.link_id
+        WHERE a.id > ? AND {MISSING_ARTIFACTS_CONDITION}
+        ORDER BY a.id
+        LIMIT ?
+        "
+    );
+    let rows: Vec<(i64, Option<String>, String)> = sqlx::query_as(&sql)
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .context("Failed to find archives missing artifacts")?;
+
+    let mut results = Vec::with_capacity(rows.len());
+    for (archive_id, content_type, url) in rows {
+        let existing = get_existing_artifact_kinds(pool, archive_id).await?;
+        let missing = missing_artifact_kinds(content_type.as_deref(), &url, &existing);
+        if !missing.is_empty() {
+            results.push(ArchiveMissingArtifacts {
+                archive_id,
+                missing,
+            });
+        }
+    }
 
-    // Missing if any of these are missing:
-    // - Subtitles or transcript (for all videos)
-    // - Comments (for YouTube/TikTok videos)
-    Ok(!has_subtitles || !has_transcript || (is_comments_platform && !has_comments))
+    Ok(results)
 }
 
 /// Find an artifact by its S3 key.
@@ -2136,7 +2499,7 @@ pub async fn get_storage_stats(pool: &SqlitePool) -> Result<(i64, f64, i64)> {
             COALESCE(SUM(size_bytes), 0) as total_size,
             COALESCE(AVG(size_bytes), 0.0) as avg_size,
             COALESCE(MAX(size_bytes), 0) as max_size
-         FROM artifacts",
+         FROM archive_artifacts",
     )
     .fetch_optional(pool)
     .await?;
@@ -2147,6 +2510,113 @@ pub async fn get_storage_stats(pool: &SqlitePool) -> Result<(i64, f64, i64)> {
     }
 }
 
+/// Artifact storage used by one link domain.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DomainStorage {
+    pub domain: String,
+    pub archive_count: i64,
+    pub total_bytes: i64,
+}
+
+/// Per-domain archive count and artifact storage; callers add the filter,
+/// grouping and ordering.
+///
+/// Archives without artifacts still count towards their domain's archive
+/// count but add nothing to its size.
+const DOMAIN_STORAGE_SELECT: &str = r"
+        SELECT
+            l.domain,
+            COUNT(DISTINCT a.id) AS archive_count,
+            COALESCE(SUM(aa.size_bytes), 0) AS total_bytes
+        FROM archives a
+        JOIN links l ON a.link_id = l.id
+        LEFT JOIN archive_artifacts aa ON aa.archive_id = a.id
+";
+
+/// Get the `limit` domains using the most artifact storage, largest first.
+pub async fn get_storage_by_domain(pool: &SqlitePool, limit: i64) -> Result<Vec<DomainStorage>> {
+    let query = format!(
+        "{DOMAIN_STORAGE_SELECT}
+        GROUP BY l.domain
+        ORDER BY total_bytes DESC, l.domain
+        LIMIT ?"
+    );
+    sqlx::query_as(&query)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .context("Failed to get storage by domain")
+}
+
+/// Archive and storage totals for one site's landing page.
+#[derive(Debug, Clone, Default)]
+pub struct DomainStats {
+    /// Storage for each matching domain (the site and its subdomains), largest first
+    pub domains: Vec<DomainStorage>,
+    /// Complete archives by content type, most common first
+    pub content_types: Vec<(String, i64)>,
+}
+
+impl DomainStats {
+    /// Archives across all matching domains, in any status.
+    #[must_use]
+    pub fn archive_count(&self) -> i64 {
+        self.domains.iter().map(|d| d.archive_count).sum()
+    }
+
+    /// Artifact bytes across all matching domains.
+    #[must_use]
+    pub fn total_bytes(&self) -> i64 {
+        self.domains.iter().map(|d| d.total_bytes).sum()
+    }
+}
+
+/// Get archive counts, storage and content types for a site.
+///
+/// `domain` is matched like [`get_domain_filter`], so subdomains are included.
+pub async fn get_domain_stats(pool: &SqlitePool, domain: &str) -> Result<DomainStats> {
+    let domain_filter = get_domain_filter(domain);
+
+    let query = format!(
+        "{DOMAIN_STORAGE_SELECT}
+        WHERE {}
+        GROUP BY l.domain
+        ORDER BY total_bytes DESC, l.domain",
+        domain_filter.sql
+    );
+    let mut query_builder = sqlx::query_as(&query);
+    for value in &domain_filter.values {
+        query_builder = query_builder.bind(value);
+    }
+    let domains = query_builder
+        .fetch_all(pool)
+        .await
+        .context("Failed to get domain storage")?;
+
+    let query = format!(
+        "SELECT COALESCE(a.content_type, 'unknown') AS content_type, COUNT(*) AS count
+         FROM archives a
+         JOIN links l ON a.link_id = l.id
+         WHERE {} AND a.status = 'complete'
+         GROUP BY COALESCE(a.content_type, 'unknown')
+         ORDER BY count DESC, content_type",
+        domain_filter.sql
+    );
+    let mut query_builder = sqlx::query_as(&query);
+    for value in &domain_filter.values {
+        query_builder = query_builder.bind(value);
+    }
+    let content_types = query_builder
+        .fetch_all(pool)
+        .await
+        .context("Failed to count domain archives by content type")?;
+
+    Ok(DomainStats {
+        domains,
+        content_types,
+    })
+}
+
 /// Get timeline data for archives (by month for the last 12 months).
 pub async fn get_archive_timeline(pool: &SqlitePool) -> Result<Vec<(String, i64)>> {
     sqlx::query_as(
@@ -2202,14 +2672,57 @@ pub async fn get_nsfw_count(pool: &SqlitePool) -> Result<i64> {
 }
 
 /// Get a post by ID.
-pub async fn get_post(pool: &SqlitePool, id: i64) -> Result<Option<Post>> {
+pub async fn get_post(executor: impl SqliteExecutor<'_>, id: i64) -> Result<Option<Post>> {
     sqlx::query_as("SELECT * FROM posts WHERE id = ?")
         .bind(id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await
         .context("Failed to fetch post")
 }
 
+/// Link a post to the archive of its own Discourse page.
+pub async fn set_post_snapshot_archive(
+    executor: impl SqliteExecutor<'_>,
+    post_id: i64,
+    archive_id: i64,
+) -> Result<()> {
+    sqlx::query("UPDATE posts SET snapshot_archive_id = ? WHERE id = ?")
+        .bind(archive_id)
+        .bind(post_id)
+        .execute(executor)
+        .await
+        .context("Failed to set post snapshot archive")?;
+
+    Ok(())
+}
+
+/// Get the archive of a post's own Discourse page, if one was queued.
+pub async fn get_post_snapshot_archive(pool: &SqlitePool, post_id: i64) -> Result<Option<Archive>> {
+    sqlx::query_as(
+        r"
+        SELECT archives.* FROM archives
+        JOIN posts ON posts.snapshot_archive_id = archives.id
+        WHERE posts.id = ?
+        ",
+    )
+    .bind(post_id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to fetch post snapshot archive")
+}
+
+/// Whether an archive is the snapshot of a Discourse post page.
+pub async fn is_post_snapshot_archive(pool: &SqlitePool, archive_id: i64) -> Result<bool> {
+    let row: Option<(i64,)> =
+        sqlx::query_as("SELECT id FROM posts WHERE snapshot_archive_id = ? LIMIT 1")
+            .bind(archive_id)
+            .fetch_optional(pool)
+            .await
+            .context("Failed to check post snapshot archive")?;
+
+    Ok(row.is_some())
+}
+
 /// Get archives for a specific post by joining through link_occurrences.
 pub async fn get_archives_for_post(pool: &SqlitePool, post_id: i64) -> Result<Vec<Archive>> {
     sqlx::query_as(
@@ -2247,7 +2760,7 @@ pub async fn get_occurrences_for_post(
 
 // ========== IPFS ==========
 
-/// Set the IPFS CID for an archive.
+/// Set the IPFS CID for an archive, clearing any pending pin.
 pub async fn set_archive_ipfs_cid(pool: &SqlitePool, id: i64, ipfs_cid: &str) -> Result<()> {
     sqlx::query("UPDATE archives SET ipfs_cid = ? WHERE id = ?")
         .bind(ipfs_cid)
@@ -2256,9 +2769,47 @@ pub async fn set_archive_ipfs_cid(pool: &SqlitePool, id: i64, ipfs_cid: &str) ->
         .await
         .context("Failed to set IPFS CID")?;
 
+    clear_ipfs_pin_pending(pool, id).await
+}
+
+/// Record that an archive's IPFS pin was skipped and should be backfilled.
+pub async fn mark_ipfs_pin_pending(pool: &SqlitePool, archive_id: i64) -> Result<()> {
+    sqlx::query("INSERT OR IGNORE INTO ipfs_pending_pins (archive_id) VALUES (?)")
+        .bind(archive_id)
+        .execute(pool)
+        .await
+        .context("Failed to mark IPFS pin pending")?;
+
+    Ok(())
+}
+
+/// Remove an archive from the pending IPFS pins.
+pub async fn clear_ipfs_pin_pending(pool: &SqlitePool, archive_id: i64) -> Result<()> {
+    sqlx::query("DELETE FROM ipfs_pending_pins WHERE archive_id = ?")
+        .bind(archive_id)
+        .execute(pool)
+        .await
+        .context("Failed to clear pending IPFS pin")?;
+
     Ok(())
 }
 
+/// Get archives waiting for an IPFS pin, oldest first.
+pub async fn get_pending_ipfs_pins(pool: &SqlitePool, limit: i64) -> Result<Vec<Archive>> {
+    sqlx::query_as(
+        r"
+        SELECT a.* FROM ipfs_pending_pins p
+        JOIN archives a ON a.id = p.archive_id
+        ORDER BY p.created_at, p.archive_id
+        LIMIT ?
+        ",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("Failed to get pending IPFS pins")
+}
+
 // ========== Submissions ==========
 
 /// Insert a new submission, returning its ID.
@@ -2321,6 +2872,25 @@ pub async fn count_submissions_from_ip_last_hour(pool: &SqlitePool, ip: &str) ->
     Ok(row.0)
 }
 
+/// Seconds until the oldest of an IP's submissions in the last hour leaves the
+/// rate limit window, or `None` if it has none.
+pub async fn submission_retry_after_secs(pool: &SqlitePool, ip: &str) -> Result<Option<i64>> {
+    let secs: Option<i64> = sqlx::query_scalar(
+        r"
+        SELECT MAX(0, 3600 - (unixepoch('now') - unixepoch(MIN(created_at))))
+        FROM submissions
+        WHERE submitted_by_ip = ?
+        AND created_at > datetime('now', '-1 hour')
+        ",
+    )
+    .bind(ip)
+    .fetch_one(pool)
+    .await
+    .context("Failed to get submission retry delay")?;
+
+    Ok(secs)
+}
+
 /// Check if a URL has already been submitted recently (within last 24 hours).
 pub async fn submission_exists_for_url(pool: &SqlitePool, normalized_url: &str) -> Result<bool> {
     let row: (i64,) = sqlx::query_as(
@@ -2618,10 +3188,91 @@ pub async fn get_artifact(pool: &SqlitePool, id: i64) -> Result<Option<ArchiveAr
         .context("Failed to fetch artifact")
 }
 
+/// Get the next batch of artifacts with an ID greater than `after_id`, in ID order.
+pub async fn get_artifacts_after(
+    pool: &SqlitePool,
+    after_id: i64,
+    limit: i64,
+) -> Result<Vec<ArchiveArtifact>> {
+    sqlx::query_as("SELECT * FROM archive_artifacts WHERE id > ? ORDER BY id LIMIT ?")
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .context("Failed to fetch artifacts")
+}
+
+/// Overwrite the stored size of an artifact.
+pub async fn set_artifact_size_bytes(
+    pool: &SqlitePool,
+    artifact_id: i64,
+    size_bytes: i64,
+) -> Result<()> {
+    sqlx::query("UPDATE archive_artifacts SET size_bytes = ? WHERE id = ?")
+        .bind(size_bytes)
+        .bind(artifact_id)
+        .execute(pool)
+        .await
+        .context("Failed to set artifact size")?;
+    Ok(())
+}
+
+/// Store the placeholder colour and pixel size of an image artifact.
+pub async fn set_artifact_placeholder(
+    pool: &SqlitePool,
+    artifact_id: i64,
+    color: &str,
+    width: i64,
+    height: i64,
+) -> Result<()> {
+    sqlx::query(
+        "UPDATE archive_artifacts SET placeholder_color = ?, width = ?, height = ? WHERE id = ?",
+    )
+    .bind(color)
+    .bind(width)
+    .bind(height)
+    .bind(artifact_id)
+    .execute(pool)
+    .await
+    .context("Failed to set artifact placeholder")?;
+    Ok(())
+}
+
+/// Get the thumbnail artifact of each archive, keyed by archive ID.
+///
+/// Archives without a thumbnail are absent from the map. If an archive has
+/// several thumbnails the most recent one is used.
+pub async fn get_thumbnails_for_archives(
+    pool: &SqlitePool,
+    archive_ids: &[i64],
+) -> Result<HashMap<i64, ArchiveArtifact>> {
+    if archive_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let placeholders = std::iter::repeat_n("?", archive_ids.len())
+        .collect::<Vec<_>>()
+        .join(",");
+    let sql = format!(
+        "SELECT * FROM archive_artifacts WHERE kind = 'thumb' AND archive_id IN ({placeholders}) ORDER BY id"
+    );
+
+    let mut query = sqlx::query_as::<_, ArchiveArtifact>(&sql);
+    for id in archive_ids {
+        query = query.bind(id);
+    }
+
+    let artifacts = query
+        .fetch_all(pool)
+        .await
+        .context("Failed to fetch thumbnails for archives")?;
+    Ok(artifacts.into_iter().map(|a| (a.archive_id, a)).collect())
+}
+
 // ========== Debug / Queue Stats ==========
 
 /// Queue statistics for debug page.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct QueueStats {
     pub pending_count: i64,
     pub processing_count: i64,
@@ -2694,31 +3345,132 @@ pub async fn get_queue_stats(pool: &SqlitePool, max_retries: i32) -> Result<Queu
     })
 }
 
+/// Pending and processing archive counts for one domain.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DomainQueueCounts {
+    pub domain: String,
+    pub pending_count: i64,
+    pub processing_count: i64,
+    /// Creation time of the domain's oldest pending archive.
+    pub oldest_pending_at: Option<String>,
+    /// Age of the domain's oldest pending archive in seconds.
+    pub oldest_pending_age_secs: Option<i64>,
+}
+
+/// Get queued archive counts per domain for the debug page.
+///
+/// Returns the `limit` domains with the most pending and processing archives,
+/// breaking ties by the oldest pending archive.
+pub async fn get_pending_counts_by_domain(
+    pool: &SqlitePool,
+    limit: i64,
+) -> Result<Vec<DomainQueueCounts>> {
+    sqlx::query_as(
+        r"
+        SELECT
+            l.domain,
+            SUM(a.status = 'pending') AS pending_count,
+            SUM(a.status = 'processing') AS processing_count,
+            MIN(CASE WHEN a.status = 'pending' THEN a.created_at END) AS oldest_pending_at,
+            CAST(
+                (julianday('now')
+                    - julianday(MIN(CASE WHEN a.status = 'pending' THEN a.created_at END)))
+                * 86400 AS INTEGER
+            ) AS oldest_pending_age_secs
+        FROM archives a
+        JOIN links l ON a.link_id = l.id
+        WHERE a.status IN ('pending', 'processing')
+        GROUP BY l.domain
+        ORDER BY
+            pending_count + processing_count DESC,
+            oldest_pending_at IS NULL,
+            oldest_pending_at ASC,
+            l.domain
+        LIMIT ?
+        ",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("Failed to get pending counts by domain")
+}
+
 /// Get recent failed archives with error details.
-pub async fn get_recent_failed_archives(pool: &SqlitePool, limit: i64) -> Result<Vec<Archive>> {
+///
+/// With `error_kind`, only failures in that category are returned (including
+/// archives waiting on authentication). With `skip_reason`, only archives
+/// skipped for that reason are returned.
+pub async fn get_recent_failed_archives(
+    pool: &SqlitePool,
+    error_kind: Option<&str>,
+    skip_reason: Option<&str>,
+    limit: i64,
+) -> Result<Vec<Archive>> {
     sqlx::query_as(
         r"
         SELECT * FROM archives
-        WHERE status = 'failed' OR status = 'skipped'
+        WHERE CASE WHEN ?1 IS NULL THEN status IN ('failed', 'skipped')
+                   ELSE status IN ('failed', 'skipped', 'auth_required') AND error_kind = ?1 END
+          AND (?2 IS NULL OR skip_reason = ?2)
         ORDER BY last_attempt_at DESC NULLS LAST, created_at DESC
-        LIMIT ?
+        LIMIT ?3
         ",
     )
+    .bind(error_kind)
+    .bind(skip_reason)
     .bind(limit)
     .fetch_all(pool)
     .await
     .context("Failed to fetch recent failed archives")
 }
 
-/// Reset all skipped archives back to pending for retry.
-pub async fn reset_skipped_archives(pool: &SqlitePool) -> Result<u64> {
-    let result = sqlx::query(
-        r"
+/// Count failed, skipped and auth-required archives by `error_kind`, largest first.
+///
+/// Archives that failed before error kinds were recorded count under `None`.
+pub async fn get_failure_kind_counts(pool: &SqlitePool) -> Result<Vec<(Option<String>, i64)>> {
+    sqlx::query_as(
+        r"
+        SELECT error_kind, COUNT(*) AS count
+        FROM archives
+        WHERE status IN ('failed', 'skipped', 'auth_required')
+        GROUP BY error_kind
+        ORDER BY count DESC, error_kind
+        ",
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to count failures by kind")
+}
+
+/// Count skipped archives by `skip_reason`, largest first.
+///
+/// Archives skipped before reasons were recorded count under `None`.
+pub async fn get_skip_reason_counts(pool: &SqlitePool) -> Result<Vec<(Option<String>, i64)>> {
+    sqlx::query_as(
+        r"
+        SELECT skip_reason, COUNT(*) AS count
+        FROM archives
+        WHERE status = 'skipped'
+        GROUP BY skip_reason
+        ORDER BY count DESC, skip_reason
+        ",
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to count skipped archives by reason")
+}
+
+/// Reset all skipped archives back to pending for retry.
+pub async fn reset_skipped_archives(pool: &SqlitePool) -> Result<u64> {
+    let result = sqlx::query(
+        r"
         UPDATE archives
         SET status = 'pending',
             retry_count = 0,
             next_retry_at = NULL,
-            error_message = NULL
+            error_message = NULL,
+            error_kind = NULL,
+            skip_reason = NULL
         WHERE status = 'skipped'
         ",
     )
@@ -2737,7 +3489,9 @@ pub async fn reset_single_skipped_archive(pool: &SqlitePool, id: i64) -> Result<
         SET status = 'pending',
             retry_count = 0,
             next_retry_at = NULL,
-            error_message = NULL
+            error_message = NULL,
+            error_kind = NULL,
+            skip_reason = NULL
         WHERE id = ? AND status = 'skipped'
         ",
     )
@@ -2843,6 +3597,12 @@ pub async fn delete_archive(pool: &SqlitePool, id: i64) -> Result<()> {
         .await
         .context("Failed to delete artifacts")?;
 
+    sqlx::query("DELETE FROM archive_versions WHERE archive_id = ?")
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to delete archive versions")?;
+
     // Delete the archive
     sqlx::query("DELETE FROM archives WHERE id = ?")
         .bind(id)
@@ -2855,8 +3615,82 @@ pub async fn delete_archive(pool: &SqlitePool, id: i64) -> Result<()> {
     Ok(())
 }
 
+/// Get archives old enough to be pruned by the retention worker.
+///
+/// Selects `failed` archives that have used up their retries and, when
+/// `include_skipped` is set, `skipped` archives, whose last attempt (or
+/// creation, if never attempted) is more than `retention_days` days ago.
+pub async fn get_archives_eligible_for_pruning(
+    pool: &SqlitePool,
+    retention_days: u32,
+    max_retries: i32,
+    include_skipped: bool,
+    limit: i64,
+) -> Result<Vec<Archive>> {
+    sqlx::query_as(
+        r"
+        SELECT * FROM archives
+        WHERE ((status = 'failed' AND retry_count >= ?)
+               OR (? AND status = 'skipped' AND COALESCE(skip_reason, '') != 'dry_run'))
+          AND COALESCE(last_attempt_at, created_at) < datetime('now', ?)
+        ORDER BY id
+        LIMIT ?
+        ",
+    )
+    .bind(max_retries)
+    .bind(include_skipped)
+    .bind(format!("-{retention_days} days"))
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch archives eligible for pruning")
+}
+
 // ========== Re-archiving ==========
 
+/// Record the current primary artifact's S3 key and sha256 on the archive.
+///
+/// Reset deletes the artifact rows, so this keeps what the worker needs to
+/// tell whether a re-download matches the object already in S3.
+async fn remember_previous_primary(conn: &mut SqliteConnection, id: i64) -> Result<()> {
+    sqlx::query(
+        r"
+        UPDATE archives
+        SET previous_primary_s3_key = s3_key_primary,
+            previous_primary_sha256 = (
+                SELECT a.sha256 FROM archive_artifacts a
+                WHERE a.archive_id = archives.id AND a.s3_key = archives.s3_key_primary
+                ORDER BY a.id DESC
+                LIMIT 1
+            )
+        WHERE id = ?
+        ",
+    )
+    .bind(id)
+    .execute(conn)
+    .await
+    .context("Failed to remember previous primary artifact")?;
+    Ok(())
+}
+
+/// Get the S3 key and sha256 of the primary artifact from before the last reset.
+///
+/// Returns `None` unless both were known.
+pub async fn get_previous_primary(pool: &SqlitePool, id: i64) -> Result<Option<(String, String)>> {
+    let row: Option<(Option<String>, Option<String>)> = sqlx::query_as(
+        "SELECT previous_primary_s3_key, previous_primary_sha256 FROM archives WHERE id = ?",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to fetch previous primary artifact")?;
+
+    Ok(match row {
+        Some((Some(key), Some(sha256))) => Some((key, sha256)),
+        _ => None,
+    })
+}
+
 /// Reset an archive for full re-archiving.
 ///
 /// This resets the archive to pending state, clears all results from previous
@@ -2884,6 +3718,8 @@ pub async fn reset_archive_for_rearchive(pool: &SqlitePool, id: i64) -> Result<(
     .await
     .context("Failed to clear duplicate references")?;
 
+    remember_previous_primary(&mut tx, id).await?;
+
     // Delete existing artifacts for this archive
     sqlx::query("DELETE FROM archive_artifacts WHERE archive_id = ?")
         .bind(id)
@@ -2915,12 +3751,15 @@ pub async fn reset_archive_for_rearchive(pool: &SqlitePool, id: i64) -> Result<(
             archive_today_url = NULL,
             ipfs_cid = NULL,
             error_message = NULL,
+            error_kind = NULL,
+            skip_reason = NULL,
             retry_count = 0,
             next_retry_at = NULL,
             last_attempt_at = NULL,
             is_nsfw = 0,
             nsfw_source = NULL,
-            http_status_code = NULL
+            http_status_code = NULL,
+            redirect_chain = NULL
         WHERE id = ?
         ",
     )
@@ -2968,6 +3807,8 @@ pub async fn reset_archive_for_rearchive_preserve_metadata(
     .await
     .context("Failed to clear duplicate references")?;
 
+    remember_previous_primary(&mut tx, id).await?;
+
     // Delete existing artifacts for this archive
     sqlx::query("DELETE FROM archive_artifacts WHERE archive_id = ?")
         .bind(id)
@@ -2995,10 +3836,13 @@ pub async fn reset_archive_for_rearchive_preserve_metadata(
             archive_today_url = NULL,
             ipfs_cid = NULL,
             error_message = NULL,
+            error_kind = NULL,
+            skip_reason = NULL,
             retry_count = 0,
             next_retry_at = NULL,
             last_attempt_at = NULL,
-            http_status_code = NULL
+            http_status_code = NULL,
+            redirect_chain = NULL
         WHERE id = ?
         ",
     )
@@ -3030,6 +3874,22 @@ pub async fn set_archive_http_status_code(
     Ok(())
 }
 
+/// Store the redirect chain (JSON) observed while resolving an archive's URL.
+pub async fn set_archive_redirect_chain(
+    pool: &SqlitePool,
+    id: i64,
+    redirect_chain: &str,
+) -> Result<()> {
+    sqlx::query("UPDATE archives SET redirect_chain = ? WHERE id = ?")
+        .bind(redirect_chain)
+        .bind(id)
+        .execute(pool)
+        .await
+        .context("Failed to set redirect chain")?;
+
+    Ok(())
+}
+
 /// Update download progress for an archive.
 ///
 /// This is called periodically during yt-dlp downloads to track progress.
@@ -3078,7 +3938,7 @@ pub async fn clear_archive_progress(pool: &SqlitePool, id: i64) -> Result<()> {
     Ok(())
 }
 
-/// Set the quoted tweet archive link for a Twitter/X archive.
+/// Set the quoted post archive link for a Twitter/X or Bluesky archive.
 pub async fn set_archive_quoted_link(
     pool: &SqlitePool,
     archive_id: i64,
@@ -3094,7 +3954,7 @@ pub async fn set_archive_quoted_link(
     Ok(())
 }
 
-/// Set the reply-to tweet archive link for a Twitter/X archive.
+/// Set the reply-to post archive link for a Twitter/X or Bluesky archive.
 pub async fn set_archive_reply_link(
     pool: &SqlitePool,
     archive_id: i64,
@@ -3110,6 +3970,9 @@ pub async fn set_archive_reply_link(
     Ok(())
 }
 
+/// Maximum number of archives returned for a quote/reply chain or self-thread.
+const QUOTE_REPLY_CHAIN_LIMIT: i64 = 100;
+
 /// Get the quote/reply chain for a Twitter/X archive.
 /// Returns archives in order from the given archive up to the root.
 pub async fn get_quote_reply_chain(pool: &SqlitePool, archive_id: i64) -> Result<Vec<Archive>> {
@@ -3124,10 +3987,11 @@ pub async fn get_quote_reply_chain(pool: &SqlitePool, archive_id: i64) -> Result
             WHERE a.id != c.id
         )
         SELECT * FROM chain
-        LIMIT 10
+        LIMIT ?
         ",
     )
     .bind(archive_id)
+    .bind(QUOTE_REPLY_CHAIN_LIMIT)
     .fetch_all(pool)
     .await
     .context("Failed to get quote/reply chain")?;
@@ -3135,6 +3999,72 @@ pub async fn get_quote_reply_chain(pool: &SqlitePool, archive_id: i64) -> Result
     Ok(archives)
 }
 
+/// Get the reply thread an archive belongs to, ordered root-first.
+///
+/// Follows replies forward to the thread's last archive, then walks its
+/// quote/reply chain back so every earlier tweet is included. Returns just
+/// the archive itself when it isn't part of a thread.
+pub async fn get_self_thread(pool: &SqlitePool, archive_id: i64) -> Result<Vec<Archive>> {
+    let tail: Option<(i64,)> = sqlx::query_as(
+        r"
+        WITH RECURSIVE descendants(id, depth) AS (
+            SELECT ?, 0
+            UNION
+            SELECT a.id, d.depth + 1 FROM archives a
+            JOIN descendants d ON a.reply_to_archive_id = d.id
+            WHERE d.depth < ?
+        )
+        SELECT id FROM descendants
+        ORDER BY depth DESC, id
+        LIMIT 1
+        ",
+    )
+    .bind(archive_id)
+    .bind(QUOTE_REPLY_CHAIN_LIMIT)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to find end of reply thread")?;
+    let tail = tail.map_or(archive_id, |(id,)| id);
+
+    let chain = get_quote_reply_chain(pool, tail).await?;
+    let links: Vec<(i64, Option<i64>)> = chain
+        .iter()
+        .map(|a| (a.id, a.reply_to_archive_id))
+        .collect();
+    let mut by_id: HashMap<i64, Archive> = chain.into_iter().map(|a| (a.id, a)).collect();
+
+    Ok(order_reply_thread(&links, tail)
+        .into_iter()
+        .filter_map(|id| by_id.remove(&id))
+        .collect())
+}
+
+/// Order a reply thread root-first by walking reply links back from `tail`.
+///
+/// `links` holds `(archive_id, reply_to_archive_id)` pairs. The walk stops at
+/// an archive with no parent, a parent missing from `links`, or a cycle;
+/// quoted archives in the chain are left out.
+#[must_use]
+pub fn order_reply_thread(links: &[(i64, Option<i64>)], tail: i64) -> Vec<i64> {
+    let parents: HashMap<i64, Option<i64>> = links.iter().copied().collect();
+    let mut ordered = Vec::new();
+    let mut current = Some(tail);
+
+    while let Some(id) = current {
+        if ordered.contains(&id) {
+            break;
+        }
+        let Some(&parent) = parents.get(&id) else {
+            break;
+        };
+        ordered.push(id);
+        current = parent;
+    }
+
+    ordered.reverse();
+    ordered
+}
+
 /// Find an existing archive for a URL (by normalized URL).
 /// Used to link quote/reply tweets to existing archives.
 pub async fn find_archive_by_url(pool: &SqlitePool, normalized_url: &str) -> Result<Option<i64>> {
@@ -3462,6 +4392,27 @@ pub async fn delete_jobs_for_archive(pool: &SqlitePool, archive_id: i64) -> Resu
     Ok(())
 }
 
+/// Check if an archive has a pending or running job of the given type.
+pub async fn has_active_job(
+    pool: &SqlitePool,
+    archive_id: i64,
+    job_type: ArchiveJobType,
+) -> Result<bool> {
+    let row: (i64,) = sqlx::query_as(
+        r"
+        SELECT COUNT(*) FROM archive_jobs
+        WHERE archive_id = ? AND job_type = ? AND status IN ('pending', 'running')
+        ",
+    )
+    .bind(archive_id)
+    .bind(job_type.as_str())
+    .fetch_one(pool)
+    .await
+    .context("Failed to check for active job")?;
+
+    Ok(row.0 > 0)
+}
+
 /// Get pending comment extraction jobs.
 pub async fn get_pending_comment_extraction_jobs(
     pool: &SqlitePool,
@@ -3472,7 +4423,7 @@ pub async fn get_pending_comment_extraction_jobs(
         SELECT * FROM archive_jobs
         WHERE job_type = 'comment_extraction'
           AND status = 'pending'
-        ORDER BY created_at ASC
+        ORDER BY created_at ASC, id ASC
         LIMIT ?
         ",
     )
@@ -3915,12 +4866,12 @@ pub async fn count_users(pool: &SqlitePool) -> Result<i64> {
     Ok(row.0)
 }
 
-/// Get all users with pagination.
+/// Get all users with pagination, newest first.
 pub async fn get_all_users(pool: &SqlitePool, limit: i64, offset: i64) -> Result<Vec<User>> {
     sqlx::query_as(
         r"
         SELECT * FROM users
-        ORDER BY created_at DESC
+        ORDER BY created_at DESC, id DESC
         LIMIT ? OFFSET ?
         ",
     )
@@ -3931,6 +4882,27 @@ pub async fn get_all_users(pool: &SqlitePool, limit: i64, offset: i64) -> Result
     .context("Failed to get all users")
 }
 
+/// Get the users with the given IDs, e.g. to name the users on a page of audit events.
+pub async fn get_users_by_ids(pool: &SqlitePool, user_ids: &[i64]) -> Result<Vec<User>> {
+    if user_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let placeholders = std::iter::repeat_n("?", user_ids.len())
+        .collect::<Vec<_>>()
+        .join(",");
+    let sql = format!("SELECT * FROM users WHERE id IN ({placeholders}) ORDER BY id");
+
+    let mut query = sqlx::query_as::<_, User>(&sql);
+    for id in user_ids {
+        query = query.bind(id);
+    }
+    query
+        .fetch_all(pool)
+        .await
+        .context("Failed to get users by id")
+}
+
 /// Update user approval status.
 pub async fn update_user_approval(
     pool: &SqlitePool,
@@ -4176,7 +5148,7 @@ pub async fn delete_expired_sessions(pool: &SqlitePool) -> Result<u64> {
 /// Create an audit event.
 #[allow(clippy::too_many_arguments)]
 pub async fn create_audit_event(
-    pool: &SqlitePool,
+    executor: impl SqliteExecutor<'_>,
     user_id: Option<i64>,
     event_type: &str,
     target_type: Option<&str>,
@@ -4200,14 +5172,18 @@ pub async fn create_audit_event(
     .bind(ip_address)
     .bind(forwarded_for)
     .bind(user_agent)
-    .execute(pool)
+    .execute(executor)
     .await
     .context("Failed to create audit event")?;
 
     Ok(result.last_insert_rowid())
 }
 
-/// Get audit events with pagination.
+/// Get audit events with pagination, newest first.
+///
+/// Events are appended in time order, so the id orders them. The offset is
+/// applied to ids alone before full rows are read, which keeps deep pages
+/// cheap.
 pub async fn get_audit_events(
     pool: &SqlitePool,
     limit: i64,
@@ -4216,8 +5192,8 @@ pub async fn get_audit_events(
     sqlx::query_as(
         r"
         SELECT * FROM audit_events
-        ORDER BY created_at DESC
-        LIMIT ? OFFSET ?
+        WHERE id IN (SELECT id FROM audit_events ORDER BY id DESC LIMIT ? OFFSET ?)
+        ORDER BY id DESC
         ",
     )
     .bind(limit)
@@ -4259,6 +5235,69 @@ pub async fn count_audit_events(pool: &SqlitePool) -> Result<i64> {
     Ok(row.0)
 }
 
+/// Get audit events, optionally filtered by event type and/or user.
+///
+/// Paged the same way as [`get_audit_events`].
+pub async fn get_audit_events_filtered(
+    pool: &SqlitePool,
+    event_type: Option<&str>,
+    user_id: Option<i64>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<AuditEvent>> {
+    sqlx::query_as(
+        r"
+        SELECT * FROM audit_events
+        WHERE id IN (
+            SELECT id FROM audit_events
+            WHERE (?1 IS NULL OR event_type = ?1)
+              AND (?2 IS NULL OR user_id = ?2)
+            ORDER BY id DESC
+            LIMIT ?3 OFFSET ?4
+        )
+        ORDER BY id DESC
+        ",
+    )
+    .bind(event_type)
+    .bind(user_id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
+    .await
+    .context("Failed to get filtered audit events")
+}
+
+/// Count audit events matching the same filters as [`get_audit_events_filtered`].
+pub async fn count_audit_events_filtered(
+    pool: &SqlitePool,
+    event_type: Option<&str>,
+    user_id: Option<i64>,
+) -> Result<i64> {
+    let row: (i64,) = sqlx::query_as(
+        r"
+        SELECT COUNT(*) FROM audit_events
+        WHERE (?1 IS NULL OR event_type = ?1)
+          AND (?2 IS NULL OR user_id = ?2)
+        ",
+    )
+    .bind(event_type)
+    .bind(user_id)
+    .fetch_one(pool)
+    .await
+    .context("Failed to count filtered audit events")?;
+    Ok(row.0)
+}
+
+/// Get the distinct audit event types, for filter dropdowns.
+pub async fn get_audit_event_types(pool: &SqlitePool) -> Result<Vec<String>> {
+    let rows: Vec<(String,)> =
+        sqlx::query_as("SELECT DISTINCT event_type FROM audit_events ORDER BY event_type")
+            .fetch_all(pool)
+            .await
+            .context("Failed to get audit event types")?;
+    Ok(rows.into_iter().map(|(t,)| t).collect())
+}
+
 // ========== User Agents ==========
 
 /// Get or create a user agent entry (for deduplication).
@@ -4631,15 +5670,50 @@ pub async fn add_excluded_domain(
     Ok(result.last_insert_rowid())
 }
 
+/// Build the `excluded_domains` entries that would match `domain`.
+///
+/// Bare entries (`example.com`) match only that exact host. Entries with a
+/// leading `*.` or `.` (`*.example.com`, `.example.com`) match the domain and
+/// all of its subdomains, so we generate both pattern forms for the domain
+/// itself and every parent suffix.
+fn excluded_domain_candidates(domain: &str) -> Vec<String> {
+    let domain = domain.trim().trim_end_matches('.').to_lowercase();
+    let mut candidates = vec![domain.clone()];
+
+    let mut suffix = domain.as_str();
+    loop {
+        if !suffix.is_empty() {
+            candidates.push(format!("*.{suffix}"));
+            candidates.push(format!(".{suffix}"));
+        }
+        match suffix.split_once('.') {
+            Some((_, parent)) => suffix = parent,
+            None => break,
+        }
+    }
+
+    candidates
+}
+
 /// Check if a domain is excluded from archiving.
+///
+/// Matches exact entries and subdomain patterns; see [`excluded_domain_candidates`].
 pub async fn is_domain_excluded(pool: &SqlitePool, domain: &str) -> Result<bool> {
-    let result: Option<(i64,)> = sqlx::query_as(
-        "SELECT id FROM excluded_domains WHERE domain = ? AND is_active = 1 LIMIT 1",
-    )
-    .bind(domain)
-    .fetch_optional(pool)
-    .await
-    .context("Failed to check excluded domain")?;
+    let candidates = excluded_domain_candidates(domain);
+    let placeholders = vec!["?"; candidates.len()].join(", ");
+    let sql = format!(
+        "SELECT id FROM excluded_domains WHERE domain IN ({placeholders}) AND is_active = 1 LIMIT 1"
+    );
+
+    let mut query = sqlx::query_as::<_, (i64,)>(&sql);
+    for candidate in &candidates {
+        query = query.bind(candidate);
+    }
+
+    let result = query
+        .fetch_optional(pool)
+        .await
+        .context("Failed to check excluded domain")?;
 
     Ok(result.is_some())
 }
@@ -4700,74 +5774,699 @@ pub async fn delete_excluded_domain(pool: &SqlitePool, domain: &str) -> Result<(
 }
 
 // ============================================================================
-// Thread Archive Jobs queries
+// Domain Quote Policy queries
 // ============================================================================
 
-use super::models::NewThreadArchiveJob;
+use super::models::DomainQuotePolicy;
 
-/// Insert a new thread archive job.
-pub async fn insert_thread_archive_job(
+/// Create or replace the quote-only archiving override for a domain.
+pub async fn set_domain_quote_policy(
     pool: &SqlitePool,
-    job: &NewThreadArchiveJob,
-) -> Result<i64> {
-    let result = sqlx::query(
+    domain: &str,
+    archive_quote_only: bool,
+    created_by_user_id: Option<i64>,
+) -> Result<()> {
+    sqlx::query(
         r"
-        INSERT INTO thread_archive_jobs (thread_url, rss_url, user_id)
+        INSERT INTO domain_quote_policies (domain, archive_quote_only, created_by_user_id)
         VALUES (?, ?, ?)
+        ON CONFLICT(domain) DO UPDATE SET
+            archive_quote_only = excluded.archive_quote_only,
+            updated_at = CURRENT_TIMESTAMP
         ",
     )
-    .bind(&job.thread_url)
-    .bind(&job.rss_url)
-    .bind(job.user_id)
+    .bind(domain)
+    .bind(archive_quote_only)
+    .bind(created_by_user_id)
     .execute(pool)
     .await
-    .context("Failed to insert thread archive job")?;
+    .context("Failed to set domain quote policy")?;
 
-    Ok(result.last_insert_rowid())
+    Ok(())
 }
 
-/// Get a thread archive job by ID.
-pub async fn get_thread_archive_job(
-    pool: &SqlitePool,
-    id: i64,
-) -> Result<Option<ThreadArchiveJob>> {
-    sqlx::query_as("SELECT * FROM thread_archive_jobs WHERE id = ?")
-        .bind(id)
-        .fetch_optional(pool)
+/// Delete the quote-only archiving override for a domain.
+pub async fn delete_domain_quote_policy(pool: &SqlitePool, domain: &str) -> Result<()> {
+    sqlx::query("DELETE FROM domain_quote_policies WHERE domain = ?")
+        .bind(domain)
+        .execute(pool)
         .await
-        .context("Failed to fetch thread archive job")
+        .context("Failed to delete domain quote policy")?;
+
+    Ok(())
 }
 
-/// Get pending thread archive jobs for processing.
-pub async fn get_pending_thread_archive_jobs(
-    pool: &SqlitePool,
-    limit: i64,
-) -> Result<Vec<ThreadArchiveJob>> {
-    sqlx::query_as(
-        r"
-        SELECT * FROM thread_archive_jobs
-        WHERE status = 'pending'
-        ORDER BY created_at ASC
-        LIMIT ?
-        ",
+/// Get all quote-only archiving overrides.
+pub async fn get_domain_quote_policies(pool: &SqlitePool) -> Result<Vec<DomainQuotePolicy>> {
+    let policies = sqlx::query_as::<_, DomainQuotePolicy>(
+        "SELECT id, domain, archive_quote_only, created_at, created_by_user_id, updated_at FROM domain_quote_policies ORDER BY domain",
     )
-    .bind(limit)
     .fetch_all(pool)
     .await
-    .context("Failed to fetch pending thread archive jobs")
+    .context("Failed to get domain quote policies")?;
+
+    Ok(policies)
 }
 
-/// Set thread archive job to processing status.
-pub async fn set_thread_archive_job_processing(
-    pool: &SqlitePool,
-    id: i64,
-    total_posts: Option<i64>,
-) -> Result<()> {
-    sqlx::query(
+/// Look up the quote-only override that applies to `domain`, if any.
+///
+/// Entries match with the same rules as excluded domains; when several match,
+/// the most specific one wins (exact host before `*.sub.example.com` before
+/// `*.example.com`).
+pub async fn get_domain_quote_override(
+    executor: impl SqliteExecutor<'_>,
+    domain: &str,
+) -> Result<Option<bool>> {
+    let candidates = excluded_domain_candidates(domain);
+    let placeholders = vec!["?"; candidates.len()].join(", ");
+    let sql = format!(
+        "SELECT domain, archive_quote_only FROM domain_quote_policies WHERE domain IN ({placeholders})"
+    );
+
+    let mut query = sqlx::query_as::<_, (String, bool)>(&sql);
+    for candidate in &candidates {
+        query = query.bind(candidate);
+    }
+
+    let rows = query
+        .fetch_all(executor)
+        .await
+        .context("Failed to look up domain quote policy")?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(entry, archive)| {
+            candidates
+                .iter()
+                .position(|c| *c == entry)
+                .map(|rank| (rank, archive))
+        })
+        .min_by_key(|(rank, _)| *rank)
+        .map(|(_, archive)| archive))
+}
+
+/// Combine the global quote-only setting with a per-domain override.
+///
+/// A domain override always takes precedence over the global default.
+#[must_use]
+pub fn resolve_quote_only_policy(global_default: bool, domain_override: Option<bool>) -> bool {
+    domain_override.unwrap_or(global_default)
+}
+
+/// Decide whether a link that has only been seen inside quotes should be archived.
+pub async fn should_archive_quote_only_link(
+    executor: impl SqliteExecutor<'_>,
+    domain: &str,
+    global_default: bool,
+) -> Result<bool> {
+    let domain_override = get_domain_quote_override(executor, domain).await?;
+    Ok(resolve_quote_only_policy(global_default, domain_override))
+}
+
+// ============================================================================
+// Priority Domain queries
+// ============================================================================
+
+use super::models::PriorityDomain;
+
+/// SQL expression that is true when the link aliased `l` is on a priority domain.
+///
+/// Entries match with the same rules as excluded domains: a bare domain
+/// matches only that host, while `*.example.com` and `.example.com` also
+/// match every subdomain.
+const PRIORITY_DOMAIN_MATCH: &str = r"EXISTS (
+    SELECT 1 FROM priority_domains p
+    WHERE p.domain = l.domain
+       OR (p.domain LIKE '*.%'
+           AND (l.domain = substr(p.domain, 3) OR l.domain LIKE '%.' || substr(p.domain, 3)))
+       OR (p.domain LIKE '.%'
+           AND (l.domain = substr(p.domain, 2) OR l.domain LIKE '%' || p.domain))
+)";
+
+/// Add a priority domain, or update the reason of an existing one.
+pub async fn add_priority_domain(
+    pool: &SqlitePool,
+    domain: &str,
+    reason: &str,
+    created_by_user_id: Option<i64>,
+) -> Result<()> {
+    sqlx::query(
+        r"
+        INSERT INTO priority_domains (domain, reason, created_by_user_id)
+        VALUES (?, ?, ?)
+        ON CONFLICT(domain) DO UPDATE SET reason = excluded.reason
+        ",
+    )
+    .bind(domain)
+    .bind(reason)
+    .bind(created_by_user_id)
+    .execute(pool)
+    .await
+    .context("Failed to add priority domain")?;
+
+    Ok(())
+}
+
+/// Delete a priority domain.
+pub async fn delete_priority_domain(pool: &SqlitePool, domain: &str) -> Result<()> {
+    sqlx::query("DELETE FROM priority_domains WHERE domain = ?")
+        .bind(domain)
+        .execute(pool)
+        .await
+        .context("Failed to delete priority domain")?;
+
+    Ok(())
+}
+
+/// Get all priority domains.
+pub async fn get_priority_domains(pool: &SqlitePool) -> Result<Vec<PriorityDomain>> {
+    let domains = sqlx::query_as::<_, PriorityDomain>(
+        "SELECT id, domain, reason, created_at, created_by_user_id FROM priority_domains ORDER BY domain",
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to get priority domains")?;
+
+    Ok(domains)
+}
+
+// ============================================================================
+// Archive Version queries
+// ============================================================================
+
+use super::models::{ArchiveVersion, VersionedDomain};
+
+/// Whether re-archiving this archive should preserve its current capture.
+///
+/// True when the archive opted in itself or its link is on a versioned
+/// domain (matched like excluded domains).
+pub async fn archive_versioning_enabled(pool: &SqlitePool, archive_id: i64) -> Result<bool> {
+    let enabled: Option<bool> = sqlx::query_scalar(
+        r"
+        SELECT a.keep_versions != 0 OR EXISTS (
+            SELECT 1 FROM versioned_domains v
+            WHERE v.domain = l.domain
+               OR (v.domain LIKE '*.%'
+                   AND (l.domain = substr(v.domain, 3) OR l.domain LIKE '%.' || substr(v.domain, 3)))
+               OR (v.domain LIKE '.%'
+                   AND (l.domain = substr(v.domain, 2) OR l.domain LIKE '%' || v.domain))
+        )
+        FROM archives a
+        JOIN links l ON l.id = a.link_id
+        WHERE a.id = ?
+        ",
+    )
+    .bind(archive_id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to check archive versioning")?;
+
+    Ok(enabled.unwrap_or(false))
+}
+
+/// Whether the archive itself opted into versioning (ignoring domains).
+pub async fn get_archive_keep_versions(pool: &SqlitePool, archive_id: i64) -> Result<bool> {
+    let keep: Option<bool> =
+        sqlx::query_scalar("SELECT keep_versions != 0 FROM archives WHERE id = ?")
+            .bind(archive_id)
+            .fetch_optional(pool)
+            .await
+            .context("Failed to get archive keep_versions")?;
+
+    Ok(keep.unwrap_or(false))
+}
+
+/// Opt an archive in or out of versioning.
+pub async fn set_archive_keep_versions(
+    pool: &SqlitePool,
+    archive_id: i64,
+    keep_versions: bool,
+) -> Result<()> {
+    sqlx::query("UPDATE archives SET keep_versions = ? WHERE id = ?")
+        .bind(keep_versions)
+        .bind(archive_id)
+        .execute(pool)
+        .await
+        .context("Failed to set archive keep_versions")?;
+
+    Ok(())
+}
+
+/// The version number the next preserved capture of an archive will get.
+pub async fn next_archive_version(pool: &SqlitePool, archive_id: i64) -> Result<i64> {
+    let next: i64 = sqlx::query_scalar(
+        "SELECT COALESCE(MAX(version), 0) + 1 FROM archive_versions WHERE archive_id = ?",
+    )
+    .bind(archive_id)
+    .fetch_one(pool)
+    .await
+    .context("Failed to get next archive version")?;
+
+    Ok(next)
+}
+
+/// Record a preserved capture of an archive, returning the row ID.
+#[allow(clippy::too_many_arguments)]
+pub async fn insert_archive_version(
+    pool: &SqlitePool,
+    archive_id: i64,
+    version: i64,
+    archived_at: Option<&str>,
+    s3_prefix: &str,
+    s3_key_primary: Option<&str>,
+    content_title: Option<&str>,
+    content_text: Option<&str>,
+) -> Result<i64> {
+    let result = sqlx::query(
+        r"
+        INSERT INTO archive_versions
+            (archive_id, version, archived_at, s3_prefix, s3_key_primary, content_title, content_text)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        ",
+    )
+    .bind(archive_id)
+    .bind(version)
+    .bind(archived_at)
+    .bind(s3_prefix)
+    .bind(s3_key_primary)
+    .bind(content_title)
+    .bind(content_text)
+    .execute(pool)
+    .await
+    .context("Failed to insert archive version")?;
+
+    Ok(result.last_insert_rowid())
+}
+
+/// Preserved captures of an archive, newest first.
+pub async fn get_archive_versions(
+    pool: &SqlitePool,
+    archive_id: i64,
+) -> Result<Vec<ArchiveVersion>> {
+    let versions = sqlx::query_as::<_, ArchiveVersion>(
+        "SELECT * FROM archive_versions WHERE archive_id = ? ORDER BY version DESC",
+    )
+    .bind(archive_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to get archive versions")?;
+
+    Ok(versions)
+}
+
+/// Add a versioned domain, or update the reason of an existing one.
+pub async fn add_versioned_domain(
+    pool: &SqlitePool,
+    domain: &str,
+    reason: &str,
+    created_by_user_id: Option<i64>,
+) -> Result<()> {
+    sqlx::query(
+        r"
+        INSERT INTO versioned_domains (domain, reason, created_by_user_id)
+        VALUES (?, ?, ?)
+        ON CONFLICT(domain) DO UPDATE SET reason = excluded.reason
+        ",
+    )
+    .bind(domain)
+    .bind(reason)
+    .bind(created_by_user_id)
+    .execute(pool)
+    .await
+    .context("Failed to add versioned domain")?;
+
+    Ok(())
+}
+
+/// Delete a versioned domain.
+pub async fn delete_versioned_domain(pool: &SqlitePool, domain: &str) -> Result<()> {
+    sqlx::query("DELETE FROM versioned_domains WHERE domain = ?")
+        .bind(domain)
+        .execute(pool)
+        .await
+        .context("Failed to delete versioned domain")?;
+
+    Ok(())
+}
+
+/// Get all versioned domains.
+pub async fn get_versioned_domains(pool: &SqlitePool) -> Result<Vec<VersionedDomain>> {
+    let domains = sqlx::query_as::<_, VersionedDomain>(
+        "SELECT id, domain, reason, created_at, created_by_user_id FROM versioned_domains ORDER BY domain",
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to get versioned domains")?;
+
+    Ok(domains)
+}
+
+// ============================================================================
+// External Service Rule queries
+// ============================================================================
+
+use super::models::{ExternalServiceRule, ExternalServiceScope, ExternalServices};
+
+/// Create or replace the external service rule for a domain or content type.
+pub async fn set_external_service_rule(
+    pool: &SqlitePool,
+    scope: ExternalServiceScope,
+    pattern: &str,
+    wayback: Option<bool>,
+    archive_today: Option<bool>,
+    ipfs: Option<bool>,
+    created_by_user_id: Option<i64>,
+) -> Result<()> {
+    sqlx::query(
+        r"
+        INSERT INTO external_service_rules
+            (scope, pattern, wayback, archive_today, ipfs, created_by_user_id)
+        VALUES (?, ?, ?, ?, ?, ?)
+        ON CONFLICT(scope, pattern) DO UPDATE SET
+            wayback = excluded.wayback,
+            archive_today = excluded.archive_today,
+            ipfs = excluded.ipfs,
+            updated_at = CURRENT_TIMESTAMP
+        ",
+    )
+    .bind(scope.as_str())
+    .bind(pattern)
+    .bind(wayback)
+    .bind(archive_today)
+    .bind(ipfs)
+    .bind(created_by_user_id)
+    .execute(pool)
+    .await
+    .context("Failed to set external service rule")?;
+
+    Ok(())
+}
+
+/// Delete an external service rule. Returns the deleted rule, if it existed.
+pub async fn delete_external_service_rule(
+    pool: &SqlitePool,
+    id: i64,
+) -> Result<Option<ExternalServiceRule>> {
+    let rule = sqlx::query_as::<_, ExternalServiceRule>(
+        "DELETE FROM external_service_rules WHERE id = ? RETURNING id, scope, pattern, wayback, archive_today, ipfs, created_at, created_by_user_id, updated_at",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to delete external service rule")?;
+
+    Ok(rule)
+}
+
+/// Get all external service rules, domain rules first.
+pub async fn get_external_service_rules(pool: &SqlitePool) -> Result<Vec<ExternalServiceRule>> {
+    let rules = sqlx::query_as::<_, ExternalServiceRule>(
+        "SELECT id, scope, pattern, wayback, archive_today, ipfs, created_at, created_by_user_id, updated_at FROM external_service_rules ORDER BY scope DESC, pattern",
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to get external service rules")?;
+
+    Ok(rules)
+}
+
+/// Decide which external services an archive is sent to.
+///
+/// Each service is decided separately: the most specific domain rule that sets
+/// it wins (domain patterns match like excluded domains), then a rule for the
+/// archive's content type, then the global default.
+#[must_use]
+pub fn resolve_external_services(
+    rules: &[ExternalServiceRule],
+    domain: &str,
+    content_type: &str,
+    defaults: ExternalServices,
+) -> ExternalServices {
+    let candidates = excluded_domain_candidates(domain);
+    let mut domain_rules: Vec<(usize, &ExternalServiceRule)> = rules
+        .iter()
+        .filter(|r| r.scope == ExternalServiceScope::Domain.as_str())
+        .filter_map(|r| {
+            candidates
+                .iter()
+                .position(|c| *c == r.pattern.to_lowercase())
+                .map(|rank| (rank, r))
+        })
+        .collect();
+    domain_rules.sort_by_key(|(rank, _)| *rank);
+
+    let content_type_rule = rules.iter().find(|r| {
+        r.scope == ExternalServiceScope::ContentType.as_str()
+            && r.pattern.eq_ignore_ascii_case(content_type)
+    });
+
+    let matching: Vec<&ExternalServiceRule> = domain_rules
+        .into_iter()
+        .map(|(_, r)| r)
+        .chain(content_type_rule)
+        .collect();
+    let decide = |field: fn(&ExternalServiceRule) -> Option<bool>, default: bool| {
+        matching.iter().find_map(|r| field(r)).unwrap_or(default)
+    };
+
+    ExternalServices {
+        wayback: decide(|r| r.wayback, defaults.wayback),
+        archive_today: decide(|r| r.archive_today, defaults.archive_today),
+        ipfs: decide(|r| r.ipfs, defaults.ipfs),
+    }
+}
+
+/// Load the rules and decide which external services an archive is sent to.
+pub async fn external_services_for(
+    pool: &SqlitePool,
+    domain: &str,
+    content_type: &str,
+    defaults: ExternalServices,
+) -> Result<ExternalServices> {
+    let rules = get_external_service_rules(pool).await?;
+    Ok(resolve_external_services(
+        &rules,
+        domain,
+        content_type,
+        defaults,
+    ))
+}
+
+// ============================================================================
+// Content Watching queries
+// ============================================================================
+
+use super::models::{ContentVersion, WatchedLink};
+
+/// Start (or update) watching a link for content changes.
+pub async fn watch_link(
+    pool: &SqlitePool,
+    link_id: i64,
+    check_interval_hours: i64,
+    created_by_user_id: Option<i64>,
+) -> Result<()> {
+    sqlx::query(
+        r"
+        INSERT INTO watched_links (link_id, check_interval_hours, created_by_user_id)
+        VALUES (?, ?, ?)
+        ON CONFLICT(link_id) DO UPDATE SET
+            check_interval_hours = excluded.check_interval_hours
+        ",
+    )
+    .bind(link_id)
+    .bind(check_interval_hours)
+    .bind(created_by_user_id)
+    .execute(pool)
+    .await
+    .context("Failed to watch link")?;
+
+    Ok(())
+}
+
+/// Stop watching a link. Existing content versions are kept.
+pub async fn unwatch_link(pool: &SqlitePool, link_id: i64) -> Result<()> {
+    sqlx::query("DELETE FROM watched_links WHERE link_id = ?")
+        .bind(link_id)
+        .execute(pool)
+        .await
+        .context("Failed to unwatch link")?;
+
+    Ok(())
+}
+
+/// Get the watch settings for a link, if it is watched.
+pub async fn get_watched_link(pool: &SqlitePool, link_id: i64) -> Result<Option<WatchedLink>> {
+    sqlx::query_as("SELECT * FROM watched_links WHERE link_id = ?")
+        .bind(link_id)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to fetch watched link")
+}
+
+/// Get watched links whose next check is due (never-checked links first).
+pub async fn get_due_watched_links(pool: &SqlitePool, limit: i64) -> Result<Vec<WatchedLink>> {
+    sqlx::query_as(
+        r"
+        SELECT * FROM watched_links
+        WHERE next_check_at IS NULL OR next_check_at <= datetime('now')
+        ORDER BY next_check_at IS NOT NULL, next_check_at
+        LIMIT ?
+        ",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch due watched links")
+}
+
+/// Record a check and schedule the next one `check_interval_hours` from now.
+pub async fn mark_watched_link_checked(pool: &SqlitePool, link_id: i64) -> Result<()> {
+    sqlx::query(
+        r"
+        UPDATE watched_links
+        SET last_checked_at = datetime('now'),
+            next_check_at = datetime('now', '+' || check_interval_hours || ' hours')
+        WHERE link_id = ?
+        ",
+    )
+    .bind(link_id)
+    .execute(pool)
+    .await
+    .context("Failed to mark watched link checked")?;
+
+    Ok(())
+}
+
+/// Record a new content version for a link, returning its ID.
+pub async fn insert_content_version(
+    pool: &SqlitePool,
+    link_id: i64,
+    archive_id: Option<i64>,
+    previous_version_id: Option<i64>,
+    content_hash: &str,
+    content_text: &str,
+) -> Result<i64> {
+    let result = sqlx::query(
+        r"
+        INSERT INTO content_versions
+            (link_id, archive_id, previous_version_id, content_hash, content_text)
+        VALUES (?, ?, ?, ?, ?)
+        ",
+    )
+    .bind(link_id)
+    .bind(archive_id)
+    .bind(previous_version_id)
+    .bind(content_hash)
+    .bind(content_text)
+    .execute(pool)
+    .await
+    .context("Failed to insert content version")?;
+
+    Ok(result.last_insert_rowid())
+}
+
+/// Get the most recent content version for a link.
+pub async fn get_latest_content_version(
+    pool: &SqlitePool,
+    link_id: i64,
+) -> Result<Option<ContentVersion>> {
+    sqlx::query_as("SELECT * FROM content_versions WHERE link_id = ? ORDER BY id DESC LIMIT 1")
+        .bind(link_id)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to fetch latest content version")
+}
+
+/// Get all content versions for a link, newest first.
+pub async fn get_content_versions_for_link(
+    pool: &SqlitePool,
+    link_id: i64,
+) -> Result<Vec<ContentVersion>> {
+    sqlx::query_as("SELECT * FROM content_versions WHERE link_id = ? ORDER BY id DESC")
+        .bind(link_id)
+        .fetch_all(pool)
+        .await
+        .context("Failed to fetch content versions")
+}
+
+/// Get a content version by ID.
+pub async fn get_content_version(pool: &SqlitePool, id: i64) -> Result<Option<ContentVersion>> {
+    sqlx::query_as("SELECT * FROM content_versions WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to fetch content version")
+}
+
+// ============================================================================
+// Thread Archive Jobs queries
+// ============================================================================
+
+use super::models::NewThreadArchiveJob;
+
+/// Insert a new thread archive job.
+pub async fn insert_thread_archive_job(
+    pool: &SqlitePool,
+    job: &NewThreadArchiveJob,
+) -> Result<i64> {
+    let result = sqlx::query(
+        r"
+        INSERT INTO thread_archive_jobs (thread_url, rss_url, user_id)
+        VALUES (?, ?, ?)
+        ",
+    )
+    .bind(&job.thread_url)
+    .bind(&job.rss_url)
+    .bind(job.user_id)
+    .execute(pool)
+    .await
+    .context("Failed to insert thread archive job")?;
+
+    Ok(result.last_insert_rowid())
+}
+
+/// Get a thread archive job by ID.
+pub async fn get_thread_archive_job(
+    pool: &SqlitePool,
+    id: i64,
+) -> Result<Option<ThreadArchiveJob>> {
+    sqlx::query_as("SELECT * FROM thread_archive_jobs WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to fetch thread archive job")
+}
+
+/// Get pending thread archive jobs for processing.
+pub async fn get_pending_thread_archive_jobs(
+    pool: &SqlitePool,
+    limit: i64,
+) -> Result<Vec<ThreadArchiveJob>> {
+    sqlx::query_as(
+        r"
+        SELECT * FROM thread_archive_jobs
+        WHERE status = 'pending'
+        ORDER BY created_at ASC
+        LIMIT ?
+        ",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch pending thread archive jobs")
+}
+
+/// Set thread archive job to processing status.
+pub async fn set_thread_archive_job_processing(
+    pool: &SqlitePool,
+    id: i64,
+    total_posts: Option<i64>,
+) -> Result<()> {
+    sqlx::query(
         r"
         UPDATE thread_archive_jobs
         SET status = 'processing', started_at = datetime('now'), total_posts = ?
-        WHERE id = ?
+        WHERE id = ? AND status != 'cancelled'
         ",
     )
     .bind(total_posts)
@@ -4813,7 +6512,7 @@ pub async fn set_thread_archive_job_complete(pool: &SqlitePool, id: i64) -> Resu
         r"
         UPDATE thread_archive_jobs
         SET status = 'complete', completed_at = datetime('now')
-        WHERE id = ?
+        WHERE id = ? AND status != 'cancelled'
         ",
     )
     .bind(id)
@@ -4830,7 +6529,7 @@ pub async fn set_thread_archive_job_failed(pool: &SqlitePool, id: i64, error: &s
         r"
         UPDATE thread_archive_jobs
         SET status = 'failed', error_message = ?, completed_at = datetime('now')
-        WHERE id = ?
+        WHERE id = ? AND status != 'cancelled'
         ",
     )
     .bind(error)
@@ -4842,6 +6541,26 @@ pub async fn set_thread_archive_job_failed(pool: &SqlitePool, id: i64, error: &s
     Ok(())
 }
 
+/// Request cancellation of a pending or processing thread archive job.
+///
+/// The worker notices the status change between posts and stops, keeping the
+/// progress recorded so far. Returns `false` if the job had already finished.
+pub async fn set_thread_archive_job_cancelled(pool: &SqlitePool, id: i64) -> Result<bool> {
+    let result = sqlx::query(
+        r"
+        UPDATE thread_archive_jobs
+        SET status = 'cancelled', completed_at = datetime('now')
+        WHERE id = ? AND status IN ('pending', 'processing')
+        ",
+    )
+    .bind(id)
+    .execute(pool)
+    .await
+    .context("Failed to set thread archive job cancelled")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
 /// Check if a thread archive job exists for this URL recently (within last hour).
 pub async fn thread_archive_job_exists_recent(pool: &SqlitePool, thread_url: &str) -> Result<bool> {
     let result: Option<(i64,)> = sqlx::query_as(
@@ -4924,7 +6643,7 @@ pub async fn get_forum_link_by_forum_username(
     pool: &SqlitePool,
     forum_username: &str,
 ) -> Result<Option<ForumAccountLink>> {
-    sqlx::query_as("SELECT * FROM forum_account_links WHERE forum_username = ?")
+    sqlx::query_as("SELECT * FROM forum_account_links WHERE forum_username = ? COLLATE NOCASE")
         .bind(forum_username)
         .fetch_optional(pool)
         .await
@@ -5096,6 +6815,61 @@ pub async fn mark_og_extraction_attempted(pool: &SqlitePool, archive_id: i64) ->
     Ok(())
 }
 
+/// Archives whose OG extraction ran but found no title.
+const OG_NEEDS_REEXTRACTION_CONDITION: &str = "og_title IS NULL AND og_extraction_attempted = 1";
+
+/// Count archives whose OG extraction was attempted but produced no title.
+pub async fn count_archives_needing_og_reextraction(pool: &SqlitePool) -> Result<i64> {
+    let sql = format!("SELECT COUNT(*) FROM archives WHERE {OG_NEEDS_REEXTRACTION_CONDITION}");
+    let row: (i64,) = sqlx::query_as(&sql)
+        .fetch_one(pool)
+        .await
+        .context("Failed to count archives needing OG re-extraction")?;
+    Ok(row.0)
+}
+
+/// Clear the OG extraction flag for an archive so extraction runs again.
+///
+/// The inverse of [`mark_og_extraction_attempted`]. Existing metadata is kept
+/// until the next extraction overwrites it. Returns false if the archive
+/// doesn't exist.
+pub async fn reset_og_extraction(pool: &SqlitePool, archive_id: i64) -> Result<bool> {
+    let result = sqlx::query(
+        r"
+        UPDATE archives
+        SET og_extraction_attempted = 0,
+            og_extracted_at = NULL
+        WHERE id = ?
+        ",
+    )
+    .bind(archive_id)
+    .execute(pool)
+    .await
+    .context("Failed to reset OG extraction")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Clear the OG extraction flag on every archive whose extraction found no title.
+///
+/// Returns the number of archives reset.
+pub async fn reset_failed_og_extractions(pool: &SqlitePool) -> Result<u64> {
+    let sql = format!(
+        r"
+        UPDATE archives
+        SET og_extraction_attempted = 0,
+            og_extracted_at = NULL
+        WHERE {OG_NEEDS_REEXTRACTION_CONDITION}
+        "
+    );
+    let result = sqlx::query(&sql)
+        .execute(pool)
+        .await
+        .context("Failed to reset failed OG extractions")?;
+
+    Ok(result.rows_affected())
+}
+
 // ========== Subtitle Languages ==========
 
 /// Insert or update a subtitle language entry.
@@ -5225,10 +6999,423 @@ pub async fn get_subtitle_languages_for_archive(
     Ok(rows.into_iter().map(|sl| (sl.artifact_id, sl)).collect())
 }
 
+// ========== Playlist Items ==========
+
+/// Record that an archive is a member of a playlist archive.
+///
+/// If the membership already exists the position is updated, so re-processing
+/// a playlist keeps member ordering in sync with the source.
+pub async fn insert_playlist_item(
+    pool: &SqlitePool,
+    playlist_archive_id: i64,
+    member_archive_id: i64,
+    position: i64,
+) -> Result<()> {
+    sqlx::query(
+        r"
+        INSERT INTO playlist_items (playlist_archive_id, member_archive_id, position)
+        VALUES (?, ?, ?)
+        ON CONFLICT(playlist_archive_id, member_archive_id) DO UPDATE SET
+            position = excluded.position
+        ",
+    )
+    .bind(playlist_archive_id)
+    .bind(member_archive_id)
+    .bind(position)
+    .execute(pool)
+    .await
+    .context("Failed to insert playlist item")?;
+
+    Ok(())
+}
+
+/// Get the member archives of a playlist, ordered by playlist position.
+pub async fn get_playlist_members_display(
+    pool: &SqlitePool,
+    playlist_archive_id: i64,
+) -> Result<Vec<ArchiveDisplay>> {
+    sqlx::query_as(
+        r"
+        SELECT
+            a.id, a.link_id, a.status, a.archived_at,
+            a.content_title, a.content_author, a.content_type,
+            a.is_nsfw, a.error_message, a.retry_count,
+            l.original_url, l.domain,
+            COALESCE(SUM(aa.size_bytes), 0) as total_size_bytes
+        FROM playlist_items pi
+        JOIN archives a ON pi.member_archive_id = a.id
+        JOIN links l ON a.link_id = l.id
+        LEFT JOIN archive_artifacts aa ON a.id = aa.archive_id
+        WHERE pi.playlist_archive_id = ?
+        GROUP BY pi.position, pi.id, a.id, a.link_id, a.status, a.archived_at,
+                 a.content_title, a.content_author, a.content_type,
+                 a.is_nsfw, a.error_message, a.retry_count,
+                 l.original_url, l.domain
+        ORDER BY pi.position ASC, pi.id ASC
+        ",
+    )
+    .bind(playlist_archive_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch playlist members")
+}
+
+/// Count the member archives of a playlist.
+pub async fn count_playlist_members(pool: &SqlitePool, playlist_archive_id: i64) -> Result<i64> {
+    let row: (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM playlist_items WHERE playlist_archive_id = ?")
+            .bind(playlist_archive_id)
+            .fetch_one(pool)
+            .await
+            .context("Failed to count playlist members")?;
+
+    Ok(row.0)
+}
+
+// ============================================================================
+// Feed state queries
+// ============================================================================
+
+use super::models::FeedState;
+
+/// Get the polling state of a feed, or `None` if it has never been polled.
+pub async fn get_feed_state(pool: &SqlitePool, feed_url: &str) -> Result<Option<FeedState>> {
+    sqlx::query_as::<_, FeedState>("SELECT * FROM feed_state WHERE feed_url = ?")
+        .bind(feed_url)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to get feed state")
+}
+
+/// Record a completed poll of a feed.
+///
+/// `newest_post_id` only ever moves forward.
+pub async fn record_feed_poll(
+    pool: &SqlitePool,
+    feed_url: &str,
+    newest_post_id: Option<i64>,
+) -> Result<()> {
+    sqlx::query(
+        r"
+        INSERT INTO feed_state (feed_url, newest_post_id)
+        VALUES (?1, ?2)
+        ON CONFLICT(feed_url) DO UPDATE SET
+            newest_post_id = COALESCE(MAX(feed_state.newest_post_id, excluded.newest_post_id),
+                                      feed_state.newest_post_id, excluded.newest_post_id),
+            last_polled_at = CURRENT_TIMESTAMP
+        ",
+    )
+    .bind(feed_url)
+    .bind(newest_post_id)
+    .execute(pool)
+    .await
+    .context("Failed to record feed poll")?;
+
+    Ok(())
+}
+
+/// Record how far a backfill of a polled feed got.
+///
+/// `cursor` is the post ID the next poll resumes paging before, or `None`
+/// once the backfill reached the feed's oldest post, which marks it done.
+pub async fn record_feed_backfill(
+    pool: &SqlitePool,
+    feed_url: &str,
+    cursor: Option<i64>,
+) -> Result<()> {
+    sqlx::query(
+        r"
+        UPDATE feed_state
+        SET backfill_cursor = ?2,
+            backfilled_at = CASE WHEN ?2 IS NULL THEN CURRENT_TIMESTAMP END
+        WHERE feed_url = ?1
+        ",
+    )
+    .bind(feed_url)
+    .bind(cursor)
+    .execute(pool)
+    .await
+    .context("Failed to record feed backfill")?;
+
+    Ok(())
+}
+
+/// Highest post ID already ingested from the forum at `domain`.
+///
+/// Post GUIDs are `{domain}-post-{id}`. Used for feeds without state, which
+/// databases from before it was tracked have despite holding their posts.
+pub async fn get_newest_ingested_post_id(pool: &SqlitePool, domain: &str) -> Result<Option<i64>> {
+    let prefix = format!("{domain}-post-");
+    let row: (Option<i64>,) = sqlx::query_as(
+        r"
+        SELECT MAX(CAST(substr(guid, length(?1) + 1) AS INTEGER))
+        FROM posts
+        WHERE substr(guid, 1, length(?1)) = ?1
+        ",
+    )
+    .bind(&prefix)
+    .fetch_one(pool)
+    .await
+    .context("Failed to get newest ingested post")?;
+
+    Ok(row.0)
+}
+
+/// `EXPLAIN QUERY PLAN` detail lines for `sql`, with its parameters unbound.
+#[cfg(test)]
+pub async fn explain_query_plan(pool: &SqlitePool, sql: &str) -> Result<Vec<String>> {
+    let rows: Vec<(i64, i64, i64, String)> = sqlx::query_as(&format!("EXPLAIN QUERY PLAN {sql}"))
+        .fetch_all(pool)
+        .await
+        .context("Failed to explain query")?;
+    Ok(rows.into_iter().map(|(_, _, _, detail)| detail).collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn kinds(list: &[&str]) -> HashSet<String> {
+        list.iter().map(ToString::to_string).collect()
+    }
+
+    #[tokio::test]
+    async fn test_retry_picker_uses_index() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = crate::db::Database::new(&temp_dir.path().join("test.sqlite"))
+            .await
+            .unwrap();
+        let plan = explain_query_plan(db.pool(), &failed_archives_for_retry_sql())
+            .await
+            .unwrap();
+        assert!(
+            plan.iter()
+                .any(|step| step.starts_with("SEARCH a USING INDEX idx_archives_status_")),
+            "retry picker should search archives by status: {plan:?}"
+        );
+        assert!(
+            !plan.iter().any(|step| step.starts_with("SCAN a")),
+            "retry picker should not scan archives: {plan:?}"
+        );
+    }
+
+    #[test]
+    fn test_jittered_retry_delay_within_band() {
+        for retry_count in 0..4 {
+            let base = RETRY_BASE_DELAY_SECS << retry_count;
+            let min = (base * 3) / 4;
+            let max = (base * 5) / 4;
+
+            let delays: Vec<i64> = (0..50)
+                .map(|_| jittered_retry_delay_secs(retry_count, false))
+                .collect();
+            for delay in &delays {
+                assert!(
+                    (min..=max).contains(delay),
+                    "delay {delay} outside [{min}, {max}] for retry {retry_count}"
+                );
+            }
+
+            let first = delays[0];
+            assert!(
+                delays.iter().any(|d| *d != first),
+                "jittered delays should not all be identical"
+            );
+        }
+    }
+
+    #[test]
+    fn test_jittered_retry_delay_caps_exponent() {
+        let max = (RETRY_BASE_DELAY_SECS << RETRY_MAX_EXPONENT) * 5 / 4;
+        assert!(jittered_retry_delay_secs(1_000, false) <= max);
+        assert!(jittered_retry_delay_secs(-1, false) <= RETRY_BASE_DELAY_SECS * 5 / 4);
+
+        let priority_max = (RETRY_BASE_DELAY_SECS << PRIORITY_RETRY_MAX_EXPONENT) * 5 / 4;
+        assert!(jittered_retry_delay_secs(1_000, true) <= priority_max);
+        assert!(jittered_retry_delay_secs(2, true) >= (RETRY_BASE_DELAY_SECS << 2) * 3 / 4);
+    }
+
+    #[test]
+    fn test_retry_delay_respects_max_retries() {
+        assert!(retry_delay_secs(0, 3, false).is_some());
+        assert!(retry_delay_secs(1, 3, false).is_some());
+        // The third failure uses up all three attempts
+        assert_eq!(retry_delay_secs(2, 3, false), None);
+        assert_eq!(retry_delay_secs(0, 1, false), None);
+        assert_eq!(retry_delay_secs(0, 0, false), None);
+        assert!(retry_delay_secs(9, 20, false).is_some());
+        // Priority domains get the same number of attempts
+        assert_eq!(retry_delay_secs(2, 3, true), None);
+    }
+
+    #[test]
+    fn test_excluded_domain_candidates() {
+        assert_eq!(
+            excluded_domain_candidates("cdn.Example.com"),
+            vec![
+                "cdn.example.com",
+                "*.cdn.example.com",
+                ".cdn.example.com",
+                "*.example.com",
+                ".example.com",
+                "*.com",
+                ".com",
+            ]
+        );
+        assert_eq!(
+            excluded_domain_candidates("localhost"),
+            vec!["localhost", "*.localhost", ".localhost"]
+        );
+    }
+
+    #[test]
+    fn test_order_reply_thread() {
+        // Chain as returned from the tail: 40 → 30 → 10, with 30 quoting 20
+        let links = [(40, Some(30)), (30, Some(10)), (20, None), (10, None)];
+        assert_eq!(order_reply_thread(&links, 40), vec![10, 30, 40]);
+        assert_eq!(order_reply_thread(&links, 30), vec![10, 30]);
+        assert_eq!(order_reply_thread(&links, 99), Vec::<i64>::new());
+
+        // A parent outside the fetched chain ends the walk
+        assert_eq!(order_reply_thread(&[(2, Some(1))], 2), vec![2]);
+
+        // Cycles don't loop forever
+        let cyclic = [(1, Some(2)), (2, Some(1))];
+        assert_eq!(order_reply_thread(&cyclic, 1), vec![2, 1]);
+    }
+
+    #[test]
+    fn test_resolve_quote_only_policy_domain_override_wins() {
+        assert!(resolve_quote_only_policy(true, None));
+        assert!(!resolve_quote_only_policy(false, None));
+        assert!(resolve_quote_only_policy(false, Some(true)));
+        assert!(!resolve_quote_only_policy(true, Some(false)));
+    }
+
+    fn service_rule(
+        scope: ExternalServiceScope,
+        pattern: &str,
+        services: [Option<bool>; 3],
+    ) -> ExternalServiceRule {
+        let [wayback, archive_today, ipfs] = services;
+        ExternalServiceRule {
+            id: 0,
+            scope: scope.as_str().to_string(),
+            pattern: pattern.to_string(),
+            wayback,
+            archive_today,
+            ipfs,
+            created_at: String::new(),
+            created_by_user_id: None,
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_external_services_precedence() {
+        let defaults = ExternalServices {
+            wayback: true,
+            archive_today: false,
+            ipfs: true,
+        };
+        let rules = vec![
+            service_rule(
+                ExternalServiceScope::ContentType,
+                "video",
+                [Some(false), Some(true), None],
+            ),
+            service_rule(
+                ExternalServiceScope::Domain,
+                "*.example.com",
+                [Some(true), None, Some(false)],
+            ),
+            service_rule(
+                ExternalServiceScope::Domain,
+                "news.example.com",
+                [None, Some(false), None],
+            ),
+        ];
+
+        // No rule matches: global defaults
+        assert_eq!(
+            resolve_external_services(&rules, "other.org", "text", defaults),
+            defaults
+        );
+
+        // Content-type rule beats the global default; unset services fall through
+        assert_eq!(
+            resolve_external_services(&rules, "other.org", "VIDEO", defaults),
+            ExternalServices {
+                wayback: false,
+                archive_today: true,
+                ipfs: true,
+            }
+        );
+
+        // Domain rule beats the content-type rule
+        assert_eq!(
+            resolve_external_services(&rules, "cdn.example.com", "video", defaults),
+            ExternalServices {
+                wayback: true,
+                archive_today: true,
+                ipfs: false,
+            }
+        );
+
+        // The most specific domain rule wins, deferring to parents for unset services
+        assert_eq!(
+            resolve_external_services(&rules, "news.example.com", "video", defaults),
+            ExternalServices {
+                wayback: true,
+                archive_today: false,
+                ipfs: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_missing_artifact_kinds_youtube_video() {
+        let url = "https://www.youtube.com/watch?v=abc";
+        assert_eq!(
+            missing_artifact_kinds(Some("video"), url, &kinds(&["video", "thumb"])),
+            vec![
+                ArtifactKind::Subtitles,
+                ArtifactKind::Transcript,
+                ArtifactKind::Comments
+            ]
+        );
+        assert_eq!(
+            missing_artifact_kinds(
+                Some("video"),
+                url,
+                &kinds(&["video", "subtitles", "transcript"])
+            ),
+            vec![ArtifactKind::Comments]
+        );
+        assert!(missing_artifact_kinds(
+            Some("video"),
+            url,
+            &kinds(&["subtitles", "transcript", "comments"])
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_missing_artifact_kinds_non_comment_platform() {
+        let url = "https://vimeo.com/12345";
+        assert_eq!(
+            missing_artifact_kinds(Some("video"), url, &kinds(&["subtitles"])),
+            vec![ArtifactKind::Transcript]
+        );
+    }
+
+    #[test]
+    fn test_missing_artifact_kinds_ignores_non_video() {
+        let url = "https://www.youtube.com/playlist?list=PL123";
+        assert!(missing_artifact_kinds(Some("playlist"), url, &kinds(&[])).is_empty());
+        assert!(missing_artifact_kinds(None, "https://example.com", &kinds(&[])).is_empty());
+    }
+
     #[test]
     fn test_extract_topic_id_from_thread_key() {
         // Valid thread keys with numeric topic IDs
@@ -5277,6 +7464,47 @@ mod tests {
         assert_eq!(thread_key_from_url("not-a-url"), "not-a-url");
     }
 
+    #[test]
+    fn test_forum_author_handle() {
+        assert_eq!(forum_author_handle("@alice"), Some("alice"));
+        assert_eq!(forum_author_handle("@alice Alice Smith"), Some("alice"));
+        assert_eq!(forum_author_handle("  bob "), Some("bob"));
+        assert_eq!(forum_author_handle("@"), None);
+        assert_eq!(forum_author_handle(""), None);
+    }
+
+    #[test]
+    fn test_normalize_forum_author_shapes() {
+        let cases = [
+            ("Jane Doe (@jane)", "jane"),
+            ("@jane Jane Doe", "jane"),
+            ("Jane Doe [@jane.doe]", "jane.doe"),
+            ("<@jane_d>", "jane_d"),
+            ("@jane-d,", "jane-d"),
+            ("jane", "jane"),
+        ];
+        for (raw, handle) in cases {
+            assert_eq!(normalize_forum_author(raw, None), Some(handle), "{raw}");
+        }
+
+        // A configured pattern wins for authors it matches
+        let pattern = Regex::new(r"^(?P<handle>\S+) \(staff\)$").unwrap();
+        assert_eq!(
+            normalize_forum_author("moderator (staff)", Some(&pattern)),
+            Some("moderator")
+        );
+        assert_eq!(
+            normalize_forum_author("Jane Doe (@jane)", Some(&pattern)),
+            Some("jane")
+        );
+        // Unnamed groups work too
+        let pattern = Regex::new(r"by (\w+)$").unwrap();
+        assert_eq!(
+            normalize_forum_author("Posted by jane", Some(&pattern)),
+            Some("jane")
+        );
+    }
+
     #[test]
     fn test_build_post_url_patterns_discourse() {
         // Standard Discourse thread with numeric topic ID
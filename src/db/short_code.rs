@@ -0,0 +1,50 @@
+//! Short codes for archive permalinks.
+//!
+//! Each archive gets a random base62 code when it is created, served at
+//! `/a/<code>`. Unlike the numeric ID, the code doesn't depend on insertion
+//! order, so shared links keep working if archives are re-imported.
+
+use rand::Rng;
+
+/// Length of generated short codes. 62^8 (about 2 * 10^14) possible codes
+/// keeps collisions rare; inserts retry on the unique index when one happens.
+pub const SHORT_CODE_LEN: usize = 8;
+
+const ALPHABET: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Generate a random short code.
+#[must_use]
+pub fn generate_short_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..SHORT_CODE_LEN)
+        .map(|_| char::from(ALPHABET[rng.gen_range(0..ALPHABET.len())]))
+        .collect()
+}
+
+/// Whether `code` could be a generated short code.
+#[must_use]
+pub fn is_valid_short_code(code: &str) -> bool {
+    code.len() == SHORT_CODE_LEN && code.bytes().all(|b| b.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_generate_short_code() {
+        let codes: HashSet<String> = (0..10_000).map(|_| generate_short_code()).collect();
+        assert_eq!(codes.len(), 10_000);
+        assert!(codes.iter().all(|c| is_valid_short_code(c)));
+    }
+
+    #[test]
+    fn test_is_valid_short_code() {
+        assert!(is_valid_short_code("aZ09bY18"));
+        assert!(!is_valid_short_code("aZ09bY1"));
+        assert!(!is_valid_short_code("aZ09bY18x"));
+        assert!(!is_valid_short_code("aZ09-Y18"));
+        assert!(!is_valid_short_code(""));
+    }
+}
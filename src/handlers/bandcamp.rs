@@ -0,0 +1,123 @@
+use std::path::Path;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use regex::Regex;
+
+use super::traits::{ArchiveResult, SiteHandler};
+use crate::archiver::{ytdlp, CookieOptions};
+
+static PATTERNS: std::sync::LazyLock<Vec<Regex>> = std::sync::LazyLock::new(|| {
+    vec![
+        // Track pages live on per-artist subdomains: <artist>.bandcamp.com/track/<slug>
+        Regex::new(r"(?i)^https?://([a-z0-9-]+)\.bandcamp\.com/track/([A-Za-z0-9_-]+)/?([?#].*)?$")
+            .unwrap(),
+    ]
+});
+
+/// Bandcamp subdomains that are not artist pages.
+const RESERVED_SUBDOMAINS: &[&str] = &["daily", "www"];
+
+pub struct BandcampHandler;
+
+impl BandcampHandler {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for BandcampHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SiteHandler for BandcampHandler {
+    fn site_id(&self) -> &'static str {
+        "bandcamp"
+    }
+
+    fn url_patterns(&self) -> &[Regex] {
+        &PATTERNS
+    }
+
+    fn can_handle(&self, url: &str) -> bool {
+        PATTERNS[0].captures(url).is_some_and(|caps| {
+            !RESERVED_SUBDOMAINS.contains(&caps[1].to_ascii_lowercase().as_str())
+        })
+    }
+
+    fn priority(&self) -> i32 {
+        100
+    }
+
+    fn normalize_url(&self, url: &str) -> String {
+        // Rebuild from the artist subdomain and track slug, dropping query and fragment
+        match PATTERNS[0].captures(url) {
+            Some(caps) => format!(
+                "https://{}.bandcamp.com/track/{}",
+                caps[1].to_ascii_lowercase(),
+                &caps[2]
+            ),
+            None => url.to_string(),
+        }
+    }
+
+    async fn archive(
+        &self,
+        url: &str,
+        work_dir: &Path,
+        cookies: &CookieOptions<'_>,
+        config: &crate::config::Config,
+    ) -> Result<ArchiveResult> {
+        ytdlp::download_audio(url, work_dir, cookies, config).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_can_handle() {
+        let handler = BandcampHandler::new();
+
+        assert!(handler.can_handle("https://artist.bandcamp.com/track/song-title"));
+        assert!(handler.can_handle("http://some-band.bandcamp.com/track/song-title/"));
+        assert!(handler.can_handle("https://Artist.Bandcamp.com/track/song?from=embed"));
+
+        assert!(!handler.can_handle("https://artist.bandcamp.com/album/record"));
+        assert!(!handler.can_handle("https://artist.bandcamp.com/"));
+        assert!(!handler.can_handle("https://bandcamp.com/track/song-title"));
+        assert!(!handler.can_handle("https://daily.bandcamp.com/track/feature"));
+        assert!(!handler.can_handle("https://artist.bandcamp.com.evil.com/track/x"));
+    }
+
+    #[test]
+    fn test_site_id() {
+        let handler = BandcampHandler::new();
+        assert_eq!(handler.site_id(), "bandcamp");
+    }
+
+    #[test]
+    fn test_priority() {
+        let handler = BandcampHandler::new();
+        assert_eq!(handler.priority(), 100);
+    }
+
+    #[test]
+    fn test_normalize_url() {
+        let handler = BandcampHandler::new();
+
+        assert_eq!(
+            handler.normalize_url("http://artist.bandcamp.com/track/song-title/"),
+            "https://artist.bandcamp.com/track/song-title"
+        );
+        assert_eq!(
+            handler.normalize_url("https://Some-Band.Bandcamp.com/track/song?from=embed#lyrics"),
+            "https://some-band.bandcamp.com/track/song"
+        );
+    }
+}
@@ -6,7 +6,7 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 use super::traits::{ArchiveResult, SiteHandler};
-use crate::archiver::CookieOptions;
+use crate::archiver::{ytdlp, CookieOptions};
 use crate::constants::ARCHIVAL_USER_AGENT;
 
 static PATTERNS: std::sync::LazyLock<Vec<Regex>> = std::sync::LazyLock::new(|| {
@@ -61,6 +61,10 @@ struct PostThreadResponse {
 #[derive(Debug, Deserialize)]
 struct ThreadPost {
     post: Post,
+    /// `#threadViewPost` for a visible parent, `#notFoundPost`/`#blockedPost`
+    /// otherwise; absent for top-level posts.
+    #[serde(default)]
+    parent: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -96,13 +100,11 @@ enum Embed {
     #[serde(rename = "app.bsky.embed.external#view")]
     External { external: ExternalEmbed },
     #[serde(rename = "app.bsky.embed.record#view")]
-    Record {
-        #[allow(dead_code)]
-        record: serde_json::Value,
-    },
+    Record { record: serde_json::Value },
     #[serde(rename = "app.bsky.embed.recordWithMedia#view")]
     RecordWithMedia {
-        #[allow(dead_code)]
+        /// An `app.bsky.embed.record#view` wrapping the quoted post.
+        record: serde_json::Value,
         media: serde_json::Value,
     },
     #[serde(rename = "app.bsky.embed.video#view")]
@@ -146,6 +148,10 @@ struct BlueskyMetadata {
     embed_type: Option<String>,
     image_urls: Vec<String>,
     external_url: Option<String>,
+    /// Post this one replies to; archived separately and linked as reply-to.
+    reply_parent_url: Option<String>,
+    /// Post quoted by this one; archived separately and linked as quoted.
+    quoted_post_url: Option<String>,
 }
 
 impl BlueskyHandler {
@@ -158,8 +164,12 @@ impl BlueskyHandler {
         })
     }
 
-    /// Resolve a handle to a DID
+    /// Resolve a handle to a DID. Profile URLs may already carry the DID.
     async fn resolve_handle(&self, handle: &str) -> Result<String> {
+        if handle.starts_with("did:") {
+            return Ok(handle.to_string());
+        }
+
         let url = format!("{BSKY_API_BASE}/com.atproto.identity.resolveHandle?handle={handle}");
 
         let response: ResolveHandleResponse = self
@@ -177,11 +187,11 @@ impl BlueskyHandler {
         Ok(response.did)
     }
 
-    /// Fetch a post thread by AT URI
-    async fn get_post(&self, did: &str, post_id: &str) -> Result<Post> {
+    /// Fetch a post and its immediate parent by AT URI
+    async fn get_post_thread(&self, did: &str, post_id: &str) -> Result<ThreadPost> {
         let at_uri = format!("at://{did}/app.bsky.feed.post/{post_id}");
         let url = format!(
-            "{}/app.bsky.feed.getPostThread?uri={}",
+            "{}/app.bsky.feed.getPostThread?uri={}&depth=0&parentHeight=1",
             BSKY_API_BASE,
             urlencoding::encode(&at_uri)
         );
@@ -198,7 +208,7 @@ impl BlueskyHandler {
             .await
             .context("Failed to parse post response")?;
 
-        Ok(response.thread.post)
+        Ok(response.thread)
     }
 
     /// Download an image from Bluesky CDN
@@ -255,16 +265,28 @@ impl SiteHandler for BlueskyHandler {
     }
 
     fn normalize_url(&self, url: &str) -> String {
-        // Normalize to bsky.app format
-        url.replace("bsky.social", "bsky.app")
+        // Normalize to bsky.app format (host only; handles may end in bsky.social)
+        let url = url.replacen("://bsky.social/", "://bsky.app/", 1);
+        let Some(caps) = URL_PARSER.captures(&url) else {
+            return url;
+        };
+
+        // Handles are case-insensitive; DIDs are kept verbatim
+        let actor = caps[2].trim_start_matches('@');
+        let actor = if actor.starts_with("did:") {
+            actor.to_string()
+        } else {
+            actor.to_lowercase()
+        };
+        format!("https://bsky.app/profile/{actor}/post/{}", &caps[3])
     }
 
     async fn archive(
         &self,
         url: &str,
         work_dir: &Path,
-        _cookies: &CookieOptions<'_>,
-        _config: &crate::config::Config,
+        cookies: &CookieOptions<'_>,
+        config: &crate::config::Config,
     ) -> Result<ArchiveResult> {
         // Parse URL to get handle and post ID
         let (handle, post_id) = Self::parse_url(url).context("Invalid Bluesky URL format")?;
@@ -272,8 +294,11 @@ impl SiteHandler for BlueskyHandler {
         // Resolve handle to DID
         let did = self.resolve_handle(&handle).await?;
 
-        // Fetch the post
-        let post = self.get_post(&did, &post_id).await?;
+        // Fetch the post along with its parent
+        let thread = self.get_post_thread(&did, &post_id).await?;
+        let reply_parent_url = thread.parent.as_ref().and_then(parent_post_url);
+        let quoted_post_url = thread.post.embed.as_ref().and_then(quoted_post_url);
+        let post = thread.post;
 
         // Prepare result
         let mut result = ArchiveResult {
@@ -292,8 +317,16 @@ impl SiteHandler for BlueskyHandler {
         let mut embed_type = None;
         let mut external_url = None;
 
+        // Quote posts with media carry the images/video in a nested embed
+        let media_embed = match &post.embed {
+            Some(Embed::RecordWithMedia { media, .. }) => {
+                serde_json::from_value::<Embed>(media.clone()).ok()
+            }
+            _ => None,
+        };
+
         // Process embed if present
-        if let Some(embed) = &post.embed {
+        if let Some(embed) = media_embed.as_ref().or(post.embed.as_ref()) {
             match embed {
                 Embed::Images { images } => {
                     result.content_type = "image".to_string();
@@ -337,6 +370,19 @@ impl SiteHandler for BlueskyHandler {
                             result.thumbnail = Some(thumb_filename);
                         }
                     }
+
+                    // The video itself is an HLS playlist; let yt-dlp fetch it
+                    match ytdlp::download(url, work_dir, cookies, config, None, None, true).await {
+                        Ok(video_result) => {
+                            result.primary_file = video_result.primary_file;
+                            if result.thumbnail.is_none() {
+                                result.thumbnail = video_result.thumbnail;
+                            }
+                        }
+                        Err(e) => {
+                            tracing::warn!("Failed to download Bluesky video: {e:#}");
+                        }
+                    }
                 }
                 Embed::External { external } => {
                     embed_type = Some("external".to_string());
@@ -356,6 +402,9 @@ impl SiteHandler for BlueskyHandler {
                 Embed::Unknown => {}
             }
         }
+        if media_embed.is_some() {
+            embed_type = Some("quote".to_string());
+        }
 
         // Create metadata JSON
         let metadata = BlueskyMetadata {
@@ -369,6 +418,8 @@ impl SiteHandler for BlueskyHandler {
             embed_type,
             image_urls,
             external_url,
+            reply_parent_url,
+            quoted_post_url,
         };
 
         let metadata_json =
@@ -387,6 +438,38 @@ impl SiteHandler for BlueskyHandler {
     }
 }
 
+/// Build a bsky.app URL for a post view (`uri` plus `author.handle`).
+fn post_view_url(view: &serde_json::Value) -> Option<String> {
+    let uri = view.get("uri")?.as_str()?;
+    let (_, rkey) = uri.split_once("/app.bsky.feed.post/")?;
+    let handle = view.pointer("/author/handle")?.as_str()?;
+    Some(format!(
+        "https://bsky.app/profile/{}/post/{rkey}",
+        handle.to_lowercase()
+    ))
+}
+
+/// URL of the parent post from a `getPostThread` `parent` node, if visible.
+fn parent_post_url(parent: &serde_json::Value) -> Option<String> {
+    if parent.get("$type")?.as_str()? != "app.bsky.feed.defs#threadViewPost" {
+        return None;
+    }
+    post_view_url(parent.get("post")?)
+}
+
+/// URL of the quoted post in a record or record-with-media embed, if visible.
+fn quoted_post_url(embed: &Embed) -> Option<String> {
+    let record = match embed {
+        Embed::Record { record } => record,
+        Embed::RecordWithMedia { record, .. } => record.get("record")?,
+        _ => return None,
+    };
+    if record.get("$type")?.as_str()? != "app.bsky.embed.record#viewRecord" {
+        return None;
+    }
+    post_view_url(record)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -431,6 +514,90 @@ mod tests {
             handler.normalize_url("https://bsky.app/profile/alice/post/123"),
             "https://bsky.app/profile/alice/post/123"
         );
+
+        // Handles are lowercased, query strings and trailing slashes dropped
+        assert_eq!(
+            handler.normalize_url("https://bsky.app/profile/Alice.BSKY.social/post/3abc/?ref=x"),
+            "https://bsky.app/profile/alice.bsky.social/post/3abc"
+        );
+
+        // DIDs are kept verbatim
+        assert_eq!(
+            handler.normalize_url("https://bsky.app/profile/did:plc:abc123xyz/post/3abc"),
+            "https://bsky.app/profile/did:plc:abc123xyz/post/3abc"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_handle_passes_dids_through() {
+        let handler = BlueskyHandler::new();
+        assert_eq!(
+            handler.resolve_handle("did:plc:abc123xyz").await.unwrap(),
+            "did:plc:abc123xyz"
+        );
+    }
+
+    #[test]
+    fn test_thread_context_urls() {
+        let response: PostThreadResponse = serde_json::from_value(serde_json::json!({
+            "thread": {
+                "$type": "app.bsky.feed.defs#threadViewPost",
+                "post": {
+                    "uri": "at://did:plc:bob/app.bsky.feed.post/3child",
+                    "cid": "cid1",
+                    "author": {"did": "did:plc:bob", "handle": "bob.dev"},
+                    "record": {"text": "reply", "createdAt": "2024-01-01T00:00:00Z"},
+                    "embed": {
+                        "$type": "app.bsky.embed.recordWithMedia#view",
+                        "record": {
+                            "record": {
+                                "$type": "app.bsky.embed.record#viewRecord",
+                                "uri": "at://did:plc:carol/app.bsky.feed.post/3quoted",
+                                "author": {"did": "did:plc:carol", "handle": "Carol.example.com"}
+                            }
+                        },
+                        "media": {"$type": "app.bsky.embed.images#view", "images": []}
+                    }
+                },
+                "parent": {
+                    "$type": "app.bsky.feed.defs#threadViewPost",
+                    "post": {
+                        "uri": "at://did:plc:alice/app.bsky.feed.post/3parent",
+                        "author": {"did": "did:plc:alice", "handle": "alice.bsky.social"}
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            response.thread.parent.as_ref().and_then(parent_post_url),
+            Some("https://bsky.app/profile/alice.bsky.social/post/3parent".to_string())
+        );
+        assert_eq!(
+            response
+                .thread
+                .post
+                .embed
+                .as_ref()
+                .and_then(quoted_post_url),
+            Some("https://bsky.app/profile/carol.example.com/post/3quoted".to_string())
+        );
+
+        // Deleted or blocked parents and quotes aren't followed
+        let missing = serde_json::json!({
+            "$type": "app.bsky.feed.defs#notFoundPost",
+            "uri": "at://did:plc:alice/app.bsky.feed.post/3gone",
+            "notFound": true
+        });
+        assert_eq!(parent_post_url(&missing), None);
+        let blocked = Embed::Record {
+            record: serde_json::json!({
+                "$type": "app.bsky.embed.record#viewBlocked",
+                "uri": "at://did:plc:carol/app.bsky.feed.post/3quoted"
+            }),
+        };
+        assert_eq!(quoted_post_url(&blocked), None);
     }
 
     #[test]
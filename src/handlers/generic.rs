@@ -1,13 +1,40 @@
 use std::path::Path;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use regex::Regex;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use scraper::{Html, Selector};
 
+use super::pdf::{extract_pdf_metadata, is_pdf_content_type, is_pdf_url, pdf_filename};
+use super::reader::{extract_article, ARTICLE_FILE};
 use super::traits::{ArchiveResult, SiteHandler};
-use crate::archiver::CookieOptions;
-use crate::constants::ARCHIVAL_USER_AGENT;
+use crate::archiver::size_cap::{
+    check_reported_size, header_content_length, read_body_capped, too_large, SizeCheck,
+};
+use crate::archiver::{ArchiveError, CookieOptions};
+use crate::config::Config;
+
+/// Error text used when a page opts out of archiving and `RESPECT_NOARCHIVE` is set.
+pub const NOARCHIVE_REQUESTED: &str = "noarchive requested";
+
+/// Error text used when a fetched page looks like a soft 404 or paywall.
+pub const SOFT_UNAVAILABLE: &str = "soft_unavailable";
+
+/// Below this much visible text, body text is checked for unavailable markers.
+const SOFT_UNAVAILABLE_SHORT_TEXT: usize = 500;
+
+/// Paywall class markers only count when the visible text is a teaser shorter than this.
+const PAYWALL_TEXT_LIMIT: usize = 2000;
+
+/// Robots directives that take a value after a colon rather than naming a user agent.
+const VALUED_ROBOTS_DIRECTIVES: &[&str] = &[
+    "max-image-preview",
+    "max-snippet",
+    "max-video-preview",
+    "unavailable_after",
+];
 
 static PATTERNS: std::sync::LazyLock<Vec<Regex>> = std::sync::LazyLock::new(|| {
     vec![
@@ -16,6 +43,37 @@ static PATTERNS: std::sync::LazyLock<Vec<Regex>> = std::sync::LazyLock::new(|| {
     ]
 });
 
+/// Build an HTTP client for generic page fetches.
+///
+/// Every request carries the configured archival user agent and extra
+/// headers, including requests made while following redirects.
+pub fn archival_client(config: &Config, timeout: Duration) -> Result<reqwest::Client> {
+    archival_client_builder(config, timeout)?
+        .build()
+        .context("Failed to build HTTP client")
+}
+
+/// Client builder with the archival user agent, extra headers and timeout set,
+/// for callers that need to adjust other options (e.g. the redirect policy).
+pub fn archival_client_builder(
+    config: &Config,
+    timeout: Duration,
+) -> Result<reqwest::ClientBuilder> {
+    let mut headers = HeaderMap::new();
+    for (name, value) in &config.archival_extra_headers {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .with_context(|| format!("Invalid header name '{name}'"))?;
+        let value =
+            HeaderValue::from_str(value).with_context(|| format!("Invalid value for {name}"))?;
+        headers.insert(name, value);
+    }
+
+    Ok(reqwest::Client::builder()
+        .user_agent(config.archival_user_agent.as_str())
+        .default_headers(headers)
+        .timeout(timeout))
+}
+
 pub struct GenericHandler;
 
 impl GenericHandler {
@@ -50,22 +108,49 @@ impl SiteHandler for GenericHandler {
         url: &str,
         work_dir: &Path,
         _cookies: &CookieOptions<'_>,
-        _config: &crate::config::Config,
+        config: &crate::config::Config,
     ) -> Result<ArchiveResult> {
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .context("Failed to build HTTP client")?;
+        let client = archival_client(config, Duration::from_secs(30))?;
+
+        // Direct document links are stored as-is instead of being rendered. The
+        // HEAD probe also lets oversized downloads be skipped before fetching.
+        let cap = config.max_artifact_bytes;
+        let head = if is_pdf_url(url) && cap == 0 {
+            HeadInfo::default()
+        } else {
+            head_probe(&client, url).await
+        };
+        if let SizeCheck::TooLarge(size) = check_reported_size(head.content_length, cap) {
+            return Err(too_large(size, cap));
+        }
+        let pdf_hint = is_pdf_url(url) || head.is_pdf;
 
         let response = client
             .get(url)
-            .header("User-Agent", ARCHIVAL_USER_AGENT)
             .send()
             .await
             .context("Failed to fetch URL")?;
 
-        if !response.status().is_success() {
-            anyhow::bail!("HTTP request failed with status {}", response.status());
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ArchiveError::from_status(
+                status,
+                format!("HTTP request failed with status {status}"),
+            )
+            .into());
+        }
+
+        // Servers that don't answer HEAD still report a length on the GET
+        let reported = header_content_length(response.headers());
+        if let SizeCheck::TooLarge(size) = check_reported_size(reported, cap) {
+            return Err(too_large(size, cap));
+        }
+
+        if config.respect_noarchive && robots_header_requests_noarchive(response.headers()) {
+            return Err(ArchiveError::Unsupported(format!(
+                "{NOARCHIVE_REQUESTED} (X-Robots-Tag header)"
+            ))
+            .into());
         }
 
         let content_type = response
@@ -74,19 +159,13 @@ impl SiteHandler for GenericHandler {
             .and_then(|v| v.to_str().ok())
             .unwrap_or("text/html");
 
-        // Handle PDF files - download directly without processing
-        if content_type.contains("application/pdf") {
-            let pdf_bytes = response.bytes().await.context("Failed to read PDF data")?;
-
-            // Extract filename from URL for title
-            let filename = url
-                .split('/')
-                .next_back()
-                .unwrap_or("document.pdf")
-                .split('?')
-                .next()
-                .unwrap_or("document.pdf")
-                .to_string();
+        // Handle PDF files - download directly without processing. A `.pdf` path or
+        // HEAD hint is trusted unless the server actually answers with HTML.
+        if is_pdf_content_type(content_type) || (pdf_hint && !content_type.contains("text/html")) {
+            let pdf_bytes = read_body_capped(response, cap)
+                .await
+                .context("Failed to read PDF data")?;
+            let filename = pdf_filename(url);
 
             // Save PDF file
             let pdf_path = work_dir.join(&filename);
@@ -94,9 +173,12 @@ impl SiteHandler for GenericHandler {
                 .await
                 .context("Failed to write PDF file")?;
 
+            let metadata = extract_pdf_metadata(&pdf_bytes);
+
             return Ok(ArchiveResult {
-                title: Some(filename.clone()),
-                content_type: "pdf".to_string(),
+                title: metadata.title.or_else(|| Some(filename.clone())),
+                author: metadata.author,
+                content_type: "document".to_string(),
                 primary_file: Some(filename),
                 ..Default::default()
             });
@@ -110,10 +192,31 @@ impl SiteHandler for GenericHandler {
             });
         }
 
-        let body = response
-            .text()
-            .await
-            .context("Failed to read response body")?;
+        // Without a cap, let reqwest decode the page's declared charset
+        let body = if cap == 0 {
+            response
+                .text()
+                .await
+                .context("Failed to read response body")?
+        } else {
+            String::from_utf8_lossy(&read_body_capped(response, cap).await?).into_owned()
+        };
+
+        if config.respect_noarchive && meta_requests_noarchive(&Html::parse_document(&body)) {
+            return Err(ArchiveError::Unsupported(format!(
+                "{NOARCHIVE_REQUESTED} (robots meta tag)"
+            ))
+            .into());
+        }
+
+        // A 200 "content unavailable" or paywall page is not worth storing
+        if let Some(reason) = detect_soft_unavailable(
+            &Html::parse_document(&body),
+            &config.soft_unavailable_markers,
+            &config.soft_unavailable_paywall_classes,
+        ) {
+            return Err(ArchiveError::NotFound(format!("{SOFT_UNAVAILABLE}: {reason}")).into());
+        }
 
         // Save raw HTML
         let html_path = work_dir.join("raw.html");
@@ -122,18 +225,188 @@ impl SiteHandler for GenericHandler {
             .context("Failed to write HTML file")?;
 
         // Extract metadata
-        let (title, text) = extract_metadata(&body);
+        let (title, mut text) = extract_metadata(&body);
+        let mut author = None;
+        let mut extra_files = Vec::new();
+
+        // Pages with a substantial article index its full text instead of a summary
+        if config.reader_mode {
+            if let Some(article) = extract_article(&body) {
+                tokio::fs::write(work_dir.join(ARTICLE_FILE), article.to_document())
+                    .await
+                    .context("Failed to write article file")?;
+                extra_files.push(ARTICLE_FILE.to_string());
+                text = Some(article.text);
+                author = article.byline;
+            }
+        }
 
         Ok(ArchiveResult {
             title,
+            author,
             content_type: "text".to_string(),
             text,
             primary_file: Some("raw.html".to_string()),
+            extra_files,
             ..Default::default()
         })
     }
 }
 
+/// What a HEAD request reports about a URL.
+#[derive(Debug, Default)]
+struct HeadInfo {
+    is_pdf: bool,
+    content_length: Option<u64>,
+}
+
+/// Probe a URL with HEAD. Failures report nothing.
+async fn head_probe(client: &reqwest::Client, url: &str) -> HeadInfo {
+    match client.head(url).send().await {
+        Ok(response) if response.status().is_success() => HeadInfo {
+            is_pdf: response
+                .headers()
+                .get("content-type")
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(is_pdf_content_type),
+            content_length: header_content_length(response.headers()),
+        },
+        _ => HeadInfo::default(),
+    }
+}
+
+/// Whether any `X-Robots-Tag` header carries a `noarchive` directive for all crawlers.
+///
+/// Directives scoped to a named bot (e.g. `googlebot: noarchive`) are not
+/// addressed to us and are ignored.
+fn robots_header_requests_noarchive(headers: &reqwest::header::HeaderMap) -> bool {
+    headers
+        .get_all("x-robots-tag")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .any(robots_value_requests_noarchive)
+}
+
+/// Parse one `X-Robots-Tag` value, tracking which user agent each directive targets.
+fn robots_value_requests_noarchive(value: &str) -> bool {
+    let mut agent: Option<String> = None;
+    for part in value.split(',') {
+        let mut directive = part.trim().to_ascii_lowercase();
+        if let Some((prefix, rest)) = directive.split_once(':') {
+            let prefix = prefix.trim();
+            if !VALUED_ROBOTS_DIRECTIVES.contains(&prefix) {
+                agent = Some(prefix.to_string());
+                directive = rest.trim().to_string();
+            }
+        }
+        let applies = agent.as_deref().is_none_or(|a| a == "*");
+        if applies && directive == "noarchive" {
+            return true;
+        }
+    }
+    false
+}
+
+/// Whether a `<meta name="robots">` tag in the page asks not to be archived.
+fn meta_requests_noarchive(document: &Html) -> bool {
+    let Ok(selector) = Selector::parse("meta[name]") else {
+        return false;
+    };
+    document
+        .select(&selector)
+        .filter(|e| {
+            e.value()
+                .attr("name")
+                .is_some_and(|n| n.eq_ignore_ascii_case("robots"))
+        })
+        .filter_map(|e| e.value().attr("content"))
+        .any(|content| {
+            content
+                .split(',')
+                .any(|d| d.trim().eq_ignore_ascii_case("noarchive"))
+        })
+}
+
+/// Check a successfully fetched page for signs that the real content is missing.
+///
+/// Returns a short reason when:
+/// - the page title contains one of `markers` (e.g. "Page not found"),
+/// - the page has very little visible text and that text contains a marker, or
+/// - an element's class contains one of `paywall_classes` and the visible text
+///   is only a teaser.
+///
+/// Markers and class names are matched case-insensitively.
+pub fn detect_soft_unavailable(
+    document: &Html,
+    markers: &[String],
+    paywall_classes: &[String],
+) -> Option<String> {
+    let find_marker = |haystack: &str| {
+        let haystack = haystack.to_lowercase();
+        markers
+            .iter()
+            .find(|m| !m.is_empty() && haystack.contains(&m.to_lowercase()))
+    };
+
+    if let Some(title) = extract_title(document) {
+        if let Some(marker) = find_marker(&title) {
+            return Some(format!("title matches \"{marker}\""));
+        }
+    }
+
+    let text = visible_body_text(document);
+    let text_len = text.chars().count();
+    if text_len < SOFT_UNAVAILABLE_SHORT_TEXT {
+        if let Some(marker) = find_marker(&text) {
+            return Some(format!("short page matches \"{marker}\""));
+        }
+    }
+
+    if text_len < PAYWALL_TEXT_LIMIT {
+        if let Ok(selector) = Selector::parse("[class]") {
+            for element in document.select(&selector) {
+                for class in element.value().classes() {
+                    let class = class.to_lowercase();
+                    if let Some(marker) = paywall_classes
+                        .iter()
+                        .find(|m| !m.is_empty() && class.contains(&m.to_lowercase()))
+                    {
+                        return Some(format!("paywall class \"{marker}\""));
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Visible text of the page body, skipping scripts and styles.
+fn visible_body_text(document: &Html) -> String {
+    let Ok(selector) = Selector::parse("body") else {
+        return String::new();
+    };
+    let Some(body) = document.select(&selector).next() else {
+        return String::new();
+    };
+
+    let mut text = String::new();
+    for node in body.descendants() {
+        let Some(chunk) = node.value().as_text() else {
+            continue;
+        };
+        let hidden = node
+            .parent()
+            .and_then(|p| p.value().as_element())
+            .is_some_and(|e| matches!(e.name(), "script" | "style" | "noscript" | "template"));
+        if !hidden {
+            text.push_str(chunk);
+            text.push(' ');
+        }
+    }
+    clean_text(&text)
+}
+
 /// Extract title and readable text from HTML.
 fn extract_metadata(html: &str) -> (Option<String>, Option<String>) {
     let document = Html::parse_document(html);
@@ -232,6 +505,72 @@ fn clean_text(text: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::archiver::{error_kind, ArchiveErrorKind};
+
+    #[tokio::test]
+    async fn test_archive_sends_configured_user_agent_and_headers() {
+        use wiremock::matchers::{header, method};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        // Only answer requests carrying the configured headers, so the HEAD
+        // probe and the GET both have to send them
+        Mock::given(method("HEAD"))
+            .and(header("user-agent", "CustomArchiver/1.0"))
+            .and(header("accept-language", "en-GB"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-type", "text/html"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(header("user-agent", "CustomArchiver/1.0"))
+            .and(header("accept-language", "en-GB"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                "<html><head><title>Hello</title></head></html>",
+                "text/html",
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = Config {
+            archival_user_agent: "CustomArchiver/1.0".to_string(),
+            archival_extra_headers: vec![("Accept-Language".to_string(), "en-GB".to_string())],
+            ..Config::for_testing()
+        };
+        let work_dir = tempfile::tempdir().unwrap();
+        let result = GenericHandler::new()
+            .archive(
+                &format!("{}/page", server.uri()),
+                work_dir.path(),
+                &CookieOptions::default(),
+                &config,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.title.as_deref(), Some("Hello"));
+    }
+
+    #[tokio::test]
+    async fn test_archival_client_defaults_to_archival_user_agent() {
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(wiremock::matchers::any())
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let client = archival_client(&Config::for_testing(), Duration::from_secs(5)).unwrap();
+        client.get(server.uri()).send().await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(
+            requests[0].headers["user-agent"],
+            crate::constants::ARCHIVAL_USER_AGENT
+        );
+    }
 
     #[test]
     fn test_extract_title() {
@@ -266,6 +605,449 @@ mod tests {
         assert_eq!(title, Some("Test Page".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_archive_pdf_detected_by_head() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/download"))
+            .respond_with(
+                ResponseTemplate::new(200).insert_header("content-type", "application/pdf"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        // Servers that mislabel the GET body should still be stored as a document
+        let pdf = b"%PDF-1.4\n1 0 obj\n<< /Title (Docket 42) /Author (Clerk) >>\nendobj\n\
+            trailer\n<< /Info 1 0 R >>\n%%EOF"
+            .to_vec();
+        Mock::given(method("GET"))
+            .and(path("/download"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/octet-stream")
+                    .set_body_bytes(pdf.clone()),
+            )
+            .mount(&server)
+            .await;
+
+        let work_dir = tempfile::tempdir().unwrap();
+        let result = GenericHandler::new()
+            .archive(
+                &format!("{}/download", server.uri()),
+                work_dir.path(),
+                &CookieOptions::default(),
+                &crate::config::Config::for_testing(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.content_type, "document");
+        assert_eq!(result.title.as_deref(), Some("Docket 42"));
+        assert_eq!(result.author.as_deref(), Some("Clerk"));
+        assert_eq!(result.primary_file.as_deref(), Some("download.pdf"));
+        assert_eq!(
+            std::fs::read(work_dir.path().join("download.pdf")).unwrap(),
+            pdf
+        );
+    }
+
+    #[tokio::test]
+    async fn test_archive_html_not_treated_as_pdf() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/page"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-type", "text/html"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/page"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                "<html><head><title>Page</title></head><body>Hi</body></html>",
+                "text/html",
+            ))
+            .mount(&server)
+            .await;
+
+        let work_dir = tempfile::tempdir().unwrap();
+        let result = GenericHandler::new()
+            .archive(
+                &format!("{}/page", server.uri()),
+                work_dir.path(),
+                &CookieOptions::default(),
+                &crate::config::Config::for_testing(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.content_type, "text");
+        assert_eq!(result.primary_file.as_deref(), Some("raw.html"));
+    }
+
+    #[tokio::test]
+    async fn test_archive_extracts_reader_article() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let paragraph = "<p>Residents packed the hall, arguing for hours about parking, \
+            bike lanes, and the future of the old market square downtown.</p>";
+        let page = format!(
+            "<html><head><title>Town hall</title>\
+             <meta name=\"description\" content=\"Short summary\"></head>\
+             <body><nav><a href=\"/\">Home</a></nav><article>{}</article></body></html>",
+            paragraph.repeat(5)
+        );
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/story"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(page, "text/html"))
+            .mount(&server)
+            .await;
+
+        let url = format!("{}/story", server.uri());
+        let work_dir = tempfile::tempdir().unwrap();
+        let mut config = crate::config::Config::for_testing();
+        let result = GenericHandler::new()
+            .archive(&url, work_dir.path(), &CookieOptions::default(), &config)
+            .await
+            .unwrap();
+
+        assert_eq!(result.extra_files, vec![ARTICLE_FILE.to_string()]);
+        let text = result.text.unwrap();
+        assert!(text.starts_with("Residents packed the hall"));
+        assert!(!text.contains("Home"));
+        let article = std::fs::read_to_string(work_dir.path().join(ARTICLE_FILE)).unwrap();
+        assert!(article.contains("<title>Town hall</title>"));
+
+        // Disabled: the page summary is indexed and no artifact is written
+        config.reader_mode = false;
+        let work_dir = tempfile::tempdir().unwrap();
+        let result = GenericHandler::new()
+            .archive(&url, work_dir.path(), &CookieOptions::default(), &config)
+            .await
+            .unwrap();
+        assert!(result.extra_files.is_empty());
+        assert_eq!(result.text.as_deref(), Some("Short summary"));
+    }
+
+    #[test]
+    fn test_robots_header_requests_noarchive() {
+        use reqwest::header::{HeaderMap, HeaderValue};
+
+        let headers_with = |values: &[&str]| {
+            let mut headers = HeaderMap::new();
+            for value in values {
+                headers.append("X-Robots-Tag", HeaderValue::from_str(value).unwrap());
+            }
+            headers
+        };
+
+        assert!(robots_header_requests_noarchive(&headers_with(&[
+            "noarchive"
+        ])));
+        assert!(robots_header_requests_noarchive(&headers_with(&[
+            "noindex, NoArchive"
+        ])));
+        assert!(robots_header_requests_noarchive(&headers_with(&[
+            "nofollow",
+            "noarchive"
+        ])));
+        assert!(robots_header_requests_noarchive(&headers_with(&[
+            "*: noarchive"
+        ])));
+        assert!(robots_header_requests_noarchive(&headers_with(&[
+            "unavailable_after: 2030-01-01, noarchive"
+        ])));
+
+        assert!(!robots_header_requests_noarchive(&HeaderMap::new()));
+        assert!(!robots_header_requests_noarchive(&headers_with(&[
+            "noindex, nofollow"
+        ])));
+        assert!(!robots_header_requests_noarchive(&headers_with(&[
+            "googlebot: noarchive"
+        ])));
+        assert!(!robots_header_requests_noarchive(&headers_with(&[
+            "googlebot: nofollow, noarchive"
+        ])));
+    }
+
+    #[test]
+    fn test_meta_requests_noarchive() {
+        let doc = |html: &str| Html::parse_document(html);
+
+        assert!(meta_requests_noarchive(&doc(
+            r#"<head><meta name="robots" content="noindex, noarchive"></head>"#
+        )));
+        assert!(meta_requests_noarchive(&doc(
+            r#"<head><meta name="ROBOTS" content="NOARCHIVE"></head>"#
+        )));
+
+        assert!(!meta_requests_noarchive(&doc(
+            r#"<head><meta name="robots" content="index, follow"></head>"#
+        )));
+        assert!(!meta_requests_noarchive(&doc(
+            r#"<head><meta name="googlebot" content="noarchive"></head>"#
+        )));
+        assert!(!meta_requests_noarchive(&doc(
+            "<head><title>x</title></head>"
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_archive_respects_noarchive_when_enabled() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/page"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"<html><head><meta name="robots" content="noarchive"></head><body>Hi</body></html>"#,
+                "text/html",
+            ))
+            .mount(&server)
+            .await;
+        let url = format!("{}/page", server.uri());
+        let work_dir = tempfile::tempdir().unwrap();
+
+        // Off by default
+        let config = crate::config::Config::for_testing();
+        let result = GenericHandler::new()
+            .archive(&url, work_dir.path(), &CookieOptions::default(), &config)
+            .await
+            .unwrap();
+        assert_eq!(result.content_type, "text");
+
+        let config = crate::config::Config {
+            respect_noarchive: true,
+            ..crate::config::Config::for_testing()
+        };
+        let err = GenericHandler::new()
+            .archive(&url, work_dir.path(), &CookieOptions::default(), &config)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains(NOARCHIVE_REQUESTED));
+        assert_eq!(error_kind(&err), ArchiveErrorKind::Unsupported);
+    }
+
+    fn soft_unavailable(html: &str) -> Option<String> {
+        let config = crate::config::Config::for_testing();
+        detect_soft_unavailable(
+            &Html::parse_document(html),
+            &config.soft_unavailable_markers,
+            &config.soft_unavailable_paywall_classes,
+        )
+    }
+
+    #[test]
+    fn test_detect_soft_unavailable_title() {
+        let html = r"<html><head><title>Page Not Found | Example News</title></head>
+            <body><nav>Home News Sport</nav><p>Try searching instead.</p></body></html>";
+        assert_eq!(
+            soft_unavailable(html).as_deref(),
+            Some("title matches \"page not found\"")
+        );
+    }
+
+    #[test]
+    fn test_detect_soft_unavailable_short_body() {
+        let html = r"<html><head><title>Example</title>
+            <script>var longScript = 'no longer available';</script></head>
+            <body><div class='notice'>Sorry, this content is no longer available.</div></body></html>";
+        assert_eq!(
+            soft_unavailable(html).as_deref(),
+            Some("short page matches \"no longer available\"")
+        );
+    }
+
+    #[test]
+    fn test_detect_soft_unavailable_paywall() {
+        let html = r#"<html><head><title>Big Story</title></head><body>
+            <article><p>The first paragraph of the story is free to read.</p>
+            <div class="article-Paywall-overlay">Subscribers get unlimited access.</div>
+            </article></body></html>"#;
+        assert_eq!(
+            soft_unavailable(html).as_deref(),
+            Some("paywall class \"paywall\"")
+        );
+    }
+
+    #[test]
+    fn test_detect_soft_unavailable_ignores_real_content() {
+        // A full article that mentions a marker phrase and carries a paywall
+        // class is still a real capture
+        let body = "The service said older posts are no longer available. ".repeat(60);
+        let html = format!(
+            r#"<html><head><title>Why old posts vanish</title></head>
+            <body><article class="no-paywall-js"><p>{body}</p></article></body></html>"#
+        );
+        assert_eq!(soft_unavailable(&html), None);
+
+        // Ordinary short page
+        assert_eq!(
+            soft_unavailable("<html><head><title>Hello</title></head><body>Hi</body></html>"),
+            None
+        );
+
+        // Markers are configurable; an empty list disables matching
+        let html = "<html><head><title>Page not found</title></head><body></body></html>";
+        assert_eq!(
+            detect_soft_unavailable(&Html::parse_document(html), &[], &[]),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_archive_rejects_soft_unavailable_page() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/gone"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                "<html><head><title>Content unavailable</title></head><body></body></html>",
+                "text/html",
+            ))
+            .mount(&server)
+            .await;
+        let url = format!("{}/gone", server.uri());
+        let work_dir = tempfile::tempdir().unwrap();
+
+        let err = GenericHandler::new()
+            .archive(
+                &url,
+                work_dir.path(),
+                &CookieOptions::default(),
+                &crate::config::Config::for_testing(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().starts_with(SOFT_UNAVAILABLE));
+        assert_eq!(error_kind(&err), ArchiveErrorKind::NotFound);
+        assert!(!work_dir.path().join("raw.html").exists());
+    }
+
+    #[tokio::test]
+    async fn test_archive_skips_oversized_download_from_head() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/big.pdf"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/pdf")
+                    .insert_header("content-length", "5000000"),
+            )
+            .mount(&server)
+            .await;
+        // The body must never be fetched
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+        let url = format!("{}/big.pdf", server.uri());
+        let work_dir = tempfile::tempdir().unwrap();
+
+        let config = crate::config::Config {
+            max_artifact_bytes: 1_000_000,
+            ..crate::config::Config::for_testing()
+        };
+        let err = GenericHandler::new()
+            .archive(&url, work_dir.path(), &CookieOptions::default(), &config)
+            .await
+            .unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.starts_with(crate::archiver::size_cap::ARTIFACT_TOO_LARGE));
+        assert!(msg.contains("5000000 bytes"));
+        assert_eq!(error_kind(&err), ArchiveErrorKind::TooLarge);
+    }
+
+    #[tokio::test]
+    async fn test_archive_http_error_kinds() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        for (status, kind) in [
+            (401, ArchiveErrorKind::AuthRequired),
+            (404, ArchiveErrorKind::NotFound),
+            (429, ArchiveErrorKind::RateLimited),
+            (500, ArchiveErrorKind::Other),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(format!("/status/{status}")))
+                .respond_with(ResponseTemplate::new(status))
+                .mount(&server)
+                .await;
+            let url = format!("{}/status/{status}", server.uri());
+            let work_dir = tempfile::tempdir().unwrap();
+
+            let err = GenericHandler::new()
+                .archive(
+                    &url,
+                    work_dir.path(),
+                    &CookieOptions::default(),
+                    &crate::config::Config::for_testing(),
+                )
+                .await
+                .unwrap_err();
+            assert_eq!(error_kind(&err), kind, "HTTP {status}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_archive_enforces_cap_without_head_length() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        // HEAD is not supported, so the size is only known from the GET
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(405))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/page"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                format!("<html><body>{}</body></html>", "word ".repeat(1000)),
+                "text/html",
+            ))
+            .mount(&server)
+            .await;
+        let url = format!("{}/page", server.uri());
+        let work_dir = tempfile::tempdir().unwrap();
+
+        let small = crate::config::Config {
+            max_artifact_bytes: 1000,
+            ..crate::config::Config::for_testing()
+        };
+        let err = GenericHandler::new()
+            .archive(&url, work_dir.path(), &CookieOptions::default(), &small)
+            .await
+            .unwrap_err();
+        assert!(format!("{err:#}").contains(crate::archiver::size_cap::ARTIFACT_TOO_LARGE));
+
+        let roomy = crate::config::Config {
+            max_artifact_bytes: 1_000_000,
+            ..crate::config::Config::for_testing()
+        };
+        let result = GenericHandler::new()
+            .archive(&url, work_dir.path(), &CookieOptions::default(), &roomy)
+            .await
+            .unwrap();
+        assert_eq!(result.primary_file.as_deref(), Some("raw.html"));
+    }
+
     #[test]
     fn test_can_handle() {
         let handler = GenericHandler::new();
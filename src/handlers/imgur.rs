@@ -1,11 +1,16 @@
 use std::path::Path;
+use std::time::Duration;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
 use regex::Regex;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use tracing::{debug, warn};
 
 use super::traits::{ArchiveResult, SiteHandler};
-use crate::archiver::{gallerydl, CookieOptions};
+use crate::archiver::{gallerydl, ArchiveError, CookieOptions};
+use crate::constants::ARCHIVAL_USER_AGENT;
 
 static PATTERNS: std::sync::LazyLock<Vec<Regex>> = std::sync::LazyLock::new(|| {
     vec![
@@ -15,6 +20,8 @@ static PATTERNS: std::sync::LazyLock<Vec<Regex>> = std::sync::LazyLock::new(|| {
         Regex::new(r"^https?://(www\.)?imgur\.com/a/[A-Za-z0-9]+").unwrap(),
         // Gallery URLs
         Regex::new(r"^https?://(www\.)?imgur\.com/gallery/[A-Za-z0-9]+").unwrap(),
+        // Tag gallery URLs
+        Regex::new(r"^https?://(www\.)?imgur\.com/t/[^/]+/[A-Za-z0-9]+").unwrap(),
         // Single image page URLs
         Regex::new(r"^https?://(www\.)?imgur\.com/[A-Za-z0-9]+$").unwrap(),
         // Video URLs
@@ -23,6 +30,54 @@ static PATTERNS: std::sync::LazyLock<Vec<Regex>> = std::sync::LazyLock::new(|| {
     ]
 });
 
+// Album, gallery and tag-gallery URLs; newer gallery URLs prefix the ID with a title slug
+static ALBUM_PARSER: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| {
+    Regex::new(r"^https?://(?:www\.)?imgur\.com/(?:a|gallery|t/[^/]+)/(?:[^/?#]*-)?([A-Za-z0-9]+)")
+        .unwrap()
+});
+
+const IMGUR_API_BASE: &str = "https://api.imgur.com/post/v1";
+
+/// Public client ID used by imgur.com's own web frontend for anonymous reads.
+const IMGUR_CLIENT_ID: &str = "546c25a59c58ad7";
+
+/// Extract the album ID from an Imgur album or gallery URL.
+///
+/// Returns `None` for single-image and direct media URLs.
+#[must_use]
+pub fn album_id(url: &str) -> Option<String> {
+    ALBUM_PARSER
+        .captures(url)
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str().to_string())
+}
+
+/// Album returned by the post API.
+#[derive(Debug, Deserialize)]
+struct ImgurAlbum {
+    id: String,
+    title: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    is_mature: bool,
+    account: Option<ImgurAccount>,
+    #[serde(default)]
+    media: Vec<ImgurMedia>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImgurAccount {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImgurMedia {
+    id: String,
+    url: String,
+    #[serde(rename = "type", default)]
+    media_type: String,
+}
+
 pub struct ImgurHandler;
 
 impl ImgurHandler {
@@ -53,6 +108,11 @@ impl SiteHandler for ImgurHandler {
     }
 
     fn normalize_url(&self, url: &str) -> String {
+        // Gallery posts share their album's ID, so both forms archive once
+        if let Some(id) = album_id(url) {
+            return format!("https://imgur.com/a/{id}");
+        }
+
         let normalized = url.replace("://www.imgur.com/", "://imgur.com/");
 
         // Convert .gifv to page URL for better archiving
@@ -81,10 +141,131 @@ impl SiteHandler for ImgurHandler {
         cookies: &CookieOptions<'_>,
         config: &crate::config::Config,
     ) -> Result<ArchiveResult> {
+        if let Some(id) = album_id(url) {
+            return archive_album(IMGUR_API_BASE, &id, work_dir).await;
+        }
         gallerydl::download(url, work_dir, cookies, config).await
     }
 }
 
+/// Archive every image and video in an album as one gallery.
+///
+/// Media files are named `{index}_{media id}.{ext}` so they sort in album order.
+async fn archive_album(api_base: &str, id: &str, work_dir: &Path) -> Result<ArchiveResult> {
+    let client = reqwest::Client::builder()
+        .user_agent(ARCHIVAL_USER_AGENT)
+        .timeout(Duration::from_secs(30))
+        .build()
+        .context("Failed to build HTTP client")?;
+
+    let api_url =
+        format!("{api_base}/albums/{id}?client_id={IMGUR_CLIENT_ID}&include=media,account");
+    let response = client
+        .get(&api_url)
+        .send()
+        .await
+        .context("Failed to fetch Imgur album")?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Err(ArchiveError::NotFound(format!(
+            "Imgur album {id} not found (deleted or private, HTTP 404)"
+        ))
+        .into());
+    }
+    let body = response
+        .error_for_status()
+        .context("Imgur album fetch returned error")?
+        .text()
+        .await
+        .context("Failed to read Imgur album response")?;
+    let album: ImgurAlbum =
+        serde_json::from_str(&body).context("Failed to parse Imgur album response")?;
+
+    if album.media.is_empty() {
+        return Err(
+            ArchiveError::NotFound(format!("Imgur album {id} has no media (removed)")).into(),
+        );
+    }
+
+    let mut files = Vec::new();
+    for (index, media) in album.media.iter().enumerate() {
+        match download_media(&client, media, index, work_dir).await {
+            Ok(filename) => files.push(filename),
+            Err(e) => {
+                warn!(album = %album.id, media = %media.id, error = %e, "Failed to download Imgur album media");
+            }
+        }
+    }
+    if files.is_empty() {
+        anyhow::bail!("Failed to download any media from Imgur album {id}");
+    }
+    debug!(album = %album.id, count = files.len(), "Downloaded Imgur album media");
+
+    let content_type = if files.len() > 1 {
+        "gallery"
+    } else if album.media[0].media_type == "video" {
+        "video"
+    } else {
+        "image"
+    };
+
+    tokio::fs::write(work_dir.join("metadata.json"), &body)
+        .await
+        .context("Failed to write metadata file")?;
+
+    let mut files = files.into_iter();
+    let primary_file = files.next();
+    let mut extra_files: Vec<String> = files.collect();
+    extra_files.push("metadata.json".to_string());
+
+    Ok(ArchiveResult {
+        title: album
+            .title
+            .filter(|t| !t.is_empty())
+            .or_else(|| Some(format!("Imgur album {}", album.id))),
+        author: album.account.map(|a| a.username),
+        text: album.description.filter(|d| !d.is_empty()),
+        content_type: content_type.to_string(),
+        primary_file,
+        extra_files,
+        metadata_json: Some(body),
+        is_nsfw: album.is_mature.then_some(true),
+        nsfw_source: album.is_mature.then(|| "api".to_string()),
+        ..Default::default()
+    })
+}
+
+/// Download one album item, returning its filename in `work_dir`.
+async fn download_media(
+    client: &reqwest::Client,
+    media: &ImgurMedia,
+    index: usize,
+    work_dir: &Path,
+) -> Result<String> {
+    let path = media.url.split(['?', '#']).next().unwrap_or_default();
+    let ext = path
+        .rsplit('/')
+        .next()
+        .and_then(|name| name.rsplit_once('.'))
+        .map_or("jpg", |(_, ext)| ext);
+    let filename = format!("{:03}_{}.{}", index + 1, media.id, ext);
+
+    let bytes = client
+        .get(&media.url)
+        .send()
+        .await
+        .context("Failed to download media")?
+        .error_for_status()
+        .context("Media download returned error")?
+        .bytes()
+        .await
+        .context("Failed to read media bytes")?;
+    tokio::fs::write(work_dir.join(&filename), &bytes)
+        .await
+        .context("Failed to write media file")?;
+
+    Ok(filename)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,6 +286,7 @@ mod tests {
         // Gallery URLs
         assert!(handler.can_handle("https://imgur.com/gallery/ABC123"));
         assert!(handler.can_handle("https://www.imgur.com/gallery/ABC123"));
+        assert!(handler.can_handle("https://imgur.com/t/cats/ABC123"));
 
         // Single image page URLs
         assert!(handler.can_handle("https://imgur.com/ABC123"));
@@ -140,5 +322,121 @@ mod tests {
             handler.normalize_url("https://imgur.com/a/ABC123?ref=test"),
             "https://imgur.com/a/ABC123"
         );
+
+        // Gallery and tag gallery URLs collapse to the album URL
+        assert_eq!(
+            handler.normalize_url("https://imgur.com/gallery/ABC123"),
+            "https://imgur.com/a/ABC123"
+        );
+        assert_eq!(
+            handler.normalize_url("https://imgur.com/gallery/funny-cat-pictures-ABC123"),
+            "https://imgur.com/a/ABC123"
+        );
+        assert_eq!(
+            handler.normalize_url("https://imgur.com/t/cats/ABC123"),
+            "https://imgur.com/a/ABC123"
+        );
+
+        // Single images keep their own URL
+        assert_eq!(
+            handler.normalize_url("https://imgur.com/XYZ789"),
+            "https://imgur.com/XYZ789"
+        );
+        assert_eq!(
+            handler.normalize_url("https://i.imgur.com/XYZ789.jpg"),
+            "https://i.imgur.com/XYZ789.jpg"
+        );
+    }
+
+    #[test]
+    fn test_album_id() {
+        assert_eq!(
+            album_id("https://imgur.com/a/ABC123"),
+            Some("ABC123".to_string())
+        );
+        assert_eq!(
+            album_id("https://www.imgur.com/gallery/some-title-XyZ9"),
+            Some("XyZ9".to_string())
+        );
+        assert_eq!(
+            album_id("https://imgur.com/t/memes/QWE1"),
+            Some("QWE1".to_string())
+        );
+        assert_eq!(album_id("https://imgur.com/ABC123"), None);
+        assert_eq!(album_id("https://i.imgur.com/ABC123.gifv"), None);
+    }
+
+    #[tokio::test]
+    async fn test_archive_album_groups_media_as_gallery() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let album = format!(
+            r#"{{"id":"ALB1","title":"Cats","description":"Two cats","is_mature":true,
+                "account":{{"username":"catfan"}},
+                "media":[
+                    {{"id":"img1","url":"{0}/img1.jpeg","type":"image"}},
+                    {{"id":"vid2","url":"{0}/vid2.mp4","type":"video"}}
+                ]}}"#,
+            server.uri()
+        );
+        Mock::given(method("GET"))
+            .and(path("/albums/ALB1"))
+            .and(query_param("include", "media,account"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(album))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/img1.jpeg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"jpeg".to_vec()))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/vid2.mp4"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"mp4".to_vec()))
+            .mount(&server)
+            .await;
+
+        let work_dir = tempfile::TempDir::new().unwrap();
+        let result = archive_album(&server.uri(), "ALB1", work_dir.path())
+            .await
+            .unwrap();
+
+        assert_eq!(result.content_type, "gallery");
+        assert_eq!(result.primary_file.as_deref(), Some("001_img1.jpeg"));
+        assert_eq!(result.extra_files, vec!["002_vid2.mp4", "metadata.json"]);
+        assert_eq!(result.title.as_deref(), Some("Cats"));
+        assert_eq!(result.author.as_deref(), Some("catfan"));
+        assert_eq!(result.is_nsfw, Some(true));
+        assert!(work_dir.path().join("001_img1.jpeg").exists());
+        assert!(work_dir.path().join("002_vid2.mp4").exists());
+    }
+
+    #[tokio::test]
+    async fn test_archive_missing_album_is_permanent_failure() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/albums/GONE1"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let work_dir = tempfile::TempDir::new().unwrap();
+        let err = archive_album(&server.uri(), "GONE1", work_dir.path())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            crate::archiver::classify_failure(&format!("{err:#}"), None, false),
+            (_, crate::archiver::FailureClass::Permanent(_))
+        ));
+        assert_eq!(
+            crate::archiver::error_kind(&err),
+            crate::archiver::ArchiveErrorKind::NotFound
+        );
     }
 }
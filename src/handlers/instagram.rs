@@ -6,7 +6,7 @@ use regex::Regex;
 use tracing::debug;
 
 use super::traits::{ArchiveResult, SiteHandler};
-use crate::archiver::{ytdlp, CookieOptions};
+use crate::archiver::{gallerydl, ytdlp, ArchiveError, CookieOptions};
 
 static PATTERNS: std::sync::LazyLock<Vec<Regex>> = std::sync::LazyLock::new(|| {
     vec![
@@ -77,7 +77,14 @@ impl SiteHandler for InstagramHandler {
         cookies: &CookieOptions<'_>,
         config: &crate::config::Config,
     ) -> Result<ArchiveResult> {
-        let mut result = ytdlp::download(url, work_dir, cookies, config, None, None, false).await?;
+        let has_cookies = cookies.cookies_file.is_some() || cookies.browser_profile.is_some();
+
+        let mut result = if is_carousel_candidate(url) {
+            archive_post(url, work_dir, cookies, config).await
+        } else {
+            ytdlp::download(url, work_dir, cookies, config, None, None, false).await
+        }
+        .map_err(|e| auth_wall_error(e, has_cookies))?;
 
         if let Some(shortcode) = extract_shortcode(url) {
             debug!(shortcode = %shortcode, "Extracted Instagram shortcode");
@@ -88,6 +95,86 @@ impl SiteHandler for InstagramHandler {
     }
 }
 
+/// gallery-dl options for posts: include carousel videos and prefix each file
+/// with its carousel position so the gallery keeps the post's order.
+const GALLERYDL_POST_ARGS: &[&str] = &[
+    "-o",
+    "videos=true",
+    "--filename",
+    "{category}_{num:>02}_{filename}.{extension}",
+];
+
+/// Check if a URL is a `/p/` post, which may be a multi-item carousel.
+///
+/// Reels, IGTV and stories are always a single video and go straight to yt-dlp.
+fn is_carousel_candidate(url: &str) -> bool {
+    url.contains("/p/")
+}
+
+/// Archive a post with gallery-dl so every carousel item is captured,
+/// falling back to yt-dlp for single videos gallery-dl can't fetch.
+async fn archive_post(
+    url: &str,
+    work_dir: &Path,
+    cookies: &CookieOptions<'_>,
+    config: &crate::config::Config,
+) -> Result<ArchiveResult> {
+    match gallerydl::download_with_options(url, work_dir, cookies, config, GALLERYDL_POST_ARGS)
+        .await
+    {
+        Ok(result) if result.primary_file.is_some() => Ok(order_carousel(result)),
+        Ok(_) => {
+            debug!(url = %url, "gallery-dl found no media, falling back to yt-dlp");
+            ytdlp::download(url, work_dir, cookies, config, None, None, false).await
+        }
+        Err(e) if is_auth_wall(&format!("{e:#}")) => Err(e),
+        Err(e) => {
+            debug!(url = %url, "gallery-dl failed, falling back to yt-dlp: {e:#}");
+            ytdlp::download(url, work_dir, cookies, config, None, None, false).await
+        }
+    }
+}
+
+/// Put carousel items back in post order, with the first item as primary.
+fn order_carousel(mut result: ArchiveResult) -> ArchiveResult {
+    let (mut media, other): (Vec<String>, Vec<String>) = result
+        .primary_file
+        .take()
+        .into_iter()
+        .chain(std::mem::take(&mut result.extra_files))
+        .partition(|name| !name.ends_with(".json"));
+    media.sort();
+
+    let mut media = media.into_iter();
+    result.primary_file = media.next();
+    result.extra_files = media.chain(other).collect();
+    result
+}
+
+/// Check whether an error means Instagram demanded a login.
+fn is_auth_wall(error_msg: &str) -> bool {
+    let lower = error_msg.to_lowercase();
+    lower.contains("login")
+        || lower.contains("log in")
+        || lower.contains("--cookies")
+        || lower.contains("checkpoint")
+        || lower.contains("401")
+}
+
+/// Replace a login-wall error with a clear "login required" message when no
+/// cookies are configured, so the archive is marked `auth_required` and can
+/// be retried once cookies are added.
+fn auth_wall_error(e: anyhow::Error, has_cookies: bool) -> anyhow::Error {
+    if !has_cookies && is_auth_wall(&format!("{e:#}")) {
+        ArchiveError::AuthRequired(format!(
+            "Instagram login required: configure COOKIES_FILE_PATH to archive this post ({e:#})"
+        ))
+        .into()
+    } else {
+        e
+    }
+}
+
 /// Extract shortcode from Instagram URL.
 ///
 /// Supports URL formats:
@@ -198,6 +285,73 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_is_carousel_candidate() {
+        assert!(is_carousel_candidate("https://instagram.com/p/ABC123"));
+        assert!(is_carousel_candidate(
+            "https://instagram.com/p/ABC123/?img_index=2"
+        ));
+
+        assert!(!is_carousel_candidate("https://instagram.com/reel/ABC123"));
+        assert!(!is_carousel_candidate("https://instagram.com/tv/ABC123"));
+        assert!(!is_carousel_candidate(
+            "https://instagram.com/stories/user/1234567890"
+        ));
+        assert!(!is_carousel_candidate("https://instagram.com/username"));
+    }
+
+    #[test]
+    fn test_order_carousel() {
+        let result = order_carousel(ArchiveResult {
+            primary_file: Some("instagram_03_c.mp4".to_string()),
+            extra_files: vec![
+                "instagram_02_b.jpg".to_string(),
+                "instagram_01_a.jpg.json".to_string(),
+                "instagram_01_a.jpg".to_string(),
+            ],
+            content_type: "gallery".to_string(),
+            ..Default::default()
+        });
+
+        assert_eq!(result.primary_file.as_deref(), Some("instagram_01_a.jpg"));
+        assert_eq!(
+            result.extra_files,
+            vec![
+                "instagram_02_b.jpg",
+                "instagram_03_c.mp4",
+                "instagram_01_a.jpg.json"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_auth_wall_without_cookies_is_auth_required() {
+        use crate::archiver::{classify_failure, FailureClass};
+
+        let err = auth_wall_error(
+            anyhow::anyhow!("gallery-dl failed: [instagram][error] HTTP redirect to login page"),
+            false,
+        );
+        let msg = format!("{err:#}");
+        assert!(msg.starts_with("Instagram login required: configure COOKIES_FILE_PATH"));
+        assert_eq!(
+            classify_failure(&msg, None, false).1,
+            FailureClass::AuthRequired
+        );
+        assert_eq!(
+            crate::archiver::error_kind(&err),
+            crate::archiver::ArchiveErrorKind::AuthRequired
+        );
+
+        // With cookies configured the original error is kept
+        let err = auth_wall_error(anyhow::anyhow!("HTTP redirect to login page"), true);
+        assert_eq!(format!("{err:#}"), "HTTP redirect to login page");
+
+        // Unrelated failures pass through untouched
+        let err = auth_wall_error(anyhow::anyhow!("Connection reset"), false);
+        assert_eq!(format!("{err:#}"), "Connection reset");
+    }
+
     #[test]
     fn test_extract_shortcode() {
         // Post URLs
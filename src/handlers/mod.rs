@@ -3,18 +3,26 @@ mod registry;
 mod traits;
 
 // Site handlers
+mod bandcamp;
 mod bluesky;
 mod facebook;
 mod generic;
 mod imgur;
 mod instagram;
-mod reddit;
+mod pdf;
+mod reader;
+pub mod reddit;
+mod soundcloud;
 mod streamable;
 pub mod tiktok;
 mod twitter;
 pub mod youtube;
 
+pub use generic::{
+    archival_client, archival_client_builder, NOARCHIVE_REQUESTED, SOFT_UNAVAILABLE,
+};
 pub use normalize::normalize_url;
+pub use reader::{extract_article, ReaderArticle, ARTICLE_FILE};
 pub use registry::HandlerRegistry;
 pub use traits::{ArchiveResult, SiteHandler};
 
@@ -30,6 +38,22 @@ pub static HANDLERS: std::sync::LazyLock<HandlerRegistry> = std::sync::LazyLock:
     registry.register(Box::new(bluesky::BlueskyHandler::new()));
     registry.register(Box::new(streamable::StreamableHandler::new()));
     registry.register(Box::new(facebook::FacebookHandler::new()));
+    registry.register(Box::new(soundcloud::SoundCloudHandler::new()));
+    registry.register(Box::new(bandcamp::BandcampHandler::new()));
     registry.register(Box::new(generic::GenericHandler::new()));
     registry
 });
+
+/// Normalize a link URL into the form it is stored under in `links.normalized_url`.
+///
+/// Generic normalization first, then the matching handler's own rules (e.g.
+/// YouTube strips `list=`/`index=`, Twitter URLs become x.com). Anything that
+/// looks links up by URL should use this, so every path agrees on the key.
+#[must_use]
+pub fn normalize_link_url(url: &str) -> String {
+    let normalized = normalize_url(url);
+    match HANDLERS.find_handler(&normalized) {
+        Some(handler) => handler.normalize_url(&normalized),
+        None => normalized,
+    }
+}
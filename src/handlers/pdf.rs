@@ -0,0 +1,297 @@
+//! Helpers for archiving directly linked PDF documents.
+
+use regex::bytes::Regex;
+
+static INFO_REF: std::sync::LazyLock<Regex> =
+    std::sync::LazyLock::new(|| Regex::new(r"/Info\s*(\d+)\s+(\d+)\s+R").unwrap());
+
+static XMP_TITLE: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| {
+    Regex::new(r"(?s)<dc:title>.*?<rdf:li[^>]*>(.*?)</rdf:li>").unwrap()
+});
+
+static XMP_CREATOR: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| {
+    Regex::new(r"(?s)<dc:creator>.*?<rdf:li[^>]*>(.*?)</rdf:li>").unwrap()
+});
+
+/// Title and author read from a PDF's document information.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PdfMetadata {
+    pub title: Option<String>,
+    pub author: Option<String>,
+}
+
+/// Whether the URL path ends in `.pdf` (ignoring query string and case).
+pub fn is_pdf_url(url: &str) -> bool {
+    url::Url::parse(url).is_ok_and(|u| u.path().to_ascii_lowercase().ends_with(".pdf"))
+}
+
+/// Whether a `Content-Type` header value describes a PDF.
+pub fn is_pdf_content_type(content_type: &str) -> bool {
+    content_type
+        .split(';')
+        .next()
+        .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/pdf"))
+}
+
+/// Pick a local filename for a downloaded PDF, always ending in `.pdf`.
+pub fn pdf_filename(url: &str) -> String {
+    let segment = url::Url::parse(url)
+        .ok()
+        .and_then(|u| {
+            u.path_segments()
+                .and_then(|mut s| s.next_back().map(ToString::to_string))
+        })
+        .unwrap_or_default();
+    let segment = urlencoding::decode(&segment)
+        .map(std::borrow::Cow::into_owned)
+        .unwrap_or(segment);
+    let stem: String = segment
+        .chars()
+        .filter(|c| !matches!(c, '/' | '\\' | '\0'))
+        .collect();
+
+    if stem.is_empty() {
+        "document.pdf".to_string()
+    } else if stem.to_ascii_lowercase().ends_with(".pdf") {
+        stem
+    } else {
+        format!("{stem}.pdf")
+    }
+}
+
+/// Extract the title and author from a PDF.
+///
+/// Reads the trailer's `/Info` dictionary when it is stored uncompressed and
+/// falls back to the XMP metadata packet. Compressed object streams are not
+/// decoded, so some PDFs will yield no metadata.
+pub fn extract_pdf_metadata(data: &[u8]) -> PdfMetadata {
+    let mut meta = info_dictionary(data)
+        .map(|dict| PdfMetadata {
+            title: dict_string(dict, b"/Title"),
+            author: dict_string(dict, b"/Author"),
+        })
+        .unwrap_or_default();
+
+    if meta.title.is_none() {
+        meta.title = xmp_value(&XMP_TITLE, data);
+    }
+    if meta.author.is_none() {
+        meta.author = xmp_value(&XMP_CREATOR, data);
+    }
+
+    meta
+}
+
+/// Locate the body of the document information dictionary.
+fn info_dictionary(data: &[u8]) -> Option<&[u8]> {
+    // Incremental updates append newer trailers, so the last reference wins.
+    let caps = INFO_REF.captures_iter(data).last()?;
+    let num = std::str::from_utf8(&caps[1]).ok()?;
+    let gen = std::str::from_utf8(&caps[2]).ok()?;
+
+    let obj_re = Regex::new(&format!(r"(?:^|[^0-9]){num}\s+{gen}\s+obj")).ok()?;
+    let start = obj_re.find_iter(data).last()?.end();
+    let rest = &data[start..];
+    let end = find_bytes(rest, b"endobj").unwrap_or(rest.len());
+    Some(&rest[..end])
+}
+
+/// Read a string value for `key` from a dictionary body.
+fn dict_string(dict: &[u8], key: &[u8]) -> Option<String> {
+    let mut offset = 0;
+    while let Some(pos) = find_bytes(&dict[offset..], key) {
+        let after = offset + pos + key.len();
+        // Skip keys that merely share a prefix (e.g. /TitleFoo)
+        if dict.get(after).is_some_and(u8::is_ascii_alphanumeric) {
+            offset = after;
+            continue;
+        }
+        let value = dict[after..].trim_ascii_start();
+        let raw = match value.first()? {
+            b'(' => parse_literal_string(value)?,
+            b'<' => parse_hex_string(value)?,
+            _ => return None,
+        };
+        return non_empty(&decode_text_string(&raw));
+    }
+    None
+}
+
+/// Parse a `(...)` literal string, handling escapes and balanced parentheses.
+fn parse_literal_string(input: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut depth = 0usize;
+    let mut iter = input.iter().copied().peekable();
+
+    while let Some(b) = iter.next() {
+        match b {
+            b'(' => {
+                if depth > 0 {
+                    out.push(b);
+                }
+                depth += 1;
+            }
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(out);
+                }
+                out.push(b);
+            }
+            b'\\' => match iter.next()? {
+                b'n' => out.push(b'\n'),
+                b'r' => out.push(b'\r'),
+                b't' => out.push(b'\t'),
+                b'b' => out.push(0x08),
+                b'f' => out.push(0x0c),
+                b'\r' => {
+                    // Line continuation
+                    if iter.peek() == Some(&b'\n') {
+                        iter.next();
+                    }
+                }
+                b'\n' => {}
+                d @ b'0'..=b'7' => {
+                    let mut value = u32::from(d - b'0');
+                    for _ in 0..2 {
+                        match iter.peek() {
+                            Some(&o @ b'0'..=b'7') => {
+                                value = value * 8 + u32::from(o - b'0');
+                                iter.next();
+                            }
+                            _ => break,
+                        }
+                    }
+                    out.push((value & 0xff) as u8);
+                }
+                other => out.push(other),
+            },
+            _ => out.push(b),
+        }
+    }
+
+    None
+}
+
+/// Parse a `<...>` hex string.
+fn parse_hex_string(input: &[u8]) -> Option<Vec<u8>> {
+    let end = input.iter().position(|&b| b == b'>')?;
+    let mut digits: Vec<u8> = input[1..end]
+        .iter()
+        .copied()
+        .filter(u8::is_ascii_hexdigit)
+        .collect();
+    if digits.len() % 2 == 1 {
+        digits.push(b'0');
+    }
+    hex::decode(digits).ok()
+}
+
+/// Decode a PDF text string (UTF-16BE with BOM, UTF-8 with BOM, or `PDFDocEncoding`).
+fn decode_text_string(raw: &[u8]) -> String {
+    if let Some(utf16) = raw.strip_prefix(&[0xfe, 0xff]) {
+        let units: Vec<u16> = utf16
+            .chunks_exact(2)
+            .map(|c| u16::from_be_bytes([c[0], c[1]]))
+            .collect();
+        return String::from_utf16_lossy(&units);
+    }
+    if let Some(utf8) = raw.strip_prefix(&[0xef, 0xbb, 0xbf]) {
+        return String::from_utf8_lossy(utf8).into_owned();
+    }
+    // PDFDocEncoding matches Latin-1 for printable characters
+    raw.iter().map(|&b| char::from(b)).collect()
+}
+
+/// Read the first `rdf:li` value of an XMP property.
+fn xmp_value(re: &Regex, data: &[u8]) -> Option<String> {
+    let caps = re.captures(data)?;
+    let text = String::from_utf8_lossy(&caps[1])
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&");
+    non_empty(&text)
+}
+
+fn non_empty(s: &str) -> Option<String> {
+    let trimmed = s.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_pdf_url() {
+        assert!(is_pdf_url("https://example.com/paper.pdf"));
+        assert!(is_pdf_url("https://example.com/Paper.PDF?download=1"));
+        assert!(!is_pdf_url("https://example.com/pdf/view"));
+        assert!(!is_pdf_url("https://example.com/?file=a.pdf"));
+        assert!(!is_pdf_url("not a url"));
+    }
+
+    #[test]
+    fn test_is_pdf_content_type() {
+        assert!(is_pdf_content_type("application/pdf"));
+        assert!(is_pdf_content_type("Application/PDF; charset=binary"));
+        assert!(!is_pdf_content_type("text/html"));
+    }
+
+    #[test]
+    fn test_pdf_filename() {
+        assert_eq!(
+            pdf_filename("https://example.com/a/report.pdf?x=1"),
+            "report.pdf"
+        );
+        assert_eq!(pdf_filename("https://example.com/download"), "download.pdf");
+        assert_eq!(pdf_filename("https://example.com/"), "document.pdf");
+        assert_eq!(
+            pdf_filename("https://example.com/My%20Paper.pdf"),
+            "My Paper.pdf"
+        );
+    }
+
+    #[test]
+    fn test_extract_pdf_metadata_info_dictionary() {
+        let pdf = b"%PDF-1.4\n1 0 obj\n<< /Type /Catalog >>\nendobj\n\
+            2 0 obj\n<< /Title (Opinion \\(Final\\)) /Author <FEFF004A0061006E0065> >>\nendobj\n\
+            trailer\n<< /Root 1 0 R /Info 2 0 R >>\n%%EOF";
+        let meta = extract_pdf_metadata(pdf);
+        assert_eq!(meta.title.as_deref(), Some("Opinion (Final)"));
+        assert_eq!(meta.author.as_deref(), Some("Jane"));
+    }
+
+    #[test]
+    fn test_extract_pdf_metadata_ignores_outline_titles() {
+        let pdf = b"%PDF-1.4\n3 0 obj\n<< /Title (Chapter 1) /Parent 4 0 R >>\nendobj\n\
+            12 0 obj\n<< /Author (Court) >>\nendobj\n\
+            trailer\n<< /Info 12 0 R >>\n%%EOF";
+        let meta = extract_pdf_metadata(pdf);
+        assert_eq!(meta.title, None);
+        assert_eq!(meta.author.as_deref(), Some("Court"));
+    }
+
+    #[test]
+    fn test_extract_pdf_metadata_xmp_fallback() {
+        let pdf = b"%PDF-1.7\n<x:xmpmeta><dc:title><rdf:Alt><rdf:li xml:lang=\"x-default\">Q&amp;A</rdf:li></rdf:Alt></dc:title>\
+            <dc:creator><rdf:Seq><rdf:li>Ann</rdf:li></rdf:Seq></dc:creator></x:xmpmeta>";
+        let meta = extract_pdf_metadata(pdf);
+        assert_eq!(meta.title.as_deref(), Some("Q&A"));
+        assert_eq!(meta.author.as_deref(), Some("Ann"));
+    }
+
+    #[test]
+    fn test_extract_pdf_metadata_none() {
+        assert_eq!(
+            extract_pdf_metadata(b"%PDF-1.4\n%%EOF"),
+            PdfMetadata::default()
+        );
+    }
+}
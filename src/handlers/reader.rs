@@ -0,0 +1,541 @@
+//! Reader-mode extraction of the main article from a page.
+//!
+//! A Readability-style pass over the fetched DOM: paragraphs are scored by
+//! length and comma count, scores flow up to their parent and grandparent,
+//! and the best-scoring container (discounted by link density) is taken as
+//! the article. Navigation, ads and other page chrome are dropped, and the
+//! result is re-serialized as minimal HTML plus plain text for indexing.
+
+use std::collections::HashMap;
+
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+
+/// File name the extracted article is written to in the work directory.
+pub const ARTICLE_FILE: &str = "article.html";
+
+/// Articles with less extracted text than this (in characters) are ignored.
+const MIN_ARTICLE_CHARS: usize = 500;
+
+/// Paragraphs shorter than this don't contribute to their container's score.
+const MIN_PARAGRAPH_CHARS: usize = 25;
+
+/// Elements never part of an article, dropped along with their contents.
+const STRIPPED_TAGS: &[&str] = &[
+    "script", "style", "noscript", "template", "nav", "header", "footer", "aside", "form",
+    "button", "input", "select", "textarea", "iframe", "object", "embed", "svg", "canvas",
+];
+
+/// Elements kept (without attributes, except those in [`kept_attrs`]) in the article HTML.
+/// Everything else is unwrapped to its children.
+const KEPT_TAGS: &[&str] = &[
+    "p",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "ul",
+    "ol",
+    "li",
+    "blockquote",
+    "pre",
+    "code",
+    "em",
+    "strong",
+    "b",
+    "i",
+    "a",
+    "img",
+    "figure",
+    "figcaption",
+    "br",
+    "hr",
+    "table",
+    "thead",
+    "tbody",
+    "tr",
+    "th",
+    "td",
+    "dl",
+    "dt",
+    "dd",
+];
+
+/// Elements that start a new block of text.
+const BLOCK_TAGS: &[&str] = &[
+    "p",
+    "div",
+    "section",
+    "article",
+    "main",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "ul",
+    "ol",
+    "li",
+    "blockquote",
+    "pre",
+    "figure",
+    "figcaption",
+    "table",
+    "tr",
+    "dl",
+    "dt",
+    "dd",
+    "br",
+    "hr",
+];
+
+/// Class/id fragments marking page chrome rather than content.
+static UNLIKELY: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(ad|ads|advert|advertisement|banner|breadcrumbs?|comments?|cookie|footer|header|menu|modal|nav|navbar|newsletter|popup|promo|related|share|sharing|sidebar|social|sponsor(ed)?|subscribe|widget)\b",
+    )
+    .unwrap()
+});
+
+/// Class/id fragments marking the main content.
+static POSITIVE: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| {
+    Regex::new(r"(?i)article|body|content|entry|main|post|story|text").unwrap()
+});
+
+/// The main article of a page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReaderArticle {
+    pub title: Option<String>,
+    pub byline: Option<String>,
+    /// Cleaned article markup (a fragment, not a full document).
+    pub html: String,
+    /// Article text with blocks separated by blank lines.
+    pub text: String,
+}
+
+impl ReaderArticle {
+    /// A standalone HTML document for storing as the `article` artifact.
+    #[must_use]
+    pub fn to_document(&self) -> String {
+        let title = self.title.as_deref().map(html_escape).unwrap_or_default();
+        let mut doc = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n</head>\n<body>\n<article>\n"
+        );
+        if !title.is_empty() {
+            doc.push_str(&format!("<h1>{title}</h1>\n"));
+        }
+        if let Some(ref byline) = self.byline {
+            doc.push_str(&format!(
+                "<p class=\"byline\">{}</p>\n",
+                html_escape(byline)
+            ));
+        }
+        doc.push_str(&self.html);
+        doc.push_str("\n</article>\n</body>\n</html>\n");
+        doc
+    }
+}
+
+/// Extract the main article from `html`.
+///
+/// Returns `None` when no container holds at least [`MIN_ARTICLE_CHARS`] of
+/// text, e.g. for index pages, media pages or app shells.
+#[must_use]
+pub fn extract_article(html: &str) -> Option<ReaderArticle> {
+    let document = Html::parse_document(html);
+    let top = top_candidate(&document)?;
+
+    let mut out = Rendered::default();
+    render_children(top, &mut out);
+    let text = out.text();
+    if text.chars().count() < MIN_ARTICLE_CHARS {
+        return None;
+    }
+
+    Some(ReaderArticle {
+        title: extract_title(&document),
+        byline: extract_byline(&document),
+        html: out.html.trim().to_string(),
+        text,
+    })
+}
+
+/// The highest-scoring content container.
+fn top_candidate(document: &Html) -> Option<ElementRef<'_>> {
+    let selector = Selector::parse("p, pre, td").ok()?;
+    let mut scores = HashMap::new();
+
+    for paragraph in document.select(&selector) {
+        if is_excluded(paragraph) {
+            continue;
+        }
+        let text = clean_text(&paragraph.text().collect::<String>());
+        let len = text.chars().count();
+        if len < MIN_PARAGRAPH_CHARS {
+            continue;
+        }
+        let score = 1.0 + text.matches(',').count() as f64 + (len / 100).min(3) as f64;
+
+        let mut ancestors = paragraph.ancestors().filter_map(ElementRef::wrap);
+        for (level, divider) in [(0, 1.0), (1, 2.0)] {
+            let Some(ancestor) = ancestors.next() else {
+                break;
+            };
+            if level == 0 || ancestor.value().name() != "body" {
+                *scores
+                    .entry(ancestor.id())
+                    .or_insert_with(|| initial_score(ancestor)) += score / divider;
+            }
+        }
+    }
+
+    scores
+        .into_iter()
+        .filter_map(|(id, score)| {
+            let element = document.tree.get(id).and_then(ElementRef::wrap)?;
+            Some((element, score * (1.0 - link_density(element))))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(element, _)| element)
+}
+
+/// Starting score for a container, from its tag and class/id names.
+fn initial_score(element: ElementRef<'_>) -> f64 {
+    let tag_score = match element.value().name() {
+        "article" => 10.0,
+        "div" | "main" | "section" => 5.0,
+        "pre" | "td" | "blockquote" => 3.0,
+        "ol" | "ul" | "dl" | "li" | "form" => -3.0,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+        _ => 0.0,
+    };
+    tag_score + class_weight(element)
+}
+
+/// +25 for content-like class/id names, -25 for chrome-like ones.
+fn class_weight(element: ElementRef<'_>) -> f64 {
+    let mut weight = 0.0;
+    for name in [element.value().attr("class"), element.value().id()]
+        .into_iter()
+        .flatten()
+    {
+        if UNLIKELY.is_match(name) {
+            weight -= 25.0;
+        }
+        if POSITIVE.is_match(name) {
+            weight += 25.0;
+        }
+    }
+    weight
+}
+
+/// Whether an element is page chrome by tag or by an unlikely class/id.
+fn is_chrome(element: ElementRef<'_>) -> bool {
+    let value = element.value();
+    if STRIPPED_TAGS.contains(&value.name()) {
+        return true;
+    }
+    if value.attr("aria-hidden") == Some("true") || value.attr("hidden").is_some() {
+        return true;
+    }
+    matches!(
+        value.attr("role"),
+        Some("navigation" | "banner" | "complementary")
+    ) || [value.attr("class"), value.id()]
+        .into_iter()
+        .flatten()
+        .any(|name| UNLIKELY.is_match(name) && !POSITIVE.is_match(name))
+}
+
+/// Whether an element is, or sits inside, page chrome.
+fn is_excluded(element: ElementRef<'_>) -> bool {
+    is_chrome(element)
+        || element
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .any(is_chrome)
+}
+
+/// Fraction of an element's text that sits inside links.
+fn link_density(element: ElementRef<'_>) -> f64 {
+    let total = clean_text(&element.text().collect::<String>())
+        .chars()
+        .count();
+    if total == 0 {
+        return 0.0;
+    }
+    let Ok(selector) = Selector::parse("a") else {
+        return 0.0;
+    };
+    let linked: usize = element
+        .select(&selector)
+        .map(|a| clean_text(&a.text().collect::<String>()).chars().count())
+        .sum();
+    (linked as f64 / total as f64).min(1.0)
+}
+
+/// Article markup and text built up while walking the chosen container.
+#[derive(Default)]
+struct Rendered {
+    html: String,
+    text: String,
+}
+
+impl Rendered {
+    /// Text with whitespace collapsed inside blocks and blank lines between them.
+    fn text(&self) -> String {
+        self.text
+            .split("\n\n")
+            .map(clean_text)
+            .filter(|block| !block.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+fn render_children(element: ElementRef<'_>, out: &mut Rendered) {
+    for child in element.children() {
+        if let Some(text) = child.value().as_text() {
+            out.html.push_str(&html_escape(text));
+            out.text.push_str(text);
+        } else if let Some(child) = ElementRef::wrap(child) {
+            render_element(child, out);
+        }
+    }
+}
+
+fn render_element(element: ElementRef<'_>, out: &mut Rendered) {
+    if is_chrome(element) {
+        return;
+    }
+    let name = element.value().name();
+    let block = BLOCK_TAGS.contains(&name);
+    if block {
+        out.text.push_str("\n\n");
+    }
+
+    if KEPT_TAGS.contains(&name) {
+        out.html.push('<');
+        out.html.push_str(name);
+        for attr in kept_attrs(name) {
+            if let Some(value) = element.value().attr(attr) {
+                out.html
+                    .push_str(&format!(" {attr}=\"{}\"", html_escape(value)));
+            }
+        }
+        out.html.push('>');
+        if !matches!(name, "br" | "hr" | "img") {
+            render_children(element, out);
+            out.html.push_str(&format!("</{name}>"));
+        }
+    } else {
+        render_children(element, out);
+    }
+
+    if block {
+        out.text.push_str("\n\n");
+        out.html.push('\n');
+    }
+}
+
+/// Attributes worth keeping on a kept element.
+fn kept_attrs(name: &str) -> &'static [&'static str] {
+    match name {
+        "a" => &["href"],
+        "img" => &["src", "alt"],
+        "td" | "th" => &["colspan", "rowspan"],
+        _ => &[],
+    }
+}
+
+/// Article title: `og:title`, then the first `<h1>`, then `<title>`.
+fn extract_title(document: &Html) -> Option<String> {
+    let meta = Selector::parse("meta[property='og:title']").ok()?;
+    if let Some(content) = document
+        .select(&meta)
+        .find_map(|e| e.value().attr("content"))
+        .map(clean_text)
+        .filter(|t| !t.is_empty())
+    {
+        return Some(content);
+    }
+    ["h1", "title"].into_iter().find_map(|tag| {
+        let selector = Selector::parse(tag).ok()?;
+        document
+            .select(&selector)
+            .map(|e| clean_text(&e.text().collect::<String>()))
+            .find(|t| !t.is_empty())
+    })
+}
+
+/// Article author from author meta tags or common byline markup.
+fn extract_byline(document: &Html) -> Option<String> {
+    const META: &[&str] = &[
+        "meta[name='author']",
+        "meta[property='article:author']",
+        "meta[name='byl']",
+    ];
+    const MARKUP: &[&str] = &[
+        "[rel='author']",
+        "[itemprop='author']",
+        ".byline",
+        ".author",
+    ];
+
+    let meta = META.iter().find_map(|sel| {
+        let selector = Selector::parse(sel).ok()?;
+        document
+            .select(&selector)
+            .filter_map(|e| e.value().attr("content"))
+            .map(clean_text)
+            // article:author is often a profile URL
+            .find(|a| !a.is_empty() && !a.starts_with("http"))
+    });
+    meta.or_else(|| {
+        MARKUP.iter().find_map(|sel| {
+            let selector = Selector::parse(sel).ok()?;
+            document
+                .select(&selector)
+                .map(|e| clean_text(&e.text().collect::<String>()))
+                .find(|a| !a.is_empty() && a.chars().count() <= 100)
+        })
+    })
+    .map(|a| a.strip_prefix("By ").map(str::to_string).unwrap_or(a))
+}
+
+fn clean_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Write as _;
+
+    use super::*;
+
+    const PARAGRAPH: &str = "The council voted on Tuesday to approve the new budget, which \
+        increases funding for public libraries, parks, and road maintenance across the \
+        district, while trimming administrative costs.";
+
+    fn sample_article() -> String {
+        let mut paragraphs = String::new();
+        for i in 1..=4 {
+            let _ = writeln!(paragraphs, "<p>{i}. {PARAGRAPH}</p>");
+        }
+        format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+  <title>Council approves budget | Example News</title>
+  <meta name="author" content="Jane Reporter">
+  <script>var tracking = "do not index";</script>
+</head>
+<body>
+  <header class="site-header"><a href="/">Example News</a></header>
+  <nav class="main-nav">
+    <ul><li><a href="/world">World</a></li><li><a href="/sport">Sport</a></li></ul>
+  </nav>
+  <div class="ad-slot"><p>Buy one, get one free at our sponsor's store, today only, hurry!</p></div>
+  <div id="page">
+    <div class="article-body">
+      <h1>Council approves budget</h1>
+      {paragraphs}
+      <div class="share-buttons"><a href="/share">Share this story on social media</a></div>
+    </div>
+    <aside class="sidebar">
+      <p>Related: Ten things you didn't know about libraries, parks, and more.</p>
+    </aside>
+  </div>
+  <footer><p>Copyright Example News, all rights reserved, 2024.</p></footer>
+</body>
+</html>"#
+        )
+    }
+
+    #[test]
+    fn test_extract_article_picks_main_content() {
+        let article = extract_article(&sample_article()).expect("article extracted");
+
+        assert_eq!(article.title.as_deref(), Some("Council approves budget"));
+        assert_eq!(article.byline.as_deref(), Some("Jane Reporter"));
+        assert!(article.text.contains("1. The council voted"));
+        assert!(article.text.contains("4. The council voted"));
+        assert!(article.html.contains("<p>1. The council voted"));
+
+        for chrome in [
+            "Example News",
+            "World",
+            "sponsor's store",
+            "Share this story",
+            "Ten things",
+            "Copyright",
+            "do not index",
+        ] {
+            assert!(!article.text.contains(chrome), "{chrome} leaked into text");
+            assert!(!article.html.contains(chrome), "{chrome} leaked into html");
+        }
+        assert!(!article.html.contains("class="));
+    }
+
+    #[test]
+    fn test_extract_article_separates_blocks() {
+        let article = extract_article(&sample_article()).unwrap();
+        let blocks: Vec<&str> = article.text.split("\n\n").collect();
+        assert_eq!(blocks[0], "Council approves budget");
+        assert!(blocks[1].starts_with("1. "));
+        assert_eq!(blocks.len(), 5);
+    }
+
+    #[test]
+    fn test_extract_article_requires_substantial_text() {
+        let html = format!("<html><body><article><p>{PARAGRAPH}</p></article></body></html>");
+        assert_eq!(extract_article(&html), None);
+        assert_eq!(extract_article("<html><body></body></html>"), None);
+    }
+
+    #[test]
+    fn test_extract_article_discounts_link_lists() {
+        let mut links = String::new();
+        for i in 0..20 {
+            let _ = write!(
+                links,
+                "<p><a href=\"/{i}\">Another headline about the budget, parks and roads {i}</a></p>"
+            );
+        }
+        let mut body = String::new();
+        for _ in 0..4 {
+            let _ = write!(body, "<p>{PARAGRAPH}</p>");
+        }
+        let html = format!(
+            "<html><body><div id=\"links\">{links}</div><div id=\"story\">{body}</div></body></html>"
+        );
+
+        let article = extract_article(&html).unwrap();
+        assert!(article.text.starts_with("The council voted"));
+        assert!(!article.text.contains("Another headline"));
+    }
+
+    #[test]
+    fn test_to_document_escapes_title() {
+        let article = ReaderArticle {
+            title: Some("Fish & <Chips>".to_string()),
+            byline: Some("A. Writer".to_string()),
+            html: "<p>Body</p>".to_string(),
+            text: "Body".to_string(),
+        };
+        let doc = article.to_document();
+        assert!(doc.contains("<title>Fish &amp; &lt;Chips&gt;</title>"));
+        assert!(doc.contains("<p class=\"byline\">A. Writer</p>"));
+        assert!(doc.contains("<p>Body</p>"));
+    }
+}
@@ -11,7 +11,7 @@ use tracing::{debug, info, warn};
 use crate::chromium_profile::fetch_html_with_chromium;
 
 use super::traits::{ArchiveResult, SiteHandler};
-use crate::archiver::{ytdlp, CookieOptions};
+use crate::archiver::{ytdlp, ArchiveError, CookieOptions};
 use crate::constants::ARCHIVAL_USER_AGENT;
 
 static PATTERNS: std::sync::LazyLock<Vec<Regex>> = std::sync::LazyLock::new(|| {
@@ -378,10 +378,12 @@ async fn fetch_reddit_html(
     // Fallback: cookies.txt via reqwest (fast, but less reliable for Reddit)
     let cookie_header = build_cookie_header(cookies, "reddit.com");
     if cookie_header.is_none() {
-        anyhow::bail!(
+        return Err(ArchiveError::AuthRequired(
             "Reddit HTML fetch requires authenticated cookies (datacenter IPs are often blocked). \
 Configure either YT_DLP_COOKIES_FROM_BROWSER (recommended) or COOKIES_FILE_PATH for reddit.com."
-        );
+                .to_string(),
+        )
+        .into());
     }
 
     let client = reqwest::Client::builder()
@@ -400,7 +402,11 @@ Configure either YT_DLP_COOKIES_FROM_BROWSER (recommended) or COOKIES_FILE_PATH
 
     let status = response.status();
     if !status.is_success() {
-        anyhow::bail!("Reddit returned HTTP status {status}");
+        return Err(ArchiveError::from_status(
+            status,
+            format!("Reddit returned HTTP status {status}"),
+        )
+        .into());
     }
 
     let html = response
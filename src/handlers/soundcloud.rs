@@ -0,0 +1,149 @@
+use std::path::Path;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use regex::Regex;
+
+use super::traits::{ArchiveResult, SiteHandler};
+use crate::archiver::{ytdlp, CookieOptions};
+
+static PATTERNS: std::sync::LazyLock<Vec<Regex>> = std::sync::LazyLock::new(|| {
+    vec![
+        // Track pages: soundcloud.com/<user>/<track>, optionally with a private share token
+        Regex::new(
+            r"^https?://(www\.|m\.)?soundcloud\.com/([A-Za-z0-9_-]+)/([A-Za-z0-9_-]+)(/s-[A-Za-z0-9]+)?/?([?#].*)?$",
+        )
+        .unwrap(),
+    ]
+});
+
+/// Top-level SoundCloud paths that are site pages rather than users.
+const RESERVED_USERS: &[&str] = &[
+    "charts", "discover", "pages", "search", "settings", "stream", "tags", "upload", "you",
+];
+
+/// Second path segments that are profile tabs or playlists rather than tracks.
+const RESERVED_TRACKS: &[&str] = &[
+    "albums",
+    "comments",
+    "followers",
+    "following",
+    "likes",
+    "popular-tracks",
+    "reposts",
+    "sets",
+    "tracks",
+];
+
+pub struct SoundCloudHandler;
+
+impl SoundCloudHandler {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for SoundCloudHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SiteHandler for SoundCloudHandler {
+    fn site_id(&self) -> &'static str {
+        "soundcloud"
+    }
+
+    fn url_patterns(&self) -> &[Regex] {
+        &PATTERNS
+    }
+
+    fn can_handle(&self, url: &str) -> bool {
+        PATTERNS[0].captures(url).is_some_and(|caps| {
+            !RESERVED_USERS.contains(&&caps[2]) && !RESERVED_TRACKS.contains(&&caps[3])
+        })
+    }
+
+    fn priority(&self) -> i32 {
+        100
+    }
+
+    fn normalize_url(&self, url: &str) -> String {
+        // Normalize mobile and www hosts to soundcloud.com
+        let normalized = url
+            .replace("http://", "https://")
+            .replace("://www.soundcloud.com/", "://soundcloud.com/")
+            .replace("://m.soundcloud.com/", "://soundcloud.com/");
+
+        // Remove query parameters (share tracking, `in=` playlist context) and trailing slashes
+        let without_query = normalized.split(['?', '#']).next().unwrap_or(&normalized);
+
+        without_query.trim_end_matches('/').to_string()
+    }
+
+    async fn archive(
+        &self,
+        url: &str,
+        work_dir: &Path,
+        cookies: &CookieOptions<'_>,
+        config: &crate::config::Config,
+    ) -> Result<ArchiveResult> {
+        ytdlp::download_audio(url, work_dir, cookies, config).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_can_handle() {
+        let handler = SoundCloudHandler::new();
+
+        assert!(handler.can_handle("https://soundcloud.com/artist/some-track"));
+        assert!(handler.can_handle("https://www.soundcloud.com/artist/some-track"));
+        assert!(handler.can_handle("https://m.soundcloud.com/artist/some-track"));
+        assert!(handler.can_handle("http://soundcloud.com/artist/some-track/"));
+        assert!(handler.can_handle("https://soundcloud.com/artist/some-track?si=abc&utm_source=x"));
+        assert!(handler.can_handle("https://soundcloud.com/artist/some-track/s-AbC123"));
+
+        assert!(!handler.can_handle("https://soundcloud.com/artist"));
+        assert!(!handler.can_handle("https://soundcloud.com/artist/sets/my-playlist"));
+        assert!(!handler.can_handle("https://soundcloud.com/artist/likes"));
+        assert!(!handler.can_handle("https://soundcloud.com/discover/sets"));
+        assert!(!handler.can_handle("https://example.com/artist/some-track"));
+    }
+
+    #[test]
+    fn test_site_id() {
+        let handler = SoundCloudHandler::new();
+        assert_eq!(handler.site_id(), "soundcloud");
+    }
+
+    #[test]
+    fn test_priority() {
+        let handler = SoundCloudHandler::new();
+        assert_eq!(handler.priority(), 100);
+    }
+
+    #[test]
+    fn test_normalize_url() {
+        let handler = SoundCloudHandler::new();
+
+        assert_eq!(
+            handler.normalize_url("https://www.soundcloud.com/artist/some-track"),
+            "https://soundcloud.com/artist/some-track"
+        );
+        assert_eq!(
+            handler.normalize_url("http://m.soundcloud.com/artist/some-track/"),
+            "https://soundcloud.com/artist/some-track"
+        );
+        assert_eq!(
+            handler
+                .normalize_url("https://soundcloud.com/artist/some-track?in=artist/sets/mix&si=1"),
+            "https://soundcloud.com/artist/some-track"
+        );
+    }
+}
@@ -1,17 +1,46 @@
+use std::collections::HashMap;
 use std::path::Path;
+use std::time::Duration;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
 use regex::Regex;
-use tracing::debug;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use tracing::{debug, warn};
 
 use super::traits::{ArchiveResult, SiteHandler};
-use crate::archiver::{ytdlp, CookieOptions};
+use crate::archiver::{ytdlp, ArchiveError, CookieOptions};
+use crate::constants::ARCHIVAL_USER_AGENT;
 
 static PATTERNS: std::sync::LazyLock<Vec<Regex>> = std::sync::LazyLock::new(|| {
     vec![Regex::new(r"^https?://(www\.)?streamable\.com/[a-zA-Z0-9]+").unwrap()]
 });
 
+// Embed (/e/), original (/o/) and share (/s/) URLs wrap the same video ID
+static PREFIXED_URL: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| {
+    Regex::new(r"^https?://(?:www\.)?streamable\.com/(?:e|o|s)/([a-zA-Z0-9]+)").unwrap()
+});
+
+const STREAMABLE_API_BASE: &str = "https://api.streamable.com";
+
+/// Response from the `/videos/<id>` API (the same JSON the embed player uses).
+#[derive(Debug, Deserialize)]
+struct StreamableVideo {
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    thumbnail_url: Option<String>,
+    #[serde(default)]
+    files: HashMap<String, StreamableFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamableFile {
+    #[serde(default)]
+    url: Option<String>,
+}
+
 pub struct StreamableHandler;
 
 impl StreamableHandler {
@@ -41,6 +70,16 @@ impl SiteHandler for StreamableHandler {
         100
     }
 
+    fn normalize_url(&self, url: &str) -> String {
+        if let Some(caps) = PREFIXED_URL.captures(url) {
+            return format!("https://streamable.com/{}", &caps[1]);
+        }
+
+        let normalized = url.replace("://www.streamable.com/", "://streamable.com/");
+        let normalized = normalized.split(['?', '#']).next().unwrap_or_default();
+        normalized.trim_end_matches('/').to_string()
+    }
+
     async fn archive(
         &self,
         url: &str,
@@ -48,10 +87,18 @@ impl SiteHandler for StreamableHandler {
         cookies: &CookieOptions<'_>,
         config: &crate::config::Config,
     ) -> Result<ArchiveResult> {
-        let mut result = ytdlp::download(url, work_dir, cookies, config, None, None, false).await?;
+        let url = self.normalize_url(url);
+        if let Some(video_id) = extract_video_id(&url) {
+            if let Some(result) = archive_direct(STREAMABLE_API_BASE, &video_id, work_dir).await? {
+                return Ok(result);
+            }
+        }
+
+        let mut result =
+            ytdlp::download(&url, work_dir, cookies, config, None, None, false).await?;
 
         // Extract video_id for deduplication
-        if let Some(video_id) = extract_video_id(url) {
+        if let Some(video_id) = extract_video_id(&url) {
             debug!(video_id = %video_id, "Extracted Streamable video ID");
             result.video_id = Some(video_id);
         }
@@ -60,6 +107,116 @@ impl SiteHandler for StreamableHandler {
     }
 }
 
+/// Download the MP4 and poster straight from Streamable's CDN.
+///
+/// Returns `None` when the API lists no MP4 or the download fails, so the
+/// caller can fall back to yt-dlp.
+///
+/// # Errors
+///
+/// Returns an error if the video was removed (HTTP 404/410) or the API
+/// request fails.
+async fn archive_direct(
+    api_base: &str,
+    video_id: &str,
+    work_dir: &Path,
+) -> Result<Option<ArchiveResult>> {
+    let client = reqwest::Client::builder()
+        .user_agent(ARCHIVAL_USER_AGENT)
+        .timeout(Duration::from_secs(120))
+        .build()
+        .context("Failed to build HTTP client")?;
+
+    let response = client
+        .get(format!("{api_base}/videos/{video_id}"))
+        .send()
+        .await
+        .context("Failed to fetch Streamable video info")?;
+    if matches!(response.status(), StatusCode::NOT_FOUND | StatusCode::GONE) {
+        return Err(ArchiveError::NotFound(format!(
+            "Streamable video {video_id} was removed (HTTP {})",
+            response.status().as_u16()
+        ))
+        .into());
+    }
+    let body = response
+        .error_for_status()
+        .context("Streamable video info returned error")?
+        .text()
+        .await
+        .context("Failed to read Streamable video info")?;
+    let video: StreamableVideo =
+        serde_json::from_str(&body).context("Failed to parse Streamable video info")?;
+
+    let Some(mp4_url) = video
+        .files
+        .get("mp4")
+        .or_else(|| video.files.get("mp4-mobile"))
+        .and_then(|f| f.url.as_deref())
+        .map(absolute_cdn_url)
+    else {
+        debug!(
+            video_id,
+            "Streamable API listed no MP4, falling back to yt-dlp"
+        );
+        return Ok(None);
+    };
+
+    let filename = format!("{video_id}.mp4");
+    if let Err(e) = download_file(&client, &mp4_url, &work_dir.join(&filename)).await {
+        warn!(video_id, error = %e, "Direct Streamable download failed, falling back to yt-dlp");
+        return Ok(None);
+    }
+
+    let mut thumbnail = None;
+    if let Some(poster_url) = video.thumbnail_url.as_deref().map(absolute_cdn_url) {
+        match download_file(&client, &poster_url, &work_dir.join("thumb.jpg")).await {
+            Ok(()) => thumbnail = Some("thumb.jpg".to_string()),
+            Err(e) => warn!(video_id, error = %e, "Failed to download Streamable poster"),
+        }
+    }
+
+    tokio::fs::write(work_dir.join("metadata.json"), &body)
+        .await
+        .context("Failed to write metadata file")?;
+
+    Ok(Some(ArchiveResult {
+        title: video.title.filter(|t| !t.is_empty()),
+        content_type: "video".to_string(),
+        primary_file: Some(filename),
+        thumbnail,
+        extra_files: vec!["metadata.json".to_string()],
+        metadata_json: Some(body),
+        video_id: Some(video_id.to_string()),
+        ..Default::default()
+    }))
+}
+
+/// The API returns protocol-relative CDN URLs (`//cdn-cf-east.streamable.com/...`).
+fn absolute_cdn_url(url: &str) -> String {
+    if url.starts_with("//") {
+        format!("https:{url}")
+    } else {
+        url.to_string()
+    }
+}
+
+async fn download_file(client: &reqwest::Client, url: &str, path: &Path) -> Result<()> {
+    let bytes = client
+        .get(url)
+        .send()
+        .await
+        .context("Failed to download file")?
+        .error_for_status()
+        .context("File download returned error")?
+        .bytes()
+        .await
+        .context("Failed to read file bytes")?;
+    tokio::fs::write(path, &bytes)
+        .await
+        .context("Failed to write file")
+}
+
 /// Extract video ID from Streamable URL.
 ///
 /// Streamable URLs have format: `https://streamable.com/{video_id}`
@@ -105,6 +262,102 @@ mod tests {
         assert_eq!(handler.priority(), 100);
     }
 
+    #[test]
+    fn test_normalize_url() {
+        let handler = StreamableHandler::new();
+
+        assert_eq!(
+            handler.normalize_url("https://www.streamable.com/abc123?src=player"),
+            "https://streamable.com/abc123"
+        );
+        assert_eq!(
+            handler.normalize_url("https://streamable.com/e/abc123"),
+            "https://streamable.com/abc123"
+        );
+        assert_eq!(
+            handler.normalize_url("https://streamable.com/o/abc123/"),
+            "https://streamable.com/abc123"
+        );
+        assert_eq!(
+            handler.normalize_url("https://streamable.com/s/abc123/xyzdef"),
+            "https://streamable.com/abc123"
+        );
+        assert_eq!(
+            handler.normalize_url("https://streamable.com/abc123/"),
+            "https://streamable.com/abc123"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_archive_direct_downloads_mp4_and_poster() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/videos/abc123"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "status": 2,
+                "title": "Great goal",
+                "thumbnail_url": format!("{}/image/abc123.jpg", server.uri()),
+                "files": {"mp4": {"url": format!("{}/video/mp4/abc123.mp4?token=t", server.uri())}}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/video/mp4/abc123.mp4"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"mp4".to_vec()))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/image/abc123.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"jpg".to_vec()))
+            .mount(&server)
+            .await;
+
+        let work_dir = tempfile::TempDir::new().unwrap();
+        let result = archive_direct(&server.uri(), "abc123", work_dir.path())
+            .await
+            .unwrap()
+            .expect("MP4 should be downloaded directly");
+
+        assert_eq!(result.content_type, "video");
+        assert_eq!(result.title.as_deref(), Some("Great goal"));
+        assert_eq!(result.primary_file.as_deref(), Some("abc123.mp4"));
+        assert_eq!(result.thumbnail.as_deref(), Some("thumb.jpg"));
+        assert_eq!(
+            std::fs::read(work_dir.path().join("abc123.mp4")).unwrap(),
+            b"mp4"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_removed_video_is_skipped() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/videos/gone12"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let work_dir = tempfile::TempDir::new().unwrap();
+        let err = archive_direct(&server.uri(), "gone12", work_dir.path())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            crate::archiver::classify_failure(&format!("{err:#}"), None, false),
+            (_, crate::archiver::FailureClass::Permanent(_))
+        ));
+        assert_eq!(
+            crate::archiver::error_kind(&err),
+            crate::archiver::ArchiveErrorKind::NotFound
+        );
+    }
+
     #[test]
     fn test_extract_video_id() {
         assert_eq!(
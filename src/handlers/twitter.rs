@@ -5,11 +5,12 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use regex::Regex;
 use reqwest::header;
+use scraper::{Html, Selector};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
 use super::traits::{ArchiveResult, SiteHandler};
-use crate::archiver::{gallerydl, ytdlp, CookieOptions};
+use crate::archiver::{gallerydl, ytdlp, ArchiveError, CookieOptions};
 use crate::chromium_profile::fetch_html_with_chromium;
 use crate::constants::ARCHIVAL_USER_AGENT;
 use crate::og_extractor::extract_og_metadata;
@@ -27,6 +28,15 @@ static PATTERNS: std::sync::LazyLock<Vec<Regex>> = std::sync::LazyLock::new(|| {
 static TWEET_ID_PATTERN: std::sync::LazyLock<Regex> =
     std::sync::LazyLock::new(|| Regex::new(r"/status/(\d+)").unwrap());
 
+/// Pattern to extract the ID from a Space or live broadcast URL.
+static SPACE_ID_PATTERN: std::sync::LazyLock<Regex> =
+    std::sync::LazyLock::new(|| Regex::new(r"/i/(?:spaces|broadcasts)/([A-Za-z0-9]+)").unwrap());
+
+/// Pattern to extract the author and tweet ID from a tweet permalink href.
+static STATUS_HREF_PATTERN: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| {
+    Regex::new(r"^(?:https?://[^/]+)?/([A-Za-z0-9_]+)/status/(\d+)").unwrap()
+});
+
 /// Metadata extracted from Twitter/X content.
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct TwitterMetadata {
@@ -114,6 +124,25 @@ impl SiteHandler for TwitterHandler {
 
         debug!(url = %normalized_url, tweet_id = ?tweet_id, "Archiving Twitter/X content");
 
+        // Spaces and live broadcasts have no tweet page; yt-dlp records them
+        // once they have ended (while live, the archive is deferred)
+        if let Some(space_id) = extract_space_id(&normalized_url) {
+            debug!(url = %normalized_url, space_id = %space_id, "Twitter Space/broadcast URL");
+            let mut result = ytdlp::download(
+                &normalized_url,
+                work_dir,
+                cookies,
+                config,
+                None,
+                None,
+                false,
+            )
+            .await?;
+            result.video_id = Some(format!("twitter_space_{space_id}"));
+            result.final_url = Some(normalized_url);
+            return Ok(result);
+        }
+
         // Start with an empty result - we'll populate it based on what we find
         let mut result = ArchiveResult::default();
 
@@ -159,6 +188,16 @@ impl SiteHandler for TwitterHandler {
             }
         }
 
+        if html_content
+            .as_deref()
+            .is_some_and(is_protected_account_html)
+        {
+            return Err(ArchiveError::AuthRequired(format!(
+                "Protected account: login required to view these posts ({normalized_url})"
+            ))
+            .into());
+        }
+
         // Step 2: Detect what type of media is in the HTML
         let media_type = html_content
             .as_ref()
@@ -213,6 +252,28 @@ impl SiteHandler for TwitterHandler {
             }
         }
 
+        // Step 5: Record the author's follow-up tweets so the worker can archive
+        // and chain them as a thread
+        if let (Some(html), Some(tid)) = (html_content.as_deref(), tweet_id.as_deref()) {
+            let thread_urls =
+                extract_self_thread_urls(html, tid, config.twitter_max_thread_length as usize);
+            if !thread_urls.is_empty() {
+                debug!(url = %normalized_url, count = thread_urls.len(), "Found self-thread replies");
+                let mut metadata: serde_json::Value = result
+                    .metadata_json
+                    .as_deref()
+                    .and_then(|json| serde_json::from_str(json).ok())
+                    .unwrap_or_else(|| serde_json::json!({}));
+                if let Some(obj) = metadata.as_object_mut() {
+                    obj.insert(
+                        "self_thread_urls".to_string(),
+                        serde_json::json!(thread_urls),
+                    );
+                    result.metadata_json = Some(metadata.to_string());
+                }
+            }
+        }
+
         // Ensure we have a primary file (either HTML or media)
         if result.primary_file.is_none() {
             // No HTML and no media - this is a failure
@@ -223,6 +284,16 @@ impl SiteHandler for TwitterHandler {
     }
 }
 
+/// Extract the ID from a Twitter Space (`/i/spaces/<id>`) or live broadcast
+/// (`/i/broadcasts/<id>`) URL.
+#[must_use]
+pub fn extract_space_id(url: &str) -> Option<String> {
+    SPACE_ID_PATTERN
+        .captures(url)
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str().to_string())
+}
+
 /// Archive Twitter media based on detected media type.
 ///
 /// - Videos and GIFs → yt-dlp (handles video content)
@@ -796,6 +867,64 @@ fn detect_deleted_quoted_tweet_from_html(html: &str) -> bool {
         || html_lower.contains("tombstone")
 }
 
+/// Detect a protected account's placeholder page in an HTML snapshot.
+///
+/// Protected posts render a "These posts are protected" notice instead of
+/// any tweet article when fetched without a follower's session.
+fn is_protected_account_html(html: &str) -> bool {
+    let html_lower = html.to_ascii_lowercase();
+
+    (html_lower.contains("posts are protected") || html_lower.contains("tweets are protected"))
+        && !html_lower.contains("data-testid=\"tweet\"")
+}
+
+/// Extract the author's self-thread replies that follow a tweet.
+///
+/// Walks the conversation's tweet articles in page order, starts after the
+/// focal tweet and keeps consecutive tweets by the same author, stopping at
+/// the first reply from anyone else or after `max` tweets.
+///
+/// # Returns
+/// Canonical `https://x.com/{user}/status/{id}` URLs, oldest first.
+pub fn extract_self_thread_urls(html: &str, focal_tweet_id: &str, max: usize) -> Vec<String> {
+    if max == 0 {
+        return Vec::new();
+    }
+
+    let document = Html::parse_document(html);
+    let article_sel = Selector::parse(r#"article[data-testid="tweet"]"#).unwrap();
+    let link_sel = Selector::parse(r#"a[href*="/status/"]"#).unwrap();
+    let time_sel = Selector::parse("time").unwrap();
+
+    // The permalink is the status link wrapping the tweet's timestamp
+    let tweets = document.select(&article_sel).filter_map(|article| {
+        article
+            .select(&link_sel)
+            .find(|a| a.select(&time_sel).next().is_some())
+            .and_then(|a| a.value().attr("href"))
+            .and_then(|href| STATUS_HREF_PATTERN.captures(href))
+            .map(|caps| (caps[1].to_string(), caps[2].to_string()))
+    });
+
+    let mut author: Option<String> = None;
+    let mut urls = Vec::new();
+    for (user, id) in tweets {
+        match &author {
+            None if id == focal_tweet_id => author = Some(user),
+            None => {}
+            Some(focal_author) if focal_author.eq_ignore_ascii_case(&user) => {
+                urls.push(format!("https://x.com/{user}/status/{id}"));
+                if urls.len() >= max {
+                    break;
+                }
+            }
+            Some(_) => break,
+        }
+    }
+
+    urls
+}
+
 /// Format combined tweet text for quote tweets.
 ///
 /// Creates a markdown-formatted string showing both the outer tweet and quoted tweet:
@@ -980,7 +1109,7 @@ pub async fn fetch_tweet_html(url: &str, cookies: &CookieOptions<'_>) -> Result<
 
     let status = response.status();
     if status.as_u16() == 429 {
-        anyhow::bail!("Rate limited (429)");
+        return Err(ArchiveError::RateLimited("Rate limited (429)".to_string()).into());
     }
     if !status.is_success() {
         anyhow::bail!("HTTP error: {status}");
@@ -1219,10 +1348,10 @@ async fn fetch_html_from_url(client: &reqwest::Client, url: &str) -> Result<Stri
 
     let status = response.status();
     if status.as_u16() == 429 {
-        anyhow::bail!("Rate limited (429)");
+        return Err(ArchiveError::RateLimited("Rate limited (429)".to_string()).into());
     }
     if status.as_u16() == 404 {
-        anyhow::bail!("Not found (404)");
+        return Err(ArchiveError::NotFound("Not found (404)".to_string()).into());
     }
     if !status.is_success() {
         anyhow::bail!("HTTP error: {status}");
@@ -1294,6 +1423,19 @@ mod tests {
         assert_eq!(extract_tweet_id("https://x.com/"), None);
     }
 
+    #[test]
+    fn test_extract_space_id() {
+        assert_eq!(
+            extract_space_id("https://x.com/i/spaces/1yNGaNPmBWkxj"),
+            Some("1yNGaNPmBWkxj".to_string())
+        );
+        assert_eq!(
+            extract_space_id("https://twitter.com/i/broadcasts/1OwxWzDlVXQKQ?s=20"),
+            Some("1OwxWzDlVXQKQ".to_string())
+        );
+        assert_eq!(extract_space_id("https://x.com/user/status/123"), None);
+    }
+
     #[test]
     fn test_get_nitter_url() {
         assert_eq!(
@@ -1997,4 +2139,46 @@ mod tests {
         );
         assert_eq!(metadata.quoted_tweet_author, Some("quoteduser".to_string()));
     }
+
+    fn tweet_article(user: &str, id: &str) -> String {
+        format!(
+            r#"<article data-testid="tweet"><a href="/{user}">@{user}</a><a href="/{user}/status/{id}"><time datetime="2024-01-01T00:00:00Z">Jan 1</time></a><a href="/{user}/status/{id}/analytics">Views</a></article>"#
+        )
+    }
+
+    #[test]
+    fn test_extract_self_thread_urls() {
+        let html = [
+            tweet_article("someone", "100"),
+            tweet_article("Author", "200"),
+            tweet_article("author", "201"),
+            tweet_article("Author", "202"),
+            tweet_article("replier", "300"),
+            tweet_article("Author", "203"),
+        ]
+        .concat();
+
+        assert_eq!(
+            extract_self_thread_urls(&html, "200", 10),
+            vec![
+                "https://x.com/author/status/201",
+                "https://x.com/Author/status/202",
+            ]
+        );
+        assert_eq!(
+            extract_self_thread_urls(&html, "200", 1),
+            vec!["https://x.com/author/status/201"]
+        );
+        assert!(extract_self_thread_urls(&html, "200", 0).is_empty());
+        assert!(extract_self_thread_urls(&html, "999", 10).is_empty());
+    }
+
+    #[test]
+    fn test_is_protected_account_html() {
+        assert!(is_protected_account_html(
+            "<div><span>These posts are protected</span></div>"
+        ));
+        assert!(!is_protected_account_html(&tweet_article("author", "1")));
+        assert!(!is_protected_account_html("<div>Hello</div>"));
+    }
 }
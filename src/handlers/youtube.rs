@@ -7,7 +7,7 @@ use tracing::debug;
 
 use super::generic::GenericHandler;
 use super::traits::{ArchiveResult, SiteHandler};
-use crate::archiver::{ytdlp, CookieOptions};
+use crate::archiver::{playlist, ytdlp, CookieOptions};
 
 static PATTERNS: std::sync::LazyLock<Vec<Regex>> = std::sync::LazyLock::new(|| {
     vec![
@@ -15,6 +15,8 @@ static PATTERNS: std::sync::LazyLock<Vec<Regex>> = std::sync::LazyLock::new(|| {
         Regex::new(r"^https?://(www\.)?youtube\.com/shorts/").unwrap(),
         Regex::new(r"^https?://(www\.)?youtube\.com/live/").unwrap(),
         Regex::new(r"^https?://(www\.)?youtube\.com/embed/").unwrap(),
+        Regex::new(r"^https?://(www\.)?youtube-nocookie\.com/embed/").unwrap(),
+        Regex::new(r"^https?://music\.youtube\.com/watch").unwrap(),
         Regex::new(r"^https?://(www\.)?youtube\.com/playlist").unwrap(),
         Regex::new(r"^https?://youtu\.be/").unwrap(),
         Regex::new(r"^https?://m\.youtube\.com/").unwrap(),
@@ -56,13 +58,11 @@ impl SiteHandler for YouTubeHandler {
     }
 
     fn normalize_url(&self, url: &str) -> String {
-        // For video URLs that include a playlist reference, strip the list= and index=
-        // parameters so the video is treated as an individual video (not playlist-scoped).
-        // This also ensures deduplication works correctly across URLs with/without list=.
-        if extract_video_id(url).is_some() {
-            return strip_playlist_params(url);
-        }
-        url.to_string()
+        // Video URLs (shorts, youtu.be, embed, mobile, playlist-scoped watch
+        // links) collapse to the canonical watch URL, so every form of a video
+        // shares one dedup key. Start timestamps are dropped with the rest of
+        // the query; the link's original URL still carries them.
+        canonical_video_url(url).unwrap_or_else(|| url.to_string())
     }
 
     async fn archive(
@@ -72,7 +72,18 @@ impl SiteHandler for YouTubeHandler {
         cookies: &CookieOptions<'_>,
         config: &crate::config::Config,
     ) -> Result<ArchiveResult> {
-        // Channels and playlists are archived as normal web pages — no yt-dlp.
+        // Playlists are enumerated (without downloading) so the worker can
+        // enqueue each video and group them under this archive.
+        if is_playlist_url(url) {
+            if let Some(playlist_id) = extract_playlist_id(url) {
+                debug!(url = %url, playlist_id = %playlist_id, "YouTube playlist URL");
+                return playlist::archive_playlist(url, work_dir, cookies, config, &playlist_id)
+                    .await;
+            }
+        }
+
+        // Channels (and playlist URLs without a list ID) are archived as
+        // normal web pages — no yt-dlp.
         if is_channel_url(url) || is_playlist_url(url) {
             debug!(url = %url, "YouTube channel/playlist URL, archiving as web page");
             return GenericHandler::new()
@@ -93,26 +104,24 @@ impl SiteHandler for YouTubeHandler {
     }
 }
 
-/// Strip playlist-related query parameters (`list=` and `index=`) from a YouTube video URL.
+/// Return the canonical `https://www.youtube.com/watch?v=<id>` URL for a
+/// YouTube video URL, or `None` for playlists, channels, and non-video URLs.
 ///
-/// When a video link includes a playlist context (e.g., `?v=abc&list=PLxyz&index=3`),
-/// the list/index params are dropped so the URL represents only the individual video.
-fn strip_playlist_params(url: &str) -> String {
-    let (base, query) = match url.split_once('?') {
-        Some((b, q)) => (b, q),
-        None => return url.to_string(),
-    };
-
-    let params: Vec<&str> = query
-        .split('&')
-        .filter(|p| !p.starts_with("list=") && !p.starts_with("index="))
-        .collect();
-
-    if params.is_empty() {
-        base.to_string()
-    } else {
-        format!("{}?{}", base, params.join("&"))
+/// All query parameters other than the video ID (including `t=`/`start=`,
+/// `list=`, and `si=`) are dropped.
+pub fn canonical_video_url(url: &str) -> Option<String> {
+    if is_playlist_url(url) || is_channel_url(url) {
+        return None;
     }
+    let video_id = extract_video_id(url)?;
+    if video_id.is_empty()
+        || !video_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return None;
+    }
+    Some(format!("https://www.youtube.com/watch?v={video_id}"))
 }
 
 /// Check if a URL is a YouTube playlist URL.
@@ -208,6 +217,7 @@ pub fn extract_video_id(url: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::handlers::normalize_link_url;
 
     #[test]
     fn test_can_handle() {
@@ -353,36 +363,28 @@ mod tests {
     }
 
     #[test]
-    fn test_strip_playlist_params() {
-        // Video with list and index — both stripped
-        assert_eq!(
-            strip_playlist_params("https://www.youtube.com/watch?v=abc123&list=PLxyz&index=3"),
-            "https://www.youtube.com/watch?v=abc123"
-        );
-        // Video with only list — stripped
-        assert_eq!(
-            strip_playlist_params("https://www.youtube.com/watch?v=abc123&list=PLxyz"),
-            "https://www.youtube.com/watch?v=abc123"
-        );
-        // list= before v= — only list stripped
+    fn test_normalize_url_drops_playlist_params() {
+        let handler = YouTubeHandler::new();
+
+        // Video with list and index — both dropped
         assert_eq!(
-            strip_playlist_params("https://www.youtube.com/watch?list=PLxyz&v=abc123"),
+            handler.normalize_url("https://www.youtube.com/watch?v=abc123&list=PLxyz&index=3"),
             "https://www.youtube.com/watch?v=abc123"
         );
-        // Video without playlist params — unchanged
+        // list= before v= — only the video ID survives
         assert_eq!(
-            strip_playlist_params("https://www.youtube.com/watch?v=abc123"),
+            handler.normalize_url("https://www.youtube.com/watch?list=PLxyz&v=abc123"),
             "https://www.youtube.com/watch?v=abc123"
         );
-        // No query string — unchanged
+        // No video ID — unchanged
         assert_eq!(
-            strip_playlist_params("https://www.youtube.com/watch"),
+            handler.normalize_url("https://www.youtube.com/watch"),
             "https://www.youtube.com/watch"
         );
-        // Extra params preserved
+        // Timestamps are dropped along with the playlist
         assert_eq!(
-            strip_playlist_params("https://www.youtube.com/watch?v=abc123&t=30s&list=PLxyz"),
-            "https://www.youtube.com/watch?v=abc123&t=30s"
+            handler.normalize_url("https://www.youtube.com/watch?v=abc123&t=30s&list=PLxyz"),
+            "https://www.youtube.com/watch?v=abc123"
         );
     }
 
@@ -395,10 +397,15 @@ mod tests {
             handler.normalize_url("https://www.youtube.com/watch?v=abc123&list=PLxyz"),
             "https://www.youtube.com/watch?v=abc123"
         );
-        // Shorts with list= → list stripped
+        // Shorts with list= → canonical watch URL
         assert_eq!(
             handler.normalize_url("https://www.youtube.com/shorts/abc123?list=PLxyz"),
-            "https://www.youtube.com/shorts/abc123"
+            "https://www.youtube.com/watch?v=abc123"
+        );
+        // Timestamps aren't part of the canonical form
+        assert_eq!(
+            handler.normalize_url("https://youtu.be/abc123?t=1m30s&si=xyz"),
+            "https://www.youtube.com/watch?v=abc123"
         );
         // Channel URL — no change
         assert_eq!(
@@ -417,6 +424,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_canonical_video_url() {
+        let expected = Some("https://www.youtube.com/watch?v=dQw4w9WgXcQ".to_string());
+        assert_eq!(
+            canonical_video_url("https://www.youtube.com/shorts/dQw4w9WgXcQ"),
+            expected
+        );
+        assert_eq!(
+            canonical_video_url("https://youtu.be/dQw4w9WgXcQ?si=abc"),
+            expected
+        );
+        assert_eq!(
+            canonical_video_url("https://www.youtube.com/embed/dQw4w9WgXcQ?start=10"),
+            expected
+        );
+        assert_eq!(
+            canonical_video_url("https://m.youtube.com/watch?v=dQw4w9WgXcQ&t=5"),
+            expected
+        );
+        assert_eq!(
+            canonical_video_url("https://www.youtube.com/watch?list=PLxyz&v=dQw4w9WgXcQ"),
+            expected
+        );
+        assert_eq!(
+            canonical_video_url("https://www.youtube.com/playlist?list=PLxyz"),
+            None
+        );
+        assert_eq!(
+            canonical_video_url("https://www.youtube.com/@someuser"),
+            None
+        );
+        assert_eq!(canonical_video_url("https://www.youtube.com/watch"), None);
+        assert_eq!(
+            canonical_video_url("https://www.youtube.com/watch?v=bad%20id"),
+            None
+        );
+    }
+
     #[test]
     fn test_is_channel_url() {
         // Channel URLs (various formats)
@@ -437,4 +482,61 @@ mod tests {
             "https://www.youtube.com/playlist?list=PLxyz"
         ));
     }
+
+    #[test]
+    fn test_link_key_collapses_video_variants() {
+        let canonical = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+        for url in [
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+            "http://youtube.com/watch?v=dQw4w9WgXcQ",
+            "https://m.youtube.com/watch?v=dQw4w9WgXcQ&feature=share",
+            "https://www.youtube.com/shorts/dQw4w9WgXcQ",
+            "https://youtube.com/shorts/dQw4w9WgXcQ?si=abcdef",
+            "https://youtu.be/dQw4w9WgXcQ",
+            "https://youtu.be/dQw4w9WgXcQ?si=abcdef",
+            "https://www.youtube.com/embed/dQw4w9WgXcQ",
+            "https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ",
+            "https://WWW.YouTube.com/watch?v=dQw4w9WgXcQ#comments",
+        ] {
+            assert_eq!(normalize_link_url(url), canonical, "variant: {url}");
+        }
+    }
+
+    #[test]
+    fn test_link_key_excludes_timestamp() {
+        let canonical = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+        assert_eq!(
+            normalize_link_url("https://youtu.be/dQw4w9WgXcQ?t=42"),
+            canonical
+        );
+        assert_eq!(
+            normalize_link_url("https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=1m30s"),
+            canonical
+        );
+        assert_eq!(
+            normalize_link_url("https://www.youtube.com/embed/dQw4w9WgXcQ?start=10"),
+            canonical
+        );
+    }
+
+    #[test]
+    fn test_link_key_preserves_v_param() {
+        // v= survives even when it is not the first parameter
+        assert_eq!(
+            normalize_link_url("https://www.youtube.com/watch?list=PLxyz&index=3&v=dQw4w9WgXcQ"),
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ"
+        );
+    }
+
+    #[test]
+    fn test_link_key_leaves_non_video_urls() {
+        assert_eq!(
+            normalize_link_url("https://www.youtube.com/playlist?list=PLxyz"),
+            "https://www.youtube.com/playlist?list=PLxyz"
+        );
+        assert_eq!(
+            normalize_link_url("https://www.youtube.com/@someuser/"),
+            "https://www.youtube.com/@someuser"
+        );
+    }
 }
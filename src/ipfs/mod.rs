@@ -3,15 +3,22 @@
 //! Communicates with a local IPFS daemon via its HTTP API to pin files
 //! and generate public gateway URLs for retrieval.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 use reqwest::multipart;
 use serde::Deserialize;
+use tokio::sync::Semaphore;
+use tokio_util::io::ReaderStream;
 use tracing::{debug, info, warn};
 
 use crate::config::Config;
 
+/// Slowest upload rate a pin is given time for: one extra second per MiB.
+const PIN_MIN_BYTES_PER_SEC: u64 = 1024 * 1024;
+
 /// IPFS API response for add operation.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -30,14 +37,111 @@ pub struct IpfsClient {
     api_url: String,
     gateway_urls: Vec<String>,
     enabled: bool,
+    /// Base timeout for pin requests, extended by upload size.
+    pin_timeout: Duration,
+    /// Limits how many pin uploads run at once.
+    pin_permits: Arc<Semaphore>,
+    /// Pauses pinning while the daemon keeps failing.
+    breaker: Arc<PinBreaker>,
+}
+
+/// Whether pins should be attempted, from [`IpfsClient::pin_readiness`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PinReadiness {
+    /// The daemon is healthy; pin as usual.
+    Ready,
+    /// A health probe just succeeded after an outage; pending pins can be
+    /// backfilled.
+    Recovered,
+    /// The daemon is failing; skip the pin and queue it for later.
+    Unavailable,
+}
+
+/// Circuit breaker state for pin requests.
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    /// Set while the breaker is open; the cooldown runs from here.
+    opened_at: Option<Instant>,
+}
+
+/// What the breaker allows right now.
+#[derive(Debug, PartialEq, Eq)]
+enum BreakerCheck {
+    Closed,
+    Open,
+    /// The cooldown elapsed; this caller should probe the daemon.
+    Probe,
+}
+
+/// Circuit breaker that opens after consecutive pin failures.
+///
+/// While open, pins are skipped; once the cooldown elapses a single caller
+/// is told to probe the daemon, and the cooldown restarts for everyone else.
+#[derive(Debug)]
+struct PinBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    state: Mutex<BreakerState>,
+}
+
+impl PinBreaker {
+    fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    fn check(&self) -> BreakerCheck {
+        let mut state = self.state.lock().expect("IPFS breaker lock poisoned");
+        match state.opened_at {
+            None => BreakerCheck::Closed,
+            Some(opened_at) if opened_at.elapsed() >= self.cooldown => {
+                state.opened_at = Some(Instant::now());
+                BreakerCheck::Probe
+            }
+            Some(_) => BreakerCheck::Open,
+        }
+    }
+
+    fn record_success(&self) {
+        let mut state = self.state.lock().expect("IPFS breaker lock poisoned");
+        *state = BreakerState::default();
+    }
+
+    fn record_failure(&self) {
+        let mut state = self.state.lock().expect("IPFS breaker lock poisoned");
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        if state.consecutive_failures >= self.failure_threshold {
+            if state.opened_at.is_none() {
+                warn!(
+                    failures = state.consecutive_failures,
+                    "IPFS daemon keeps failing, pausing pins"
+                );
+            }
+            state.opened_at = Some(Instant::now());
+        }
+    }
+}
+
+/// A file found while walking a directory to pin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DirectoryFile {
+    /// Path relative to the pinned directory, used as the IPFS file name.
+    relative_path: String,
+    path: PathBuf,
+    size: u64,
 }
 
 impl IpfsClient {
     /// Create a new IPFS client from configuration.
     #[must_use]
     pub fn new(config: &Config) -> Self {
+        // Pin requests set their own size-based timeout
         let http = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(300))
+            .connect_timeout(Duration::from_secs(30))
             .build()
             .expect("Failed to create HTTP client");
 
@@ -46,7 +150,59 @@ impl IpfsClient {
             api_url: config.ipfs_api_url.clone(),
             gateway_urls: config.ipfs_gateway_urls.clone(),
             enabled: config.ipfs_enabled,
+            pin_timeout: Duration::from_secs(config.ipfs_pin_timeout_secs),
+            pin_permits: Arc::new(Semaphore::new(config.ipfs_max_concurrent_pins.max(1))),
+            breaker: Arc::new(PinBreaker::new(
+                config.ipfs_failure_threshold,
+                Duration::from_secs(config.ipfs_cooldown_secs),
+            )),
+        }
+    }
+
+    /// Decide whether to pin now, probing the daemon if a pause has expired.
+    ///
+    /// After `ipfs_failure_threshold` consecutive pin failures pins are paused
+    /// for `ipfs_cooldown_secs`; the first call after that runs
+    /// [`Self::health_check`] and either resumes pinning or restarts the pause.
+    pub async fn pin_readiness(&self) -> PinReadiness {
+        match self.breaker.check() {
+            BreakerCheck::Closed => PinReadiness::Ready,
+            BreakerCheck::Open => PinReadiness::Unavailable,
+            BreakerCheck::Probe => {
+                if matches!(self.health_check().await, Ok(true)) {
+                    info!("IPFS daemon is healthy again, resuming pins");
+                    self.breaker.record_success();
+                    PinReadiness::Recovered
+                } else {
+                    debug!("IPFS daemon still unavailable");
+                    PinReadiness::Unavailable
+                }
+            }
+        }
+    }
+
+    /// Send a pin request, tracking daemon failures for the circuit breaker.
+    async fn send_pin(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                self.breaker.record_failure();
+                return Err(e).context("Failed to send request to IPFS daemon");
+            }
+        };
+
+        if !response.status().is_success() {
+            self.breaker.record_failure();
+            let status = response.status();
+            let body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "unknown".to_string());
+            anyhow::bail!("IPFS add failed: {status} - {body}");
         }
+
+        self.breaker.record_success();
+        Ok(response)
     }
 
     /// Check if IPFS is enabled.
@@ -66,33 +222,29 @@ impl IpfsClient {
         }
 
         let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("file");
-
-        let file_bytes = tokio::fs::read(path)
+        let size = tokio::fs::metadata(path)
             .await
-            .context("Failed to read file for IPFS pinning")?;
+            .context("Failed to read file for IPFS pinning")?
+            .len();
 
-        let part = multipart::Part::bytes(file_bytes).file_name(filename.to_string());
+        let part = file_part(path, filename, size).await?;
         let form = multipart::Form::new().part("file", part);
 
+        let _permit = self
+            .pin_permits
+            .acquire()
+            .await
+            .context("IPFS pin limiter closed")?;
+
         let url = format!("{}/api/v0/add?pin=true", self.api_url);
         debug!(url = %url, file = %path.display(), "Pinning file to IPFS");
 
-        let response = self
+        let request = self
             .http
             .post(&url)
-            .multipart(form)
-            .send()
-            .await
-            .context("Failed to send request to IPFS daemon")?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "unknown".to_string());
-            anyhow::bail!("IPFS add failed: {status} - {body}");
-        }
+            .timeout(pin_timeout(self.pin_timeout, size))
+            .multipart(form);
+        let response = self.send_pin(request).await?;
 
         let add_response: AddResponse = response
             .json()
@@ -117,13 +269,54 @@ impl IpfsClient {
         let part = multipart::Part::bytes(data.to_vec()).file_name(filename.to_string());
         let form = multipart::Form::new().part("file", part);
 
+        let _permit = self
+            .pin_permits
+            .acquire()
+            .await
+            .context("IPFS pin limiter closed")?;
+
         let url = format!("{}/api/v0/add?pin=true", self.api_url);
         debug!(url = %url, filename = %filename, "Pinning bytes to IPFS");
 
+        let request = self
+            .http
+            .post(&url)
+            .timeout(pin_timeout(self.pin_timeout, data.len() as u64))
+            .multipart(form);
+        let response = self.send_pin(request).await?;
+
+        let add_response: AddResponse = response
+            .json()
+            .await
+            .context("Failed to parse IPFS add response")?;
+
+        info!(cid = %add_response.hash, filename = %filename, "Pinned bytes to IPFS");
+
+        Ok(add_response.hash)
+    }
+
+    /// Remove a pin so the daemon can garbage-collect the content.
+    ///
+    /// CIDs that are already unpinned are treated as success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the IPFS daemon rejects the request.
+    pub async fn unpin(&self, cid: &str) -> Result<()> {
+        if !self.enabled {
+            anyhow::bail!("IPFS is not enabled");
+        }
+
+        let url = format!(
+            "{}/api/v0/pin/rm?arg={}",
+            self.api_url,
+            urlencoding::encode(cid)
+        );
+        debug!(url = %url, cid = %cid, "Unpinning from IPFS");
+
         let response = self
             .http
             .post(&url)
-            .multipart(form)
             .send()
             .await
             .context("Failed to send request to IPFS daemon")?;
@@ -134,17 +327,16 @@ impl IpfsClient {
                 .text()
                 .await
                 .unwrap_or_else(|_| "unknown".to_string());
-            anyhow::bail!("IPFS add failed: {status} - {body}");
+            if body.contains("not pinned") {
+                debug!(cid = %cid, "CID was not pinned");
+                return Ok(());
+            }
+            anyhow::bail!("IPFS pin rm failed: {status} - {body}");
         }
 
-        let add_response: AddResponse = response
-            .json()
-            .await
-            .context("Failed to parse IPFS add response")?;
-
-        info!(cid = %add_response.hash, filename = %filename, "Pinned bytes to IPFS");
+        info!(cid = %cid, "Unpinned from IPFS");
 
-        Ok(add_response.hash)
+        Ok(())
     }
 
     /// Pin a directory of files to IPFS recursively.
@@ -163,36 +355,32 @@ impl IpfsClient {
             self.api_url
         );
 
-        let mut form = multipart::Form::new();
+        // Files are streamed from disk as the request is sent, so large
+        // directories are never held in memory
+        let files = walkdir(path).await?;
+        let total_bytes: u64 = files.iter().map(|f| f.size).sum();
+        let form = directory_form(&files).await?;
 
-        // Walk the directory and add all files
-        let entries = walkdir(path).await?;
-        for (relative_path, file_bytes) in entries {
-            let part = multipart::Part::bytes(file_bytes)
-                .file_name(relative_path.clone())
-                .mime_str("application/octet-stream")
-                .context("Failed to set mime type")?;
-            form = form.part("file", part);
-        }
-
-        debug!(url = %url, dir = %path.display(), "Pinning directory to IPFS");
+        let _permit = self
+            .pin_permits
+            .acquire()
+            .await
+            .context("IPFS pin limiter closed")?;
+
+        debug!(
+            url = %url,
+            dir = %path.display(),
+            files = files.len(),
+            total_bytes,
+            "Pinning directory to IPFS"
+        );
 
-        let response = self
+        let request = self
             .http
             .post(&url)
-            .multipart(form)
-            .send()
-            .await
-            .context("Failed to send request to IPFS daemon")?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "unknown".to_string());
-            anyhow::bail!("IPFS add failed: {status} - {body}");
-        }
+            .timeout(pin_timeout(self.pin_timeout, total_bytes))
+            .multipart(form);
+        let response = self.send_pin(request).await?;
 
         // The response contains multiple JSON objects, one per file plus the directory
         // The last one is the root directory CID
@@ -217,10 +405,7 @@ impl IpfsClient {
     /// Generate public gateway URLs for a CID.
     #[must_use]
     pub fn gateway_urls(&self, cid: &str) -> Vec<String> {
-        self.gateway_urls
-            .iter()
-            .map(|base| format!("{base}{cid}"))
-            .collect()
+        gateway_urls_for(&self.gateway_urls, cid)
     }
 
     /// Check if the IPFS daemon is reachable.
@@ -245,8 +430,44 @@ impl IpfsClient {
     }
 }
 
-/// Walk a directory and return all files with their relative paths.
-async fn walkdir(path: &Path) -> Result<Vec<(String, Vec<u8>)>> {
+/// Public gateway URLs for a CID, one per configured gateway base URL.
+#[must_use]
+pub fn gateway_urls_for(gateways: &[String], cid: &str) -> Vec<String> {
+    gateways.iter().map(|base| format!("{base}{cid}")).collect()
+}
+
+/// Timeout for uploading `bytes`: the base plus a second per MiB.
+fn pin_timeout(base: Duration, bytes: u64) -> Duration {
+    base + Duration::from_secs(bytes / PIN_MIN_BYTES_PER_SEC)
+}
+
+/// Build a multipart part that streams a file from disk.
+async fn file_part(path: &Path, file_name: &str, size: u64) -> Result<multipart::Part> {
+    let file = tokio::fs::File::open(path)
+        .await
+        .with_context(|| format!("Failed to open {} for IPFS pinning", path.display()))?;
+    let body = reqwest::Body::wrap_stream(ReaderStream::new(file));
+
+    multipart::Part::stream_with_length(body, size)
+        .file_name(file_name.to_string())
+        .mime_str("application/octet-stream")
+        .context("Failed to set mime type")
+}
+
+/// Build the multipart form for a directory pin, one streamed part per file.
+async fn directory_form(files: &[DirectoryFile]) -> Result<multipart::Form> {
+    let mut form = multipart::Form::new();
+    for file in files {
+        let part = file_part(&file.path, &file.relative_path, file.size).await?;
+        form = form.part("file", part);
+    }
+    Ok(form)
+}
+
+/// Walk a directory and return all files with their relative paths and sizes.
+///
+/// File contents aren't read here; [`directory_form`] streams them later.
+async fn walkdir(path: &Path) -> Result<Vec<DirectoryFile>> {
     let mut entries = Vec::new();
     let mut stack = vec![path.to_path_buf()];
 
@@ -262,13 +483,17 @@ async fn walkdir(path: &Path) -> Result<Vec<(String, Vec<u8>)>> {
             if file_type.is_dir() {
                 stack.push(entry_path);
             } else if file_type.is_file() {
-                let relative = entry_path
+                let relative_path = entry_path
                     .strip_prefix(path)
                     .unwrap_or(&entry_path)
                     .to_string_lossy()
                     .to_string();
-                let bytes = tokio::fs::read(&entry_path).await?;
-                entries.push((relative, bytes));
+                let size = entry.metadata().await?.len();
+                entries.push(DirectoryFile {
+                    relative_path,
+                    path: entry_path,
+                    size,
+                });
             }
         }
     }
@@ -309,4 +534,138 @@ mod tests {
         assert_eq!(urls[1], "https://dweb.link/ipfs/QmTest123");
         assert_eq!(urls[2], "https://gateway.pinata.cloud/ipfs/QmTest123");
     }
+
+    #[test]
+    fn test_pin_timeout_scales_with_size() {
+        let base = Duration::from_secs(300);
+        assert_eq!(pin_timeout(base, 0), base);
+        assert_eq!(pin_timeout(base, 1024), base);
+        assert_eq!(
+            pin_timeout(base, 600 * 1024 * 1024),
+            Duration::from_secs(900)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_walkdir_collects_sizes_without_contents() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("video.mp4"), vec![0u8; 4096]).unwrap();
+        std::fs::create_dir(temp_dir.path().join("thumbs")).unwrap();
+        std::fs::write(temp_dir.path().join("thumbs/thumb.jpg"), b"jpeg").unwrap();
+
+        let mut files = walkdir(temp_dir.path()).await.unwrap();
+        files.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].relative_path, "thumbs/thumb.jpg");
+        assert_eq!(files[0].size, 4);
+        assert_eq!(files[1].relative_path, "video.mp4");
+        assert_eq!(files[1].size, 4096);
+    }
+
+    #[tokio::test]
+    async fn test_pin_directory_streams_every_file() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v0/add"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(concat!(
+                r#"{"Name":"a.txt","Hash":"QmFileA","Size":"9"}"#,
+                "\n",
+                r#"{"Name":"b.txt","Hash":"QmFileB","Size":"9"}"#,
+                "\n",
+                r#"{"Name":"","Hash":"QmRootDir","Size":"30"}"#,
+                "\n",
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "content a").unwrap();
+        std::fs::write(temp_dir.path().join("b.txt"), "content b").unwrap();
+
+        let config = Config {
+            ipfs_enabled: true,
+            ipfs_api_url: server.uri(),
+            ..Config::for_testing()
+        };
+        let cid = IpfsClient::new(&config)
+            .pin_directory(temp_dir.path())
+            .await
+            .unwrap();
+        assert_eq!(cid, "QmRootDir");
+
+        let requests = server.received_requests().await.unwrap();
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert_eq!(body.matches(r#"name="file""#).count(), 2);
+        assert!(body.contains(r#"filename="a.txt""#));
+        assert!(body.contains("content a"));
+        assert!(body.contains("content b"));
+    }
+
+    #[test]
+    fn test_breaker_opens_after_threshold() {
+        let breaker = PinBreaker::new(3, Duration::from_secs(300));
+
+        breaker.record_failure();
+        breaker.record_failure();
+        assert_eq!(breaker.check(), BreakerCheck::Closed);
+
+        // A success resets the streak
+        breaker.record_success();
+        breaker.record_failure();
+        breaker.record_failure();
+        assert_eq!(breaker.check(), BreakerCheck::Closed);
+
+        breaker.record_failure();
+        assert_eq!(breaker.check(), BreakerCheck::Open);
+        assert_eq!(breaker.check(), BreakerCheck::Open);
+    }
+
+    #[tokio::test]
+    async fn test_pin_readiness_probes_and_recovers() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v0/add"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v0/id"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v0/id"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let config = Config {
+            ipfs_enabled: true,
+            ipfs_api_url: server.uri(),
+            ipfs_failure_threshold: 2,
+            ipfs_cooldown_secs: 0,
+            ..Config::for_testing()
+        };
+        let client = IpfsClient::new(&config);
+        assert_eq!(client.pin_readiness().await, PinReadiness::Ready);
+
+        for _ in 0..2 {
+            assert!(client.pin_bytes(b"data", "a.txt").await.is_err());
+        }
+
+        // Breaker is open: the first probe fails, the next one recovers
+        assert_eq!(client.pin_readiness().await, PinReadiness::Unavailable);
+        assert_eq!(client.pin_readiness().await, PinReadiness::Recovered);
+        assert_eq!(client.pin_readiness().await, PinReadiness::Ready);
+    }
 }
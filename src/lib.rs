@@ -32,6 +32,7 @@ pub mod archive_today;
 pub mod archiver;
 pub mod auth;
 pub mod backup;
+pub mod check_config;
 pub mod chromium_profile;
 pub mod components;
 pub mod config;
@@ -42,8 +43,11 @@ pub mod fs_utils;
 pub mod handlers;
 pub mod ipfs;
 pub mod og_extractor;
+pub mod placeholder;
+pub mod retry_after;
 pub mod rss;
 pub mod s3;
 pub mod tls;
 pub mod wayback;
 pub mod web;
+pub mod webhook;
@@ -7,8 +7,9 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilte
 use discourse_link_archiver::archiver::ArchiveWorker;
 use discourse_link_archiver::auth::{run_cleanup_worker, CleanupConfig};
 use discourse_link_archiver::backup::BackupManager;
+use discourse_link_archiver::check_config::{self, CHECK_CONFIG_FLAG};
 use discourse_link_archiver::config::Config;
-use discourse_link_archiver::db::Database;
+use discourse_link_archiver::db::{Database, DatabaseOptions};
 use discourse_link_archiver::ipfs::IpfsClient;
 use discourse_link_archiver::s3::S3Client;
 use discourse_link_archiver::{rss, web};
@@ -16,6 +17,14 @@ use tokio_util::sync::CancellationToken;
 
 #[tokio::main]
 async fn main() {
+    if std::env::args().skip(1).any(|arg| arg == CHECK_CONFIG_FLAG) {
+        // Load .env file if present
+        let _ = dotenvy::dotenv();
+        let report = check_config::run().await;
+        print!("{report}");
+        std::process::exit(i32::from(!report.is_ok()));
+    }
+
     if let Err(e) = run().await {
         error!("Fatal error: {e:#}");
         std::process::exit(1);
@@ -108,7 +117,7 @@ async fn run() -> Result<()> {
     }
 
     // Initialize database
-    let db = Database::new(&config.database_path)
+    let db = Database::with_options(&config.database_path, &DatabaseOptions::from(&config))
         .await
         .context("Failed to initialize database")?;
 
@@ -161,8 +170,9 @@ async fn run() -> Result<()> {
     let worker_config = config.clone();
     let worker_db = db.clone();
     let worker_s3 = s3_client.clone();
-    let worker_ipfs = ipfs_client;
+    let worker_ipfs = ipfs_client.clone();
     let worker = ArchiveWorker::new(worker_config, worker_db, worker_s3, worker_ipfs);
+    let domain_limiter = worker.domain_limiter();
 
     // Recover from any interrupted processing on startup
     if let Err(e) = worker.recover_on_startup().await {
@@ -198,6 +208,25 @@ async fn run() -> Result<()> {
     });
     info!("Cleanup worker started");
 
+    // Start archive retention worker if enabled
+    let retention_handle = if config.archive_retention_days > 0 {
+        let retention_db = db.clone();
+        let retention_s3 = s3_client.clone();
+        let retention_ipfs = ipfs_client.clone();
+        let retention_config = config.clone();
+        Some(tokio::spawn(async move {
+            discourse_link_archiver::archiver::retention::run(
+                retention_db,
+                retention_s3,
+                retention_ipfs,
+                retention_config,
+            )
+            .await;
+        }))
+    } else {
+        None
+    };
+
     // Start thread archive worker
     let thread_archive_config = config.clone();
     let thread_archive_db = db.clone();
@@ -215,11 +244,30 @@ async fn run() -> Result<()> {
             comment_worker_config,
             comment_worker_db,
             comment_worker_s3,
+            domain_limiter,
         )
         .await;
     });
     info!("Comment extraction worker started");
 
+    // Start change watch worker if enabled
+    let change_watch_handle = if config.change_watch_enabled {
+        let change_watch_db = db.clone();
+        let change_watch_s3 = s3_client.clone();
+        let change_watch_config = config.clone();
+        info!("Change watch worker enabled");
+        Some(tokio::spawn(async move {
+            discourse_link_archiver::archiver::change_watch::run(
+                change_watch_db,
+                change_watch_s3,
+                &change_watch_config,
+            )
+            .await;
+        }))
+    } else {
+        None
+    };
+
     // Start search content backfill worker (runs once then exits)
     let backfill_db = db.clone();
     let backfill_s3 = s3_client.clone();
@@ -306,6 +354,12 @@ async fn run() -> Result<()> {
     if let Some(handle) = backup_handle {
         handle.abort();
     }
+    if let Some(handle) = change_watch_handle {
+        handle.abort();
+    }
+    if let Some(handle) = retention_handle {
+        handle.abort();
+    }
 
     info!("Shutdown complete");
 
@@ -372,16 +426,17 @@ async fn initialize_self_excluded_domains(config: &Config, db: &Database) -> Res
     // Collect all domains that should be self-excluded
     let mut self_domains = std::collections::HashSet::new();
 
-    // Extract domain from web_host if it looks like a domain/hostname
-    let web_host = config.web_host.to_lowercase();
-    // Skip localhost/127.0.0.1 since those are only for testing
-    if !web_host.contains("localhost")
-        && !web_host.starts_with("127.")
-        && web_host != "0.0.0.0"
-        && web_host != "[::1]"
-        && !web_host.is_empty()
-    {
-        self_domains.insert(web_host.clone());
+    // Extract domain from web_host entries if they look like a domain/hostname
+    for web_host in config.web_host.to_lowercase().split(',').map(str::trim) {
+        // Skip localhost/127.0.0.1 since those are only for testing
+        if !web_host.contains("localhost")
+            && !web_host.starts_with("127.")
+            && web_host != "0.0.0.0"
+            && web_host != "[::1]"
+            && !web_host.is_empty()
+        {
+            self_domains.insert(web_host.to_string());
+        }
     }
 
     // Add any TLS domains from configuration
@@ -0,0 +1,92 @@
+//! Low-quality placeholders for image thumbnails.
+//!
+//! Each thumbnail's dominant colour and pixel size are stored with its
+//! artifact so archive cards can reserve the right space and paint a solid
+//! colour before the real image is lazily loaded.
+
+use anyhow::{Context, Result};
+use img_hash::image::{self, GenericImageView};
+
+/// Side length of the downscaled image the colour is averaged over.
+const SAMPLE_SIZE: u32 = 8;
+
+/// Dominant colour and dimensions of an image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImagePlaceholder {
+    /// Average colour as `#rrggbb`.
+    pub color: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Compute a placeholder for an encoded image.
+///
+/// # Errors
+///
+/// Returns an error if the image cannot be decoded.
+pub fn compute_placeholder(data: &[u8]) -> Result<ImagePlaceholder> {
+    let img = image::load_from_memory(data).context("Failed to decode image")?;
+    let (width, height) = img.dimensions();
+
+    let sample = img.thumbnail(SAMPLE_SIZE, SAMPLE_SIZE).to_rgb8();
+    let mut sums = [0u64; 3];
+    for pixel in sample.pixels() {
+        for (sum, channel) in sums.iter_mut().zip(pixel.0) {
+            *sum += u64::from(channel);
+        }
+    }
+    let count = u64::from(sample.width() * sample.height()).max(1);
+    let [r, g, b] = sums.map(|sum| sum / count);
+
+    Ok(ImagePlaceholder {
+        color: format!("#{r:02x}{g:02x}{b:02x}"),
+        width,
+        height,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use img_hash::image::{DynamicImage, ImageOutputFormat, Rgb, RgbImage};
+
+    fn encode_png(img: RgbImage) -> Vec<u8> {
+        let mut out = Vec::new();
+        DynamicImage::ImageRgb8(img)
+            .write_to(&mut out, ImageOutputFormat::Png)
+            .unwrap();
+        out
+    }
+
+    #[test]
+    fn test_solid_image_placeholder() {
+        let png = encode_png(RgbImage::from_pixel(64, 36, Rgb([0x20, 0x80, 0xff])));
+        let placeholder = compute_placeholder(&png).unwrap();
+        assert_eq!(
+            placeholder,
+            ImagePlaceholder {
+                color: "#2080ff".to_string(),
+                width: 64,
+                height: 36,
+            }
+        );
+    }
+
+    #[test]
+    fn test_placeholder_averages_colours() {
+        let img = RgbImage::from_fn(16, 16, |x, _| {
+            if x < 8 {
+                Rgb([0, 0, 0])
+            } else {
+                Rgb([200, 100, 50])
+            }
+        });
+        let placeholder = compute_placeholder(&encode_png(img)).unwrap();
+        assert_eq!(placeholder.color, "#643219");
+    }
+
+    #[test]
+    fn test_invalid_image_errors() {
+        assert!(compute_placeholder(b"not an image").is_err());
+    }
+}
@@ -0,0 +1,96 @@
+//! `Retry-After` header parsing for rate-limited HTTP responses.
+//!
+//! Archive services answer 429 with a `Retry-After` header holding either a
+//! number of seconds or an HTTP-date. Honouring it keeps the clients from
+//! retrying too early and getting soft-banned.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+
+/// Longest wait taken from a `Retry-After` header.
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(300);
+
+/// How long a response's `Retry-After` header asks us to wait.
+///
+/// Accepts both delta-seconds (`120`) and HTTP-date
+/// (`Wed, 21 Oct 2015 07:28:00 GMT`, measured from `now`) forms. Dates in the
+/// past give a zero wait and long waits are capped at [`MAX_RETRY_AFTER`].
+///
+/// # Returns
+/// `None` when the header is missing or malformed, so the caller can fall
+/// back to its own delay.
+#[must_use]
+pub fn parse_retry_after(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    let wait = if let Ok(seconds) = value.parse::<u64>() {
+        Duration::from_secs(seconds)
+    } else {
+        let at = DateTime::parse_from_rfc2822(value).ok()?;
+        (at.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO)
+    };
+
+    Some(wait.min(MAX_RETRY_AFTER))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_retry_after_seconds() {
+        assert_eq!(
+            parse_retry_after(&headers("120"), now()),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after(&headers(" 0 "), now()),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            parse_retry_after(&headers("86400"), now()),
+            Some(MAX_RETRY_AFTER)
+        );
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date() {
+        assert_eq!(
+            parse_retry_after(&headers("Wed, 21 Oct 2015 07:28:45 GMT"), now()),
+            Some(Duration::from_secs(45))
+        );
+        // Dates in the past mean "retry now"
+        assert_eq!(
+            parse_retry_after(&headers("Wed, 21 Oct 2015 07:00:00 GMT"), now()),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            parse_retry_after(&headers("Thu, 22 Oct 2015 07:28:00 GMT"), now()),
+            Some(MAX_RETRY_AFTER)
+        );
+    }
+
+    #[test]
+    fn test_parse_retry_after_missing_or_invalid() {
+        assert_eq!(parse_retry_after(&HeaderMap::new(), now()), None);
+        assert_eq!(parse_retry_after(&headers("soon"), now()), None);
+        assert_eq!(parse_retry_after(&headers("-5"), now()), None);
+    }
+}
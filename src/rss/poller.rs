@@ -2,21 +2,25 @@ use std::time::Duration;
 
 use anyhow::{Context, Result};
 use once_cell::sync::Lazy;
+use rand::Rng;
 use regex::Regex;
 use sha2::{Digest, Sha256};
+use sqlx::SqliteConnection;
 use tracing::{debug, error, info, trace, warn};
 
 use crate::config::Config;
 use crate::constants::ARCHIVER_HONEST_USER_AGENT;
 use crate::db::{
-    self, create_audit_event, create_forum_account_link, create_pending_archive,
-    display_name_exists, get_archive_by_link_id, get_forum_link_by_forum_username,
-    get_forum_link_by_user_id, get_link_by_normalized_url, get_post_by_guid, get_user_by_username,
-    insert_link, insert_link_occurrence, insert_post, link_occurrence_exists, update_post,
-    update_user_approval, update_user_profile, Database, LatestPostsResponse, NewLink,
-    NewLinkOccurrence, NewPost,
+    self, create_forum_account_link, create_pending_archive_on, display_name_exists,
+    get_archive_by_link_id, get_feed_state, get_forum_link_by_forum_username,
+    get_forum_link_by_user_id, get_newest_ingested_post_id, get_or_create_link_on,
+    get_post_by_guid, get_user_by_username, insert_link_occurrence, insert_post,
+    link_occurrence_exists, normalize_forum_author, record_audit, record_feed_backfill,
+    record_feed_poll, set_post_snapshot_archive, update_post, update_user_approval,
+    update_user_profile, AuditAction, AuditActor, Database, FeedState, LatestPost,
+    LatestPostsResponse, NewLink, NewLinkOccurrence, NewPost,
 };
-use crate::handlers::{normalize_url, HANDLERS};
+use crate::handlers::{normalize_link_url, normalize_url};
 use crate::rss::link_extractor::{extract_links, ExtractedLink};
 
 /// Regex to match the link_archive_account command at the start of text content.
@@ -31,9 +35,26 @@ pub async fn poll_loop(config: Config, db: Database) {
         .expect("Failed to build HTTP client");
 
     let mut consecutive_empty = 0u32;
-    let base_interval = config.poll_interval;
+    let base_interval = config.poll_interval_for(&config.rss_url);
     let max_interval = Duration::from_secs(300); // 5 minutes max
 
+    info!(
+        rss_url = %config.rss_url,
+        interval_secs = base_interval.as_secs(),
+        jitter_secs = config.poll_jitter.as_secs(),
+        overridden = config.poll_interval_overrides.contains_key(&config.rss_url),
+        "Starting RSS poll loop"
+    );
+    for (url, interval) in &config.poll_interval_overrides {
+        if *url != config.rss_url {
+            warn!(
+                url = %url,
+                interval_secs = interval.as_secs(),
+                "Poll interval override does not match any configured feed"
+            );
+        }
+    }
+
     loop {
         match poll_once(&client, &config, &db).await {
             Ok(new_count) => {
@@ -60,7 +81,57 @@ pub async fn poll_loop(config: Config, db: Database) {
             base_interval
         };
 
-        tokio::time::sleep(interval).await;
+        tokio::time::sleep(jittered_interval(interval, config.poll_jitter)).await;
+    }
+}
+
+/// Add a random delay of up to `jitter` to `interval`.
+///
+/// Keeps multiple instances from polling the forum in lockstep.
+fn jittered_interval(interval: Duration, jitter: Duration) -> Duration {
+    if jitter.is_zero() {
+        return interval;
+    }
+    interval + rand::thread_rng().gen_range(Duration::ZERO..=jitter)
+}
+
+/// How a poll pages through the feed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PollMode {
+    /// Page from the newest post until reaching `newest_seen`, a page with
+    /// nothing new, or `rss_max_pages`.
+    Incremental { newest_seen: Option<i64> },
+    /// Page through the feed's history from before `resume_from` (the newest
+    /// post when `None`) until it runs out or `rss_backfill_max_pages`.
+    Backfill { resume_from: Option<i64> },
+}
+
+/// Decide how to poll a feed with polling state `state`.
+///
+/// With `RSS_BACKFILL`, a feed is backfilled on its first poll, and later
+/// polls resume a backfill that hit its page limit. `ingested_newest` is the
+/// newest post already stored from a feed with no state: databases from
+/// before feed state was tracked have polled the feed, so it isn't backfilled.
+fn poll_mode(config: &Config, state: Option<&FeedState>, ingested_newest: Option<i64>) -> PollMode {
+    match state {
+        None if config.rss_backfill && ingested_newest.is_none() => {
+            PollMode::Backfill { resume_from: None }
+        }
+        None => PollMode::Incremental {
+            newest_seen: ingested_newest,
+        },
+        Some(state)
+            if config.rss_backfill
+                && state.backfilled_at.is_none()
+                && state.backfill_cursor.is_some() =>
+        {
+            PollMode::Backfill {
+                resume_from: state.backfill_cursor,
+            }
+        }
+        Some(state) => PollMode::Incremental {
+            newest_seen: state.newest_post_id,
+        },
     }
 }
 
@@ -70,55 +141,163 @@ pub async fn poll_loop(config: Config, db: Database) {
 ///
 /// Returns an error if the RSS feed cannot be fetched or parsed.
 pub async fn poll_once(client: &reqwest::Client, config: &Config, db: &Database) -> Result<usize> {
+    let state = get_feed_state(db.pool(), &config.rss_url).await?;
+    let ingested_newest = if state.is_none() {
+        let domain = extract_domain(&config.rss_url).unwrap_or_else(|| "unknown".to_string());
+        get_newest_ingested_post_id(db.pool(), &domain).await?
+    } else {
+        None
+    };
+    let mode = poll_mode(config, state.as_ref(), ingested_newest);
+
     let mut total_new_count = 0;
-    let mut before_post_id: Option<i64> = None;
+    let mut newest_post_id: Option<i64> = None;
+
+    if let PollMode::Backfill {
+        resume_from: Some(before),
+    } = mode
+    {
+        // Catch up on posts made since the last poll before paging further back
+        let newest_seen = state.as_ref().and_then(|s| s.newest_post_id);
+        let pass = page_feed(client, config, db, PollMode::Incremental { newest_seen }).await?;
+        total_new_count += pass.new_count;
+        newest_post_id = pass.newest_post_id;
+        info!(rss_url = %config.rss_url, before, "Resuming feed backfill");
+    } else if mode == (PollMode::Backfill { resume_from: None }) {
+        info!(rss_url = %config.rss_url, "First poll of feed, backfilling its history");
+    }
+
+    let pass = page_feed(client, config, db, mode).await?;
+    total_new_count += pass.new_count;
+    newest_post_id = newest_post_id.max(pass.newest_post_id);
+
+    record_feed_poll(db.pool(), &config.rss_url, newest_post_id).await?;
+
+    if let PollMode::Backfill { .. } = mode {
+        record_feed_backfill(db.pool(), &config.rss_url, pass.resume_cursor).await?;
+        if pass.resume_cursor.is_none() {
+            info!(
+                rss_url = %config.rss_url,
+                new_posts = total_new_count,
+                "Feed backfill complete"
+            );
+        } else {
+            info!(
+                rss_url = %config.rss_url,
+                new_posts = total_new_count,
+                before = pass.resume_cursor,
+                "Feed backfill reached RSS_BACKFILL_MAX_PAGES, resuming next poll"
+            );
+        }
+    }
+
+    Ok(total_new_count)
+}
+
+/// What one pass through the feed's pages found.
+#[derive(Debug, Default)]
+struct PassSummary {
+    new_count: usize,
+    newest_post_id: Option<i64>,
+    /// Cursor for the next page when the pass stopped at its page limit
+    /// before the feed ran out.
+    resume_cursor: Option<i64>,
+}
+
+/// Page through the feed as `mode` says, ingesting each page.
+///
+/// # Errors
+///
+/// Returns an error if a page cannot be fetched or parsed.
+async fn page_feed(
+    client: &reqwest::Client,
+    config: &Config,
+    db: &Database,
+    mode: PollMode,
+) -> Result<PassSummary> {
+    let (mut before_post_id, max_pages, newest_seen, backfill) = match mode {
+        PollMode::Incremental { newest_seen } => (None, config.rss_max_pages, newest_seen, false),
+        PollMode::Backfill { resume_from } => {
+            (resume_from, config.rss_backfill_max_pages, None, true)
+        }
+    };
+    let mut summary = PassSummary::default();
 
     // Fetch multiple pages if configured
     // Discourse /posts.rss uses cursor-based pagination with 'before' parameter
-    for page_num in 0..config.rss_max_pages {
-        let (page_new_count, min_post_id) =
-            fetch_and_process_page(client, config, db, before_post_id).await?;
-        total_new_count += page_new_count;
+    for page_num in 0..max_pages {
+        let page = fetch_and_process_page(client, config, db, before_post_id).await?;
+        summary.new_count += page.new_count;
+        summary.newest_post_id = summary.newest_post_id.max(page.max_post_id);
 
         // If we got no posts, we've reached the end
-        if min_post_id.is_none() {
+        let Some(min_post_id) = page.min_post_id else {
             debug!(
                 page = page_num,
                 "No posts in this batch, stopping pagination"
             );
-            break;
+            return Ok(summary);
+        };
+
+        // A feed that ignores the cursor would otherwise be paged forever
+        if before_post_id.is_some_and(|before| min_post_id >= before) {
+            warn!(
+                page = page_num,
+                "Feed cursor did not advance, stopping pagination"
+            );
+            return Ok(summary);
         }
 
         // Use the minimum post ID from this batch as the 'before' cursor for the next request
-        before_post_id = min_post_id;
+        before_post_id = Some(min_post_id);
 
         // Optimization: If the first page has no new posts, all posts are already in DB
-        // No need to fetch older pages - stop immediately to save resources
-        if page_new_count == 0 {
-            if page_num == 0 {
-                trace!("First page has no new posts, all content is up to date");
-            } else {
+        // No need to fetch older pages - stop immediately to save resources.
+        // A backfill keeps going, since older pages may still be missing.
+        if !backfill {
+            if page.new_count == 0 {
+                if page_num == 0 {
+                    trace!("First page has no new posts, all content is up to date");
+                } else {
+                    debug!(
+                        page = page_num,
+                        "No new posts on this page, stopping pagination"
+                    );
+                }
+                return Ok(summary);
+            }
+            if newest_seen.is_some_and(|seen| min_post_id <= seen) {
                 debug!(
                     page = page_num,
-                    "No new posts on this page, stopping pagination"
+                    "Reached already-seen posts, stopping pagination"
                 );
+                return Ok(summary);
             }
-            break;
         }
 
         // Add a small delay between pages to be polite
-        if page_num + 1 < config.rss_max_pages {
+        if page_num + 1 < max_pages {
             tokio::time::sleep(Duration::from_millis(500)).await;
         }
     }
 
-    Ok(total_new_count)
+    if backfill {
+        summary.resume_cursor = before_post_id;
+    }
+    Ok(summary)
+}
+
+/// What ingesting one page of the feed found.
+#[derive(Debug, Default)]
+struct PageSummary {
+    new_count: usize,
+    /// Used as the cursor for the next page.
+    min_post_id: Option<i64>,
+    max_post_id: Option<i64>,
 }
 
 /// Fetch and process a single page of the RSS feed.
 ///
-/// Returns (new_count, min_post_id) where min_post_id is used for cursor pagination.
-///
 /// # Errors
 ///
 /// Returns an error if the RSS feed cannot be fetched or parsed.
@@ -127,7 +306,7 @@ async fn fetch_and_process_page(
     config: &Config,
     db: &Database,
     before_post_id: Option<i64>,
-) -> Result<(usize, Option<i64>)> {
+) -> Result<PageSummary> {
     // Convert RSS URL to JSON URL and build with 'before' parameter for cursor-based pagination
     let json_url = config.rss_url.replace("/posts.rss", "/posts.json");
 
@@ -157,13 +336,34 @@ async fn fetch_and_process_page(
         .await
         .context("Failed to parse JSON response")?;
 
-    let mut new_count = 0;
-    let mut min_post_id: Option<i64> = None;
+    ingest_posts(config, db, json_response.latest_posts).await
+}
+
+/// A `link_archive_account` command found while ingesting a page.
+///
+/// Commands are run after the page's transaction commits, since they write
+/// through the pool and would otherwise wait on the transaction's lock.
+struct AccountLinkCommand {
+    target_username: String,
+    post: NewPost,
+}
+
+/// Store a page of posts with their links and occurrences in one transaction.
+///
+/// One commit per page instead of one per row keeps fsyncs and lock
+/// contention with the archive worker down. On error the transaction rolls
+/// back, so a page is never left half-ingested.
+async fn ingest_posts(
+    config: &Config,
+    db: &Database,
+    mut posts: Vec<LatestPost>,
+) -> Result<PageSummary> {
+    let mut summary = PageSummary::default();
+    let mut account_link_commands = Vec::new();
 
     // Sort posts by created_at timestamp (oldest first) to ensure chronological processing.
     // This is critical for security-sensitive operations like link_archive_account,
     // where processing order determines who successfully claims an account.
-    let mut posts = json_response.latest_posts;
     posts.sort_by(|a, b| a.created_at.cmp(&b.created_at));
 
     // Get base URL and domain for GUID/URL construction
@@ -172,26 +372,34 @@ async fn fetch_and_process_page(
         .context("Failed to get discourse base URL")?;
     let domain = extract_domain(&config.rss_url).unwrap_or_else(|| "unknown".to_string());
 
+    // IMMEDIATE takes the write lock up front: a deferred transaction that
+    // reads first can't upgrade if the worker commits in between
+    let mut tx = db
+        .pool()
+        .begin_with("BEGIN IMMEDIATE")
+        .await
+        .context("Failed to begin ingest transaction")?;
+
     for post in posts {
         // Construct GUID in EXACT same format as RSS: {domain}-post-{id}
         // Example: "discuss.criticalfallibilism.com-post-20218"
         let guid = format!("{}-post-{}", domain, post.id);
 
-        // Track minimum post ID for pagination
-        min_post_id = Some(match min_post_id {
-            Some(current_min) => current_min.min(post.id),
-            None => post.id,
-        });
+        // Track the page's post ID range for pagination
+        summary.min_post_id = Some(summary.min_post_id.map_or(post.id, |min| min.min(post.id)));
+        summary.max_post_id = summary.max_post_id.max(Some(post.id));
 
         // Check if we've seen this post before
-        let existing = get_post_by_guid(db.pool(), &guid).await?;
+        let existing = get_post_by_guid(&mut *tx, &guid).await?;
         let content_html = post.cooked.clone();
         let content_hash = compute_hash(&content_html);
 
         let discourse_url = format!("{}{}", base_url, post.post_url);
 
         // Standardize on simple @username format (ignore display name)
-        let author = Some(format!("@{}", post.username));
+        let author = format!("@{}", post.username);
+        let author_handle =
+            normalize_forum_author(&author, config.forum_author_pattern.as_ref()).map(String::from);
 
         // Use topic title as post title
         let title = post.topic_title.clone();
@@ -202,57 +410,156 @@ async fn fetch_and_process_page(
         let new_post = NewPost {
             guid: guid.clone(),
             discourse_url,
-            author,
+            author: Some(author),
+            author_handle,
             title,
             body_html: Some(content_html.clone()),
             content_hash: Some(content_hash.clone()),
             published_at,
         };
 
+        let is_new = existing.is_none();
         let post_id = if let Some(post) = existing {
             // Check if content changed
             if post.content_hash.as_deref() != Some(&content_hash) {
                 debug!(guid = %guid, "Post content changed, updating");
-                update_post(db.pool(), post.id, &new_post).await?;
+                update_post(&mut *tx, post.id, &new_post).await?;
             }
             post.id
         } else {
             debug!(guid = %guid, "New post found");
-            let id = insert_post(db.pool(), &new_post).await?;
-            new_count += 1;
+            let id = insert_post(&mut *tx, &new_post).await?;
+            summary.new_count += 1;
             id
         };
 
-        // Extract and process links
-        process_links(db, post_id, &content_html, config).await?;
+        // Extract and process links, unless the author isn't allowlisted
+        if is_author_allowed(
+            &config.archive_author_allowlist,
+            new_post.author_handle.as_deref(),
+        ) {
+            process_links(&mut tx, post_id, &content_html, config).await?;
+
+            if is_new && config.archive_post_snapshots {
+                if let Err(e) = queue_post_snapshot(&mut tx, post_id, &new_post).await {
+                    warn!(guid = %guid, "Failed to queue post snapshot: {e:#}");
+                }
+            }
+        } else if is_new {
+            debug!(guid = %guid, author = ?new_post.author, "Skipping links from author not on allowlist");
+            let action = AuditAction::PostAuthorNotAllowed {
+                post_id,
+                post_guid: guid.clone(),
+                author: new_post.author.clone(),
+            };
+            if let Err(e) = record_audit(&mut *tx, &AuditActor::new(None), &action).await {
+                warn!(guid = %guid, "Failed to record skipped post: {e:#}");
+            }
+        }
 
         // Check for account linking command at start of post
         if let Some(target_username) = extract_link_account_command(&content_html) {
-            process_account_link_command(
-                db,
-                &target_username,
-                new_post.author.as_deref(),
-                &new_post.guid,
-                &new_post.discourse_url,
-                new_post.title.as_deref(),
-                new_post.published_at.as_deref(),
-            )
-            .await;
+            account_link_commands.push(AccountLinkCommand {
+                target_username,
+                post: new_post,
+            });
         }
     }
 
-    Ok((new_count, min_post_id))
+    tx.commit()
+        .await
+        .context("Failed to commit ingest transaction")?;
+
+    // Still oldest first, so the earliest post claims an account
+    for command in account_link_commands {
+        process_account_link_command(
+            db,
+            &command.target_username,
+            command.post.author.as_deref(),
+            command.post.author_handle.as_deref(),
+            &command.post.guid,
+            &command.post.discourse_url,
+            command.post.title.as_deref(),
+            command.post.published_at.as_deref(),
+        )
+        .await;
+    }
+
+    Ok(summary)
+}
+
+/// Check whether links from a post author should be archived.
+///
+/// An empty allowlist allows everyone. Entries may be written with or without
+/// a leading `@` and match the author's normalized forum handle
+/// case-insensitively; posts without an author are rejected when an allowlist
+/// is set.
+fn is_author_allowed(allowlist: &[String], handle: Option<&str>) -> bool {
+    if allowlist.is_empty() {
+        return true;
+    }
+    let Some(handle) = handle else {
+        return false;
+    };
+    allowlist.iter().any(|entry| {
+        let entry = entry.trim();
+        entry
+            .strip_prefix('@')
+            .unwrap_or(entry)
+            .eq_ignore_ascii_case(handle)
+    })
+}
+
+/// Queue an archive of the post's own Discourse page and tie it to the post.
+///
+/// This deliberately bypasses the self-link and excluded-domain checks that
+/// `process_single_link` applies to the forum domain; the worker honours the
+/// same opt-in when it sees the archive belongs to a post snapshot.
+async fn queue_post_snapshot(
+    conn: &mut SqliteConnection,
+    post_id: i64,
+    post: &NewPost,
+) -> Result<()> {
+    let normalized = normalize_url(&post.discourse_url);
+
+    let new_link = NewLink {
+        original_url: post.discourse_url.clone(),
+        normalized_url: normalized.clone(),
+        canonical_url: None,
+        domain: extract_domain(&normalized).unwrap_or_default(),
+    };
+    let link_id = get_or_create_link_on(&mut *conn, &new_link).await?;
+
+    let archive_id =
+        create_pending_archive_on(&mut *conn, link_id, post.published_at.as_deref()).await?;
+    set_post_snapshot_archive(&mut *conn, post_id, archive_id).await?;
+    debug!(post_id, archive_id, url = %post.discourse_url, "Queued post snapshot");
+
+    Ok(())
 }
 
 /// Process links extracted from a post.
-async fn process_links(db: &Database, post_id: i64, html: &str, config: &Config) -> Result<()> {
+async fn process_links(
+    conn: &mut SqliteConnection,
+    post_id: i64,
+    html: &str,
+    config: &Config,
+) -> Result<()> {
     let extracted = extract_links(html);
 
     // Extract forum domain from RSS URL to skip self-links
     let forum_domain = extract_domain(&config.rss_url);
 
     for link in extracted {
-        if let Err(e) = process_single_link(db, post_id, &link, forum_domain.as_deref()).await {
+        if let Err(e) = process_single_link(
+            conn,
+            post_id,
+            &link,
+            forum_domain.as_deref(),
+            config.archive_quote_only_links,
+        )
+        .await
+        {
             warn!(url = %link.url, "Failed to process link: {e:#}");
         }
     }
@@ -262,18 +569,14 @@ async fn process_links(db: &Database, post_id: i64, html: &str, config: &Config)
 
 /// Process a single extracted link.
 async fn process_single_link(
-    db: &Database,
+    conn: &mut SqliteConnection,
     post_id: i64,
     link: &ExtractedLink,
     forum_domain: Option<&str>,
+    archive_quote_only: bool,
 ) -> Result<()> {
     // Normalize the URL: generic first, then handler-specific (e.g. YouTube strips list=/index=).
-    let normalized = normalize_url(&link.url);
-    let normalized = if let Some(handler) = HANDLERS.find_handler(&normalized) {
-        handler.normalize_url(&normalized)
-    } else {
-        normalized
-    };
+    let normalized = normalize_link_url(&link.url);
     let domain = extract_domain(&normalized).unwrap_or_default();
 
     // Skip internal links and non-http URLs
@@ -310,23 +613,17 @@ async fn process_single_link(
         }
     }
 
-    // Check if we already have this link
-    let existing_link = get_link_by_normalized_url(db.pool(), &normalized).await?;
-
-    let link_id = if let Some(existing) = existing_link {
-        existing.id
-    } else {
-        let new_link = NewLink {
-            original_url: link.url.clone(),
-            normalized_url: normalized.clone(),
-            canonical_url: None,
-            domain: domain.clone(),
-        };
-        insert_link(db.pool(), &new_link).await?
+    // Reuse the existing link for this URL or create it
+    let new_link = NewLink {
+        original_url: link.url.clone(),
+        normalized_url: normalized.clone(),
+        canonical_url: None,
+        domain: domain.clone(),
     };
+    let link_id = get_or_create_link_on(&mut *conn, &new_link).await?;
 
     // Check if this occurrence already exists
-    if link_occurrence_exists(db.pool(), link_id, post_id).await? {
+    if link_occurrence_exists(&mut *conn, link_id, post_id).await? {
         return Ok(());
     }
 
@@ -337,20 +634,27 @@ async fn process_single_link(
         in_quote: link.in_quote,
         context_snippet: link.context.clone(),
     };
-    insert_link_occurrence(db.pool(), &occurrence).await?;
+    insert_link_occurrence(&mut *conn, &occurrence).await?;
 
     // Decide if we should create an archive
-    let should_archive = should_archive_link(db, link_id, link.in_quote).await?;
+    let should_archive = should_archive_link(
+        conn,
+        link_id,
+        link.in_quote,
+        &domain_lower,
+        archive_quote_only,
+    )
+    .await?;
 
     if should_archive {
         // Check if archive already exists
-        if get_archive_by_link_id(db.pool(), link_id).await?.is_none() {
+        if get_archive_by_link_id(&mut *conn, link_id).await?.is_none() {
             // Fetch the post to get its publication date
-            let post = db::get_post(db.pool(), post_id).await?;
+            let post = db::get_post(&mut *conn, post_id).await?;
             let post_date = post.and_then(|p| p.published_at);
 
             debug!(url = %link.url, post_date = ?post_date, "Creating pending archive");
-            create_pending_archive(db.pool(), link_id, post_date.as_deref()).await?;
+            create_pending_archive_on(&mut *conn, link_id, post_date.as_deref()).await?;
         }
     }
 
@@ -358,17 +662,25 @@ async fn process_single_link(
 }
 
 /// Determine if a link should be archived.
-async fn should_archive_link(db: &Database, link_id: i64, in_quote: bool) -> Result<bool> {
-    // If this is a quote link, only archive if it's the first occurrence ever
+async fn should_archive_link(
+    conn: &mut SqliteConnection,
+    link_id: i64,
+    in_quote: bool,
+    domain: &str,
+    archive_quote_only: bool,
+) -> Result<bool> {
+    // If this is a quote link, only consider archiving if it's the first occurrence ever
     if in_quote {
         // Check if there's already an archive for this link
-        if get_archive_by_link_id(db.pool(), link_id).await?.is_some() {
+        if get_archive_by_link_id(&mut *conn, link_id).await?.is_some() {
             return Ok(false);
         }
         // Check if there's any non-quote occurrence
-        if db::link_has_non_quote_occurrence(db.pool(), link_id).await? {
+        if db::link_has_non_quote_occurrence(&mut *conn, link_id).await? {
             return Ok(false);
         }
+        // First occurrence is quote-only: defer to the domain override or global setting
+        return db::should_archive_quote_only_link(&mut *conn, domain, archive_quote_only).await;
     }
 
     Ok(true)
@@ -452,20 +764,6 @@ fn extract_link_account_command(html: &str) -> Option<String> {
         .map(|m| m.as_str().to_string())
 }
 
-/// Extract the forum username from an RSS author name.
-///
-/// RSS author names are in the format `@Username Firstname Lastname`.
-/// This extracts just the `@Username` part (the @ followed by adjacent non-whitespace characters).
-fn extract_forum_display_name(author: &str) -> Option<String> {
-    let trimmed = author.trim();
-    if !trimmed.starts_with('@') {
-        return None;
-    }
-    // Find the first whitespace to get just @Username
-    let username = trimmed.split_whitespace().next().filter(|s| s.len() > 1)?; // Must have at least @ and one character
-    Some(username.to_string())
-}
-
 /// Process a link_archive_account command from a forum post.
 ///
 /// This links a forum account to an existing archive account, sets the display name,
@@ -474,35 +772,27 @@ async fn process_account_link_command(
     db: &Database,
     target_username: &str,
     forum_author: Option<&str>,
+    forum_handle: Option<&str>,
     post_guid: &str,
     post_url: &str,
     post_title: Option<&str>,
     published_at: Option<&str>,
 ) {
-    let forum_username = match forum_author {
-        Some(name) if !name.is_empty() => name,
+    // Links are keyed by the normalized handle; the raw author string is kept
+    // on the link for auditing.
+    let forum_username = match forum_handle {
+        Some(handle) if !handle.is_empty() => handle,
         _ => {
             warn!(
                 post_guid = %post_guid,
                 target_username = %target_username,
-                "Cannot link account: post has no author"
-            );
-            return;
-        }
-    };
-
-    // Extract display name from forum author (e.g., "@Max Max" -> "@Max")
-    let forum_display_name = match extract_forum_display_name(forum_username) {
-        Some(name) => name,
-        None => {
-            warn!(
-                post_guid = %post_guid,
-                forum_username = %forum_username,
-                "Cannot extract display name from forum author (must start with @)"
+                forum_author = ?forum_author,
+                "Cannot link account: post has no recognizable author"
             );
             return;
         }
     };
+    let forum_display_name = format!("@{forum_username}");
 
     // Check if this forum account is already linked
     match get_forum_link_by_forum_username(db.pool(), forum_username).await {
@@ -590,7 +880,7 @@ async fn process_account_link_command(
         forum_username,
         post_guid,
         post_url,
-        Some(&forum_display_name),
+        forum_author,
         post_title,
         published_at,
     )
@@ -604,7 +894,7 @@ async fn process_account_link_command(
         return;
     }
 
-    // Update user's display_name to extracted forum username (e.g., "@Max")
+    // Update user's display_name to the forum handle (e.g., "@Max")
     if let Err(e) = update_user_profile(
         db.pool(),
         target_user.id,
@@ -630,24 +920,14 @@ async fn process_account_link_command(
     }
 
     // Create audit event
-    let metadata = serde_json::json!({
-        "forum_username": forum_username,
-        "post_guid": post_guid,
-        "post_url": post_url,
-    });
-    if let Err(e) = create_audit_event(
-        db.pool(),
-        Some(target_user.id),
-        "forum_account_linked",
-        Some("user"),
-        Some(target_user.id),
-        Some(&metadata.to_string()),
-        None, // No IP - triggered by RSS
-        None,
-        None,
-    )
-    .await
-    {
+    let action = AuditAction::ForumAccountLinked {
+        user_id: target_user.id,
+        forum_username: forum_username.to_string(),
+        post_guid: post_guid.to_string(),
+        post_url: post_url.to_string(),
+    };
+    // No request context - triggered by RSS
+    if let Err(e) = record_audit(db.pool(), &AuditActor::new(Some(target_user.id)), &action).await {
         error!(
             target_username = %target_username,
             "Failed to create audit event: {e:#}"
@@ -666,6 +946,67 @@ async fn process_account_link_command(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_jittered_interval_within_bounds() {
+        let interval = Duration::from_secs(60);
+        let jitter = Duration::from_secs(5);
+        for _ in 0..1000 {
+            let next = jittered_interval(interval, jitter);
+            assert!(next >= interval);
+            assert!(next <= interval + jitter);
+        }
+        assert_eq!(jittered_interval(interval, Duration::ZERO), interval);
+    }
+
+    #[test]
+    fn test_poll_mode_backfills_first_poll_and_resumes() {
+        let mut config = Config::for_testing();
+        let mut state = FeedState {
+            feed_url: config.rss_url.clone(),
+            newest_post_id: Some(42),
+            backfill_cursor: None,
+            backfilled_at: None,
+            last_polled_at: "2024-01-01 00:00:00".to_string(),
+            created_at: "2024-01-01 00:00:00".to_string(),
+        };
+        let steady = PollMode::Incremental {
+            newest_seen: Some(42),
+        };
+
+        // Without the flag, even the first poll is incremental
+        assert_eq!(
+            poll_mode(&config, None, None),
+            PollMode::Incremental { newest_seen: None }
+        );
+        assert_eq!(poll_mode(&config, Some(&state), None), steady);
+
+        config.rss_backfill = true;
+        assert_eq!(
+            poll_mode(&config, None, None),
+            PollMode::Backfill { resume_from: None }
+        );
+        // Posts stored before feed state was tracked mean the feed was polled
+        assert_eq!(
+            poll_mode(&config, None, Some(40)),
+            PollMode::Incremental {
+                newest_seen: Some(40)
+            }
+        );
+        // Once the feed has state, polling is incremental again
+        assert_eq!(poll_mode(&config, Some(&state), None), steady);
+
+        // ...unless a backfill was cut short
+        state.backfill_cursor = Some(7);
+        assert_eq!(
+            poll_mode(&config, Some(&state), None),
+            PollMode::Backfill {
+                resume_from: Some(7)
+            }
+        );
+        config.rss_backfill = false;
+        assert_eq!(poll_mode(&config, Some(&state), None), steady);
+    }
+
     #[test]
     fn test_compute_hash() {
         let hash1 = compute_hash("hello");
@@ -800,40 +1141,28 @@ mod tests {
     }
 
     #[test]
-    fn test_extract_forum_display_name() {
-        // Standard case: @Username followed by real name
-        assert_eq!(
-            extract_forum_display_name("@Max Max"),
-            Some("@Max".to_string())
-        );
-
-        // Multiple name parts
-        assert_eq!(
-            extract_forum_display_name("@JohnDoe John Doe"),
-            Some("@JohnDoe".to_string())
-        );
-
-        // Just username, no real name
-        assert_eq!(
-            extract_forum_display_name("@Username"),
-            Some("@Username".to_string())
-        );
-
-        // With leading/trailing whitespace
-        assert_eq!(
-            extract_forum_display_name("  @User  Firstname Lastname  "),
-            Some("@User".to_string())
-        );
-
-        // No @ prefix - should return None
-        assert_eq!(extract_forum_display_name("Username"), None);
-        assert_eq!(extract_forum_display_name("User Name"), None);
-
-        // Empty string
-        assert_eq!(extract_forum_display_name(""), None);
+    fn test_is_author_allowed_empty_list_allows_all() {
+        assert!(is_author_allowed(&[], Some("anyone")));
+        assert!(is_author_allowed(&[], None));
+    }
 
-        // Just @ with no username - should return None
-        assert_eq!(extract_forum_display_name("@"), None);
-        assert_eq!(extract_forum_display_name("@ something"), None);
+    #[test]
+    fn test_is_author_allowed_case_insensitive() {
+        let allowlist = vec!["Alice".to_string(), "@bob".to_string()];
+
+        assert!(is_author_allowed(&allowlist, Some("alice")));
+        assert!(is_author_allowed(&allowlist, Some("ALICE")));
+        assert!(is_author_allowed(&allowlist, Some("Bob")));
+        assert!(is_author_allowed(&allowlist, Some("bob")));
+        // Handle normalized from an RSS-style author with display name
+        assert!(is_author_allowed(
+            &allowlist,
+            normalize_forum_author("Alice Smith (@alice)", None)
+        ));
+
+        assert!(!is_author_allowed(&allowlist, Some("mallory")));
+        assert!(!is_author_allowed(&allowlist, Some("alicex")));
+        assert!(!is_author_allowed(&allowlist, Some("")));
+        assert!(!is_author_allowed(&allowlist, None));
     }
 }
@@ -5,16 +5,20 @@
 
 use std::time::Duration;
 
-use tracing::{error, info, trace};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, trace};
 
 use crate::config::Config;
 use crate::db::{
-    get_pending_thread_archive_jobs, set_thread_archive_job_complete,
-    set_thread_archive_job_failed, Database,
+    get_pending_thread_archive_jobs, get_thread_archive_job, set_thread_archive_job_complete,
+    set_thread_archive_job_failed, Database, ThreadArchiveJob, ThreadArchiveJobStatus,
 };
 
 use super::thread_archiver::archive_thread_links;
 
+/// How often a running job's status is re-read to pick up cancellation requests.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Run the thread archive worker loop.
 ///
 /// This function runs forever, polling for pending thread archive jobs
@@ -27,39 +31,7 @@ pub async fn run(config: Config, db: Database) {
         // Check for pending jobs
         match get_pending_thread_archive_jobs(db.pool(), 1).await {
             Ok(jobs) if !jobs.is_empty() => {
-                let job = &jobs[0];
-                info!(
-                    job_id = job.id,
-                    thread_url = %job.thread_url,
-                    user_id = job.user_id,
-                    "Processing thread archive job"
-                );
-
-                // Process the thread
-                match archive_thread_links(&config, &db, job).await {
-                    Ok(progress) => {
-                        info!(
-                            job_id = job.id,
-                            posts = progress.processed_posts,
-                            links = progress.new_links_found,
-                            archives = progress.archives_created,
-                            skipped = progress.skipped_links,
-                            "Thread archive job completed successfully"
-                        );
-                        if let Err(e) = set_thread_archive_job_complete(db.pool(), job.id).await {
-                            error!(job_id = job.id, "Failed to mark job complete: {e}");
-                        }
-                    }
-                    Err(e) => {
-                        error!(job_id = job.id, error = %e, "Thread archive job failed");
-                        let error_msg = format!("{e:#}");
-                        if let Err(e) =
-                            set_thread_archive_job_failed(db.pool(), job.id, &error_msg).await
-                        {
-                            error!(job_id = job.id, "Failed to mark job failed: {e}");
-                        }
-                    }
-                }
+                process_job(&config, &db, &jobs[0]).await;
             }
             Ok(_) => {
                 // No pending jobs, just wait
@@ -74,3 +46,71 @@ pub async fn run(config: Config, db: Database) {
         tokio::time::sleep(Duration::from_secs(1)).await;
     }
 }
+
+/// Process a single thread archive job and record its outcome.
+///
+/// A watcher task polls the job's status while it runs; if the job is
+/// cancelled from the web UI the archiver stops between posts and the job is
+/// left in `cancelled` with the progress made so far.
+pub async fn process_job(config: &Config, db: &Database, job: &ThreadArchiveJob) {
+    info!(
+        job_id = job.id,
+        thread_url = %job.thread_url,
+        user_id = job.user_id,
+        "Processing thread archive job"
+    );
+
+    let cancel = CancellationToken::new();
+    let watcher = tokio::spawn(watch_for_cancellation(db.clone(), job.id, cancel.clone()));
+
+    let result = archive_thread_links(config, db, job, &cancel).await;
+    watcher.abort();
+
+    match result {
+        Ok(progress) if progress.cancelled => {
+            info!(
+                job_id = job.id,
+                posts = progress.processed_posts,
+                links = progress.new_links_found,
+                archives = progress.archives_created,
+                "Thread archive job cancelled"
+            );
+        }
+        Ok(progress) => {
+            info!(
+                job_id = job.id,
+                posts = progress.processed_posts,
+                links = progress.new_links_found,
+                archives = progress.archives_created,
+                skipped = progress.skipped_links,
+                "Thread archive job completed successfully"
+            );
+            if let Err(e) = set_thread_archive_job_complete(db.pool(), job.id).await {
+                error!(job_id = job.id, "Failed to mark job complete: {e}");
+            }
+        }
+        Err(e) => {
+            error!(job_id = job.id, error = %e, "Thread archive job failed");
+            let error_msg = format!("{e:#}");
+            if let Err(e) = set_thread_archive_job_failed(db.pool(), job.id, &error_msg).await {
+                error!(job_id = job.id, "Failed to mark job failed: {e}");
+            }
+        }
+    }
+}
+
+/// Cancel `token` once the job's status becomes `cancelled`.
+async fn watch_for_cancellation(db: Database, job_id: i64, token: CancellationToken) {
+    loop {
+        match get_thread_archive_job(db.pool(), job_id).await {
+            Ok(Some(job)) if job.status_enum() == Some(ThreadArchiveJobStatus::Cancelled) => {
+                debug!(job_id, "Thread archive job cancellation requested");
+                token.cancel();
+                return;
+            }
+            Ok(_) => {}
+            Err(e) => error!(job_id, "Failed to check thread archive job status: {e}"),
+        }
+        tokio::time::sleep(CANCEL_POLL_INTERVAL).await;
+    }
+}
@@ -8,17 +8,19 @@ use std::time::Duration;
 
 use anyhow::{Context, Result};
 use sha2::{Digest, Sha256};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, trace, warn};
 
 use crate::config::Config;
 use crate::constants::ARCHIVER_HONEST_USER_AGENT;
 use crate::db::{
-    self, create_pending_archive, get_archive_by_link_id, get_link_by_normalized_url,
-    get_post_by_guid, insert_link, insert_link_occurrence, insert_post, is_domain_excluded,
-    link_occurrence_exists, update_post, update_thread_archive_job_progress, Database,
-    DiscoursePost, DiscoursePostsResponse, NewLink, NewLinkOccurrence, NewPost, ThreadArchiveJob,
+    self, create_pending_archive, forum_author_handle, get_archive_by_link_id,
+    get_link_by_normalized_url, get_or_create_link, get_post_by_guid, insert_link_occurrence,
+    insert_post, is_domain_excluded, link_occurrence_exists, update_post,
+    update_thread_archive_job_progress, Database, DiscoursePost, DiscoursePostsResponse, NewLink,
+    NewLinkOccurrence, NewPost, ThreadArchiveJob,
 };
-use crate::handlers::{normalize_url, HANDLERS};
+use crate::handlers::normalize_link_url;
 use crate::rss::link_extractor::extract_links;
 
 /// Progress tracking for thread archive job.
@@ -28,6 +30,8 @@ pub struct ArchiveProgress {
     pub new_links_found: i64,
     pub archives_created: i64,
     pub skipped_links: i64,
+    /// The run stopped early because the job was cancelled.
+    pub cancelled: bool,
 }
 
 /// Archive all links from a Discourse thread via JSON API.
@@ -35,6 +39,9 @@ pub struct ArchiveProgress {
 /// Paginates through all posts in the thread, processes each post, extracts links,
 /// and creates pending archives for new links.
 ///
+/// `cancel` is checked before each post and page; once it fires the run stops
+/// and returns the progress so far with `cancelled` set.
+///
 /// # Errors
 ///
 /// Returns an error if the JSON API cannot be fetched or parsed.
@@ -42,6 +49,7 @@ pub async fn archive_thread_links(
     config: &Config,
     db: &Database,
     job: &ThreadArchiveJob,
+    cancel: &CancellationToken,
 ) -> Result<ArchiveProgress> {
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(60))
@@ -67,7 +75,12 @@ pub async fn archive_thread_links(
     let mut page_num = 0;
     const PAGE_SIZE: i64 = 20; // Each request returns ~20 posts
 
-    loop {
+    'pages: loop {
+        if cancel.is_cancelled() {
+            progress.cancelled = true;
+            break;
+        }
+
         // Fetch page - use /t/{topic_id}/{post_number}.json endpoint
         // Pattern: 1, 21, 41, 61, 81... (increments of 20)
         // This endpoint returns posts centered around the specified post_number
@@ -126,6 +139,11 @@ pub async fn archive_thread_links(
 
         // Process each post in this batch
         for post in &batch.post_stream.posts {
+            if cancel.is_cancelled() {
+                progress.cancelled = true;
+                break 'pages;
+            }
+
             let guid = format!("topic-{}-post-{}", post.topic_id, post.id);
 
             // Skip if already processed in this run (handles potential duplicates from pagination)
@@ -165,6 +183,7 @@ pub async fn archive_thread_links(
                 &post.cooked,
                 forum_domain.as_deref(),
                 Some(&post.created_at),
+                config.archive_quote_only_links,
             )
             .await?;
 
@@ -206,7 +225,22 @@ pub async fn archive_thread_links(
         }
 
         // Rate limiting between requests
-        tokio::time::sleep(Duration::from_millis(500)).await;
+        tokio::select! {
+            () = cancel.cancelled() => {
+                progress.cancelled = true;
+                break;
+            }
+            () = tokio::time::sleep(Duration::from_millis(500)) => {}
+        }
+    }
+
+    if progress.cancelled {
+        info!(
+            job_id = job.id,
+            processed_posts = progress.processed_posts,
+            "Thread archive cancelled"
+        );
+        return Ok(progress);
     }
 
     // Final check: fetch the latest posts to ensure we didn't miss any
@@ -228,6 +262,11 @@ pub async fn archive_thread_links(
         if let Ok(final_batch) = final_response.json::<DiscoursePostsResponse>().await {
             let mut final_new_count = 0;
             for post in &final_batch.post_stream.posts {
+                if cancel.is_cancelled() {
+                    progress.cancelled = true;
+                    break;
+                }
+
                 let guid = format!("topic-{}-post-{}", post.topic_id, post.id);
 
                 if processed_guids.contains(&guid) {
@@ -257,6 +296,7 @@ pub async fn archive_thread_links(
                     &post.cooked,
                     forum_domain.as_deref(),
                     Some(&post.created_at),
+                    config.archive_quote_only_links,
                 )
                 .await?;
 
@@ -337,6 +377,8 @@ fn build_new_post(post: &DiscoursePost, content_hash: &str, forum_domain: Option
         guid,
         discourse_url,
         author: Some(post.username.clone()),
+        // The JSON API gives the bare username, so no configured pattern applies.
+        author_handle: forum_author_handle(&post.username).map(String::from),
         title: None, // We don't have thread title in individual posts
         body_html: Some(post.cooked.clone()),
         content_hash: Some(content_hash.to_string()),
@@ -351,12 +393,22 @@ async fn process_post_links(
     html: &str,
     forum_domain: Option<&str>,
     post_date: Option<&str>,
+    archive_quote_only: bool,
 ) -> Result<ArchiveProgress> {
     let extracted = extract_links(html);
     let mut progress = ArchiveProgress::default();
 
     for link in extracted {
-        match process_single_link(db, post_id, &link, forum_domain, post_date).await {
+        match process_single_link(
+            db,
+            post_id,
+            &link,
+            forum_domain,
+            post_date,
+            archive_quote_only,
+        )
+        .await
+        {
             Ok(result) => {
                 progress.new_links_found += i64::from(result.is_new_link);
                 progress.archives_created += i64::from(result.archive_created);
@@ -386,14 +438,10 @@ async fn process_single_link(
     link: &crate::rss::link_extractor::ExtractedLink,
     forum_domain: Option<&str>,
     post_date: Option<&str>,
+    archive_quote_only: bool,
 ) -> Result<LinkProcessResult> {
     // Normalize the URL: generic first, then handler-specific (e.g. YouTube strips list=/index=).
-    let normalized = normalize_url(&link.url);
-    let normalized = if let Some(handler) = HANDLERS.find_handler(&normalized) {
-        handler.normalize_url(&normalized)
-    } else {
-        normalized
-    };
+    let normalized = normalize_link_url(&link.url);
     let domain = extract_domain(&normalized).unwrap_or_default();
 
     // Skip internal links and non-http URLs
@@ -452,21 +500,18 @@ async fn process_single_link(
         });
     }
 
-    // Check if we already have this link
-    let existing_link = get_link_by_normalized_url(db.pool(), &normalized).await?;
-    let is_new_link = existing_link.is_none();
+    // Check if we already have this link (only used for reporting)
+    let is_new_link = get_link_by_normalized_url(db.pool(), &normalized)
+        .await?
+        .is_none();
 
-    let link_id = if let Some(existing) = existing_link {
-        existing.id
-    } else {
-        let new_link = NewLink {
-            original_url: link.url.clone(),
-            normalized_url: normalized.clone(),
-            canonical_url: None,
-            domain: domain.clone(),
-        };
-        insert_link(db.pool(), &new_link).await?
+    let new_link = NewLink {
+        original_url: link.url.clone(),
+        normalized_url: normalized.clone(),
+        canonical_url: None,
+        domain: domain.clone(),
     };
+    let link_id = get_or_create_link(db.pool(), &new_link).await?;
 
     // Check if this occurrence already exists
     if link_occurrence_exists(db.pool(), link_id, post_id).await? {
@@ -487,7 +532,14 @@ async fn process_single_link(
     insert_link_occurrence(db.pool(), &occurrence).await?;
 
     // Decide if we should create an archive
-    let should_archive = should_archive_link(db, link_id, link.in_quote).await?;
+    let should_archive = should_archive_link(
+        db,
+        link_id,
+        link.in_quote,
+        &domain_lower,
+        archive_quote_only,
+    )
+    .await?;
     let mut archive_created = false;
 
     if should_archive {
@@ -507,8 +559,14 @@ async fn process_single_link(
 }
 
 /// Determine if a link should be archived.
-async fn should_archive_link(db: &Database, link_id: i64, in_quote: bool) -> Result<bool> {
-    // If this is a quote link, only archive if it's the first occurrence ever
+async fn should_archive_link(
+    db: &Database,
+    link_id: i64,
+    in_quote: bool,
+    domain: &str,
+    archive_quote_only: bool,
+) -> Result<bool> {
+    // If this is a quote link, only consider archiving if it's the first occurrence ever
     if in_quote {
         // Check if there's already an archive for this link
         if get_archive_by_link_id(db.pool(), link_id).await?.is_some() {
@@ -518,6 +576,8 @@ async fn should_archive_link(db: &Database, link_id: i64, in_quote: bool) -> Res
         if db::link_has_non_quote_occurrence(db.pool(), link_id).await? {
             return Ok(false);
         }
+        // First occurrence is quote-only: defer to the domain override or global setting
+        return db::should_archive_quote_only_link(db.pool(), domain, archive_quote_only).await;
     }
 
     Ok(true)
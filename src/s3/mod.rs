@@ -6,11 +6,49 @@ use anyhow::{Context, Result};
 use s3::creds::Credentials;
 use s3::region::Region;
 use s3::Bucket;
-use tracing::debug;
+use std::time::Duration;
+
+use tracing::{debug, trace, warn};
 
 use crate::config::Config;
 use multipart::StreamingUploader;
 
+/// `Content-Encoding` used for precompressed text artifacts such as complete.html.
+///
+/// zstd rather than gzip: the S3 clients' HTTP stack transparently decodes gzip
+/// responses, which would hide the stored encoding from the proxy.
+pub const PRECOMPRESSED_ENCODING: &str = "zstd";
+
+/// zstd level for precompressed artifacts: they are written once and served many
+/// times, but monolith output can run to tens of megabytes.
+const PRECOMPRESSION_LEVEL: i32 = 12;
+
+/// Compress an artifact body with [`PRECOMPRESSED_ENCODING`].
+///
+/// # Errors
+///
+/// Returns an error if compression fails.
+pub fn precompress(data: &[u8]) -> Result<Vec<u8>> {
+    zstd::encode_all(data, PRECOMPRESSION_LEVEL).context("Failed to zstd-compress artifact")
+}
+
+/// Undo the `Content-Encoding` an artifact was stored with.
+///
+/// Data without an encoding is returned unchanged.
+///
+/// # Errors
+///
+/// Returns an error if the encoding is unsupported or the data is corrupt.
+pub fn decode_stored(data: Vec<u8>, content_encoding: Option<&str>) -> Result<Vec<u8>> {
+    match content_encoding {
+        None => Ok(data),
+        Some(encoding) if encoding.eq_ignore_ascii_case(PRECOMPRESSED_ENCODING) => {
+            zstd::decode_all(data.as_slice()).context("Failed to decompress zstd artifact")
+        }
+        Some(other) => anyhow::bail!("Unsupported stored content encoding: {other}"),
+    }
+}
+
 /// S3 client wrapper.
 #[derive(Clone)]
 pub struct S3Client {
@@ -84,10 +122,13 @@ impl S3Client {
             .first_or_octet_stream()
             .to_string();
 
-        // Use streaming uploader to avoid memory constraints
-        self.streaming_uploader
-            .upload_file(s3_key, local_path, &content_type, archive_id)
-            .await
+        // Use streaming uploader to avoid memory constraints. Boxed so the
+        // uploader's large future doesn't inflate every caller's stack frame.
+        Box::pin(
+            self.streaming_uploader
+                .upload_file(s3_key, local_path, &content_type, archive_id),
+        )
+        .await
     }
 
     /// Upload bytes to S3.
@@ -97,26 +138,70 @@ impl S3Client {
     /// Returns an error if the upload fails.
     pub async fn upload_bytes(&self, data: &[u8], s3_key: &str, content_type: &str) -> Result<()> {
         // Use streaming uploader for consistency
-        self.streaming_uploader
-            .upload_bytes(data, s3_key, content_type)
-            .await
+        Box::pin(
+            self.streaming_uploader
+                .upload_bytes(data, s3_key, content_type),
+        )
+        .await
+    }
+
+    /// Upload precompressed bytes to S3 with the given `Content-Encoding`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the upload fails.
+    pub async fn upload_encoded_bytes(
+        &self,
+        data: &[u8],
+        s3_key: &str,
+        content_type: &str,
+        content_encoding: &str,
+    ) -> Result<()> {
+        Box::pin(self.streaming_uploader.upload_encoded_bytes(
+            data,
+            s3_key,
+            content_type,
+            Some(content_encoding),
+        ))
+        .await
     }
 
     /// Check if an object exists in S3.
     ///
+    /// Only an explicit 404 counts as "not found". Transient failures
+    /// (network errors, 5xx, throttling) are retried with backoff so that a
+    /// flaky endpoint isn't mistaken for a missing object.
+    ///
     /// # Errors
     ///
     /// Returns an error if the head request fails for reasons other than not found.
     pub async fn object_exists(&self, s3_key: &str) -> Result<bool> {
-        match self.bucket.head_object(s3_key).await {
-            Ok(_) => Ok(true),
-            Err(s3::error::S3Error::HttpFailWithBody(404, _)) => Ok(false),
-            Err(s3::error::S3Error::HttpFail) => {
-                // Check if it was a 404
-                Ok(false)
-            }
-            Err(e) => Err(anyhow::anyhow!("S3 head object failed: {e}")),
-        }
+        head_object_exists(&self.bucket, s3_key).await
+    }
+
+    /// Size in bytes of an object in S3, or `None` if it doesn't exist.
+    ///
+    /// Retries transient failures like [`Self::object_exists`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the head request fails for reasons other than not found.
+    pub async fn object_size(&self, s3_key: &str) -> Result<Option<i64>> {
+        head_object_size(&self.bucket, s3_key).await
+    }
+
+    /// Check that the bucket is reachable with the configured credentials.
+    ///
+    /// Read-only: heads a key that normally doesn't exist, so a 404 counts as
+    /// success while access or network errors are returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bucket can't be reached or access is denied.
+    pub async fn check_connectivity(&self) -> Result<()> {
+        head_object_exists(&self.bucket, CONNECTIVITY_PROBE_KEY)
+            .await
+            .map(|_| ())
     }
 
     /// Get metadata for an S3 object (size, content-type).
@@ -228,7 +313,8 @@ impl S3Client {
     ///
     /// Returns an error if the get request fails.
     pub async fn get_object(&self, s3_key: &str) -> Result<Option<(Vec<u8>, String)>> {
-        debug!(key = %s3_key, "Getting S3 object");
+        // Per-request logging for the S3 proxy comes from the HTTP trace span
+        trace!(key = %s3_key, "Getting S3 object");
 
         match self.bucket.get_object(s3_key).await {
             Ok(response) => {
@@ -301,3 +387,205 @@ impl std::fmt::Debug for S3Client {
             .finish()
     }
 }
+
+/// Number of HEAD attempts before giving up on transient failures.
+const HEAD_MAX_ATTEMPTS: u32 = 3;
+
+/// Key headed by [`S3Client::check_connectivity`].
+const CONNECTIVITY_PROBE_KEY: &str = ".check-config";
+
+/// Delay before the first HEAD retry; doubles on each subsequent attempt.
+const HEAD_RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+
+/// How a HEAD response status should be interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HeadStatus {
+    Exists,
+    Missing,
+    Transient,
+    Failed,
+}
+
+fn classify_head_status(status: u16) -> HeadStatus {
+    match status {
+        200..=299 => HeadStatus::Exists,
+        404 => HeadStatus::Missing,
+        408 | 429 | 500..=599 => HeadStatus::Transient,
+        _ => HeadStatus::Failed,
+    }
+}
+
+/// Whether a request error is a connection-level failure worth retrying.
+fn is_transient_s3_error(error: &s3::error::S3Error) -> bool {
+    matches!(
+        error,
+        s3::error::S3Error::Hyper(_) | s3::error::S3Error::Io(_) | s3::error::S3Error::HttpFail
+    )
+}
+
+async fn head_object_exists(bucket: &Bucket, s3_key: &str) -> Result<bool> {
+    Ok(head_object_size(bucket, s3_key).await?.is_some())
+}
+
+/// Size of an object from a HEAD request, or `None` on a 404.
+///
+/// Uses the same retry policy as [`head_object_exists`].
+async fn head_object_size(bucket: &Bucket, s3_key: &str) -> Result<Option<i64>> {
+    let mut attempt = 1;
+    loop {
+        let mut size = 0;
+        let (status, error) = match bucket.head_object(s3_key).await {
+            Ok((head, status)) => {
+                size = head.content_length.unwrap_or(0);
+                (classify_head_status(status), format!("HTTP {status}"))
+            }
+            Err(s3::error::S3Error::HttpFailWithBody(status, body)) => (
+                classify_head_status(status),
+                format!("HTTP {status}: {body}"),
+            ),
+            Err(e) if is_transient_s3_error(&e) => (HeadStatus::Transient, e.to_string()),
+            Err(e) => return Err(anyhow::anyhow!("S3 head object failed: {e}")),
+        };
+
+        match status {
+            HeadStatus::Exists => return Ok(Some(size)),
+            HeadStatus::Missing => return Ok(None),
+            HeadStatus::Transient if attempt < HEAD_MAX_ATTEMPTS => {
+                let delay = HEAD_RETRY_BASE_DELAY * 2u32.pow(attempt - 1);
+                warn!(
+                    s3_key = %s3_key,
+                    attempt,
+                    error = %error,
+                    "Transient S3 head object failure, retrying in {delay:?}"
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            HeadStatus::Transient | HeadStatus::Failed => {
+                anyhow::bail!("S3 head object failed after {attempt} attempt(s): {error}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_bucket(endpoint: String) -> Bucket {
+        let credentials =
+            Credentials::new(Some("test-key"), Some("test-secret"), None, None, None).unwrap();
+        let region = Region::Custom {
+            region: "us-east-1".to_string(),
+            endpoint,
+        };
+        *Bucket::new("test-bucket", region, credentials)
+            .unwrap()
+            .with_path_style()
+    }
+
+    #[test]
+    fn test_precompress_round_trip() {
+        let html = "<html><body>".to_string() + &"archived text ".repeat(1000) + "</body></html>";
+        let compressed = precompress(html.as_bytes()).unwrap();
+        assert!(compressed.len() < html.len() / 10);
+
+        let decoded = decode_stored(compressed.clone(), Some("zstd")).unwrap();
+        assert_eq!(decoded, html.as_bytes());
+        assert_eq!(decode_stored(compressed.clone(), None).unwrap(), compressed);
+        assert!(decode_stored(compressed, Some("br")).is_err());
+    }
+
+    #[test]
+    fn test_classify_head_status() {
+        assert_eq!(classify_head_status(200), HeadStatus::Exists);
+        assert_eq!(classify_head_status(404), HeadStatus::Missing);
+        assert_eq!(classify_head_status(503), HeadStatus::Transient);
+        assert_eq!(classify_head_status(429), HeadStatus::Transient);
+        assert_eq!(classify_head_status(403), HeadStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_head_object_exists_404_is_missing() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/archives/1/raw.html"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let bucket = test_bucket(server.uri());
+        let exists = head_object_exists(&bucket, "archives/1/raw.html")
+            .await
+            .unwrap();
+        assert!(!exists);
+    }
+
+    #[tokio::test]
+    async fn test_head_object_exists_retries_server_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/videos/abc.mp4"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/videos/abc.mp4"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let bucket = test_bucket(server.uri());
+        let exists = head_object_exists(&bucket, "videos/abc.mp4").await.unwrap();
+        assert!(exists);
+    }
+
+    #[tokio::test]
+    async fn test_head_object_size_reads_content_length() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/videos/abc.mp4"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0u8; 4096]))
+            .mount(&server)
+            .await;
+
+        let bucket = test_bucket(server.uri());
+        let size = head_object_size(&bucket, "videos/abc.mp4").await.unwrap();
+        assert_eq!(size, Some(4096));
+        let missing = head_object_size(&bucket, "videos/other.mp4").await.unwrap();
+        assert_eq!(missing, None);
+    }
+
+    #[tokio::test]
+    async fn test_head_object_exists_forbidden_is_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(403))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let bucket = test_bucket(server.uri());
+        assert!(head_object_exists(&bucket, "videos/abc.mp4").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_head_object_exists_network_error_is_error() {
+        // Bind then drop a listener so the port refuses connections
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        let bucket = test_bucket(format!("http://127.0.0.1:{port}"));
+        let err = head_object_exists(&bucket, "videos/abc.mp4")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("after 3 attempt(s)"));
+    }
+}
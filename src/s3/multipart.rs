@@ -1,13 +1,82 @@
 use std::path::Path;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
+use aws_sdk_s3::config::retry::RetryConfig;
 use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::StorageClass;
 use tracing::{debug, info};
 
 use crate::config::Config;
 
 const CHUNK_SIZE: u64 = 5 * 1024 * 1024; // 5MB - minimum S3 multipart chunk size
 
+/// Delay before the first upload retry; doubles (with jitter) on each subsequent attempt.
+const UPLOAD_RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Upper bound on the delay between upload retries.
+const UPLOAD_RETRY_MAX_DELAY: Duration = Duration::from_secs(20);
+
+/// Retry policy for S3 requests.
+///
+/// The SDK's standard strategy only retries transient failures (5xx,
+/// throttling, timeouts, dropped connections), so permanent errors such as
+/// 403 or a missing bucket still fail on the first attempt. Request bodies
+/// are built from files or in-memory buffers, so they can be replayed.
+fn upload_retry_config(max_retries: u32) -> RetryConfig {
+    RetryConfig::standard()
+        .with_max_attempts(max_retries + 1)
+        .with_initial_backoff(UPLOAD_RETRY_BASE_DELAY)
+        .with_max_backoff(UPLOAD_RETRY_MAX_DELAY)
+}
+
+/// Artifact kinds that may be moved to a colder storage class.
+///
+/// Thumbnails, HTML snapshots and metadata are read on every page view and
+/// stay in standard storage.
+const COLD_KINDS: &[&str] = &["video", "audio", "music"];
+
+/// Infer the artifact kind of an object from its key and content type.
+///
+/// Used for the `kind` object tag so bucket lifecycle rules can filter by type.
+fn artifact_kind_for_key(key: &str, content_type: &str) -> &'static str {
+    let filename = key.rsplit('/').next().unwrap_or(key);
+
+    if key.contains("/thumb/") {
+        "thumb"
+    } else if filename == "complete.html" {
+        "complete_html"
+    } else if filename == "raw.html" {
+        "raw_html"
+    } else if filename == "view.html" {
+        "view_html"
+    } else if filename.ends_with(".mhtml") {
+        "mhtml"
+    } else if key.contains("/render/screenshot") {
+        "screenshot"
+    } else if filename == "transcript.txt" {
+        "transcript"
+    } else if key.contains("/subtitles/") {
+        "subtitles"
+    } else if filename == "comments.json" {
+        "comments"
+    } else if filename == "meta.json" || filename.ends_with(".info.json") {
+        "metadata"
+    } else if filename.starts_with("archive-backup-") {
+        "backup"
+    } else if content_type == "application/pdf" {
+        "pdf"
+    } else if content_type.starts_with("video/") {
+        "video"
+    } else if content_type.starts_with("audio/") {
+        "music"
+    } else if content_type.starts_with("image/") {
+        "image"
+    } else {
+        "other"
+    }
+}
+
 /// Streaming S3 uploader using AWS SDK with multipart support.
 ///
 /// This uploader eliminates memory constraints by streaming files
@@ -17,6 +86,10 @@ const CHUNK_SIZE: u64 = 5 * 1024 * 1024; // 5MB - minimum S3 multipart chunk siz
 pub struct StreamingUploader {
     client: aws_sdk_s3::Client,
     bucket: String,
+    /// Storage class applied to cold artifact kinds.
+    storage_class: Option<StorageClass>,
+    /// Whether to send `x-amz-tagging` with uploads.
+    tag_objects: bool,
 }
 
 impl StreamingUploader {
@@ -47,16 +120,42 @@ impl StreamingUploader {
 
         let s3_config = aws_sdk_s3::config::Builder::from(&aws_config)
             .force_path_style(config.s3_endpoint.is_some())
+            .retry_config(upload_retry_config(config.s3_upload_max_retries))
             .build();
 
         let client = aws_sdk_s3::Client::from_conf(s3_config);
 
+        // Storage classes and object tagging are AWS features; R2 and MinIO
+        // either ignore or reject them, so only use them without a custom endpoint.
+        let is_aws = config.s3_endpoint.is_none();
+
         Ok(Self {
             client,
             bucket: config.s3_bucket.clone(),
+            storage_class: config
+                .s3_storage_class
+                .as_deref()
+                .filter(|_| is_aws)
+                .map(StorageClass::from),
+            tag_objects: is_aws,
         })
     }
 
+    /// Storage class and tag set to send with an upload of `key`.
+    fn upload_options(
+        &self,
+        key: &str,
+        content_type: &str,
+    ) -> (Option<StorageClass>, Option<String>) {
+        let kind = artifact_kind_for_key(key, content_type);
+        let storage_class = self
+            .storage_class
+            .clone()
+            .filter(|_| COLD_KINDS.contains(&kind));
+        let tagging = self.tag_objects.then(|| format!("kind={kind}"));
+        (storage_class, tagging)
+    }
+
     /// Upload a file to S3 using streaming upload.
     ///
     /// Small files (<5MB) use simple PUT for efficiency.
@@ -107,6 +206,7 @@ impl StreamingUploader {
         let body = ByteStream::from_path(file_path)
             .await
             .context("Failed to create ByteStream from file")?;
+        let (storage_class, tagging) = self.upload_options(key, content_type);
 
         self.client
             .put_object()
@@ -114,6 +214,8 @@ impl StreamingUploader {
             .key(key)
             .body(body)
             .content_type(content_type)
+            .set_storage_class(storage_class)
+            .set_tagging(tagging)
             .send()
             .await
             .context("Failed to upload small file to S3")?;
@@ -141,12 +243,15 @@ impl StreamingUploader {
         );
 
         // 1. Initiate multipart upload
+        let (storage_class, tagging) = self.upload_options(key, content_type);
         let create_multipart = self
             .client
             .create_multipart_upload()
             .bucket(&self.bucket)
             .key(key)
             .content_type(content_type)
+            .set_storage_class(storage_class)
+            .set_tagging(tagging)
             .send()
             .await
             .context("Failed to create multipart upload")?;
@@ -316,9 +421,32 @@ impl StreamingUploader {
     ///
     /// Returns an error if the upload fails.
     pub async fn upload_bytes(&self, data: &[u8], s3_key: &str, content_type: &str) -> Result<()> {
-        debug!(key = %s3_key, content_type = %content_type, size = data.len(), "Uploading bytes to S3");
+        self.upload_encoded_bytes(data, s3_key, content_type, None)
+            .await
+    }
+
+    /// Upload bytes to S3, setting `Content-Encoding` when they are precompressed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the upload fails.
+    pub async fn upload_encoded_bytes(
+        &self,
+        data: &[u8],
+        s3_key: &str,
+        content_type: &str,
+        content_encoding: Option<&str>,
+    ) -> Result<()> {
+        debug!(
+            key = %s3_key,
+            content_type = %content_type,
+            content_encoding = ?content_encoding,
+            size = data.len(),
+            "Uploading bytes to S3"
+        );
 
         let body = ByteStream::from(data.to_vec());
+        let (storage_class, tagging) = self.upload_options(s3_key, content_type);
 
         self.client
             .put_object()
@@ -326,6 +454,9 @@ impl StreamingUploader {
             .key(s3_key)
             .body(body)
             .content_type(content_type)
+            .set_content_encoding(content_encoding.map(String::from))
+            .set_storage_class(storage_class)
+            .set_tagging(tagging)
             .send()
             .await
             .context("Failed to upload bytes to S3")?;
@@ -345,6 +476,149 @@ impl std::fmt::Debug for StreamingUploader {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Build an uploader that talks to `endpoint` but behaves like the AWS path.
+    fn aws_uploader(endpoint: &str, storage_class: Option<&str>) -> StreamingUploader {
+        let s3_config = aws_sdk_s3::config::Builder::new()
+            .behavior_version(aws_sdk_s3::config::BehaviorVersion::latest())
+            .region(aws_sdk_s3::config::Region::new("us-east-1"))
+            .credentials_provider(aws_sdk_s3::config::Credentials::new(
+                "test", "test", None, None, "test",
+            ))
+            .endpoint_url(endpoint)
+            .force_path_style(true)
+            .retry_config(upload_retry_config(3).with_initial_backoff(Duration::from_millis(10)))
+            .build();
+
+        StreamingUploader {
+            client: aws_sdk_s3::Client::from_conf(s3_config),
+            bucket: "test-bucket".to_string(),
+            storage_class: storage_class.map(StorageClass::from),
+            tag_objects: true,
+        }
+    }
+
+    #[test]
+    fn test_artifact_kind_for_key() {
+        assert_eq!(
+            artifact_kind_for_key("archives/1/media/video.mp4", "video/mp4"),
+            "video"
+        );
+        assert_eq!(
+            artifact_kind_for_key("archives/1/thumb/thumb.jpg", "image/jpeg"),
+            "thumb"
+        );
+        assert_eq!(
+            artifact_kind_for_key("archives/1/media/complete.html", "text/html"),
+            "complete_html"
+        );
+        assert_eq!(
+            artifact_kind_for_key("archives/1/subtitles/transcript.txt", "text/plain"),
+            "transcript"
+        );
+        assert_eq!(
+            artifact_kind_for_key("archives/1/media/song.mp3", "audio/mpeg"),
+            "music"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_put_sets_storage_class_for_video_on_aws() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test-bucket/archives/1/media/video.mp4"))
+            .and(header("x-amz-storage-class", "STANDARD_IA"))
+            .and(header("x-amz-tagging", "kind=video"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let uploader = aws_uploader(&server.uri(), Some("STANDARD_IA"));
+        uploader
+            .upload_bytes(b"fake video", "archives/1/media/video.mp4", "video/mp4")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_put_keeps_thumbnails_in_standard_storage() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test-bucket/archives/1/thumb/thumb.jpg"))
+            .and(header("x-amz-tagging", "kind=thumb"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let uploader = aws_uploader(&server.uri(), Some("STANDARD_IA"));
+        uploader
+            .upload_bytes(b"fake image", "archives/1/thumb/thumb.jpg", "image/jpeg")
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        assert!(requests[0].headers.get("x-amz-storage-class").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_put_retries_server_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test-bucket/archives/1/media/page.pdf"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test-bucket/archives/1/media/page.pdf"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let uploader = aws_uploader(&server.uri(), None);
+        uploader
+            .upload_bytes(b"fake pdf", "archives/1/media/page.pdf", "application/pdf")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_put_does_not_retry_forbidden() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .respond_with(ResponseTemplate::new(403))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let uploader = aws_uploader(&server.uri(), None);
+        let result = uploader
+            .upload_bytes(b"fake pdf", "archives/1/media/page.pdf", "application/pdf")
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_put_gives_up_after_max_retries() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(4)
+            .mount(&server)
+            .await;
+
+        let uploader = aws_uploader(&server.uri(), None);
+        let result = uploader
+            .upload_bytes(b"fake pdf", "archives/1/media/page.pdf", "application/pdf")
+            .await;
+        assert!(result.is_err());
+    }
 
     #[test]
     fn test_chunk_size_is_5mb() {
@@ -3,14 +3,27 @@
 //! This module provides ACME-based automatic TLS certificate acquisition and renewal
 //! using the TLS-ALPN-01 challenge method. Certificates are cached to disk to persist
 //! across restarts and avoid rate limits.
+//!
+//! Alternatively, a certificate chain and key can be loaded from PEM files (e.g. an
+//! existing wildcard certificate). The files are polled for changes and reloaded
+//! without a restart when they are renewed.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use axum_server::tls_rustls::RustlsConfig;
+use rustls::ServerConfig;
 use rustls_acme::caches::DirCache;
 use rustls_acme::AcmeConfig;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::config::Config;
 
+/// How often certificate files are checked for changes.
+const CERT_RELOAD_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Create an ACME configuration for automatic TLS certificate management.
 ///
 /// This configures the TLS-ALPN-01 challenge method, which handles certificate
@@ -52,6 +65,87 @@ pub fn create_acme_config(config: &Config) -> Result<AcmeConfig<std::io::Error>>
     Ok(acme_config)
 }
 
+/// Build a server configuration from PEM-encoded certificate chain and key files.
+///
+/// # Errors
+///
+/// Returns an error if either file cannot be read, contains no certificate or
+/// key, or if the key does not match the certificate.
+pub fn load_pem_files(cert_path: &Path, key_path: &Path) -> Result<ServerConfig> {
+    let cert = std::fs::read(cert_path).with_context(|| {
+        format!(
+            "Failed to read TLS certificate file: {}",
+            cert_path.display()
+        )
+    })?;
+    let key = std::fs::read(key_path)
+        .with_context(|| format!("Failed to read TLS key file: {}", key_path.display()))?;
+    server_config_from_pem(&cert, &key)
+}
+
+/// Build a server configuration from a PEM certificate chain and private key.
+///
+/// # Errors
+///
+/// Returns an error if the PEM data is malformed, contains no certificate or
+/// key, or if the key does not match the leaf certificate.
+pub fn server_config_from_pem(cert_pem: &[u8], key_pem: &[u8]) -> Result<ServerConfig> {
+    let certs = rustls_pemfile::certs(&mut &cert_pem[..])
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to parse TLS certificate PEM")?;
+    if certs.is_empty() {
+        bail!("No certificates found in TLS certificate PEM");
+    }
+    let key = rustls_pemfile::private_key(&mut &key_pem[..])
+        .context("Failed to parse TLS key PEM")?
+        .context("No private key found in TLS key PEM")?;
+
+    let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+    let mut config = ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .context("Failed to select TLS protocol versions")?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .context("TLS key does not match certificate")?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(config)
+}
+
+/// Poll certificate files and reload them into `rustls_config` when they change.
+///
+/// A renewal that fails to load is logged and the previous certificate stays
+/// in use, so a half-written file does not take the server down.
+pub async fn watch_pem_files(rustls_config: RustlsConfig, cert_path: PathBuf, key_path: PathBuf) {
+    let mut last_modified = modified_times(&cert_path, &key_path).await;
+    let mut interval = tokio::time::interval(CERT_RELOAD_INTERVAL);
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+        let modified = modified_times(&cert_path, &key_path).await;
+        if modified.is_none() || modified == last_modified {
+            continue;
+        }
+
+        match load_pem_files(&cert_path, &key_path) {
+            Ok(config) => {
+                rustls_config.reload_from_config(Arc::new(config));
+                last_modified = modified;
+                info!(cert = %cert_path.display(), "Reloaded TLS certificate");
+            }
+            Err(e) => warn!("Failed to reload TLS certificate, keeping previous: {e:#}"),
+        }
+    }
+}
+
+/// Modification times of the certificate and key files, if both exist.
+async fn modified_times(cert_path: &Path, key_path: &Path) -> Option<(SystemTime, SystemTime)> {
+    let cert = tokio::fs::metadata(cert_path).await.ok()?.modified().ok()?;
+    let key = tokio::fs::metadata(key_path).await.ok()?.modified().ok()?;
+    Some((cert, key))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -85,4 +179,47 @@ mod tests {
         // Clean up
         let _ = std::fs::remove_dir_all("./test_acme_cache");
     }
+
+    fn generate_pem(domain: &str) -> (String, String) {
+        let cert = rcgen::generate_simple_self_signed(vec![domain.to_string()]).unwrap();
+        (cert.cert.pem(), cert.key_pair.serialize_pem())
+    }
+
+    #[test]
+    fn test_server_config_from_pem() {
+        let (cert, key) = generate_pem("archive.example.com");
+        let config = server_config_from_pem(cert.as_bytes(), key.as_bytes()).unwrap();
+        assert_eq!(
+            config.alpn_protocols,
+            vec![b"h2".to_vec(), b"http/1.1".to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_server_config_from_pem_rejects_mismatched_key() {
+        let (cert, _) = generate_pem("archive.example.com");
+        let (_, other_key) = generate_pem("other.example.com");
+        let err = server_config_from_pem(cert.as_bytes(), other_key.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("does not match"));
+    }
+
+    #[test]
+    fn test_server_config_from_pem_requires_cert_and_key() {
+        let (cert, key) = generate_pem("archive.example.com");
+        assert!(server_config_from_pem(b"", key.as_bytes()).is_err());
+        assert!(server_config_from_pem(cert.as_bytes(), b"").is_err());
+    }
+
+    #[test]
+    fn test_load_pem_files() {
+        let (cert, key) = generate_pem("archive.example.com");
+        let dir = tempfile::tempdir().unwrap();
+        let cert_path = dir.path().join("cert.pem");
+        let key_path = dir.path().join("key.pem");
+        std::fs::write(&cert_path, cert).unwrap();
+        std::fs::write(&key_path, key).unwrap();
+
+        assert!(load_pem_files(&cert_path, &key_path).is_ok());
+        assert!(load_pem_files(&dir.path().join("missing.pem"), &key_path).is_err());
+    }
 }
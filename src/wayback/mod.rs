@@ -2,12 +2,14 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{Context, Result};
+use chrono::Utc;
 use reqwest::Client;
 use tokio::sync::Semaphore;
 use tokio::time::sleep;
 use tracing::{debug, info, warn};
 
 use crate::constants::ARCHIVAL_USER_AGENT;
+use crate::retry_after::parse_retry_after;
 
 /// Rate-limited Wayback Machine client.
 pub struct WaybackClient {
@@ -88,6 +90,7 @@ impl WaybackClient {
             .context("Failed to submit to Wayback Machine")?;
 
         let status = response.status();
+        let retry_after = parse_retry_after(response.headers(), Utc::now());
 
         if status.is_success() || status.as_u16() == 302 {
             // Check for the Content-Location header which contains the snapshot URL
@@ -114,9 +117,10 @@ impl WaybackClient {
             info!(url = %url, "Wayback submission accepted (no specific snapshot URL)");
             Ok(Some(generic_url))
         } else if status.as_u16() == 429 {
-            warn!(url = %url, "Wayback Machine rate limited, will retry later");
-            // Wait extra time on rate limit
-            sleep(self.permit_interval * 2).await;
+            // Wait as long as the server asks, or extra time if it doesn't say
+            let wait = retry_after.unwrap_or(self.permit_interval * 2);
+            warn!(url = %url, wait_secs = wait.as_secs(), "Wayback Machine rate limited, will retry later");
+            sleep(wait).await;
             Ok(None)
         } else if status.as_u16() == 523 || status.as_u16() == 520 {
             // Cloudflare errors - the target site may be blocking archival
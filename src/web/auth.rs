@@ -1,5 +1,5 @@
 use axum::{
-    extract::State,
+    extract::{Multipart, State},
     http::{header, StatusCode},
     response::{Html, IntoResponse, Redirect, Response},
     Form,
@@ -14,7 +14,8 @@ use crate::auth::{
     SessionDuration,
 };
 use crate::db as queries;
-use crate::web::{pages, stream_command, AppState};
+use crate::db::{AuditAction, AuditActor};
+use crate::web::{bulk_import, pages, stream_command, AppState};
 
 /// Login form data.
 #[derive(Debug, Deserialize)]
@@ -44,15 +45,17 @@ pub async fn login_post(
     headers: axum::http::HeaderMap,
     Form(form): Form<LoginForm>,
 ) -> Response {
-    let direct_ip = addr.ip().to_string();
+    // Registration is rate limited on this address
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
     let forwarded_for = headers
         .get("x-forwarded-for")
         .and_then(|h| h.to_str().ok())
         .map(String::from);
 
     match form.action.as_str() {
-        "register" => handle_registration(state, direct_ip, forwarded_for).await,
-        "login" | "" => handle_login(state, direct_ip, forwarded_for, form).await,
+        "register" => handle_registration(state, client_ip, forwarded_for).await,
+        "login" | "" => handle_login(state, client_ip, forwarded_for, form).await,
         _ => (StatusCode::BAD_REQUEST, "Invalid action").into_response(),
     }
 }
@@ -70,7 +73,7 @@ async fn handle_registration(
     )
     .bind(&ip)
     .bind(five_minutes_ago.to_rfc3339())
-    .fetch_one(state.db.pool())
+    .fetch_one(state.db.read_pool())
     .await;
 
     if let Ok(count) = count_result {
@@ -84,7 +87,7 @@ async fn handle_registration(
     }
 
     // Generate random credentials with unique username
-    let username = match generate_unique_username(state.db.pool()).await {
+    let username = match generate_unique_username(state.db.read_pool()).await {
         Ok(u) => u,
         Err(e) => {
             tracing::error!("Failed to generate unique username: {e}");
@@ -103,7 +106,9 @@ async fn handle_registration(
     };
 
     // Check if this is the first user (becomes admin)
-    let user_count = queries::count_users(state.db.pool()).await.unwrap_or(0);
+    let user_count = queries::count_users(state.db.read_pool())
+        .await
+        .unwrap_or(0);
     let is_first_user = user_count == 0;
 
     // Create user
@@ -122,16 +127,10 @@ async fn handle_registration(
         };
 
     // Log registration event
-    let _ = queries::create_audit_event(
+    let _ = queries::record_audit(
         state.db.pool(),
-        Some(user_id),
-        "registration",
-        None,
-        None,
-        None,
-        Some(&ip),
-        forwarded_for.as_deref(),
-        None,
+        &AuditActor::from_request(Some(user_id), &ip, forwarded_for.as_deref()),
+        &AuditAction::Registration,
     )
     .await;
 
@@ -163,7 +162,8 @@ async fn handle_login(
     };
 
     // Get user by username or display_name (users can sign in with either)
-    let user = match queries::get_user_by_username_or_display_name(state.db.pool(), &username).await
+    let user = match queries::get_user_by_username_or_display_name(state.db.read_pool(), &username)
+        .await
     {
         Ok(Some(u)) => u,
         Ok(None) => {
@@ -227,16 +227,10 @@ async fn handle_login(
         }
 
         // Log failed login
-        let _ = queries::create_audit_event(
+        let _ = queries::record_audit(
             state.db.pool(),
-            Some(user.id),
-            "login_failed",
-            None,
-            None,
-            None,
-            Some(&ip),
-            forwarded_for.as_deref(),
-            None,
+            &AuditActor::from_request(Some(user.id), &ip, forwarded_for.as_deref()),
+            &AuditAction::LoginFailed,
         )
         .await;
 
@@ -249,7 +243,7 @@ async fn handle_login(
 
     // Enforce max concurrent sessions (10)
     const MAX_SESSIONS: i64 = 10;
-    if let Ok(session_count) = queries::count_user_sessions(state.db.pool(), user.id).await {
+    if let Ok(session_count) = queries::count_user_sessions(state.db.read_pool(), user.id).await {
         if session_count >= MAX_SESSIONS {
             // Delete oldest sessions to make room (keep MAX_SESSIONS - 1 so new one fits)
             if let Err(e) =
@@ -287,16 +281,10 @@ async fn handle_login(
     }
 
     // Log successful login
-    let _ = queries::create_audit_event(
+    let _ = queries::record_audit(
         state.db.pool(),
-        Some(user.id),
-        "login_success",
-        None,
-        None,
-        None,
-        Some(&ip),
-        forwarded_for.as_deref(),
-        None,
+        &AuditActor::from_request(Some(user.id), &ip, forwarded_for.as_deref()),
+        &AuditAction::LoginSuccess,
     )
     .await;
 
@@ -316,7 +304,8 @@ pub async fn logout(
     headers: axum::http::HeaderMap,
     RequireUser(user): RequireUser,
 ) -> Response {
-    let ip = addr.ip().to_string();
+    let ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
     let forwarded_for = headers.get("x-forwarded-for").and_then(|h| h.to_str().ok());
 
     // Get session token from cookie
@@ -325,16 +314,10 @@ pub async fn logout(
     let _ = queries::delete_user_sessions(state.db.pool(), user.id).await;
 
     // Log logout event
-    let _ = queries::create_audit_event(
+    let _ = queries::record_audit(
         state.db.pool(),
-        Some(user.id),
-        "logout",
-        None,
-        None,
-        None,
-        Some(&ip),
-        forwarded_for,
-        None,
+        &AuditActor::from_request(Some(user.id), &ip, forwarded_for),
+        &AuditAction::Logout,
     )
     .await;
 
@@ -350,7 +333,7 @@ pub async fn profile_page(
     RequireUser(user): RequireUser,
 ) -> Response {
     // Check if user has a forum account link
-    let has_forum_link = match queries::user_has_forum_link(state.db.pool(), user.id).await {
+    let has_forum_link = match queries::user_has_forum_link(state.db.read_pool(), user.id).await {
         Ok(linked) => linked,
         Err(e) => {
             tracing::error!("Failed to check forum link status: {e}");
@@ -382,7 +365,7 @@ pub async fn profile_post(
     let mut password_changed = false;
 
     // Check if user has a forum account link (prevents display_name changes)
-    let has_forum_link = match queries::user_has_forum_link(state.db.pool(), user.id).await {
+    let has_forum_link = match queries::user_has_forum_link(state.db.read_pool(), user.id).await {
         Ok(linked) => linked,
         Err(e) => {
             tracing::error!("Failed to check forum link status: {e}");
@@ -428,7 +411,7 @@ pub async fn profile_post(
                 error = Some(e.to_string());
             } else {
                 // Check uniqueness (excluding current user)
-                match queries::display_name_exists(state.db.pool(), dn, Some(user.id)).await {
+                match queries::display_name_exists(state.db.read_pool(), dn, Some(user.id)).await {
                     Ok(true) => {
                         error = Some("Display name is already taken".to_string());
                     }
@@ -515,7 +498,7 @@ pub async fn profile_post(
     }
 
     // Reload user and show profile page
-    let updated_user = queries::get_user_by_id(state.db.pool(), user.id)
+    let updated_user = queries::get_user_by_id(state.db.read_pool(), user.id)
         .await
         .ok()
         .flatten()
@@ -525,20 +508,14 @@ pub async fn profile_post(
     if error.is_none() && !has_forum_link {
         let new_display_name = updated_user.display_name.as_deref();
         if old_display_name.as_deref() != new_display_name {
-            let metadata = serde_json::json!({
-                "old_value": old_display_name,
-                "new_value": new_display_name,
-            });
-            if let Err(e) = queries::create_audit_event(
+            if let Err(e) = queries::record_audit(
                 state.db.pool(),
-                Some(updated_user.id),
-                "display_name_changed",
-                Some("user"),
-                Some(updated_user.id),
-                Some(&metadata.to_string()),
-                None,
-                None,
-                None,
+                &AuditActor::new(Some(updated_user.id)),
+                &AuditAction::DisplayNameChanged {
+                    user_id: updated_user.id,
+                    old_value: old_display_name.clone(),
+                    new_value: new_display_name.map(str::to_string),
+                },
             )
             .await
             {
@@ -561,6 +538,28 @@ pub struct AdminPanelQuery {
     tab: Option<String>,
     /// Success/error message
     message: Option<String>,
+    /// Resume point for the missing-artifacts sweep
+    backfill_after: Option<i64>,
+    /// Resume point for the artifact size recompute
+    sizes_after: Option<i64>,
+    /// Users tab page (0-indexed)
+    users_page: Option<usize>,
+    /// Audit log tab page (0-indexed)
+    audit_page: Option<usize>,
+    /// Rows per page for both lists; one of [`pages::ADMIN_PAGE_SIZES`]
+    per_page: Option<i64>,
+}
+
+/// Clamp a requested page to the available pages.
+///
+/// Returns the page, the page count (at least 1) and the row offset.
+fn page_window(total: i64, per_page: i64, page: usize) -> (usize, usize, i64) {
+    let total_pages = usize::try_from((total + per_page - 1) / per_page)
+        .unwrap_or(0)
+        .max(1);
+    let page = page.min(total_pages - 1);
+    let offset = i64::try_from(page).unwrap_or(0) * per_page;
+    (page, total_pages, offset)
 }
 
 /// GET /admin - Admin panel.
@@ -569,26 +568,49 @@ pub async fn admin_panel(
     axum::extract::Query(query): axum::extract::Query<AdminPanelQuery>,
     RequireAdmin(admin): RequireAdmin,
 ) -> Response {
-    // Get all users
-    let users = match queries::get_all_users(state.db.pool(), 100, 0).await {
-        Ok(u) => u,
+    let per_page = query
+        .per_page
+        .filter(|n| pages::ADMIN_PAGE_SIZES.contains(n))
+        .unwrap_or(pages::ADMIN_DEFAULT_PAGE_SIZE);
+
+    // Get one page of users
+    let users_total = match queries::count_users(state.db.read_pool()).await {
+        Ok(n) => n,
         Err(e) => {
-            tracing::error!("Failed to fetch users: {e}");
+            tracing::error!("Failed to count users: {e}");
             return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to load users").into_response();
         }
     };
-
-    // Get recent audit events
-    let audit_events = match queries::get_audit_events(state.db.pool(), 50, 0).await {
-        Ok(e) => e,
+    let (users_page, users_total_pages, users_offset) =
+        page_window(users_total, per_page, query.users_page.unwrap_or(0));
+    let users = match queries::get_all_users(state.db.read_pool(), per_page, users_offset).await {
+        Ok(u) => u,
         Err(e) => {
-            tracing::error!("Failed to fetch audit events: {e}");
-            vec![]
+            tracing::error!("Failed to fetch users: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to load users").into_response();
         }
     };
 
+    // Get one page of audit events
+    let audit_total = queries::count_audit_events(state.db.read_pool())
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("Failed to count audit events: {e}");
+            0
+        });
+    let (audit_page, audit_total_pages, audit_offset) =
+        page_window(audit_total, per_page, query.audit_page.unwrap_or(0));
+    let audit_events =
+        match queries::get_audit_events(state.db.read_pool(), per_page, audit_offset).await {
+            Ok(e) => e,
+            Err(e) => {
+                tracing::error!("Failed to fetch audit events: {e}");
+                vec![]
+            }
+        };
+
     // Get all forum links
-    let forum_links = match queries::get_all_forum_links(state.db.pool()).await {
+    let forum_links = match queries::get_all_forum_links(state.db.read_pool()).await {
         Ok(l) => l,
         Err(e) => {
             tracing::error!("Failed to fetch forum links: {e}");
@@ -597,7 +619,7 @@ pub async fn admin_panel(
     };
 
     // Get all subtitle languages
-    let subtitle_languages = match queries::get_all_subtitle_languages(state.db.pool()).await {
+    let subtitle_languages = match queries::get_all_subtitle_languages(state.db.read_pool()).await {
         Ok(sl) => sl,
         Err(e) => {
             tracing::error!("Failed to fetch subtitle languages: {e}");
@@ -605,14 +627,55 @@ pub async fn admin_panel(
         }
     };
 
+    let missing_artifacts_count = queries::count_archives_missing_artifacts(state.db.read_pool())
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("Failed to count archives missing artifacts: {e}");
+            0
+        });
+
+    let og_reextraction_count =
+        queries::count_archives_needing_og_reextraction(state.db.read_pool())
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!("Failed to count archives needing OG re-extraction: {e}");
+                0
+            });
+
+    // The users page only holds a slice, so name audit and forum link users separately
+    let mut named_user_ids: Vec<i64> = audit_events
+        .iter()
+        .filter_map(|e| e.user_id)
+        .chain(forum_links.iter().map(|l| l.user_id))
+        .collect();
+    named_user_ids.sort_unstable();
+    named_user_ids.dedup();
+    let named_users = queries::get_users_by_ids(state.db.read_pool(), &named_user_ids)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("Failed to fetch users for audit and forum links: {e}");
+            vec![]
+        });
+
     let params = pages::AdminPanelParams {
         users: &users,
+        users_total,
+        users_page,
+        users_total_pages,
+        named_users: &named_users,
         audit_events: &audit_events,
+        audit_page,
+        audit_total_pages,
+        per_page,
         forum_links: &forum_links,
         subtitle_languages: &subtitle_languages,
         current_user: &admin,
         active_tab: query.tab.as_deref(),
         message: query.message.as_deref(),
+        missing_artifacts_count,
+        missing_artifacts_cursor: query.backfill_after,
+        recompute_sizes_cursor: query.sizes_after,
+        og_reextraction_count,
     };
 
     Html(pages::render_admin_panel(&params).into_string()).into_response()
@@ -631,7 +694,8 @@ pub async fn admin_approve_user(
     RequireAdmin(admin): RequireAdmin,
     Form(form): Form<UserIdForm>,
 ) -> Response {
-    let ip = addr.ip().to_string();
+    let ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
     let forwarded_for = headers.get("x-forwarded-for").and_then(|h| h.to_str().ok());
 
     if let Err(e) = queries::update_user_approval(state.db.pool(), form.user_id, true).await {
@@ -640,16 +704,12 @@ pub async fn admin_approve_user(
     }
 
     // Log audit event
-    let _ = queries::create_audit_event(
+    let _ = queries::record_audit(
         state.db.pool(),
-        Some(admin.id),
-        "user_approved",
-        Some("user"),
-        Some(form.user_id),
-        None,
-        Some(&ip),
-        forwarded_for,
-        None,
+        &AuditActor::from_request(Some(admin.id), &ip, forwarded_for),
+        &AuditAction::UserApproved {
+            user_id: form.user_id,
+        },
     )
     .await;
 
@@ -664,7 +724,8 @@ pub async fn admin_revoke_user(
     RequireAdmin(admin): RequireAdmin,
     Form(form): Form<UserIdForm>,
 ) -> Response {
-    let ip = addr.ip().to_string();
+    let ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
     let forwarded_for = headers.get("x-forwarded-for").and_then(|h| h.to_str().ok());
 
     if let Err(e) = queries::update_user_approval(state.db.pool(), form.user_id, false).await {
@@ -677,16 +738,12 @@ pub async fn admin_revoke_user(
     }
 
     // Log audit event
-    let _ = queries::create_audit_event(
+    let _ = queries::record_audit(
         state.db.pool(),
-        Some(admin.id),
-        "user_approval_revoked",
-        Some("user"),
-        Some(form.user_id),
-        None,
-        Some(&ip),
-        forwarded_for,
-        None,
+        &AuditActor::from_request(Some(admin.id), &ip, forwarded_for),
+        &AuditAction::UserApprovalRevoked {
+            user_id: form.user_id,
+        },
     )
     .await;
 
@@ -701,7 +758,8 @@ pub async fn admin_promote_user(
     RequireAdmin(admin): RequireAdmin,
     Form(form): Form<UserIdForm>,
 ) -> Response {
-    let ip = addr.ip().to_string();
+    let ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
     let forwarded_for = headers.get("x-forwarded-for").and_then(|h| h.to_str().ok());
 
     if let Err(e) = queries::update_user_admin(state.db.pool(), form.user_id, true).await {
@@ -710,16 +768,12 @@ pub async fn admin_promote_user(
     }
 
     // Log audit event
-    let _ = queries::create_audit_event(
+    let _ = queries::record_audit(
         state.db.pool(),
-        Some(admin.id),
-        "user_promoted_admin",
-        Some("user"),
-        Some(form.user_id),
-        None,
-        Some(&ip),
-        forwarded_for,
-        None,
+        &AuditActor::from_request(Some(admin.id), &ip, forwarded_for),
+        &AuditAction::UserPromotedAdmin {
+            user_id: form.user_id,
+        },
     )
     .await;
 
@@ -734,7 +788,8 @@ pub async fn admin_demote_user(
     RequireAdmin(admin): RequireAdmin,
     Form(form): Form<UserIdForm>,
 ) -> Response {
-    let ip = addr.ip().to_string();
+    let ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
     let forwarded_for = headers.get("x-forwarded-for").and_then(|h| h.to_str().ok());
 
     // Prevent self-demotion
@@ -748,16 +803,12 @@ pub async fn admin_demote_user(
     }
 
     // Log audit event
-    let _ = queries::create_audit_event(
+    let _ = queries::record_audit(
         state.db.pool(),
-        Some(admin.id),
-        "user_demoted_admin",
-        Some("user"),
-        Some(form.user_id),
-        None,
-        Some(&ip),
-        forwarded_for,
-        None,
+        &AuditActor::from_request(Some(admin.id), &ip, forwarded_for),
+        &AuditAction::UserDemotedAdmin {
+            user_id: form.user_id,
+        },
     )
     .await;
 
@@ -772,7 +823,8 @@ pub async fn admin_deactivate_user(
     RequireAdmin(admin): RequireAdmin,
     Form(form): Form<UserIdForm>,
 ) -> Response {
-    let ip = addr.ip().to_string();
+    let ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
     let forwarded_for = headers.get("x-forwarded-for").and_then(|h| h.to_str().ok());
 
     // Prevent self-deactivation
@@ -793,16 +845,12 @@ pub async fn admin_deactivate_user(
     let _ = queries::delete_user_sessions(state.db.pool(), form.user_id).await;
 
     // Log audit event
-    let _ = queries::create_audit_event(
+    let _ = queries::record_audit(
         state.db.pool(),
-        Some(admin.id),
-        "user_deactivated",
-        Some("user"),
-        Some(form.user_id),
-        None,
-        Some(&ip),
-        forwarded_for,
-        None,
+        &AuditActor::from_request(Some(admin.id), &ip, forwarded_for),
+        &AuditAction::UserDeactivated {
+            user_id: form.user_id,
+        },
     )
     .await;
 
@@ -817,7 +865,8 @@ pub async fn admin_reactivate_user(
     RequireAdmin(admin): RequireAdmin,
     Form(form): Form<UserIdForm>,
 ) -> Response {
-    let ip = addr.ip().to_string();
+    let ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
     let forwarded_for = headers.get("x-forwarded-for").and_then(|h| h.to_str().ok());
 
     if let Err(e) = queries::update_user_active(state.db.pool(), form.user_id, true).await {
@@ -830,16 +879,12 @@ pub async fn admin_reactivate_user(
     }
 
     // Log audit event
-    let _ = queries::create_audit_event(
+    let _ = queries::record_audit(
         state.db.pool(),
-        Some(admin.id),
-        "user_reactivated",
-        Some("user"),
-        Some(form.user_id),
-        None,
-        Some(&ip),
-        forwarded_for,
-        None,
+        &AuditActor::from_request(Some(admin.id), &ip, forwarded_for),
+        &AuditAction::UserReactivated {
+            user_id: form.user_id,
+        },
     )
     .await;
 
@@ -854,11 +899,12 @@ pub async fn admin_reset_password(
     RequireAdmin(admin): RequireAdmin,
     Form(form): Form<UserIdForm>,
 ) -> Response {
-    let ip = addr.ip().to_string();
+    let ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
     let forwarded_for = headers.get("x-forwarded-for").and_then(|h| h.to_str().ok());
 
     // Get target user info
-    let target_user = match queries::get_user_by_id(state.db.pool(), form.user_id).await {
+    let target_user = match queries::get_user_by_id(state.db.read_pool(), form.user_id).await {
         Ok(Some(u)) => u,
         Ok(None) => {
             return (StatusCode::NOT_FOUND, "User not found").into_response();
@@ -903,16 +949,12 @@ pub async fn admin_reset_password(
     let _ = queries::delete_user_sessions(state.db.pool(), form.user_id).await;
 
     // Log audit event
-    let _ = queries::create_audit_event(
+    let _ = queries::record_audit(
         state.db.pool(),
-        Some(admin.id),
-        "admin_password_reset",
-        Some("user"),
-        Some(form.user_id),
-        None,
-        Some(&ip),
-        forwarded_for,
-        None,
+        &AuditActor::from_request(Some(admin.id), &ip, forwarded_for),
+        &AuditAction::AdminPasswordReset {
+            user_id: form.user_id,
+        },
     )
     .await;
 
@@ -930,6 +972,95 @@ pub async fn admin_reset_password(
     .into_response()
 }
 
+/// Audit events shown per page on the audit log page.
+const AUDIT_EVENTS_PER_PAGE: i64 = 50;
+
+/// Query params for the audit log page.
+#[derive(Debug, Deserialize)]
+pub struct AdminAuditQuery {
+    /// Filter by event type (empty means all)
+    event_type: Option<String>,
+    /// Filter by user ID (empty means all; kept as a string so the blank
+    /// option of the filter form doesn't fail to parse)
+    user_id: Option<String>,
+    /// Page number (0-indexed)
+    page: Option<usize>,
+}
+
+/// GET /admin/audit - Paginated, filterable audit log.
+pub async fn admin_audit_log(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<AdminAuditQuery>,
+    RequireAdmin(admin): RequireAdmin,
+) -> Response {
+    let pool = state.db.read_pool();
+    let event_type = query.event_type.as_deref().filter(|t| !t.is_empty());
+    let user_id = query
+        .user_id
+        .as_deref()
+        .and_then(|id| id.trim().parse::<i64>().ok());
+    let page = query.page.unwrap_or(0);
+
+    let total = match queries::count_audit_events_filtered(pool, event_type, user_id).await {
+        Ok(n) => n,
+        Err(e) => {
+            tracing::error!("Failed to count audit events: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to load audit log",
+            )
+                .into_response();
+        }
+    };
+    let (page, total_pages, offset) = page_window(total, AUDIT_EVENTS_PER_PAGE, page);
+
+    let events = match queries::get_audit_events_filtered(
+        pool,
+        event_type,
+        user_id,
+        AUDIT_EVENTS_PER_PAGE,
+        offset,
+    )
+    .await
+    {
+        Ok(e) => e,
+        Err(e) => {
+            tracing::error!("Failed to fetch audit events: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to load audit log",
+            )
+                .into_response();
+        }
+    };
+
+    let users = queries::get_all_users(pool, 1000, 0)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("Failed to fetch users: {e}");
+            vec![]
+        });
+    let event_types = queries::get_audit_event_types(pool)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("Failed to fetch audit event types: {e}");
+            vec![]
+        });
+
+    let params = pages::AdminAuditPageParams {
+        events: &events,
+        users: &users,
+        event_types: &event_types,
+        event_type_filter: event_type,
+        user_id_filter: user_id,
+        total,
+        page,
+        total_pages,
+        current_user: &admin,
+    };
+    Html(pages::render_admin_audit_page(&params).into_string()).into_response()
+}
+
 // ============================================================================
 // Excluded Domains Admin Functions
 // ============================================================================
@@ -939,11 +1070,34 @@ pub async fn admin_excluded_domains_page(
     State(state): State<AppState>,
     RequireAdmin(admin): RequireAdmin,
 ) -> Response {
-    match queries::get_all_excluded_domains(state.db.pool()).await {
-        Ok(domains) => {
-            Html(pages::render_admin_excluded_domains_page(&domains, None, &admin).into_string())
-                .into_response()
-        }
+    let pool = state.db.read_pool();
+    let result = match queries::get_all_excluded_domains(pool).await {
+        Ok(domains) => match queries::get_domain_quote_policies(pool).await {
+            Ok(policies) => match queries::get_priority_domains(pool).await {
+                Ok(priority) => queries::get_versioned_domains(pool)
+                    .await
+                    .map(|versioned| (domains, policies, priority, versioned)),
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e),
+        },
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok((domains, quote_policies, priority_domains, versioned_domains)) => Html(
+            pages::render_admin_excluded_domains_page(
+                &domains,
+                &quote_policies,
+                &priority_domains,
+                &versioned_domains,
+                state.config.archive_quote_only_links,
+                None,
+                &admin,
+            )
+            .into_string(),
+        )
+        .into_response(),
         Err(e) => {
             tracing::error!("Failed to fetch excluded domains: {e}");
             (
@@ -969,7 +1123,8 @@ pub async fn admin_add_excluded_domain(
     RequireAdmin(admin): RequireAdmin,
     Form(form): Form<ExcludedDomainForm>,
 ) -> Response {
-    let direct_ip = addr.ip().to_string();
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
     let forwarded_for = headers
         .get("x-forwarded-for")
         .and_then(|h| h.to_str().ok())
@@ -990,16 +1145,13 @@ pub async fn admin_add_excluded_domain(
             tracing::info!(admin_id = admin.id, domain = %domain, "Admin added excluded domain");
 
             // Log audit event
-            let _ = queries::create_audit_event(
+            let _ = queries::record_audit(
                 state.db.pool(),
-                Some(admin.id),
-                "admin_add_excluded_domain",
-                Some("excluded_domain"),
-                None,
-                Some(&domain),
-                Some(&direct_ip),
-                forwarded_for.as_deref(),
-                None,
+                &AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for.as_deref()),
+                &AuditAction::AdminAddExcludedDomain {
+                    domain: domain.clone(),
+                    reason: reason.clone(),
+                },
             )
             .await;
 
@@ -1030,7 +1182,8 @@ pub async fn admin_toggle_excluded_domain(
     RequireAdmin(admin): RequireAdmin,
     Form(form): Form<ExcludedDomainActionForm>,
 ) -> Response {
-    let direct_ip = addr.ip().to_string();
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
     let forwarded_for = headers
         .get("x-forwarded-for")
         .and_then(|h| h.to_str().ok())
@@ -1039,7 +1192,7 @@ pub async fn admin_toggle_excluded_domain(
     let domain = form.domain.trim().to_lowercase();
 
     // First get current status
-    match queries::get_all_excluded_domains(state.db.pool()).await {
+    match queries::get_all_excluded_domains(state.db.read_pool()).await {
         Ok(domains) => {
             if let Some(d) = domains
                 .iter()
@@ -1058,16 +1211,17 @@ pub async fn admin_toggle_excluded_domain(
                         );
 
                         // Log audit event
-                        let _ = queries::create_audit_event(
+                        let _ = queries::record_audit(
                             state.db.pool(),
-                            Some(admin.id),
-                            "admin_toggle_excluded_domain",
-                            Some("excluded_domain"),
-                            None,
-                            Some(&format!("{domain} -> {action}")),
-                            Some(&direct_ip),
-                            forwarded_for.as_deref(),
-                            None,
+                            &AuditActor::from_request(
+                                Some(admin.id),
+                                &client_ip,
+                                forwarded_for.as_deref(),
+                            ),
+                            &AuditAction::AdminToggleExcludedDomain {
+                                domain: domain.clone(),
+                                active: !d.is_active,
+                            },
                         )
                         .await;
 
@@ -1105,7 +1259,8 @@ pub async fn admin_delete_excluded_domain(
     RequireAdmin(admin): RequireAdmin,
     Form(form): Form<ExcludedDomainActionForm>,
 ) -> Response {
-    let direct_ip = addr.ip().to_string();
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
     let forwarded_for = headers
         .get("x-forwarded-for")
         .and_then(|h| h.to_str().ok())
@@ -1118,16 +1273,12 @@ pub async fn admin_delete_excluded_domain(
             tracing::info!(admin_id = admin.id, domain = %domain, "Admin deleted excluded domain");
 
             // Log audit event
-            let _ = queries::create_audit_event(
+            let _ = queries::record_audit(
                 state.db.pool(),
-                Some(admin.id),
-                "admin_delete_excluded_domain",
-                Some("excluded_domain"),
-                None,
-                Some(&domain),
-                Some(&direct_ip),
-                forwarded_for.as_deref(),
-                None,
+                &AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for.as_deref()),
+                &AuditAction::AdminDeleteExcludedDomain {
+                    domain: domain.clone(),
+                },
             )
             .await;
 
@@ -1144,201 +1295,1199 @@ pub async fn admin_delete_excluded_domain(
     }
 }
 
-/// Form data for forum link actions.
 #[derive(Debug, Deserialize)]
-pub struct ForumLinkActionForm {
-    link_id: i64,
+pub struct DomainQuotePolicyForm {
+    domain: String,
+    archive_quote_only: bool,
 }
 
-/// POST /admin/forum-link/delete - Delete a forum account link.
-pub async fn admin_delete_forum_link(
+/// POST /admin/quote-policies/set - Create or update a quote-only override.
+pub async fn admin_set_quote_policy(
     State(state): State<AppState>,
     axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<SocketAddr>,
     headers: axum::http::HeaderMap,
     RequireAdmin(admin): RequireAdmin,
-    Form(form): Form<ForumLinkActionForm>,
+    Form(form): Form<DomainQuotePolicyForm>,
 ) -> Response {
-    let direct_ip = addr.ip().to_string();
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
     let forwarded_for = headers
         .get("x-forwarded-for")
         .and_then(|h| h.to_str().ok())
-        .map(|s| s.to_string());
+        .map(str::to_string);
 
-    // Get the link details before deleting for logging
-    let link = match queries::get_forum_link_by_id(state.db.pool(), form.link_id).await {
-        Ok(Some(l)) => l,
-        Ok(None) => {
-            return Redirect::to("/admin?tab=forum-links&message=Forum%20link%20not%20found")
-                .into_response();
-        }
-        Err(e) => {
-            tracing::error!("Failed to fetch forum link: {e}");
-            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
-        }
-    };
+    let domain = form.domain.trim().to_lowercase();
+    if domain.is_empty() {
+        return (StatusCode::BAD_REQUEST, "Domain cannot be empty").into_response();
+    }
 
-    match queries::delete_forum_link(state.db.pool(), form.link_id).await {
-        Ok(Some(user_id)) => {
+    match queries::set_domain_quote_policy(
+        state.db.pool(),
+        &domain,
+        form.archive_quote_only,
+        Some(admin.id),
+    )
+    .await
+    {
+        Ok(()) => {
             tracing::info!(
                 admin_id = admin.id,
-                link_id = form.link_id,
-                user_id = user_id,
-                forum_username = %link.forum_username,
-                "Admin deleted forum account link"
+                domain = %domain,
+                archive_quote_only = form.archive_quote_only,
+                "Admin set domain quote policy"
             );
 
-            // Log audit event
-            let metadata = format!(
-                r#"{{"forum_username": "{}", "user_id": {}}}"#,
-                link.forum_username, user_id
-            );
-            let _ = queries::create_audit_event(
+            let _ = queries::record_audit(
                 state.db.pool(),
-                Some(admin.id),
-                "admin_delete_forum_link",
-                Some("forum_link"),
-                Some(form.link_id),
-                Some(&metadata),
-                Some(&direct_ip),
-                forwarded_for.as_deref(),
-                None,
+                &AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for.as_deref()),
+                &AuditAction::AdminSetDomainQuotePolicy {
+                    domain: domain.clone(),
+                    archive_quote_only: form.archive_quote_only,
+                },
             )
             .await;
 
-            Redirect::to("/admin?tab=forum-links&message=Forum%20link%20deleted%20successfully")
-                .into_response()
+            Redirect::to("/admin/excluded-domains#quote-policies").into_response()
         }
-        Ok(None) => Redirect::to("/admin?tab=forum-links&message=Forum%20link%20not%20found")
-            .into_response(),
         Err(e) => {
-            tracing::error!("Failed to delete forum link: {e}");
+            tracing::error!("Failed to set domain quote policy: {e}");
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to delete forum link",
+                "Failed to set domain quote policy",
             )
                 .into_response()
         }
     }
 }
 
-/// GET /admin/user/:id - Show user profile (admin-only).
-pub async fn admin_user_profile(
+/// POST /admin/quote-policies/delete - Remove a quote-only override.
+pub async fn admin_delete_quote_policy(
     State(state): State<AppState>,
-    axum::extract::Path(user_id): axum::extract::Path<i64>,
+    axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<SocketAddr>,
+    headers: axum::http::HeaderMap,
     RequireAdmin(admin): RequireAdmin,
+    Form(form): Form<ExcludedDomainActionForm>,
 ) -> Response {
-    // Fetch the user
-    let user = match queries::get_user_by_id(state.db.pool(), user_id).await {
-        Ok(Some(u)) => u,
-        Ok(None) => {
-            return (StatusCode::NOT_FOUND, "User not found").into_response();
-        }
-        Err(e) => {
-            tracing::error!("Failed to fetch user: {e}");
-            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
-        }
-    };
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
+    let forwarded_for = headers
+        .get("x-forwarded-for")
+        .and_then(|h| h.to_str().ok())
+        .map(str::to_string);
 
-    // Fetch forum account link if it exists
-    let forum_link = match queries::get_forum_link_by_user_id(state.db.pool(), user_id).await {
-        Ok(link) => link,
+    let domain = form.domain.trim().to_lowercase();
+
+    match queries::delete_domain_quote_policy(state.db.pool(), &domain).await {
+        Ok(()) => {
+            tracing::info!(admin_id = admin.id, domain = %domain, "Admin deleted domain quote policy");
+
+            let _ = queries::record_audit(
+                state.db.pool(),
+                &AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for.as_deref()),
+                &AuditAction::AdminDeleteDomainQuotePolicy {
+                    domain: domain.clone(),
+                },
+            )
+            .await;
+
+            Redirect::to("/admin/excluded-domains#quote-policies").into_response()
+        }
         Err(e) => {
-            tracing::warn!("Failed to fetch forum link for user {}: {e}", user_id);
-            None
+            tracing::error!("Failed to delete domain quote policy: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to delete domain quote policy",
+            )
+                .into_response()
         }
-    };
-
-    // Fetch audit events for this user
-    let audit_events =
-        match queries::get_audit_events_for_user(state.db.pool(), user_id, 50, 0).await {
-            Ok(events) => events,
-            Err(e) => {
-                tracing::warn!("Failed to fetch audit events for user {}: {e}", user_id);
-                Vec::new()
-            }
-        };
+    }
+}
 
-    Html(
-        pages::render_admin_user_profile(&user, forum_link.as_ref(), &audit_events, &admin)
-            .into_string(),
-    )
-    .into_response()
+#[derive(Debug, Deserialize)]
+pub struct PriorityDomainForm {
+    domain: String,
+    reason: Option<String>,
 }
 
-/// GET /admin/forum-user/:username - Show forum user profile (admin-only).
-pub async fn admin_forum_user_profile(
+/// POST /admin/priority-domains/add - Add or update a priority domain.
+pub async fn admin_add_priority_domain(
     State(state): State<AppState>,
-    axum::extract::Path(forum_username): axum::extract::Path<String>,
+    axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<SocketAddr>,
+    headers: axum::http::HeaderMap,
     RequireAdmin(admin): RequireAdmin,
+    Form(form): Form<PriorityDomainForm>,
 ) -> Response {
-    // Fetch forum account link
-    let forum_link =
-        match queries::get_forum_link_by_forum_username(state.db.pool(), &forum_username).await {
-            Ok(Some(link)) => link,
-            Ok(None) => {
-                return (StatusCode::NOT_FOUND, "Forum user not found").into_response();
-            }
-            Err(e) => {
-                tracing::error!("Failed to fetch forum link: {e}");
-                return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
-            }
-        };
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
+    let forwarded_for = headers
+        .get("x-forwarded-for")
+        .and_then(|h| h.to_str().ok())
+        .map(str::to_string);
 
-    // Fetch the linked user
-    let user = match queries::get_user_by_id(state.db.pool(), forum_link.user_id).await {
-        Ok(u) => u,
-        Err(e) => {
-            tracing::warn!("Failed to fetch user for forum link: {e}");
-            None
-        }
-    };
+    let domain = form.domain.trim().to_lowercase();
+    if domain.is_empty() {
+        return (StatusCode::BAD_REQUEST, "Domain cannot be empty").into_response();
+    }
+    let reason = form.reason.unwrap_or_default().trim().to_string();
 
-    Html(pages::render_admin_forum_user_profile(&forum_link, user.as_ref(), &admin).into_string())
-        .into_response()
-}
+    match queries::add_priority_domain(state.db.pool(), &domain, &reason, Some(admin.id)).await {
+        Ok(()) => {
+            tracing::info!(admin_id = admin.id, domain = %domain, "Admin added priority domain");
 
-/// Form data for subtitle language delete actions.
-#[derive(Debug, Deserialize)]
-pub struct SubtitleLanguageActionForm {
-    id: i64,
+            let _ = queries::record_audit(
+                state.db.pool(),
+                &AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for.as_deref()),
+                &AuditAction::AdminAddPriorityDomain {
+                    domain: domain.clone(),
+                    reason,
+                },
+            )
+            .await;
+
+            Redirect::to("/admin/excluded-domains#priority-domains").into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to add priority domain: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to add priority domain",
+            )
+                .into_response()
+        }
+    }
 }
 
-/// POST /admin/subtitle-language/delete - Delete a subtitle language entry.
-///
-/// Deleting a subtitle language entry will cause the language to be re-detected
-/// when the archive page is visited.
-pub async fn admin_delete_subtitle_language(
+/// POST /admin/priority-domains/delete - Remove a priority domain.
+pub async fn admin_delete_priority_domain(
     State(state): State<AppState>,
     axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<SocketAddr>,
     headers: axum::http::HeaderMap,
     RequireAdmin(admin): RequireAdmin,
-    Form(form): Form<SubtitleLanguageActionForm>,
+    Form(form): Form<ExcludedDomainActionForm>,
 ) -> Response {
-    let direct_ip = addr.ip().to_string();
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
     let forwarded_for = headers
         .get("x-forwarded-for")
         .and_then(|h| h.to_str().ok())
-        .map(|s| s.to_string());
+        .map(str::to_string);
 
-    match queries::delete_subtitle_language(state.db.pool(), form.id).await {
-        Ok(true) => {
-            tracing::info!(
-                admin_id = admin.id,
-                subtitle_language_id = form.id,
-                "Admin deleted subtitle language entry"
-            );
+    let domain = form.domain.trim().to_lowercase();
 
-            // Log audit event
-            let _ = queries::create_audit_event(
+    match queries::delete_priority_domain(state.db.pool(), &domain).await {
+        Ok(()) => {
+            tracing::info!(admin_id = admin.id, domain = %domain, "Admin deleted priority domain");
+
+            let _ = queries::record_audit(
                 state.db.pool(),
-                Some(admin.id),
-                "admin_delete_subtitle_language",
-                Some("subtitle_language"),
-                Some(form.id),
-                None,
-                Some(&direct_ip),
-                forwarded_for.as_deref(),
-                None,
+                &AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for.as_deref()),
+                &AuditAction::AdminDeletePriorityDomain {
+                    domain: domain.clone(),
+                },
+            )
+            .await;
+
+            Redirect::to("/admin/excluded-domains#priority-domains").into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to delete priority domain: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to delete priority domain",
+            )
+                .into_response()
+        }
+    }
+}
+
+/// POST /admin/versioned-domains/add - Add or update a versioned domain.
+pub async fn admin_add_versioned_domain(
+    State(state): State<AppState>,
+    axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<SocketAddr>,
+    headers: axum::http::HeaderMap,
+    RequireAdmin(admin): RequireAdmin,
+    Form(form): Form<PriorityDomainForm>,
+) -> Response {
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
+    let forwarded_for = headers
+        .get("x-forwarded-for")
+        .and_then(|h| h.to_str().ok())
+        .map(str::to_string);
+
+    let domain = form.domain.trim().to_lowercase();
+    if domain.is_empty() {
+        return (StatusCode::BAD_REQUEST, "Domain cannot be empty").into_response();
+    }
+    let reason = form.reason.unwrap_or_default().trim().to_string();
+
+    match queries::add_versioned_domain(state.db.pool(), &domain, &reason, Some(admin.id)).await {
+        Ok(()) => {
+            tracing::info!(admin_id = admin.id, domain = %domain, "Admin added versioned domain");
+
+            let _ = queries::record_audit(
+                state.db.pool(),
+                &AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for.as_deref()),
+                &AuditAction::AdminAddVersionedDomain {
+                    domain: domain.clone(),
+                    reason,
+                },
+            )
+            .await;
+
+            Redirect::to("/admin/excluded-domains#versioned-domains").into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to add versioned domain: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to add versioned domain",
+            )
+                .into_response()
+        }
+    }
+}
+
+/// POST /admin/versioned-domains/delete - Remove a versioned domain.
+pub async fn admin_delete_versioned_domain(
+    State(state): State<AppState>,
+    axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<SocketAddr>,
+    headers: axum::http::HeaderMap,
+    RequireAdmin(admin): RequireAdmin,
+    Form(form): Form<ExcludedDomainActionForm>,
+) -> Response {
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
+    let forwarded_for = headers
+        .get("x-forwarded-for")
+        .and_then(|h| h.to_str().ok())
+        .map(str::to_string);
+
+    let domain = form.domain.trim().to_lowercase();
+
+    match queries::delete_versioned_domain(state.db.pool(), &domain).await {
+        Ok(()) => {
+            tracing::info!(admin_id = admin.id, domain = %domain, "Admin deleted versioned domain");
+
+            let _ = queries::record_audit(
+                state.db.pool(),
+                &AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for.as_deref()),
+                &AuditAction::AdminDeleteVersionedDomain {
+                    domain: domain.clone(),
+                },
+            )
+            .await;
+
+            Redirect::to("/admin/excluded-domains#versioned-domains").into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to delete versioned domain: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to delete versioned domain",
+            )
+                .into_response()
+        }
+    }
+}
+
+// ============================================================================
+// External Service Rules Admin Functions
+// ============================================================================
+
+/// GET /admin/external-services - Show external service rules.
+pub async fn admin_external_services_page(
+    State(state): State<AppState>,
+    RequireAdmin(admin): RequireAdmin,
+) -> Response {
+    let defaults = crate::db::ExternalServices {
+        wayback: state.config.wayback_enabled,
+        archive_today: state.config.archive_today_enabled,
+        ipfs: state.config.ipfs_enabled,
+    };
+
+    match queries::get_external_service_rules(state.db.read_pool()).await {
+        Ok(rules) => {
+            Html(pages::render_admin_external_services_page(&rules, defaults, &admin).into_string())
+                .into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to load external service rules: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to load external service rules",
+            )
+                .into_response()
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExternalServiceRuleForm {
+    scope: String,
+    pattern: String,
+    /// `on`, `off`, or empty to leave the service to the next rule.
+    #[serde(default)]
+    wayback: String,
+    #[serde(default)]
+    archive_today: String,
+    #[serde(default)]
+    ipfs: String,
+}
+
+/// Parse one service setting from the rule form.
+fn parse_service_setting(value: &str) -> Option<bool> {
+    match value {
+        "on" => Some(true),
+        "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// POST /admin/external-services/set - Create or update an external service rule.
+pub async fn admin_set_external_service_rule(
+    State(state): State<AppState>,
+    axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<SocketAddr>,
+    headers: axum::http::HeaderMap,
+    RequireAdmin(admin): RequireAdmin,
+    Form(form): Form<ExternalServiceRuleForm>,
+) -> Response {
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
+    let forwarded_for = headers
+        .get("x-forwarded-for")
+        .and_then(|h| h.to_str().ok())
+        .map(str::to_string);
+
+    let Some(scope) = crate::db::ExternalServiceScope::from_str(&form.scope) else {
+        return (StatusCode::BAD_REQUEST, "Invalid rule scope").into_response();
+    };
+    let pattern = form.pattern.trim().to_lowercase();
+    if pattern.is_empty() {
+        return (StatusCode::BAD_REQUEST, "Pattern cannot be empty").into_response();
+    }
+    let wayback = parse_service_setting(&form.wayback);
+    let archive_today = parse_service_setting(&form.archive_today);
+    let ipfs = parse_service_setting(&form.ipfs);
+
+    match queries::set_external_service_rule(
+        state.db.pool(),
+        scope,
+        &pattern,
+        wayback,
+        archive_today,
+        ipfs,
+        Some(admin.id),
+    )
+    .await
+    {
+        Ok(()) => {
+            tracing::info!(
+                admin_id = admin.id,
+                scope = scope.as_str(),
+                pattern = %pattern,
+                ?wayback,
+                ?archive_today,
+                ?ipfs,
+                "Admin set external service rule"
+            );
+
+            let _ = queries::record_audit(
+                state.db.pool(),
+                &AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for.as_deref()),
+                &AuditAction::AdminSetExternalServiceRule {
+                    scope: scope.as_str().to_string(),
+                    pattern,
+                    wayback,
+                    archive_today,
+                    ipfs,
+                },
+            )
+            .await;
+
+            Redirect::to("/admin/external-services").into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to set external service rule: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to set external service rule",
+            )
+                .into_response()
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExternalServiceRuleActionForm {
+    id: i64,
+}
+
+/// POST /admin/external-services/delete - Remove an external service rule.
+pub async fn admin_delete_external_service_rule(
+    State(state): State<AppState>,
+    axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<SocketAddr>,
+    headers: axum::http::HeaderMap,
+    RequireAdmin(admin): RequireAdmin,
+    Form(form): Form<ExternalServiceRuleActionForm>,
+) -> Response {
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
+    let forwarded_for = headers
+        .get("x-forwarded-for")
+        .and_then(|h| h.to_str().ok())
+        .map(str::to_string);
+
+    match queries::delete_external_service_rule(state.db.pool(), form.id).await {
+        Ok(Some(rule)) => {
+            tracing::info!(
+                admin_id = admin.id,
+                scope = %rule.scope,
+                pattern = %rule.pattern,
+                "Admin deleted external service rule"
+            );
+
+            let _ = queries::record_audit(
+                state.db.pool(),
+                &AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for.as_deref()),
+                &AuditAction::AdminDeleteExternalServiceRule {
+                    scope: rule.scope,
+                    pattern: rule.pattern,
+                },
+            )
+            .await;
+
+            Redirect::to("/admin/external-services").into_response()
+        }
+        Ok(None) => Redirect::to("/admin/external-services").into_response(),
+        Err(e) => {
+            tracing::error!("Failed to delete external service rule: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to delete external service rule",
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Form data for forum link actions.
+#[derive(Debug, Deserialize)]
+pub struct ForumLinkActionForm {
+    link_id: i64,
+}
+
+/// POST /admin/forum-link/delete - Delete a forum account link.
+pub async fn admin_delete_forum_link(
+    State(state): State<AppState>,
+    axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<SocketAddr>,
+    headers: axum::http::HeaderMap,
+    RequireAdmin(admin): RequireAdmin,
+    Form(form): Form<ForumLinkActionForm>,
+) -> Response {
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
+    let forwarded_for = headers
+        .get("x-forwarded-for")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    // Get the link details before deleting for logging
+    let link = match queries::get_forum_link_by_id(state.db.read_pool(), form.link_id).await {
+        Ok(Some(l)) => l,
+        Ok(None) => {
+            return Redirect::to("/admin?tab=forum-links&message=Forum%20link%20not%20found")
+                .into_response();
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch forum link: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    match queries::delete_forum_link(state.db.pool(), form.link_id).await {
+        Ok(Some(user_id)) => {
+            tracing::info!(
+                admin_id = admin.id,
+                link_id = form.link_id,
+                user_id = user_id,
+                forum_username = %link.forum_username,
+                "Admin deleted forum account link"
+            );
+
+            // Log audit event
+            let _ = queries::record_audit(
+                state.db.pool(),
+                &AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for.as_deref()),
+                &AuditAction::AdminDeleteForumLink {
+                    link_id: form.link_id,
+                    forum_username: link.forum_username.clone(),
+                    user_id,
+                },
+            )
+            .await;
+
+            Redirect::to("/admin?tab=forum-links&message=Forum%20link%20deleted%20successfully")
+                .into_response()
+        }
+        Ok(None) => Redirect::to("/admin?tab=forum-links&message=Forum%20link%20not%20found")
+            .into_response(),
+        Err(e) => {
+            tracing::error!("Failed to delete forum link: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to delete forum link",
+            )
+                .into_response()
+        }
+    }
+}
+
+/// GET /admin/user/:id - Show user profile (admin-only).
+pub async fn admin_user_profile(
+    State(state): State<AppState>,
+    axum::extract::Path(user_id): axum::extract::Path<i64>,
+    RequireAdmin(admin): RequireAdmin,
+) -> Response {
+    // Fetch the user
+    let user = match queries::get_user_by_id(state.db.read_pool(), user_id).await {
+        Ok(Some(u)) => u,
+        Ok(None) => {
+            return (StatusCode::NOT_FOUND, "User not found").into_response();
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch user: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    // Fetch forum account link if it exists
+    let forum_link = match queries::get_forum_link_by_user_id(state.db.read_pool(), user_id).await {
+        Ok(link) => link,
+        Err(e) => {
+            tracing::warn!("Failed to fetch forum link for user {}: {e}", user_id);
+            None
+        }
+    };
+
+    // Fetch audit events for this user
+    let audit_events =
+        match queries::get_audit_events_for_user(state.db.read_pool(), user_id, 50, 0).await {
+            Ok(events) => events,
+            Err(e) => {
+                tracing::warn!("Failed to fetch audit events for user {}: {e}", user_id);
+                Vec::new()
+            }
+        };
+
+    Html(
+        pages::render_admin_user_profile(&user, forum_link.as_ref(), &audit_events, &admin)
+            .into_string(),
+    )
+    .into_response()
+}
+
+/// GET /admin/forum-user/:username - Show forum user profile (admin-only).
+pub async fn admin_forum_user_profile(
+    State(state): State<AppState>,
+    axum::extract::Path(forum_username): axum::extract::Path<String>,
+    RequireAdmin(admin): RequireAdmin,
+) -> Response {
+    // Fetch forum account link
+    let forum_link = match queries::get_forum_link_by_forum_username(
+        state.db.read_pool(),
+        &forum_username,
+    )
+    .await
+    {
+        Ok(Some(link)) => link,
+        Ok(None) => {
+            return (StatusCode::NOT_FOUND, "Forum user not found").into_response();
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch forum link: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    // Fetch the linked user
+    let user = match queries::get_user_by_id(state.db.read_pool(), forum_link.user_id).await {
+        Ok(u) => u,
+        Err(e) => {
+            tracing::warn!("Failed to fetch user for forum link: {e}");
+            None
+        }
+    };
+
+    Html(pages::render_admin_forum_user_profile(&forum_link, user.as_ref(), &admin).into_string())
+        .into_response()
+}
+
+/// Form data for subtitle language delete actions.
+#[derive(Debug, Deserialize)]
+pub struct SubtitleLanguageActionForm {
+    id: i64,
+}
+
+/// Maximum number of archives queued per missing-artifacts sweep run.
+const MISSING_ARTIFACTS_SWEEP_LIMIT: i64 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct MissingArtifactsSweepForm {
+    #[serde(default)]
+    after_id: i64,
+}
+
+/// POST /admin/reprocess-missing-artifacts - Queue jobs for missing artifacts.
+///
+/// Scans the next batch of completed archives (by ID, after `after_id`) that
+/// lack expected artifacts and queues targeted jobs: a supplementary artifacts
+/// job for subtitles/transcripts and a comment extraction job for comments.
+/// Archives that already have such a job pending or running are skipped, so
+/// re-running the sweep is safe.
+pub async fn admin_reprocess_missing_artifacts(
+    State(state): State<AppState>,
+    axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<SocketAddr>,
+    headers: axum::http::HeaderMap,
+    RequireAdmin(admin): RequireAdmin,
+    Form(form): Form<MissingArtifactsSweepForm>,
+) -> Response {
+    use crate::db::{ArchiveJobType, ArtifactKind};
+
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
+    let forwarded_for = headers
+        .get("x-forwarded-for")
+        .and_then(|h| h.to_str().ok())
+        .map(ToString::to_string);
+
+    let archives = match queries::get_archives_missing_artifacts(
+        state.db.read_pool(),
+        form.after_id,
+        MISSING_ARTIFACTS_SWEEP_LIMIT,
+    )
+    .await
+    {
+        Ok(a) => a,
+        Err(e) => {
+            tracing::error!("Failed to scan archives missing artifacts: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to scan archives").into_response();
+        }
+    };
+
+    let pool = state.db.pool();
+    let mut supplementary_jobs = Vec::new();
+    let mut comment_jobs = 0;
+
+    for entry in &archives {
+        let needs_subtitles = entry.missing.contains(&ArtifactKind::Subtitles);
+        let needs_transcript = entry.missing.contains(&ArtifactKind::Transcript);
+        let needs_comments = entry.missing.contains(&ArtifactKind::Comments);
+
+        if (needs_subtitles || needs_transcript)
+            && !queries::has_active_job(
+                pool,
+                entry.archive_id,
+                ArchiveJobType::SupplementaryArtifacts,
+            )
+            .await
+            .unwrap_or(true)
+        {
+            match queries::create_archive_job(
+                pool,
+                entry.archive_id,
+                ArchiveJobType::SupplementaryArtifacts,
+            )
+            .await
+            {
+                Ok(job_id) => supplementary_jobs.push((entry.archive_id, job_id, needs_transcript)),
+                Err(e) => tracing::warn!(
+                    archive_id = entry.archive_id,
+                    error = %e,
+                    "Failed to create supplementary artifacts job"
+                ),
+            }
+        }
+
+        // Comment jobs are picked up by the comment worker's queue
+        if needs_comments
+            && state.config.comments_enabled
+            && !queries::has_active_job(pool, entry.archive_id, ArchiveJobType::CommentExtraction)
+                .await
+                .unwrap_or(true)
+        {
+            match queries::create_archive_job(
+                pool,
+                entry.archive_id,
+                ArchiveJobType::CommentExtraction,
+            )
+            .await
+            {
+                Ok(_) => comment_jobs += 1,
+                Err(e) => tracing::warn!(
+                    archive_id = entry.archive_id,
+                    error = %e,
+                    "Failed to create comment extraction job"
+                ),
+            }
+        }
+    }
+
+    tracing::info!(
+        admin_id = admin.id,
+        after_id = form.after_id,
+        scanned = archives.len(),
+        supplementary_jobs = supplementary_jobs.len(),
+        comment_jobs,
+        "Admin queued missing artifact backfill"
+    );
+
+    let _ = queries::record_audit(
+        pool,
+        &AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for.as_deref()),
+        &AuditAction::AdminReprocessMissingArtifacts {
+            after_id: form.after_id,
+            archives: archives.len(),
+            supplementary_jobs: supplementary_jobs.len(),
+            comment_jobs,
+        },
+    )
+    .await;
+
+    // Run supplementary jobs one at a time so the sweep doesn't flood yt-dlp
+    if !supplementary_jobs.is_empty() {
+        let state_clone = state.clone();
+        tokio::spawn(async move {
+            for (archive_id, job_id, needs_transcript) in supplementary_jobs {
+                crate::web::routes::process_supplementary_artifacts_job(
+                    state_clone.clone(),
+                    archive_id,
+                    job_id,
+                    needs_transcript,
+                )
+                .await;
+            }
+        });
+    }
+
+    let message = format!(
+        "Queued backfill for {} archives ({} comment jobs)",
+        archives.len(),
+        comment_jobs
+    );
+    let mut location = format!("/admin?tab=tools&message={}", urlencoding::encode(&message));
+    // A full batch means there may be more archives after the last one
+    if archives.len() as i64 == MISSING_ARTIFACTS_SWEEP_LIMIT {
+        if let Some(last) = archives.last() {
+            location.push_str(&format!("&backfill_after={}", last.archive_id));
+        }
+    }
+
+    Redirect::to(&location).into_response()
+}
+
+/// Artifacts checked per size recompute run.
+const RECOMPUTE_SIZES_BATCH: i64 = 500;
+
+/// POST /admin/recompute-sizes - Correct stored artifact sizes from S3.
+///
+/// Heads the next batch of artifacts (by ID, after `after_id`) and overwrites
+/// `size_bytes` wherever it differs from the object in S3, so storage stats
+/// that have drifted are fixed.
+pub async fn admin_recompute_sizes(
+    State(state): State<AppState>,
+    axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<SocketAddr>,
+    headers: axum::http::HeaderMap,
+    RequireAdmin(admin): RequireAdmin,
+    Form(form): Form<MissingArtifactsSweepForm>,
+) -> Response {
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
+    let forwarded_for = headers
+        .get("x-forwarded-for")
+        .and_then(|h| h.to_str().ok())
+        .map(ToString::to_string);
+
+    let report = match crate::archiver::storage_sizes::recompute_artifact_sizes(
+        &state.db,
+        &state.s3,
+        form.after_id,
+        RECOMPUTE_SIZES_BATCH,
+    )
+    .await
+    {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::error!("Failed to recompute artifact sizes: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to recompute sizes",
+            )
+                .into_response();
+        }
+    };
+
+    tracing::info!(
+        admin_id = admin.id,
+        after_id = form.after_id,
+        checked = report.checked,
+        fixed = report.discrepancies.len(),
+        missing = report.missing,
+        "Admin recomputed artifact sizes"
+    );
+
+    let _ = queries::record_audit(
+        state.db.pool(),
+        &AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for.as_deref()),
+        &AuditAction::AdminRecomputeSizes {
+            after_id: form.after_id,
+            checked: report.checked,
+            fixed: report.discrepancies.len(),
+            missing: report.missing,
+        },
+    )
+    .await;
+
+    let delta: i64 = report
+        .discrepancies
+        .iter()
+        .map(|d| d.actual - d.stored.unwrap_or(0))
+        .sum();
+    let mut message = format!(
+        "Checked {} artifacts: {} sizes corrected ({:+} bytes), {} missing from S3",
+        report.checked,
+        report.discrepancies.len(),
+        delta,
+        report.missing
+    );
+    if report.errors > 0 {
+        message.push_str(&format!(", {} could not be checked", report.errors));
+    }
+    let mut location = format!("/admin?tab=tools&message={}", urlencoding::encode(&message));
+    // A full batch means there may be more artifacts after the last one
+    if report.checked as i64 == RECOMPUTE_SIZES_BATCH {
+        if let Some(last_id) = report.last_id {
+            location.push_str(&format!("&sizes_after={last_id}"));
+        }
+    }
+
+    Redirect::to(&location).into_response()
+}
+
+/// Links re-normalized per page of the duplicate links scan.
+const DUPLICATE_SCAN_BATCH: i64 = 1000;
+
+/// Query params for the duplicate links page.
+#[derive(Debug, Deserialize)]
+pub struct DuplicateLinksQuery {
+    /// Link ID the page starts after
+    #[serde(default)]
+    after_id: i64,
+    /// Success/error message
+    message: Option<String>,
+}
+
+/// GET /admin/links/duplicates - Find duplicate links, one page at a time.
+pub async fn admin_duplicate_links_page(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<DuplicateLinksQuery>,
+    RequireAdmin(admin): RequireAdmin,
+) -> Response {
+    match queries::find_duplicate_links(state.db.read_pool(), query.after_id, DUPLICATE_SCAN_BATCH)
+        .await
+    {
+        Ok(scan) => Html(
+            pages::render_admin_duplicate_links_page(
+                &scan,
+                query.after_id,
+                query.message.as_deref(),
+                &admin,
+            )
+            .into_string(),
+        )
+        .into_response(),
+        Err(e) => {
+            tracing::error!(
+                after_id = query.after_id,
+                "Failed to find duplicate links: {e:#}"
+            );
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to find duplicate links",
+            )
+                .into_response()
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MergeLinksForm {
+    keep_id: i64,
+    /// Comma-separated IDs of the links to merge into `keep_id`
+    merge_ids: String,
+    /// Duplicate links page to return to
+    #[serde(default)]
+    after_id: i64,
+}
+
+/// POST /admin/links/merge - Merge duplicate links into one.
+pub async fn admin_merge_links(
+    State(state): State<AppState>,
+    axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<SocketAddr>,
+    headers: axum::http::HeaderMap,
+    RequireAdmin(admin): RequireAdmin,
+    Form(form): Form<MergeLinksForm>,
+) -> Response {
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
+    let forwarded_for = headers
+        .get("x-forwarded-for")
+        .and_then(|h| h.to_str().ok())
+        .map(ToString::to_string);
+
+    let Ok(merge_ids) = form
+        .merge_ids
+        .split(',')
+        .map(|id| id.trim().parse::<i64>())
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return (StatusCode::BAD_REQUEST, "Invalid link IDs").into_response();
+    };
+
+    let summary = match queries::merge_links(state.db.pool(), form.keep_id, &merge_ids).await {
+        Ok(s) => s,
+        Err(e) => {
+            tracing::error!(keep_id = form.keep_id, "Failed to merge links: {e:#}");
+            let message = format!("Failed to merge links: {e:#}");
+            return Redirect::to(&format!(
+                "/admin/links/duplicates?after_id={}&message={}",
+                form.after_id,
+                urlencoding::encode(&message)
+            ))
+            .into_response();
+        }
+    };
+
+    tracing::info!(
+        admin_id = admin.id,
+        keep_id = form.keep_id,
+        merged = ?merge_ids,
+        ?summary,
+        "Admin merged duplicate links"
+    );
+
+    let _ = queries::record_audit(
+        state.db.pool(),
+        &AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for.as_deref()),
+        &AuditAction::AdminMergeLinks {
+            keep_id: form.keep_id,
+            merged_link_ids: merge_ids,
+            archives_merged: summary.archives_merged,
+        },
+    )
+    .await;
+
+    let message = format!(
+        "Merged {} links into link #{} ({} archives folded, {} duplicate artifacts removed)",
+        summary.links_removed,
+        form.keep_id,
+        summary.archives_merged,
+        summary.artifacts_deduplicated
+    );
+    Redirect::to(&format!(
+        "/admin/links/duplicates?after_id={}&message={}",
+        form.after_id,
+        urlencoding::encode(&message)
+    ))
+    .into_response()
+}
+
+/// POST /admin/og/reset - Reset OG extraction for archives that found no title.
+///
+/// Clears the attempted flag so extraction runs again from the stored HTML
+/// the next time each archive is viewed.
+pub async fn admin_reset_og_extraction(
+    State(state): State<AppState>,
+    axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<SocketAddr>,
+    headers: axum::http::HeaderMap,
+    RequireAdmin(admin): RequireAdmin,
+) -> Response {
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
+    let forwarded_for = headers
+        .get("x-forwarded-for")
+        .and_then(|h| h.to_str().ok())
+        .map(ToString::to_string);
+
+    let archives_reset = match queries::reset_failed_og_extractions(state.db.pool()).await {
+        Ok(n) => n,
+        Err(e) => {
+            tracing::error!("Failed to reset OG extraction: {e:#}");
+            let message = format!("Failed to reset OG extraction: {e}");
+            return Redirect::to(&format!(
+                "/admin?tab=tools&message={}",
+                urlencoding::encode(&message)
+            ))
+            .into_response();
+        }
+    };
+
+    tracing::info!(
+        admin_id = admin.id,
+        archives_reset,
+        "Admin reset OG extraction"
+    );
+
+    let _ = queries::record_audit(
+        state.db.pool(),
+        &AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for.as_deref()),
+        &AuditAction::AdminResetOgExtraction {
+            archive_id: None,
+            archives_reset,
+        },
+    )
+    .await;
+
+    let message = format!("Reset OG extraction for {archives_reset} archives");
+    Redirect::to(&format!(
+        "/admin?tab=tools&message={}",
+        urlencoding::encode(&message)
+    ))
+    .into_response()
+}
+
+/// GET /admin/bulk-import - Show the bulk URL import form.
+pub async fn admin_bulk_import_page(RequireAdmin(admin): RequireAdmin) -> Response {
+    Html(
+        pages::render_admin_bulk_import_page(None, None, bulk_import::MAX_BULK_IMPORT_URLS, &admin)
+            .into_string(),
+    )
+    .into_response()
+}
+
+/// Read the pasted `urls` field and uploaded `file` field into one input text.
+async fn read_bulk_import_input(mut multipart: Multipart) -> Result<String, String> {
+    let mut input = String::new();
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| format!("Invalid upload: {e}"))?
+    {
+        if !matches!(field.name(), Some("urls" | "file")) {
+            continue;
+        }
+        let text = field
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read upload: {e}"))?;
+        input.push_str(&text);
+        input.push('\n');
+    }
+    Ok(input)
+}
+
+/// POST /admin/bulk-import - Queue a pasted or uploaded list of URLs.
+pub async fn admin_bulk_import(
+    State(state): State<AppState>,
+    axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<SocketAddr>,
+    headers: axum::http::HeaderMap,
+    RequireAdmin(admin): RequireAdmin,
+    multipart: Multipart,
+) -> Response {
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
+    let forwarded_for = headers
+        .get("x-forwarded-for")
+        .and_then(|h| h.to_str().ok())
+        .map(ToString::to_string);
+
+    let render_error = |message: &str| {
+        (
+            StatusCode::BAD_REQUEST,
+            Html(
+                pages::render_admin_bulk_import_page(
+                    None,
+                    Some(message),
+                    bulk_import::MAX_BULK_IMPORT_URLS,
+                    &admin,
+                )
+                .into_string(),
+            ),
+        )
+            .into_response()
+    };
+
+    let input = match read_bulk_import_input(multipart).await {
+        Ok(input) => input,
+        Err(message) => return render_error(&message),
+    };
+    if input.trim().is_empty() {
+        return render_error("Paste some URLs or choose a file to import.");
+    }
+
+    let parsed = bulk_import::parse_bulk_input(&input, bulk_import::MAX_BULK_IMPORT_URLS);
+    let summary =
+        bulk_import::import_bulk_urls(state.db.pool(), &parsed, &client_ip, admin.id).await;
+
+    tracing::info!(admin_id = admin.id, ?summary, "Admin bulk imported URLs");
+
+    let _ = queries::record_audit(
+        state.db.pool(),
+        &AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for.as_deref()),
+        &AuditAction::AdminBulkImport {
+            queued: summary.queued,
+            excluded: summary.excluded,
+            already_archived: summary.already_archived,
+            invalid: summary.invalid,
+        },
+    )
+    .await;
+
+    Html(
+        pages::render_admin_bulk_import_page(
+            Some(&summary),
+            None,
+            bulk_import::MAX_BULK_IMPORT_URLS,
+            &admin,
+        )
+        .into_string(),
+    )
+    .into_response()
+}
+
+/// POST /admin/subtitle-language/delete - Delete a subtitle language entry.
+///
+/// Deleting a subtitle language entry will cause the language to be re-detected
+/// when the archive page is visited.
+pub async fn admin_delete_subtitle_language(
+    State(state): State<AppState>,
+    axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<SocketAddr>,
+    headers: axum::http::HeaderMap,
+    RequireAdmin(admin): RequireAdmin,
+    Form(form): Form<SubtitleLanguageActionForm>,
+) -> Response {
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
+    let forwarded_for = headers
+        .get("x-forwarded-for")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match queries::delete_subtitle_language(state.db.pool(), form.id).await {
+        Ok(true) => {
+            tracing::info!(
+                admin_id = admin.id,
+                subtitle_language_id = form.id,
+                "Admin deleted subtitle language entry"
+            );
+
+            // Log audit event
+            let _ = queries::record_audit(
+                state.db.pool(),
+                &AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for.as_deref()),
+                &AuditAction::AdminDeleteSubtitleLanguage {
+                    subtitle_language_id: form.id,
+                },
             )
             .await;
 
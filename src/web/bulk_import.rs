@@ -0,0 +1,405 @@
+//! Admin bulk URL import.
+//!
+//! Admins can paste a list of URLs or upload a CSV/newline-delimited file.
+//! Input is parsed into normalized candidates here, then each candidate goes
+//! through the same dedup checks and queueing steps as a single `/submit`.
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+use sqlx::SqlitePool;
+
+use crate::db::{
+    create_pending_archive, get_archive_by_link_id, get_link_by_normalized_url, get_or_create_link,
+    insert_submission, is_domain_excluded, reset_archive_for_retry, submission_exists_for_url,
+    NewLink, NewSubmission,
+};
+use crate::handlers::normalize_link_url;
+
+/// Maximum number of URLs accepted in one bulk import.
+pub const MAX_BULK_IMPORT_URLS: usize = 500;
+
+/// Maximum number of invalid entries echoed back in the summary.
+const MAX_REPORTED_INVALID: usize = 50;
+
+/// A URL from bulk input that passed validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkCandidate {
+    pub url: String,
+    pub normalized_url: String,
+    pub domain: String,
+}
+
+/// Bulk input split into valid candidates and rejected entries.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ParsedBulkInput {
+    /// Valid, unique URLs in input order (at most the batch limit).
+    pub candidates: Vec<BulkCandidate>,
+    /// Entries that are not valid HTTP(S) URLs.
+    pub invalid: Vec<String>,
+    /// URLs that normalize to one already seen earlier in the input.
+    pub duplicates: usize,
+    /// Valid URLs dropped because the batch limit was reached.
+    pub over_limit: usize,
+}
+
+/// Outcome counts for a bulk import.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BulkImportSummary {
+    pub queued: usize,
+    pub excluded: usize,
+    pub already_archived: usize,
+    /// Already pending/processing, or submitted in the last 24 hours.
+    pub already_queued: usize,
+    pub invalid: usize,
+    pub duplicates: usize,
+    pub over_limit: usize,
+    /// Candidates that hit a database error while queueing.
+    pub failed: usize,
+    /// The first few invalid entries, for display.
+    pub invalid_examples: Vec<String>,
+}
+
+/// What happened to a single candidate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Queued,
+    Excluded,
+    AlreadyArchived,
+    AlreadyQueued,
+}
+
+/// Split one CSV line into fields, honouring double-quoted fields.
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields.into_iter().map(|f| f.trim().to_string()).collect()
+}
+
+/// Pick the URL entry from a line: the first field that looks like a URL,
+/// or the whole line when there is none.
+fn line_entry(line: &str) -> String {
+    if !line.contains(',') {
+        return line.trim_matches('"').to_string();
+    }
+    let fields = split_csv_line(line);
+    fields
+        .iter()
+        .find(|f| f.contains("://"))
+        .or_else(|| fields.first())
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Validate a single entry, returning the candidate to queue.
+fn validate_entry(entry: &str) -> Option<BulkCandidate> {
+    let parsed = url::Url::parse(entry).ok()?;
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return None;
+    }
+    parsed.host_str()?;
+    let normalized_url = normalize_link_url(entry);
+    let domain = url::Url::parse(&normalized_url)
+        .ok()?
+        .host_str()?
+        .to_string();
+    Some(BulkCandidate {
+        url: entry.to_string(),
+        normalized_url,
+        domain,
+    })
+}
+
+/// Parse pasted or uploaded bulk input.
+///
+/// Accepts one URL per line or CSV rows (the first field containing `://` is
+/// used). Blank lines, `#` comments and a leading `url` header are ignored.
+/// URLs are deduplicated by their normalized form, and at most `max_urls`
+/// candidates are kept.
+#[must_use]
+pub fn parse_bulk_input(input: &str, max_urls: usize) -> ParsedBulkInput {
+    let mut parsed = ParsedBulkInput::default();
+    let mut seen = HashSet::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim().trim_start_matches('\u{feff}');
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let entry = line_entry(line);
+        if index == 0 && entry.eq_ignore_ascii_case("url") {
+            continue;
+        }
+
+        let Some(candidate) = validate_entry(&entry) else {
+            parsed.invalid.push(entry);
+            continue;
+        };
+        if !seen.insert(candidate.normalized_url.clone()) {
+            parsed.duplicates += 1;
+        } else if parsed.candidates.len() >= max_urls {
+            parsed.over_limit += 1;
+        } else {
+            parsed.candidates.push(candidate);
+        }
+    }
+
+    parsed
+}
+
+/// Run the submission dedup checks for a candidate and queue it if new.
+async fn import_candidate(
+    pool: &SqlitePool,
+    candidate: &BulkCandidate,
+    client_ip: &str,
+    user_id: i64,
+) -> Result<Outcome> {
+    if is_domain_excluded(pool, &candidate.domain).await? {
+        return Ok(Outcome::Excluded);
+    }
+
+    let existing_link = get_link_by_normalized_url(pool, &candidate.normalized_url).await?;
+    let existing_archive = match &existing_link {
+        Some(link) => get_archive_by_link_id(pool, link.id).await?,
+        None => None,
+    };
+    match existing_archive.as_ref().map(|a| a.status.as_str()) {
+        Some("complete") => return Ok(Outcome::AlreadyArchived),
+        Some("pending" | "processing") => return Ok(Outcome::AlreadyQueued),
+        _ => {}
+    }
+    if submission_exists_for_url(pool, &candidate.normalized_url).await? {
+        return Ok(Outcome::AlreadyQueued);
+    }
+
+    let submission = NewSubmission {
+        url: candidate.url.clone(),
+        normalized_url: candidate.normalized_url.clone(),
+        submitted_by_ip: client_ip.to_string(),
+        submitted_by_user_id: Some(user_id),
+    };
+    insert_submission(pool, &submission).await?;
+
+    let link_id = match existing_link {
+        Some(link) => link.id,
+        None => {
+            let new_link = NewLink {
+                original_url: candidate.url.clone(),
+                normalized_url: candidate.normalized_url.clone(),
+                canonical_url: None,
+                domain: candidate.domain.clone(),
+            };
+            get_or_create_link(pool, &new_link).await?
+        }
+    };
+
+    // Failed or skipped archives are queued again; the admin asked for them
+    match existing_archive {
+        Some(archive) => reset_archive_for_retry(pool, archive.id).await?,
+        None => {
+            create_pending_archive(pool, link_id, None).await?;
+        }
+    }
+
+    Ok(Outcome::Queued)
+}
+
+/// Import parsed bulk input as submissions attributed to `user_id`.
+///
+/// Database errors for one URL are logged and counted as failed rather than
+/// aborting the rest of the batch.
+pub async fn import_bulk_urls(
+    pool: &SqlitePool,
+    parsed: &ParsedBulkInput,
+    client_ip: &str,
+    user_id: i64,
+) -> BulkImportSummary {
+    let mut summary = BulkImportSummary {
+        invalid: parsed.invalid.len(),
+        duplicates: parsed.duplicates,
+        over_limit: parsed.over_limit,
+        invalid_examples: parsed
+            .invalid
+            .iter()
+            .take(MAX_REPORTED_INVALID)
+            .cloned()
+            .collect(),
+        ..Default::default()
+    };
+
+    for candidate in &parsed.candidates {
+        match import_candidate(pool, candidate, client_ip, user_id).await {
+            Ok(Outcome::Queued) => summary.queued += 1,
+            Ok(Outcome::Excluded) => summary.excluded += 1,
+            Ok(Outcome::AlreadyArchived) => summary.already_archived += 1,
+            Ok(Outcome::AlreadyQueued) => summary.already_queued += 1,
+            Err(e) => {
+                tracing::error!(url = %candidate.url, "Bulk import failed for URL: {e:#}");
+                summary.failed += 1;
+            }
+        }
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{add_excluded_domain, create_user, set_archive_complete, Database};
+
+    #[test]
+    fn test_parse_bulk_input_mixed() {
+        let input = "\
+url,title
+https://example.com/a,First
+\"https://example.com/b?x=1,2\",\"Second, quoted\"
+
+# a comment
+not a url
+ftp://example.com/file
+http://example.com/a/
+https://youtu.be/dQw4w9WgXcQ
+https://www.youtube.com/shorts/dQw4w9WgXcQ
+notes,https://other.example.org/page
+";
+        let parsed = parse_bulk_input(input, MAX_BULK_IMPORT_URLS);
+
+        let urls: Vec<&str> = parsed
+            .candidates
+            .iter()
+            .map(|c| c.normalized_url.as_str())
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://example.com/a",
+                "https://example.com/b?x=1,2",
+                "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+                "https://other.example.org/page",
+            ]
+        );
+        assert_eq!(parsed.candidates[3].domain, "other.example.org");
+        assert_eq!(parsed.invalid, vec!["not a url", "ftp://example.com/file"]);
+        // http://example.com/a/ and the shorts URL collapse onto earlier entries
+        assert_eq!(parsed.duplicates, 2);
+        assert_eq!(parsed.over_limit, 0);
+    }
+
+    #[test]
+    fn test_parse_bulk_input_limit() {
+        let input = "https://a.example/1\nhttps://a.example/2\nhttps://a.example/3\n";
+        let parsed = parse_bulk_input(input, 2);
+        assert_eq!(parsed.candidates.len(), 2);
+        assert_eq!(parsed.over_limit, 1);
+
+        assert_eq!(parse_bulk_input("", 2), ParsedBulkInput::default());
+    }
+
+    #[test]
+    fn test_split_csv_line() {
+        assert_eq!(split_csv_line("a, b ,c"), vec!["a", "b", "c"]);
+        assert_eq!(
+            split_csv_line("\"say \"\"hi\"\"\",x"),
+            vec!["say \"hi\"", "x"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_bulk_urls_classifies_entries() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db = Database::new(&temp_dir.path().join("test.sqlite"))
+            .await
+            .unwrap();
+        let pool = db.pool();
+        let admin_id = create_user(pool, "admin", "hash", true).await.unwrap();
+        add_excluded_domain(pool, "*.blocked.example", "test", None)
+            .await
+            .unwrap();
+
+        // An archived URL and a pending one
+        let archived_link = get_or_create_link(
+            pool,
+            &NewLink {
+                original_url: "https://done.example/page".to_string(),
+                normalized_url: "https://done.example/page".to_string(),
+                canonical_url: None,
+                domain: "done.example".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        let archive_id = create_pending_archive(pool, archived_link, None)
+            .await
+            .unwrap();
+        set_archive_complete(pool, archive_id, None, None, None, None, None, None)
+            .await
+            .unwrap();
+        let pending_link = get_or_create_link(
+            pool,
+            &NewLink {
+                original_url: "https://pending.example/page".to_string(),
+                normalized_url: "https://pending.example/page".to_string(),
+                canonical_url: None,
+                domain: "pending.example".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        create_pending_archive(pool, pending_link, None)
+            .await
+            .unwrap();
+
+        let input = "\
+https://new.example/one
+https://sub.blocked.example/two
+https://done.example/page
+https://pending.example/page
+https://new.example/one?utm_source=x
+garbage
+";
+        let parsed = parse_bulk_input(input, MAX_BULK_IMPORT_URLS);
+        let summary = import_bulk_urls(pool, &parsed, "127.0.0.1", admin_id).await;
+
+        assert_eq!(summary.queued, 1);
+        assert_eq!(summary.excluded, 1);
+        assert_eq!(summary.already_archived, 1);
+        assert_eq!(summary.already_queued, 1);
+        assert_eq!(summary.invalid, 1);
+        assert_eq!(summary.duplicates, 1);
+        assert_eq!(summary.failed, 0);
+        assert_eq!(summary.invalid_examples, vec!["garbage"]);
+
+        let link = get_link_by_normalized_url(pool, "https://new.example/one")
+            .await
+            .unwrap()
+            .unwrap();
+        let archive = get_archive_by_link_id(pool, link.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(archive.status, "pending");
+
+        // Importing again finds the fresh submission and queues nothing new
+        let again = import_bulk_urls(pool, &parsed, "127.0.0.1", admin_id).await;
+        assert_eq!(again.queued, 0);
+        assert_eq!(again.already_queued, 2);
+    }
+}
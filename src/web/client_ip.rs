@@ -0,0 +1,247 @@
+//! Client IP extraction behind reverse proxies.
+//!
+//! `Forwarded`, `X-Forwarded-For` and `X-Real-IP` are only believed when the
+//! connecting peer is a trusted proxy (`TRUSTED_PROXIES`, or loopback when
+//! that list is empty). Anyone else gets their socket address, so a client
+//! talking to the app directly can't spoof past per-IP rate limits.
+
+use std::net::{IpAddr, SocketAddr};
+
+use axum::extract::ConnectInfo;
+use axum::http::{HeaderMap, Request};
+use ipnet::IpNet;
+
+/// Whether `peer` may set proxy headers.
+#[must_use]
+pub fn is_trusted_proxy(peer: IpAddr, trusted: &[IpNet]) -> bool {
+    let peer = peer.to_canonical();
+    if trusted.is_empty() {
+        peer.is_loopback()
+    } else {
+        trusted.iter().any(|net| net.contains(&peer))
+    }
+}
+
+/// Resolve the client address for a connection from `peer`.
+///
+/// When `peer` is trusted, the forwarding chain is walked from the nearest
+/// hop outwards, skipping trusted proxies, and the first other address wins.
+/// A malformed entry stops the walk at the last address that could be
+/// verified.
+#[must_use]
+pub fn client_ip(peer: IpAddr, headers: &HeaderMap, trusted: &[IpNet]) -> IpAddr {
+    let peer = peer.to_canonical();
+    if !is_trusted_proxy(peer, trusted) {
+        return peer;
+    }
+    let Some(chain) = forwarding_chain(headers) else {
+        return peer;
+    };
+
+    let mut nearest = peer;
+    for hop in chain.iter().rev() {
+        let Some(ip) = *hop else {
+            break;
+        };
+        nearest = ip;
+        if !is_trusted_proxy(ip, trusted) {
+            break;
+        }
+    }
+    nearest
+}
+
+/// Client address for a request, using its `ConnectInfo` peer.
+///
+/// Returns `None` when the request carries no connection info.
+pub fn request_client_ip<B>(req: &Request<B>, trusted: &[IpNet]) -> Option<IpAddr> {
+    let peer = req.extensions().get::<ConnectInfo<SocketAddr>>()?.0.ip();
+    Some(client_ip(peer, req.headers(), trusted))
+}
+
+/// Addresses from the first proxy header present, client first.
+///
+/// Unparseable entries (`unknown`, obfuscated identifiers, garbage) are `None`.
+fn forwarding_chain(headers: &HeaderMap) -> Option<Vec<Option<IpAddr>>> {
+    if let Some(value) = joined_header(headers, "forwarded") {
+        return Some(
+            value
+                .split(',')
+                .map(|elem| forwarded_for(elem).and_then(parse_ip))
+                .collect(),
+        );
+    }
+    if let Some(value) = joined_header(headers, "x-forwarded-for") {
+        return Some(value.split(',').map(parse_ip).collect());
+    }
+    joined_header(headers, "x-real-ip").map(|value| vec![parse_ip(&value)])
+}
+
+/// All values of a header joined with commas, or `None` if absent or empty.
+fn joined_header(headers: &HeaderMap, name: &str) -> Option<String> {
+    let values: Vec<&str> = headers
+        .get_all(name)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .collect();
+    (!values.is_empty()).then(|| values.join(","))
+}
+
+/// The `for=` value of one `Forwarded` element.
+///
+/// `Forwarded: for=1.2.3.4;proto=https;by=...`
+/// `Forwarded: for="[2001:db8::1]:1234";proto=https`
+fn forwarded_for(element: &str) -> Option<&str> {
+    element.split(';').find_map(|part| {
+        let (key, value) = part.trim().split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case("for")
+            .then_some(value.trim())
+    })
+}
+
+fn parse_ip(value: &str) -> Option<IpAddr> {
+    strip_port_and_brackets(value)
+        .parse::<IpAddr>()
+        .ok()
+        .map(|ip| ip.to_canonical())
+}
+
+fn strip_port_and_brackets(s: &str) -> String {
+    let mut v = s.trim().trim_matches('"').to_string();
+    if v.starts_with('[') {
+        if let Some(end) = v.find(']') {
+            v = v[1..end].to_string();
+            return v;
+        }
+    }
+
+    // IPv4:port or hostname:port => strip port.
+    // For raw IPv6 without brackets, we leave it as-is.
+    if let Some((host, port)) = v.rsplit_once(':') {
+        if !host.contains(':') && port.chars().all(|c| c.is_ascii_digit()) {
+            return host.to_string();
+        }
+    }
+
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use axum::http::HeaderValue;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    fn nets(list: &[&str]) -> Vec<IpNet> {
+        list.iter().map(|n| n.parse().unwrap()).collect()
+    }
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.append(*name, HeaderValue::from_str(value).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn test_untrusted_peer_uses_socket_address() {
+        let spoofed = headers(&[
+            ("x-forwarded-for", "1.2.3.4"),
+            ("forwarded", "for=5.6.7.8"),
+            ("x-real-ip", "9.9.9.9"),
+        ]);
+        let peer = ip("203.0.113.7");
+
+        assert_eq!(client_ip(peer, &spoofed, &[]), peer);
+        assert_eq!(client_ip(peer, &spoofed, &nets(&["10.0.0.0/8"])), peer);
+    }
+
+    #[test]
+    fn test_empty_list_trusts_loopback_only() {
+        let h = headers(&[("x-forwarded-for", "198.51.100.20")]);
+
+        assert_eq!(client_ip(ip("127.0.0.1"), &h, &[]), ip("198.51.100.20"));
+        assert_eq!(client_ip(ip("::1"), &h, &[]), ip("198.51.100.20"));
+        // A configured list replaces the loopback default
+        assert_eq!(
+            client_ip(ip("127.0.0.1"), &h, &nets(&["10.0.0.0/8"])),
+            ip("127.0.0.1")
+        );
+    }
+
+    #[test]
+    fn test_trusted_peer_walks_chain_past_trusted_hops() {
+        let trusted = nets(&["10.0.0.0/8"]);
+        // The client prepended a fake address; the proxies appended the real ones
+        let h = headers(&[("x-forwarded-for", "6.6.6.6, 198.51.100.20, 10.0.0.3")]);
+
+        assert_eq!(client_ip(ip("10.0.0.2"), &h, &trusted), ip("198.51.100.20"));
+
+        // Every hop trusted: the outermost one is the client
+        let h = headers(&[("x-forwarded-for", "10.1.1.1, 10.0.0.3")]);
+        assert_eq!(client_ip(ip("10.0.0.2"), &h, &trusted), ip("10.1.1.1"));
+
+        // Repeated headers form one chain
+        let h = headers(&[
+            ("x-forwarded-for", "198.51.100.20"),
+            ("x-forwarded-for", "10.0.0.3"),
+        ]);
+        assert_eq!(client_ip(ip("10.0.0.2"), &h, &trusted), ip("198.51.100.20"));
+    }
+
+    #[test]
+    fn test_forwarded_header_preferred() {
+        let h = headers(&[
+            (
+                "forwarded",
+                "for=\"[2001:db8::1]:4711\";proto=https, For=10.0.0.3",
+            ),
+            ("x-forwarded-for", "1.2.3.4"),
+        ]);
+        assert_eq!(
+            client_ip(ip("10.0.0.2"), &h, &nets(&["10.0.0.0/8"])),
+            ip("2001:db8::1")
+        );
+
+        let h = headers(&[("x-real-ip", "198.51.100.20:1234")]);
+        assert_eq!(client_ip(ip("127.0.0.1"), &h, &[]), ip("198.51.100.20"));
+    }
+
+    #[test]
+    fn test_malformed_headers_stop_at_last_verified_hop() {
+        let trusted = nets(&["10.0.0.0/8"]);
+        let peer = ip("10.0.0.2");
+
+        for value in ["not-an-ip", "", " , ", "unknown"] {
+            let h = headers(&[("x-forwarded-for", value)]);
+            assert_eq!(client_ip(peer, &h, &trusted), peer, "{value:?}");
+        }
+
+        let h = headers(&[("x-forwarded-for", "198.51.100.20, garbage, 10.0.0.3")]);
+        assert_eq!(client_ip(peer, &h, &trusted), ip("10.0.0.3"));
+
+        let h = headers(&[("forwarded", "for=_hidden;proto=https")]);
+        assert_eq!(client_ip(peer, &h, &trusted), peer);
+    }
+
+    #[test]
+    fn test_ipv4_mapped_peer_matches_ipv4_ranges() {
+        let h = headers(&[("x-forwarded-for", "198.51.100.20")]);
+        assert_eq!(
+            client_ip(ip("::ffff:10.0.0.2"), &h, &nets(&["10.0.0.0/8"])),
+            ip("198.51.100.20")
+        );
+        assert_eq!(
+            client_ip(ip("::ffff:203.0.113.7"), &h, &[]),
+            ip("203.0.113.7")
+        );
+    }
+}
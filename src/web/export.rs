@@ -7,7 +7,7 @@
 //!
 //! - **Rate Limiting**: 1 export per hour per IP address to prevent abuse
 //! - **Size Limits**: 2GB maximum export size, excludes videos >50MB
-//! - **Streaming**: Uses `spawn_blocking` for CPU-intensive ZIP generation
+//! - **Streaming**: Artifacts are downloaded concurrently and the ZIP is streamed to the client as it is built
 //! - **Comprehensive Metadata**: JSON manifest with archive details, artifact info, and skip reasons
 //! - **Smart Filtering**: Only includes completed archives, documents all exclusions
 //!
@@ -51,19 +51,26 @@
 //!
 //! # Implementation Notes
 //!
-//! - Uses `tokio::task::spawn_blocking` to avoid blocking the async runtime during ZIP creation
-//! - Downloads files from S3 within the blocking task using `block_on`
+//! - Downloads up to [`DOWNLOAD_CONCURRENCY`] artifacts from S3 at once; each is
+//!   written to the ZIP as soon as it arrives, so entry order follows download order
+//! - ZIP compression runs in `tokio::task::spawn_blocking`, and each finished entry
+//!   is sent to the client immediately instead of buffering the whole archive
+//! - Size limits are applied up front from the recorded artifact sizes
 //! - Tracks export statistics in the database for analytics and rate limiting
 //! - Gracefully handles S3 download failures by documenting them in metadata
 
 use anyhow::{Context, Result};
+use axum::body::Body;
 use axum::extract::{ConnectInfo, Path, State};
 use axum::http::{header, StatusCode};
 use axum::response::{IntoResponse, Response};
 use chrono::Utc;
-use serde_json::json;
-use std::io::Cursor;
+use serde_json::{json, Value};
+use std::future::Future;
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::net::SocketAddr;
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
 use tracing::{error, info, warn};
 use zip::write::SimpleFileOptions;
 use zip::ZipWriter;
@@ -71,12 +78,43 @@ use zip::ZipWriter;
 use super::AppState;
 use crate::db::{
     count_exports_from_ip_last_hour, get_archives_with_artifacts_for_domain, insert_export,
+    Archive, ArchiveArtifact, Link,
 };
 
 const MAX_EXPORT_SIZE_BYTES: i64 = 2 * 1024 * 1024 * 1024; // 2 GB
 const MAX_VIDEO_SIZE_BYTES: i64 = 50 * 1024 * 1024; // 50 MB
 const EXPORTS_PER_HOUR: i64 = 1;
 
+/// Maximum number of artifacts downloaded from S3 at once.
+const DOWNLOAD_CONCURRENCY: usize = 8;
+
+/// Number of finished ZIP chunks buffered ahead of a slow client.
+const STREAM_BUFFER_CHUNKS: usize = 4;
+
+/// A downloaded artifact (or the reason it couldn't be downloaded).
+type Downloaded = (ExportFile, Result<Vec<u8>>);
+
+/// An artifact selected for inclusion in the export.
+struct ExportFile {
+    /// Index of the owning archive in the manifest.
+    archive_index: usize,
+    /// Index of this artifact's entry in the archive's `artifacts` array.
+    entry_index: usize,
+    s3_key: String,
+    /// Encoding the object is stored with; undone before it goes in the ZIP.
+    content_encoding: Option<String>,
+    zip_path: String,
+    size: i64,
+    /// Manifest entry recorded once the file is in the ZIP.
+    entry: Value,
+}
+
+/// Manifest entries plus the files that still need downloading.
+struct ExportPlan {
+    archives: Vec<Value>,
+    files: Vec<ExportFile>,
+}
+
 /// Handler for bulk export route (GET /export/{site}).
 ///
 /// Creates a ZIP archive containing all archives for a specific site,
@@ -86,15 +124,17 @@ const EXPORTS_PER_HOUR: i64 = 1;
 pub async fn export_site(
     State(state): State<AppState>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: axum::http::HeaderMap,
     Path(site): Path<String>,
 ) -> Response {
-    let client_ip = addr.ip().to_string();
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
 
     // Check if exports are enabled (check config if we add a setting)
     // For now, exports are always enabled
 
     // Rate limit check
-    match count_exports_from_ip_last_hour(state.db.pool(), &client_ip).await {
+    match count_exports_from_ip_last_hour(state.db.read_pool(), &client_ip).await {
         Ok(count) => {
             if count >= EXPORTS_PER_HOUR {
                 warn!(
@@ -117,7 +157,7 @@ pub async fn export_site(
 
     // Fetch archives with artifacts for the domain
     let archives_with_artifacts =
-        match get_archives_with_artifacts_for_domain(state.db.pool(), &site).await {
+        match get_archives_with_artifacts_for_domain(state.db.read_pool(), &site).await {
             Ok(data) => data,
             Err(e) => {
                 error!(error = ?e, site = %site, "Failed to fetch archives for export");
@@ -137,229 +177,671 @@ pub async fn export_site(
             .into_response();
     }
 
-    // Generate ZIP file in memory (using spawn_blocking for CPU-intensive work)
-    match generate_export_zip(&state, &site, archives_with_artifacts).await {
-        Ok((zip_bytes, archive_count, total_size)) => {
-            // Record the export
-            if let Err(e) = insert_export(
-                state.db.pool(),
-                &site,
-                &client_ip,
-                archive_count,
-                total_size,
-            )
-            .await
-            {
-                error!(error = ?e, "Failed to record export");
-                // Don't fail the export if we can't record it
-            }
+    let plan = plan_export(&site, archives_with_artifacts);
 
-            info!(
-                client_ip = %client_ip,
-                site = %site,
-                archive_count = archive_count,
-                total_size_mb = total_size / (1024 * 1024),
-                "Export completed"
-            );
+    // Downloads feed the ZIP writer, which feeds the response body
+    let (file_tx, file_rx) = mpsc::channel(DOWNLOAD_CONCURRENCY);
+    let (chunk_tx, mut chunk_rx) = mpsc::channel(STREAM_BUFFER_CHUNKS);
 
-            // Return ZIP as download
-            let filename = format!("{site}-archives.zip");
-            (
-                StatusCode::OK,
-                [
-                    (header::CONTENT_TYPE, "application/zip"),
-                    (
-                        header::CONTENT_DISPOSITION,
-                        &format!("attachment; filename=\"{filename}\""),
-                    ),
-                    (header::CONTENT_LENGTH, &zip_bytes.len().to_string()),
-                ],
-                zip_bytes,
-            )
-                .into_response()
+    let s3 = state.s3.clone();
+    tokio::spawn(download_files(
+        plan.files,
+        DOWNLOAD_CONCURRENCY,
+        move |key: String| {
+            let s3 = s3.clone();
+            async move { s3.download_file(&key).await.map(|(bytes, _)| bytes) }
+        },
+        file_tx,
+    ));
+
+    let site_owned = site.clone();
+    let archives = plan.archives;
+    let zip_task = tokio::task::spawn_blocking(move || {
+        let sink = ChunkSink::new(chunk_tx.clone());
+        let result = write_export_zip(sink, &site_owned, archives, file_rx);
+        if let Err(ref e) = result {
+            // Abort the response so the client doesn't receive a truncated ZIP as complete
+            let _ = chunk_tx.blocking_send(Err(std::io::Error::other(e.to_string())));
         }
-        Err(e) => {
-            error!(error = ?e, site = %site, "Failed to generate export ZIP");
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to generate export",
-            )
-                .into_response()
+        result
+    });
+
+    let db = state.db.clone();
+    let export_site = site.clone();
+    tokio::spawn(async move {
+        match zip_task.await {
+            Ok(Ok((_, archive_count, total_size))) => {
+                // Record the export
+                if let Err(e) = insert_export(
+                    db.pool(),
+                    &export_site,
+                    &client_ip,
+                    archive_count,
+                    total_size,
+                )
+                .await
+                {
+                    error!(error = ?e, "Failed to record export");
+                    // Don't fail the export if we can't record it
+                }
+
+                info!(
+                    client_ip = %client_ip,
+                    site = %export_site,
+                    archive_count = archive_count,
+                    total_size_mb = total_size / (1024 * 1024),
+                    "Export completed"
+                );
+            }
+            Ok(Err(e)) => error!(error = ?e, site = %export_site, "Failed to generate export ZIP"),
+            Err(e) => error!(error = ?e, site = %export_site, "ZIP generation task panicked"),
         }
-    }
+    });
+
+    let stream = futures_util::stream::poll_fn(move |cx| chunk_rx.poll_recv(cx));
+
+    // Return ZIP as download
+    let filename = format!("{site}-archives.zip");
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/zip"),
+            (
+                header::CONTENT_DISPOSITION,
+                &format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response()
 }
 
-/// Generate export ZIP file containing archives and metadata.
+/// Build the manifest and decide which artifacts to download.
 ///
-/// Returns (zip_bytes, archive_count, total_size_bytes).
-async fn generate_export_zip(
-    state: &AppState,
+/// Large videos and files beyond the total size limit are recorded as skipped.
+/// Selected files get a placeholder entry that is replaced once the download
+/// finishes, so the manifest keeps the original artifact order.
+fn plan_export(
     site: &str,
-    archives_with_artifacts: Vec<(
-        crate::db::Archive,
-        crate::db::Link,
-        Vec<crate::db::ArchiveArtifact>,
-    )>,
-) -> Result<(Vec<u8>, i64, i64)> {
-    let s3 = state.s3.clone();
-    let site_owned = site.to_string();
-
-    // Spawn blocking task for ZIP creation (CPU-intensive)
-    tokio::task::spawn_blocking(move || {
-        let mut zip_buffer = Vec::new();
-        let cursor = Cursor::new(&mut zip_buffer);
-        let mut zip = ZipWriter::new(cursor);
-        let options =
-            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
-
-        let mut metadata = Vec::new();
-        let mut total_size = 0i64;
-        let mut included_count = 0i64;
-        let mut current_export_size = 0i64;
-
-        for (archive, link, artifacts) in archives_with_artifacts {
-            // Build metadata for this archive
-            let mut archive_metadata = json!({
-                "archive_id": archive.id,
-                "url": link.original_url,
-                "normalized_url": link.normalized_url,
-                "domain": link.domain,
-                "title": archive.content_title,
-                "author": archive.content_author,
-                "content_type": archive.content_type,
-                "archived_at": archive.archived_at,
-                "is_nsfw": archive.is_nsfw,
-                "wayback_url": archive.wayback_url,
-                "archive_today_url": archive.archive_today_url,
-                "ipfs_cid": archive.ipfs_cid,
-                "artifacts": []
-            });
+    archives_with_artifacts: Vec<(Archive, Link, Vec<ArchiveArtifact>)>,
+) -> ExportPlan {
+    let mut archives = Vec::new();
+    let mut files = Vec::new();
+    let mut current_export_size = 0i64;
 
-            let archive_artifacts = archive_metadata["artifacts"].as_array_mut().unwrap();
+    for (archive_index, (archive, link, artifacts)) in
+        archives_with_artifacts.into_iter().enumerate()
+    {
+        let mut entries = Vec::new();
 
-            // Process artifacts
-            for artifact in artifacts {
-                // Skip internal marker artifacts (no real S3 file)
-                if artifact.is_internal_marker() {
-                    continue;
-                }
+        for artifact in artifacts {
+            // Skip internal marker artifacts (no real S3 file)
+            if artifact.is_internal_marker() {
+                continue;
+            }
 
-                let size = artifact.size_bytes.unwrap_or(0);
-
-                // Skip large video files
-                if artifact.kind == "video" && size > MAX_VIDEO_SIZE_BYTES {
-                    warn!(
-                        archive_id = archive.id,
-                        size_mb = size / (1024 * 1024),
-                        "Skipping large video file"
-                    );
-                    archive_artifacts.push(json!({
-                        "kind": artifact.kind,
-                        "filename": extract_filename(&artifact.s3_key),
-                        "size_bytes": size,
-                        "skipped": true,
-                        "reason": "File too large (>50MB)"
-                    }));
-                    continue;
-                }
+            let size = artifact.size_bytes.unwrap_or(0);
+            let filename = extract_filename(&artifact.s3_key);
 
-                // Check if adding this file would exceed export size limit
-                if current_export_size + size > MAX_EXPORT_SIZE_BYTES {
-                    warn!(
-                        archive_id = archive.id,
-                        current_size_mb = current_export_size / (1024 * 1024),
-                        "Export size limit reached, stopping"
-                    );
-                    archive_artifacts.push(json!({
-                        "kind": artifact.kind,
-                        "filename": extract_filename(&artifact.s3_key),
-                        "size_bytes": size,
-                        "skipped": true,
-                        "reason": "Export size limit reached (2GB)"
-                    }));
-                    continue;
-                }
-
-                // Download file from S3 (blocking operation)
-                let file_data = match tokio::runtime::Handle::current()
-                    .block_on(s3.download_file(&artifact.s3_key))
-                {
-                    Ok((bytes, _content_type)) => bytes,
-                    Err(e) => {
-                        warn!(
-                            error = ?e,
-                            s3_key = %artifact.s3_key,
-                            "Failed to download artifact from S3"
-                        );
-                        archive_artifacts.push(json!({
-                            "kind": artifact.kind,
-                            "filename": extract_filename(&artifact.s3_key),
-                            "size_bytes": size,
-                            "skipped": true,
-                            "reason": format!("Download failed: {e}")
-                        }));
-                        continue;
-                    }
-                };
-
-                // Add file to ZIP
-                let zip_path = format!(
-                    "{}/archive-{}/{}",
-                    site_owned,
-                    archive.id,
-                    extract_filename(&artifact.s3_key)
+            // Skip large video files
+            if artifact.kind == "video" && size > MAX_VIDEO_SIZE_BYTES {
+                warn!(
+                    archive_id = archive.id,
+                    size_mb = size / (1024 * 1024),
+                    "Skipping large video file"
                 );
+                entries.push(skipped_entry(
+                    &artifact.kind,
+                    &filename,
+                    size,
+                    "File too large (>50MB)",
+                ));
+                continue;
+            }
 
-                zip.start_file(&zip_path, options)
-                    .context("Failed to start ZIP entry")?;
-                std::io::Write::write_all(&mut zip, &file_data)
-                    .context("Failed to write file data to ZIP")?;
-
-                current_export_size += size;
-                total_size += size;
+            // Check if adding this file would exceed export size limit
+            if current_export_size + size > MAX_EXPORT_SIZE_BYTES {
+                warn!(
+                    archive_id = archive.id,
+                    current_size_mb = current_export_size / (1024 * 1024),
+                    "Export size limit reached, stopping"
+                );
+                entries.push(skipped_entry(
+                    &artifact.kind,
+                    &filename,
+                    size,
+                    "Export size limit reached (2GB)",
+                ));
+                continue;
+            }
+            current_export_size += size;
 
-                archive_artifacts.push(json!({
+            let zip_path = format!("{site}/archive-{}/{filename}", archive.id);
+            entries.push(skipped_entry(
+                &artifact.kind,
+                &filename,
+                size,
+                "Download did not complete",
+            ));
+            files.push(ExportFile {
+                archive_index,
+                entry_index: entries.len() - 1,
+                s3_key: artifact.s3_key,
+                content_encoding: artifact.content_encoding,
+                entry: json!({
                     "kind": artifact.kind,
-                    "filename": extract_filename(&artifact.s3_key),
+                    "filename": filename,
                     "size_bytes": size,
                     "content_type": artifact.content_type,
                     "sha256": artifact.sha256,
                     "zip_path": zip_path
-                }));
+                }),
+                zip_path,
+                size,
+            });
+        }
+
+        archives.push(json!({
+            "archive_id": archive.id,
+            "url": link.original_url,
+            "normalized_url": link.normalized_url,
+            "domain": link.domain,
+            "title": archive.content_title,
+            "author": archive.content_author,
+            "content_type": archive.content_type,
+            "archived_at": archive.archived_at,
+            "is_nsfw": archive.is_nsfw,
+            "wayback_url": archive.wayback_url,
+            "archive_today_url": archive.archive_today_url,
+            "ipfs_cid": archive.ipfs_cid,
+            "artifacts": entries
+        }));
+    }
+
+    ExportPlan { archives, files }
+}
+
+/// Manifest entry for an artifact left out of the ZIP.
+fn skipped_entry(kind: &str, filename: &str, size: i64, reason: &str) -> Value {
+    json!({
+        "kind": kind,
+        "filename": filename,
+        "size_bytes": size,
+        "skipped": true,
+        "reason": reason
+    })
+}
+
+/// Download files with at most `concurrency` requests in flight.
+///
+/// Each result is sent as soon as its download finishes. Stops early if the
+/// receiver goes away (e.g. the client disconnected).
+async fn download_files<F, Fut>(
+    files: Vec<ExportFile>,
+    concurrency: usize,
+    fetch: F,
+    tx: mpsc::Sender<Downloaded>,
+) where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<Vec<u8>>> + Send + 'static,
+{
+    let mut in_flight = JoinSet::new();
+
+    for file in files {
+        if in_flight.len() >= concurrency.max(1) {
+            if let Some(done) = in_flight.join_next().await {
+                if !send_download(&tx, done).await {
+                    return;
+                }
             }
+        }
+        let download = fetch(file.s3_key.clone());
+        in_flight.spawn(async move { (file, download.await) });
+    }
 
-            metadata.push(archive_metadata);
-            included_count += 1;
+    while let Some(done) = in_flight.join_next().await {
+        if !send_download(&tx, done).await {
+            return;
         }
+    }
+}
 
-        // Add metadata.json to ZIP
-        let manifest = json!({
-            "export_metadata": {
-                "site": site_owned,
-                "archive_count": included_count,
-                "total_size_bytes": total_size,
-                "max_video_size_bytes": MAX_VIDEO_SIZE_BYTES,
-                "exported_at": Utc::now().to_rfc3339()
-            },
-            "archives": metadata
-        });
+/// Forward a finished download, returning false once the receiver is gone.
+async fn send_download(
+    tx: &mpsc::Sender<Downloaded>,
+    done: Result<Downloaded, tokio::task::JoinError>,
+) -> bool {
+    match done {
+        Ok(downloaded) => tx.send(downloaded).await.is_ok(),
+        Err(e) => {
+            // The manifest keeps the placeholder "did not complete" entry
+            error!(error = ?e, "Export download task panicked");
+            true
+        }
+    }
+}
 
-        let manifest_json = serde_json::to_string_pretty(&manifest)?;
-        zip.start_file("metadata.json", options)
-            .context("Failed to start metadata entry")?;
-        std::io::Write::write_all(&mut zip, manifest_json.as_bytes())
-            .context("Failed to write metadata JSON")?;
+/// Write downloaded files and the manifest into a ZIP.
+///
+/// Blocks on `files`, so run it inside `spawn_blocking`. Returns the writer
+/// along with (archive_count, total_size_bytes).
+fn write_export_zip<W: Read + Write + Seek>(
+    writer: W,
+    site: &str,
+    mut archives: Vec<Value>,
+    mut files: mpsc::Receiver<Downloaded>,
+) -> Result<(W, i64, i64)> {
+    let mut zip = ZipWriter::new(writer);
+    // Hand each finished entry to the writer so it can be streamed out
+    zip.set_flush_on_finish_file(true);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
 
-        // Finalize ZIP
-        zip.finish().context("Failed to finish ZIP file")?;
+    let mut total_size = 0i64;
 
-        Ok((zip_buffer, included_count, total_size))
-    })
-    .await
-    .context("ZIP generation task panicked")?
+    while let Some((file, downloaded)) = files.blocking_recv() {
+        let downloaded = downloaded
+            .and_then(|data| crate::s3::decode_stored(data, file.content_encoding.as_deref()));
+        let entry = match downloaded {
+            Ok(file_data) => {
+                zip.start_file(&file.zip_path, options)
+                    .context("Failed to start ZIP entry")?;
+                zip.write_all(&file_data)
+                    .context("Failed to write file data to ZIP")?;
+                total_size += file.size;
+                file.entry
+            }
+            Err(e) => {
+                warn!(
+                    error = ?e,
+                    s3_key = %file.s3_key,
+                    "Failed to download artifact from S3"
+                );
+                skipped_entry(
+                    file.entry["kind"].as_str().unwrap_or_default(),
+                    file.entry["filename"].as_str().unwrap_or_default(),
+                    file.size,
+                    &format!("Download failed: {e}"),
+                )
+            }
+        };
+        if let Some(slot) = archives
+            .get_mut(file.archive_index)
+            .and_then(|a| a["artifacts"].get_mut(file.entry_index))
+        {
+            *slot = entry;
+        }
+    }
+
+    let archive_count = i64::try_from(archives.len()).unwrap_or(i64::MAX);
+
+    // Add metadata.json to ZIP
+    let manifest = json!({
+        "export_metadata": {
+            "site": site,
+            "archive_count": archive_count,
+            "total_size_bytes": total_size,
+            "max_video_size_bytes": MAX_VIDEO_SIZE_BYTES,
+            "exported_at": Utc::now().to_rfc3339()
+        },
+        "archives": archives
+    });
+
+    let manifest_json = serde_json::to_string_pretty(&manifest)?;
+    zip.start_file("metadata.json", options)
+        .context("Failed to start metadata entry")?;
+    zip.write_all(manifest_json.as_bytes())
+        .context("Failed to write metadata JSON")?;
+
+    // Finalize ZIP
+    let mut writer = zip.finish().context("Failed to finish ZIP file")?;
+    writer.flush().context("Failed to flush ZIP")?;
+
+    Ok((writer, archive_count, total_size))
+}
+
+/// `Write + Seek` sink that sends flushed bytes to the HTTP response.
+///
+/// `ZipWriter` seeks back to patch each entry's header after writing its body,
+/// so bytes are held until the next flush (at the end of every entry with
+/// `set_flush_on_finish_file`). Seeking into bytes that were already sent fails.
+/// Reading is unsupported; `ZipWriter` only needs it to copy existing entries.
+struct ChunkSink {
+    tx: mpsc::Sender<std::io::Result<Vec<u8>>>,
+    /// Bytes already sent to the client.
+    sent: u64,
+    /// Bytes written since the last flush.
+    buf: Vec<u8>,
+    /// Absolute write position.
+    pos: u64,
+}
+
+impl ChunkSink {
+    const fn new(tx: mpsc::Sender<std::io::Result<Vec<u8>>>) -> Self {
+        Self {
+            tx,
+            sent: 0,
+            buf: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+impl Write for ChunkSink {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        let offset = usize::try_from(self.pos - self.sent).map_err(std::io::Error::other)?;
+        let end = offset + data.len();
+        if end > self.buf.len() {
+            self.buf.resize(end, 0);
+        }
+        self.buf[offset..end].copy_from_slice(data);
+        self.pos += data.len() as u64;
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+        let chunk = std::mem::take(&mut self.buf);
+        self.sent += chunk.len() as u64;
+        self.pos = self.sent;
+        self.tx
+            .blocking_send(Ok(chunk))
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::BrokenPipe, "client disconnected"))
+    }
+}
+
+impl Read for ChunkSink {
+    fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "export stream is write-only",
+        ))
+    }
+}
+
+impl Seek for ChunkSink {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let end = self.sent + self.buf.len() as u64;
+        let target = match pos {
+            SeekFrom::Start(p) => Some(p),
+            SeekFrom::End(delta) => end.checked_add_signed(delta),
+            SeekFrom::Current(delta) => self.pos.checked_add_signed(delta),
+        };
+        match target {
+            Some(p) if p >= self.sent => {
+                self.pos = p;
+                Ok(p)
+            }
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "cannot seek into bytes already streamed",
+            )),
+        }
+    }
 }
 
 /// Extract filename from S3 key.
 fn extract_filename(s3_key: &str) -> String {
     s3_key.rsplit('/').next().unwrap_or(s3_key).to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn archive(id: i64) -> Archive {
+        Archive {
+            id,
+            link_id: id,
+            status: "complete".to_string(),
+            archived_at: Some("2024-01-15 12:00:00".to_string()),
+            content_title: Some(format!("Archive {id}")),
+            content_author: None,
+            content_text: None,
+            content_type: Some("video".to_string()),
+            s3_key_primary: None,
+            s3_key_thumb: None,
+            s3_keys_extra: None,
+            wayback_url: None,
+            archive_today_url: None,
+            ipfs_cid: None,
+            error_message: None,
+            retry_count: 0,
+            created_at: "2024-01-15 12:00:00".to_string(),
+            is_nsfw: false,
+            nsfw_source: None,
+            next_retry_at: None,
+            last_attempt_at: None,
+            http_status_code: Some(200),
+            redirect_chain: None,
+            post_date: None,
+            quoted_archive_id: None,
+            reply_to_archive_id: None,
+            submitted_by_user_id: None,
+            progress_percent: None,
+            progress_details: None,
+            last_progress_update: None,
+            og_title: None,
+            og_description: None,
+            og_image: None,
+            og_type: None,
+            og_extracted_at: None,
+            og_extraction_attempted: false,
+            transcript_text: None,
+            full_text: None,
+            view_count: None,
+            like_count: None,
+            repost_count: None,
+            platform_comment_count: None,
+            save_count: None,
+            metrics_backfill_version: None,
+            short_code: None,
+            error_kind: None,
+            skip_reason: None,
+        }
+    }
+
+    fn link(id: i64) -> Link {
+        Link {
+            id,
+            original_url: format!("https://example.com/{id}"),
+            normalized_url: format!("https://example.com/{id}"),
+            canonical_url: None,
+            final_url: None,
+            domain: "example.com".to_string(),
+            first_seen_at: "2024-01-15 12:00:00".to_string(),
+            last_archived_at: None,
+        }
+    }
+
+    fn artifact(archive_id: i64, kind: &str, s3_key: &str, size: i64) -> ArchiveArtifact {
+        ArchiveArtifact {
+            id: 0,
+            archive_id,
+            kind: kind.to_string(),
+            s3_key: s3_key.to_string(),
+            content_type: None,
+            size_bytes: Some(size),
+            sha256: None,
+            created_at: "2024-01-15 12:00:00".to_string(),
+            perceptual_hash: None,
+            duplicate_of_artifact_id: None,
+            video_file_id: None,
+            metadata: None,
+            placeholder_color: None,
+            width: None,
+            height: None,
+            content_encoding: None,
+        }
+    }
+
+    fn sample_plan() -> ExportPlan {
+        plan_export(
+            "example.com",
+            vec![
+                (
+                    archive(1),
+                    link(1),
+                    vec![
+                        artifact(1, "video", "archives/1/media/video.mp4", 100),
+                        artifact(1, "screenshot", "archives/1/render/screenshot.webp", 10),
+                    ],
+                ),
+                (
+                    archive(2),
+                    link(2),
+                    vec![
+                        artifact(
+                            2,
+                            "video",
+                            "archives/2/media/huge.mp4",
+                            MAX_VIDEO_SIZE_BYTES + 1,
+                        ),
+                        artifact(2, "subtitle_backfill_attempted", "none", 0),
+                    ],
+                ),
+                (
+                    archive(3),
+                    link(3),
+                    vec![artifact(3, "raw_html", "archives/3/raw.html", 5)],
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_plan_export_skips_large_videos_and_markers() {
+        let plan = sample_plan();
+        assert_eq!(plan.archives.len(), 3);
+        assert_eq!(
+            plan.files
+                .iter()
+                .map(|f| f.s3_key.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "archives/1/media/video.mp4",
+                "archives/1/render/screenshot.webp",
+                "archives/3/raw.html"
+            ]
+        );
+        let skipped = &plan.archives[1]["artifacts"];
+        assert_eq!(skipped.as_array().unwrap().len(), 1);
+        assert_eq!(skipped[0]["reason"], "File too large (>50MB)");
+    }
+
+    #[tokio::test]
+    async fn test_download_files_respects_concurrency_bound() {
+        let files = (0..20)
+            .map(|i| ExportFile {
+                archive_index: 0,
+                entry_index: i,
+                s3_key: format!("key-{i}"),
+                content_encoding: None,
+                zip_path: format!("site/archive-1/file-{i}"),
+                size: 1,
+                entry: json!({}),
+            })
+            .collect();
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_seen = Arc::new(AtomicUsize::new(0));
+        let (tx, mut rx) = mpsc::channel(4);
+        let fetch = {
+            let in_flight = Arc::clone(&in_flight);
+            let max_seen = Arc::clone(&max_seen);
+            move |key: String| {
+                let in_flight = Arc::clone(&in_flight);
+                let max_seen = Arc::clone(&max_seen);
+                async move {
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_seen.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    Ok(key.into_bytes())
+                }
+            }
+        };
+
+        let downloader = tokio::spawn(download_files(files, 3, fetch, tx));
+        let mut received = Vec::new();
+        while let Some((file, data)) = rx.recv().await {
+            assert_eq!(data.unwrap(), file.s3_key.as_bytes());
+            received.push(file.entry_index);
+        }
+        downloader.await.unwrap();
+
+        received.sort_unstable();
+        assert_eq!(received, (0..20).collect::<Vec<_>>());
+        assert_eq!(max_seen.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_streamed_zip_manifest_lists_every_archive() {
+        let plan = sample_plan();
+        let (file_tx, file_rx) = mpsc::channel(8);
+        let (chunk_tx, mut chunk_rx) = mpsc::channel(STREAM_BUFFER_CHUNKS);
+
+        // Deliver out of order, with one failed download
+        let mut files = plan.files;
+        let failed = files.remove(1);
+        for file in files.into_iter().rev() {
+            let data = file.s3_key.clone().into_bytes();
+            file_tx.send((file, Ok(data))).await.unwrap();
+        }
+        file_tx
+            .send((failed, Err(anyhow::anyhow!("S3 unavailable"))))
+            .await
+            .unwrap();
+        drop(file_tx);
+
+        let archives = plan.archives;
+        let writer = tokio::task::spawn_blocking(move || {
+            write_export_zip(ChunkSink::new(chunk_tx), "example.com", archives, file_rx)
+                .map(|(_, count, size)| (count, size))
+        });
+        let mut bytes = Vec::new();
+        let mut chunks = 0;
+        while let Some(chunk) = chunk_rx.recv().await {
+            bytes.extend(chunk.unwrap());
+            chunks += 1;
+        }
+        let (count, size) = writer.await.unwrap().unwrap();
+        assert_eq!((count, size), (3, 105));
+        assert!(chunks > 1, "ZIP should be streamed in several chunks");
+
+        let mut zip = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let mut video = String::new();
+        zip.by_name("example.com/archive-1/video.mp4")
+            .unwrap()
+            .read_to_string(&mut video)
+            .unwrap();
+        assert_eq!(video, "archives/1/media/video.mp4");
+
+        let mut manifest = String::new();
+        zip.by_name("metadata.json")
+            .unwrap()
+            .read_to_string(&mut manifest)
+            .unwrap();
+        let manifest: Value = serde_json::from_str(&manifest).unwrap();
+        let ids: Vec<i64> = manifest["archives"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|a| a["archive_id"].as_i64().unwrap())
+            .collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert_eq!(manifest["export_metadata"]["archive_count"], 3);
+
+        let first = &manifest["archives"][0]["artifacts"];
+        assert_eq!(first[0]["zip_path"], "example.com/archive-1/video.mp4");
+        assert_eq!(first[1]["skipped"], true);
+        assert!(first[1]["reason"]
+            .as_str()
+            .unwrap()
+            .starts_with("Download failed"));
+    }
+}
@@ -1,7 +1,17 @@
 use crate::db::Archive;
+use crate::ipfs::gateway_urls_for;
+
+/// First gateway URL for an archive's IPFS pin, if it has one.
+fn ipfs_gateway_url(archive: &Archive, ipfs_gateways: &[String]) -> Option<String> {
+    let cid = archive.ipfs_cid.as_deref()?;
+    gateway_urls_for(ipfs_gateways, cid).into_iter().next()
+}
 
 /// Generate RSS 2.0 feed XML
-pub fn generate_rss(archives: &[Archive], base_url: &str) -> String {
+///
+/// Items pinned to IPFS carry an `atom:link rel="related"` to the first
+/// configured gateway.
+pub fn generate_rss(archives: &[Archive], base_url: &str, ipfs_gateways: &[String]) -> String {
     let items: String = archives
         .iter()
         .map(|archive| {
@@ -15,6 +25,14 @@ pub fn generate_rss(archives: &[Archive], base_url: &str) -> String {
             let description = xml_escape(archive.content_text.as_deref().unwrap_or(""));
             let pub_date = archive.archived_at.as_deref().unwrap_or("");
             let content_type = archive.content_type.as_deref().unwrap_or("unknown");
+            let ipfs_link = ipfs_gateway_url(archive, ipfs_gateways)
+                .map(|url| {
+                    format!(
+                        "\n      <atom:link href=\"{}\" rel=\"related\"/>",
+                        xml_escape(&url)
+                    )
+                })
+                .unwrap_or_default();
 
             format!(
                 r#"    <item>
@@ -23,7 +41,7 @@ pub fn generate_rss(archives: &[Archive], base_url: &str) -> String {
       <guid isPermaLink="true">{link}</guid>
       <description><![CDATA[{description}]]></description>
       <pubDate>{pub_date}</pubDate>
-      <category>{content_type}</category>
+      <category>{content_type}</category>{ipfs_link}
     </item>"#
             )
         })
@@ -46,7 +64,10 @@ pub fn generate_rss(archives: &[Archive], base_url: &str) -> String {
 }
 
 /// Generate Atom 1.0 feed XML
-pub fn generate_atom(archives: &[Archive], base_url: &str) -> String {
+///
+/// Entries pinned to IPFS carry a `rel="related"` link to the first
+/// configured gateway.
+pub fn generate_atom(archives: &[Archive], base_url: &str, ipfs_gateways: &[String]) -> String {
     let now = chrono::Utc::now().to_rfc3339();
 
     let entries: String = archives
@@ -63,11 +84,19 @@ pub fn generate_atom(archives: &[Archive], base_url: &str) -> String {
             let updated = archive.archived_at.as_deref().unwrap_or(&now);
             let author = xml_escape(archive.content_author.as_deref().unwrap_or("Unknown"));
             let content_type = archive.content_type.as_deref().unwrap_or("unknown");
+            let ipfs_link = ipfs_gateway_url(archive, ipfs_gateways)
+                .map(|url| {
+                    format!(
+                        "\n    <link href=\"{}\" rel=\"related\"/>",
+                        xml_escape(&url)
+                    )
+                })
+                .unwrap_or_default();
 
             format!(
                 r#"  <entry>
     <title>{title}</title>
-    <link href="{link}" rel="alternate" type="text/html"/>
+    <link href="{link}" rel="alternate" type="text/html"/>{ipfs_link}
     <id>{link}</id>
     <updated>{updated}</updated>
     <author><name>{author}</name></author>
@@ -108,7 +137,7 @@ mod tests {
 
     #[test]
     fn test_generate_rss_empty() {
-        let rss = generate_rss(&[], "https://example.com");
+        let rss = generate_rss(&[], "https://example.com", &[]);
         assert!(rss.contains("<?xml version="));
         assert!(rss.contains("<rss version=\"2.0\""));
         assert!(rss.contains("Discourse Link Archiver"));
@@ -116,12 +145,50 @@ mod tests {
 
     #[test]
     fn test_generate_atom_empty() {
-        let atom = generate_atom(&[], "https://example.com");
+        let atom = generate_atom(&[], "https://example.com", &[]);
         assert!(atom.contains("<?xml version="));
         assert!(atom.contains("<feed xmlns="));
         assert!(atom.contains("Discourse Link Archiver"));
     }
 
+    fn pinned_archive(cid: Option<&str>) -> Archive {
+        serde_json::from_value(serde_json::json!({
+            "id": 7,
+            "link_id": 1,
+            "status": "complete",
+            "retry_count": 0,
+            "created_at": "2024-01-15 12:00:00",
+            "is_nsfw": false,
+            "og_extraction_attempted": false,
+            "content_title": "Pinned",
+            "ipfs_cid": cid,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_feeds_link_first_ipfs_gateway() {
+        let gateways = vec![
+            "https://ipfs.io/ipfs/".to_string(),
+            "https://dweb.link/ipfs/".to_string(),
+        ];
+        let archives = [pinned_archive(Some("bafyexample"))];
+
+        let rss = generate_rss(&archives, "https://example.com", &gateways);
+        assert!(
+            rss.contains(r#"<atom:link href="https://ipfs.io/ipfs/bafyexample" rel="related"/>"#)
+        );
+        assert!(!rss.contains("dweb.link"));
+
+        let atom = generate_atom(&archives, "https://example.com", &gateways);
+        assert!(atom.contains(r#"<link href="https://ipfs.io/ipfs/bafyexample" rel="related"/>"#));
+
+        // No CID or no configured gateways: no IPFS link
+        let unpinned = [pinned_archive(None)];
+        assert!(!generate_rss(&unpinned, "https://example.com", &gateways).contains("ipfs"));
+        assert!(!generate_atom(&archives, "https://example.com", &[]).contains("rel=\"related\""));
+    }
+
     #[test]
     fn test_xml_escape() {
         assert_eq!(xml_escape("<script>"), "&lt;script&gt;");
@@ -1,21 +1,28 @@
 mod auth;
+mod bulk_import;
+mod client_ip;
 pub mod diff;
 pub mod export;
 mod feeds;
 pub mod pages;
 mod routes;
+mod sitemap;
 mod stats_cache;
 pub mod stream_command;
+mod thread_job_events;
+pub mod warc;
 
-// Re-export StatsCache for tests
+// Re-export caches for tests
+pub use sitemap::SitemapCache;
 pub use stats_cache::StatsCache;
 
 use std::net::SocketAddr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
-use axum::extract::ConnectInfo;
+use axum::extract::DefaultBodyLimit;
 use axum::extract::{FromRef, Host};
 use axum::handler::HandlerWithoutStateExt;
 use axum::http::header::HeaderValue;
@@ -24,13 +31,16 @@ use axum::http::Uri;
 use axum::middleware::Next;
 use axum::response::{Redirect, Response};
 use axum::Router;
+use axum_server::tls_rustls::RustlsConfig;
 use futures_util::StreamExt;
 use rustls_acme::AcmeState;
 use sqlx::SqlitePool;
 use tower_http::compression::CompressionLayer;
+use tower_http::limit::RequestBodyLimitLayer;
 use tower_http::services::ServeDir;
+use tower_http::timeout::TimeoutLayer;
 use tower_http::trace::TraceLayer;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use crate::config::Config;
 use crate::db::Database;
@@ -44,6 +54,7 @@ pub struct AppState {
     pub config: Arc<Config>,
     pub s3: Arc<S3Client>,
     pub stats_cache: Arc<stats_cache::StatsCache>,
+    pub sitemap_cache: Arc<sitemap::SitemapCache>,
 }
 
 // Implement FromRef for SqlitePool to enable auth extractors
@@ -56,7 +67,8 @@ impl FromRef<AppState> for SqlitePool {
 /// Start the web server.
 ///
 /// When TLS is enabled, this starts both an HTTP server (for redirects) and
-/// an HTTPS server with automatic Let's Encrypt certificate management.
+/// an HTTPS server using the configured certificate files or automatic
+/// Let's Encrypt certificate management.
 ///
 /// # Errors
 ///
@@ -69,10 +81,38 @@ pub async fn serve(config: Config, db: Database, s3: S3Client) -> Result<()> {
     }
 }
 
+/// Bind one listener per address.
+///
+/// When IPv4 and IPv6 addresses are mixed, IPv6 sockets are made IPv6-only so
+/// `::` can be bound next to `0.0.0.0`; a lone IPv6 address keeps the
+/// platform default (dual-stack on most Linux systems).
+fn bind_listeners(addrs: &[SocketAddr]) -> Result<Vec<std::net::TcpListener>> {
+    let v6_only = addrs.iter().any(SocketAddr::is_ipv4);
+    addrs
+        .iter()
+        .map(|addr| bind_listener(*addr, v6_only).with_context(|| format!("Failed to bind {addr}")))
+        .collect()
+}
+
+fn bind_listener(addr: SocketAddr, v6_only: bool) -> std::io::Result<std::net::TcpListener> {
+    use socket2::{Domain, Protocol, Socket, Type};
+
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+    if addr.is_ipv6() && v6_only {
+        socket.set_only_v6(true)?;
+    }
+    #[cfg(unix)]
+    socket.set_reuse_address(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(1024)?;
+    Ok(socket.into())
+}
+
 /// Serve HTTP only (no TLS).
 async fn serve_http_only(config: Config, db: Database, s3: S3Client) -> Result<()> {
-    let addr: SocketAddr = format!("{}:{}", config.web_host, config.web_port)
-        .parse()
+    let addrs = config
+        .bind_addrs(config.web_port)
         .context("Invalid web server address")?;
 
     let state = AppState {
@@ -80,70 +120,131 @@ async fn serve_http_only(config: Config, db: Database, s3: S3Client) -> Result<(
         config: Arc::new(config),
         s3: Arc::new(s3),
         stats_cache: Arc::new(stats_cache::StatsCache::default()),
+        sitemap_cache: Arc::new(sitemap::SitemapCache::default()),
     };
 
     let app = create_app(state);
-
-    info!(addr = %addr, "Starting HTTP web server");
-
-    let listener = tokio::net::TcpListener::bind(addr)
-        .await
-        .context("Failed to bind web server")?;
-
-    axum::serve(
-        listener,
-        app.into_make_service_with_connect_info::<SocketAddr>(),
-    )
-    .await
-    .context("Web server error")?;
+    let listeners = bind_listeners(&addrs).context("Failed to bind web server")?;
+
+    let servers = listeners.into_iter().zip(addrs).map(|(listener, addr)| {
+        info!(addr = %addr, "Starting HTTP web server");
+        let app = app.clone();
+        async move {
+            let listener = tokio::net::TcpListener::from_std(listener)
+                .context("Failed to register web server listener")?;
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .with_context(|| format!("Web server error on {addr}"))
+        }
+    });
+    futures_util::future::try_join_all(servers).await?;
 
     Ok(())
 }
 
-/// Serve with TLS using automatic Let's Encrypt certificates.
+/// Serve with TLS using certificate files if configured, otherwise automatic
+/// Let's Encrypt certificates.
 async fn serve_with_tls(config: Config, db: Database, s3: S3Client) -> Result<()> {
-    let http_addr: SocketAddr = format!("{}:{}", config.web_host, config.web_port)
-        .parse()
+    let http_addrs = config
+        .bind_addrs(config.web_port)
         .context("Invalid HTTP address")?;
-
-    let https_addr: SocketAddr = format!("{}:{}", config.web_host, config.tls_https_port)
-        .parse()
+    let https_addrs = config
+        .bind_addrs(config.tls_https_port)
         .context("Invalid HTTPS address")?;
 
     let https_port = config.tls_https_port;
+    let cert_files = config
+        .tls_cert_file
+        .clone()
+        .zip(config.tls_key_file.clone());
+
+    // Load static certificates up front so a bad file fails startup
+    let static_tls = match cert_files {
+        Some((cert_path, key_path)) => {
+            let server_config = tls::load_pem_files(&cert_path, &key_path)?;
+            Some((
+                RustlsConfig::from_config(Arc::new(server_config)),
+                cert_path,
+                key_path,
+            ))
+        }
+        None => None,
+    };
 
-    // Create ACME configuration for automatic certificate management
-    let acme_config = tls::create_acme_config(&config)?;
-    let mut acme_state = acme_config.state();
-    let acceptor = acme_state.axum_acceptor(acme_state.default_rustls_config());
-
+    let config = Arc::new(config);
     let state = AppState {
         db,
-        config: Arc::new(config),
+        config: Arc::clone(&config),
         s3: Arc::new(s3),
         stats_cache: Arc::new(stats_cache::StatsCache::default()),
+        sitemap_cache: Arc::new(sitemap::SitemapCache::default()),
     };
 
     let app = create_app(state);
+    let http_listeners =
+        bind_listeners(&http_addrs).context("Failed to bind HTTP redirect server")?;
+    let https_listeners = bind_listeners(&https_addrs).context("Failed to bind HTTPS server")?;
+
+    // Spawn HTTP servers for redirects to HTTPS
+    for (listener, addr) in http_listeners.into_iter().zip(http_addrs) {
+        tokio::spawn(async move {
+            if let Err(e) = serve_http_redirect(listener, addr, https_port).await {
+                error!("HTTP redirect server error: {e:#}");
+            }
+        });
+    }
+
+    if let Some((rustls_config, cert_path, key_path)) = static_tls {
+        tokio::spawn(tls::watch_pem_files(
+            rustls_config.clone(),
+            cert_path,
+            key_path,
+        ));
+
+        let servers = https_listeners
+            .into_iter()
+            .zip(https_addrs)
+            .map(|(listener, addr)| {
+                info!(addr = %addr, "Starting HTTPS web server with certificate files");
+                axum_server::from_tcp_rustls(listener, rustls_config.clone()).serve(
+                    app.clone()
+                        .into_make_service_with_connect_info::<SocketAddr>(),
+                )
+            });
+        futures_util::future::try_join_all(servers)
+            .await
+            .context("HTTPS server error")?;
+
+        return Ok(());
+    }
+
+    // Create ACME configuration for automatic certificate management
+    let acme_config = tls::create_acme_config(&config)?;
+    let mut acme_state = acme_config.state();
+    let acceptor = acme_state.axum_acceptor(acme_state.default_rustls_config());
 
     // Spawn task to log certificate events
     tokio::spawn(async move {
         log_acme_events(&mut acme_state).await;
     });
 
-    // Spawn HTTP server for redirects to HTTPS
-    tokio::spawn(async move {
-        if let Err(e) = serve_http_redirect(http_addr, https_port).await {
-            error!("HTTP redirect server error: {e:#}");
-        }
-    });
-
-    info!(addr = %https_addr, "Starting HTTPS web server with Let's Encrypt");
-
-    // Start HTTPS server with ACME acceptor
-    axum_server::bind(https_addr)
-        .acceptor(acceptor)
-        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+    // Start HTTPS servers with ACME acceptor
+    let servers = https_listeners
+        .into_iter()
+        .zip(https_addrs)
+        .map(|(listener, addr)| {
+            info!(addr = %addr, "Starting HTTPS web server with Let's Encrypt");
+            axum_server::from_tcp(listener)
+                .acceptor(acceptor.clone())
+                .serve(
+                    app.clone()
+                        .into_make_service_with_connect_info::<SocketAddr>(),
+                )
+        });
+    futures_util::future::try_join_all(servers)
         .await
         .context("HTTPS server error")?;
 
@@ -151,7 +252,11 @@ async fn serve_with_tls(config: Config, db: Database, s3: S3Client) -> Result<()
 }
 
 /// Serve HTTP redirect to HTTPS.
-async fn serve_http_redirect(addr: SocketAddr, https_port: u16) -> Result<()> {
+async fn serve_http_redirect(
+    listener: std::net::TcpListener,
+    addr: SocketAddr,
+    https_port: u16,
+) -> Result<()> {
     info!(addr = %addr, "Starting HTTP redirect server");
 
     let redirect = move |Host(host): Host, uri: Uri| async move {
@@ -175,9 +280,8 @@ async fn serve_http_redirect(addr: SocketAddr, https_port: u16) -> Result<()> {
         Redirect::permanent(&https_uri)
     };
 
-    let listener = tokio::net::TcpListener::bind(addr)
-        .await
-        .context("Failed to bind HTTP redirect server")?;
+    let listener = tokio::net::TcpListener::from_std(listener)
+        .context("Failed to register HTTP redirect listener")?;
 
     axum::serve(listener, redirect.into_make_service())
         .await
@@ -201,44 +305,83 @@ where
     }
 }
 
-/// Add X-No-Archive header to all responses to signal archiving prevention.
+/// Path prefixes whose responses are archived media or assets rather than our pages.
+const NO_ARCHIVE_EXEMPT_PREFIXES: [&str; 2] = ["/s3/", "/static/"];
+
+/// Add X-No-Archive header to page responses to signal archiving prevention.
+///
+/// Proxied artifacts and static assets are left untagged so caches in front of
+/// them don't treat the media itself as ours to keep out of archives.
 async fn add_no_archive_header(req: Request<axum::body::Body>, next: Next) -> Response {
+    let exempt = NO_ARCHIVE_EXEMPT_PREFIXES
+        .iter()
+        .any(|prefix| req.uri().path().starts_with(prefix));
     let mut response = next.run(req).await;
+    if !exempt {
+        response
+            .headers_mut()
+            .insert("X-No-Archive", HeaderValue::from_static("1"));
+    }
     response
-        .headers_mut()
-        .insert("X-No-Archive", HeaderValue::from_static("1"));
-    response
+}
+
+/// Apply the request timeout (408) and body size limit (413) to a router.
+///
+/// Only routes already added to `router` are affected, so large download
+/// routes and static files merged afterwards stay exempt.
+fn with_request_limits<S>(router: Router<S>, timeout_secs: u64, max_body_bytes: usize) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router
+        .layer(TimeoutLayer::new(Duration::from_secs(timeout_secs)))
+        .layer(RequestBodyLimitLayer::new(max_body_bytes))
+        .layer(DefaultBodyLimit::max(max_body_bytes))
 }
 
 /// Create the main application router.
 fn create_app(state: AppState) -> Router {
-    // Determine static files directory
-    let static_dir = find_static_dir();
-    info!(static_dir = ?static_dir, "Serving static files");
+    let static_files =
+        static_router(find_static_dir(state.config.static_dir.as_deref()).as_deref());
+
+    let trusted_proxies = state.config.trusted_proxies.clone();
+
+    let app_routes = with_request_limits(
+        routes::router(),
+        state.config.web_request_timeout_secs,
+        state.config.web_max_body_bytes,
+    );
 
     Router::new()
-        .merge(routes::router())
-        .nest_service("/static", ServeDir::new(&static_dir))
+        .merge(app_routes)
+        .merge(routes::download_router())
+        .nest_service("/static", static_files)
         .layer(axum::middleware::from_fn(add_no_archive_header))
         .layer(CompressionLayer::new())
         .layer(
             TraceLayer::new_for_http()
-                .make_span_with(|req: &Request<_>| {
-                    let client_ip =
-                        best_effort_client_ip(req).unwrap_or_else(|| "unknown".to_string());
+                .make_span_with(move |req: &Request<_>| {
+                    let client_ip = client_ip::request_client_ip(req, &trusted_proxies)
+                        .map_or_else(|| "unknown".to_string(), |ip| ip.to_string());
                     let user_agent = req
                         .headers()
                         .get(axum::http::header::USER_AGENT)
                         .and_then(|v| v.to_str().ok())
                         .unwrap_or("");
 
-                    tracing::info_span!(
+                    let span = tracing::info_span!(
                         "http_request",
                         method = %req.method(),
                         uri = %req.uri(),
                         client_ip = %client_ip,
                         user_agent = %user_agent,
-                    )
+                        s3_key = tracing::field::Empty,
+                    );
+                    // The S3 proxy doesn't log per request; the key is recorded here instead
+                    if let Some(key) = s3_key_from_path(req.uri().path()) {
+                        span.record("s3_key", key.as_str());
+                    }
+                    span
                 })
                 .on_request(|req: &Request<_>, _span: &tracing::Span| {
                     tracing::debug!(
@@ -251,94 +394,270 @@ fn create_app(state: AppState) -> Router {
         .with_state(state)
 }
 
-fn best_effort_client_ip<B>(req: &Request<B>) -> Option<String> {
-    // Prefer proxy headers if present.
-    if let Some(v) = req.headers().get("forwarded").and_then(|v| v.to_str().ok()) {
-        if let Some(ip) = parse_forwarded_for(v) {
-            return Some(ip);
-        }
-    }
+/// Extract the decoded S3 key from an `/s3/...` request path.
+fn s3_key_from_path(path: &str) -> Option<String> {
+    let key = path.strip_prefix("/s3/").filter(|k| !k.is_empty())?;
+    Some(urlencoding::decode(key).map_or_else(|_| key.to_string(), std::borrow::Cow::into_owned))
+}
 
-    if let Some(v) = req
-        .headers()
-        .get("x-forwarded-for")
-        .and_then(|v| v.to_str().ok())
-    {
-        if let Some(first) = v.split(',').next().map(str::trim).filter(|s| !s.is_empty()) {
-            return Some(strip_port_and_brackets(first));
+/// Stylesheet served in place of `css/style.css` when no static directory exists.
+const FALLBACK_STYLESHEET: &str = include_str!("fallback.css");
+
+/// Built-in locations searched for the static files directory, in order.
+const STATIC_DIR_CANDIDATES: [&str; 2] = ["./static", "/usr/share/discourse-link-archiver/static"];
+
+/// Find the static files directory.
+///
+/// Checks in order:
+/// 1. `STATIC_DIR` override, if set
+/// 2. ./static (development)
+/// 3. /usr/share/discourse-link-archiver/static (installed)
+///
+/// Returns `None` if none of them exist.
+fn find_static_dir(override_dir: Option<&Path>) -> Option<PathBuf> {
+    if let Some(dir) = override_dir {
+        if dir.is_dir() {
+            return Some(dir.to_path_buf());
         }
+        warn!(static_dir = %dir.display(), "STATIC_DIR does not exist, trying defaults");
     }
 
-    if let Some(v) = req.headers().get("x-real-ip").and_then(|v| v.to_str().ok()) {
-        let v = v.trim();
-        if !v.is_empty() {
-            return Some(strip_port_and_brackets(v));
-        }
+    STATIC_DIR_CANDIDATES
+        .iter()
+        .map(PathBuf::from)
+        .find(|path| path.is_dir())
+}
+
+/// Build the service mounted at `/static`.
+///
+/// Serves `static_dir` when found; otherwise logs a warning and serves only
+/// the embedded fallback stylesheet so pages stay usable.
+fn static_router(static_dir: Option<&Path>) -> Router {
+    if let Some(dir) = static_dir {
+        info!(static_dir = %dir.display(), "Serving static files");
+        return Router::new().fallback_service(ServeDir::new(dir));
     }
 
-    // Fall back to connect info (remote socket address).
-    req.extensions()
-        .get::<ConnectInfo<SocketAddr>>()
-        .map(|ci| ci.0.ip().to_string())
+    warn!(
+        searched = ?STATIC_DIR_CANDIDATES,
+        "No static files directory found; serving the built-in fallback stylesheet. \
+         Set STATIC_DIR to the directory containing css/ and js/"
+    );
+    Router::new().route(
+        "/css/style.css",
+        axum::routing::get(|| async {
+            (
+                [(axum::http::header::CONTENT_TYPE, "text/css; charset=utf-8")],
+                FALLBACK_STYLESHEET,
+            )
+        }),
+    )
 }
 
-fn parse_forwarded_for(header_value: &str) -> Option<String> {
-    // Forwarded: for=1.2.3.4;proto=https;by=...
-    // Forwarded: for="[2001:db8::1]:1234";proto=https
-    // We take the first `for=` value in the first element.
-    let first_elem = header_value.split(',').next()?.trim();
-    for part in first_elem.split(';') {
-        let part = part.trim();
-        let Some(rest) = part.strip_prefix("for=") else {
-            continue;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use axum::routing::post;
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_bind_listeners_one_per_address() {
+        let config = Config {
+            web_host: "127.0.0.1, ::1".to_string(),
+            ..Config::for_testing()
         };
-        let rest = rest.trim().trim_matches('"');
-        if rest.is_empty() {
-            continue;
-        }
-        return Some(strip_port_and_brackets(rest));
+        let addrs = config.bind_addrs(0).unwrap();
+        assert_eq!(addrs.len(), 2);
+
+        let listeners = bind_listeners(&addrs).unwrap();
+        assert_eq!(listeners.len(), 2);
+        let bound: Vec<std::net::IpAddr> = listeners
+            .iter()
+            .map(|l| l.local_addr().unwrap().ip())
+            .collect();
+        assert_eq!(bound, vec![addrs[0].ip(), addrs[1].ip()]);
     }
-    None
-}
 
-fn strip_port_and_brackets(s: &str) -> String {
-    let mut v = s.trim().trim_matches('"').to_string();
-    if v.starts_with('[') {
-        if let Some(end) = v.find(']') {
-            v = v[1..end].to_string();
-            return v;
-        }
+    #[test]
+    fn test_bind_listeners_dual_stack_same_port() {
+        // Reserve a free port, then bind both wildcards to it
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let config = Config {
+            web_host: "0.0.0.0,::".to_string(),
+            ..Config::for_testing()
+        };
+        let listeners = bind_listeners(&config.bind_addrs(port).unwrap()).unwrap();
+        assert_eq!(listeners.len(), 2);
+        assert!(listeners
+            .iter()
+            .all(|l| l.local_addr().unwrap().port() == port));
+    }
+    #[derive(serde::Deserialize)]
+    struct TestForm {
+        url: String,
     }
 
-    // IPv4:port or hostname:port => strip port.
-    // For raw IPv6 without brackets, we leave it as-is.
-    if let Some((host, port)) = v.rsplit_once(':') {
-        if !host.contains(':') && port.chars().all(|c| c.is_ascii_digit()) {
-            return host.to_string();
-        }
+    fn limited_app(timeout_secs: u64) -> Router {
+        let router = Router::new()
+            .route(
+                "/submit",
+                post(|axum::Form(form): axum::Form<TestForm>| async move { form.url }),
+            )
+            .route(
+                "/slow",
+                axum::routing::get(|| async {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    "done"
+                }),
+            );
+        with_request_limits(router, timeout_secs, 64)
     }
 
-    v
-}
+    fn submit_request(body: String) -> axum::http::Request<Body> {
+        axum::http::Request::builder()
+            .method("POST")
+            .uri("/submit")
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(Body::from(body))
+            .unwrap()
+    }
 
-/// Find the static files directory.
-///
-/// Checks in order:
-/// 1. ./static (development)
-/// 2. /usr/share/discourse-link-archiver/static (installed)
-/// 3. Falls back to ./static
-fn find_static_dir() -> PathBuf {
-    let candidates = [
-        PathBuf::from("./static"),
-        PathBuf::from("/usr/share/discourse-link-archiver/static"),
-    ];
-
-    for path in &candidates {
-        if path.exists() && path.is_dir() {
-            return path.clone();
+    #[tokio::test]
+    async fn test_request_limits_allow_normal_submit() {
+        let response = limited_app(30)
+            .oneshot(submit_request("url=https://example.com/page".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_request_limits_reject_large_body() {
+        let body = format!("url=https://example.com/{}", "a".repeat(200));
+        let response = limited_app(30).oneshot(submit_request(body)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_request_limits_time_out_slow_handler() {
+        let request = axum::http::Request::builder()
+            .uri("/slow")
+            .body(Body::empty())
+            .unwrap();
+        let response = limited_app(1).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::REQUEST_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn test_no_archive_header_skips_media_and_assets() {
+        let app = Router::new()
+            .route(
+                "/archive/1",
+                axum::routing::get(|| async { axum::response::Html("<p>page</p>") }),
+            )
+            .route(
+                "/s3/*path",
+                axum::routing::get(|| async {
+                    ([(axum::http::header::CONTENT_TYPE, "image/png")], "png")
+                }),
+            )
+            .nest_service("/static", static_router(None))
+            .layer(axum::middleware::from_fn(add_no_archive_header));
+        let response = app
+            .clone()
+            .oneshot(static_request("/archive/1"))
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["x-no-archive"], "1");
+
+        for uri in ["/s3/archives/1/image.png", "/static/css/style.css"] {
+            let response = app.clone().oneshot(static_request(uri)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{uri}");
+            assert!(!response.headers().contains_key("x-no-archive"), "{uri}");
         }
     }
 
-    // Default fallback
-    PathBuf::from("./static")
+    #[test]
+    fn test_find_static_dir_honors_override() {
+        let temp = tempfile::TempDir::new().unwrap();
+        assert_eq!(
+            find_static_dir(Some(temp.path())).as_deref(),
+            Some(temp.path())
+        );
+
+        // A missing override falls through to the built-in candidates
+        let missing = temp.path().join("missing");
+        assert_ne!(
+            find_static_dir(Some(&missing)).as_deref(),
+            Some(missing.as_path())
+        );
+    }
+
+    fn static_request(path: &str) -> axum::http::Request<Body> {
+        axum::http::Request::builder()
+            .uri(path)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_static_router_serves_fallback_stylesheet() {
+        let response = static_router(None)
+            .oneshot(static_request("/css/style.css"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[axum::http::header::CONTENT_TYPE],
+            "text/css; charset=utf-8"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, FALLBACK_STYLESHEET.as_bytes());
+
+        let response = static_router(None)
+            .oneshot(static_request("/js/theme.js"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_static_router_serves_directory() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp.path().join("css")).unwrap();
+        std::fs::write(temp.path().join("css/style.css"), "body{}").unwrap();
+
+        let response = static_router(Some(temp.path()))
+            .oneshot(static_request("/css/style.css"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"body{}");
+    }
+
+    #[test]
+    fn test_s3_key_from_path() {
+        assert_eq!(
+            s3_key_from_path("/s3/archives/12/media/video.mp4").as_deref(),
+            Some("archives/12/media/video.mp4")
+        );
+        assert_eq!(
+            s3_key_from_path("/s3/archives/12/My%20File.pdf").as_deref(),
+            Some("archives/12/My File.pdf")
+        );
+        assert_eq!(s3_key_from_path("/s3/"), None);
+        assert_eq!(s3_key_from_path("/archive/12"), None);
+    }
 }
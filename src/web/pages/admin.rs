@@ -1,16 +1,22 @@
 //! Admin pages using maud templates.
 //!
-//! This module contains the admin panel, user management, and excluded domains pages.
+//! This module contains the admin panel, user management, excluded domains and
+//! external service rule pages.
 
 use std::collections::HashMap;
 
 use maud::{html, Markup, Render};
 
 use crate::components::{
-    Alert, BaseLayout, Button, Form, FormGroup, HiddenInput, Input, ResponsiveTable, StatusBox,
-    Table, TableRow, TableVariant,
+    Alert, BaseLayout, Button, Form, FormGroup, HiddenInput, Input, KeyValueTable, Pagination,
+    ResponsiveTable, Select, SelectOption, StatusBox, Table, TableRow, TableVariant, TextArea,
 };
-use crate::db::{AuditEvent, ExcludedDomain, ForumAccountLink, SubtitleLanguageWithContext, User};
+use crate::db::{
+    forum_author_handle, AuditEvent, DomainQuotePolicy, DuplicateLinkGroup, DuplicateLinkScan,
+    ExcludedDomain, ExternalServiceRule, ExternalServices, ForumAccountLink, PriorityDomain,
+    SubtitleLanguageWithContext, User, VersionedDomain,
+};
+use crate::web::bulk_import::BulkImportSummary;
 
 /// User status badge for admin panel.
 #[derive(Debug, Clone, Copy)]
@@ -172,6 +178,51 @@ fn render_user_row(user: &User, current_user: &User) -> Markup {
     }
 }
 
+/// Page sizes offered for the admin panel's user and audit lists.
+pub const ADMIN_PAGE_SIZES: [i64; 4] = [25, 50, 100, 200];
+
+/// Admin panel page size when none (or an unlisted one) is requested.
+pub const ADMIN_DEFAULT_PAGE_SIZE: i64 = 50;
+
+/// Render a GET form choosing the admin panel page size for a tab.
+fn render_page_size_form(tab: &str, per_page: i64) -> Markup {
+    let per_page = per_page.to_string();
+    let select_id = format!("per_page-{tab}");
+    let sizes: Vec<String> = ADMIN_PAGE_SIZES.iter().map(ToString::to_string).collect();
+    let select = Select::new("per_page")
+        .id(&select_id)
+        .options(sizes.iter().map(|n| SelectOption::new(n, n)).collect())
+        .selected(&per_page);
+
+    Form::get(
+        "/admin",
+        html! {
+            (HiddenInput::new("tab", tab))
+            div class="audit-filters" {
+                (FormGroup::new("Per page:", &select_id, select.render()).render())
+                (Button::primary("Show").r#type("submit"))
+            }
+        },
+    )
+    .class("page-size-form")
+    .render()
+}
+
+/// Pagination links for one admin panel tab, keeping the tab and page size.
+fn admin_tab_pagination(
+    tab: &str,
+    page_param: &str,
+    page: usize,
+    total_pages: usize,
+    per_page: i64,
+) -> Pagination {
+    let per_page = (per_page != ADMIN_DEFAULT_PAGE_SIZE).then(|| per_page.to_string());
+    Pagination::new(page, total_pages, "/admin")
+        .with_page_param(page_param)
+        .with_param("tab", Some(tab))
+        .with_param("per_page", per_page.as_deref())
+}
+
 /// Render the users table for the admin panel.
 fn render_users_table(users: &[User], current_user: &User) -> Markup {
     let rows: Vec<Markup> = users
@@ -225,6 +276,20 @@ fn render_audit_target(event: &AuditEvent) -> Markup {
                     // Fallback to plain text if we can't extract the username
                     html! { (label) }
                 }
+                "post" => {
+                    // Posts are addressed by GUID; link when the metadata has it
+                    let guid = event
+                        .metadata
+                        .as_deref()
+                        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+                        .and_then(|v| v.get("post_guid")?.as_str().map(String::from));
+                    match guid {
+                        Some(guid) => html! {
+                            a href=(format!("/post/{}", urlencoding::encode(&guid))) { (label) }
+                        },
+                        None => html! { (label) },
+                    }
+                }
                 _ => html! { (label) },
             }
         }
@@ -234,6 +299,18 @@ fn render_audit_target(event: &AuditEvent) -> Markup {
                 "excluded_domain" => html! {
                     a href="/admin/excluded-domains" { "excluded domains" }
                 },
+                "domain_quote_policy" => html! {
+                    a href="/admin/excluded-domains#quote-policies" { "quote-only overrides" }
+                },
+                "priority_domain" => html! {
+                    a href="/admin/excluded-domains#priority-domains" { "priority domains" }
+                },
+                "versioned_domain" => html! {
+                    a href="/admin/excluded-domains#versioned-domains" { "versioned domains" }
+                },
+                "external_service_rule" => html! {
+                    a href="/admin/external-services" { "external service rules" }
+                },
                 _ => html! { (target_type) },
             }
         }
@@ -241,9 +318,8 @@ fn render_audit_target(event: &AuditEvent) -> Markup {
     }
 }
 
-/// Render a single audit event row.
-fn render_audit_row(event: &AuditEvent, user_lookup: &HashMap<i64, &User>) -> Markup {
-    // Determine user display
+/// Render the user cell for an audit event ("System" when there is no user).
+fn render_audit_user(event: &AuditEvent, user_lookup: &HashMap<i64, &User>) -> Markup {
     let user_str = event.user_id.map_or_else(
         || "System".to_string(),
         |id| {
@@ -260,17 +336,56 @@ fn render_audit_row(event: &AuditEvent, user_lookup: &HashMap<i64, &User>) -> Ma
     );
 
     html! {
-        tr {
-            td class="audit-cell" { (event.created_at) }
-            td class="audit-cell" {
-                @if let Some(user_id) = event.user_id {
-                    a href=(format!("/admin/user/{}", user_id)) {
-                        (user_str)
+        @if let Some(user_id) = event.user_id {
+            a href=(format!("/admin/user/{}", user_id)) {
+                (user_str)
+            }
+        } @else {
+            (user_str)
+        }
+    }
+}
+
+/// Render an audit event's metadata JSON in a readable form.
+///
+/// Short objects are shown inline as `key: value` pairs; anything else is
+/// pretty-printed inside a collapsible block.
+fn render_audit_metadata(metadata: Option<&str>) -> Markup {
+    let Some(raw) = metadata.filter(|m| !m.trim().is_empty()) else {
+        return html! { "\u{2014}" };
+    };
+
+    match serde_json::from_str::<serde_json::Value>(raw) {
+        Ok(serde_json::Value::Object(map))
+            if map.len() <= 3 && map.values().all(|v| !v.is_object() && !v.is_array()) =>
+        {
+            html! {
+                @for (i, (key, value)) in map.iter().enumerate() {
+                    @if i > 0 { br; }
+                    span class="audit-metadata-key" { (key) ":" } " "
+                    @match value.as_str() {
+                        Some(text) => (text),
+                        None => (value.to_string()),
                     }
-                } @else {
-                    (user_str)
                 }
             }
+        }
+        Ok(value) => html! {
+            details class="audit-metadata" {
+                summary { "Details" }
+                pre { (serde_json::to_string_pretty(&value).unwrap_or_else(|_| raw.to_string())) }
+            }
+        },
+        Err(_) => html! { code { (raw) } },
+    }
+}
+
+/// Render a single audit event row.
+fn render_audit_row(event: &AuditEvent, user_lookup: &HashMap<i64, &User>) -> Markup {
+    html! {
+        tr {
+            td class="audit-cell" { (event.created_at) }
+            td class="audit-cell" { (render_audit_user(event, user_lookup)) }
             td class="audit-cell" { (event.event_type) }
             td class="audit-cell" { (render_audit_target(event)) }
             td class="audit-cell" { (event.ip_address.as_deref().unwrap_or("\u{2014}")) }
@@ -292,13 +407,150 @@ fn render_audit_table(audit_events: &[AuditEvent], users: &[User]) -> Markup {
         .variant(TableVariant::Admin)
         .rows(rows);
 
-    ResponsiveTable::new(table.render()).render()
+    html! {
+        (ResponsiveTable::new(table.render()).render())
+        p { a href="/admin/audit" { "View full audit log \u{2192}" } }
+    }
+}
+
+/// Parameters for the admin audit log page.
+pub struct AdminAuditPageParams<'a> {
+    pub events: &'a [AuditEvent],
+    /// Users, for display names and the user filter
+    pub users: &'a [User],
+    /// Distinct event types, for the event type filter
+    pub event_types: &'a [String],
+    pub event_type_filter: Option<&'a str>,
+    pub user_id_filter: Option<i64>,
+    /// Total events matching the filters
+    pub total: i64,
+    /// Current page (0-indexed)
+    pub page: usize,
+    pub total_pages: usize,
+    pub current_user: &'a User,
+}
+
+/// Render the admin audit log page with filters and pagination.
+#[must_use]
+pub fn render_admin_audit_page(params: &AdminAuditPageParams<'_>) -> Markup {
+    let user_lookup: HashMap<i64, &User> = params.users.iter().map(|u| (u.id, u)).collect();
+    let user_id_str = params.user_id_filter.map(|id| id.to_string());
+
+    let user_options: Vec<(String, String)> = params
+        .users
+        .iter()
+        .map(|u| (u.id.to_string(), format!("{} (#{})", u.username, u.id)))
+        .collect();
+
+    let event_type_select = Select::new("event_type")
+        .id("event_type")
+        .options(
+            std::iter::once(SelectOption::new("", "All events"))
+                .chain(params.event_types.iter().map(|t| SelectOption::new(t, t)))
+                .collect(),
+        )
+        .selected(params.event_type_filter.unwrap_or(""));
+
+    let user_select = Select::new("user_id")
+        .id("user_id")
+        .options(
+            std::iter::once(SelectOption::new("", "All users"))
+                .chain(
+                    user_options
+                        .iter()
+                        .map(|(id, label)| SelectOption::new(id, label)),
+                )
+                .collect(),
+        )
+        .selected(user_id_str.as_deref().unwrap_or(""));
+
+    let rows: Vec<Markup> = params
+        .events
+        .iter()
+        .map(|event| {
+            TableRow::new()
+                .cell_with_class(&event.created_at, "audit-cell")
+                .cell_markup_with_class(render_audit_user(event, &user_lookup), "audit-cell")
+                .cell_with_class(&event.event_type, "audit-cell")
+                .cell_markup_with_class(render_audit_target(event), "audit-cell")
+                .cell_with_class(
+                    event.ip_address.as_deref().unwrap_or("\u{2014}"),
+                    "audit-cell",
+                )
+                .cell_markup_with_class(
+                    render_audit_metadata(event.metadata.as_deref()),
+                    "audit-cell",
+                )
+                .render()
+        })
+        .collect();
+
+    let table = Table::new(vec![
+        "Timestamp",
+        "User",
+        "Event",
+        "Target",
+        "IP",
+        "Details",
+    ])
+    .variant(TableVariant::Admin)
+    .rows(rows);
+
+    let pagination = Pagination::new(params.page, params.total_pages, "/admin/audit")
+        .with_param("event_type", params.event_type_filter)
+        .with_param("user_id", user_id_str.as_deref());
+
+    let content = html! {
+        h1 { "Audit Log" }
+
+        (Form::get("/admin/audit", html! {
+            div class="audit-filters" {
+                (FormGroup::new("Event type:", "event_type", event_type_select.render()).render())
+                (FormGroup::new("User:", "user_id", user_select.render()).render())
+                (Button::primary("Filter").r#type("submit"))
+                @if params.event_type_filter.is_some() || params.user_id_filter.is_some() {
+                    (Button::outline("Clear").href("/admin/audit"))
+                }
+            }
+        }).class("audit-filter-form"))
+
+        p class="page-description" {
+            (params.total) " matching event" @if params.total != 1 { "s" }
+        }
+
+        @if params.events.is_empty() {
+            p { "No audit events match these filters." }
+        } @else {
+            (ResponsiveTable::new(table.render()).render())
+        }
+
+        (pagination)
+
+        div class="action-buttons" {
+            (Button::outline("Back to Admin Panel").href("/admin?tab=audit"))
+        }
+    };
+
+    BaseLayout::new("Audit Log", Some(params.current_user)).render(content)
 }
 
 /// Parameters for the admin panel page.
 pub struct AdminPanelParams<'a> {
+    /// The current page of users
     pub users: &'a [User],
+    pub users_total: i64,
+    /// Current users page (0-indexed)
+    pub users_page: usize,
+    pub users_total_pages: usize,
+    /// Users named by the audit events and forum links shown
+    pub named_users: &'a [User],
+    /// The current page of audit events
     pub audit_events: &'a [AuditEvent],
+    /// Current audit page (0-indexed)
+    pub audit_page: usize,
+    pub audit_total_pages: usize,
+    /// Rows per page for both lists
+    pub per_page: i64,
     pub forum_links: &'a [ForumAccountLink],
     pub subtitle_languages: &'a [SubtitleLanguageWithContext],
     pub current_user: &'a User,
@@ -306,6 +558,236 @@ pub struct AdminPanelParams<'a> {
     pub active_tab: Option<&'a str>,
     /// Optional success/error message
     pub message: Option<&'a str>,
+    /// Number of completed archives missing expected artifacts
+    pub missing_artifacts_count: i64,
+    /// Archive ID to resume the missing-artifacts sweep after, if one is in progress
+    pub missing_artifacts_cursor: Option<i64>,
+    /// Artifact ID to resume the size recompute after, if one is in progress
+    pub recompute_sizes_cursor: Option<i64>,
+    /// Number of archives whose OG extraction ran but found no title
+    pub og_reextraction_count: i64,
+}
+
+/// Render the "reprocess missing artifacts" tool card.
+///
+/// Each run queues jobs for the next batch of archives; the cursor lets the
+/// operator continue where the previous run stopped.
+fn render_missing_artifacts_card(count: i64, cursor: Option<i64>) -> Markup {
+    html! {
+        h3 class="admin-section-header" { "Backfill" }
+
+        div class="tool-card" {
+            h4 { "Reprocess Missing Artifacts" }
+            p {
+                @if count == 1 {
+                    "1 completed video archive is missing subtitles, a transcript, or comments."
+                } @else {
+                    (count) " completed video archives are missing subtitles, transcripts, or comments."
+                }
+            }
+            p class="text-muted" {
+                "Queues jobs for just the missing pieces, without re-archiving."
+            }
+            @if count > 0 {
+                form method="post" action="/admin/reprocess-missing-artifacts" style="display: inline;" {
+                    input type="hidden" name="after_id" value=(cursor.unwrap_or(0));
+                    button type="submit" class="btn btn-primary" {
+                        @if let Some(after_id) = cursor {
+                            "Continue after archive #" (after_id)
+                        } @else {
+                            "Queue backfill jobs"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Render the "recompute sizes" tool card.
+fn render_recompute_sizes_card(cursor: Option<i64>) -> Markup {
+    html! {
+        div class="tool-card" {
+            h4 { "Recompute Sizes" }
+            p {
+                "Re-checks each artifact in S3 and corrects stored sizes that have drifted, "
+                "so storage stats match the bucket."
+            }
+            form method="post" action="/admin/recompute-sizes" style="display: inline;" {
+                input type="hidden" name="after_id" value=(cursor.unwrap_or(0));
+                button type="submit" class="btn btn-primary" {
+                    @if let Some(after_id) = cursor {
+                        "Continue after artifact #" (after_id)
+                    } @else {
+                        "Recompute sizes"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Render the "re-run OG extraction" tool card.
+fn render_og_reextraction_card(count: i64) -> Markup {
+    html! {
+        div class="tool-card" {
+            h4 { "Re-run OG Extraction" }
+            p {
+                @if count == 1 {
+                    "1 archive has no Open Graph title after extraction."
+                } @else {
+                    (count) " archives have no Open Graph title after extraction."
+                }
+            }
+            p class="text-muted" {
+                "Resetting lets extraction run again from the stored HTML the next time each archive is viewed."
+            }
+            @if count > 0 {
+                form method="post" action="/admin/og/reset" style="display: inline;" {
+                    button type="submit" class="btn btn-primary" { "Reset OG extraction" }
+                }
+            }
+        }
+    }
+}
+
+/// Render the duplicate links tool card, which links to the scan page.
+fn render_duplicate_links_card() -> Markup {
+    html! {
+        h3 class="admin-section-header" { "Duplicate Links" }
+
+        div class="tool-card" {
+            h4 { "Find Duplicate Links" }
+            p {
+                "Re-normalizes stored URLs a page at a time and lists links that now point at "
+                "the same URL, so they can be merged."
+            }
+            (Button::primary("Find duplicate links").href("/admin/links/duplicates"))
+        }
+    }
+}
+
+/// Render one duplicate link group as a table row with a merge button.
+///
+/// `after_id` is the page the group was found on, so the merge returns to it.
+fn render_duplicate_link_row(group: &DuplicateLinkGroup, after_id: i64) -> Markup {
+    let keep = group.keep();
+    let merge_ids = group
+        .merge_ids()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let row = TableRow::new()
+        .cell_markup(html! { code { (group.normalized_url) } })
+        .cell_markup(html! {
+            @for (i, link) in group.links.iter().enumerate() {
+                @if i > 0 { ", " }
+                span title=(link.normalized_url) { "#" (link.id) }
+            }
+        })
+        .cell_markup(html! {
+            (Form::post("/admin/links/merge", html! {
+                (HiddenInput::new("keep_id", &keep.id.to_string()))
+                (HiddenInput::new("merge_ids", &merge_ids))
+                (HiddenInput::new("after_id", &after_id.to_string()))
+                (Button::primary(&format!("Merge into #{}", keep.id))
+                    .r#type("submit")
+                    .class("btn-sm")
+                    .onclick("return confirm('Merge these links? This cannot be undone.');"))
+            }).class("inline-form"))
+        });
+
+    row.render()
+}
+
+/// Render the duplicate groups found in one page of links.
+///
+/// Merging keeps the oldest link and folds the others (and their archives)
+/// into it.
+fn render_duplicate_links_results(scan: &DuplicateLinkScan, after_id: i64) -> Markup {
+    let groups = &scan.groups;
+    html! {
+        @if scan.scanned == 0 {
+            p { "No more links to check." }
+        } @else {
+            p class="text-muted" {
+                "Checked " (scan.scanned) " links after #" (after_id)
+                @if let Some(last_id) = scan.last_id { ", up to #" (last_id) } "."
+            }
+            @if groups.is_empty() {
+                p { "No duplicate links found." }
+            } @else {
+                p {
+                    @if groups.len() == 1 {
+                        "1 URL is stored as more than one link."
+                    } @else {
+                        (groups.len()) " URLs are stored as more than one link."
+                    }
+                }
+                p class="text-muted" {
+                    "Merging keeps the oldest link and moves occurrences, archives and artifacts onto it."
+                }
+                (ResponsiveTable::new(
+                    Table::new(vec!["URL", "Links", "Actions"])
+                        .rows(groups.iter().map(|g| render_duplicate_link_row(g, after_id)).collect())
+                        .render(),
+                ))
+            }
+        }
+    }
+}
+
+/// Render the admin duplicate links page for one page of links.
+///
+/// # Arguments
+///
+/// * `scan` - Duplicates found among the links after `after_id`
+/// * `after_id` - Link ID the page starts after
+/// * `message` - Optional success/error message to display
+/// * `current_user` - The currently logged-in admin user
+///
+/// # Returns
+///
+/// Complete HTML page as maud Markup
+#[must_use]
+pub fn render_admin_duplicate_links_page(
+    scan: &DuplicateLinkScan,
+    after_id: i64,
+    message: Option<&str>,
+    current_user: &User,
+) -> Markup {
+    let content = html! {
+        div class="excluded-domains-container" {
+            h1 { "Duplicate Links" }
+
+            p class="page-description" {
+                "Stored URLs are re-normalized with the current rules, a page of links at a time. "
+                "Links that now share a URL are listed here and can be merged."
+            }
+
+            @if let Some(msg) = message {
+                (Alert::success(msg).render())
+            }
+
+            div class="domains-list-section" {
+                (render_duplicate_links_results(scan, after_id))
+            }
+
+            div class="action-buttons" {
+                @if let Some(last_id) = scan.last_id {
+                    (Button::primary(&format!("Next page after #{last_id}"))
+                        .href(&format!("/admin/links/duplicates?after_id={last_id}")))
+                } @else if after_id > 0 {
+                    (Button::primary("Start over").href("/admin/links/duplicates"))
+                }
+                (Button::outline("Back to Admin Panel").href("/admin"))
+            }
+        }
+    };
+
+    BaseLayout::new("Duplicate Links", Some(current_user)).render(content)
 }
 
 /// Render the main admin panel page with tabs.
@@ -322,7 +804,7 @@ pub fn render_admin_panel(params: &AdminPanelParams) -> Markup {
     let active_tab = params.active_tab.unwrap_or("users");
 
     // Build user lookup for forum links table
-    let user_lookup: HashMap<i64, &User> = params.users.iter().map(|u| (u.id, u)).collect();
+    let user_lookup: HashMap<i64, &User> = params.named_users.iter().map(|u| (u.id, u)).collect();
 
     let content = html! {
         div class="admin-panel-container" {
@@ -349,12 +831,25 @@ pub fn render_admin_panel(params: &AdminPanelParams) -> Markup {
 
             // Users tab
             div id="tab-users" class=(format!("tab-content {}", if active_tab == "users" { "active" } else { "" })) {
+                p class="page-description" {
+                    (params.users_total) " user" @if params.users_total != 1 { "s" }
+                }
+                (render_page_size_form("users", params.per_page))
                 (render_users_table(params.users, params.current_user))
+                (admin_tab_pagination(
+                    "users",
+                    "users_page",
+                    params.users_page,
+                    params.users_total_pages,
+                    params.per_page,
+                ))
 
                 // Admin tools section
                 h3 class="admin-section-header" style="margin-top: var(--spacing-lg);" { "Admin Tools" }
                 div class="admin-tools" {
                     (Button::primary("Manage Excluded Domains").href("/admin/excluded-domains"))
+                    (Button::primary("External Service Rules").href("/admin/external-services"))
+                    (Button::primary("Bulk Import URLs").href("/admin/bulk-import"))
                 }
             }
 
@@ -370,7 +865,15 @@ pub fn render_admin_panel(params: &AdminPanelParams) -> Markup {
 
             // Audit Log tab
             div id="tab-audit" class=(format!("tab-content {}", if active_tab == "audit" { "active" } else { "" })) {
-                (render_audit_table(params.audit_events, params.users))
+                (render_page_size_form("audit", params.per_page))
+                (render_audit_table(params.audit_events, params.named_users))
+                (admin_tab_pagination(
+                    "audit",
+                    "audit_page",
+                    params.audit_page,
+                    params.audit_total_pages,
+                    params.per_page,
+                ))
             }
 
             // Tools tab
@@ -398,6 +901,14 @@ pub fn render_admin_panel(params: &AdminPanelParams) -> Markup {
                     { "Upgrade gallery-dl" }
                     pre id="output-gallery-dl" class="stream-output" {}
                 }
+
+                (render_missing_artifacts_card(params.missing_artifacts_count, params.missing_artifacts_cursor))
+
+                (render_recompute_sizes_card(params.recompute_sizes_cursor))
+
+                (render_og_reextraction_card(params.og_reextraction_count))
+
+                (render_duplicate_links_card())
             }
         }
 
@@ -487,6 +998,12 @@ fn render_forum_link_row(link: &ForumAccountLink, user_lookup: &HashMap<i64, &Us
             a href=(format!("/admin/forum-user/{}", link.forum_username)) {
                 code { (&link.forum_username) }
             }
+            @if let Some(handle) = forum_author_handle(&link.forum_username) {
+                " "
+                a href=(format!("/forum-user/{}", urlencoding::encode(handle))) class="text-muted" {
+                    small { "activity" }
+                }
+            }
         })
         .cell_markup(html! {
             a href=(format!("/admin/user/{}", link.user_id)) {
@@ -705,7 +1222,12 @@ fn render_domain_actions(domain: &ExcludedDomain) -> Markup {
 /// Render a single excluded domain row.
 fn render_domain_row(domain: &ExcludedDomain) -> Markup {
     let row = TableRow::new()
-        .cell_markup(html! { code { (domain.domain) } })
+        .cell_markup(html! {
+            code { (domain.domain) }
+            @if domain.applies_to_subdomains() {
+                " " span class="domain-subdomains-badge" title="Also excludes all subdomains" { "+ subdomains" }
+            }
+        })
         .cell(&domain.reason)
         .cell_markup(render_domain_status_badge(domain.is_active))
         .cell(&domain.created_at)
@@ -729,11 +1251,248 @@ fn render_excluded_domains_table(domains: &[ExcludedDomain]) -> Markup {
     ResponsiveTable::new(table.render()).render()
 }
 
+/// Human-readable label for a quote-only archiving decision.
+const fn quote_policy_label(archive_quote_only: bool) -> &'static str {
+    if archive_quote_only {
+        "Always archive"
+    } else {
+        "Never archive"
+    }
+}
+
+/// Render a single quote-only override row.
+fn render_quote_policy_row(policy: &DomainQuotePolicy) -> Markup {
+    let row = TableRow::new()
+        .cell_markup(html! { code { (policy.domain) } })
+        .cell(quote_policy_label(policy.archive_quote_only))
+        .cell(&policy.updated_at)
+        .cell_markup(html! {
+            (Form::post("/admin/quote-policies/delete", html! {
+                (HiddenInput::new("domain", &policy.domain))
+                (Button::danger("Delete")
+                    .r#type("submit")
+                    .class("btn-sm")
+                    .onclick("return confirm('Remove this quote-only override?');"))
+            }).class("inline-form"))
+        });
+
+    row.render()
+}
+
+/// Render the quote-only overrides table.
+fn render_quote_policies_table(policies: &[DomainQuotePolicy]) -> Markup {
+    if policies.is_empty() {
+        return html! {
+            p class="no-domains-message" { "No quote-only overrides yet." }
+        };
+    }
+
+    let rows: Vec<Markup> = policies.iter().map(render_quote_policy_row).collect();
+
+    let table = Table::new(vec!["Domain", "Quote-only links", "Updated", "Actions"]).rows(rows);
+
+    ResponsiveTable::new(table.render()).render()
+}
+
+/// Render the quote-only overrides section of the excluded domains page.
+fn render_quote_policies_section(policies: &[DomainQuotePolicy], global_default: bool) -> Markup {
+    let policy_select = Select::new("archive_quote_only")
+        .id("archive_quote_only")
+        .options(vec![
+            SelectOption::new("true", quote_policy_label(true)),
+            SelectOption::new("false", quote_policy_label(false)),
+        ])
+        .selected(if global_default { "false" } else { "true" });
+
+    html! {
+        div class="domains-list-section" id="quote-policies" {
+            h2 { "Quote-Only Link Overrides" }
+            p class="page-description" {
+                "Links that first appear inside a quote are currently "
+                strong { (if global_default { "archived" } else { "not archived" }) }
+                " by default (" code { "ARCHIVE_QUOTE_ONLY_LINKS" } "). "
+                "An override for a domain takes precedence over that default; domain patterns "
+                "follow the same rules as excluded domains, and the most specific match wins."
+            }
+
+            (Form::post("/admin/quote-policies/set", html! {
+                (FormGroup::new(
+                    "Domain:",
+                    "quote_domain",
+                    Input::text("domain")
+                        .id("quote_domain")
+                        .placeholder("courtlistener.com or *.x.com")
+                        .required()
+                        .render()
+                ).render())
+
+                (FormGroup::new(
+                    "Quote-only links:",
+                    "archive_quote_only",
+                    policy_select.render()
+                ).render())
+
+                (Button::primary("Save Override").r#type("submit"))
+            }))
+
+            (render_quote_policies_table(policies))
+        }
+    }
+}
+
+/// Render the priority domains table.
+fn render_priority_domains_table(domains: &[PriorityDomain]) -> Markup {
+    if domains.is_empty() {
+        return html! {
+            p class="no-domains-message" { "No priority domains yet." }
+        };
+    }
+
+    let rows: Vec<Markup> = domains
+        .iter()
+        .map(|domain| {
+            TableRow::new()
+                .cell_markup(html! { code { (domain.domain) } })
+                .cell(&domain.reason)
+                .cell(&domain.created_at)
+                .cell_markup(html! {
+                    (Form::post("/admin/priority-domains/delete", html! {
+                        (HiddenInput::new("domain", &domain.domain))
+                        (Button::danger("Delete")
+                            .r#type("submit")
+                            .class("btn-sm")
+                            .onclick("return confirm('Remove this priority domain?');"))
+                    }).class("inline-form"))
+                })
+                .render()
+        })
+        .collect();
+
+    let table = Table::new(vec!["Domain", "Reason", "Added", "Actions"]).rows(rows);
+
+    ResponsiveTable::new(table.render()).render()
+}
+
+/// Render the priority domains section of the excluded domains page.
+fn render_priority_domains_section(domains: &[PriorityDomain]) -> Markup {
+    html! {
+        div class="domains-list-section" id="priority-domains" {
+            h2 { "Priority Domains" }
+            p class="page-description" {
+                "Links on these domains are archived before other pending links, and failed "
+                "attempts retry on a shorter backoff (capped at about 40 minutes). "
+                "Domain patterns follow the same rules as excluded domains."
+            }
+
+            (Form::post("/admin/priority-domains/add", html! {
+                (FormGroup::new(
+                    "Domain:",
+                    "priority_domain",
+                    Input::text("domain")
+                        .id("priority_domain")
+                        .placeholder("abc.net.au or *.gov.au")
+                        .required()
+                        .render()
+                ).render())
+
+                (FormGroup::new(
+                    "Reason (optional):",
+                    "priority_reason",
+                    Input::text("reason")
+                        .id("priority_reason")
+                        .placeholder("Articles are often pulled")
+                        .render()
+                ).render())
+
+                (Button::primary("Add Priority Domain").r#type("submit"))
+            }))
+
+            (render_priority_domains_table(domains))
+        }
+    }
+}
+
+/// Render the versioned domains table.
+fn render_versioned_domains_table(domains: &[VersionedDomain]) -> Markup {
+    if domains.is_empty() {
+        return html! {
+            p class="no-domains-message" { "No versioned domains yet." }
+        };
+    }
+
+    let rows: Vec<Markup> = domains
+        .iter()
+        .map(|domain| {
+            TableRow::new()
+                .cell_markup(html! { code { (domain.domain) } })
+                .cell(&domain.reason)
+                .cell(&domain.created_at)
+                .cell_markup(html! {
+                    (Form::post("/admin/versioned-domains/delete", html! {
+                        (HiddenInput::new("domain", &domain.domain))
+                        (Button::danger("Delete")
+                            .r#type("submit")
+                            .class("btn-sm")
+                            .onclick("return confirm('Remove this versioned domain?');"))
+                    }).class("inline-form"))
+                })
+                .render()
+        })
+        .collect();
+
+    let table = Table::new(vec!["Domain", "Reason", "Added", "Actions"]).rows(rows);
+
+    ResponsiveTable::new(table.render()).render()
+}
+
+/// Render the versioned domains section of the excluded domains page.
+fn render_versioned_domains_section(domains: &[VersionedDomain]) -> Markup {
+    html! {
+        div class="domains-list-section" id="versioned-domains" {
+            h2 { "Versioned Domains" }
+            p class="page-description" {
+                "Re-archiving a link on these domains keeps the previous capture as a "
+                "version instead of replacing it. Individual archives can also opt in "
+                "from their detail page. Domain patterns follow the same rules as excluded domains."
+            }
+
+            (Form::post("/admin/versioned-domains/add", html! {
+                (FormGroup::new(
+                    "Domain:",
+                    "versioned_domain",
+                    Input::text("domain")
+                        .id("versioned_domain")
+                        .placeholder("abc.net.au or *.gov.au")
+                        .required()
+                        .render()
+                ).render())
+
+                (FormGroup::new(
+                    "Reason (optional):",
+                    "versioned_reason",
+                    Input::text("reason")
+                        .id("versioned_reason")
+                        .placeholder("Articles are edited after publication")
+                        .render()
+                ).render())
+
+                (Button::primary("Add Versioned Domain").r#type("submit"))
+            }))
+
+            (render_versioned_domains_table(domains))
+        }
+    }
+}
+
 /// Render the excluded domains management page.
 ///
 /// # Arguments
 ///
 /// * `domains` - List of excluded domains
+/// * `quote_policies` - Per-domain quote-only archiving overrides
+/// * `priority_domains` - Domains processed ahead of the rest of the queue
+/// * `versioned_domains` - Domains whose archives keep prior captures
+/// * `quote_only_default` - Global `ARCHIVE_QUOTE_ONLY_LINKS` setting
 /// * `message` - Optional success/error message to display
 ///
 /// # Returns
@@ -742,6 +1501,10 @@ fn render_excluded_domains_table(domains: &[ExcludedDomain]) -> Markup {
 #[must_use]
 pub fn render_admin_excluded_domains_page(
     domains: &[ExcludedDomain],
+    quote_policies: &[DomainQuotePolicy],
+    priority_domains: &[PriorityDomain],
+    versioned_domains: &[VersionedDomain],
+    quote_only_default: bool,
     message: Option<&str>,
     current_user: &User,
 ) -> Markup {
@@ -752,6 +1515,10 @@ pub fn render_admin_excluded_domains_page(
             p class="page-description" {
                 "Manage domains that should not be archived. These domains will be automatically excluded from archiving."
             }
+            p class="page-description" {
+                "A bare domain like " code { "example.com" } " matches only that host. Prefix it with "
+                code { "*." } " (e.g. " code { "*.example.com" } ") to also exclude every subdomain."
+            }
 
             // Success/error message if present
             @if let Some(msg) = message {
@@ -767,7 +1534,7 @@ pub fn render_admin_excluded_domains_page(
                         "domain",
                         Input::text("domain")
                             .id("domain")
-                            .placeholder("example.com")
+                            .placeholder("example.com or *.example.com")
                             .required()
                             .render()
                     ).render())
@@ -791,6 +1558,12 @@ pub fn render_admin_excluded_domains_page(
                 (render_excluded_domains_table(domains))
             }
 
+            (render_quote_policies_section(quote_policies, quote_only_default))
+
+            (render_priority_domains_section(priority_domains))
+
+            (render_versioned_domains_section(versioned_domains))
+
             // Back button
             div class="action-buttons" {
                 (Button::outline("Back to Admin Panel").href("/admin"))
@@ -801,6 +1574,261 @@ pub fn render_admin_excluded_domains_page(
     BaseLayout::new("Excluded Domains", Some(current_user)).render(content)
 }
 
+/// Label for one service setting of an external service rule.
+const fn service_rule_label(setting: Option<bool>) -> &'static str {
+    match setting {
+        Some(true) => "On",
+        Some(false) => "Off",
+        None => "Default",
+    }
+}
+
+/// Select for one service in the external service rule form.
+fn service_rule_select(name: &str) -> Markup {
+    Select::new(name)
+        .id(name)
+        .options(vec![
+            SelectOption::new("", "Default"),
+            SelectOption::new("on", "On"),
+            SelectOption::new("off", "Off"),
+        ])
+        .render()
+}
+
+/// Render a single external service rule row.
+fn render_service_rule_row(rule: &ExternalServiceRule) -> Markup {
+    let scope = if rule.scope == "content_type" {
+        "Content type"
+    } else {
+        "Domain"
+    };
+    let row = TableRow::new()
+        .cell(scope)
+        .cell_markup(html! { code { (rule.pattern) } })
+        .cell(service_rule_label(rule.wayback))
+        .cell(service_rule_label(rule.archive_today))
+        .cell(service_rule_label(rule.ipfs))
+        .cell(&rule.updated_at)
+        .cell_markup(html! {
+            (Form::post("/admin/external-services/delete", html! {
+                (HiddenInput::new("id", &rule.id.to_string()))
+                (Button::danger("Delete")
+                    .r#type("submit")
+                    .class("btn-sm")
+                    .onclick("return confirm('Remove this rule?');"))
+            }).class("inline-form"))
+        });
+
+    row.render()
+}
+
+/// Render the external service rules table.
+fn render_service_rules_table(rules: &[ExternalServiceRule]) -> Markup {
+    if rules.is_empty() {
+        return html! {
+            p class="no-domains-message" { "No external service rules yet." }
+        };
+    }
+
+    let rows: Vec<Markup> = rules.iter().map(render_service_rule_row).collect();
+
+    let table = Table::new(vec![
+        "Scope",
+        "Pattern",
+        "Wayback",
+        "Archive.today",
+        "IPFS",
+        "Updated",
+        "Actions",
+    ])
+    .rows(rows);
+
+    ResponsiveTable::new(table.render()).render()
+}
+
+/// Render the external service rules page.
+///
+/// # Arguments
+///
+/// * `rules` - Domain and content-type rules
+/// * `defaults` - Global settings used when no rule decides a service
+/// * `current_user` - The currently logged-in admin user
+///
+/// # Returns
+///
+/// Complete HTML page as maud Markup
+#[must_use]
+pub fn render_admin_external_services_page(
+    rules: &[ExternalServiceRule],
+    defaults: ExternalServices,
+    current_user: &User,
+) -> Markup {
+    let on_off = |enabled: bool| if enabled { "on" } else { "off" };
+    let scope_select = Select::new("scope")
+        .id("scope")
+        .options(vec![
+            SelectOption::new("domain", "Domain"),
+            SelectOption::new("content_type", "Content type"),
+        ])
+        .selected("domain");
+
+    let content = html! {
+        div class="excluded-domains-container" {
+            h1 { "External Service Rules" }
+
+            p class="page-description" {
+                "Globally, Wayback is " strong { (on_off(defaults.wayback)) }
+                ", Archive.today is " strong { (on_off(defaults.archive_today)) }
+                " and IPFS pinning is " strong { (on_off(defaults.ipfs)) } ". "
+                "Rules override those settings for a domain or a content type "
+                "(" code { "video" } ", " code { "text" } ", " code { "image" } ", ...)."
+            }
+            p class="page-description" {
+                "Each service is decided separately: the most specific domain rule that sets it wins, "
+                "then the content-type rule, then the global setting. Domain patterns follow the same "
+                "rules as excluded domains. IPFS can only be turned on for archives when "
+                code { "IPFS_ENABLED" } " is set."
+            }
+
+            div class="add-domain-section" {
+                h2 { "Add or Update Rule" }
+                (Form::post("/admin/external-services/set", html! {
+                    (FormGroup::new("Scope:", "scope", scope_select.render()).render())
+
+                    (FormGroup::new(
+                        "Pattern:",
+                        "pattern",
+                        Input::text("pattern")
+                            .id("pattern")
+                            .placeholder("*.nytimes.com or video")
+                            .required()
+                            .render()
+                    ).render())
+
+                    (FormGroup::new("Wayback:", "wayback", service_rule_select("wayback")).render())
+                    (FormGroup::new(
+                        "Archive.today:",
+                        "archive_today",
+                        service_rule_select("archive_today")
+                    ).render())
+                    (FormGroup::new("IPFS:", "ipfs", service_rule_select("ipfs")).render())
+
+                    (Button::primary("Save Rule").r#type("submit"))
+                }))
+            }
+
+            div class="domains-list-section" {
+                h2 { "Current Rules" }
+                (render_service_rules_table(rules))
+            }
+
+            div class="action-buttons" {
+                (Button::outline("Back to Admin Panel").href("/admin"))
+            }
+        }
+    };
+
+    BaseLayout::new("External Service Rules", Some(current_user)).render(content)
+}
+
+/// Render the outcome table for a bulk import.
+fn render_bulk_import_summary(summary: &BulkImportSummary) -> Markup {
+    let table = KeyValueTable::new()
+        .item("Queued", &summary.queued.to_string())
+        .item("Skipped (excluded domain)", &summary.excluded.to_string())
+        .item("Already archived", &summary.already_archived.to_string())
+        .item("Already queued", &summary.already_queued.to_string())
+        .item("Invalid", &summary.invalid.to_string())
+        .item("Duplicates in input", &summary.duplicates.to_string())
+        .item("Over batch limit", &summary.over_limit.to_string())
+        .item("Failed", &summary.failed.to_string());
+
+    html! {
+        div class="domains-list-section" {
+            h2 { "Import Summary" }
+            (table)
+            @if !summary.invalid_examples.is_empty() {
+                h3 { "Invalid entries" }
+                ul {
+                    @for entry in &summary.invalid_examples {
+                        li { code { (entry) } }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Render the admin bulk URL import page.
+///
+/// # Arguments
+///
+/// * `summary` - Outcome of the import just submitted, if any
+/// * `error` - Error message to show above the form
+/// * `max_urls` - Maximum number of URLs accepted per import
+/// * `current_user` - The currently logged-in admin user
+///
+/// # Returns
+///
+/// Complete HTML page as maud Markup
+#[must_use]
+pub fn render_admin_bulk_import_page(
+    summary: Option<&BulkImportSummary>,
+    error: Option<&str>,
+    max_urls: usize,
+    current_user: &User,
+) -> Markup {
+    let content = html! {
+        div class="excluded-domains-container" {
+            h1 { "Bulk Import URLs" }
+
+            p class="page-description" {
+                "Paste URLs one per line, or upload a CSV or text file. For CSV rows the first "
+                "field containing a URL is used. Up to " strong { (max_urls) } " URLs are queued per "
+                "import as submissions from your account; excluded domains and URLs that are "
+                "already archived or queued are skipped."
+            }
+
+            @if let Some(msg) = error {
+                (Alert::error(msg).render())
+            }
+
+            @if let Some(summary) = summary {
+                (render_bulk_import_summary(summary))
+            }
+
+            div class="add-domain-section" {
+                h2 { "Import" }
+                (Form::post("/admin/bulk-import", html! {
+                    (FormGroup::new(
+                        "URLs:",
+                        "urls",
+                        TextArea::new("urls")
+                            .id("urls")
+                            .rows(12)
+                            .placeholder("https://example.com/article")
+                            .render()
+                    ).render())
+
+                    (FormGroup::new(
+                        "Or upload a file:",
+                        "file",
+                        Input::new("file", "file").id("file").render()
+                    ).render())
+
+                    (Button::primary("Import").r#type("submit"))
+                }).multipart())
+            }
+
+            div class="action-buttons" {
+                (Button::outline("Back to Admin Panel").href("/admin"))
+            }
+        }
+    };
+
+    BaseLayout::new("Bulk Import URLs", Some(current_user)).render(content)
+}
+
 /// Render the admin user profile page.
 ///
 /// # Arguments
@@ -1040,6 +2068,10 @@ pub fn render_admin_forum_user_profile(
 
             // Back button
             div class="action-buttons" {
+                @if let Some(handle) = forum_author_handle(&forum_link.forum_username) {
+                    (Button::secondary("View Forum Activity")
+                        .href(&format!("/forum-user/{}", urlencoding::encode(handle))))
+                }
                 (Button::outline("Back to Admin Panel").href("/admin"))
             }
         }
@@ -1078,6 +2110,7 @@ fn render_user_audit_table(audit_events: &[AuditEvent]) -> Markup {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::Link;
 
     /// Create a test user for unit tests.
     fn test_user(
@@ -1245,12 +2278,23 @@ mod tests {
 
         let params = AdminPanelParams {
             users: &users,
+            users_total: 2,
+            users_page: 0,
+            users_total_pages: 1,
+            named_users: &users,
             audit_events: &events,
+            audit_page: 0,
+            audit_total_pages: 1,
+            per_page: ADMIN_DEFAULT_PAGE_SIZE,
             forum_links: &forum_links,
             subtitle_languages: &[],
             current_user: &admin,
             active_tab: None,
             message: None,
+            missing_artifacts_count: 0,
+            missing_artifacts_cursor: None,
+            recompute_sizes_cursor: None,
+            og_reextraction_count: 0,
         };
         let html = render_admin_panel(&params).into_string();
 
@@ -1282,12 +2326,23 @@ mod tests {
         let admin = test_user(1, "admin", true, true, true);
         let params = AdminPanelParams {
             users: &[admin.clone()],
+            users_total: 1,
+            users_page: 0,
+            users_total_pages: 1,
+            named_users: &[],
             audit_events: &[],
+            audit_page: 0,
+            audit_total_pages: 1,
+            per_page: ADMIN_DEFAULT_PAGE_SIZE,
             forum_links: &[],
             subtitle_languages: &[],
             current_user: &admin,
             active_tab: Some("forum-links"),
             message: Some("Test message"),
+            missing_artifacts_count: 0,
+            missing_artifacts_cursor: None,
+            recompute_sizes_cursor: None,
+            og_reextraction_count: 0,
         };
         let html = render_admin_panel(&params).into_string();
 
@@ -1296,6 +2351,157 @@ mod tests {
         assert!(html.contains("tab-forum-links"));
     }
 
+    #[test]
+    fn test_render_admin_panel_pagination() {
+        let admin = test_user(1, "admin", true, true, true);
+        let users = vec![test_user(30, "user30", false, true, true)];
+        let named_users = vec![admin.clone()];
+        let events = vec![test_audit_event(7, Some(1), "login")];
+        let params = AdminPanelParams {
+            users: &users,
+            users_total: 60,
+            users_page: 1,
+            users_total_pages: 3,
+            named_users: &named_users,
+            audit_events: &events,
+            audit_page: 0,
+            audit_total_pages: 4,
+            per_page: 25,
+            forum_links: &[],
+            subtitle_languages: &[],
+            current_user: &admin,
+            active_tab: None,
+            message: None,
+            missing_artifacts_count: 0,
+            missing_artifacts_cursor: None,
+            recompute_sizes_cursor: None,
+            og_reextraction_count: 0,
+        };
+        let html = render_admin_panel(&params).into_string();
+
+        assert!(html.contains("60 users"));
+        // Each tab pages independently and keeps the page size
+        assert!(html.contains("href=\"/admin?tab=users&amp;per_page=25\""));
+        assert!(html.contains("href=\"/admin?users_page=2&amp;tab=users&amp;per_page=25\""));
+        assert!(html.contains("href=\"/admin?audit_page=1&amp;tab=audit&amp;per_page=25\""));
+        assert!(html.contains("<option value=\"25\" selected"));
+        // Audit users are named even when they aren't on the current users page
+        assert!(html.contains("href=\"/admin/user/1\""));
+        assert!(!html.contains("User #1"));
+    }
+
+    #[test]
+    fn test_render_admin_duplicate_links_page() {
+        let link = |id: i64, url: &str| Link {
+            id,
+            original_url: url.to_string(),
+            normalized_url: url.to_string(),
+            canonical_url: None,
+            final_url: None,
+            domain: "example.com".to_string(),
+            first_seen_at: "2024-01-01 00:00:00".to_string(),
+            last_archived_at: None,
+        };
+        let admin = test_user(1, "admin", true, true, true);
+        let scan = DuplicateLinkScan {
+            groups: vec![DuplicateLinkGroup {
+                normalized_url: "https://example.com/page".to_string(),
+                links: vec![
+                    link(3, "https://example.com/page"),
+                    link(7, "https://example.com/page?ref=x"),
+                    link(9, "https://EXAMPLE.com/page/"),
+                ],
+            }],
+            scanned: 500,
+            last_id: Some(600),
+        };
+
+        let html = render_admin_duplicate_links_page(&scan, 100, None, &admin).into_string();
+        assert!(html.contains("Checked 500 links after #100, up to #600."));
+        assert!(html.contains("1 URL is stored as more than one link."));
+        assert!(html.contains("action=\"/admin/links/merge\""));
+        assert!(html.contains("name=\"keep_id\" value=\"3\""));
+        assert!(html.contains("name=\"merge_ids\" value=\"7,9\""));
+        assert!(html.contains("name=\"after_id\" value=\"100\""));
+        assert!(html.contains("Merge into #3"));
+        assert!(html.contains("/admin/links/duplicates?after_id=600"));
+
+        let clean = DuplicateLinkScan {
+            scanned: 20,
+            last_id: Some(120),
+            ..DuplicateLinkScan::default()
+        };
+        let html = render_admin_duplicate_links_page(&clean, 100, None, &admin).into_string();
+        assert!(html.contains("No duplicate links found."));
+
+        let done =
+            render_admin_duplicate_links_page(&DuplicateLinkScan::default(), 120, None, &admin)
+                .into_string();
+        assert!(done.contains("No more links to check."));
+        assert!(done.contains("Start over"));
+    }
+
+    #[test]
+    fn test_render_admin_bulk_import_page() {
+        let admin = test_user(1, "admin", true, true, true);
+
+        let html = render_admin_bulk_import_page(None, None, 500, &admin).into_string();
+        assert!(html.contains("action=\"/admin/bulk-import\""));
+        assert!(html.contains("enctype=\"multipart/form-data\""));
+        assert!(html.contains("type=\"file\""));
+        assert!(html.contains("Up to <strong>500</strong> URLs"));
+        assert!(!html.contains("Import Summary"));
+
+        let summary = BulkImportSummary {
+            queued: 4,
+            excluded: 1,
+            invalid: 1,
+            invalid_examples: vec!["not a <url>".to_string()],
+            ..Default::default()
+        };
+        let html = render_admin_bulk_import_page(Some(&summary), None, 500, &admin).into_string();
+        assert!(html.contains("Import Summary"));
+        assert!(html.contains("Skipped (excluded domain)"));
+        assert!(html.contains("not a &lt;url&gt;"));
+    }
+
+    #[test]
+    fn test_render_domain_row_subdomain_badge() {
+        let wildcard = test_excluded_domain(1, "*.example.com", true);
+        assert!(render_domain_row(&wildcard)
+            .into_string()
+            .contains("+ subdomains"));
+
+        let exact = test_excluded_domain(2, "example.com", true);
+        assert!(!render_domain_row(&exact)
+            .into_string()
+            .contains("+ subdomains"));
+    }
+
+    #[test]
+    fn test_render_missing_artifacts_card() {
+        let html = render_missing_artifacts_card(0, None).into_string();
+        assert!(html.contains("0 completed video archives"));
+        assert!(!html.contains("/admin/reprocess-missing-artifacts"));
+
+        let html = render_missing_artifacts_card(12, Some(340)).into_string();
+        assert!(html.contains("12 completed video archives"));
+        assert!(html.contains("/admin/reprocess-missing-artifacts"));
+        assert!(html.contains(r#"value="340""#));
+        assert!(html.contains("Continue after archive #340"));
+    }
+
+    #[test]
+    fn test_render_og_reextraction_card() {
+        let html = render_og_reextraction_card(0).into_string();
+        assert!(html.contains("0 archives have no Open Graph title"));
+        assert!(!html.contains("/admin/og/reset"));
+
+        let html = render_og_reextraction_card(1).into_string();
+        assert!(html.contains("1 archive has no Open Graph title"));
+        assert!(html.contains("/admin/og/reset"));
+    }
+
     #[test]
     fn test_render_forum_links_table_empty() {
         let user_lookup: HashMap<i64, &User> = HashMap::new();
@@ -1355,6 +2561,10 @@ mod tests {
         let domains = vec![test_excluded_domain(1, "example.com", true)];
         let html = render_admin_excluded_domains_page(
             &domains,
+            &[],
+            &[],
+            &[],
+            true,
             Some("Domain added successfully!"),
             &admin,
         )
@@ -1371,14 +2581,146 @@ mod tests {
     #[test]
     fn test_render_admin_excluded_domains_page_no_message() {
         let admin = test_user(1, "admin", true, true, true);
-        let html = render_admin_excluded_domains_page(&[], None, &admin).into_string();
+        let html = render_admin_excluded_domains_page(&[], &[], &[], &[], true, None, &admin)
+            .into_string();
 
         assert!(html.contains("Excluded Domains"));
         assert!(html.contains("No excluded domains yet"));
+        assert!(html.contains("No quote-only overrides yet"));
+        assert!(html.contains("No priority domains yet"));
+        assert!(html.contains("No versioned domains yet"));
         // Should not contain any alert
         assert!(!html.contains("class=\"success\""));
     }
 
+    #[test]
+    fn test_render_priority_domains_section() {
+        let domains = vec![PriorityDomain {
+            id: 1,
+            domain: "*.gov.au".to_string(),
+            reason: "Media releases get pulled".to_string(),
+            created_at: "2024-01-01 00:00:00".to_string(),
+            created_by_user_id: Some(1),
+        }];
+        let html = render_priority_domains_section(&domains).into_string();
+
+        assert!(html.contains("Priority Domains"));
+        assert!(html.contains("/admin/priority-domains/add"));
+        assert!(html.contains("/admin/priority-domains/delete"));
+        assert!(html.contains("*.gov.au"));
+        assert!(html.contains("Media releases get pulled"));
+    }
+
+    #[test]
+    fn test_render_quote_policies_section() {
+        let policies = vec![
+            DomainQuotePolicy {
+                id: 1,
+                domain: "courtlistener.com".to_string(),
+                archive_quote_only: true,
+                created_at: "2024-01-01 00:00:00".to_string(),
+                created_by_user_id: Some(1),
+                updated_at: "2024-01-01 00:00:00".to_string(),
+            },
+            DomainQuotePolicy {
+                id: 2,
+                domain: "*.x.com".to_string(),
+                archive_quote_only: false,
+                created_at: "2024-01-01 00:00:00".to_string(),
+                created_by_user_id: Some(1),
+                updated_at: "2024-01-01 00:00:00".to_string(),
+            },
+        ];
+        let html = render_quote_policies_section(&policies, false).into_string();
+
+        assert!(html.contains("Quote-Only Link Overrides"));
+        assert!(html.contains("not archived"));
+        assert!(html.contains("/admin/quote-policies/set"));
+        assert!(html.contains("/admin/quote-policies/delete"));
+        assert!(html.contains("courtlistener.com"));
+        assert!(html.contains("Always archive"));
+        assert!(html.contains("*.x.com"));
+        assert!(html.contains("Never archive"));
+    }
+
+    #[test]
+    fn test_render_admin_external_services_page() {
+        let admin = test_user(1, "admin", true, true, true);
+        let defaults = ExternalServices {
+            wayback: true,
+            archive_today: false,
+            ipfs: false,
+        };
+        let empty = render_admin_external_services_page(&[], defaults, &admin).into_string();
+        assert!(empty.contains("No external service rules yet"));
+
+        let rules = vec![ExternalServiceRule {
+            id: 7,
+            scope: "content_type".to_string(),
+            pattern: "video".to_string(),
+            wayback: Some(false),
+            archive_today: None,
+            ipfs: Some(true),
+            created_at: "2024-01-01 00:00:00".to_string(),
+            created_by_user_id: Some(1),
+            updated_at: "2024-01-01 00:00:00".to_string(),
+        }];
+        let html = render_admin_external_services_page(&rules, defaults, &admin).into_string();
+
+        assert!(html.contains("External Service Rules"));
+        assert!(html.contains("/admin/external-services/set"));
+        assert!(html.contains("/admin/external-services/delete"));
+        assert!(html.contains("Content type"));
+        assert!(html.contains("<code>video</code>"));
+        assert!(html.contains("<td>Off</td>"));
+        assert!(html.contains("<td>Default</td>"));
+        assert!(html.contains("<td>On</td>"));
+    }
+
+    #[test]
+    fn test_render_audit_metadata() {
+        assert_eq!(render_audit_metadata(None).into_string(), "\u{2014}");
+
+        let inline = render_audit_metadata(Some(r#"{"domain":"example.com"}"#)).into_string();
+        assert!(inline.contains("domain:"));
+        assert!(inline.contains("example.com"));
+        assert!(!inline.contains("<details"));
+
+        let nested =
+            render_audit_metadata(Some(r#"{"changes":{"before":"a","after":"b"}}"#)).into_string();
+        assert!(nested.contains("<details"));
+        assert!(nested.contains("&quot;before&quot;"));
+
+        let invalid = render_audit_metadata(Some("not json")).into_string();
+        assert!(invalid.contains("<code>not json</code>"));
+    }
+
+    #[test]
+    fn test_render_admin_audit_page_with_filters() {
+        let admin = test_user(1, "admin", true, true, true);
+        let users = vec![admin.clone()];
+        let events = vec![test_audit_event(1, Some(1), "login")];
+        let event_types = vec!["login".to_string(), "user_approved".to_string()];
+        let params = AdminAuditPageParams {
+            events: &events,
+            users: &users,
+            event_types: &event_types,
+            event_type_filter: Some("login"),
+            user_id_filter: Some(1),
+            total: 120,
+            page: 0,
+            total_pages: 3,
+            current_user: &admin,
+        };
+
+        let html = render_admin_audit_page(&params).into_string();
+
+        assert!(html.contains("Audit Log"));
+        assert!(html.contains("120 matching events"));
+        assert!(html.contains(r#"<option value="login" selected>"#));
+        assert!(html.contains("/admin/audit?page=1&amp;event_type=login&amp;user_id=1"));
+    }
+
     #[test]
     fn test_audit_row_with_user() {
         let user = test_user(1, "admin", true, true, true);
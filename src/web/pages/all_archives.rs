@@ -4,10 +4,11 @@
 //! large datasets with 1000 entries per page.
 
 use maud::{html, Markup};
+use urlencoding::encode;
 
 use crate::components::badge::StatusVariant;
-use crate::components::{BaseLayout, MediaTypeBadge, Pagination};
-use crate::db::{ArchiveDisplay, User};
+use crate::components::{BaseLayout, MediaTypeBadge, Pagination, SizeBadge};
+use crate::db::{ArchiveDisplay, ArchiveSort, SortDirection, User};
 
 /// Parameters for the all archives table page.
 #[derive(Debug)]
@@ -17,9 +18,64 @@ pub struct AllArchivesPageParams<'a> {
     pub total_pages: usize,
     pub content_type_filter: Option<&'a str>,
     pub source_filter: Option<&'a str>,
+    pub sort_by: ArchiveSort,
+    pub sort_dir: SortDirection,
     pub user: Option<&'a User>,
 }
 
+impl AllArchivesPageParams<'_> {
+    /// Whether the table uses its default order (ID, newest first).
+    fn is_default_sort(&self) -> bool {
+        self.sort_by == ArchiveSort::Id && self.sort_dir == SortDirection::Desc
+    }
+
+    /// Pagination that preserves the current filters and sort order.
+    fn pagination(&self) -> Pagination {
+        let sort = (!self.is_default_sort()).then_some((self.sort_by, self.sort_dir));
+        Pagination::new(self.page, self.total_pages, "/archives/all")
+            .with_content_type_filter(self.content_type_filter)
+            .with_source_filter(self.source_filter)
+            .with_param("sort_by", sort.map(|(by, _)| by.as_param()))
+            .with_param("sort_dir", sort.map(|(_, dir)| dir.as_param()))
+    }
+
+    /// Link for a column header: toggles direction if the table is already
+    /// sorted by `key`, otherwise sorts by it in its default direction.
+    fn sort_url(&self, key: ArchiveSort) -> String {
+        let dir = if self.sort_by == key {
+            self.sort_dir.reversed()
+        } else {
+            key.default_direction()
+        };
+        let mut url = format!(
+            "/archives/all?sort_by={}&sort_dir={}",
+            key.as_param(),
+            dir.as_param()
+        );
+        if let Some(ct) = self.content_type_filter {
+            url.push_str(&format!("&type={}", encode(ct)));
+        }
+        if let Some(source) = self.source_filter {
+            url.push_str(&format!("&source={}", encode(source)));
+        }
+        url
+    }
+
+    /// A clickable column header for `key`, marked with the current direction.
+    fn sort_header(&self, label: &str, key: ArchiveSort) -> Markup {
+        let indicator = match (self.sort_by == key, self.sort_dir) {
+            (false, _) => "",
+            (true, SortDirection::Asc) => " \u{25b2}",
+            (true, SortDirection::Desc) => " \u{25bc}",
+        };
+        html! {
+            th class="sortable" {
+                a href=(self.sort_url(key)) { (label) (indicator) }
+            }
+        }
+    }
+}
+
 /// Render the all archives table page.
 #[must_use]
 pub fn render_all_archives_table_page(params: &AllArchivesPageParams) -> Markup {
@@ -33,9 +89,7 @@ pub fn render_all_archives_table_page(params: &AllArchivesPageParams) -> Markup
 
             // Pagination at top
             @if params.total_pages > 1 {
-                (Pagination::new(params.page, params.total_pages, "/archives/all")
-                    .with_content_type_filter(params.content_type_filter)
-                    .with_source_filter(params.source_filter))
+                (params.pagination())
             }
 
             // Table
@@ -46,10 +100,12 @@ pub fn render_all_archives_table_page(params: &AllArchivesPageParams) -> Markup
                     thead {
                         tr {
                             th { "Status" }
-                            th { "ID" }
+                            (params.sort_header("ID", ArchiveSort::Id))
                             th { "Type" }
                             th { "Title" }
-                            th { "URL" }
+                            (params.sort_header("URL", ArchiveSort::Domain))
+                            (params.sort_header("Size", ArchiveSort::Size))
+                            (params.sort_header("Archived", ArchiveSort::Activity))
                         }
                     }
                     tbody {
@@ -62,9 +118,7 @@ pub fn render_all_archives_table_page(params: &AllArchivesPageParams) -> Markup
 
             // Pagination at bottom
             @if params.total_pages > 1 {
-                (Pagination::new(params.page, params.total_pages, "/archives/all")
-                    .with_content_type_filter(params.content_type_filter)
-                    .with_source_filter(params.source_filter))
+                (params.pagination())
             }
         }
     };
@@ -111,6 +165,16 @@ fn render_archive_table_row(archive: &ArchiveDisplay) -> Markup {
             td class="url-cell" title=(archive.original_url) {
                 (archive.original_url)
             }
+            td {
+                @if let Some(bytes) = archive.total_size_bytes.filter(|b| *b > 0) {
+                    (SizeBadge::format_bytes(bytes))
+                }
+            }
+            td {
+                @if let Some(archived_at) = archive.archived_at.as_deref() {
+                    (archived_at.get(..10).unwrap_or(archived_at))
+                }
+            }
         }
     }
 }
@@ -157,6 +221,8 @@ mod tests {
             total_pages: 0,
             content_type_filter: None,
             source_filter: None,
+            sort_by: ArchiveSort::Id,
+            sort_dir: SortDirection::Desc,
             user: None,
         };
 
@@ -189,6 +255,8 @@ mod tests {
             total_pages: 1,
             content_type_filter: None,
             source_filter: None,
+            sort_by: ArchiveSort::Id,
+            sort_dir: SortDirection::Desc,
             user: None,
         };
 
@@ -196,9 +264,40 @@ mod tests {
 
         assert!(html.contains("<table class=\"archives-table\">"));
         assert!(html.contains("<th>Status</th>"));
-        assert!(html.contains("<th>ID</th>"));
+        assert!(html.contains(">ID \u{25bc}</a>"));
         assert!(html.contains("<th>Type</th>"));
         assert!(html.contains("<th>Title</th>"));
-        assert!(html.contains("<th>URL</th>"));
+        assert!(html.contains(">URL</a>"));
+        assert!(html.contains(">Size</a>"));
+        assert!(html.contains(">Archived</a>"));
+    }
+
+    #[test]
+    fn test_sort_headers_toggle_direction_and_keep_filters() {
+        let params = AllArchivesPageParams {
+            archives: &[],
+            page: 0,
+            total_pages: 1,
+            content_type_filter: Some("video"),
+            source_filter: None,
+            sort_by: ArchiveSort::Size,
+            sort_dir: SortDirection::Desc,
+            user: None,
+        };
+
+        // Current column flips direction
+        assert_eq!(
+            params.sort_url(ArchiveSort::Size),
+            "/archives/all?sort_by=size&sort_dir=asc&type=video"
+        );
+        // Other columns start in their default direction
+        assert_eq!(
+            params.sort_url(ArchiveSort::Domain),
+            "/archives/all?sort_by=domain&sort_dir=asc&type=video"
+        );
+        assert_eq!(
+            params.sort_url(ArchiveSort::Id),
+            "/archives/all?sort_by=id&sort_dir=desc&type=video"
+        );
     }
 }
@@ -17,14 +17,21 @@
 
 use maud::{html, Markup, PreEscaped, Render};
 
+use crate::archiver::classify_failure;
+use crate::archiver::redirects::{parse_chain, RedirectHop};
 use crate::components::{
     render_media_player_with_options, AudioPlayer, BaseLayout, Button, Carousel, KeyValueTable,
     MediaTypeBadge, NsfwBadge, NsfwWarning, OpenGraphMetadata, StatusBadge, Table, TableRow,
     TableVariant,
 };
 use crate::db::{
-    Archive, ArchiveArtifact, ArchiveJob, Link, LinkOccurrenceWithPost, SubtitleLanguage, User,
+    Archive, ArchiveArtifact, ArchiveJob, ArchiveVersion, ContentVersion, Link,
+    LinkOccurrenceWithPost, SubtitleLanguage, User, WatchedLink,
 };
+use crate::ipfs::gateway_urls_for;
+use crate::web::diff::DiffResult;
+
+use super::comparison::DiffView;
 
 /// Parameters for rendering the archive detail page.
 #[derive(Debug)]
@@ -41,6 +48,8 @@ pub struct ArchiveDetailParams<'a> {
     pub jobs: &'a [ArchiveJob],
     /// Related archives in quote/reply chain (for Twitter).
     pub quote_reply_chain: &'a [Archive],
+    /// The author's self-thread this archive belongs to, root first (for Twitter).
+    pub self_thread: &'a [Archive],
     /// Current authenticated user (if any).
     pub user: Option<&'a User>,
     /// Whether the archive has missing artifacts (e.g., subtitles, transcripts).
@@ -49,6 +58,83 @@ pub struct ArchiveDetailParams<'a> {
     pub og_metadata: Option<OpenGraphMetadata>,
     /// Subtitle language info keyed by artifact ID.
     pub subtitle_languages: &'a std::collections::HashMap<i64, SubtitleLanguage>,
+    /// Whether the user was redirected here after submitting an already-archived URL.
+    pub already_archived: bool,
+    /// Watch settings if the link is being checked for content changes.
+    pub watched: Option<&'a WatchedLink>,
+    /// Recorded content versions for the link, newest first.
+    pub content_versions: &'a [ContentVersion],
+    /// Preserved earlier captures of this archive, newest first.
+    pub archive_versions: &'a [ArchiveVersion],
+    /// The preserved capture chosen for comparison, if any.
+    pub selected_version: Option<&'a ArchiveVersion>,
+    /// Diff from the selected capture's text to the current text.
+    pub version_diff: Option<&'a DiffResult>,
+    /// Whether the archive itself opted into versioning.
+    pub keep_versions: bool,
+    /// Configured public IPFS gateway base URLs.
+    pub ipfs_gateway_urls: &'a [String],
+    /// Public base URL, used to build the copyable permalink.
+    pub public_base_url: &'a str,
+}
+
+/// Build Open Graph / Twitter card metadata for an archive detail page.
+///
+/// Prefers the archive's own title and thumbnail (served via `/s3/`) over
+/// metadata extracted from the original page. URLs are absolute, rooted at
+/// `public_base_url`, so chat and social previews can resolve them.
+#[must_use]
+pub fn archive_og_metadata(
+    archive: &Archive,
+    link: &Link,
+    public_base_url: &str,
+    og_title: Option<&str>,
+    og_description: Option<&str>,
+    og_image: Option<&str>,
+    og_type: Option<&str>,
+) -> OpenGraphMetadata {
+    let base = public_base_url.trim_end_matches('/');
+    let title = archive
+        .content_title
+        .as_deref()
+        .filter(|t| !t.trim().is_empty())
+        .or(og_title)
+        .unwrap_or("Untitled Archive");
+
+    let description = og_description.map_or_else(
+        || {
+            archive.content_text.as_deref().map_or_else(
+                || format!("Archived content from {}", link.domain),
+                |text| crate::components::truncate_text(text, 200),
+            )
+        },
+        str::to_string,
+    );
+
+    // Never expose images for NSFW archives
+    let image = if archive.is_nsfw {
+        None
+    } else {
+        archive
+            .s3_key_thumb
+            .as_deref()
+            .map(|key| format!("{base}/s3/{key}"))
+            .or_else(|| og_image.map(str::to_string))
+    };
+
+    let url = format!("{base}/archive/{}", archive.id);
+    let oembed_url = format!("{base}/oembed?url={}", urlencoding::encode(&url));
+
+    OpenGraphMetadata::new(title, description, &url)
+        .with_type(og_type.unwrap_or("article"))
+        .with_twitter_card(if image.is_some() {
+            "summary_large_image"
+        } else {
+            "summary"
+        })
+        .with_image(image)
+        .with_nsfw(archive.is_nsfw)
+        .with_oembed_url(oembed_url)
 }
 
 /// Render the archive detail page.
@@ -56,6 +142,7 @@ pub struct ArchiveDetailParams<'a> {
 pub fn render_archive_detail_page(params: &ArchiveDetailParams<'_>) -> Markup {
     let archive = params.archive;
     let link = params.link;
+    let is_admin = params.user.is_some_and(|u| u.is_admin);
     let title = archive
         .content_title
         .as_deref()
@@ -67,6 +154,10 @@ pub fn render_archive_detail_page(params: &ArchiveDetailParams<'_>) -> Markup {
             (NsfwWarning::new())
         }
 
+        @if params.already_archived {
+            (render_already_archived_notice())
+        }
+
         // Page header with title and NSFW badge
         h1 {
             (title)
@@ -80,6 +171,7 @@ pub fn render_archive_detail_page(params: &ArchiveDetailParams<'_>) -> Markup {
             // Archive header metadata
             header {
                 (render_archive_header(archive, link))
+                (render_permalink(archive, params.public_base_url))
             }
 
             // Auto-refresh notification for pending/processing archives
@@ -93,13 +185,18 @@ pub fn render_archive_detail_page(params: &ArchiveDetailParams<'_>) -> Markup {
             }
 
             // Archive actions section (for authorized users)
-            (render_actions_section(archive, params.user, params.has_missing_artifacts))
+            (render_actions_section(archive, params.user, params.has_missing_artifacts, params.watched))
 
             // Quote/reply chain section (for Twitter)
             @if archive.quoted_archive_id.is_some() || archive.reply_to_archive_id.is_some() {
                 (render_quote_reply_chain(archive, params.quote_reply_chain))
             }
 
+            // Author's self-thread (for Twitter)
+            @if params.self_thread.len() > 1 {
+                (render_self_thread(archive, params.self_thread))
+            }
+
             // Media and transcript sections (with optional side-by-side layout)
             (render_media_and_transcript_sections(archive, link, params.artifacts, params.subtitle_languages))
 
@@ -120,7 +217,7 @@ pub fn render_archive_detail_page(params: &ArchiveDetailParams<'_>) -> Markup {
             (render_artifacts_section(archive, link, params.artifacts, params.subtitle_languages))
 
             // External archive links (Wayback, Archive.today, IPFS)
-            (render_external_archives_section(archive))
+            (render_external_archives_section(archive, params.ipfs_gateway_urls))
         }
 
         // Link occurrences section
@@ -128,6 +225,16 @@ pub fn render_archive_detail_page(params: &ArchiveDetailParams<'_>) -> Markup {
             (render_occurrences_section(params.occurrences))
         }
 
+        // Content versions recorded by change watching
+        @if !params.content_versions.is_empty() || params.watched.is_some() {
+            (render_content_versions_section(archive, params.watched, params.content_versions))
+        }
+
+        // Earlier captures preserved by versioning
+        @if !params.archive_versions.is_empty() || is_admin {
+            (render_archive_versions_section(archive, params))
+        }
+
         // Archive jobs section (collapsible)
         @if !params.jobs.is_empty() {
             (render_jobs_section(params.jobs))
@@ -260,6 +367,26 @@ fn render_archive_header(archive: &Archive, link: &Link) -> Markup {
     }
 }
 
+/// Render the archive's short permalink with a copy button.
+fn render_permalink(archive: &Archive, public_base_url: &str) -> Markup {
+    let Some(code) = archive.short_code.as_deref() else {
+        return html! {};
+    };
+    let path = format!("/a/{code}");
+    let permalink = format!("{}{path}", public_base_url.trim_end_matches('/'));
+
+    html! {
+        div class="archive-url-section" {
+            div class="info-label" { "Permalink" }
+            div class="info-value" {
+                a href=(path) class="archive-url-link" { (permalink) }
+                " "
+                button type="button" class="btn btn-sm btn-secondary" data-copy-url=(permalink) { "Copy permalink" }
+            }
+        }
+    }
+}
+
 /// Check if any engagement metrics are present.
 fn has_engagement_metrics(archive: &Archive) -> bool {
     archive.view_count.is_some()
@@ -346,6 +473,15 @@ fn render_error_section(archive: &Archive) -> Markup {
                     strong { "Error:" } " "
                     code { (error) }
                 }
+                @let (_, class) = classify_failure(error, archive.http_status_code, false);
+                p class="failure-class" {
+                    strong { "Failure Type:" } " " (class.reason())
+                    @if archive.status == "skipped" {
+                        " (permanent, not retried)"
+                    } @else if archive.status == "failed" {
+                        " (will retry)"
+                    }
+                }
             }
 
             @if let Some(ref last_attempt) = archive.last_attempt_at {
@@ -361,11 +497,21 @@ fn render_error_section(archive: &Archive) -> Markup {
     }
 }
 
+/// Short label for where an archive's NSFW status came from.
+fn nsfw_source_label(source: Option<&str>) -> Option<&'static str> {
+    match source? {
+        "manual" => Some("set manually"),
+        "auto" => Some("auto-classified"),
+        _ => Some("detected from source"),
+    }
+}
+
 /// Render archive actions section for authorized users.
 fn render_actions_section(
     archive: &Archive,
     user: Option<&User>,
     has_missing_artifacts: bool,
+    watched: Option<&WatchedLink>,
 ) -> Markup {
     let is_admin = user.map(|u| u.is_admin).unwrap_or(false);
     let is_approved = user.map(|u| u.is_approved).unwrap_or(false);
@@ -403,6 +549,17 @@ fn render_actions_section(
                         }
                     }
 
+                    // Re-run OG extraction - admins only, when extraction found no title
+                    @if is_admin && archive.og_extraction_attempted && archive.og_title.is_none() {
+                        form method="post" action=(format!("/archive/{}/reset-og", archive.id))
+                             style="display: inline;" {
+                            button type="submit" class="debug-button"
+                                   title="Re-run Open Graph metadata extraction from the stored HTML" {
+                                "\u{1F3F7}\u{FE0F} Re-extract Metadata"  // 🏷️
+                            }
+                        }
+                    }
+
                     // Toggle NSFW button - approved users and admins
                     @if is_approved || is_admin {
                         form method="post" action=(format!("/archive/{}/toggle-nsfw", archive.id))
@@ -414,6 +571,12 @@ fn render_actions_section(
                                     "\u{1F51E} Toggle NSFW"  // 🔞
                                 }
                             }
+                            @if let Some(source) = nsfw_source_label(archive.nsfw_source.as_deref()) {
+                                " "
+                                span class="nsfw-source" title="Where the current NSFW status came from" {
+                                    "(" (source) ")"
+                                }
+                            }
                         }
                     }
 
@@ -428,6 +591,29 @@ fn render_actions_section(
                         }
                     }
 
+                    // Watch for changes - admins only
+                    @if is_admin {
+                        @if let Some(watch) = watched {
+                            form method="post" action=(format!("/archive/{}/unwatch", archive.id))
+                                 style="display: inline;" {
+                                button type="submit" class="debug-button"
+                                       title=(format!("Checked every {} hours", watch.check_interval_hours)) {
+                                    "\u{1F441}\u{FE0F} Stop Watching"  // 👁️
+                                }
+                            }
+                        } @else {
+                            form method="post" action=(format!("/archive/{}/watch", archive.id))
+                                 style="display: inline;" {
+                                input type="number" name="interval_hours" placeholder="Hours"
+                                      min="1" style="width: 80px;";
+                                button type="submit" class="debug-button"
+                                       title="Periodically re-fetch this link and record content changes" {
+                                    "\u{1F441}\u{FE0F} Watch for Changes"  // 👁️
+                                }
+                            }
+                        }
+                    }
+
                     // Delete button - admins only
                     @if is_admin {
                         form method="post" action=(format!("/archive/{}/delete", archive.id))
@@ -445,7 +631,7 @@ fn render_actions_section(
     }
 }
 
-/// Render quote/reply chain section for Twitter/X archives.
+/// Render quote/reply chain section for Twitter/X and Bluesky archives.
 fn render_quote_reply_chain(archive: &Archive, chain: &[Archive]) -> Markup {
     html! {
         section class="quote-reply-chain" {
@@ -484,6 +670,30 @@ fn render_quote_reply_chain(archive: &Archive, chain: &[Archive]) -> Markup {
     }
 }
 
+/// Render the author's self-thread, root first, highlighting this archive.
+fn render_self_thread(archive: &Archive, thread: &[Archive]) -> Markup {
+    html! {
+        section class="self-thread" {
+            h2 { "Thread (" (thread.len()) " posts)" }
+            ol class="chain-list self-thread-list" {
+                @for thread_archive in thread {
+                    @let title = thread_archive.content_title.as_deref().unwrap_or("Tweet");
+                    @if thread_archive.id == archive.id {
+                        li class="self-thread-current" { strong { (title) } }
+                    } @else {
+                        li {
+                            a href=(format!("/archive/{}", thread_archive.id)) { (title) }
+                            @if thread_archive.status != "complete" {
+                                " " span class="self-thread-status" { "(" (thread_archive.status) ")" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Render a single item in the quote/reply chain.
 fn render_chain_item(target_id: i64, chain: &[Archive], item_type: &str) -> Markup {
     let (label, icon, class) = if item_type == "quote" {
@@ -548,7 +758,7 @@ fn render_media_and_transcript_sections(
                     // Render playlist content if it's a playlist
                     @if archive.content_type.as_deref() == Some("playlist") {
                         @if let Some(ref metadata_json) = archive.content_text {
-                            (render_playlist_content(metadata_json))
+                            (render_playlist_content(metadata_json, archive.id))
                         }
                     }
                 }
@@ -592,7 +802,7 @@ fn render_content_sections(
         // Playlist content
         @if archive.content_type.as_deref() == Some("playlist") {
             @if let Some(ref metadata_json) = archive.content_text {
-                (render_playlist_content(metadata_json))
+                (render_playlist_content(metadata_json, archive.id))
             }
         } @else if let Some(ref text) = archive.content_text {
             // Plaintext content - expanded for Twitter, collapsible for others
@@ -681,9 +891,31 @@ fn render_transcript_section(
 
     let download_name = suggested_download_filename(&link.domain, archive_id, &transcript.s3_key);
 
+    // Transcripts generated locally by Whisper are flagged so they aren't
+    // mistaken for platform-provided captions.
+    let is_whisper = transcript
+        .metadata
+        .as_ref()
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .and_then(|json| {
+            json.get("source")
+                .and_then(|s| s.as_str())
+                .map(|s| s == "whisper")
+        })
+        .unwrap_or(false);
+
     html! {
         section class="transcript-section" {
-            h2 { "Video Transcript" }
+            h2 {
+                "Video Transcript"
+                @if is_whisper {
+                    " "
+                    span class="badge-auto-transcribed"
+                         title="Generated locally from the audio with Whisper; may contain errors" {
+                        "Auto-transcribed"
+                    }
+                }
+            }
 
             div style="margin-bottom: 1rem;" {
                 input type="text" id="transcript-search" placeholder="Search transcript..."
@@ -949,7 +1181,9 @@ fn render_chapters_ui(chapters: &[VideoChapter]) -> Markup {
 }
 
 /// Render playlist content from JSON metadata.
-fn render_playlist_content(metadata_json: &str) -> Markup {
+///
+/// Links to the playlist page, which lists the archived member videos.
+fn render_playlist_content(metadata_json: &str, archive_id: i64) -> Markup {
     let playlist_info: Result<serde_json::Value, _> = serde_json::from_str(metadata_json);
 
     match playlist_info {
@@ -973,6 +1207,9 @@ fn render_playlist_content(metadata_json: &str) -> Markup {
                         @if let Some(channel) = uploader {
                             p { strong { "Channel:" } " " (channel) }
                         }
+                        p {
+                            a href=(format!("/playlist/{archive_id}")) { "View archived videos" }
+                        }
                     }
 
                     @if let Some(video_list) = videos {
@@ -1443,12 +1680,15 @@ fn render_html_embed_section(
 /// Render embedded PDF preview.
 fn render_pdf_embed_section(archive: &Archive, artifacts: &[ArchiveArtifact]) -> Markup {
     // Only show for PDF content type
-    if archive.content_type.as_deref() != Some("pdf") {
+    if !matches!(archive.content_type.as_deref(), Some("pdf" | "document")) {
         return html! {};
     }
 
     // Find the PDF artifact
-    let pdf_artifact = artifacts.iter().find(|a| a.kind == "pdf");
+    let pdf_artifact = artifacts
+        .iter()
+        .find(|a| a.kind == "document")
+        .or_else(|| artifacts.iter().find(|a| a.kind == "pdf"));
 
     let pdf_key = match pdf_artifact {
         Some(artifact) => &artifact.s3_key,
@@ -1478,7 +1718,9 @@ fn render_captures_section(
     link: &Link,
     artifacts: &[ArchiveArtifact],
 ) -> Markup {
-    let screenshot = artifacts.iter().find(|a| a.kind == "screenshot");
+    let screenshot = artifacts
+        .iter()
+        .find(|a| a.kind == "screenshot" || a.kind == "post_snapshot");
     let pdf = artifacts.iter().find(|a| a.kind == "pdf");
     let mhtml = artifacts.iter().find(|a| a.kind == "mhtml");
     let nsfw_attr = if archive.is_nsfw { Some("true") } else { None };
@@ -1676,6 +1918,13 @@ fn render_artifacts_section(
                     strong { "Total Size:" } " " (format_bytes(total_size))
                 }
             }
+
+            p {
+                a href=(format!("/archive/{}/warc{}", archive.id, if archive.is_nsfw { "?nsfw=true" } else { "" }))
+                  title="All archived files as one WARC file, for pywb or replayweb.page" {
+                    "Download as WARC"
+                }
+            }
         }
     }
 }
@@ -1789,7 +2038,10 @@ fn render_artifact_row(
 }
 
 /// Render external archive links section.
-fn render_external_archives_section(archive: &Archive) -> Markup {
+///
+/// IPFS pins link to each configured gateway; with no gateways configured
+/// only the CID is shown.
+fn render_external_archives_section(archive: &Archive, ipfs_gateway_urls: &[String]) -> Markup {
     html! {
         @if let Some(ref wayback) = archive.wayback_url {
             section {
@@ -1814,25 +2066,26 @@ fn render_external_archives_section(archive: &Archive) -> Markup {
         }
 
         @if let Some(ref ipfs_cid) = archive.ipfs_cid {
-            section {
+            @let gateway_links = gateway_urls_for(ipfs_gateway_urls, ipfs_cid);
+            section class="ipfs-section" {
                 h2 { "IPFS" }
-                p { strong { "CID:" } " " code title="Click to copy CID" data-copy-url=(ipfs_cid) { (ipfs_cid) } }
-                p { strong { "Public Gateways:" } }
-                ul {
-                    li {
-                        a href=(format!("https://ipfs.io/ipfs/{}", ipfs_cid))
-                          target="_blank" rel="noopener"
-                          data-copy-url=(format!("https://ipfs.io/ipfs/{}", ipfs_cid)) { "ipfs.io" }
-                    }
-                    li {
-                        a href=(format!("https://dweb.link/ipfs/{}", ipfs_cid))
-                          target="_blank" rel="noopener"
-                          data-copy-url=(format!("https://dweb.link/ipfs/{}", ipfs_cid)) { "dweb.link" }
-                    }
-                    li {
-                        a href=(format!("https://gateway.pinata.cloud/ipfs/{}", ipfs_cid))
-                          target="_blank" rel="noopener"
-                          data-copy-url=(format!("https://gateway.pinata.cloud/ipfs/{}", ipfs_cid)) { "gateway.pinata.cloud" }
+                p {
+                    strong { "CID:" } " " code title="Click to copy CID" data-copy-url=(ipfs_cid) { (ipfs_cid) }
+                    " "
+                    button type="button" class="btn btn-sm btn-secondary copy-cid-btn" data-copy-url=(ipfs_cid) { "Copy CID" }
+                }
+                @if !gateway_links.is_empty() {
+                    p { strong { "Public Gateways:" } }
+                    ul {
+                        @for gateway_url in &gateway_links {
+                            @let label = url::Url::parse(gateway_url)
+                                .ok()
+                                .and_then(|u| u.host_str().map(ToString::to_string))
+                                .unwrap_or_else(|| gateway_url.clone());
+                            li {
+                                a href=(gateway_url) target="_blank" rel="noopener" data-copy-url=(gateway_url) { (label) }
+                            }
+                        }
                     }
                 }
             }
@@ -2016,6 +2269,12 @@ fn render_metadata_section(archive: &Archive, link: &Link) -> Markup {
             html! { code { (final_url) } },
         );
     }
+    if let Some(ref chain_json) = archive.redirect_chain {
+        let chain = parse_chain(chain_json);
+        if !chain.is_empty() {
+            link_table = link_table.item_markup("Redirect Chain", render_redirect_chain(&chain));
+        }
+    }
     link_table = link_table.item("Domain", &link.domain);
     if let Some(ref last_archived) = link.last_archived_at {
         link_table = link_table.item("Last Archived At", last_archived);
@@ -2042,6 +2301,113 @@ fn render_metadata_section(archive: &Archive, link: &Link) -> Markup {
     }
 }
 
+/// Render each redirect hop as `status url`, in request order.
+fn render_redirect_chain(chain: &[RedirectHop]) -> Markup {
+    html! {
+        ol class="redirect-chain" {
+            @for hop in chain {
+                li {
+                    strong { (hop.status) } " " code { (hop.url) }
+                }
+            }
+        }
+    }
+}
+
+/// Render the content versions recorded for a watched link.
+fn render_content_versions_section(
+    archive: &Archive,
+    watched: Option<&WatchedLink>,
+    versions: &[ContentVersion],
+) -> Markup {
+    html! {
+        section class="content-versions" {
+            h2 { "Content Versions" }
+            @if let Some(watch) = watched {
+                p {
+                    "Watching for changes every " (watch.check_interval_hours) " hours."
+                    @if let Some(ref next) = watch.next_check_at {
+                        " Next check: " (next) "."
+                    }
+                }
+            }
+            @if versions.is_empty() {
+                p { em { "No content checks have run yet." } }
+            } @else {
+                ul {
+                    @for version in versions {
+                        li {
+                            a href=(format!("/archive/{}/versions/{}", archive.id, version.id)) {
+                                (version.captured_at)
+                            }
+                            @if version.previous_version_id.is_none() {
+                                " (baseline)"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Render the preserved captures section with a version picker and diff.
+///
+/// The current capture is selected unless `?version=N` picked an earlier one.
+fn render_archive_versions_section(archive: &Archive, params: &ArchiveDetailParams<'_>) -> Markup {
+    let is_admin = params.user.is_some_and(|u| u.is_admin);
+    let selected = params.selected_version.map(|v| v.version);
+
+    html! {
+        section class="archive-versions" id="archive-versions" {
+            h2 { "Previous Captures" }
+            @if is_admin {
+                form method="post" action=(format!("/archive/{}/keep-versions", archive.id)) {
+                    input type="hidden" name="keep_versions"
+                          value=(if params.keep_versions { "false" } else { "true" });
+                    @if params.keep_versions {
+                        "Re-archiving keeps the current capture as a new version. "
+                        button type="submit" class="debug-button" { "Stop Keeping Versions" }
+                    } @else {
+                        "Re-archiving replaces the current capture unless its domain is versioned. "
+                        button type="submit" class="debug-button" { "Keep Versions" }
+                    }
+                }
+            }
+            @if params.archive_versions.is_empty() {
+                p { em { "No earlier captures have been preserved." } }
+            } @else {
+                form method="get" action=(format!("/archive/{}#archive-versions", archive.id)) {
+                    label for="archive-version" { "Capture: " }
+                    select id="archive-version" name="version" onchange="this.form.submit()" {
+                        option value="" selected[selected.is_none()] {
+                            "Current (" (archive.archived_at.as_deref().unwrap_or("pending")) ")"
+                        }
+                        @for version in params.archive_versions {
+                            option value=(version.version) selected[selected == Some(version.version)] {
+                                "Version " (version.version) " ("
+                                (version.archived_at.as_deref().unwrap_or(&version.created_at)) ")"
+                            }
+                        }
+                    }
+                    noscript { " " button type="submit" { "Show" } }
+                }
+                @if let Some(version) = params.selected_version {
+                    @if let Some(ref key) = version.s3_key_primary {
+                        p {
+                            a href=(format!("/s3/{key}")) { "View version " (version.version) }
+                        }
+                    }
+                    h3 { "Changes since version " (version.version) }
+                    @if let Some(diff) = params.version_diff {
+                        (DiffView::new(diff))
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Render comparison form.
 fn render_comparison_form(archive: &Archive) -> Markup {
     let archive_id = archive.id;
@@ -2074,13 +2440,17 @@ fn artifact_kind_display(kind: &str) -> &'static str {
         "complete_html" => "HTML (Full Archive)",
         "mhtml" => "MHTML Archive",
         "screenshot" => "Screenshot",
+        "post_snapshot" => "Post Snapshot",
         "pdf" => "PDF",
+        "document" => "Document",
         "video" => "Video",
+        "audio" => "Audio",
         "thumb" => "Thumbnail",
         "metadata" => "Metadata",
         "image" => "Image",
         "subtitles" => "Subtitles",
         "transcript" => "Transcript",
+        "article" => "Article (Reader Mode)",
         _ => "File",
     }
 }
@@ -2202,13 +2572,19 @@ fn render_platform_comments_section(archive: &Archive, artifacts: &[ArchiveArtif
         None => return html! {}, // No comments artifact, render nothing
     };
 
-    // Extract comment count from metadata if available
-    let comment_count = comments_artifact
+    // Extract comment count (and whether the cap truncated it) from metadata
+    let metadata = comments_artifact
         .metadata
         .as_ref()
-        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok());
+    let comment_count = metadata
+        .as_ref()
         .and_then(|json| json.get("comment_count").and_then(|c| c.as_i64()))
         .unwrap_or(0);
+    let truncated = metadata
+        .as_ref()
+        .and_then(|json| json.get("truncated").and_then(serde_json::Value::as_bool))
+        .unwrap_or(false);
 
     html! {
         section class="platform-comments-section" id="platform-comments" {
@@ -2219,6 +2595,12 @@ fn render_platform_comments_section(archive: &Archive, artifacts: &[ArchiveArtif
                     span class="comments-count-badge" {
                         (comment_count) " comments"
                     }
+                    @if truncated {
+                        " "
+                        span class="comments-truncated-note" title="Extraction stopped at the configured comment limit" {
+                            "(truncated)"
+                        }
+                    }
                 }
 
                 div class="comments-loading-container"
@@ -2233,6 +2615,18 @@ fn render_platform_comments_section(archive: &Archive, artifacts: &[ArchiveArtif
     }
 }
 
+/// Render notice shown when a submitted URL was already archived
+fn render_already_archived_notice() -> Markup {
+    html! {
+        div class="alert alert-info" role="alert" style="margin-bottom: 1rem;" {
+            span class="alert-icon" { "ℹ️" }
+            span class="alert-message" {
+                "This URL has already been archived, so it was not queued again."
+            }
+        }
+    }
+}
+
 /// Render status box for auto-refresh notification
 fn render_auto_refresh_status_box() -> Markup {
     html! {
@@ -2277,6 +2671,7 @@ mod tests {
             next_retry_at: None,
             last_attempt_at: Some("2024-01-15 11:00:00".to_string()),
             http_status_code: Some(200),
+            redirect_chain: None,
             post_date: None,
             quoted_archive_id: None,
             reply_to_archive_id: None,
@@ -2298,6 +2693,9 @@ mod tests {
             platform_comment_count: None,
             save_count: None,
             metrics_backfill_version: None,
+            short_code: None,
+            error_kind: None,
+            skip_reason: None,
         }
     }
 
@@ -2328,6 +2726,10 @@ mod tests {
             duplicate_of_artifact_id: None,
             video_file_id: None,
             metadata: None,
+            placeholder_color: None,
+            width: None,
+            height: None,
+            content_encoding: None,
         }
     }
 
@@ -2359,6 +2761,35 @@ mod tests {
             html.contains("1000 comments"),
             "Badge should show count from metadata"
         );
+        assert!(!html.contains("(truncated)"));
+    }
+
+    #[test]
+    fn test_transcript_section_whisper_badge() {
+        let link = sample_link();
+        let mut transcript = sample_artifact();
+        transcript.kind = "transcript".to_string();
+        transcript.metadata = Some(r#"{"source":"whisper","language":"en"}"#.to_string());
+        let languages = std::collections::HashMap::new();
+
+        let html = render_transcript_section(&transcript, &[], &link, 1, &languages).into_string();
+        assert!(html.contains("badge-auto-transcribed"));
+
+        transcript.metadata = Some(r#"{"source":"manual_subtitles"}"#.to_string());
+        let html = render_transcript_section(&transcript, &[], &link, 1, &languages).into_string();
+        assert!(!html.contains("badge-auto-transcribed"));
+    }
+
+    #[test]
+    fn test_comments_badge_shows_truncation() {
+        let archive = sample_archive();
+        let mut artifact = sample_artifact();
+        artifact.kind = "comments".to_string();
+        artifact.metadata = Some(r#"{"comment_count":500,"truncated":true}"#.to_string());
+
+        let html = render_platform_comments_section(&archive, &[artifact]).into_string();
+        assert!(html.contains("500 comments"));
+        assert!(html.contains("(truncated)"));
     }
 
     #[test]
@@ -2376,10 +2807,20 @@ mod tests {
             occurrences: &[],
             jobs: &jobs,
             quote_reply_chain: &[],
+            self_thread: &[],
             user: None,
             has_missing_artifacts: false,
             og_metadata: None,
             subtitle_languages: &subtitle_languages,
+            already_archived: false,
+            watched: None,
+            content_versions: &[],
+            archive_versions: &[],
+            selected_version: None,
+            version_diff: None,
+            keep_versions: false,
+            ipfs_gateway_urls: &[],
+            public_base_url: "https://archive.example.com",
         };
 
         let html = render_archive_detail_page(&params).into_string();
@@ -2391,6 +2832,142 @@ mod tests {
         assert!(html.contains("Wayback Machine"));
     }
 
+    fn sample_archive_version(version: i64, text: &str) -> ArchiveVersion {
+        ArchiveVersion {
+            id: version,
+            archive_id: 1,
+            version,
+            archived_at: Some(format!("2024-01-0{version} 00:00:00")),
+            s3_prefix: format!("archives/1/versions/{version}/"),
+            s3_key_primary: Some(format!("archives/1/versions/{version}/page.html")),
+            content_title: Some("Test Archive Title".to_string()),
+            content_text: Some(text.to_string()),
+            created_at: "2024-02-01 00:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_archive_versions_defaults_to_current() {
+        let mut archive = sample_archive();
+        archive.content_text = Some("The vote failed.".to_string());
+        let link = sample_link();
+        let subtitle_languages = std::collections::HashMap::new();
+        let versions = vec![
+            sample_archive_version(2, "The vote was delayed."),
+            sample_archive_version(1, "The vote passed."),
+        ];
+
+        let mut params = ArchiveDetailParams {
+            archive: &archive,
+            link: &link,
+            artifacts: &[],
+            occurrences: &[],
+            jobs: &[],
+            quote_reply_chain: &[],
+            self_thread: &[],
+            user: None,
+            has_missing_artifacts: false,
+            og_metadata: None,
+            subtitle_languages: &subtitle_languages,
+            already_archived: false,
+            watched: None,
+            content_versions: &[],
+            archive_versions: &versions,
+            selected_version: None,
+            version_diff: None,
+            keep_versions: false,
+            ipfs_gateway_urls: &[],
+            public_base_url: "https://archive.example.com",
+        };
+
+        let html = render_archive_detail_page(&params).into_string();
+        assert!(html.contains("Previous Captures"));
+        assert!(html.contains(r#"<option value="" selected>Current"#));
+        assert!(html.contains(r#"<option value="2">Version 2"#));
+        assert!(!html.contains("Changes since version"));
+
+        let diff = crate::web::diff::compute_diff(
+            "The vote passed.",
+            archive.content_text.as_deref().unwrap(),
+        );
+        params.selected_version = Some(&versions[1]);
+        params.version_diff = Some(&diff);
+        let html = render_archive_detail_page(&params).into_string();
+        assert!(html.contains(r#"<option value="1" selected>Version 1"#));
+        assert!(html.contains("Changes since version 1"));
+        assert!(html.contains("/s3/archives/1/versions/1/page.html"));
+        assert!(html.contains("diff-added"));
+    }
+
+    #[test]
+    fn test_render_archive_detail_page_og_tags() {
+        let mut archive = sample_archive();
+        archive.s3_key_thumb = Some("archives/1/thumb.jpg".to_string());
+        let link = sample_link();
+        let subtitle_languages = std::collections::HashMap::new();
+
+        let og = archive_og_metadata(
+            &archive,
+            &link,
+            "https://archive.example.com/",
+            Some("Page Title From Site"),
+            Some("A stored description"),
+            Some("https://example.com/og.jpg"),
+            None,
+        );
+        let params = ArchiveDetailParams {
+            archive: &archive,
+            link: &link,
+            artifacts: &[],
+            occurrences: &[],
+            jobs: &[],
+            quote_reply_chain: &[],
+            self_thread: &[],
+            user: None,
+            has_missing_artifacts: false,
+            og_metadata: Some(og),
+            subtitle_languages: &subtitle_languages,
+            already_archived: false,
+            watched: None,
+            content_versions: &[],
+            archive_versions: &[],
+            selected_version: None,
+            version_diff: None,
+            keep_versions: false,
+            ipfs_gateway_urls: &[],
+            public_base_url: "https://archive.example.com",
+        };
+
+        let html = render_archive_detail_page(&params).into_string();
+
+        assert!(html.contains(r#"property="og:title" content="Test Archive Title""#));
+        assert!(html.contains(
+            r#"property="og:image" content="https://archive.example.com/s3/archives/1/thumb.jpg""#
+        ));
+        assert!(html.contains(r#"property="og:description" content="A stored description""#));
+        assert!(
+            html.contains(r#"property="og:url" content="https://archive.example.com/archive/1""#)
+        );
+        assert!(html.contains(r#"name="twitter:card" content="summary_large_image""#));
+        assert!(html.contains(
+            r#"type="application/json+oembed" href="https://archive.example.com/oembed?url=https%3A%2F%2Farchive.example.com%2Farchive%2F1""#
+        ));
+        assert!(html.contains(r#"<meta name="robots" content="noarchive">"#));
+    }
+
+    #[test]
+    fn test_archive_og_metadata_nsfw_has_no_image() {
+        let mut archive = sample_archive();
+        archive.is_nsfw = true;
+        archive.s3_key_thumb = Some("archives/1/thumb.jpg".to_string());
+        let link = sample_link();
+
+        let og = archive_og_metadata(&archive, &link, "https://a.example", None, None, None, None);
+
+        assert!(og.image.is_none());
+        assert_eq!(og.twitter_card, "summary");
+    }
+
     #[test]
     fn test_render_archive_detail_page_nsfw() {
         let mut archive = sample_archive();
@@ -2405,10 +2982,20 @@ mod tests {
             occurrences: &[],
             jobs: &[],
             quote_reply_chain: &[],
+            self_thread: &[],
             user: None,
             has_missing_artifacts: false,
             og_metadata: None,
             subtitle_languages: &subtitle_languages,
+            already_archived: false,
+            watched: None,
+            content_versions: &[],
+            archive_versions: &[],
+            selected_version: None,
+            version_diff: None,
+            keep_versions: false,
+            ipfs_gateway_urls: &[],
+            public_base_url: "https://archive.example.com",
         };
 
         let html = render_archive_detail_page(&params).into_string();
@@ -2433,10 +3020,20 @@ mod tests {
             occurrences: &[],
             jobs: &[],
             quote_reply_chain: &[],
+            self_thread: &[],
             user: None,
             has_missing_artifacts: false,
             og_metadata: None,
             subtitle_languages: &subtitle_languages,
+            already_archived: false,
+            watched: None,
+            content_versions: &[],
+            archive_versions: &[],
+            selected_version: None,
+            version_diff: None,
+            keep_versions: false,
+            ipfs_gateway_urls: &[],
+            public_base_url: "https://archive.example.com",
         };
 
         let html = render_archive_detail_page(&params).into_string();
@@ -2446,6 +3043,122 @@ mod tests {
         assert!(html.contains("status-failed"));
     }
 
+    #[test]
+    fn test_render_archive_detail_page_failure_type() {
+        let mut archive = sample_archive();
+        archive.status = "skipped".to_string();
+        archive.error_message = Some("ERROR: [youtube] abc: Video unavailable".to_string());
+        let link = sample_link();
+        let subtitle_languages = std::collections::HashMap::new();
+
+        let params = ArchiveDetailParams {
+            archive: &archive,
+            link: &link,
+            artifacts: &[],
+            occurrences: &[],
+            jobs: &[],
+            quote_reply_chain: &[],
+            self_thread: &[],
+            user: None,
+            has_missing_artifacts: false,
+            og_metadata: None,
+            subtitle_languages: &subtitle_languages,
+            already_archived: false,
+            watched: None,
+            content_versions: &[],
+            archive_versions: &[],
+            selected_version: None,
+            version_diff: None,
+            keep_versions: false,
+            ipfs_gateway_urls: &[],
+            public_base_url: "https://archive.example.com",
+        };
+
+        let html = render_archive_detail_page(&params).into_string();
+
+        assert!(html.contains("Failure Type:"));
+        assert!(html.contains("Content unavailable"));
+        assert!(html.contains("permanent, not retried"));
+    }
+
+    #[test]
+    fn test_render_archive_detail_page_ipfs_gateways() {
+        let mut archive = sample_archive();
+        archive.ipfs_cid = Some("bafyexample".to_string());
+        let link = sample_link();
+        let subtitle_languages = std::collections::HashMap::new();
+        let gateways = vec![
+            "https://ipfs.io/ipfs/".to_string(),
+            "https://dweb.link/ipfs/".to_string(),
+            "https://gateway.example.org/ipfs/".to_string(),
+        ];
+
+        let params = ArchiveDetailParams {
+            archive: &archive,
+            link: &link,
+            artifacts: &[],
+            occurrences: &[],
+            jobs: &[],
+            quote_reply_chain: &[],
+            self_thread: &[],
+            user: None,
+            has_missing_artifacts: false,
+            og_metadata: None,
+            subtitle_languages: &subtitle_languages,
+            already_archived: false,
+            watched: None,
+            content_versions: &[],
+            archive_versions: &[],
+            selected_version: None,
+            version_diff: None,
+            keep_versions: false,
+            ipfs_gateway_urls: &gateways,
+            public_base_url: "https://archive.example.com",
+        };
+        let html = render_archive_detail_page(&params).into_string();
+
+        for gateway in &gateways {
+            assert!(html.contains(&format!(r#"href="{gateway}bafyexample""#)));
+        }
+        assert!(html.contains(">gateway.example.org</a>"));
+        assert!(html.contains(r#"data-copy-url="bafyexample">Copy CID</button>"#));
+
+        // Without configured gateways only the CID is shown
+        let params = ArchiveDetailParams {
+            ipfs_gateway_urls: &[],
+            public_base_url: "https://archive.example.com",
+            ..params
+        };
+        let html = render_archive_detail_page(&params).into_string();
+        assert!(html.contains("Copy CID"));
+        assert!(!html.contains("Public Gateways"));
+    }
+
+    #[test]
+    fn test_render_self_thread() {
+        let mut head = sample_archive();
+        head.content_title = Some("First tweet".to_string());
+        let mut current = sample_archive();
+        current.id = 2;
+        current.content_title = Some("Second tweet".to_string());
+        let mut pending = sample_archive();
+        pending.id = 3;
+        pending.content_title = None;
+        pending.status = "pending".to_string();
+
+        let thread = [head.clone(), current.clone(), pending];
+        let html = render_self_thread(&current, &thread).into_string();
+
+        assert!(html.contains("Thread (3 posts)"));
+        assert!(html.contains(r#"<a href="/archive/1">First tweet</a>"#));
+        assert!(
+            html.contains(r#"<li class="self-thread-current"><strong>Second tweet</strong></li>"#)
+        );
+        assert!(html.contains(r#"<a href="/archive/3">Tweet</a>"#));
+        assert!(html.contains("(pending)"));
+        assert!(html.find("First tweet") < html.find("Second tweet"));
+    }
+
     #[test]
     fn test_render_archive_header() {
         let archive = sample_archive();
@@ -2462,6 +3175,21 @@ mod tests {
         assert!(html.contains("200"));
     }
 
+    #[test]
+    fn test_render_permalink() {
+        let mut archive = sample_archive();
+        assert!(render_permalink(&archive, "https://archive.example.com")
+            .into_string()
+            .is_empty());
+
+        archive.short_code = Some("aZ09bY18".to_string());
+        let html = render_permalink(&archive, "https://archive.example.com/").into_string();
+        assert!(html.contains(r#"href="/a/aZ09bY18""#));
+        assert!(html.contains(
+            r#"data-copy-url="https://archive.example.com/a/aZ09bY18">Copy permalink</button>"#
+        ));
+    }
+
     #[test]
     fn test_render_artifact_row() {
         let artifact = sample_artifact();
@@ -2563,17 +3291,18 @@ mod tests {
     #[test]
     fn test_render_playlist_content_valid() {
         let json = r#"{"title": "Test Playlist", "video_count": 5, "uploader": "TestChannel", "videos": [{"title": "Video 1", "url": "https://example.com/1", "uploader": "TestChannel", "upload_date": "2024-01-01", "duration": 120}]}"#;
-        let html = render_playlist_content(json).into_string();
+        let html = render_playlist_content(json, 7).into_string();
 
         assert!(html.contains("playlist-section"));
         assert!(html.contains("Test Playlist"));
         assert!(html.contains("TestChannel"));
         assert!(html.contains("Video 1"));
+        assert!(html.contains(r#"href="/playlist/7""#));
     }
 
     #[test]
     fn test_render_playlist_content_invalid() {
-        let html = render_playlist_content("not valid json").into_string();
+        let html = render_playlist_content("not valid json", 7).into_string();
 
         assert!(html.contains("content-text-section"));
         assert!(html.contains("Playlist Metadata"));
@@ -2582,7 +3311,7 @@ mod tests {
     #[test]
     fn test_render_actions_section_no_user() {
         let archive = sample_archive();
-        let html = render_actions_section(&archive, None, false).into_string();
+        let html = render_actions_section(&archive, None, false, None).into_string();
 
         assert!(html.is_empty());
     }
@@ -2606,14 +3335,47 @@ mod tests {
             updated_at: "2024-01-01".to_string(),
         };
 
-        let html = render_actions_section(&archive, Some(&user), false).into_string();
+        let html = render_actions_section(&archive, Some(&user), false, None).into_string();
 
         assert!(html.contains("debug-actions"));
         assert!(html.contains("Re-archive"));
         assert!(html.contains("Toggle NSFW"));
+        assert!(html.contains("Watch for Changes"));
         assert!(html.contains("Delete"));
     }
 
+    #[test]
+    fn test_render_actions_section_shows_nsfw_source() {
+        let mut archive = sample_archive();
+        let user = User {
+            id: 2,
+            username: "member".to_string(),
+            password_hash: "hash".to_string(),
+            email: None,
+            display_name: None,
+            is_approved: true,
+            is_admin: false,
+            is_active: true,
+            failed_login_attempts: 0,
+            locked_until: None,
+            password_updated_at: "2024-01-01".to_string(),
+            created_at: "2024-01-01".to_string(),
+            updated_at: "2024-01-01".to_string(),
+        };
+
+        let html = render_actions_section(&archive, Some(&user), false, None).into_string();
+        assert!(!html.contains("nsfw-source"));
+
+        archive.is_nsfw = true;
+        archive.nsfw_source = Some("auto".to_string());
+        let html = render_actions_section(&archive, Some(&user), false, None).into_string();
+        assert!(html.contains("(auto-classified)"));
+
+        archive.nsfw_source = Some("manual".to_string());
+        let html = render_actions_section(&archive, Some(&user), false, None).into_string();
+        assert!(html.contains("(set manually)"));
+    }
+
     #[test]
     fn test_render_pdf_embed_section() {
         let mut archive = sample_archive();
@@ -2632,6 +3394,10 @@ mod tests {
             duplicate_of_artifact_id: None,
             video_file_id: None,
             metadata: None,
+            placeholder_color: None,
+            width: None,
+            height: None,
+            content_encoding: None,
         };
 
         let html = render_pdf_embed_section(&archive, &[pdf_artifact]).into_string();
@@ -2856,4 +3622,21 @@ Don't forget to like and share!
             "Should not show metrics section when no metrics"
         );
     }
+
+    #[test]
+    fn test_render_metadata_redirect_chain() {
+        let mut archive = sample_archive();
+        let link = sample_link();
+        let html = render_metadata_section(&archive, &link).into_string();
+        assert!(!html.contains("Redirect Chain"));
+
+        archive.redirect_chain = Some(
+            r#"[{"url":"https://bit.ly/abc","status":301},{"url":"https://example.com/page","status":403}]"#
+                .to_string(),
+        );
+        let html = render_metadata_section(&archive, &link).into_string();
+        assert!(html.contains("Redirect Chain"));
+        assert!(html.contains("<strong>301</strong> <code>https://bit.ly/abc</code>"));
+        assert!(html.contains("<strong>403</strong> <code>https://example.com/page</code>"));
+    }
 }
@@ -213,6 +213,7 @@ mod tests {
             next_retry_at: None,
             last_attempt_at: None,
             http_status_code: Some(200),
+            redirect_chain: None,
             post_date: None,
             quoted_archive_id: None,
             reply_to_archive_id: None,
@@ -234,6 +235,9 @@ mod tests {
             platform_comment_count: None,
             save_count: None,
             metrics_backfill_version: None,
+            short_code: None,
+            error_kind: None,
+            skip_reason: None,
         }
     }
 
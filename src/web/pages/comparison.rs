@@ -6,7 +6,7 @@
 use maud::{html, Markup, Render};
 
 use crate::components::BaseLayout;
-use crate::db::{Archive, Link, User};
+use crate::db::{Archive, ContentVersion, Link, User};
 use crate::web::diff::{ChangeType, DiffLine, DiffResult};
 
 /// Truncate a URL for display, adding ellipsis if too long.
@@ -261,6 +261,57 @@ pub fn render_comparison_page(
     BaseLayout::new("Archive Comparison", user).render(content)
 }
 
+/// Render a content version of a watched link, diffed against the version before it.
+#[must_use]
+pub fn render_content_version_page(
+    archive: &Archive,
+    link: &Link,
+    version: &ContentVersion,
+    previous: Option<&ContentVersion>,
+    diff_result: &DiffResult,
+    user: Option<&User>,
+) -> Markup {
+    let title = archive
+        .content_title
+        .as_deref()
+        .unwrap_or("Untitled Archive");
+
+    let content = html! {
+        h1 { "Content Changes" }
+
+        p class="meta" {
+            a href=(format!("/archive/{}", archive.id)) { (title) }
+            br;
+            strong { "URL:" }
+            " "
+            a href=(link.normalized_url) { (truncate_url(&link.normalized_url, 50)) }
+            br;
+            strong { "Captured:" }
+            " " (version.captured_at)
+            @if let Some(prev) = previous {
+                br;
+                strong { "Compared with:" }
+                " "
+                a href=(format!("/archive/{}/versions/{}", archive.id, prev.id)) { (prev.captured_at) }
+            }
+        }
+
+        section {
+            h2 { "Content Diff" }
+            @if previous.is_some() {
+                (DiffView::new(diff_result))
+            } @else {
+                p {
+                    em { "This is the first recorded version of this link's content." }
+                }
+                pre class="content-text" { (version.content_text) }
+            }
+        }
+    };
+
+    BaseLayout::new("Content Changes", user).render(content)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -289,6 +340,7 @@ mod tests {
             next_retry_at: None,
             last_attempt_at: None,
             http_status_code: Some(200),
+            redirect_chain: None,
             post_date: None,
             quoted_archive_id: None,
             reply_to_archive_id: None,
@@ -310,6 +362,9 @@ mod tests {
             platform_comment_count: None,
             save_count: None,
             metrics_backfill_version: None,
+            short_code: None,
+            error_kind: None,
+            skip_reason: None,
         }
     }
 
@@ -551,6 +606,57 @@ mod tests {
         assert!(html.contains("only media"));
     }
 
+    fn sample_version(id: i64, previous_version_id: Option<i64>, text: &str) -> ContentVersion {
+        ContentVersion {
+            id,
+            link_id: 1,
+            archive_id: Some(1),
+            previous_version_id,
+            content_hash: "hash".to_string(),
+            content_text: text.to_string(),
+            captured_at: format!("2024-01-0{id} 00:00:00"),
+        }
+    }
+
+    #[test]
+    fn test_render_content_version_page() {
+        let archive = sample_archive(1, "Watched Article");
+        let link = sample_link();
+        let previous = sample_version(1, None, "The vote passed.");
+        let version = sample_version(2, Some(1), "The vote failed.");
+        let diff_result =
+            crate::web::diff::compute_diff(&previous.content_text, &version.content_text);
+
+        let html = render_content_version_page(
+            &archive,
+            &link,
+            &version,
+            Some(&previous),
+            &diff_result,
+            None,
+        )
+        .into_string();
+
+        assert!(html.contains("Content Changes"));
+        assert!(html.contains("/archive/1/versions/1"));
+        assert!(html.contains("diff-added"));
+        assert!(html.contains("diff-removed"));
+    }
+
+    #[test]
+    fn test_render_content_version_page_baseline() {
+        let archive = sample_archive(1, "Watched Article");
+        let link = sample_link();
+        let version = sample_version(1, None, "Original text");
+        let diff_result = crate::web::diff::compute_diff("", &version.content_text);
+
+        let html = render_content_version_page(&archive, &link, &version, None, &diff_result, None)
+            .into_string();
+
+        assert!(html.contains("first recorded version"));
+        assert!(html.contains("Original text"));
+    }
+
     #[test]
     fn test_comparison_archive_info_long_url() {
         let archive = sample_archive(1, "Test");
@@ -1,20 +1,34 @@
 //! Debug queue page rendering using maud templates.
 //!
 //! This module provides the debug queue page which displays archive queue
-//! statistics, recent failures, and administrative actions.
+//! statistics, a per-domain queue breakdown, failures by category, skipped
+//! archives by reason, recent failures, and administrative actions.
 
 use maud::{html, Markup, Render};
 
+use crate::archiver::{ArchiveErrorKind, SkipReason};
 use crate::components::{BaseLayout, KeyValueTable, StatusBadge, Table, TableRow, TableVariant};
-use crate::db::{Archive, QueueStats, User};
+use crate::db::{Archive, DomainQueueCounts, QueueStats, User};
 
 /// Parameters for rendering the debug queue page.
 #[derive(Debug)]
 pub struct DebugQueueParams<'a> {
     /// Queue statistics.
     pub stats: &'a QueueStats,
+    /// Domains with the most queued archives.
+    pub domain_counts: &'a [DomainQueueCounts],
     /// Recent failed/skipped archives.
     pub recent_failures: &'a [Archive],
+    /// Failed, skipped and auth-required archive counts by `error_kind`.
+    pub failure_kinds: &'a [(Option<String>, i64)],
+    /// Category the recent failures are filtered to, if any.
+    pub error_kind: Option<ArchiveErrorKind>,
+    /// Skipped archive counts by `skip_reason`.
+    pub skip_reasons: &'a [(Option<String>, i64)],
+    /// Skip reason the recent failures are filtered to, if any.
+    pub skip_reason: Option<SkipReason>,
+    /// Effective retry limit for failed archives, if known.
+    pub max_retries: Option<i32>,
     /// Currently logged in user (for header navigation).
     pub user: Option<&'a User>,
     /// CSRF token for form submissions.
@@ -27,12 +41,56 @@ impl<'a> DebugQueueParams<'a> {
     pub fn new(stats: &'a QueueStats, recent_failures: &'a [Archive]) -> Self {
         Self {
             stats,
+            domain_counts: &[],
             recent_failures,
+            failure_kinds: &[],
+            error_kind: None,
+            skip_reasons: &[],
+            skip_reason: None,
+            max_retries: None,
             user: None,
             csrf_token: None,
         }
     }
 
+    /// Set the per-domain queue breakdown.
+    #[must_use]
+    pub fn with_domain_counts(mut self, domain_counts: &'a [DomainQueueCounts]) -> Self {
+        self.domain_counts = domain_counts;
+        self
+    }
+
+    /// Set the failure category counts and the category recent failures are filtered to.
+    #[must_use]
+    pub fn with_failure_kinds(
+        mut self,
+        failure_kinds: &'a [(Option<String>, i64)],
+        error_kind: Option<ArchiveErrorKind>,
+    ) -> Self {
+        self.failure_kinds = failure_kinds;
+        self.error_kind = error_kind;
+        self
+    }
+
+    /// Set the skip reason counts and the reason recent failures are filtered to.
+    #[must_use]
+    pub fn with_skip_reasons(
+        mut self,
+        skip_reasons: &'a [(Option<String>, i64)],
+        skip_reason: Option<SkipReason>,
+    ) -> Self {
+        self.skip_reasons = skip_reasons;
+        self.skip_reason = skip_reason;
+        self
+    }
+
+    /// Set the effective retry limit shown with the queue statistics.
+    #[must_use]
+    pub fn with_max_retries(mut self, max_retries: i32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
     /// Set the current user.
     #[must_use]
     pub fn with_user(mut self, user: Option<&'a User>) -> Self {
@@ -63,13 +121,23 @@ pub fn render_debug_queue_page(params: &DebugQueueParams<'_>) -> Markup {
         h1 { "Debug: Archive Queue Status" }
 
         // Queue Statistics Section
-        (QueueStatsSection::new(params.stats))
+        (QueueStatsSection::new(params.stats, params.max_retries))
+
+        // Per-domain Queue Section
+        (DomainQueueSection::new(params.domain_counts))
+
+        // Failures by Category Section
+        (FailureKindsSection::new(params.failure_kinds, params.error_kind))
+
+        // Skipped Archives by Reason Section
+        (SkipReasonsSection::new(params.skip_reasons, params.skip_reason))
 
         // Actions Section
         (ActionsSection::new(params.stats.skipped_count, params.csrf_token))
 
         // Recent Failures Section
-        (RecentFailuresSection::new(params.recent_failures, params.csrf_token))
+        (RecentFailuresSection::new(params.recent_failures, params.error_kind, params.csrf_token)
+            .with_skip_reason(params.skip_reason))
 
         // Navigation Section
         (NavigationSection)
@@ -81,11 +149,12 @@ pub fn render_debug_queue_page(params: &DebugQueueParams<'_>) -> Markup {
 /// Queue statistics section component.
 struct QueueStatsSection<'a> {
     stats: &'a QueueStats,
+    max_retries: Option<i32>,
 }
 
 impl<'a> QueueStatsSection<'a> {
-    fn new(stats: &'a QueueStats) -> Self {
-        Self { stats }
+    fn new(stats: &'a QueueStats, max_retries: Option<i32>) -> Self {
+        Self { stats, max_retries }
     }
 }
 
@@ -123,6 +192,10 @@ impl Render for QueueStatsSection<'_> {
             html! { span class="stat-complete" { (self.stats.complete_count) } },
         );
 
+        if let Some(max_retries) = self.max_retries {
+            table = table.item("Max Retries", &max_retries.to_string());
+        }
+
         if let Some(ref next_retry) = self.stats.next_retry_at {
             table = table.item("Next Retry At", next_retry);
         }
@@ -140,6 +213,180 @@ impl Render for QueueStatsSection<'_> {
     }
 }
 
+/// Describe an age in seconds with its two largest units, e.g. "3h 12m".
+fn format_age(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let (days, hours) = (seconds / 86_400, (seconds % 86_400) / 3_600);
+    let (minutes, secs) = ((seconds % 3_600) / 60, seconds % 60);
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {secs}s")
+    } else {
+        format!("{secs}s")
+    }
+}
+
+/// Per-domain queue breakdown section component.
+struct DomainQueueSection<'a> {
+    counts: &'a [DomainQueueCounts],
+}
+
+impl<'a> DomainQueueSection<'a> {
+    fn new(counts: &'a [DomainQueueCounts]) -> Self {
+        Self { counts }
+    }
+}
+
+impl Render for DomainQueueSection<'_> {
+    fn render(&self) -> Markup {
+        let rows: Vec<Markup> = self
+            .counts
+            .iter()
+            .map(|row| {
+                let oldest = row.oldest_pending_age_secs.map_or_else(
+                    || "\u{2014}".to_string(), // em dash
+                    format_age,
+                );
+                TableRow::new()
+                    .cell(&row.domain)
+                    .cell(&row.pending_count.to_string())
+                    .cell(&row.processing_count.to_string())
+                    .cell_markup(html! {
+                        span title=(row.oldest_pending_at.as_deref().unwrap_or("")) { (oldest) }
+                    })
+                    .render()
+            })
+            .collect();
+
+        html! {
+            section class="queue-domains" {
+                h2 { "Queue by Domain" }
+
+                @if self.counts.is_empty() {
+                    p { "Nothing queued." }
+                } @else {
+                    (Table::new(vec!["Domain", "Pending", "Processing", "Oldest Pending"])
+                        .variant(TableVariant::Debug)
+                        .class("queue-domains-table")
+                        .rows(rows)
+                        .render())
+                }
+            }
+        }
+    }
+}
+
+/// Failure counts by `error_kind` section component.
+struct FailureKindsSection<'a> {
+    counts: &'a [(Option<String>, i64)],
+    selected: Option<ArchiveErrorKind>,
+}
+
+impl<'a> FailureKindsSection<'a> {
+    fn new(counts: &'a [(Option<String>, i64)], selected: Option<ArchiveErrorKind>) -> Self {
+        Self { counts, selected }
+    }
+}
+
+impl Render for FailureKindsSection<'_> {
+    fn render(&self) -> Markup {
+        let rows: Vec<Markup> = self
+            .counts
+            .iter()
+            .map(|(kind, count)| {
+                let kind = kind.as_deref().and_then(ArchiveErrorKind::from_str);
+                let category = match kind {
+                    // Failures recorded before error kinds existed can't be filtered
+                    None => html! { "Uncategorized" },
+                    Some(kind) if Some(kind) == self.selected => html! {
+                        strong { (kind.label()) }
+                    },
+                    Some(kind) => html! {
+                        a href=(format!("/debug/queue?kind={}", kind.as_str())) { (kind.label()) }
+                    },
+                };
+                TableRow::new()
+                    .cell_markup(category)
+                    .cell(&count.to_string())
+                    .render()
+            })
+            .collect();
+
+        html! {
+            section class="failure-kinds" {
+                h2 { "Failures by Category" }
+
+                @if self.counts.is_empty() {
+                    p { "No failures." }
+                } @else {
+                    (Table::new(vec!["Category", "Archives"])
+                        .variant(TableVariant::Debug)
+                        .class("failure-kinds-table")
+                        .rows(rows)
+                        .render())
+                }
+            }
+        }
+    }
+}
+
+/// Skipped archive counts by `skip_reason` section component.
+struct SkipReasonsSection<'a> {
+    counts: &'a [(Option<String>, i64)],
+    selected: Option<SkipReason>,
+}
+
+impl<'a> SkipReasonsSection<'a> {
+    fn new(counts: &'a [(Option<String>, i64)], selected: Option<SkipReason>) -> Self {
+        Self { counts, selected }
+    }
+}
+
+impl Render for SkipReasonsSection<'_> {
+    fn render(&self) -> Markup {
+        let rows: Vec<Markup> = self
+            .counts
+            .iter()
+            .map(|(reason, count)| {
+                let reason = reason.as_deref().and_then(SkipReason::from_str);
+                let label = match reason {
+                    // Skipped before reasons were recorded
+                    None => html! { "Unknown" },
+                    Some(reason) if Some(reason) == self.selected => html! {
+                        strong { (reason.label()) }
+                    },
+                    Some(reason) => html! {
+                        a href=(format!("/debug/queue?skip_reason={}", reason.as_str())) { (reason.label()) }
+                    },
+                };
+                TableRow::new()
+                    .cell_markup(label)
+                    .cell(&count.to_string())
+                    .render()
+            })
+            .collect();
+
+        html! {
+            section class="skip-reasons" {
+                h2 { "Skipped by Reason" }
+
+                @if self.counts.is_empty() {
+                    p { "No skipped archives." }
+                } @else {
+                    (Table::new(vec!["Reason", "Archives"])
+                        .variant(TableVariant::Debug)
+                        .class("skip-reasons-table")
+                        .rows(rows)
+                        .render())
+                }
+            }
+        }
+    }
+}
+
 /// Actions section component.
 struct ActionsSection<'a> {
     skipped_count: i64,
@@ -186,17 +433,30 @@ impl Render for ActionsSection<'_> {
 /// Recent failures section component.
 struct RecentFailuresSection<'a> {
     failures: &'a [Archive],
+    error_kind: Option<ArchiveErrorKind>,
+    skip_reason: Option<SkipReason>,
     csrf_token: Option<&'a str>,
 }
 
 impl<'a> RecentFailuresSection<'a> {
-    fn new(failures: &'a [Archive], csrf_token: Option<&'a str>) -> Self {
+    fn new(
+        failures: &'a [Archive],
+        error_kind: Option<ArchiveErrorKind>,
+        csrf_token: Option<&'a str>,
+    ) -> Self {
         Self {
             failures,
+            error_kind,
+            skip_reason: None,
             csrf_token,
         }
     }
 
+    const fn with_skip_reason(mut self, skip_reason: Option<SkipReason>) -> Self {
+        self.skip_reason = skip_reason;
+        self
+    }
+
     /// Truncate error message to a reasonable display length.
     fn truncate_error(error: &str, max_len: usize) -> String {
         if error.len() <= max_len {
@@ -211,7 +471,15 @@ impl Render for RecentFailuresSection<'_> {
     fn render(&self) -> Markup {
         html! {
             section class="recent-failures" {
-                h2 { "Recent Failures" }
+                @if let Some(kind) = self.error_kind {
+                    h2 { "Recent Failures: " (kind.label()) }
+                    p { a href="/debug/queue" { "Show all failures" } }
+                } @else if let Some(reason) = self.skip_reason {
+                    h2 { "Recently Skipped: " (reason.label()) }
+                    p { a href="/debug/queue" { "Show all failures" } }
+                } @else {
+                    h2 { "Recent Failures" }
+                }
 
                 @if self.failures.is_empty() {
                     p { "No recent failures." }
@@ -230,6 +498,8 @@ impl RecentFailuresSection<'_> {
             "Status",
             "Retries",
             "Last Attempt",
+            "Category",
+            "Skip Reason",
             "Error",
             "Actions",
         ];
@@ -261,6 +531,18 @@ impl RecentFailuresSection<'_> {
 
         let last_attempt = archive.last_attempt_at.as_deref().unwrap_or("\u{2014}"); // em dash
 
+        let category = archive
+            .error_kind
+            .as_deref()
+            .and_then(ArchiveErrorKind::from_str)
+            .map_or("\u{2014}", |kind| kind.label()); // em dash
+
+        let skip_reason = archive
+            .skip_reason
+            .as_deref()
+            .and_then(SkipReason::from_str)
+            .map_or("\u{2014}", |reason| reason.label()); // em dash
+
         TableRow::new()
             .cell_markup(html! {
                 a href=(format!("/archive/{}", archive.id)) {
@@ -270,6 +552,8 @@ impl RecentFailuresSection<'_> {
             .cell_markup(status_badge.render())
             .cell(&archive.retry_count.to_string())
             .cell(last_attempt)
+            .cell(category)
+            .cell(skip_reason)
             .cell_markup(html! {
                 code title=(full_error) { (error_display) }
             })
@@ -355,6 +639,7 @@ mod tests {
             next_retry_at: Some("2024-01-15 11:00:00".to_string()),
             last_attempt_at: Some("2024-01-15 10:30:00".to_string()),
             http_status_code: Some(404),
+            redirect_chain: None,
             post_date: None,
             quoted_archive_id: None,
             reply_to_archive_id: None,
@@ -376,6 +661,9 @@ mod tests {
             platform_comment_count: None,
             save_count: None,
             metrics_backfill_version: None,
+            short_code: None,
+            error_kind: None,
+            skip_reason: None,
         }
     }
 
@@ -398,6 +686,109 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(42), "42s");
+        assert_eq!(format_age(125), "2m 5s");
+        assert_eq!(format_age(3 * 3_600 + 12 * 60), "3h 12m");
+        assert_eq!(format_age(2 * 86_400 + 5 * 3_600), "2d 5h");
+        assert_eq!(format_age(-5), "0s");
+    }
+
+    #[test]
+    fn test_render_debug_queue_page_domain_breakdown() {
+        let stats = test_queue_stats();
+        let failures: Vec<Archive> = vec![];
+        let params = DebugQueueParams::new(&stats, &failures);
+        let html = render_debug_queue_page(&params).into_string();
+        assert!(html.contains("Queue by Domain"));
+        assert!(html.contains("Nothing queued."));
+
+        let counts = vec![
+            DomainQueueCounts {
+                domain: "slow.example.com".to_string(),
+                pending_count: 12,
+                processing_count: 1,
+                oldest_pending_at: Some("2024-01-15 09:00:00".to_string()),
+                oldest_pending_age_secs: Some(2 * 3_600 + 30 * 60),
+            },
+            DomainQueueCounts {
+                domain: "busy.example.org".to_string(),
+                pending_count: 0,
+                processing_count: 2,
+                oldest_pending_at: None,
+                oldest_pending_age_secs: None,
+            },
+        ];
+        let params = DebugQueueParams::new(&stats, &failures).with_domain_counts(&counts);
+        let html = render_debug_queue_page(&params).into_string();
+
+        assert!(html.contains("queue-domains-table"));
+        assert!(html.contains("slow.example.com"));
+        assert!(html.contains("2h 30m"));
+        assert!(html.contains("title=\"2024-01-15 09:00:00\""));
+        assert!(html.contains("busy.example.org"));
+        assert!(!html.contains("Nothing queued."));
+    }
+
+    #[test]
+    fn test_render_debug_queue_page_failure_kinds() {
+        let stats = test_queue_stats();
+        let mut failure = test_archive(1, "failed", Some("Not found (404)"));
+        failure.error_kind = Some("not_found".to_string());
+        let failures = vec![failure];
+        let html = render_debug_queue_page(&DebugQueueParams::new(&stats, &failures)).into_string();
+        assert!(html.contains("Failures by Category"));
+        assert!(html.contains("No failures."));
+
+        let counts = vec![
+            (Some("not_found".to_string()), 7),
+            (Some("rate_limited".to_string()), 2),
+            (None, 3),
+        ];
+        let params = DebugQueueParams::new(&stats, &failures)
+            .with_failure_kinds(&counts, Some(ArchiveErrorKind::NotFound));
+        let html = render_debug_queue_page(&params).into_string();
+
+        assert!(html.contains("failure-kinds-table"));
+        assert!(html.contains("href=\"/debug/queue?kind=rate_limited\""));
+        // The selected category isn't linked
+        assert!(!html.contains("href=\"/debug/queue?kind=not_found\""));
+        assert!(html.contains("Uncategorized"));
+        assert!(html.contains("Recent Failures: Not found"));
+        assert!(html.contains("Show all failures"));
+        assert!(html.contains("<th>Category</th>"));
+    }
+
+    #[test]
+    fn test_render_debug_queue_page_skip_reasons() {
+        let stats = test_queue_stats();
+        let mut skipped = test_archive(1, "skipped", Some("Exceeds MAX_ARTIFACT_BYTES"));
+        skipped.skip_reason = Some("size_cap".to_string());
+        let failures = vec![skipped];
+        let html = render_debug_queue_page(&DebugQueueParams::new(&stats, &failures)).into_string();
+        assert!(html.contains("Skipped by Reason"));
+        assert!(html.contains("No skipped archives."));
+        assert!(html.contains("<th>Skip Reason</th>"));
+        assert!(html.contains("Too large"));
+
+        let counts = vec![
+            (Some("excluded_domain".to_string()), 4),
+            (Some("size_cap".to_string()), 1),
+            (None, 2),
+        ];
+        let params = DebugQueueParams::new(&stats, &failures)
+            .with_skip_reasons(&counts, Some(SkipReason::SizeCap));
+        let html = render_debug_queue_page(&params).into_string();
+
+        assert!(html.contains("skip-reasons-table"));
+        assert!(html.contains("href=\"/debug/queue?skip_reason=excluded_domain\""));
+        // The selected reason isn't linked
+        assert!(!html.contains("href=\"/debug/queue?skip_reason=size_cap\""));
+        assert!(html.contains("Recently Skipped: Too large"));
+        assert!(html.contains("Show all failures"));
+    }
+
     #[test]
     fn test_render_debug_queue_page_basic() {
         let stats = test_queue_stats();
@@ -420,6 +811,20 @@ mod tests {
         assert!(html.contains("100")); // complete_count
     }
 
+    #[test]
+    fn test_render_debug_queue_page_max_retries() {
+        let stats = test_queue_stats();
+        let failures: Vec<Archive> = vec![];
+
+        let html = render_debug_queue_page(&DebugQueueParams::new(&stats, &failures)).into_string();
+        assert!(!html.contains("Max Retries"));
+
+        let params = DebugQueueParams::new(&stats, &failures).with_max_retries(5);
+        let html = render_debug_queue_page(&params).into_string();
+        assert!(html.contains("Max Retries"));
+        assert!(html.contains(">5<"));
+    }
+
     #[test]
     fn test_render_debug_queue_page_with_user() {
         let stats = test_queue_stats();
@@ -560,7 +965,7 @@ mod tests {
     #[test]
     fn test_queue_stats_section() {
         let stats = test_queue_stats();
-        let section = QueueStatsSection::new(&stats);
+        let section = QueueStatsSection::new(&stats, None);
         let html = section.render().into_string();
 
         assert!(html.contains("queue-stats"));
@@ -596,7 +1001,7 @@ mod tests {
     #[test]
     fn test_recent_failures_section_empty() {
         let failures: Vec<Archive> = vec![];
-        let section = RecentFailuresSection::new(&failures, None);
+        let section = RecentFailuresSection::new(&failures, None, None);
         let html = section.render().into_string();
 
         assert!(html.contains("recent-failures"));
@@ -606,7 +1011,7 @@ mod tests {
     #[test]
     fn test_recent_failures_section_with_data() {
         let failures = vec![test_archive(42, "failed", Some("Network error"))];
-        let section = RecentFailuresSection::new(&failures, Some("token123"));
+        let section = RecentFailuresSection::new(&failures, None, Some("token123"));
         let html = section.render().into_string();
 
         assert!(html.contains("#42"));
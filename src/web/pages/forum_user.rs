@@ -0,0 +1,158 @@
+//! Forum user page templates using maud.
+//!
+//! This module provides maud-based templates for forum-user pages:
+//! - Activity page listing a forum user's posts and the archives linked from them
+
+use std::collections::BTreeSet;
+
+use maud::{html, Markup};
+
+use crate::components::{ArchiveGrid, BaseLayout, EmptyState};
+use crate::db::{forum_author_handle, ArchiveDisplay, Post, User};
+
+/// Parameters for the forum user activity page.
+#[derive(Debug, Clone)]
+pub struct ForumUserPageParams<'a> {
+    /// Forum handle (username without `@`).
+    pub handle: &'a str,
+    pub posts: &'a [Post],
+    pub archives: &'a [ArchiveDisplay],
+    pub user: Option<&'a User>,
+}
+
+/// Render a post author, linking to the forum user page for admins.
+///
+/// Links use the post's normalized `handle`, falling back to the default rules
+/// for posts stored without one.
+#[must_use]
+pub fn render_post_author(
+    author: Option<&str>,
+    handle: Option<&str>,
+    user: Option<&User>,
+) -> Markup {
+    let Some(author) = author else {
+        return html! { "Unknown" };
+    };
+    let is_admin = user.is_some_and(|u| u.is_admin);
+    let handle = handle.or_else(|| forum_author_handle(author));
+
+    html! {
+        @match handle.filter(|_| is_admin) {
+            Some(handle) => a href=(format!("/forum-user/{}", urlencoding::encode(handle))) { (author) },
+            None => (author),
+        }
+    }
+}
+
+/// Render the forum user activity page.
+#[must_use]
+pub fn render_forum_user_page(params: &ForumUserPageParams<'_>) -> Markup {
+    // The same user can appear as "@name" and "@name Full Name"
+    let author_variants: BTreeSet<&str> = params
+        .posts
+        .iter()
+        .filter_map(|p| p.author.as_deref())
+        .collect();
+
+    let content = html! {
+        h1 { "Forum User: @" (params.handle) }
+
+        article {
+            header {
+                p class="meta" {
+                    strong { "Posts:" } " " (params.posts.len())
+                    br;
+                    strong { "Archives:" } " " (params.archives.len())
+                    @if author_variants.len() > 1 {
+                        br;
+                        strong { "Appears as:" } " "
+                        @for (i, variant) in author_variants.iter().enumerate() {
+                            @if i > 0 { ", " }
+                            code { (variant) }
+                        }
+                    }
+                }
+            }
+        }
+
+        section {
+            h2 { "Posts" }
+
+            @if params.posts.is_empty() {
+                (EmptyState::new("No posts found for this forum user."))
+            } @else {
+                ul {
+                    @for post in params.posts {
+                        li {
+                            a href=(format!("/post/{}", post.guid)) {
+                                (super::format_post_title(post.title.as_deref(), &post.discourse_url))
+                            }
+                            @if let Some(ref published) = post.published_at {
+                                " " small class="text-muted" { (published) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        section {
+            h2 { "Archived Links" }
+
+            @if params.archives.is_empty() {
+                (EmptyState::new("No archives from this forum user's posts."))
+            } @else {
+                (ArchiveGrid::new(params.archives))
+            }
+        }
+    };
+
+    BaseLayout::new(&format!("Forum User: @{}", params.handle), params.user).render(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_post(id: i64, author: &str) -> Post {
+        Post {
+            id,
+            guid: format!("guid-{id}"),
+            discourse_url: format!("https://forum.example.com/t/topic/1/{id}"),
+            author: Some(author.to_string()),
+            title: Some("Topic".to_string()),
+            body_html: None,
+            content_hash: None,
+            published_at: Some("2024-01-01T00:00:00Z".to_string()),
+            processed_at: "2024-01-01T00:00:00Z".to_string(),
+            author_handle: forum_author_handle(author).map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_render_forum_user_page_lists_author_variants() {
+        let posts = vec![
+            sample_post(1, "@alice"),
+            sample_post(2, "@alice Alice Smith"),
+        ];
+        let params = ForumUserPageParams {
+            handle: "alice",
+            posts: &posts,
+            archives: &[],
+            user: None,
+        };
+
+        let html = render_forum_user_page(&params).into_string();
+
+        assert!(html.contains("Forum User: @alice"));
+        assert!(html.contains("Appears as:"));
+        assert!(html.contains("@alice Alice Smith"));
+        assert!(html.contains("/post/guid-2"));
+    }
+
+    #[test]
+    fn test_render_post_author_without_admin_is_plain_text() {
+        let html = render_post_author(Some("@alice"), None, None).into_string();
+        assert_eq!(html, "@alice");
+    }
+}
@@ -3,6 +3,8 @@
 //! This module provides the home page and related archive listing pages
 //! (Recent, All, Failed) using maud for HTML generation.
 
+use std::collections::HashMap;
+
 use maud::{html, Markup, Render};
 use urlencoding::encode;
 
@@ -10,7 +12,7 @@ use crate::components::{
     archive_list_tabs, ArchiveGrid, ArchiveTab, BaseLayout, EmptyState, OpenGraphMetadata,
     Pagination,
 };
-use crate::db::{ArchiveDisplay, User};
+use crate::db::{ArchiveArtifact, ArchiveDisplay, User};
 
 /// Which archive tab is currently active.
 ///
@@ -125,6 +127,7 @@ const CONTENT_TYPES: &[(&str, Option<&str>)] = &[
     ("Text", Some("text")),
     ("Thread", Some("thread")),
     ("Playlist", Some("playlist")),
+    ("Document", Some("document")),
 ];
 
 impl Render for ContentTypeFilter<'_> {
@@ -201,6 +204,9 @@ const SOURCES: &[(&str, Option<&str>)] = &[
     ("YouTube", Some("youtube")),
     ("TikTok", Some("tiktok")),
     ("Twitter/X", Some("twitter")),
+    ("SoundCloud", Some("soundcloud")),
+    ("Bandcamp", Some("bandcamp")),
+    ("Streamable", Some("streamable")),
 ];
 
 impl Render for SourceFilter<'_> {
@@ -245,6 +251,8 @@ pub struct HomePageParams<'a> {
     pub user: Option<&'a User>,
     /// Optional Open Graph metadata for social media previews
     pub og_metadata: Option<OpenGraphMetadata>,
+    /// Thumbnail artifacts keyed by archive ID, shown on the cards
+    pub thumbnails: Option<&'a HashMap<i64, ArchiveArtifact>>,
 }
 
 impl<'a> HomePageParams<'a> {
@@ -265,6 +273,7 @@ impl<'a> HomePageParams<'a> {
             source_filter: None,
             user: None,
             og_metadata: None,
+            thumbnails: None,
         }
     }
 
@@ -287,6 +296,7 @@ impl<'a> HomePageParams<'a> {
             source_filter: None,
             user: None,
             og_metadata: None,
+            thumbnails: None,
         }
     }
 
@@ -317,6 +327,16 @@ impl<'a> HomePageParams<'a> {
         self.og_metadata = Some(og);
         self
     }
+
+    /// Set the thumbnails shown on the archive cards.
+    #[must_use]
+    pub fn with_thumbnails(
+        mut self,
+        thumbnails: Option<&'a HashMap<i64, ArchiveArtifact>>,
+    ) -> Self {
+        self.thumbnails = thumbnails;
+        self
+    }
 }
 
 /// Render the home page (or archive listing page).
@@ -410,7 +430,7 @@ pub fn render_home_page(params: &HomePageParams) -> Markup {
         @if params.archives.is_empty() {
             (EmptyState::no_archives())
         } @else {
-            (ArchiveGrid::new(params.archives))
+            (ArchiveGrid::new(params.archives).with_thumbnails(params.thumbnails))
         }
 
         // Show pagination below the archive grid (even if empty)
@@ -437,6 +457,7 @@ pub fn render_home(archives: &[ArchiveDisplay], recent_failed_count: usize) -> M
 
 /// Render the recent archives home page with pagination.
 #[must_use]
+#[allow(clippy::implicit_hasher)]
 pub fn render_home_paginated(
     archives: &[ArchiveDisplay],
     recent_failed_count: usize,
@@ -446,6 +467,7 @@ pub fn render_home_paginated(
     source_filter: Option<&str>,
     user: Option<&User>,
     og_metadata: Option<OpenGraphMetadata>,
+    thumbnails: Option<&HashMap<i64, ArchiveArtifact>>,
 ) -> Markup {
     let mut params = HomePageParams::paginated(
         archives,
@@ -456,7 +478,8 @@ pub fn render_home_paginated(
     )
     .with_content_type_filter(content_type_filter)
     .with_source_filter(source_filter)
-    .with_user(user);
+    .with_user(user)
+    .with_thumbnails(thumbnails);
 
     if let Some(og) = og_metadata {
         params = params.with_og_metadata(og);
@@ -816,6 +839,7 @@ mod tests {
             Some("reddit"),
             None,
             None,
+            None,
         )
         .into_string();
 
@@ -109,7 +109,9 @@ pub mod banner;
 pub mod comment;
 pub mod comparison;
 pub mod debug;
+pub mod forum_user;
 pub mod home;
+pub mod playlist;
 pub mod post;
 pub mod search;
 pub mod site;
@@ -119,25 +121,30 @@ pub mod threads;
 
 // Re-export page rendering functions for convenience
 pub use admin::{
-    render_admin_excluded_domains_page, render_admin_forum_user_profile, render_admin_panel,
-    render_admin_password_reset_result, render_admin_user_profile, AdminPanelParams,
+    render_admin_audit_page, render_admin_bulk_import_page, render_admin_duplicate_links_page,
+    render_admin_excluded_domains_page, render_admin_external_services_page,
+    render_admin_forum_user_profile, render_admin_panel, render_admin_password_reset_result,
+    render_admin_user_profile, AdminAuditPageParams, AdminPanelParams, ADMIN_DEFAULT_PAGE_SIZE,
+    ADMIN_PAGE_SIZES,
 };
 pub use all_archives::{render_all_archives_table_page, AllArchivesPageParams};
-pub use archive::{render_archive_detail_page, ArchiveDetailParams};
+pub use archive::{archive_og_metadata, render_archive_detail_page, ArchiveDetailParams};
 pub use auth::{
     login_page, profile_page, profile_page_with_link_status, profile_page_with_message,
     render_login_page, render_profile_page, ProfilePageParams,
 };
 pub use banner::render_archive_banner;
 pub use comment::render_comment_edit_history_page;
-pub use comparison::render_comparison_page;
+pub use comparison::{render_comparison_page, render_content_version_page};
 pub use debug::{render_debug_queue_page, DebugQueueParams};
+pub use forum_user::{render_forum_user_page, ForumUserPageParams};
 pub use home::{
     render_home, render_home_page, render_home_paginated, render_recent_all_archives,
     render_recent_all_archives_paginated, render_recent_failed_archives,
     render_recent_failed_archives_paginated, ContentTypeFilter, HomePageParams, RecentArchivesTab,
     SourceFilter,
 };
+pub use playlist::render_playlist_page;
 pub use post::{render_post_detail_page, PostDetailParams};
 pub use search::{render_search_page, SearchPageParams};
 pub use site::render_site_list_page;
@@ -0,0 +1,66 @@
+//! Playlist detail page rendering using maud templates.
+//!
+//! A playlist archive stores the playlist title/author, and each video in the
+//! playlist is archived separately. This page lists those member archives in
+//! playlist order.
+
+use maud::{html, Markup};
+
+use crate::components::{ArchiveGrid, BaseLayout, EmptyState};
+use crate::db::{Archive, ArchiveDisplay, Link, User};
+
+/// Render the playlist detail page.
+///
+/// # Arguments
+///
+/// * `archive` - The parent playlist archive
+/// * `link` - The link for the playlist archive
+/// * `members` - Member archives, already ordered by playlist position
+/// * `user` - Optional authenticated user for navigation
+#[must_use]
+pub fn render_playlist_page(
+    archive: &Archive,
+    link: &Link,
+    members: &[ArchiveDisplay],
+    user: Option<&User>,
+) -> Markup {
+    let title = archive
+        .content_title
+        .clone()
+        .unwrap_or_else(|| "Untitled Playlist".to_string());
+
+    let content = html! {
+        h1 { "Playlist: " (title) }
+
+        div class="playlist-info" {
+            @if let Some(ref author) = archive.content_author {
+                p { strong { "Author:" } " " (author) }
+            }
+            p {
+                strong { "Source:" } " "
+                a href=(link.original_url) target="_blank" rel="noopener noreferrer" {
+                    (link.original_url)
+                }
+            }
+            p {
+                a href=(format!("/archive/{}", archive.id)) { "View playlist archive" }
+            }
+        }
+
+        p class="archive-count" {
+            @if members.len() == 1 {
+                "1 video archived from this playlist"
+            } @else {
+                (members.len()) " videos archived from this playlist"
+            }
+        }
+
+        @if members.is_empty() {
+            (EmptyState::new("No videos from this playlist have been queued yet."))
+        } @else {
+            (ArchiveGrid::new(members))
+        }
+    };
+
+    BaseLayout::new(&format!("Playlist: {title}"), user).render(content)
+}
@@ -6,22 +6,54 @@
 use maud::{html, Markup};
 
 use crate::components::{ArchiveGrid, BaseLayout, EmptyState};
-use crate::db::{ArchiveDisplay, Post, User};
+use crate::db::{Archive, ArchiveArtifact, ArchiveDisplay, Post, User};
 
 /// Parameters for the post detail page.
 #[derive(Debug, Clone)]
 pub struct PostDetailParams<'a> {
     pub post: &'a Post,
     pub archives: &'a [ArchiveDisplay],
+    /// Archive of the post's own page, when post snapshots are enabled.
+    pub snapshot: Option<&'a Archive>,
+    /// Artifacts of the snapshot archive.
+    pub snapshot_artifacts: &'a [ArchiveArtifact],
     pub user: Option<&'a User>,
 }
 
+/// Render the archived copy of the post page itself.
+fn render_post_snapshot(archive: &Archive, artifacts: &[ArchiveArtifact]) -> Markup {
+    let image = artifacts.iter().find(|a| a.kind == "post_snapshot");
+    let complete = artifacts.iter().find(|a| a.kind == "complete_html");
+
+    html! {
+        section class="post-snapshot" {
+            h2 { "Post Snapshot" }
+            @if let Some(image) = image {
+                a href=(format!("/s3/{}", image.s3_key)) target="_blank" rel="noopener" {
+                    img src=(format!("/s3/{}", image.s3_key))
+                        alt="Snapshot of the forum post"
+                        loading="lazy"
+                        style="max-width: 100%; height: auto;";
+                }
+            } @else {
+                p { "Snapshot status: " (archive.status) }
+            }
+            p {
+                a href=(format!("/archive/{}", archive.id)) { "View snapshot archive" }
+                @if let Some(complete) = complete {
+                    " \u{00b7} "
+                    a href=(format!("/s3/{}", complete.s3_key)) target="_blank" rel="noopener" { "Full HTML copy" }
+                }
+            }
+        }
+    }
+}
+
 /// Render the post detail page showing all archives from a post.
 #[must_use]
 pub fn render_post_detail_page(params: &PostDetailParams<'_>) -> Markup {
     let post = params.post;
     let title = super::format_post_title(post.title.as_deref(), &post.discourse_url);
-    let author = post.author.as_deref().unwrap_or("Unknown");
     let published = post.published_at.as_deref().unwrap_or("Unknown");
 
     let content = html! {
@@ -30,7 +62,7 @@ pub fn render_post_detail_page(params: &PostDetailParams<'_>) -> Markup {
         article {
             header {
                 p class="meta" {
-                    strong { "Author:" } " " (author)
+                    strong { "Author:" } " " (super::forum_user::render_post_author(post.author.as_deref(), post.author_handle.as_deref(), params.user))
                     br;
                     strong { "Published:" } " " (published)
                     br;
@@ -40,6 +72,10 @@ pub fn render_post_detail_page(params: &PostDetailParams<'_>) -> Markup {
             }
         }
 
+        @if let Some(snapshot) = params.snapshot {
+            (render_post_snapshot(snapshot, params.snapshot_artifacts))
+        }
+
         section {
             h2 { "Archived Links" }
 
@@ -72,6 +108,7 @@ mod tests {
             content_hash: Some("abc123".to_string()),
             published_at: Some("2024-01-15 12:00:00".to_string()),
             processed_at: "2024-01-15 12:30:00".to_string(),
+            author_handle: None,
         }
     }
 
@@ -100,6 +137,8 @@ mod tests {
         let params = PostDetailParams {
             post: &post,
             archives: &archives,
+            snapshot: None,
+            snapshot_artifacts: &[],
             user: None,
         };
         let html = render_post_detail_page(&params).into_string();
@@ -124,6 +163,8 @@ mod tests {
         let params = PostDetailParams {
             post: &post,
             archives: &archives,
+            snapshot: None,
+            snapshot_artifacts: &[],
             user: None,
         };
         let html = render_post_detail_page(&params).into_string();
@@ -141,6 +182,8 @@ mod tests {
         let params = PostDetailParams {
             post: &post,
             archives: &archives,
+            snapshot: None,
+            snapshot_artifacts: &[],
             user: None,
         };
         let html = render_post_detail_page(&params).into_string();
@@ -159,6 +202,8 @@ mod tests {
         let params = PostDetailParams {
             post: &post,
             archives: &archives,
+            snapshot: None,
+            snapshot_artifacts: &[],
             user: None,
         };
         let html = render_post_detail_page(&params).into_string();
@@ -177,6 +222,8 @@ mod tests {
         let params = PostDetailParams {
             post: &post,
             archives: &archives,
+            snapshot: None,
+            snapshot_artifacts: &[],
             user: None,
         };
         let html = render_post_detail_page(&params).into_string();
@@ -193,6 +240,8 @@ mod tests {
         let params = PostDetailParams {
             post: &post,
             archives: &archives,
+            snapshot: None,
+            snapshot_artifacts: &[],
             user: None,
         };
         let html = render_post_detail_page(&params).into_string();
@@ -208,6 +257,8 @@ mod tests {
         let params = PostDetailParams {
             post: &post,
             archives: &archives,
+            snapshot: None,
+            snapshot_artifacts: &[],
             user: None,
         };
         let html = render_post_detail_page(&params).into_string();
@@ -222,6 +273,8 @@ mod tests {
         let params = PostDetailParams {
             post: &post,
             archives: &archives,
+            snapshot: None,
+            snapshot_artifacts: &[],
             user: None,
         };
         let html = render_post_detail_page(&params).into_string();
@@ -252,6 +305,8 @@ mod tests {
         let params = PostDetailParams {
             post: &post,
             archives: &archives,
+            snapshot: None,
+            snapshot_artifacts: &[],
             user: None,
         };
         let html = render_post_detail_page(&params).into_string();
@@ -284,6 +339,8 @@ mod tests {
         let params = PostDetailParams {
             post: &post,
             archives: &archives,
+            snapshot: None,
+            snapshot_artifacts: &[],
             user: Some(&user),
         };
         let html = render_post_detail_page(&params).into_string();
@@ -291,4 +348,89 @@ mod tests {
         // Check that user-specific navigation is rendered
         assert!(html.contains("/profile"));
     }
+
+    #[test]
+    fn test_post_detail_page_with_snapshot() {
+        let post = sample_post();
+        let archives: Vec<ArchiveDisplay> = vec![];
+        let snapshot = Archive {
+            id: 7,
+            link_id: 3,
+            status: "complete".to_string(),
+            archived_at: Some("2024-01-15 14:00:00".to_string()),
+            content_title: None,
+            content_author: None,
+            content_text: None,
+            content_type: Some("text".to_string()),
+            s3_key_primary: None,
+            s3_key_thumb: None,
+            s3_keys_extra: None,
+            wayback_url: None,
+            archive_today_url: None,
+            ipfs_cid: None,
+            error_message: None,
+            retry_count: 0,
+            created_at: "2024-01-15 12:30:00".to_string(),
+            is_nsfw: false,
+            nsfw_source: None,
+            next_retry_at: None,
+            last_attempt_at: None,
+            http_status_code: Some(200),
+            redirect_chain: None,
+            post_date: None,
+            quoted_archive_id: None,
+            reply_to_archive_id: None,
+            submitted_by_user_id: None,
+            progress_percent: None,
+            progress_details: None,
+            last_progress_update: None,
+            og_title: None,
+            og_description: None,
+            og_image: None,
+            og_type: None,
+            og_extracted_at: None,
+            og_extraction_attempted: false,
+            transcript_text: None,
+            full_text: None,
+            view_count: None,
+            like_count: None,
+            repost_count: None,
+            platform_comment_count: None,
+            save_count: None,
+            metrics_backfill_version: None,
+            short_code: None,
+            error_kind: None,
+            skip_reason: None,
+        };
+        let artifacts = vec![ArchiveArtifact {
+            id: 1,
+            archive_id: 7,
+            kind: "post_snapshot".to_string(),
+            s3_key: "archives/7/render/screenshot.webp".to_string(),
+            content_type: Some("image/webp".to_string()),
+            size_bytes: Some(1024),
+            sha256: None,
+            created_at: "2024-01-15 14:00:00".to_string(),
+            perceptual_hash: None,
+            duplicate_of_artifact_id: None,
+            video_file_id: None,
+            metadata: None,
+            placeholder_color: None,
+            width: None,
+            height: None,
+            content_encoding: None,
+        }];
+        let params = PostDetailParams {
+            post: &post,
+            archives: &archives,
+            snapshot: Some(&snapshot),
+            snapshot_artifacts: &artifacts,
+            user: None,
+        };
+        let html = render_post_detail_page(&params).into_string();
+
+        assert!(html.contains("Post Snapshot"));
+        assert!(html.contains("/s3/archives/7/render/screenshot.webp"));
+        assert!(html.contains("/archive/7"));
+    }
 }
@@ -1,24 +1,30 @@
 //! Site list page rendering using maud templates.
 //!
 //! This module provides the site-specific archive listing page using maud
-//! for HTML generation. It displays all archives from a specific domain/site.
+//! for HTML generation. It displays a domain's archive stats and all archives
+//! from that domain/site.
 
 use maud::{html, Markup};
 
-use crate::components::{ArchiveGrid, BaseLayout, DomainBadge, EmptyState, Pagination};
-use crate::db::{ArchiveDisplay, User};
+use crate::components::{
+    ArchiveGrid, BaseLayout, DomainBadge, EmptyState, Pagination, SizeBadge, StatsCard,
+    StatsCardGrid,
+};
+use crate::db::{ArchiveDisplay, DomainStats, User};
 
 /// Render the site list page showing archives from a specific domain.
 ///
 /// This function renders a page with:
 /// - Site name as the page title with a domain badge
-/// - Count of archives from this site
+/// - Archive count, storage and content-type breakdown for the site
+/// - A link to bulk-export the site
 /// - Archive grid showing the archives
 /// - Pagination controls (if more than one page)
 ///
 /// # Arguments
 ///
 /// * `site` - The domain/site name (e.g., "reddit.com")
+/// * `stats` - Totals for the site and its subdomains
 /// * `archives` - Archives to display from this site
 /// * `page` - Current page number (0-indexed)
 /// * `total_pages` - Total number of pages
@@ -29,11 +35,12 @@ use crate::db::{ArchiveDisplay, User};
 /// ```ignore
 /// use crate::web::pages::site::render_site_list_page;
 ///
-/// let html = render_site_list_page("reddit.com", &archives, 0, 5, Some(&user));
+/// let html = render_site_list_page("reddit.com", &stats, &archives, 0, 5, Some(&user));
 /// ```
 #[must_use]
 pub fn render_site_list_page(
     site: &str,
+    stats: &DomainStats,
     archives: &[ArchiveDisplay],
     page: i32,
     total_pages: i32,
@@ -47,8 +54,29 @@ pub fn render_site_list_page(
     let total = total_pages.max(0) as usize;
     let pagination = Pagination::new(current_page, total, &base_url);
 
-    // Calculate archive count for display
-    let archive_count = archives.len();
+    let archive_count = stats.archive_count();
+
+    let mut overview_card = StatsCard::new("Overview")
+        .item("Archives", archive_count.to_string())
+        .item("Storage", SizeBadge::format_bytes(stats.total_bytes()));
+    // Only worth listing when the site spans several (sub)domains
+    if stats.domains.len() > 1 {
+        for domain in &stats.domains {
+            overview_card = overview_card.item(
+                &domain.domain,
+                format!(
+                    "{} ({})",
+                    domain.archive_count,
+                    SizeBadge::format_bytes(domain.total_bytes)
+                ),
+            );
+        }
+    }
+
+    let mut content_type_card = StatsCard::new("Archived Content");
+    for (content_type, count) in &stats.content_types {
+        content_type_card = content_type_card.item(content_type, count.to_string());
+    }
 
     // Build the main content
     let content = html! {
@@ -69,6 +97,19 @@ pub fn render_site_list_page(
             }
         }
 
+        @if archive_count > 0 {
+            @let grid = StatsCardGrid::new().card(overview_card);
+            @if stats.content_types.is_empty() {
+                (grid)
+            } @else {
+                (grid.card(content_type_card))
+            }
+
+            p class="site-export" {
+                a href=(format!("/export/{site}")) download { "Download all archives from this site (ZIP)" }
+            }
+        }
+
         @if archives.is_empty() {
             (EmptyState::new("No archives from this site."))
         } @else {
@@ -87,6 +128,7 @@ pub fn render_site_list_page(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::DomainStorage;
 
     fn sample_archive(id: i64, domain: &str) -> ArchiveDisplay {
         ArchiveDisplay {
@@ -106,6 +148,22 @@ mod tests {
         }
     }
 
+    fn sample_stats(domain: &str, archive_count: usize) -> DomainStats {
+        let archive_count = archive_count as i64;
+        DomainStats {
+            domains: if archive_count > 0 {
+                vec![DomainStorage {
+                    domain: domain.to_string(),
+                    archive_count,
+                    total_bytes: archive_count * 1_048_576,
+                }]
+            } else {
+                Vec::new()
+            },
+            content_types: vec![("video".to_string(), archive_count)],
+        }
+    }
+
     fn sample_user() -> User {
         User {
             id: 1,
@@ -130,7 +188,15 @@ mod tests {
             sample_archive(1, "reddit.com"),
             sample_archive(2, "reddit.com"),
         ];
-        let html = render_site_list_page("reddit.com", &archives, 0, 1, None).into_string();
+        let html = render_site_list_page(
+            "reddit.com",
+            &sample_stats("reddit.com", archives.len()),
+            &archives,
+            0,
+            1,
+            None,
+        )
+        .into_string();
 
         // Check page structure
         assert!(html.contains("<!DOCTYPE html>"));
@@ -153,7 +219,15 @@ mod tests {
     #[test]
     fn test_render_site_list_page_empty() {
         let archives: Vec<ArchiveDisplay> = vec![];
-        let html = render_site_list_page("example.com", &archives, 0, 1, None).into_string();
+        let html = render_site_list_page(
+            "example.com",
+            &sample_stats("example.com", archives.len()),
+            &archives,
+            0,
+            1,
+            None,
+        )
+        .into_string();
 
         // Should show empty state
         assert!(html.contains("No archives from this site."));
@@ -166,7 +240,15 @@ mod tests {
     #[test]
     fn test_render_site_list_page_single_archive() {
         let archives = vec![sample_archive(1, "youtube.com")];
-        let html = render_site_list_page("youtube.com", &archives, 0, 1, None).into_string();
+        let html = render_site_list_page(
+            "youtube.com",
+            &sample_stats("youtube.com", archives.len()),
+            &archives,
+            0,
+            1,
+            None,
+        )
+        .into_string();
 
         // Check singular form
         assert!(html.contains("1 archive from this site"));
@@ -175,7 +257,15 @@ mod tests {
     #[test]
     fn test_render_site_list_page_with_pagination() {
         let archives = vec![sample_archive(1, "tiktok.com")];
-        let html = render_site_list_page("tiktok.com", &archives, 2, 5, None).into_string();
+        let html = render_site_list_page(
+            "tiktok.com",
+            &sample_stats("tiktok.com", archives.len()),
+            &archives,
+            2,
+            5,
+            None,
+        )
+        .into_string();
 
         // Should show pagination info
         assert!(html.contains("(page 3 of 5)"));
@@ -189,7 +279,15 @@ mod tests {
     #[test]
     fn test_render_site_list_page_first_page_no_previous() {
         let archives = vec![sample_archive(1, "twitter.com")];
-        let html = render_site_list_page("twitter.com", &archives, 0, 3, None).into_string();
+        let html = render_site_list_page(
+            "twitter.com",
+            &sample_stats("twitter.com", archives.len()),
+            &archives,
+            0,
+            3,
+            None,
+        )
+        .into_string();
 
         // Previous should be disabled button on first page
         assert!(html.contains("disabled"));
@@ -200,7 +298,15 @@ mod tests {
     fn test_render_site_list_page_with_user() {
         let archives = vec![sample_archive(1, "reddit.com")];
         let user = sample_user();
-        let html = render_site_list_page("reddit.com", &archives, 0, 1, Some(&user)).into_string();
+        let html = render_site_list_page(
+            "reddit.com",
+            &sample_stats("reddit.com", archives.len()),
+            &archives,
+            0,
+            1,
+            Some(&user),
+        )
+        .into_string();
 
         // Should show profile link for logged-in user
         assert!(html.contains(r#"<a href="/profile">Profile</a>"#));
@@ -211,7 +317,15 @@ mod tests {
     #[test]
     fn test_render_site_list_page_without_user() {
         let archives = vec![sample_archive(1, "reddit.com")];
-        let html = render_site_list_page("reddit.com", &archives, 0, 1, None).into_string();
+        let html = render_site_list_page(
+            "reddit.com",
+            &sample_stats("reddit.com", archives.len()),
+            &archives,
+            0,
+            1,
+            None,
+        )
+        .into_string();
 
         // Should show login link for anonymous user
         assert!(html.contains(r#"<a href="/login">Login</a>"#));
@@ -222,7 +336,15 @@ mod tests {
     #[test]
     fn test_render_site_list_page_domain_badge_links() {
         let archives = vec![sample_archive(1, "instagram.com")];
-        let html = render_site_list_page("instagram.com", &archives, 0, 1, None).into_string();
+        let html = render_site_list_page(
+            "instagram.com",
+            &sample_stats("instagram.com", archives.len()),
+            &archives,
+            0,
+            1,
+            None,
+        )
+        .into_string();
 
         // Domain badge should link to site page
         assert!(html.contains(r#"href="/site/instagram.com""#));
@@ -231,7 +353,15 @@ mod tests {
     #[test]
     fn test_render_site_list_page_pagination_urls() {
         let archives = vec![sample_archive(1, "twitch.tv")];
-        let html = render_site_list_page("twitch.tv", &archives, 1, 3, None).into_string();
+        let html = render_site_list_page(
+            "twitch.tv",
+            &sample_stats("twitch.tv", archives.len()),
+            &archives,
+            1,
+            3,
+            None,
+        )
+        .into_string();
 
         // Check pagination URLs contain correct site
         assert!(html.contains("/site/twitch.tv"));
@@ -241,7 +371,15 @@ mod tests {
     fn test_render_site_list_page_special_characters_in_site() {
         // Test that special characters in site names are handled
         let archives = vec![sample_archive(1, "sub.example.com")];
-        let html = render_site_list_page("sub.example.com", &archives, 0, 1, None).into_string();
+        let html = render_site_list_page(
+            "sub.example.com",
+            &sample_stats("sub.example.com", archives.len()),
+            &archives,
+            0,
+            1,
+            None,
+        )
+        .into_string();
 
         assert!(html.contains("Archives from sub.example.com"));
         assert!(html.contains("/site/sub.example.com"));
@@ -250,7 +388,15 @@ mod tests {
     #[test]
     fn test_render_site_list_page_no_pagination_single_page() {
         let archives = vec![sample_archive(1, "reddit.com")];
-        let html = render_site_list_page("reddit.com", &archives, 0, 1, None).into_string();
+        let html = render_site_list_page(
+            "reddit.com",
+            &sample_stats("reddit.com", archives.len()),
+            &archives,
+            0,
+            1,
+            None,
+        )
+        .into_string();
 
         // Should not show page info when single page
         assert!(!html.contains("(page"));
@@ -262,9 +408,55 @@ mod tests {
     fn test_render_site_list_page_negative_page_handled() {
         // Test that negative page numbers are handled gracefully
         let archives = vec![sample_archive(1, "reddit.com")];
-        let html = render_site_list_page("reddit.com", &archives, -1, 5, None).into_string();
+        let html = render_site_list_page(
+            "reddit.com",
+            &sample_stats("reddit.com", archives.len()),
+            &archives,
+            -1,
+            5,
+            None,
+        )
+        .into_string();
 
         // Should still render without crashing
         assert!(html.contains("Archives from"));
     }
+
+    #[test]
+    fn test_render_site_list_page_stats_and_export() {
+        let archives = vec![sample_archive(1, "reddit.com")];
+        let stats = DomainStats {
+            domains: vec![
+                DomainStorage {
+                    domain: "i.reddit.com".to_string(),
+                    archive_count: 2,
+                    total_bytes: 2 * 1_048_576,
+                },
+                DomainStorage {
+                    domain: "reddit.com".to_string(),
+                    archive_count: 3,
+                    total_bytes: 1024,
+                },
+            ],
+            content_types: vec![("image".to_string(), 2), ("text".to_string(), 1)],
+        };
+        let html = render_site_list_page("reddit", &stats, &archives, 0, 1, None).into_string();
+
+        assert!(html.contains("5 archives from this site"));
+        assert!(html.contains("2.0 MB"));
+        // Subdomains are broken out when there are several
+        assert!(html.contains("i.reddit.com"));
+        assert!(html.contains("Archived Content"));
+        assert!(html.contains(r#"<span class="stats-card-label">image:</span>"#));
+        assert!(html.contains(r#"href="/export/reddit""#));
+    }
+
+    #[test]
+    fn test_render_site_list_page_no_export_when_empty() {
+        let html = render_site_list_page("example.com", &DomainStats::default(), &[], 0, 1, None)
+            .into_string();
+
+        assert!(!html.contains("/export/"));
+        assert!(!html.contains("Archived Content"));
+    }
 }
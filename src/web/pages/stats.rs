@@ -5,7 +5,7 @@
 use maud::{html, Markup, Render};
 
 use crate::components::{BaseLayout, StatsCard, StatsCardGrid, Table, TableRow, TableVariant};
-use crate::db::{User, UserSubmissionDetail};
+use crate::db::{DomainStorage, User, UserSubmissionDetail};
 
 /// Data for the statistics page.
 #[derive(Debug, Clone)]
@@ -34,6 +34,8 @@ pub struct StatsData {
     pub nsfw_count: i64,
     /// Total completed archives count (for percentages)
     pub total_complete: i64,
+    /// Domains using the most artifact storage (shown to admins)
+    pub storage_by_domain: Vec<DomainStorage>,
 }
 
 impl StatsData {
@@ -67,9 +69,17 @@ impl StatsData {
             quality_metrics,
             nsfw_count,
             total_complete,
+            storage_by_domain: Vec::new(),
         }
     }
 
+    /// Set the per-domain storage report.
+    #[must_use]
+    pub fn with_storage_by_domain(mut self, storage_by_domain: Vec<DomainStorage>) -> Self {
+        self.storage_by_domain = storage_by_domain;
+        self
+    }
+
     /// Get the total number of archives across all statuses.
     #[must_use]
     pub fn total_archives(&self) -> i64 {
@@ -248,6 +258,16 @@ pub fn render_stats_page(
             }
         }
 
+        // Storage by domain (admins only)
+        @if user.is_some_and(|u| u.is_admin) && !stats.storage_by_domain.is_empty() {
+            section class="stats-card" {
+                h2 class="stats-card-title" { "Storage by Domain" }
+                div class="stats-card-content" {
+                    (render_storage_by_domain_table(&stats.storage_by_domain))
+                }
+            }
+        }
+
         // User-specific stats (if logged in)
         @if let Some(user_stats) = user_stats {
             (render_user_stats_section(user_stats))
@@ -312,6 +332,25 @@ fn render_domain_table(domains: &[(String, i64)]) -> Markup {
         .render()
 }
 
+/// Render the per-domain storage table.
+fn render_storage_by_domain_table(domains: &[DomainStorage]) -> Markup {
+    let rows: Vec<Markup> = domains
+        .iter()
+        .map(|d| {
+            TableRow::new()
+                .cell_markup(html! { a href=(format!("/site/{}", d.domain)) { (d.domain) } })
+                .cell(&d.archive_count.to_string())
+                .cell(&format_bytes(d.total_bytes))
+                .render()
+        })
+        .collect();
+
+    Table::new(vec!["Domain", "Archives", "Storage"])
+        .variant(TableVariant::Stats)
+        .rows(rows)
+        .render()
+}
+
 /// Format bytes to human-readable format.
 fn format_bytes(bytes: i64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
@@ -659,6 +698,26 @@ mod tests {
         assert!(html.contains(r#"<a href="/admin">Admin</a>"#));
     }
 
+    #[test]
+    fn test_render_stats_page_storage_by_domain_admin_only() {
+        let stats =
+            test_stats_data(10, 50, vec![], vec![]).with_storage_by_domain(vec![DomainStorage {
+                domain: "video.example.com".to_string(),
+                archive_count: 3,
+                total_bytes: 5 * 1024 * 1024,
+            }]);
+
+        let html = render_stats_page(&stats, Some(&test_user(true)), None).into_string();
+        assert!(html.contains("Storage by Domain"));
+        assert!(html.contains(r#"<a href="/site/video.example.com">video.example.com</a>"#));
+        assert!(html.contains(">5.00 MB</td>"));
+
+        let html = render_stats_page(&stats, Some(&test_user(false)), None).into_string();
+        assert!(!html.contains("Storage by Domain"));
+        let html = render_stats_page(&stats, None, None).into_string();
+        assert!(!html.contains("Storage by Domain"));
+    }
+
     #[test]
     fn test_render_stats_page_anonymous() {
         let stats = test_stats_data(10, 50, vec![], vec![]);
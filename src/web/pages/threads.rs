@@ -5,25 +5,88 @@
 //! - Thread detail page
 //! - Thread archive job status page
 
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use maud::{html, Markup, PreEscaped, Render};
 use std::collections::HashMap;
 use urlencoding::encode;
 
-use crate::components::{Alert, ArchiveGrid, BaseLayout, EmptyState, KeyValueTable, Pagination};
+use crate::components::{
+    Alert, ArchiveGrid, BaseLayout, Button, EmptyState, KeyValueTable, Pagination, ResponsiveTable,
+    Table, TableRow,
+};
 use crate::db::{
-    extract_topic_id_from_thread_key, thread_key_from_url, ArchiveDisplay, Post, ThreadArchiveJob,
-    ThreadDisplay, User,
+    extract_topic_id_from_thread_key, thread_key_from_url, ArchiveDisplay, Post, PostLinkCounts,
+    ThreadArchiveJob, ThreadDisplay, User,
 };
 
-/// Format a SQLite datetime string into a more readable format.
-/// Input: "2024-01-15 12:34:56"
-/// Output: "Jan 15, 2024 12:34"
-fn format_datetime(datetime_str: &str) -> String {
-    NaiveDateTime::parse_from_str(datetime_str, "%Y-%m-%d %H:%M:%S")
+/// A datetime formatted for display, with an absolute and a relative form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FriendlyDateTime {
+    /// e.g. "Jan 20, 2026 · 16:31 UTC"
+    pub absolute: String,
+    /// e.g. "2 days ago"
+    pub relative: String,
+}
+
+/// Parse a stored datetime (SQLite `YYYY-MM-DD HH:MM:SS` or RFC 3339).
+fn parse_datetime(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
         .ok()
-        .map(|dt| dt.format("%b %d, %Y %H:%M").to_string())
-        .unwrap_or_else(|| datetime_str.to_string())
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .map(|dt| dt.and_utc())
+        })
+}
+
+/// Format a stored datetime into absolute and relative strings.
+///
+/// Returns `None` for missing or unparseable values.
+#[must_use]
+pub fn format_friendly_datetime(
+    value: Option<&str>,
+    now: DateTime<Utc>,
+) -> Option<FriendlyDateTime> {
+    let dt = parse_datetime(value?)?;
+    Some(FriendlyDateTime {
+        absolute: dt.format("%b %-d, %Y · %H:%M UTC").to_string(),
+        relative: format_relative(now.signed_duration_since(dt).num_seconds()),
+    })
+}
+
+/// Describe an elapsed number of seconds, e.g. "3 hours ago".
+fn format_relative(seconds: i64) -> String {
+    const MINUTE: i64 = 60;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
+    const MONTH: i64 = 30 * DAY;
+    const YEAR: i64 = 365 * DAY;
+
+    if seconds < 0 {
+        return "in the future".to_string();
+    }
+    let (count, unit) = match seconds {
+        s if s < MINUTE => return "just now".to_string(),
+        s if s < HOUR => (s / MINUTE, "minute"),
+        s if s < DAY => (s / HOUR, "hour"),
+        s if s < MONTH => (s / DAY, "day"),
+        s if s < YEAR => (s / MONTH, "month"),
+        s => (s / YEAR, "year"),
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{count} {unit}{plural} ago")
+}
+
+/// Render a datetime with the relative form as a tooltip.
+fn render_friendly_datetime(value: Option<&str>, now: DateTime<Utc>) -> Markup {
+    match format_friendly_datetime(value, now) {
+        Some(friendly) => html! {
+            time datetime=[value] title=(friendly.relative) { (friendly.absolute) }
+        },
+        None => html! { (value.unwrap_or("Unknown")) },
+    }
 }
 
 /// Sort option for threads list.
@@ -233,6 +296,8 @@ pub struct ThreadDetailParams<'a> {
     pub thread_key: &'a str,
     pub posts: &'a [Post],
     pub archives: &'a [ArchiveDisplay],
+    /// Link and archive counts per post.
+    pub post_counts: &'a [PostLinkCounts],
     pub user: Option<&'a User>,
 }
 
@@ -245,11 +310,7 @@ pub fn render_thread_detail_page(params: &ThreadDetailParams<'_>) -> Markup {
         .find_map(|p| p.title.clone())
         .unwrap_or_else(|| "Untitled Thread".to_string());
 
-    let author = params
-        .posts
-        .iter()
-        .find_map(|p| p.author.clone())
-        .unwrap_or_else(|| "Unknown".to_string());
+    let author_post = params.posts.iter().find(|p| p.author.is_some());
 
     let published = params
         .posts
@@ -276,7 +337,11 @@ pub fn render_thread_detail_page(params: &ThreadDetailParams<'_>) -> Markup {
         article {
             header {
                 p class="meta" {
-                    strong { "Author:" } " " (author)
+                    strong { "Author:" } " " (super::forum_user::render_post_author(
+                        author_post.and_then(|p| p.author.as_deref()),
+                        author_post.and_then(|p| p.author_handle.as_deref()),
+                        params.user,
+                    ))
                     br;
                     strong { "Published:" } " " (published)
                     br;
@@ -298,38 +363,7 @@ pub fn render_thread_detail_page(params: &ThreadDetailParams<'_>) -> Markup {
         // Posts list section
         section {
             h2 { "Posts" }
-            table style="width: 100%; border-collapse: collapse;" {
-                thead {
-                    tr {
-                        th style="text-align: left; padding: 0.5rem; border-bottom: 1px solid var(--border, #e4e4e7);" { "Title" }
-                        th style="text-align: left; padding: 0.5rem; border-bottom: 1px solid var(--border, #e4e4e7);" { "Author" }
-                        th style="text-align: left; padding: 0.5rem; border-bottom: 1px solid var(--border, #e4e4e7); white-space: nowrap;" { "Published" }
-                    }
-                }
-                tbody {
-                    @for post in params.posts {
-                        @let post_title = super::format_post_title(post.title.as_deref(), &post.discourse_url);
-                        @let post_author = post.author.as_deref().unwrap_or("Unknown");
-                        @let published_at = post.published_at.as_deref().unwrap_or("Unknown");
-                        @let formatted_date = if published_at != "Unknown" {
-                            format_datetime(published_at)
-                        } else {
-                            published_at.to_string()
-                        };
-                        tr {
-                            td style="padding: 0.5rem; border-bottom: 1px solid var(--border, #e4e4e7);" {
-                                a href=(format!("/post/{}", post.guid)) { (post_title) }
-                            }
-                            td style="padding: 0.5rem; border-bottom: 1px solid var(--border, #e4e4e7);" {
-                                (post_author)
-                            }
-                            td style="padding: 0.5rem; border-bottom: 1px solid var(--border, #e4e4e7); white-space: nowrap;" {
-                                (formatted_date)
-                            }
-                        }
-                    }
-                }
-            }
+            (render_thread_posts_table(params.posts, params.post_counts, params.user))
         }
 
         // Archives section
@@ -350,6 +384,58 @@ pub fn render_thread_detail_page(params: &ThreadDetailParams<'_>) -> Markup {
     BaseLayout::new(&format!("Thread: {title}"), params.user).render(content)
 }
 
+/// Render the thread's posts as a table sorted by publish date.
+fn render_thread_posts_table(
+    posts: &[Post],
+    post_counts: &[PostLinkCounts],
+    user: Option<&User>,
+) -> Markup {
+    let now = Utc::now();
+    let counts: HashMap<i64, &PostLinkCounts> =
+        post_counts.iter().map(|c| (c.post_id, c)).collect();
+
+    // Oldest first; posts without a parseable date go last
+    let mut sorted: Vec<&Post> = posts.iter().collect();
+    sorted.sort_by_key(|p| {
+        let published = p.published_at.as_deref().and_then(parse_datetime);
+        (published.is_none(), published)
+    });
+
+    let rows: Vec<Markup> = sorted
+        .into_iter()
+        .map(|post| {
+            let title = super::format_post_title(post.title.as_deref(), &post.discourse_url);
+            let count = counts.get(&post.id);
+            TableRow::new()
+                .cell_markup_with_class(
+                    render_friendly_datetime(post.published_at.as_deref(), now),
+                    "thread-post-date",
+                )
+                .cell_markup(super::forum_user::render_post_author(
+                    post.author.as_deref(),
+                    post.author_handle.as_deref(),
+                    user,
+                ))
+                .cell_markup(html! { a href=(format!("/post/{}", post.guid)) { (title) } })
+                .cell_with_class(
+                    &count.map_or(0, |c| c.link_count).to_string(),
+                    "thread-post-count",
+                )
+                .cell_with_class(
+                    &count.map_or(0, |c| c.archive_count).to_string(),
+                    "thread-post-count",
+                )
+                .render()
+        })
+        .collect();
+
+    let table = Table::new(vec!["Published", "Author", "Title", "Links", "Archives"])
+        .class("thread-posts-table")
+        .rows(rows);
+
+    ResponsiveTable::new(table.render()).render()
+}
+
 /// Job status variant for display styling.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JobStatusVariant {
@@ -357,6 +443,7 @@ pub enum JobStatusVariant {
     Processing,
     Complete,
     Failed,
+    Cancelled,
 }
 
 impl JobStatusVariant {
@@ -368,6 +455,7 @@ impl JobStatusVariant {
             "processing" => Self::Processing,
             "complete" => Self::Complete,
             "failed" => Self::Failed,
+            "cancelled" => Self::Cancelled,
             _ => Self::Pending,
         }
     }
@@ -380,6 +468,7 @@ impl JobStatusVariant {
             Self::Processing => "info",
             Self::Complete => "success",
             Self::Failed => "error",
+            Self::Cancelled => "warning",
         }
     }
 
@@ -391,6 +480,7 @@ impl JobStatusVariant {
             Self::Processing => "Processing",
             Self::Complete => "Complete",
             Self::Failed => "Failed",
+            Self::Cancelled => "Cancelled",
         }
     }
 
@@ -482,6 +572,56 @@ pub struct ThreadJobStatusParams<'a> {
     pub user: Option<&'a User>,
 }
 
+/// Script that follows `/thread-job/:id/events` and updates the scanning progress.
+///
+/// Reloads the page when the job changes phase or finishes, so the server
+/// renders the sections for the new status.
+fn thread_job_events_script(job: &ThreadArchiveJob) -> String {
+    let status = serde_json::to_string(&job.status).unwrap_or_else(|_| "\"\"".to_string());
+    format!(
+        r#"
+        (function() {{
+            var status = {status};
+            if (!window.EventSource) {{
+                setTimeout(function() {{ window.location.reload(); }}, 1000);
+                return;
+            }}
+            var source = new EventSource('/thread-job/{id}/events');
+            function set(id, value) {{
+                var el = document.getElementById(id);
+                if (el) el.textContent = value;
+            }}
+            source.addEventListener('progress', function(e) {{
+                var p = JSON.parse(e.data);
+                if (p.status !== status) {{
+                    source.close();
+                    window.location.reload();
+                    return;
+                }}
+                var total = p.total_posts === null ? '?' : p.total_posts;
+                set('job-processed-posts', p.processed_posts + ' / ' + total);
+                set('job-new-links-found', p.new_links_found);
+                set('job-archives-created', p.archives_created);
+                set('job-skipped-links', p.skipped_links);
+                var bar = document.querySelector('#scan-progress > div > div');
+                if (bar && p.total_posts > 0) {{
+                    bar.style.width = Math.min(100, Math.floor(p.processed_posts * 100 / p.total_posts)) + '%';
+                }}
+            }});
+            source.addEventListener('done', function() {{
+                source.close();
+                window.location.reload();
+            }});
+            source.addEventListener('error', function(e) {{
+                // Server-sent errors carry data; connection drops don't and are retried.
+                if (e.data) source.close();
+            }});
+        }})();
+        "#,
+        id = job.id,
+    )
+}
+
 /// Render the thread archive job status page.
 #[must_use]
 pub fn render_thread_job_status_page(params: &ThreadJobStatusParams<'_>) -> Markup {
@@ -498,14 +638,8 @@ pub fn render_thread_job_status_page(params: &ThreadJobStatusParams<'_>) -> Mark
     let fully_complete =
         status_variant == JobStatusVariant::Complete && !params.archive_status_counts.has_active();
 
-    let should_refresh = scanning_phase || archiving_phase;
-    let refresh_interval = if scanning_phase {
-        1 // 1 second during scanning
-    } else if archiving_phase {
-        5 // 5 seconds during archiving
-    } else {
-        0 // No refresh when complete
-    };
+    // Scanning progress streams over SSE; archiving still reloads every 5 seconds
+    let should_refresh = archiving_phase;
 
     let content = html! {
         h1 { "Thread Archive Job #" (job.id) }
@@ -519,6 +653,11 @@ pub fn render_thread_job_status_page(params: &ThreadJobStatusParams<'_>) -> Mark
                     (status_variant.label())
                 }
             }
+            @if scanning_phase {
+                form method="post" action=(format!("/submit/thread/{}/cancel", job.id)) {
+                    (Button::danger("Cancel job").r#type("submit"))
+                }
+            }
         }
 
         // Details section
@@ -549,7 +688,10 @@ pub fn render_thread_job_status_page(params: &ThreadJobStatusParams<'_>) -> Mark
         }
 
         // Progress section - show both phases
-        @if matches!(status_variant, JobStatusVariant::Processing | JobStatusVariant::Complete) {
+        @if matches!(
+            status_variant,
+            JobStatusVariant::Processing | JobStatusVariant::Complete | JobStatusVariant::Cancelled
+        ) {
             section {
                 h2 { "Progress" }
 
@@ -559,19 +701,31 @@ pub fn render_thread_job_status_page(params: &ThreadJobStatusParams<'_>) -> Mark
                     .filter(|&total| total > 0)
                     .map(|total| ((job.processed_posts * 100 / total) as u32).min(100))
                     .unwrap_or(0);
-                (ProgressBar::new(progress_percent))
+                div id="scan-progress" { (ProgressBar::new(progress_percent)) }
 
                 @let total_display = job.total_posts.map_or("?".to_string(), |t| t.to_string());
                 (KeyValueTable::new()
-                    .item("Posts Processed", &format!("{} / {}", job.processed_posts, total_display))
-                    .item("New Links Found", &job.new_links_found.to_string())
-                    .item("Archives Created", &job.archives_created.to_string())
-                    .item("Skipped Links", &job.skipped_links.to_string()))
+                    .item_markup("Posts Processed", html! {
+                        span id="job-processed-posts" { (job.processed_posts) " / " (total_display) }
+                    })
+                    .item_markup("New Links Found", html! {
+                        span id="job-new-links-found" { (job.new_links_found) }
+                    })
+                    .item_markup("Archives Created", html! {
+                        span id="job-archives-created" { (job.archives_created) }
+                    })
+                    .item_markup("Skipped Links", html! {
+                        span id="job-skipped-links" { (job.skipped_links) }
+                    }))
 
                 @if status_variant == JobStatusVariant::Complete {
                     p style="color: var(--success, #10b981); font-weight: 600; margin-top: 0.5rem;" {
                         "✓ Post scanning complete"
                     }
+                } @else if status_variant == JobStatusVariant::Cancelled {
+                    p style="color: var(--foreground-muted, #71717a); margin-top: 0.5rem;" {
+                        "Post scanning was cancelled. Links found before cancelling are still archived."
+                    }
                 }
 
                 // Phase 2: Archiving Content
@@ -647,23 +801,29 @@ pub fn render_thread_job_status_page(params: &ThreadJobStatusParams<'_>) -> Mark
             }
         }
 
+        // Live scanning progress via server-sent events
+        @if scanning_phase {
+            script {
+                (PreEscaped(thread_job_events_script(job)))
+            }
+            p style="color: var(--foreground-muted, #71717a); font-size: 0.875rem;" {
+                "This page updates live while scanning posts."
+            }
+        }
+
         // Auto-refresh with JavaScript
         @if should_refresh {
             script {
-                (PreEscaped(format!(r#"
-                (function() {{
-                    setTimeout(function() {{
+                (PreEscaped(r#"
+                (function() {
+                    setTimeout(function() {
                         window.location.reload();
-                    }}, {});
-                }})();
-                "#, refresh_interval * 1000)))
+                    }, 5000);
+                })();
+                "#))
             }
             p style="color: var(--foreground-muted, #71717a); font-size: 0.875rem;" {
-                @if scanning_phase {
-                    "This page will automatically refresh every second while scanning posts."
-                } @else if archiving_phase {
-                    "This page will automatically refresh every 5 seconds while archives are being created."
-                }
+                "This page will automatically refresh every 5 seconds while archives are being created."
             }
         }
 
@@ -703,6 +863,7 @@ mod tests {
             content_hash: Some("abc123".to_string()),
             published_at: Some("2024-01-15 12:00:00".to_string()),
             processed_at: "2024-01-15 12:30:00".to_string(),
+            author_handle: None,
         }
     }
 
@@ -853,6 +1014,7 @@ mod tests {
             thread_key: "forum.example.com:123",
             posts: &posts,
             archives: &archives,
+            post_counts: &[],
             user: None,
         };
         let html = render_thread_detail_page(&params).into_string();
@@ -877,6 +1039,7 @@ mod tests {
             thread_key: "forum.example.com:123",
             posts: &posts,
             archives: &archives,
+            post_counts: &[],
             user: None,
         };
         let html = render_thread_detail_page(&params).into_string();
@@ -899,7 +1062,8 @@ mod tests {
         assert!(html.contains("Processing"));
         assert!(html.contains("Progress"));
         assert!(html.contains("5 / 10")); // processed / total
-        assert!(html.contains("window.location.reload")); // JavaScript refresh
+        assert!(html.contains("new EventSource('/thread-job/42/events')")); // Live progress
+        assert!(html.contains(r#"id="job-processed-posts""#));
     }
 
     #[test]
@@ -967,6 +1131,33 @@ mod tests {
         assert!(html.contains("Connection timeout"));
     }
 
+    #[test]
+    fn test_job_status_page_cancel_button() {
+        let job = sample_job();
+        let params = ThreadJobStatusParams {
+            job: &job,
+            archives: &[],
+            archive_status_counts: ArchiveStatusCounts::default(),
+            user: None,
+        };
+        let html = render_thread_job_status_page(&params).into_string();
+        assert!(html.contains(&format!("/submit/thread/{}/cancel", job.id)));
+
+        let mut job = sample_job();
+        job.status = "cancelled".to_string();
+        let params = ThreadJobStatusParams {
+            job: &job,
+            archives: &[],
+            archive_status_counts: ArchiveStatusCounts::default(),
+            user: None,
+        };
+        let html = render_thread_job_status_page(&params).into_string();
+        assert!(html.contains("Cancelled"));
+        assert!(html.contains("Post scanning was cancelled"));
+        assert!(!html.contains("/cancel\""));
+        assert!(!html.contains("window.location.reload"));
+    }
+
     #[test]
     fn test_job_status_variant() {
         assert_eq!(
@@ -985,6 +1176,10 @@ mod tests {
             JobStatusVariant::from_str("failed"),
             JobStatusVariant::Failed
         );
+        assert_eq!(
+            JobStatusVariant::from_str("cancelled"),
+            JobStatusVariant::Cancelled
+        );
         assert_eq!(
             JobStatusVariant::from_str("unknown"),
             JobStatusVariant::Pending
@@ -1010,12 +1205,43 @@ mod tests {
         assert!(html.contains("width: 50%"));
     }
 
+    fn fixed_now() -> DateTime<Utc> {
+        parse_datetime("2026-01-22 16:31:00").unwrap()
+    }
+
+    #[test]
+    fn test_format_friendly_datetime_sqlite() {
+        let friendly = format_friendly_datetime(Some("2026-01-20 16:31:00"), fixed_now()).unwrap();
+        assert_eq!(friendly.absolute, "Jan 20, 2026 · 16:31 UTC");
+        assert_eq!(friendly.relative, "2 days ago");
+    }
+
+    #[test]
+    fn test_format_friendly_datetime_rfc3339() {
+        let friendly =
+            format_friendly_datetime(Some("2026-01-22T15:31:00.000Z"), fixed_now()).unwrap();
+        assert_eq!(friendly.absolute, "Jan 22, 2026 · 15:31 UTC");
+        assert_eq!(friendly.relative, "1 hour ago");
+    }
+
+    #[test]
+    fn test_format_friendly_datetime_null_and_malformed() {
+        assert_eq!(format_friendly_datetime(None, fixed_now()), None);
+        assert_eq!(format_friendly_datetime(Some(""), fixed_now()), None);
+        assert_eq!(format_friendly_datetime(Some("invalid"), fixed_now()), None);
+        assert_eq!(
+            format_friendly_datetime(Some("2026-13-45 99:00:00"), fixed_now()),
+            None
+        );
+    }
+
     #[test]
-    fn test_format_datetime() {
-        assert_eq!(format_datetime("2024-01-15 12:34:56"), "Jan 15, 2024 12:34");
-        assert_eq!(format_datetime("2023-12-31 23:59:59"), "Dec 31, 2023 23:59");
-        // Invalid format should return original string
-        assert_eq!(format_datetime("invalid"), "invalid");
-        assert_eq!(format_datetime("Unknown"), "Unknown");
+    fn test_format_relative() {
+        assert_eq!(format_relative(-5), "in the future");
+        assert_eq!(format_relative(30), "just now");
+        assert_eq!(format_relative(60), "1 minute ago");
+        assert_eq!(format_relative(3 * 3600), "3 hours ago");
+        assert_eq!(format_relative(45 * 86400), "1 month ago");
+        assert_eq!(format_relative(800 * 86400), "2 years ago");
     }
 }
@@ -13,6 +13,9 @@ use super::diff;
 use super::export;
 use super::feeds;
 use super::pages;
+use super::sitemap;
+use super::thread_job_events;
+use super::warc;
 use super::AppState;
 use crate::auth::{MaybeUser, RequireAdmin, RequireApproved, RequireUser};
 use crate::components::OpenGraphMetadata;
@@ -21,26 +24,34 @@ use crate::db::{
     count_archives_by_content_type, count_archives_by_status, count_archives_by_status_for_thread,
     count_links, count_posts, count_submissions_from_ip_last_hour,
     count_user_thread_archive_jobs_last_hour, create_comment, create_comment_reply,
-    create_pending_archive, delete_archive, find_artifact_by_s3_key, get_all_archives_table_view,
-    get_all_threads, get_archive, get_archive_by_link_id, get_archive_timeline,
-    get_archives_by_domain_display, get_archives_for_post_display, get_archives_for_posts_display,
-    get_archives_for_thread_job, get_artifacts_for_archive, get_comment_edit_history,
-    get_comment_with_author, get_jobs_for_archive, get_link, get_link_by_normalized_url,
-    get_link_occurrences_with_posts, get_nsfw_count, get_post_by_guid, get_posts_by_topic_id,
-    get_quality_metrics, get_queue_stats, get_quote_reply_chain, get_recent_activity_counts,
-    get_recent_archives_display_filtered, get_recent_archives_filtered_full,
-    get_recent_archives_with_filters, get_recent_failed_archives, get_storage_stats,
-    get_subtitle_languages_for_archive, get_thread_archive_job, get_top_domains,
-    get_user_submission_stats, get_user_submissions, get_video_file, has_missing_artifacts,
-    insert_link, insert_submission, insert_thread_archive_job, mark_og_extraction_attempted,
-    pin_comment, remove_comment_reaction, reset_archive_for_rearchive,
+    create_pending_archive, delete_archive, find_artifact_by_s3_key, forum_author_handle,
+    get_all_archives_table_view, get_all_threads, get_archive, get_archive_by_link_id,
+    get_archive_by_short_code, get_archive_keep_versions, get_archive_timeline,
+    get_archive_versions, get_archives_by_domain_display, get_archives_for_post_display,
+    get_archives_for_posts_display, get_archives_for_thread_job, get_artifacts_for_archive,
+    get_comment_edit_history, get_comment_with_author, get_content_version,
+    get_content_versions_for_link, get_domain_stats, get_failure_kind_counts, get_jobs_for_archive,
+    get_link, get_link_by_normalized_url, get_link_counts_for_posts,
+    get_link_occurrences_with_posts, get_nsfw_count, get_or_create_link,
+    get_pending_counts_by_domain, get_playlist_members_display, get_post_by_guid,
+    get_post_snapshot_archive, get_posts_by_forum_author, get_posts_by_topic_id,
+    get_quality_metrics, get_queue_stats, get_quote_reply_chain, get_random_archive_id,
+    get_recent_activity_counts, get_recent_archives_display_filtered, get_recent_failed_archives,
+    get_self_thread, get_skip_reason_counts, get_storage_by_domain, get_storage_stats,
+    get_subtitle_languages_for_archive, get_thread_archive_job, get_thumbnails_for_archives,
+    get_top_domains, get_user_submission_stats, get_user_submissions, get_video_file,
+    get_watched_link, has_missing_artifacts, insert_submission, insert_thread_archive_job,
+    is_valid_short_code, mark_og_extraction_attempted, pin_comment, remove_comment_reaction,
+    reset_archive_for_rearchive, reset_archive_for_retry, reset_og_extraction,
     reset_single_skipped_archive, reset_skipped_archives, search_archives_display_filtered,
-    search_archives_filtered_full, set_archive_nsfw, soft_delete_comment,
-    submission_exists_for_url, thread_archive_job_exists_recent, thread_key_from_url,
-    toggle_archive_nsfw, unpin_comment, update_archive_og_metadata, update_comment,
-    upsert_subtitle_language, NewLink, NewSubmission, NewThreadArchiveJob,
+    search_archives_filtered_full, set_archive_keep_versions, set_archive_nsfw,
+    set_submission_complete, set_thread_archive_job_cancelled, soft_delete_comment,
+    submission_exists_for_url, submission_retry_after_secs, thread_archive_job_exists_recent,
+    thread_key_from_url, toggle_archive_nsfw, unpin_comment, unwatch_link,
+    update_archive_og_metadata, update_comment, upsert_subtitle_language, watch_link, Archive,
+    ArchiveQuery, ArchiveSort, NewLink, NewSubmission, NewThreadArchiveJob, SortDirection, User,
 };
-use crate::handlers::normalize_url;
+use crate::handlers::normalize_link_url;
 use crate::og_extractor;
 
 /// Pagination query parameters.
@@ -53,6 +64,10 @@ struct PaginationParams {
     content_type: Option<String>,
     /// Filter by source platform (e.g., "reddit", "youtube", "tiktok", "twitter")
     source: Option<String>,
+    /// Column to sort the all-archives table by (`id`, `size`, `domain` or `date`)
+    sort_by: Option<String>,
+    /// Sort direction for the all-archives table (`asc` or `desc`)
+    sort_dir: Option<String>,
 }
 
 const ITEMS_PER_PAGE: i64 = 24;
@@ -92,6 +107,43 @@ pub fn router() -> Router<AppState> {
             "/admin/excluded-domains/delete",
             post(auth::admin_delete_excluded_domain),
         )
+        .route(
+            "/admin/quote-policies/set",
+            post(auth::admin_set_quote_policy),
+        )
+        .route(
+            "/admin/quote-policies/delete",
+            post(auth::admin_delete_quote_policy),
+        )
+        .route(
+            "/admin/priority-domains/add",
+            post(auth::admin_add_priority_domain),
+        )
+        .route(
+            "/admin/priority-domains/delete",
+            post(auth::admin_delete_priority_domain),
+        )
+        .route(
+            "/admin/versioned-domains/add",
+            post(auth::admin_add_versioned_domain),
+        )
+        .route(
+            "/admin/versioned-domains/delete",
+            post(auth::admin_delete_versioned_domain),
+        )
+        .route(
+            "/admin/external-services",
+            get(auth::admin_external_services_page),
+        )
+        .route(
+            "/admin/external-services/set",
+            post(auth::admin_set_external_service_rule),
+        )
+        .route(
+            "/admin/external-services/delete",
+            post(auth::admin_delete_external_service_rule),
+        )
+        .route("/admin/audit", get(auth::admin_audit_log))
         .route("/admin/user/:id", get(auth::admin_user_profile))
         .route(
             "/admin/forum-user/:username",
@@ -105,6 +157,21 @@ pub fn router() -> Router<AppState> {
             "/admin/subtitle-language/delete",
             post(auth::admin_delete_subtitle_language),
         )
+        .route(
+            "/admin/reprocess-missing-artifacts",
+            post(auth::admin_reprocess_missing_artifacts),
+        )
+        .route("/admin/recompute-sizes", post(auth::admin_recompute_sizes))
+        .route(
+            "/admin/links/duplicates",
+            get(auth::admin_duplicate_links_page),
+        )
+        .route("/admin/links/merge", post(auth::admin_merge_links))
+        .route("/admin/og/reset", post(auth::admin_reset_og_extraction))
+        .route(
+            "/admin/bulk-import",
+            get(auth::admin_bulk_import_page).post(auth::admin_bulk_import),
+        )
         .route("/admin/upgrade/ytdlp", get(auth::admin_upgrade_ytdlp))
         .route(
             "/admin/upgrade/gallery-dl",
@@ -116,13 +183,28 @@ pub fn router() -> Router<AppState> {
         .route("/submit", get(submit_form).post(submit_url))
         .route("/submit/thread", post(submit_thread))
         .route("/submit/thread/:id", get(thread_job_status))
+        .route("/submit/thread/:id/cancel", post(cancel_thread_job))
+        .route("/thread-job/:id/events", get(thread_job_progress_events))
         .route("/archive/:id", get(archive_detail))
+        .route("/a/:code", get(archive_permalink))
+        .route("/random", get(random_archive))
         .route("/archive/:id/rearchive", post(rearchive))
         .route(
             "/archive/:id/get-missing-artifacts",
             post(get_missing_artifacts),
         )
         .route("/archive/:id/toggle-nsfw", post(toggle_nsfw))
+        .route("/archive/:id/reset-og", post(reset_archive_og_extraction))
+        .route("/archive/:id/watch", post(watch_archive_link))
+        .route("/archive/:id/unwatch", post(unwatch_archive_link))
+        .route(
+            "/archive/:id/keep-versions",
+            post(set_archive_keep_versions_handler),
+        )
+        .route(
+            "/archive/:id/versions/:version_id",
+            get(content_version_detail),
+        )
         .route("/archive/:id/delete", post(delete_archive_handler))
         .route("/archive/:id/retry-skipped", post(retry_skipped))
         .route("/archive/:id/comment", post(create_comment_handler))
@@ -152,25 +234,41 @@ pub fn router() -> Router<AppState> {
         )
         .route("/compare/:id1/:id2", get(compare_archives))
         .route("/post/:guid", get(post_detail))
+        .route("/forum-user/:username", get(forum_user_page))
         .route("/threads/:thread_id", get(thread_detail))
         .route("/threads", get(threads_list))
+        .route("/playlist/:id", get(playlist_detail))
         .route("/site/:site", get(site_list))
         .route("/stats", get(stats))
         .route("/healthz", get(health))
         .route("/favicon.ico", get(favicon))
+        .route("/oembed", get(oembed))
         .route("/feed.rss", get(feed_rss))
         .route("/feed.atom", get(feed_atom))
-        .route("/export/:site", get(export::export_site))
+        .route("/sitemap.xml", get(sitemap_xml))
+        .route("/sitemap/:file", get(sitemap_page))
         .route("/api/archives", get(api_archives))
+        .route("/api/archive/:id", get(api_archive_json))
         .route("/api/archive/:id/progress", get(api_archive_progress))
         .route("/api/archive/:id/comments", get(api_archive_comments))
         .route("/api/search", get(api_search))
-        .route("/s3/*path", get(serve_s3_file))
+        .route("/api/queue.json", get(api_queue_json))
         // Debug routes
         .route("/debug/queue", get(debug_queue))
         .route("/debug/reset-skipped", post(debug_reset_skipped))
 }
 
+/// Routes that serve large downloads.
+///
+/// These are kept out of [`router`] so they are exempt from the request
+/// timeout and body size limit applied to the rest of the app.
+pub fn download_router() -> Router<AppState> {
+    Router::new()
+        .route("/export/:site", get(export::export_site))
+        .route("/archive/:id/warc", get(warc::archive_warc))
+        .route("/s3/*path", get(serve_s3_file))
+}
+
 // ========== HTML Routes ==========
 
 async fn home(
@@ -181,7 +279,7 @@ async fn home(
     let page = params.page;
 
     let all_recent = match get_recent_archives_display_filtered(
-        state.db.pool(),
+        state.db.read_pool(),
         100,
         params.content_type.as_deref(),
         params.source.as_deref(),
@@ -213,7 +311,7 @@ async fn home(
 
     // Generate OG metadata for home page (only on first page without filters)
     let og_metadata = if page == 0 && params.content_type.is_none() && params.source.is_none() {
-        match state.stats_cache.get_or_refresh(state.db.pool()).await {
+        match state.stats_cache.get_or_refresh(state.db.read_pool()).await {
             Ok(stats) => {
                 let description = stats.format_breakdown();
                 Some(
@@ -231,6 +329,16 @@ async fn home(
         None
     };
 
+    // Thumbnails are optional decoration; render without them on error
+    let archive_ids: Vec<i64> = archives.iter().map(|a| a.id).collect();
+    let thumbnails = match get_thumbnails_for_archives(state.db.read_pool(), &archive_ids).await {
+        Ok(t) => Some(t),
+        Err(e) => {
+            tracing::warn!("Failed to fetch thumbnails: {e}");
+            None
+        }
+    };
+
     let markup = pages::render_home_paginated(
         &archives,
         recent_failed_count,
@@ -240,6 +348,7 @@ async fn home(
         params.source.as_deref(),
         user.as_ref(),
         og_metadata,
+        thumbnails.as_ref(),
     );
     Html(markup.into_string()).into_response()
 }
@@ -252,7 +361,7 @@ async fn recent_failed_archives(
     let page = params.page;
 
     let all_recent = match get_recent_archives_display_filtered(
-        state.db.pool(),
+        state.db.read_pool(),
         100,
         params.content_type.as_deref(),
         params.source.as_deref(),
@@ -301,7 +410,7 @@ async fn recent_all_archives(
 
     // Count total archives with filters
     let total_count = match count_all_archives_filtered(
-        state.db.pool(),
+        state.db.read_pool(),
         params.content_type.as_deref(),
         params.source.as_deref(),
     )
@@ -318,13 +427,27 @@ async fn recent_all_archives(
     let total_pages = ((total_count as f64 / TABLE_ITEMS_PER_PAGE as f64).ceil() as usize).max(1);
     let offset = (page as i64) * TABLE_ITEMS_PER_PAGE;
 
+    // Unknown sort keys fall back to the default rather than reaching SQL
+    let sort_by = params
+        .sort_by
+        .as_deref()
+        .and_then(ArchiveSort::from_param)
+        .unwrap_or(ArchiveSort::Id);
+    let sort_dir = params
+        .sort_dir
+        .as_deref()
+        .and_then(SortDirection::from_param)
+        .unwrap_or_else(|| sort_by.default_direction());
+
     // Fetch page of archives
     let archives = match get_all_archives_table_view(
-        state.db.pool(),
+        state.db.read_pool(),
         TABLE_ITEMS_PER_PAGE,
         offset,
         params.content_type.as_deref(),
         params.source.as_deref(),
+        sort_by,
+        sort_dir,
     )
     .await
     {
@@ -341,6 +464,8 @@ async fn recent_all_archives(
         total_pages,
         content_type_filter: params.content_type.as_deref(),
         source_filter: params.source.as_deref(),
+        sort_by,
+        sort_dir,
         user: user.as_ref(),
     };
 
@@ -374,7 +499,7 @@ async fn search(
 
     let archives = if query.is_empty() {
         match get_recent_archives_display_filtered(
-            state.db.pool(),
+            state.db.read_pool(),
             per_page + offset,
             params.content_type.as_deref(),
             params.source.as_deref(),
@@ -389,7 +514,7 @@ async fn search(
         }
     } else {
         match search_archives_display_filtered(
-            state.db.pool(),
+            state.db.read_pool(),
             &query,
             per_page,
             params.content_type.as_deref(),
@@ -419,12 +544,49 @@ async fn search(
     Html(markup.into_string()).into_response()
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ArchiveDetailQuery {
+    /// Set when a submission was short-circuited to this existing archive.
+    #[serde(default)]
+    already_archived: bool,
+    /// Preserved capture to compare against the current one.
+    version: Option<i64>,
+}
+
+/// Handler for archive permalinks (GET /a/:code), redirecting to the detail page.
+async fn archive_permalink(State(state): State<AppState>, Path(code): Path<String>) -> Response {
+    if !is_valid_short_code(&code) {
+        return (StatusCode::NOT_FOUND, "Archive not found").into_response();
+    }
+    match get_archive_by_short_code(state.db.read_pool(), &code).await {
+        Ok(Some(archive)) => Redirect::to(&format!("/archive/{}", archive.id)).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "Archive not found").into_response(),
+        Err(e) => {
+            tracing::error!("Failed to fetch archive by short code: {e}");
+            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+        }
+    }
+}
+
+/// Redirect to a random complete, non-NSFW archive, or home when there are none.
+async fn random_archive(State(state): State<AppState>) -> Response {
+    match get_random_archive_id(state.db.read_pool()).await {
+        Ok(Some(id)) => Redirect::to(&format!("/archive/{id}")).into_response(),
+        Ok(None) => Redirect::to("/").into_response(),
+        Err(e) => {
+            tracing::error!("Failed to pick a random archive: {e}");
+            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+        }
+    }
+}
+
 async fn archive_detail(
     State(state): State<AppState>,
     Path(id): Path<i64>,
+    Query(query): Query<ArchiveDetailQuery>,
     MaybeUser(user): MaybeUser,
 ) -> Response {
-    let archive = match get_archive(state.db.pool(), id).await {
+    let archive = match get_archive(state.db.read_pool(), id).await {
         Ok(Some(a)) => a,
         Ok(None) => {
             return (StatusCode::NOT_FOUND, "Archive not found").into_response();
@@ -435,7 +597,7 @@ async fn archive_detail(
         }
     };
 
-    let link = match get_link(state.db.pool(), archive.link_id).await {
+    let link = match get_link(state.db.read_pool(), archive.link_id).await {
         Ok(Some(l)) => l,
         Ok(None) => {
             return (StatusCode::NOT_FOUND, "Link not found").into_response();
@@ -446,7 +608,7 @@ async fn archive_detail(
         }
     };
 
-    let artifacts = match get_artifacts_for_archive(state.db.pool(), id).await {
+    let artifacts = match get_artifacts_for_archive(state.db.read_pool(), id).await {
         Ok(a) => a,
         Err(e) => {
             tracing::error!("Failed to fetch artifacts: {e}");
@@ -455,13 +617,14 @@ async fn archive_detail(
     };
 
     // Load and populate subtitle languages
-    let subtitle_languages = match get_subtitle_languages_for_archive(state.db.pool(), id).await {
-        Ok(langs) => langs,
-        Err(e) => {
-            tracing::error!("Failed to fetch subtitle languages: {e}");
-            std::collections::HashMap::new()
-        }
-    };
+    let subtitle_languages =
+        match get_subtitle_languages_for_archive(state.db.read_pool(), id).await {
+            Ok(langs) => langs,
+            Err(e) => {
+                tracing::error!("Failed to fetch subtitle languages: {e}");
+                std::collections::HashMap::new()
+            }
+        };
 
     // Detect and store language for subtitle artifacts missing entries
     for artifact in artifacts.iter().filter(|a| a.kind == "subtitles") {
@@ -523,24 +686,25 @@ async fn archive_detail(
     }
 
     // Re-fetch subtitle languages after detection
-    let subtitle_languages = match get_subtitle_languages_for_archive(state.db.pool(), id).await {
-        Ok(langs) => langs,
-        Err(e) => {
-            tracing::error!("Failed to re-fetch subtitle languages: {e}");
-            std::collections::HashMap::new()
-        }
-    };
+    let subtitle_languages =
+        match get_subtitle_languages_for_archive(state.db.read_pool(), id).await {
+            Ok(langs) => langs,
+            Err(e) => {
+                tracing::error!("Failed to re-fetch subtitle languages: {e}");
+                std::collections::HashMap::new()
+            }
+        };
 
-    let occurrences = match get_link_occurrences_with_posts(state.db.pool(), archive.link_id).await
-    {
-        Ok(o) => o,
-        Err(e) => {
-            tracing::error!("Failed to fetch link occurrences: {e}");
-            Vec::new()
-        }
-    };
+    let occurrences =
+        match get_link_occurrences_with_posts(state.db.read_pool(), archive.link_id).await {
+            Ok(o) => o,
+            Err(e) => {
+                tracing::error!("Failed to fetch link occurrences: {e}");
+                Vec::new()
+            }
+        };
 
-    let jobs = match get_jobs_for_archive(state.db.pool(), archive.id).await {
+    let jobs = match get_jobs_for_archive(state.db.read_pool(), archive.id).await {
         Ok(j) => j,
         Err(e) => {
             tracing::error!("Failed to fetch archive jobs: {e}");
@@ -551,7 +715,7 @@ async fn archive_detail(
     // Fetch quote/reply chain for Twitter/X archives
     let quote_reply_chain =
         if archive.quoted_archive_id.is_some() || archive.reply_to_archive_id.is_some() {
-            match get_quote_reply_chain(state.db.pool(), archive.id).await {
+            match get_quote_reply_chain(state.db.read_pool(), archive.id).await {
                 Ok(chain) => chain,
                 Err(e) => {
                     tracing::error!("Failed to fetch quote/reply chain: {e}");
@@ -562,8 +726,26 @@ async fn archive_detail(
             Vec::new()
         };
 
+    // Fetch the author's self-thread for Twitter/X archives
+    let is_twitter = link.domain == "x.com"
+        || link.domain == "twitter.com"
+        || link.domain.ends_with(".x.com")
+        || link.domain.ends_with(".twitter.com");
+    let self_thread = if is_twitter {
+        match get_self_thread(state.db.read_pool(), archive.id).await {
+            Ok(thread) => thread,
+            Err(e) => {
+                tracing::error!("Failed to fetch self-thread: {e}");
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+
     // Check if archive has missing artifacts
-    let has_missing_artifacts = match has_missing_artifacts(state.db.pool(), archive.id).await {
+    let has_missing_artifacts = match has_missing_artifacts(state.db.read_pool(), archive.id).await
+    {
         Ok(missing) => missing,
         Err(e) => {
             tracing::error!("Failed to check for missing artifacts: {e}");
@@ -591,52 +773,57 @@ async fn archive_detail(
                 (None, None, None, None)
             };
 
-        // Build metadata using extracted data or fallback to generated data
-        let title = og_title
-            .as_deref()
-            .or(archive.content_title.as_deref())
-            .unwrap_or("Untitled Archive");
-
-        let description = og_description.unwrap_or_else(|| {
-            if let Some(text) = &archive.content_text {
-                crate::components::truncate_text(text, 200)
-            } else {
-                format!("Archived content from {}", link.domain)
-            }
-        });
+        Some(pages::archive_og_metadata(
+            &archive,
+            &link,
+            &state.config.public_base_url,
+            og_title.as_deref(),
+            og_description.as_deref(),
+            og_image.as_deref(),
+            og_type.as_deref(),
+        ))
+    };
 
-        // Use extracted OG image if available, otherwise use thumbnail
-        let image_url = if !archive.is_nsfw {
-            og_image.or_else(|| {
-                // Fallback to thumbnail if no OG image
-                if let Some(ref base) = state.config.s3_public_url_base {
-                    artifacts
-                        .iter()
-                        .find(|a| a.kind == "thumbnail")
-                        .map(|a| format!("{}/{}", base, a.s3_key))
-                } else {
-                    None
-                }
-            })
-        } else {
+    let watched = match get_watched_link(state.db.read_pool(), archive.link_id).await {
+        Ok(w) => w,
+        Err(e) => {
+            tracing::error!("Failed to fetch watched link: {e}");
             None
-        };
+        }
+    };
 
-        let archive_url = format!("/archive/{}", archive.id);
-        let og_type_str = og_type.as_deref().unwrap_or("article");
+    let content_versions =
+        match get_content_versions_for_link(state.db.read_pool(), archive.link_id).await {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::error!("Failed to fetch content versions: {e}");
+                Vec::new()
+            }
+        };
 
-        Some(
-            OpenGraphMetadata::new(title, &description, &archive_url)
-                .with_type(og_type_str)
-                .with_image(image_url.as_deref())
-                .with_nsfw(archive.is_nsfw)
-                .with_twitter_card(if image_url.is_some() {
-                    "summary_large_image"
-                } else {
-                    "summary"
-                }),
-        )
+    let archive_versions = match get_archive_versions(state.db.read_pool(), archive.id).await {
+        Ok(v) => v,
+        Err(e) => {
+            tracing::error!("Failed to fetch archive versions: {e}");
+            Vec::new()
+        }
+    };
+    let keep_versions = match get_archive_keep_versions(state.db.read_pool(), archive.id).await {
+        Ok(keep) => keep,
+        Err(e) => {
+            tracing::error!("Failed to fetch archive versioning: {e}");
+            false
+        }
     };
+    let selected_version = query
+        .version
+        .and_then(|n| archive_versions.iter().find(|v| v.version == n));
+    let version_diff = selected_version.map(|v| {
+        diff::compute_diff(
+            v.content_text.as_deref().unwrap_or(""),
+            archive.content_text.as_deref().unwrap_or(""),
+        )
+    });
 
     let params = pages::ArchiveDetailParams {
         archive: &archive,
@@ -645,10 +832,20 @@ async fn archive_detail(
         occurrences: &occurrences,
         jobs: &jobs,
         quote_reply_chain: &quote_reply_chain,
+        self_thread: &self_thread,
         user: user.as_ref(),
         has_missing_artifacts,
         og_metadata,
         subtitle_languages: &subtitle_languages,
+        already_archived: query.already_archived,
+        watched: watched.as_ref(),
+        content_versions: &content_versions,
+        archive_versions: &archive_versions,
+        selected_version,
+        version_diff: version_diff.as_ref(),
+        keep_versions,
+        ipfs_gateway_urls: &state.config.ipfs_gateway_urls,
+        public_base_url: &state.config.public_base_url,
     };
     let markup = pages::render_archive_detail_page(&params);
     Html(markup.into_string()).into_response()
@@ -665,7 +862,7 @@ async fn rearchive(
 ) -> Response {
     tracing::debug!(archive_id = id, "HTTP API: POST /archive/:id/rearchive");
     // Check that the archive exists
-    let archive = match get_archive(state.db.pool(), id).await {
+    let archive = match get_archive(state.db.read_pool(), id).await {
         Ok(Some(a)) => a,
         Ok(None) => {
             return (StatusCode::NOT_FOUND, "Archive not found").into_response();
@@ -685,6 +882,23 @@ async fn rearchive(
             .into_response();
     }
 
+    // Keep the current capture first if the archive is versioned
+    if let Err(e) = crate::archiver::versions::preserve_current_capture(
+        &state.db,
+        &state.s3,
+        &state.config.s3_prefix,
+        &archive,
+    )
+    .await
+    {
+        tracing::error!(archive_id = id, error = ?e, "Failed to preserve archive version");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to preserve the current capture",
+        )
+            .into_response();
+    }
+
     // Reset the archive for re-processing
     if let Err(e) = reset_archive_for_rearchive(state.db.pool(), id).await {
         tracing::error!(error = ?e, "Failed to reset archive for rearchive");
@@ -717,7 +931,7 @@ async fn get_missing_artifacts(
     );
 
     // Check that the archive exists
-    let archive = match get_archive(state.db.pool(), id).await {
+    let archive = match get_archive(state.db.read_pool(), id).await {
         Ok(Some(a)) => a,
         Ok(None) => {
             return (StatusCode::NOT_FOUND, "Archive not found").into_response();
@@ -751,11 +965,12 @@ async fn get_missing_artifacts(
     }
 
     // Check what artifacts are missing
-    let needs_subtitles = !has_artifact_kind(state.db.pool(), id, ArtifactKind::Subtitles.as_str())
-        .await
-        .unwrap_or(true);
+    let needs_subtitles =
+        !has_artifact_kind(state.db.read_pool(), id, ArtifactKind::Subtitles.as_str())
+            .await
+            .unwrap_or(true);
     let needs_transcript =
-        !has_artifact_kind(state.db.pool(), id, ArtifactKind::Transcript.as_str())
+        !has_artifact_kind(state.db.read_pool(), id, ArtifactKind::Transcript.as_str())
             .await
             .unwrap_or(true);
 
@@ -803,7 +1018,7 @@ async fn get_missing_artifacts(
 }
 
 /// Background task to fetch supplementary artifacts for a video archive.
-async fn process_supplementary_artifacts_job(
+pub async fn process_supplementary_artifacts_job(
     state: AppState,
     archive_id: i64,
     job_id: i64,
@@ -821,8 +1036,8 @@ async fn process_supplementary_artifacts_job(
 
     // Get the link
     let link = match get_link(
-        state.db.pool(),
-        match get_archive(state.db.pool(), archive_id).await {
+        state.db.read_pool(),
+        match get_archive(state.db.read_pool(), archive_id).await {
             Ok(Some(a)) => a.link_id,
             Ok(None) => {
                 let _ = set_job_failed(state.db.pool(), job_id, "Archive not found").await;
@@ -878,8 +1093,11 @@ async fn process_supplementary_artifacts_job(
         tracing::info!(archive_id, "Using TikTok-specific subtitle handling");
 
         // Get meta.json S3 key from database (respects configured s3_prefix)
-        let meta_key = match crate::db::get_metadata_s3_key_for_archive(state.db.pool(), archive_id)
-            .await
+        let meta_key = match crate::db::get_metadata_s3_key_for_archive(
+            state.db.read_pool(),
+            archive_id,
+        )
+        .await
         {
             Ok(Some(key)) => key,
             Ok(None) => {
@@ -1188,7 +1406,7 @@ async fn process_supplementary_artifacts_job(
         );
 
         // Fetch existing subtitle artifacts
-        match get_artifacts_for_archive(state.db.pool(), archive_id).await {
+        match get_artifacts_for_archive(state.db.read_pool(), archive_id).await {
             Ok(all_artifacts) => {
                 let subtitle_artifacts: Vec<_> = all_artifacts
                     .into_iter()
@@ -1405,7 +1623,8 @@ async fn toggle_nsfw(
         user_id = user.id,
         "HTTP API: POST /archive/:id/toggle-nsfw"
     );
-    let client_ip = addr.ip().to_string();
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
     let forwarded_for = headers.get("x-forwarded-for").and_then(|v| v.to_str().ok());
     let user_agent = headers
         .get(header::USER_AGENT)
@@ -1417,23 +1636,15 @@ async fn toggle_nsfw(
             tracing::info!(archive_id = id, is_nsfw = new_status, "Toggled NSFW status");
 
             // Audit log the NSFW toggle
-            if let Err(e) = crate::db::create_audit_event(
-                state.db.pool(),
-                Some(user.id),
-                if new_status {
-                    "nsfw_enabled"
-                } else {
-                    "nsfw_disabled"
-                },
-                Some("archive"),
-                Some(id),
-                None,
-                Some(&client_ip),
-                forwarded_for,
-                user_agent.as_deref(),
-            )
-            .await
-            {
+            let action = if new_status {
+                crate::db::AuditAction::NsfwEnabled { archive_id: id }
+            } else {
+                crate::db::AuditAction::NsfwDisabled { archive_id: id }
+            };
+            let actor =
+                crate::db::AuditActor::from_request(Some(user.id), &client_ip, forwarded_for)
+                    .with_user_agent(user_agent.as_deref());
+            if let Err(e) = crate::db::record_audit(state.db.pool(), &actor, &action).await {
                 tracing::error!("Failed to create audit event: {e}");
             }
 
@@ -1446,108 +1657,490 @@ async fn toggle_nsfw(
     }
 }
 
-/// Handler for deleting an archive (POST /archive/:id/delete).
-async fn delete_archive_handler(
+/// Handler for re-running OG extraction on an archive (POST /archive/:id/reset-og).
+///
+/// Viewing the archive afterwards re-extracts from the stored HTML.
+async fn reset_archive_og_extraction(
     State(state): State<AppState>,
     Path(id): Path<i64>,
-    RequireAdmin(_admin): RequireAdmin,
+    RequireAdmin(admin): RequireAdmin,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
 ) -> Response {
-    tracing::debug!(archive_id = id, "HTTP API: POST /archive/:id/delete");
-    // Get the archive first to log what we're deleting
-    let archive = match get_archive(state.db.pool(), id).await {
-        Ok(Some(a)) => a,
-        Ok(None) => {
-            return (StatusCode::NOT_FOUND, "Archive not found").into_response();
+    tracing::debug!(archive_id = id, "HTTP API: POST /archive/:id/reset-og");
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
+    let forwarded_for = headers.get("x-forwarded-for").and_then(|v| v.to_str().ok());
+
+    match reset_og_extraction(state.db.pool(), id).await {
+        Ok(true) => {
+            tracing::info!(archive_id = id, admin_id = admin.id, "Reset OG extraction");
+            let actor =
+                crate::db::AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for);
+            let action = crate::db::AuditAction::AdminResetOgExtraction {
+                archive_id: Some(id),
+                archives_reset: 1,
+            };
+            if let Err(e) = crate::db::record_audit(state.db.pool(), &actor, &action).await {
+                tracing::error!("Failed to create audit event: {e}");
+            }
+            Redirect::to(&format!("/archive/{id}")).into_response()
         }
+        Ok(false) => (StatusCode::NOT_FOUND, "Archive not found").into_response(),
         Err(e) => {
-            tracing::error!("Failed to fetch archive for deletion: {e}");
-            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+            tracing::error!("Failed to reset OG extraction: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to reset OG extraction",
+            )
+                .into_response()
         }
-    };
-
-    // Don't allow deletion if currently processing
-    if archive.status == "processing" {
-        return (
-            StatusCode::CONFLICT,
-            "Cannot delete archive while processing",
-        )
-            .into_response();
-    }
-
-    if let Err(e) = delete_archive(state.db.pool(), id).await {
-        tracing::error!("Failed to delete archive: {e}");
-        return (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "Failed to delete archive",
-        )
-            .into_response();
     }
+}
 
-    tracing::info!(archive_id = id, "Archive deleted");
-
-    // Redirect to home page since archive no longer exists
-    axum::response::Redirect::to("/").into_response()
+#[derive(Debug, Deserialize)]
+pub struct WatchForm {
+    #[serde(default)]
+    interval_hours: String,
 }
 
-/// Handler for retrying a single skipped archive (POST /archive/:id/retry-skipped).
-async fn retry_skipped(
+/// Handler for watching an archive's link for content changes (POST /archive/:id/watch).
+async fn watch_archive_link(
     State(state): State<AppState>,
     Path(id): Path<i64>,
-    RequireAdmin(_admin): RequireAdmin,
+    RequireAdmin(admin): RequireAdmin,
+    Form(form): Form<WatchForm>,
 ) -> Response {
-    tracing::debug!(archive_id = id, "HTTP API: POST /archive/:id/retry-skipped");
-    match reset_single_skipped_archive(state.db.pool(), id).await {
-        Ok(true) => {
-            tracing::info!(archive_id = id, "Reset skipped archive for retry");
-            axum::response::Redirect::to(&format!("/archive/{id}")).into_response()
+    tracing::debug!(archive_id = id, "HTTP API: POST /archive/:id/watch");
+    let archive = match get_archive(state.db.read_pool(), id).await {
+        Ok(Some(a)) => a,
+        Ok(None) => return (StatusCode::NOT_FOUND, "Archive not found").into_response(),
+        Err(e) => {
+            tracing::error!("Failed to fetch archive: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    let interval_hours = crate::archiver::change_watch::bounded_interval_hours(
+        form.interval_hours.trim().parse().ok(),
+        state.config.change_watch_default_interval_hours,
+        state.config.change_watch_min_interval_hours,
+    );
+
+    match watch_link(
+        state.db.pool(),
+        archive.link_id,
+        interval_hours,
+        Some(admin.id),
+    )
+    .await
+    {
+        Ok(()) => {
+            tracing::info!(
+                archive_id = id,
+                link_id = archive.link_id,
+                interval_hours,
+                "Watching link for changes"
+            );
+            Redirect::to(&format!("/archive/{id}")).into_response()
         }
-        Ok(false) => (StatusCode::BAD_REQUEST, "Archive is not in skipped status").into_response(),
         Err(e) => {
-            tracing::error!("Failed to reset skipped archive: {e}");
-            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to reset archive").into_response()
+            tracing::error!("Failed to watch link: {e}");
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to watch link").into_response()
         }
     }
 }
 
-// ========== Debug Routes ==========
-
-const MAX_RETRIES: i32 = 3;
-
-/// Handler for debug queue page (GET /debug/queue).
-async fn debug_queue(
+/// Handler for no longer watching an archive's link (POST /archive/:id/unwatch).
+async fn unwatch_archive_link(
     State(state): State<AppState>,
+    Path(id): Path<i64>,
     RequireAdmin(_admin): RequireAdmin,
 ) -> Response {
-    let stats = match get_queue_stats(state.db.pool(), MAX_RETRIES).await {
-        Ok(s) => s,
+    tracing::debug!(archive_id = id, "HTTP API: POST /archive/:id/unwatch");
+    let archive = match get_archive(state.db.read_pool(), id).await {
+        Ok(Some(a)) => a,
+        Ok(None) => return (StatusCode::NOT_FOUND, "Archive not found").into_response(),
         Err(e) => {
-            tracing::error!("Failed to get queue stats: {e}");
+            tracing::error!("Failed to fetch archive: {e}");
             return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
         }
     };
 
-    let recent_failures = match get_recent_failed_archives(state.db.pool(), 20).await {
-        Ok(f) => f,
+    match unwatch_link(state.db.pool(), archive.link_id).await {
+        Ok(()) => Redirect::to(&format!("/archive/{id}")).into_response(),
         Err(e) => {
-            tracing::error!("Failed to get recent failures: {e}");
-            Vec::new()
+            tracing::error!("Failed to unwatch link: {e}");
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to unwatch link").into_response()
         }
-    };
+    }
+}
 
-    let params = pages::DebugQueueParams::new(&stats, &recent_failures);
-    let markup = pages::render_debug_queue_page(&params);
-    Html(markup.into_string()).into_response()
+#[derive(Debug, Deserialize)]
+pub struct KeepVersionsForm {
+    #[serde(default)]
+    keep_versions: bool,
 }
 
-/// Handler for resetting all skipped archives (POST /debug/reset-skipped).
-async fn debug_reset_skipped(
+/// Handler for opting an archive in or out of versioning (POST /archive/:id/keep-versions).
+async fn set_archive_keep_versions_handler(
     State(state): State<AppState>,
-    RequireAdmin(_admin): RequireAdmin,
+    Path(id): Path<i64>,
+    RequireAdmin(admin): RequireAdmin,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Form(form): Form<KeepVersionsForm>,
 ) -> Response {
-    match reset_skipped_archives(state.db.pool()).await {
-        Ok(count) => {
-            tracing::info!(count, "Reset all skipped archives");
-            axum::response::Redirect::to("/debug/queue").into_response()
+    tracing::debug!(archive_id = id, "HTTP API: POST /archive/:id/keep-versions");
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
+    let forwarded_for = headers.get("x-forwarded-for").and_then(|v| v.to_str().ok());
+
+    match get_archive(state.db.read_pool(), id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return (StatusCode::NOT_FOUND, "Archive not found").into_response(),
+        Err(e) => {
+            tracing::error!("Failed to fetch archive: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    }
+
+    match set_archive_keep_versions(state.db.pool(), id, form.keep_versions).await {
+        Ok(()) => {
+            tracing::info!(
+                archive_id = id,
+                keep_versions = form.keep_versions,
+                "Set archive versioning"
+            );
+            let actor =
+                crate::db::AuditActor::from_request(Some(admin.id), &client_ip, forwarded_for);
+            let action = crate::db::AuditAction::AdminSetArchiveVersioning {
+                archive_id: id,
+                keep_versions: form.keep_versions,
+            };
+            if let Err(e) = crate::db::record_audit(state.db.pool(), &actor, &action).await {
+                tracing::error!("Failed to create audit event: {e}");
+            }
+            Redirect::to(&format!("/archive/{id}#archive-versions")).into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to set archive versioning: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to update versioning",
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Handler for viewing a content version diff (GET /archive/:id/versions/:version_id).
+async fn content_version_detail(
+    State(state): State<AppState>,
+    Path((id, version_id)): Path<(i64, i64)>,
+    MaybeUser(user): MaybeUser,
+) -> Response {
+    let archive = match get_archive(state.db.read_pool(), id).await {
+        Ok(Some(a)) => a,
+        Ok(None) => return (StatusCode::NOT_FOUND, "Archive not found").into_response(),
+        Err(e) => {
+            tracing::error!("Failed to fetch archive: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    let version = match get_content_version(state.db.read_pool(), version_id).await {
+        Ok(Some(v)) if v.link_id == archive.link_id => v,
+        Ok(_) => return (StatusCode::NOT_FOUND, "Content version not found").into_response(),
+        Err(e) => {
+            tracing::error!("Failed to fetch content version: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    let previous = match version.previous_version_id {
+        Some(prev_id) => match get_content_version(state.db.read_pool(), prev_id).await {
+            Ok(prev) => prev,
+            Err(e) => {
+                tracing::error!("Failed to fetch previous content version: {e}");
+                return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+            }
+        },
+        None => None,
+    };
+
+    let link = match get_link(state.db.read_pool(), archive.link_id).await {
+        Ok(Some(l)) => l,
+        Ok(None) => return (StatusCode::NOT_FOUND, "Link not found").into_response(),
+        Err(e) => {
+            tracing::error!("Failed to fetch link: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    let old_text = previous.as_ref().map_or("", |p| p.content_text.as_str());
+    let diff_result = diff::compute_diff(old_text, &version.content_text);
+
+    let markup = pages::render_content_version_page(
+        &archive,
+        &link,
+        &version,
+        previous.as_ref(),
+        &diff_result,
+        user.as_ref(),
+    );
+    Html(markup.into_string()).into_response()
+}
+
+/// Handler for deleting an archive (POST /archive/:id/delete).
+async fn delete_archive_handler(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    RequireAdmin(_admin): RequireAdmin,
+) -> Response {
+    tracing::debug!(archive_id = id, "HTTP API: POST /archive/:id/delete");
+    // Get the archive first to log what we're deleting
+    let archive = match get_archive(state.db.read_pool(), id).await {
+        Ok(Some(a)) => a,
+        Ok(None) => {
+            return (StatusCode::NOT_FOUND, "Archive not found").into_response();
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch archive for deletion: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    // Don't allow deletion if currently processing
+    if archive.status == "processing" {
+        return (
+            StatusCode::CONFLICT,
+            "Cannot delete archive while processing",
+        )
+            .into_response();
+    }
+
+    if let Err(e) = delete_archive(state.db.pool(), id).await {
+        tracing::error!("Failed to delete archive: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to delete archive",
+        )
+            .into_response();
+    }
+
+    tracing::info!(archive_id = id, "Archive deleted");
+
+    // Redirect to home page since archive no longer exists
+    axum::response::Redirect::to("/").into_response()
+}
+
+/// Handler for retrying a single skipped archive (POST /archive/:id/retry-skipped).
+async fn retry_skipped(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    RequireAdmin(_admin): RequireAdmin,
+) -> Response {
+    tracing::debug!(archive_id = id, "HTTP API: POST /archive/:id/retry-skipped");
+    match reset_single_skipped_archive(state.db.pool(), id).await {
+        Ok(true) => {
+            tracing::info!(archive_id = id, "Reset skipped archive for retry");
+            axum::response::Redirect::to(&format!("/archive/{id}")).into_response()
+        }
+        Ok(false) => (StatusCode::BAD_REQUEST, "Archive is not in skipped status").into_response(),
+        Err(e) => {
+            tracing::error!("Failed to reset skipped archive: {e}");
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to reset archive").into_response()
+        }
+    }
+}
+
+// ========== Debug Routes ==========
+
+/// Domains listed in the debug page's per-domain queue breakdown.
+const DEBUG_QUEUE_TOP_DOMAINS: i64 = 25;
+
+/// JSON body of `GET /api/queue.json`.
+#[derive(Debug, Serialize)]
+pub struct QueueStatsResponse {
+    #[serde(flatten)]
+    pub stats: crate::db::QueueStats,
+    /// Seconds since the oldest pending archive was queued.
+    pub oldest_pending_age_secs: Option<i64>,
+    /// Archive counts keyed by status.
+    pub status_counts: std::collections::BTreeMap<String, i64>,
+}
+
+impl QueueStatsResponse {
+    fn new(
+        stats: crate::db::QueueStats,
+        status_counts: Vec<(String, i64)>,
+        now: chrono::NaiveDateTime,
+    ) -> Self {
+        let oldest_pending_age_secs = stats.oldest_pending_at.as_deref().and_then(|at| {
+            chrono::NaiveDateTime::parse_from_str(at, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .map(|at| (now - at).num_seconds().max(0))
+        });
+        Self {
+            stats,
+            oldest_pending_age_secs,
+            status_counts: status_counts.into_iter().collect(),
+        }
+    }
+}
+
+/// Whether the request carries `Authorization: Bearer <token>` for `token`.
+fn has_bearer_token(headers: &HeaderMap, token: Option<&str>) -> bool {
+    use sha2::{Digest, Sha256};
+
+    let (Some(token), Some(provided)) = (
+        token,
+        headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer ")),
+    ) else {
+        return false;
+    };
+    // Compare digests so the check doesn't leak the token length or prefix
+    Sha256::digest(provided.trim().as_bytes()) == Sha256::digest(token.as_bytes())
+}
+
+/// Handler for queue statistics as JSON (GET /api/queue.json).
+///
+/// For external monitoring: needs an admin session or the `QUEUE_API_TOKEN`
+/// bearer token.
+async fn api_queue_json(
+    State(state): State<AppState>,
+    MaybeUser(user): MaybeUser,
+    headers: HeaderMap,
+) -> Response {
+    let is_admin = user.is_some_and(|u| u.is_admin);
+    if !is_admin && !has_bearer_token(&headers, state.config.queue_api_token.as_deref()) {
+        return (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+            "Admin login or API token required",
+        )
+            .into_response();
+    }
+
+    let stats = match get_queue_stats(state.db.read_pool(), state.config.archive_max_retries).await
+    {
+        Ok(s) => s,
+        Err(e) => {
+            tracing::error!("Failed to get queue stats: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+    let status_counts = match count_archives_by_status(state.db.read_pool()).await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Failed to count archives by status: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    Json(QueueStatsResponse::new(
+        stats,
+        status_counts,
+        chrono::Utc::now().naive_utc(),
+    ))
+    .into_response()
+}
+
+/// Query parameters for the debug queue page.
+#[derive(Debug, Deserialize)]
+pub struct DebugQueueQuery {
+    /// Only list recent failures with this `error_kind`.
+    kind: Option<String>,
+    /// Only list archives skipped for this `skip_reason`.
+    skip_reason: Option<String>,
+}
+
+/// Handler for debug queue page (GET /debug/queue).
+async fn debug_queue(
+    State(state): State<AppState>,
+    RequireAdmin(_admin): RequireAdmin,
+    Query(query): Query<DebugQueueQuery>,
+) -> Response {
+    let kind = query
+        .kind
+        .as_deref()
+        .and_then(crate::archiver::ArchiveErrorKind::from_str);
+    let skip_reason = query
+        .skip_reason
+        .as_deref()
+        .and_then(crate::archiver::SkipReason::from_str);
+
+    let stats = match get_queue_stats(state.db.read_pool(), state.config.archive_max_retries).await
+    {
+        Ok(s) => s,
+        Err(e) => {
+            tracing::error!("Failed to get queue stats: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    let recent_failures = match get_recent_failed_archives(
+        state.db.read_pool(),
+        kind.map(|k| k.as_str()),
+        skip_reason.map(|r| r.as_str()),
+        20,
+    )
+    .await
+    {
+        Ok(f) => f,
+        Err(e) => {
+            tracing::error!("Failed to get recent failures: {e}");
+            Vec::new()
+        }
+    };
+
+    let kind_counts = match get_failure_kind_counts(state.db.read_pool()).await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Failed to count failures by kind: {e}");
+            Vec::new()
+        }
+    };
+
+    let skip_reason_counts = match get_skip_reason_counts(state.db.read_pool()).await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Failed to count skipped archives by reason: {e}");
+            Vec::new()
+        }
+    };
+
+    let domain_counts =
+        match get_pending_counts_by_domain(state.db.read_pool(), DEBUG_QUEUE_TOP_DOMAINS).await {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::error!("Failed to get per-domain queue counts: {e}");
+                Vec::new()
+            }
+        };
+
+    let params = pages::DebugQueueParams::new(&stats, &recent_failures)
+        .with_domain_counts(&domain_counts)
+        .with_failure_kinds(&kind_counts, kind)
+        .with_skip_reasons(&skip_reason_counts, skip_reason)
+        .with_max_retries(state.config.archive_max_retries);
+    let markup = pages::render_debug_queue_page(&params);
+    Html(markup.into_string()).into_response()
+}
+
+/// Handler for resetting all skipped archives (POST /debug/reset-skipped).
+async fn debug_reset_skipped(
+    State(state): State<AppState>,
+    RequireAdmin(_admin): RequireAdmin,
+) -> Response {
+    match reset_skipped_archives(state.db.pool()).await {
+        Ok(count) => {
+            tracing::info!(count, "Reset all skipped archives");
+            axum::response::Redirect::to("/debug/queue").into_response()
         }
         Err(e) => {
             tracing::error!("Failed to reset skipped archives: {e}");
@@ -1573,7 +2166,7 @@ async fn compare_archives(
     MaybeUser(user): MaybeUser,
 ) -> Response {
     // Fetch both archives
-    let archive1 = match get_archive(state.db.pool(), params.id1).await {
+    let archive1 = match get_archive(state.db.read_pool(), params.id1).await {
         Ok(Some(a)) => a,
         Ok(None) => {
             return (
@@ -1588,7 +2181,7 @@ async fn compare_archives(
         }
     };
 
-    let archive2 = match get_archive(state.db.pool(), params.id2).await {
+    let archive2 = match get_archive(state.db.read_pool(), params.id2).await {
         Ok(Some(a)) => a,
         Ok(None) => {
             return (
@@ -1604,7 +2197,7 @@ async fn compare_archives(
     };
 
     // Fetch associated links for display
-    let link1 = match get_link(state.db.pool(), archive1.link_id).await {
+    let link1 = match get_link(state.db.read_pool(), archive1.link_id).await {
         Ok(Some(l)) => l,
         Ok(None) => {
             return (StatusCode::NOT_FOUND, "Link not found").into_response();
@@ -1615,7 +2208,7 @@ async fn compare_archives(
         }
     };
 
-    let link2 = match get_link(state.db.pool(), archive2.link_id).await {
+    let link2 = match get_link(state.db.read_pool(), archive2.link_id).await {
         Ok(Some(l)) => l,
         Ok(None) => {
             return (StatusCode::NOT_FOUND, "Link not found").into_response();
@@ -1647,7 +2240,7 @@ async fn post_detail(
     Path(guid): Path<String>,
     MaybeUser(user): MaybeUser,
 ) -> Response {
-    let post = match get_post_by_guid(state.db.pool(), &guid).await {
+    let post = match get_post_by_guid(state.db.read_pool(), &guid).await {
         Ok(Some(p)) => p,
         Ok(None) => {
             return (StatusCode::NOT_FOUND, "Post not found").into_response();
@@ -1658,7 +2251,7 @@ async fn post_detail(
         }
     };
 
-    let archives = match get_archives_for_post_display(state.db.pool(), post.id).await {
+    let archives = match get_archives_for_post_display(state.db.read_pool(), post.id).await {
         Ok(a) => a,
         Err(e) => {
             tracing::error!("Failed to fetch archives for post: {e}");
@@ -1666,15 +2259,73 @@ async fn post_detail(
         }
     };
 
+    // Snapshot of the post page itself; failures only hide the section
+    let snapshot = get_post_snapshot_archive(state.db.read_pool(), post.id)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to fetch post snapshot: {e}");
+            None
+        });
+    let snapshot_artifacts = match &snapshot {
+        Some(archive) => get_artifacts_for_archive(state.db.read_pool(), archive.id)
+            .await
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+
     let params = pages::PostDetailParams {
         post: &post,
         archives: &archives,
+        snapshot: snapshot.as_ref(),
+        snapshot_artifacts: &snapshot_artifacts,
         user: user.as_ref(),
     };
     let markup = pages::render_post_detail_page(&params);
     Html(markup.into_string()).into_response()
 }
 
+/// Maximum number of posts listed on a forum user page.
+const FORUM_USER_POSTS_LIMIT: i64 = 200;
+
+/// GET /forum-user/:username - Forum user's posts and linked archives (admin-only for now).
+async fn forum_user_page(
+    State(state): State<AppState>,
+    Path(username): Path<String>,
+    RequireAdmin(admin): RequireAdmin,
+) -> Response {
+    let Some(handle) = forum_author_handle(&username) else {
+        return (StatusCode::BAD_REQUEST, "Invalid forum username").into_response();
+    };
+
+    let posts =
+        match get_posts_by_forum_author(state.db.read_pool(), handle, FORUM_USER_POSTS_LIMIT).await
+        {
+            Ok(p) => p,
+            Err(e) => {
+                tracing::error!("Failed to fetch posts for forum user: {e}");
+                return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+            }
+        };
+
+    let post_ids: Vec<i64> = posts.iter().map(|p| p.id).collect();
+    let archives = match get_archives_for_posts_display(state.db.read_pool(), &post_ids).await {
+        Ok(a) => a,
+        Err(e) => {
+            tracing::error!("Failed to fetch archives for forum user: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    let params = pages::ForumUserPageParams {
+        handle,
+        posts: &posts,
+        archives: &archives,
+        user: Some(&admin),
+    };
+    let markup = pages::render_forum_user_page(&params);
+    Html(markup.into_string()).into_response()
+}
+
 async fn thread_detail(
     State(state): State<AppState>,
     Path(thread_id): Path<String>,
@@ -1691,7 +2342,7 @@ async fn thread_detail(
 
     tracing::debug!("Thread detail request for topic_id: {}", topic_id);
 
-    let posts = match get_posts_by_topic_id(state.db.pool(), topic_id).await {
+    let posts = match get_posts_by_topic_id(state.db.read_pool(), topic_id).await {
         Ok(p) => p,
         Err(e) => {
             tracing::error!("Failed to fetch posts for topic_id {}: {}", topic_id, e);
@@ -1709,7 +2360,7 @@ async fn thread_detail(
 
     let post_ids: Vec<i64> = posts.iter().map(|p| p.id).collect();
 
-    let archives = match get_archives_for_posts_display(state.db.pool(), &post_ids).await {
+    let archives = match get_archives_for_posts_display(state.db.read_pool(), &post_ids).await {
         Ok(a) => a,
         Err(e) => {
             tracing::error!("Failed to fetch archives for thread: {e}");
@@ -1717,10 +2368,18 @@ async fn thread_detail(
         }
     };
 
+    let post_counts = get_link_counts_for_posts(state.db.read_pool(), &post_ids)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("Failed to count links for thread posts: {e}");
+            Vec::new()
+        });
+
     let params = pages::ThreadDetailParams {
         thread_key: &thread_key,
         posts: &posts,
         archives: &archives,
+        post_counts: &post_counts,
         user: user.as_ref(),
     };
     let markup = pages::render_thread_detail_page(&params);
@@ -1744,7 +2403,7 @@ async fn threads_list(
     let offset = i64::from(page.saturating_sub(1)) * per_page;
 
     // Get total count for pagination
-    let total_threads = match count_all_threads(state.db.pool()).await {
+    let total_threads = match count_all_threads(state.db.read_pool()).await {
         Ok(count) => count,
         Err(e) => {
             tracing::error!("Failed to count threads: {e}");
@@ -1754,7 +2413,7 @@ async fn threads_list(
 
     let total_pages = ((total_threads + per_page - 1) / per_page).max(1) as usize;
 
-    let threads = match get_all_threads(state.db.pool(), sort_by, per_page, offset).await {
+    let threads = match get_all_threads(state.db.read_pool(), sort_by, per_page, offset).await {
         Ok(t) => t,
         Err(e) => {
             tracing::error!("Failed to fetch threads: {e}");
@@ -1782,13 +2441,22 @@ async fn site_list(
     State(state): State<AppState>,
     Path(site): Path<String>,
     Query(params): Query<SiteListParams>,
+    MaybeUser(user): MaybeUser,
 ) -> Response {
-    let page = params.page.unwrap_or(1);
+    let page = params.page.unwrap_or(1).max(1);
     let per_page = 20i64;
-    let offset = i64::from(page.saturating_sub(1)) * per_page;
+    let offset = i64::from(page - 1) * per_page;
+
+    let stats = match get_domain_stats(state.db.read_pool(), &site).await {
+        Ok(stats) => stats,
+        Err(e) => {
+            tracing::error!("Failed to fetch domain stats: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
 
     let archives =
-        match get_archives_by_domain_display(state.db.pool(), &site, per_page, offset).await {
+        match get_archives_by_domain_display(state.db.read_pool(), &site, per_page, offset).await {
             Ok(a) => a,
             Err(e) => {
                 tracing::error!("Failed to fetch archives by domain: {e}");
@@ -1796,20 +2464,63 @@ async fn site_list(
             }
         };
 
-    // Calculate total pages (approximation based on current page having data)
-    let total_pages = if archives.len() < per_page as usize {
-        page as i32
-    } else {
-        (page as i32) + 1
+    let total_pages = ((stats.archive_count() + per_page - 1) / per_page).max(1) as i32;
+    let markup = pages::render_site_list_page(
+        &site,
+        &stats,
+        &archives,
+        (page - 1) as i32,
+        total_pages,
+        user.as_ref(),
+    );
+    Html(markup.into_string()).into_response()
+}
+
+async fn playlist_detail(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    MaybeUser(user): MaybeUser,
+) -> Response {
+    let archive = match get_archive(state.db.read_pool(), id).await {
+        Ok(Some(a)) if a.content_type.as_deref() == Some("playlist") => a,
+        Ok(_) => {
+            return (StatusCode::NOT_FOUND, "Playlist not found").into_response();
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch archive: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    let link = match get_link(state.db.read_pool(), archive.link_id).await {
+        Ok(Some(l)) => l,
+        Ok(None) => {
+            return (StatusCode::NOT_FOUND, "Link not found").into_response();
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch link: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    let members = match get_playlist_members_display(state.db.read_pool(), id).await {
+        Ok(m) => m,
+        Err(e) => {
+            tracing::error!("Failed to fetch playlist members: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
     };
-    let markup =
-        pages::render_site_list_page(&site, &archives, (page - 1) as i32, total_pages, None);
+
+    let markup = pages::render_playlist_page(&archive, &link, &members, user.as_ref());
     Html(markup.into_string()).into_response()
 }
 
+/// Domains listed in the admin storage-by-domain report on the stats page.
+const STATS_STORAGE_TOP_DOMAINS: i64 = 25;
+
 async fn stats(State(state): State<AppState>, MaybeUser(user): MaybeUser) -> Response {
     // Fetch all stats data
-    let status_counts = match count_archives_by_status(state.db.pool()).await {
+    let status_counts = match count_archives_by_status(state.db.read_pool()).await {
         Ok(c) => c,
         Err(e) => {
             tracing::error!("Failed to count archives: {e}");
@@ -1817,7 +2528,7 @@ async fn stats(State(state): State<AppState>, MaybeUser(user): MaybeUser) -> Res
         }
     };
 
-    let content_type_counts = match count_archives_by_content_type(state.db.pool()).await {
+    let content_type_counts = match count_archives_by_content_type(state.db.read_pool()).await {
         Ok(c) => c,
         Err(e) => {
             tracing::error!("Failed to count archives by content type: {e}");
@@ -1825,30 +2536,32 @@ async fn stats(State(state): State<AppState>, MaybeUser(user): MaybeUser) -> Res
         }
     };
 
-    let top_domains = get_top_domains(state.db.pool(), 10)
+    let top_domains = get_top_domains(state.db.read_pool(), 10)
         .await
         .unwrap_or_default();
-    let recent_activity = get_recent_activity_counts(state.db.pool())
+    let recent_activity = get_recent_activity_counts(state.db.read_pool())
         .await
         .unwrap_or((0, 0, 0));
-    let storage_stats = get_storage_stats(state.db.pool())
+    let storage_stats = get_storage_stats(state.db.read_pool())
         .await
         .unwrap_or((0, 0.0, 0));
-    let timeline = get_archive_timeline(state.db.pool())
+    let timeline = get_archive_timeline(state.db.read_pool())
         .await
         .unwrap_or_default();
-    let queue_stats_full = get_queue_stats(state.db.pool(), MAX_RETRIES).await.ok();
+    let queue_stats_full = get_queue_stats(state.db.read_pool(), state.config.archive_max_retries)
+        .await
+        .ok();
     let queue_stats = match queue_stats_full {
         Some(qs) => (qs.pending_count, qs.processing_count),
         None => (0, 0),
     };
-    let quality_metrics = get_quality_metrics(state.db.pool())
+    let quality_metrics = get_quality_metrics(state.db.read_pool())
         .await
         .unwrap_or((0, 0, 0));
-    let nsfw_count = get_nsfw_count(state.db.pool()).await.unwrap_or(0);
+    let nsfw_count = get_nsfw_count(state.db.read_pool()).await.unwrap_or(0);
 
-    let link_count = count_links(state.db.pool()).await.unwrap_or(0);
-    let post_count = count_posts(state.db.pool()).await.unwrap_or(0);
+    let link_count = count_links(state.db.read_pool()).await.unwrap_or(0);
+    let post_count = count_posts(state.db.read_pool()).await.unwrap_or(0);
 
     // Calculate total completed archives
     let total_complete = status_counts
@@ -1872,11 +2585,24 @@ async fn stats(State(state): State<AppState>, MaybeUser(user): MaybeUser) -> Res
         total_complete,
     );
 
+    // The per-domain storage report is only rendered for admins
+    let stats_data = if user.as_ref().is_some_and(|u| u.is_admin) {
+        match get_storage_by_domain(state.db.read_pool(), STATS_STORAGE_TOP_DOMAINS).await {
+            Ok(storage) => stats_data.with_storage_by_domain(storage),
+            Err(e) => {
+                tracing::error!("Failed to get storage by domain: {e}");
+                stats_data
+            }
+        }
+    } else {
+        stats_data
+    };
+
     // Fetch user-specific stats if logged in
     let user_stats = if let Some(ref u) = user {
-        match get_user_submission_stats(state.db.pool(), u.id).await {
+        match get_user_submission_stats(state.db.read_pool(), u.id).await {
             Ok((total, complete, pending, failed)) => {
-                let recent_submissions = get_user_submissions(state.db.pool(), u.id, 20)
+                let recent_submissions = get_user_submissions(state.db.read_pool(), u.id, 20)
                     .await
                     .unwrap_or_default();
                 Some(pages::UserStats {
@@ -1955,9 +2681,45 @@ pub struct SubmitForm {
     nsfw: bool,
 }
 
+/// 429 response for a submitter over `SUBMISSION_RATE_LIMIT_PER_HOUR`.
+///
+/// Carries `Retry-After` and `X-RateLimit-*` headers so scripts can back off,
+/// and the form with a message saying when the next submission is allowed.
+fn submission_rate_limited(limit: u32, retry_after_secs: i64) -> Response {
+    // Rounded up so "try again" never comes early; at least one second
+    let retry_after_secs = retry_after_secs.max(1);
+    let minutes = (retry_after_secs + 59) / 60;
+    let message = format!(
+        "You've reached the limit of {limit} submissions per hour. You can submit again in about {minutes} minute{}.",
+        if minutes == 1 { "" } else { "s" }
+    );
+    let html = pages::render_submit_form(Some(&message), None, None, true);
+    let mut response = (
+        StatusCode::TOO_MANY_REQUESTS,
+        [(header::RETRY_AFTER, retry_after_secs.to_string())],
+        Html(html),
+    )
+        .into_response();
+    set_rate_limit_headers(response.headers_mut(), limit, 0);
+    response
+}
+
+/// Set the `X-RateLimit-*` headers sent with every submission response.
+fn set_rate_limit_headers(headers: &mut HeaderMap, limit: u32, remaining: u32) {
+    headers.insert(
+        header::HeaderName::from_static("x-ratelimit-limit"),
+        header::HeaderValue::from(limit),
+    );
+    headers.insert(
+        header::HeaderName::from_static("x-ratelimit-remaining"),
+        header::HeaderValue::from(remaining),
+    );
+}
+
 async fn submit_url(
     State(state): State<AppState>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: axum::http::HeaderMap,
     RequireApproved(user): RequireApproved,
     Form(form): Form<SubmitForm>,
 ) -> Response {
@@ -1968,22 +2730,49 @@ async fn submit_url(
         return Html(html).into_response();
     }
 
-    let client_ip = addr.ip().to_string();
+    let client_ip =
+        super::client_ip::client_ip(addr.ip(), &headers, &state.config.trusted_proxies).to_string();
+
+    let mut response = submit_url_for_ip(&state, &client_ip, &user, form).await;
+    if response.status() != StatusCode::TOO_MANY_REQUESTS {
+        // Count again so a submission this request made is included
+        let rate_limit = state.config.submission_rate_limit_per_hour;
+        match count_submissions_from_ip_last_hour(state.db.read_pool(), &client_ip).await {
+            Ok(count) => {
+                let used = u32::try_from(count).unwrap_or(u32::MAX);
+                set_rate_limit_headers(
+                    response.headers_mut(),
+                    rate_limit,
+                    rate_limit.saturating_sub(used),
+                );
+            }
+            Err(e) => tracing::warn!("Failed to count submissions for rate limit headers: {e}"),
+        }
+    }
+    response
+}
 
+/// Handle a submission from `client_ip` once submissions are known to be enabled.
+async fn submit_url_for_ip(
+    state: &AppState,
+    client_ip: &str,
+    user: &User,
+    form: SubmitForm,
+) -> Response {
     // Rate limit check
     let rate_limit = state.config.submission_rate_limit_per_hour;
-    match count_submissions_from_ip_last_hour(state.db.pool(), &client_ip).await {
+    match count_submissions_from_ip_last_hour(state.db.read_pool(), client_ip).await {
         Ok(count) => {
             if count >= i64::from(rate_limit) {
-                let html = pages::render_submit_form(
-                    Some(&format!(
-                        "Rate limit exceeded. Maximum {rate_limit} submissions per hour."
-                    )),
-                    None,
-                    None,
-                    true,
-                );
-                return Html(html).into_response();
+                let retry_after_secs =
+                    match submission_retry_after_secs(state.db.read_pool(), client_ip).await {
+                        Ok(secs) => secs.unwrap_or(0),
+                        Err(e) => {
+                            tracing::warn!("Failed to get submission retry delay: {e}");
+                            3600
+                        }
+                    };
+                return submission_rate_limited(rate_limit, retry_after_secs);
             }
         }
         Err(e) => {
@@ -2016,8 +2805,11 @@ async fn submit_url(
     }
 
     // Normalize URL
-    let normalized = normalize_url(url);
-    let domain = parsed_url.host_str().unwrap_or("unknown").to_string();
+    let normalized = normalize_link_url(url);
+    let domain = url::Url::parse(&normalized)
+        .ok()
+        .and_then(|u| u.host_str().map(ToString::to_string))
+        .unwrap_or_else(|| parsed_url.host_str().unwrap_or("unknown").to_string());
 
     // Detect and log Twitter URL submissions from authenticated users
     let is_twitter = domain.contains("twitter.com") || domain.contains("x.com");
@@ -2030,8 +2822,45 @@ async fn submit_url(
         );
     }
 
+    // If we already have a completed archive of this URL, point the user at it
+    // instead of queueing redundant work
+    let existing = match find_completed_archive_for_url(state.db.read_pool(), &normalized).await {
+        Ok(existing) => existing,
+        Err(e) => {
+            tracing::error!("Failed to check existing archive: {e:#}");
+            let html = pages::render_submit_form(Some("Internal error"), None, None, true);
+            return Html(html).into_response();
+        }
+    };
+    if let Some((link_id, archive_id)) = existing {
+        let submission = NewSubmission {
+            url: url.to_string(),
+            normalized_url: normalized.clone(),
+            submitted_by_ip: client_ip.to_string(),
+            submitted_by_user_id: Some(user.id),
+        };
+        match insert_submission(state.db.pool(), &submission).await {
+            Ok(submission_id) => {
+                if let Err(e) =
+                    set_submission_complete(state.db.pool(), submission_id, link_id).await
+                {
+                    tracing::error!(submission_id, "Failed to mark submission complete: {e:#}");
+                }
+            }
+            Err(e) => tracing::error!("Failed to insert submission: {e:#}"),
+        }
+
+        tracing::info!(
+            archive_id,
+            url = %normalized,
+            "Submitted URL already archived, redirecting to existing archive"
+        );
+        return Redirect::to(&format!("/archive/{archive_id}?already_archived=true"))
+            .into_response();
+    }
+
     // Check if this URL was submitted recently
-    match submission_exists_for_url(state.db.pool(), &normalized).await {
+    match submission_exists_for_url(state.db.read_pool(), &normalized).await {
         Ok(true) => {
             let html = pages::render_submit_form(
                 Some("This URL was already submitted recently"),
@@ -2053,7 +2882,7 @@ async fn submit_url(
     let submission = NewSubmission {
         url: url.to_string(),
         normalized_url: normalized.clone(),
-        submitted_by_ip: client_ip,
+        submitted_by_ip: client_ip.to_string(),
         submitted_by_user_id: Some(user.id),
     };
 
@@ -2066,38 +2895,32 @@ async fn submit_url(
             return Html(html).into_response();
         }
     };
-
-    // Check if we already have this link
-    let link_id = match get_link_by_normalized_url(state.db.pool(), &normalized).await {
-        Ok(Some(link)) => link.id,
-        Ok(None) => {
-            // Create new link
-            let new_link = NewLink {
-                original_url: url.to_string(),
-                normalized_url: normalized.clone(),
-                canonical_url: None,
-                domain,
-            };
-            match insert_link(state.db.pool(), &new_link).await {
-                Ok(id) => id,
-                Err(e) => {
-                    tracing::error!("Failed to insert link: {e}");
-                    let html = pages::render_submit_error("Failed to process URL");
-                    return Html(html).into_response();
-                }
-            }
-        }
+
+    // Reuse the existing link for this URL or create it
+    let new_link = NewLink {
+        original_url: url.to_string(),
+        normalized_url: normalized.clone(),
+        canonical_url: None,
+        domain,
+    };
+    let link_id = match get_or_create_link(state.db.pool(), &new_link).await {
+        Ok(id) => id,
         Err(e) => {
-            tracing::error!("Failed to check existing link: {e}");
-            let html = pages::render_submit_error("Internal error");
+            tracing::error!("Failed to get or create link: {e}");
+            let html = pages::render_submit_error("Failed to process URL");
             return Html(html).into_response();
         }
     };
 
     // Check if archive already exists or create new one
-    let archive_id = match get_archive_by_link_id(state.db.pool(), link_id).await {
+    let archive_id = match get_archive_by_link_id(state.db.read_pool(), link_id).await {
         Ok(Some(archive)) => {
-            // Archive already exists, redirect to it
+            // Resubmitting a previously failed URL queues it again
+            if archive.status == "failed" {
+                if let Err(e) = reset_archive_for_retry(state.db.pool(), archive.id).await {
+                    tracing::error!(archive_id = archive.id, "Failed to requeue archive: {e:#}");
+                }
+            }
             archive.id
         }
         Ok(None) => {
@@ -2143,6 +2966,22 @@ async fn submit_url(
     Redirect::to(&format!("/archive/{}", archive_id)).into_response()
 }
 
+/// Find a completed archive for a normalized URL, returning `(link_id, archive_id)`.
+///
+/// Pending, failed or skipped archives return `None` so they can be queued again.
+async fn find_completed_archive_for_url(
+    pool: &sqlx::SqlitePool,
+    normalized_url: &str,
+) -> anyhow::Result<Option<(i64, i64)>> {
+    let Some(link) = get_link_by_normalized_url(pool, normalized_url).await? else {
+        return Ok(None);
+    };
+    let archive = get_archive_by_link_id(pool, link.id).await?;
+    Ok(archive
+        .filter(|a| a.status == "complete")
+        .map(|a| (link.id, a.id)))
+}
+
 // ========== Thread Archive Routes ==========
 
 #[derive(Debug, Deserialize)]
@@ -2220,7 +3059,7 @@ async fn submit_thread(
 
     // Rate limit check: 5 thread jobs per hour per user
     const THREAD_RATE_LIMIT: i64 = 5;
-    match count_user_thread_archive_jobs_last_hour(state.db.pool(), user.id).await {
+    match count_user_thread_archive_jobs_last_hour(state.db.read_pool(), user.id).await {
         Ok(count) => {
             if count >= THREAD_RATE_LIMIT {
                 let html = pages::render_submit_form(
@@ -2243,7 +3082,7 @@ async fn submit_thread(
     }
 
     // Check if this thread was recently submitted
-    match thread_archive_job_exists_recent(state.db.pool(), &clean_url).await {
+    match thread_archive_job_exists_recent(state.db.read_pool(), &clean_url).await {
         Ok(true) => {
             let html = pages::render_submit_form(
                 Some("This thread was already submitted recently. Check the status page."),
@@ -2339,7 +3178,7 @@ async fn thread_job_status(
     Path(id): Path<i64>,
     MaybeUser(user): MaybeUser,
 ) -> Response {
-    let job = match get_thread_archive_job(state.db.pool(), id).await {
+    let job = match get_thread_archive_job(state.db.read_pool(), id).await {
         Ok(Some(j)) => j,
         Ok(None) => {
             return (StatusCode::NOT_FOUND, "Job not found").into_response();
@@ -2368,8 +3207,8 @@ async fn thread_job_status(
     }
 
     // Fetch archives for processing/completed jobs
-    let mut archives = if matches!(job.status.as_str(), "processing" | "complete") {
-        match get_archives_for_thread_job(state.db.pool(), &job).await {
+    let mut archives = if matches!(job.status.as_str(), "processing" | "complete" | "cancelled") {
+        match get_archives_for_thread_job(state.db.read_pool(), &job).await {
             Ok(archives) => archives,
             Err(e) => {
                 tracing::error!("Failed to fetch archives for thread job {}: {e}", job.id);
@@ -2391,20 +3230,21 @@ async fn thread_job_status(
     });
 
     // Fetch archive status counts for progress tracking
-    let archive_status_counts = if matches!(job.status.as_str(), "processing" | "complete") {
-        match count_archives_by_status_for_thread(state.db.pool(), &job.thread_url).await {
-            Ok(counts) => pages::threads::ArchiveStatusCounts::from_hashmap(&counts),
-            Err(e) => {
-                tracing::error!(
-                    "Failed to fetch archive status counts for thread job {}: {e}",
-                    job.id
-                );
-                pages::threads::ArchiveStatusCounts::default()
+    let archive_status_counts =
+        if matches!(job.status.as_str(), "processing" | "complete" | "cancelled") {
+            match count_archives_by_status_for_thread(state.db.read_pool(), &job.thread_url).await {
+                Ok(counts) => pages::threads::ArchiveStatusCounts::from_hashmap(&counts),
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to fetch archive status counts for thread job {}: {e}",
+                        job.id
+                    );
+                    pages::threads::ArchiveStatusCounts::default()
+                }
             }
-        }
-    } else {
-        pages::threads::ArchiveStatusCounts::default()
-    };
+        } else {
+            pages::threads::ArchiveStatusCounts::default()
+        };
 
     let params = pages::ThreadJobStatusParams {
         job: &job,
@@ -2416,6 +3256,99 @@ async fn thread_job_status(
     Html(markup.into_string()).into_response()
 }
 
+/// SSE stream of thread archive job progress (GET /thread-job/:id/events).
+async fn thread_job_progress_events(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    MaybeUser(user): MaybeUser,
+) -> Response {
+    let job = match get_thread_archive_job(state.db.read_pool(), id).await {
+        Ok(Some(j)) => j,
+        Ok(None) => {
+            return (StatusCode::NOT_FOUND, "Job not found").into_response();
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch thread archive job: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    // Authorization: user must own the job or be an admin
+    match &user {
+        Some(u) if u.id == job.user_id || u.is_admin => {}
+        Some(_) => {
+            return (
+                StatusCode::FORBIDDEN,
+                "You don't have permission to view this job",
+            )
+                .into_response();
+        }
+        None => {
+            return (StatusCode::UNAUTHORIZED, "Please log in to view this job").into_response();
+        }
+    }
+
+    (
+        [(
+            header::HeaderName::from_static("x-accel-buffering"),
+            header::HeaderValue::from_static("no"),
+        )],
+        thread_job_events::thread_job_events(
+            state.db.read_pool().clone(),
+            id,
+            thread_job_events::THREAD_JOB_EVENTS_INTERVAL,
+        ),
+    )
+        .into_response()
+}
+
+/// Handler for cancelling a thread archive job (POST /submit/thread/:id/cancel).
+async fn cancel_thread_job(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    MaybeUser(user): MaybeUser,
+) -> Response {
+    tracing::debug!(job_id = id, "HTTP API: POST /submit/thread/:id/cancel");
+    let job = match get_thread_archive_job(state.db.read_pool(), id).await {
+        Ok(Some(j)) => j,
+        Ok(None) => {
+            return (StatusCode::NOT_FOUND, "Job not found").into_response();
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch thread archive job: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    // Authorization: user must own the job or be an admin
+    match &user {
+        Some(u) if u.id == job.user_id || u.is_admin => {}
+        Some(_) => {
+            return (
+                StatusCode::FORBIDDEN,
+                "You don't have permission to cancel this job",
+            )
+                .into_response();
+        }
+        None => {
+            return (StatusCode::UNAUTHORIZED, "Please log in to cancel this job").into_response();
+        }
+    }
+
+    match set_thread_archive_job_cancelled(state.db.pool(), id).await {
+        Ok(cancelled) => {
+            if cancelled {
+                tracing::info!(job_id = id, "Thread archive job cancelled");
+            }
+            Redirect::to(&format!("/submit/thread/{id}")).into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to cancel thread archive job: {e}");
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to cancel job").into_response()
+        }
+    }
+}
+
 // ========== Feed Routes ==========
 
 #[derive(Debug, Deserialize)]
@@ -2427,29 +3360,72 @@ pub struct FeedParams {
     content_type: Option<String>,
     /// Maximum number of items to return (default 50, max 100)
     limit: Option<i64>,
+    /// Only items at or after this RFC 3339 timestamp, oldest first
+    since: Option<String>,
+    /// Only items strictly before this RFC 3339 timestamp
+    before: Option<String>,
 }
 
-async fn feed_rss(State(state): State<AppState>, Query(params): Query<FeedParams>) -> Response {
+/// Normalize an optional RFC 3339 feed parameter to a UTC SQLite datetime.
+fn parse_feed_timestamp(name: &str, value: Option<&str>) -> Result<Option<String>, String> {
+    value
+        .map(|v| {
+            chrono::DateTime::parse_from_rfc3339(v)
+                .map(|dt| {
+                    dt.with_timezone(&chrono::Utc)
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string()
+                })
+                .map_err(|_| format!("Invalid `{name}` timestamp: expected RFC 3339"))
+        })
+        .transpose()
+}
+
+/// Fetch the archives for a feed request.
+///
+/// Without a window the newest archives come first. With `since` the feed
+/// becomes an incremental sync source: archives are returned oldest first so
+/// a client can resume from the last item it received.
+async fn fetch_feed_archives(
+    state: &AppState,
+    params: &FeedParams,
+) -> Result<Vec<Archive>, Response> {
     let limit = params.limit.unwrap_or(50).min(100);
+    let since = parse_feed_timestamp("since", params.since.as_deref())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e).into_response())?;
+    let before = parse_feed_timestamp("before", params.before.as_deref())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e).into_response())?;
+    let direction = if since.is_some() {
+        SortDirection::Asc
+    } else {
+        SortDirection::Desc
+    };
 
-    let archives = match get_recent_archives_with_filters(
-        state.db.pool(),
-        limit,
-        params.site.as_deref(),
-        params.content_type.as_deref(),
-    )
-    .await
-    {
+    ArchiveQuery::new()
+        .status("complete")
+        .domain(params.site.as_deref())
+        .content_type(params.content_type.as_deref())
+        .since(since.as_deref())
+        .before(before.as_deref())
+        .direction(direction)
+        .limit(limit)
+        .fetch_archives(state.db.read_pool())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch archives for feed: {e}");
+            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+        })
+}
+
+async fn feed_rss(State(state): State<AppState>, Query(params): Query<FeedParams>) -> Response {
+    let archives = match fetch_feed_archives(&state, &params).await {
         Ok(a) => a,
-        Err(e) => {
-            tracing::error!("Failed to fetch archives for RSS feed: {e}");
-            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
-        }
+        Err(response) => return response,
     };
 
     let base_url = &state.config.public_base_url;
 
-    let rss = feeds::generate_rss(&archives, base_url);
+    let rss = feeds::generate_rss(&archives, base_url, &state.config.ipfs_gateway_urls);
 
     (
         StatusCode::OK,
@@ -2460,26 +3436,14 @@ async fn feed_rss(State(state): State<AppState>, Query(params): Query<FeedParams
 }
 
 async fn feed_atom(State(state): State<AppState>, Query(params): Query<FeedParams>) -> Response {
-    let limit = params.limit.unwrap_or(50).min(100);
-
-    let archives = match get_recent_archives_with_filters(
-        state.db.pool(),
-        limit,
-        params.site.as_deref(),
-        params.content_type.as_deref(),
-    )
-    .await
-    {
+    let archives = match fetch_feed_archives(&state, &params).await {
         Ok(a) => a,
-        Err(e) => {
-            tracing::error!("Failed to fetch archives for Atom feed: {e}");
-            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
-        }
+        Err(response) => return response,
     };
 
     let base_url = &state.config.public_base_url;
 
-    let atom = feeds::generate_atom(&archives, base_url);
+    let atom = feeds::generate_atom(&archives, base_url, &state.config.ipfs_gateway_urls);
 
     (
         StatusCode::OK,
@@ -2489,6 +3453,79 @@ async fn feed_atom(State(state): State<AppState>, Query(params): Query<FeedParam
         .into_response()
 }
 
+// ========== Sitemaps ==========
+
+fn sitemap_response(xml: String) -> Response {
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/xml; charset=utf-8")],
+        xml,
+    )
+        .into_response()
+}
+
+/// Sitemap of archive and thread pages, or a sitemap index once the catalog
+/// outgrows a single file.
+async fn sitemap_xml(State(state): State<AppState>) -> Response {
+    let entries = match state
+        .sitemap_cache
+        .get_or_refresh(state.db.read_pool())
+        .await
+    {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::error!("Failed to build sitemap: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    let base_url = &state.config.public_base_url;
+    if entries.len() <= sitemap::SITEMAP_MAX_URLS {
+        sitemap_response(sitemap::generate_urlset(&entries, base_url))
+    } else {
+        sitemap_response(sitemap::generate_index(
+            sitemap::page_count(entries.len()),
+            base_url,
+        ))
+    }
+}
+
+/// Child sitemap `/sitemap/{n}.xml` referenced from the sitemap index.
+async fn sitemap_page(State(state): State<AppState>, Path(file): Path<String>) -> Response {
+    let Some(page) = file
+        .strip_suffix(".xml")
+        .and_then(|n| n.parse::<usize>().ok())
+        .filter(|&n| n > 0)
+    else {
+        return (StatusCode::NOT_FOUND, "Sitemap not found").into_response();
+    };
+
+    let entries = match state
+        .sitemap_cache
+        .get_or_refresh(state.db.read_pool())
+        .await
+    {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::error!("Failed to build sitemap: {e}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    if page > sitemap::page_count(entries.len()) {
+        return (StatusCode::NOT_FOUND, "Sitemap not found").into_response();
+    }
+
+    let chunk = entries
+        .chunks(sitemap::SITEMAP_MAX_URLS)
+        .nth(page - 1)
+        .unwrap_or_default();
+    sitemap_response(sitemap::generate_urlset(
+        chunk,
+        &state.config.public_base_url,
+    ))
+}
+
 // ========== JSON API Routes ==========
 
 /// NSFW filter mode for API queries.
@@ -2514,6 +3551,8 @@ pub struct ApiArchivesParams {
     /// Filter by content type (e.g., "video", "image", "gallery", "text", "thread")
     #[serde(rename = "type")]
     content_type: Option<String>,
+    /// Filter by source (e.g., "reddit", "youtube") or domain
+    source: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -2539,14 +3578,15 @@ async fn api_archives(
         NsfwFilter::Only => Some(true),
     };
 
-    let archives = match get_recent_archives_filtered_full(
-        state.db.pool(),
-        i64::from(per_page),
-        offset,
-        nsfw_filter,
-        params.content_type.as_deref(),
-    )
-    .await
+    let archives = match ArchiveQuery::new()
+        .status("complete")
+        .nsfw(nsfw_filter)
+        .content_type(params.content_type.as_deref())
+        .source(params.source.as_deref())
+        .limit(i64::from(per_page))
+        .offset(offset)
+        .fetch_archives(state.db.read_pool())
+        .await
     {
         Ok(a) => a,
         Err(e) => {
@@ -2579,7 +3619,7 @@ async fn api_archive_progress(State(state): State<AppState>, Path(id): Path<i64>
         "SELECT status, progress_percent, progress_details FROM archives WHERE id = ?",
     )
     .bind(id)
-    .fetch_optional(state.db.pool())
+    .fetch_optional(state.db.read_pool())
     .await;
 
     match result {
@@ -2606,7 +3646,7 @@ async fn api_archive_comments(State(state): State<AppState>, Path(id): Path<i64>
     use axum::http::header;
 
     // Get archive (to verify it exists)
-    let _archive = match get_archive(state.db.pool(), id).await {
+    let _archive = match get_archive(state.db.read_pool(), id).await {
         Ok(Some(a)) => a,
         Ok(None) => {
             return (StatusCode::NOT_FOUND, "Archive not found").into_response();
@@ -2618,7 +3658,7 @@ async fn api_archive_comments(State(state): State<AppState>, Path(id): Path<i64>
     };
 
     // Get artifacts
-    let artifacts = match get_artifacts_for_archive(state.db.pool(), id).await {
+    let artifacts = match get_artifacts_for_archive(state.db.read_pool(), id).await {
         Ok(arts) => arts,
         Err(e) => {
             tracing::error!("Failed to fetch artifacts: {e}");
@@ -2659,6 +3699,247 @@ async fn api_archive_comments(State(state): State<AppState>, Path(id): Path<i64>
         .into_response()
 }
 
+/// JSON error body returned by API endpoints.
+#[derive(Debug, Serialize)]
+struct ApiError {
+    error: String,
+}
+
+fn api_error(status: StatusCode, message: &str) -> Response {
+    (
+        status,
+        Json(ApiError {
+            error: message.to_string(),
+        }),
+    )
+        .into_response()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiArchiveParams {
+    /// Allow NSFW archives for unauthenticated requests.
+    #[serde(default)]
+    nsfw: bool,
+}
+
+/// One artifact in the single-archive JSON response.
+#[derive(Debug, Serialize)]
+struct ApiArtifact {
+    id: i64,
+    kind: String,
+    content_type: Option<String>,
+    size_bytes: Option<i64>,
+    sha256: Option<String>,
+    created_at: String,
+    /// Path of the file on this server (`/s3/...`).
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subtitle_language: Option<crate::db::SubtitleLanguage>,
+}
+
+/// Full record for a single archive, served by `GET /api/archive/:id.json`.
+#[derive(Debug, Serialize)]
+struct ApiArchiveRecord {
+    archive: crate::db::Archive,
+    link: crate::db::Link,
+    artifacts: Vec<ApiArtifact>,
+    ipfs_cid: Option<String>,
+    /// First configured public gateway URL for `ipfs_cid`.
+    ipfs_gateway_url: Option<String>,
+    wayback_url: Option<String>,
+    archive_today_url: Option<String>,
+}
+
+impl ApiArchiveRecord {
+    fn new(
+        archive: crate::db::Archive,
+        link: crate::db::Link,
+        artifacts: Vec<crate::db::ArchiveArtifact>,
+        mut subtitle_languages: std::collections::HashMap<i64, crate::db::SubtitleLanguage>,
+        ipfs_gateways: &[String],
+    ) -> Self {
+        let artifacts = artifacts
+            .into_iter()
+            .map(|a| ApiArtifact {
+                url: format!("/s3/{}", a.s3_key),
+                subtitle_language: subtitle_languages.remove(&a.id),
+                id: a.id,
+                kind: a.kind,
+                content_type: a.content_type,
+                size_bytes: a.size_bytes,
+                sha256: a.sha256,
+                created_at: a.created_at,
+            })
+            .collect();
+
+        Self {
+            ipfs_gateway_url: archive.ipfs_cid.as_deref().and_then(|cid| {
+                crate::ipfs::gateway_urls_for(ipfs_gateways, cid)
+                    .into_iter()
+                    .next()
+            }),
+            ipfs_cid: archive.ipfs_cid.clone(),
+            wayback_url: archive.wayback_url.clone(),
+            archive_today_url: archive.archive_today_url.clone(),
+            archive,
+            link,
+            artifacts,
+        }
+    }
+}
+
+/// API endpoint returning a single archive's full record as JSON.
+///
+/// Accepts both `/api/archive/123` and `/api/archive/123.json`. NSFW archives
+/// require either a logged-in user or `?nsfw=true`.
+async fn api_archive_json(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<ApiArchiveParams>,
+    MaybeUser(user): MaybeUser,
+) -> Response {
+    archive_json_response(
+        state.db.read_pool(),
+        &id,
+        params.nsfw || user.is_some(),
+        &state.config.ipfs_gateway_urls,
+    )
+    .await
+}
+
+async fn archive_json_response(
+    pool: &sqlx::SqlitePool,
+    id_param: &str,
+    allow_nsfw: bool,
+    ipfs_gateways: &[String],
+) -> Response {
+    let Ok(id) = id_param
+        .strip_suffix(".json")
+        .unwrap_or(id_param)
+        .parse::<i64>()
+    else {
+        return api_error(StatusCode::NOT_FOUND, "Archive not found");
+    };
+
+    let archive = match get_archive(pool, id).await {
+        Ok(Some(a)) => a,
+        Ok(None) => return api_error(StatusCode::NOT_FOUND, "Archive not found"),
+        Err(e) => {
+            tracing::error!("Failed to fetch archive: {e}");
+            return api_error(StatusCode::INTERNAL_SERVER_ERROR, "Database error");
+        }
+    };
+
+    if archive.is_nsfw && !allow_nsfw {
+        return api_error(
+            StatusCode::FORBIDDEN,
+            "NSFW archive: log in or pass ?nsfw=true to view",
+        );
+    }
+
+    let link = match get_link(pool, archive.link_id).await {
+        Ok(Some(l)) => l,
+        Ok(None) => return api_error(StatusCode::NOT_FOUND, "Link not found"),
+        Err(e) => {
+            tracing::error!("Failed to fetch link: {e}");
+            return api_error(StatusCode::INTERNAL_SERVER_ERROR, "Database error");
+        }
+    };
+
+    let artifacts = match get_artifacts_for_archive(pool, id).await {
+        Ok(a) => a,
+        Err(e) => {
+            tracing::error!("Failed to fetch artifacts: {e}");
+            return api_error(StatusCode::INTERNAL_SERVER_ERROR, "Database error");
+        }
+    };
+
+    let subtitle_languages = match get_subtitle_languages_for_archive(pool, id).await {
+        Ok(langs) => langs,
+        Err(e) => {
+            tracing::error!("Failed to fetch subtitle languages: {e}");
+            std::collections::HashMap::new()
+        }
+    };
+
+    Json(ApiArchiveRecord::new(
+        archive,
+        link,
+        artifacts,
+        subtitle_languages,
+        ipfs_gateways,
+    ))
+    .into_response()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OEmbedParams {
+    url: String,
+    format: Option<String>,
+}
+
+/// oEmbed "link" response for an archive page.
+#[derive(Debug, Serialize)]
+struct OEmbedResponse {
+    version: &'static str,
+    #[serde(rename = "type")]
+    oembed_type: &'static str,
+    title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author_name: Option<String>,
+    provider_name: String,
+    provider_url: String,
+    cache_age: u64,
+}
+
+/// Extract the archive ID from an archive page URL (absolute or path-only).
+fn archive_id_from_oembed_url(url: &str) -> Option<i64> {
+    let path = url::Url::parse(url).map_or_else(|_| url.to_string(), |u| u.path().to_string());
+    let rest = path.strip_prefix("/archive/")?;
+    rest.trim_end_matches('/').parse().ok()
+}
+
+/// oEmbed endpoint (GET /oembed?url=<archive-url>) so platforms can auto-embed archives.
+async fn oembed(State(state): State<AppState>, Query(params): Query<OEmbedParams>) -> Response {
+    if params.format.as_deref().is_some_and(|f| f != "json") {
+        return api_error(StatusCode::NOT_IMPLEMENTED, "Only JSON format is supported");
+    }
+
+    let Some(id) = archive_id_from_oembed_url(&params.url) else {
+        return api_error(StatusCode::NOT_FOUND, "Not an archive URL");
+    };
+
+    let archive = match get_archive(state.db.read_pool(), id).await {
+        Ok(Some(a)) => a,
+        Ok(None) => return api_error(StatusCode::NOT_FOUND, "Archive not found"),
+        Err(e) => {
+            tracing::error!("Failed to fetch archive: {e}");
+            return api_error(StatusCode::INTERNAL_SERVER_ERROR, "Database error");
+        }
+    };
+
+    let title = archive
+        .content_title
+        .clone()
+        .unwrap_or_else(|| "Untitled Archive".to_string());
+    let og = OpenGraphMetadata::default();
+
+    Json(OEmbedResponse {
+        version: "1.0",
+        oembed_type: "link",
+        title: if archive.is_nsfw {
+            format!("[NSFW] {title}")
+        } else {
+            title
+        },
+        author_name: archive.content_author,
+        provider_name: og.site_name,
+        provider_url: state.config.public_base_url.clone(),
+        cache_age: 3600,
+    })
+    .into_response()
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ApiSearchParams {
     q: String,
@@ -2687,7 +3968,7 @@ async fn api_search(
     };
 
     let archives = match search_archives_filtered_full(
-        state.db.pool(),
+        state.db.read_pool(),
         &params.q,
         i64::from(per_page),
         nsfw_filter,
@@ -2712,20 +3993,30 @@ async fn api_search(
 
 // ========== S3 File Serving ==========
 
-async fn serve_s3_file(State(state): State<AppState>, Path(path): Path<String>) -> Response {
+/// `Cache-Control` for proxied files backed by a hashed artifact.
+const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+async fn serve_s3_file(
+    State(state): State<AppState>,
+    Path(path): Path<String>,
+    request_headers: HeaderMap,
+) -> Response {
     // Path already contains the full path after /s3/, use it directly as S3 key
     let s3_key = &path;
 
     // Check if this is an archive-specific video that should redirect to canonical path
-    if let Some(canonical_key) = try_get_canonical_video_redirect(state.db.pool(), s3_key).await {
+    if let Some(canonical_key) =
+        try_get_canonical_video_redirect(state.db.read_pool(), s3_key).await
+    {
         // Redirect to canonical video URL
         let redirect_url = format!("/s3/{}", canonical_key);
         return axum::response::Redirect::temporary(&redirect_url).into_response();
     }
 
     // Check if S3 is public (AWS S3) - redirect to public URL for large media files
-    // but proxy subtitle/transcript files to avoid CORS issues with JavaScript fetch
-    if state.s3.is_public() && !is_cors_sensitive_file(s3_key) {
+    // but proxy subtitle/transcript files to avoid CORS issues with JavaScript fetch,
+    // and precompressed HTML so clients without zstd support get it decompressed
+    if state.s3.is_public() && !is_cors_sensitive_file(s3_key) && !may_be_precompressed(s3_key) {
         let public_url = state.s3.get_public_url(s3_key);
         return axum::response::Redirect::temporary(&public_url).into_response();
     }
@@ -2748,6 +4039,19 @@ async fn serve_s3_file(State(state): State<AppState>, Path(path): Path<String>)
         }
     }
 
+    // Artifacts with a stored hash never change under the same key, so they can
+    // be cached forever and revalidated by hash without touching S3
+    let artifact = find_served_artifact(state.db.read_pool(), &final_key).await;
+    let varies_by_encoding = artifact
+        .as_ref()
+        .is_some_and(|a| a.content_encoding.is_some());
+    if let Some(response) = artifact_etag(artifact.as_ref(), &request_headers)
+        .as_deref()
+        .and_then(|etag| not_modified_response(&request_headers, etag, varies_by_encoding))
+    {
+        return response;
+    }
+
     // Download file from S3
     let (content, content_type) = match state.s3.download_file(&final_key).await {
         Ok((bytes, ct)) => (bytes, ct),
@@ -2757,10 +4061,72 @@ async fn serve_s3_file(State(state): State<AppState>, Path(path): Path<String>)
         }
     };
 
+    s3_file_response(
+        &final_key,
+        content,
+        content_type,
+        artifact.as_ref(),
+        state.config.verify_downloads,
+        &request_headers,
+    )
+    .await
+}
+
+/// Build the proxy response for a downloaded S3 object.
+///
+/// With `verify` set, an object whose bytes no longer match its artifact's
+/// stored SHA-256 is refused with 502 rather than served corrupt.
+async fn s3_file_response(
+    final_key: &str,
+    content: Vec<u8>,
+    content_type: String,
+    artifact: Option<&crate::db::ArchiveArtifact>,
+    verify: bool,
+    request_headers: &HeaderMap,
+) -> Response {
+    let etag = artifact_etag(artifact, request_headers);
+    let stored_encoding = artifact.and_then(|a| a.content_encoding.clone());
+
+    // Range requests aren't checked: a partial body can't be hashed against the whole
+    let expected_sha256 = artifact
+        .and_then(|a| a.sha256.clone())
+        .filter(|_| verify && !request_headers.contains_key(header::RANGE));
+    let content = match expected_sha256 {
+        Some(expected) => match verify_download(content, expected).await {
+            Ok(content) => content,
+            Err(actual) => {
+                tracing::error!(
+                    key = %final_key,
+                    expected = artifact.and_then(|a| a.sha256.as_deref()).unwrap_or_default(),
+                    actual = %actual,
+                    "S3 object does not match its stored SHA-256, refusing to serve it"
+                );
+                return (
+                    StatusCode::BAD_GATEWAY,
+                    "Stored file failed its integrity check",
+                )
+                    .into_response();
+            }
+        },
+        None => content,
+    };
+
+    // Precompressed artifacts go out as stored when the client can decode them;
+    // everything else is left to the CompressionLayer
+    let (content, content_encoding) =
+        match negotiate_stored_encoding(content, stored_encoding.as_deref(), request_headers).await
+        {
+            Ok(negotiated) => negotiated,
+            Err(e) => {
+                tracing::error!(key = %final_key, error = %e, "Failed to decode stored S3 file");
+                return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to read file").into_response();
+            }
+        };
+
     // Determine proper content type
     let mime_type = if content_type == "application/octet-stream" || content_type.is_empty() {
         // Try to guess from file extension
-        mime_guess::from_path(&final_key)
+        mime_guess::from_path(final_key)
             .first_or_octet_stream()
             .to_string()
     } else {
@@ -2774,13 +4140,13 @@ async fn serve_s3_file(State(state): State<AppState>, Path(path): Path<String>)
         &mime_type
     };
 
-    let content_disposition = suggest_content_disposition_filename(&final_key).map_or_else(
+    let content_disposition = suggest_content_disposition_filename(final_key).map_or_else(
         || "inline".to_string(),
         |name| format!("inline; filename=\"{name}\""),
     );
 
     // Add CORS headers for files accessed via JavaScript fetch
-    if is_cors_sensitive_file(&final_key) {
+    let mut response = if is_cors_sensitive_file(final_key) {
         (
             StatusCode::OK,
             [
@@ -2801,7 +4167,191 @@ async fn serve_s3_file(State(state): State<AppState>, Path(path): Path<String>)
             content,
         )
             .into_response()
+    };
+
+    if let Some(ref etag) = etag {
+        set_artifact_cache_headers(&mut response, etag);
+    }
+    if stored_encoding.is_some() {
+        let headers = response.headers_mut();
+        if let Some(encoding) = content_encoding {
+            headers.insert(
+                header::CONTENT_ENCODING,
+                header::HeaderValue::from_static(encoding),
+            );
+        }
+        headers.insert(
+            header::VARY,
+            header::HeaderValue::from_static("Accept-Encoding"),
+        );
+    }
+    response
+}
+
+/// Quoted `ETag` for the body this request will get, derived from the stored SHA-256.
+///
+/// A precompressed artifact sent decoded is a different representation, so it
+/// gets its own tag. Files without an artifact row or hash are served without
+/// caching headers.
+fn artifact_etag(
+    artifact: Option<&crate::db::ArchiveArtifact>,
+    request_headers: &HeaderMap,
+) -> Option<String> {
+    let artifact = artifact?;
+    let sha256 = artifact.sha256.as_ref()?;
+    let decoded = artifact
+        .content_encoding
+        .as_deref()
+        .is_some_and(|encoding| !sends_stored_encoding(encoding, request_headers));
+    Some(if decoded {
+        format!("\"{sha256}-identity\"")
+    } else {
+        format!("\"{sha256}\"")
+    })
+}
+
+/// Check downloaded bytes against an artifact's stored SHA-256.
+///
+/// Hashing runs on the blocking pool since proxied objects can be large.
+/// Returns the bytes if they match, or the actual hash if they don't.
+async fn verify_download(content: Vec<u8>, expected_sha256: String) -> Result<Vec<u8>, String> {
+    use sha2::{Digest, Sha256};
+
+    let checked = tokio::task::spawn_blocking(move || {
+        let actual = hex::encode(Sha256::digest(&content));
+        if actual.eq_ignore_ascii_case(&expected_sha256) {
+            Ok(content)
+        } else {
+            Err(actual)
+        }
+    })
+    .await;
+    checked.unwrap_or_else(|e| Err(format!("hash task failed: {e}")))
+}
+
+/// The artifact row for a served S3 key, for its hash and stored encoding.
+///
+/// Files without an artifact row are served without caching headers.
+async fn find_served_artifact(
+    pool: &sqlx::SqlitePool,
+    s3_key: &str,
+) -> Option<crate::db::ArchiveArtifact> {
+    match find_artifact_by_s3_key(pool, s3_key).await {
+        Ok(artifact) => artifact,
+        Err(e) => {
+            tracing::debug!(s3_key, error = %e, "Failed to find artifact for S3 file");
+            None
+        }
+    }
+}
+
+/// Whether an S3 key may be stored precompressed (see [`crate::s3::PRECOMPRESSED_ENCODING`]).
+fn may_be_precompressed(s3_key: &str) -> bool {
+    s3_key.ends_with("/complete.html")
+}
+
+/// Whether the request's `Accept-Encoding` allows `encoding`.
+///
+/// An explicit entry wins over `*`; a `q` of zero refuses the encoding.
+fn accepts_encoding(request_headers: &HeaderMap, encoding: &str) -> bool {
+    let mut explicit = None;
+    let mut wildcard = None;
+    for item in request_headers
+        .get_all(header::ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+    {
+        let mut parts = item.split(';').map(str::trim);
+        let name = parts.next().unwrap_or_default();
+        let allowed = parts
+            .filter_map(|param| param.strip_prefix("q="))
+            .next_back()
+            .is_none_or(|q| q.parse::<f32>().is_ok_and(|q| q > 0.0));
+        if name.eq_ignore_ascii_case(encoding) {
+            explicit = Some(allowed);
+        } else if name == "*" {
+            wildcard = Some(allowed);
+        }
+    }
+    explicit.or(wildcard).unwrap_or(false)
+}
+
+/// Whether an object stored with `stored_encoding` goes out as stored.
+///
+/// Only [`crate::s3::PRECOMPRESSED_ENCODING`] is passed through, and only to
+/// clients that accept it; anything else is decoded first.
+fn sends_stored_encoding(stored_encoding: &str, request_headers: &HeaderMap) -> bool {
+    stored_encoding.eq_ignore_ascii_case(crate::s3::PRECOMPRESSED_ENCODING)
+        && accepts_encoding(request_headers, crate::s3::PRECOMPRESSED_ENCODING)
+}
+
+/// Pick the body to send for an S3 object stored with `stored_encoding`.
+///
+/// Returns the stored bytes with their encoding when the client accepts it,
+/// otherwise the decompressed bytes with no encoding.
+async fn negotiate_stored_encoding(
+    content: Vec<u8>,
+    stored_encoding: Option<&str>,
+    request_headers: &HeaderMap,
+) -> anyhow::Result<(Vec<u8>, Option<&'static str>)> {
+    let Some(encoding) = stored_encoding else {
+        return Ok((content, None));
+    };
+    if sends_stored_encoding(encoding, request_headers) {
+        return Ok((content, Some(crate::s3::PRECOMPRESSED_ENCODING)));
+    }
+    let encoding = encoding.to_string();
+    let decoded =
+        tokio::task::spawn_blocking(move || crate::s3::decode_stored(content, Some(&encoding)))
+            .await??;
+    Ok((decoded, None))
+}
+
+/// Whether the request's `If-None-Match` header matches `etag`.
+///
+/// Handles comma-separated lists, weak validators and `*`.
+fn if_none_match_matches(request_headers: &HeaderMap, etag: &str) -> bool {
+    request_headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+}
+
+/// A `304 Not Modified` response if the client already has `etag`.
+///
+/// `varies_by_encoding` repeats the full response's `Vary: Accept-Encoding`.
+fn not_modified_response(
+    request_headers: &HeaderMap,
+    etag: &str,
+    varies_by_encoding: bool,
+) -> Option<Response> {
+    if !if_none_match_matches(request_headers, etag) {
+        return None;
+    }
+    let mut response = StatusCode::NOT_MODIFIED.into_response();
+    set_artifact_cache_headers(&mut response, etag);
+    if varies_by_encoding {
+        response.headers_mut().insert(
+            header::VARY,
+            header::HeaderValue::from_static("Accept-Encoding"),
+        );
     }
+    Some(response)
+}
+
+fn set_artifact_cache_headers(response: &mut Response, etag: &str) {
+    let headers = response.headers_mut();
+    if let Ok(value) = header::HeaderValue::from_str(etag) {
+        headers.insert(header::ETAG, value);
+    }
+    headers.insert(
+        header::CACHE_CONTROL,
+        header::HeaderValue::from_static(IMMUTABLE_CACHE_CONTROL),
+    );
 }
 
 /// Check if a file should be proxied instead of redirected to avoid CORS issues.
@@ -2929,7 +4479,7 @@ async fn create_comment_handler(
     }
 
     // Verify archive exists
-    if get_archive(state.db.pool(), archive_id)
+    if get_archive(state.db.read_pool(), archive_id)
         .await
         .ok()
         .flatten()
@@ -2989,7 +4539,7 @@ async fn create_reply_handler(
     }
 
     // Verify parent comment exists and belongs to the archive
-    match get_comment_with_author(state.db.pool(), parent_comment_id).await {
+    match get_comment_with_author(state.db.read_pool(), parent_comment_id).await {
         Ok(Some(parent)) => {
             if parent.archive_id != archive_id {
                 return (StatusCode::BAD_REQUEST, "Comment not on this archive").into_response();
@@ -3051,7 +4601,7 @@ async fn edit_comment_handler(
     }
 
     // Check if user can edit
-    match can_user_edit_comment(state.db.pool(), comment_id, user.id, user.is_admin).await {
+    match can_user_edit_comment(state.db.read_pool(), comment_id, user.id, user.is_admin).await {
         Ok(true) => {}
         Ok(false) => {
             tracing::warn!(
@@ -3104,7 +4654,7 @@ async fn delete_comment_handler(
         "HTTP API: DELETE /archive/:id/comment/:comment_id"
     );
     // Get comment to verify ownership
-    let comment = match get_comment_with_author(state.db.pool(), comment_id).await {
+    let comment = match get_comment_with_author(state.db.read_pool(), comment_id).await {
         Ok(Some(c)) => c,
         Ok(None) => return (StatusCode::NOT_FOUND, "Comment not found").into_response(),
         Err(e) => {
@@ -3249,7 +4799,7 @@ async fn comment_history_handler(
     Path((_archive_id, comment_id)): Path<(i64, i64)>,
     RequireUser(_user): RequireUser,
 ) -> Response {
-    match get_comment_edit_history(state.db.pool(), comment_id).await {
+    match get_comment_edit_history(state.db.read_pool(), comment_id).await {
         Ok(edits) => {
             let markup = pages::render_comment_edit_history_page(&edits);
             Html(markup.into_string()).into_response()
@@ -3388,6 +4938,213 @@ async fn extract_og_from_s3_fallback(
 mod tests {
     use super::*;
 
+    const TEST_ETAG: &str = "\"9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08\"";
+
+    fn if_none_match(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::IF_NONE_MATCH,
+            header::HeaderValue::from_str(value).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_parse_feed_timestamp() {
+        assert_eq!(
+            parse_feed_timestamp("since", Some("2024-03-01T12:30:00+02:00")).unwrap(),
+            Some("2024-03-01 10:30:00".to_string())
+        );
+        assert_eq!(parse_feed_timestamp("since", None).unwrap(), None);
+
+        assert_eq!(
+            parse_feed_timestamp("before", Some("yesterday")).unwrap_err(),
+            "Invalid `before` timestamp: expected RFC 3339"
+        );
+        assert!(parse_feed_timestamp("since", Some("2024-03-01 10:30:00")).is_err());
+    }
+
+    #[test]
+    fn test_if_none_match_matches_same_etag() {
+        assert!(if_none_match_matches(&if_none_match(TEST_ETAG), TEST_ETAG));
+        assert!(if_none_match_matches(
+            &if_none_match(&format!("\"other\", W/{TEST_ETAG}")),
+            TEST_ETAG
+        ));
+        assert!(if_none_match_matches(&if_none_match("*"), TEST_ETAG));
+    }
+
+    #[test]
+    fn test_if_none_match_mismatch_or_missing() {
+        assert!(!if_none_match_matches(
+            &if_none_match("\"other\""),
+            TEST_ETAG
+        ));
+        assert!(!if_none_match_matches(&HeaderMap::new(), TEST_ETAG));
+    }
+
+    #[test]
+    fn test_not_modified_response_for_matching_etag() {
+        let response = not_modified_response(&if_none_match(TEST_ETAG), TEST_ETAG, false).unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], TEST_ETAG);
+        assert!(!response.headers().contains_key(header::VARY));
+
+        // Precompressed artifacts keep their Vary on revalidation
+        let response = not_modified_response(&if_none_match(TEST_ETAG), TEST_ETAG, true).unwrap();
+        assert_eq!(response.headers()[header::VARY], "Accept-Encoding");
+
+        // A mismatch falls through to a normal 200 response
+        assert!(not_modified_response(&if_none_match("\"other\""), TEST_ETAG, false).is_none());
+    }
+
+    fn accept_encoding(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT_ENCODING, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_accepts_encoding() {
+        assert!(accepts_encoding(
+            &accept_encoding("gzip, deflate, br, zstd"),
+            "zstd"
+        ));
+        assert!(accepts_encoding(&accept_encoding("ZSTD;q=0.5"), "zstd"));
+        assert!(accepts_encoding(&accept_encoding("gzip, *"), "zstd"));
+
+        assert!(!accepts_encoding(
+            &accept_encoding("gzip, deflate, br"),
+            "zstd"
+        ));
+        assert!(!accepts_encoding(
+            &accept_encoding("zstd;q=0, gzip"),
+            "zstd"
+        ));
+        // An explicit refusal beats the wildcard
+        assert!(!accepts_encoding(&accept_encoding("*, zstd;q=0"), "zstd"));
+        assert!(!accepts_encoding(&HeaderMap::new(), "zstd"));
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_stored_encoding() {
+        let html = b"<html>archived page</html>".repeat(50);
+        let stored = crate::s3::precompress(&html).unwrap();
+
+        // Clients that accept zstd get the stored bytes as-is
+        let (body, encoding) =
+            negotiate_stored_encoding(stored.clone(), Some("zstd"), &accept_encoding("gzip, zstd"))
+                .await
+                .unwrap();
+        assert_eq!(body, stored);
+        assert_eq!(encoding, Some("zstd"));
+
+        // Others get it decompressed, left for the CompressionLayer
+        let (body, encoding) =
+            negotiate_stored_encoding(stored, Some("zstd"), &accept_encoding("gzip"))
+                .await
+                .unwrap();
+        assert_eq!(body, html);
+        assert_eq!(encoding, None);
+
+        // Uncompressed artifacts pass through untouched
+        let (body, encoding) =
+            negotiate_stored_encoding(html.clone(), None, &accept_encoding("zstd"))
+                .await
+                .unwrap();
+        assert_eq!(body, html);
+        assert_eq!(encoding, None);
+    }
+
+    #[test]
+    fn test_may_be_precompressed() {
+        assert!(may_be_precompressed("archives/1/media/complete.html"));
+        assert!(!may_be_precompressed("archives/1/media/view.html"));
+        assert!(!may_be_precompressed("archives/1/media/video.mp4"));
+    }
+
+    /// An artifact whose stored hash is that of `b"test"` (see [`TEST_ETAG`]).
+    fn hashed_artifact() -> crate::db::ArchiveArtifact {
+        crate::db::ArchiveArtifact {
+            sha256: Some(TEST_ETAG.trim_matches('"').to_string()),
+            ..sample_artifact(1, "video", "archives/7/media/video.mp4")
+        }
+    }
+
+    #[test]
+    fn test_artifact_etag_depends_on_served_encoding() {
+        let plain = hashed_artifact();
+        assert_eq!(
+            artifact_etag(Some(&plain), &accept_encoding("zstd")).as_deref(),
+            Some(TEST_ETAG)
+        );
+
+        // The stored zstd body and its decoded form must not share a tag
+        let precompressed = crate::db::ArchiveArtifact {
+            content_encoding: Some("zstd".to_string()),
+            ..hashed_artifact()
+        };
+        let stored = artifact_etag(Some(&precompressed), &accept_encoding("zstd")).unwrap();
+        let decoded = artifact_etag(Some(&precompressed), &accept_encoding("gzip")).unwrap();
+        assert_eq!(stored, TEST_ETAG);
+        assert_ne!(decoded, stored);
+        assert!(decoded.starts_with('"') && decoded.ends_with('"'));
+
+        assert!(artifact_etag(None, &HeaderMap::new()).is_none());
+    }
+
+    async fn verified_response(body: &[u8], verify: bool, headers: &HeaderMap) -> Response {
+        s3_file_response(
+            "archives/7/media/video.mp4",
+            body.to_vec(),
+            "video/mp4".to_string(),
+            Some(&hashed_artifact()),
+            verify,
+            headers,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_verify_downloads_matching_hash_is_served() {
+        let response = verified_response(b"test", true, &HeaderMap::new()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::ETAG], TEST_ETAG);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"test");
+    }
+
+    #[tokio::test]
+    async fn test_verify_downloads_mismatched_hash_is_bad_gateway() {
+        let response = verified_response(b"corrupt", true, &HeaderMap::new()).await;
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    }
+
+    #[tokio::test]
+    async fn test_verify_downloads_skipped_when_off_or_ranged() {
+        let response = verified_response(b"corrupt", false, &HeaderMap::new()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let mut range = HeaderMap::new();
+        range.insert(header::RANGE, "bytes=0-3".parse().unwrap());
+        let response = verified_response(b"corrupt", true, &range).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_set_artifact_cache_headers() {
+        let mut response = (StatusCode::OK, "body").into_response();
+        set_artifact_cache_headers(&mut response, TEST_ETAG);
+
+        assert_eq!(response.headers()[header::ETAG], TEST_ETAG);
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            IMMUTABLE_CACHE_CONTROL
+        );
+    }
+
     #[test]
     fn test_normalize_discourse_thread_url_with_post_number() {
         let mut url = url::Url::parse("https://discuss.example.com/t/topic-name/1491/16").unwrap();
@@ -3485,4 +5242,403 @@ mod tests {
             "other.example.com"
         ));
     }
+
+    fn sample_archive() -> crate::db::Archive {
+        crate::db::Archive {
+            id: 7,
+            link_id: 3,
+            status: "complete".to_string(),
+            archived_at: Some("2024-01-15 12:00:00".to_string()),
+            content_title: Some("Example".to_string()),
+            content_author: None,
+            content_text: None,
+            content_type: Some("video".to_string()),
+            s3_key_primary: Some("archives/7/media/video.mp4".to_string()),
+            s3_key_thumb: None,
+            s3_keys_extra: None,
+            wayback_url: Some("https://web.archive.org/web/2024/https://example.com/v".to_string()),
+            archive_today_url: None,
+            ipfs_cid: Some("bafyexample".to_string()),
+            error_message: None,
+            retry_count: 0,
+            created_at: "2024-01-15 12:00:00".to_string(),
+            is_nsfw: false,
+            nsfw_source: None,
+            next_retry_at: None,
+            last_attempt_at: None,
+            http_status_code: Some(200),
+            redirect_chain: None,
+            post_date: None,
+            quoted_archive_id: None,
+            reply_to_archive_id: None,
+            submitted_by_user_id: None,
+            progress_percent: None,
+            progress_details: None,
+            last_progress_update: None,
+            og_title: None,
+            og_description: None,
+            og_image: None,
+            og_type: None,
+            og_extracted_at: None,
+            og_extraction_attempted: false,
+            transcript_text: None,
+            full_text: None,
+            view_count: None,
+            like_count: None,
+            repost_count: None,
+            platform_comment_count: None,
+            save_count: None,
+            metrics_backfill_version: None,
+            short_code: None,
+            error_kind: None,
+            skip_reason: None,
+        }
+    }
+
+    fn sample_artifact(id: i64, kind: &str, s3_key: &str) -> crate::db::ArchiveArtifact {
+        crate::db::ArchiveArtifact {
+            id,
+            archive_id: 7,
+            kind: kind.to_string(),
+            s3_key: s3_key.to_string(),
+            content_type: Some("video/mp4".to_string()),
+            size_bytes: Some(1024),
+            sha256: None,
+            created_at: "2024-01-15 12:00:00".to_string(),
+            perceptual_hash: None,
+            duplicate_of_artifact_id: None,
+            video_file_id: None,
+            metadata: None,
+            placeholder_color: None,
+            width: None,
+            height: None,
+            content_encoding: None,
+        }
+    }
+
+    #[test]
+    fn test_archive_id_from_oembed_url() {
+        assert_eq!(
+            archive_id_from_oembed_url("https://archive.example.com/archive/42"),
+            Some(42)
+        );
+        assert_eq!(
+            archive_id_from_oembed_url("https://archive.example.com/archive/42/?x=1"),
+            Some(42)
+        );
+        assert_eq!(archive_id_from_oembed_url("/archive/7"), Some(7));
+        assert_eq!(
+            archive_id_from_oembed_url("https://archive.example.com/post/abc"),
+            None
+        );
+        assert_eq!(
+            archive_id_from_oembed_url("https://archive.example.com/archive/42/versions/1"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_api_archive_record_serialization() {
+        let link = crate::db::Link {
+            id: 3,
+            original_url: "https://example.com/v".to_string(),
+            normalized_url: "https://example.com/v".to_string(),
+            canonical_url: None,
+            final_url: None,
+            domain: "example.com".to_string(),
+            first_seen_at: "2024-01-01 00:00:00".to_string(),
+            last_archived_at: None,
+        };
+        let artifacts = vec![
+            sample_artifact(1, "video", "archives/7/media/video.mp4"),
+            sample_artifact(2, "subtitles", "archives/7/media/video.en.vtt"),
+        ];
+        let mut languages = std::collections::HashMap::new();
+        languages.insert(
+            2,
+            crate::db::SubtitleLanguage {
+                id: 1,
+                artifact_id: 2,
+                language: "en".to_string(),
+                detected_from: "filename".to_string(),
+                is_auto: false,
+                created_at: "2024-01-15 12:00:00".to_string(),
+                updated_at: "2024-01-15 12:00:00".to_string(),
+            },
+        );
+
+        let gateways = vec!["https://ipfs.io/ipfs/".to_string()];
+        let record = ApiArchiveRecord::new(sample_archive(), link, artifacts, languages, &gateways);
+        let json = serde_json::to_value(&record).unwrap();
+
+        assert_eq!(json["archive"]["id"], 7);
+        assert_eq!(json["link"]["domain"], "example.com");
+        assert_eq!(json["ipfs_cid"], "bafyexample");
+        assert_eq!(json["ipfs_gateway_url"], "https://ipfs.io/ipfs/bafyexample");
+        assert!(json["wayback_url"]
+            .as_str()
+            .unwrap()
+            .starts_with("https://web.archive.org/"));
+        assert!(json["archive_today_url"].is_null());
+
+        let artifacts = json["artifacts"].as_array().unwrap();
+        assert_eq!(artifacts.len(), 2);
+        assert_eq!(artifacts[0]["kind"], "video");
+        assert_eq!(artifacts[0]["url"], "/s3/archives/7/media/video.mp4");
+        assert_eq!(artifacts[0]["size_bytes"], 1024);
+        assert_eq!(artifacts[0]["content_type"], "video/mp4");
+        assert!(artifacts[0].get("subtitle_language").is_none());
+        assert_eq!(artifacts[1]["subtitle_language"]["language"], "en");
+    }
+
+    #[tokio::test]
+    async fn test_api_archive_json_not_found() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db = crate::db::Database::new(&temp_dir.path().join("test.sqlite"))
+            .await
+            .unwrap();
+
+        for id in ["999.json", "999", "abc.json"] {
+            let response = archive_json_response(db.pool(), id, false, &[]).await;
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+            assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(json["error"], "Archive not found");
+        }
+    }
+
+    #[test]
+    fn test_queue_stats_response_serialization() {
+        let stats = crate::db::QueueStats {
+            pending_count: 4,
+            processing_count: 1,
+            failed_awaiting_retry: 2,
+            failed_max_retries: 3,
+            skipped_count: 0,
+            complete_count: 10,
+            next_retry_at: None,
+            oldest_pending_at: Some("2024-01-15 12:00:00".to_string()),
+        };
+        let now = chrono::NaiveDateTime::parse_from_str("2024-01-15 12:30:00", "%Y-%m-%d %H:%M:%S")
+            .unwrap();
+        let counts = vec![("pending".to_string(), 4), ("complete".to_string(), 10)];
+
+        let json = serde_json::to_value(QueueStatsResponse::new(stats, counts, now)).unwrap();
+
+        assert_eq!(json["pending_count"], 4);
+        assert_eq!(json["failed_max_retries"], 3);
+        assert!(json["next_retry_at"].is_null());
+        assert_eq!(json["oldest_pending_at"], "2024-01-15 12:00:00");
+        assert_eq!(json["oldest_pending_age_secs"], 1800);
+        assert_eq!(
+            json["status_counts"],
+            serde_json::json!({"complete": 10, "pending": 4})
+        );
+    }
+
+    /// App state over a fresh database in `temp_dir`.
+    async fn test_state(config: crate::config::Config, temp_dir: &tempfile::TempDir) -> AppState {
+        std::env::set_var("AWS_ACCESS_KEY_ID", "test");
+        std::env::set_var("AWS_SECRET_ACCESS_KEY", "test");
+        let db = crate::db::Database::new(&temp_dir.path().join("test.sqlite"))
+            .await
+            .unwrap();
+        AppState {
+            db,
+            s3: std::sync::Arc::new(crate::s3::S3Client::new(&config).await.unwrap()),
+            config: std::sync::Arc::new(config),
+            stats_cache: std::sync::Arc::new(super::super::StatsCache::new(
+                std::time::Duration::from_secs(60),
+            )),
+            sitemap_cache: std::sync::Arc::new(super::super::SitemapCache::default()),
+        }
+    }
+
+    /// Create an approved user with a live session, returning its cookie.
+    async fn login_cookie(db: &crate::db::Database) -> String {
+        let user_id = crate::db::create_user(db.pool(), "submitter", "hash", true)
+            .await
+            .unwrap();
+        let expires_at = (chrono::Utc::now() + chrono::Duration::days(1)).to_rfc3339();
+        crate::db::create_session(
+            db.pool(),
+            user_id,
+            "test-token",
+            "test-csrf",
+            "127.0.0.1",
+            None,
+            &expires_at,
+        )
+        .await
+        .unwrap();
+        "session=test-token".to_string()
+    }
+
+    fn submit_request(cookie: &str, url: &str) -> axum::http::Request<axum::body::Body> {
+        axum::http::Request::builder()
+            .method("POST")
+            .uri("/submit")
+            .header(header::COOKIE, cookie)
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))))
+            .body(axum::body::Body::from(format!(
+                "url={}",
+                urlencoding::encode(url)
+            )))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_api_queue_json_requires_auth() {
+        use tower::ServiceExt;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = crate::config::Config {
+            queue_api_token: Some("s3cret".to_string()),
+            ..crate::config::Config::for_testing()
+        };
+        let state = test_state(config, &temp_dir).await;
+        let app = router().with_state(state);
+
+        let request = |auth: Option<&str>| {
+            let mut builder = axum::http::Request::builder().uri("/api/queue.json");
+            if let Some(auth) = auth {
+                builder = builder.header(header::AUTHORIZATION, auth);
+            }
+            builder.body(axum::body::Body::empty()).unwrap()
+        };
+
+        for auth in [None, Some("Bearer wrong"), Some("s3cret")] {
+            let response = app.clone().oneshot(request(auth)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{auth:?}");
+        }
+
+        let response = app
+            .clone()
+            .oneshot(request(Some("Bearer s3cret")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["pending_count"], 0);
+        assert!(json["oldest_pending_age_secs"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_submission_rate_limited_response() {
+        let response = submission_rate_limited(5, 1250);
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "1250");
+        assert_eq!(response.headers()["x-ratelimit-limit"], "5");
+        assert_eq!(response.headers()["x-ratelimit-remaining"], "0");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("limit of 5 submissions per hour"));
+        assert!(body.contains("about 21 minutes"));
+
+        // A window that already expired still asks the client to wait a moment
+        let response = submission_rate_limited(5, 0);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "1");
+    }
+
+    #[tokio::test]
+    async fn test_submit_twitter_url_reuses_x_link() {
+        use tower::ServiceExt;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = crate::config::Config {
+            submission_enabled: true,
+            ..crate::config::Config::for_testing()
+        };
+        let state = test_state(config, &temp_dir).await;
+        let db = state.db.clone();
+        let cookie = login_cookie(&db).await;
+
+        // The same tweet, already ingested from the feed under its x.com key
+        let link_id = get_or_create_link(
+            db.pool(),
+            &NewLink {
+                original_url: "https://x.com/someone/status/123".to_string(),
+                normalized_url: normalize_link_url("https://x.com/someone/status/123"),
+                canonical_url: None,
+                domain: "x.com".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let response = router()
+            .with_state(state)
+            .oneshot(submit_request(
+                &cookie,
+                "https://twitter.com/someone/status/123",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+
+        let links: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM links")
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(links, 1);
+        let archive = get_archive_by_link_id(db.pool(), link_id)
+            .await
+            .unwrap()
+            .expect("submission queued against the existing link");
+        assert_eq!(archive.status, "pending");
+        assert_eq!(
+            response.headers()[header::LOCATION],
+            format!("/archive/{}", archive.id).as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submit_rate_limit_headers() {
+        use tower::ServiceExt;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = crate::config::Config {
+            submission_enabled: true,
+            submission_rate_limit_per_hour: 2,
+            ..crate::config::Config::for_testing()
+        };
+        let state = test_state(config, &temp_dir).await;
+        let cookie = login_cookie(&state.db).await;
+        let app = router().with_state(state);
+
+        for (n, remaining) in [(1, "1"), (2, "0")] {
+            let url = format!("https://example.com/page/{n}");
+            let response = app
+                .clone()
+                .oneshot(submit_request(&cookie, &url))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::SEE_OTHER);
+            assert_eq!(response.headers()["x-ratelimit-limit"], "2");
+            assert_eq!(response.headers()["x-ratelimit-remaining"], remaining);
+        }
+
+        let response = app
+            .oneshot(submit_request(&cookie, "https://example.com/page/3"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: i64 = response.headers()[header::RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((3500..=3600).contains(&retry_after));
+        assert_eq!(response.headers()["x-ratelimit-limit"], "2");
+        assert_eq!(response.headers()["x-ratelimit-remaining"], "0");
+    }
 }
@@ -0,0 +1,232 @@
+//! Sitemap generation for search engines.
+//!
+//! `/sitemap.xml` lists public archive and thread pages. Catalogs larger than
+//! the 50,000-URL limit of a single sitemap get a sitemap index pointing at
+//! numbered child sitemaps under `/sitemap/`. Raw `/s3/` files are never listed.
+
+use std::fmt::Write as _;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use sqlx::SqlitePool;
+
+use crate::db;
+
+/// Maximum number of URLs in a single sitemap file.
+pub const SITEMAP_MAX_URLS: usize = 50_000;
+
+/// A page listed in the sitemap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SitemapEntry {
+    /// Path relative to the public base URL, e.g. `/archive/12`.
+    pub path: String,
+    /// Last modification date (`YYYY-MM-DD`).
+    pub lastmod: Option<String>,
+}
+
+impl SitemapEntry {
+    fn new(path: String, timestamp: Option<&str>) -> Self {
+        Self {
+            path,
+            lastmod: timestamp.and_then(w3c_date),
+        }
+    }
+}
+
+/// Reduce a stored timestamp (`YYYY-MM-DD HH:MM:SS` or RFC 3339) to its date.
+fn w3c_date(timestamp: &str) -> Option<String> {
+    let date = timestamp.get(..10)?;
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .map(|_| date.to_string())
+}
+
+/// Number of child sitemaps needed for `entry_count` URLs.
+#[must_use]
+pub fn page_count(entry_count: usize) -> usize {
+    entry_count.div_ceil(SITEMAP_MAX_URLS).max(1)
+}
+
+/// Generate a `<urlset>` sitemap for `entries`.
+#[must_use]
+pub fn generate_urlset(entries: &[SitemapEntry], base_url: &str) -> String {
+    let base_url = base_url.trim_end_matches('/');
+    let mut urls = String::new();
+    for entry in entries {
+        let loc = xml_escape(&format!("{base_url}{}", entry.path));
+        let _ = writeln!(urls, "  <url>\n    <loc>{loc}</loc>");
+        if let Some(ref lastmod) = entry.lastmod {
+            let _ = writeln!(urls, "    <lastmod>{lastmod}</lastmod>");
+        }
+        urls.push_str("  </url>\n");
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+{urls}</urlset>"#
+    )
+}
+
+/// Generate a `<sitemapindex>` pointing at `pages` child sitemaps.
+#[must_use]
+pub fn generate_index(pages: usize, base_url: &str) -> String {
+    let base_url = base_url.trim_end_matches('/');
+    let mut sitemaps = String::new();
+    for page in 1..=pages {
+        let loc = xml_escape(&format!("{base_url}/sitemap/{page}.xml"));
+        let _ = writeln!(sitemaps, "  <sitemap>\n    <loc>{loc}</loc>\n  </sitemap>");
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+{sitemaps}</sitemapindex>"#
+    )
+}
+
+/// Escape XML special characters
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Collect every sitemap entry: archive pages, then thread pages.
+async fn fetch_entries(pool: &SqlitePool) -> Result<Vec<SitemapEntry>> {
+    let mut entries: Vec<SitemapEntry> = db::get_sitemap_archives(pool)
+        .await?
+        .into_iter()
+        .map(|(id, archived_at)| {
+            SitemapEntry::new(format!("/archive/{id}"), archived_at.as_deref())
+        })
+        .collect();
+
+    // Thread listings are aggregated in memory, so there is no cheaper way to page
+    let threads = db::get_all_threads(pool, "newest", i64::MAX, 0).await?;
+    entries.extend(threads.iter().filter_map(|thread| {
+        let key = db::thread_key_from_url(&thread.discourse_url);
+        let topic_id = db::extract_topic_id_from_thread_key(&key)?;
+        Some(SitemapEntry::new(
+            format!("/threads/{topic_id}"),
+            thread.last_archived_at.as_deref(),
+        ))
+    }));
+
+    Ok(entries)
+}
+
+/// Sitemap entries cached for a short TTL so crawlers don't rerun the queries.
+pub struct SitemapCache {
+    cache: RwLock<Option<(Instant, Arc<Vec<SitemapEntry>>)>>,
+    ttl: Duration,
+}
+
+impl SitemapCache {
+    /// Create a new sitemap cache with the given TTL.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            cache: RwLock::new(None),
+            ttl,
+        }
+    }
+
+    /// Get cached entries or fetch fresh ones if expired.
+    pub async fn get_or_refresh(&self, pool: &SqlitePool) -> Result<Arc<Vec<SitemapEntry>>> {
+        {
+            let cache = self.cache.read().unwrap();
+            if let Some((cached_at, ref entries)) = *cache {
+                if cached_at.elapsed() < self.ttl {
+                    return Ok(entries.clone());
+                }
+            }
+        }
+
+        let entries = Arc::new(fetch_entries(pool).await?);
+        *self.cache.write().unwrap() = Some((Instant::now(), entries.clone()));
+        Ok(entries)
+    }
+}
+
+impl Default for SitemapCache {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(600))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    /// Parse `xml` end to end, returning the text of every `<loc>`.
+    fn parse_locs(xml: &str) -> Vec<String> {
+        let mut reader = Reader::from_str(xml);
+        reader.config_mut().check_end_names = true;
+        let mut locs = Vec::new();
+        let mut in_loc = false;
+        loop {
+            match reader.read_event().expect("sitemap is not well-formed XML") {
+                Event::Start(e) => in_loc = e.name().as_ref() == b"loc",
+                Event::Text(t) if in_loc => locs.push(t.unescape().unwrap().into_owned()),
+                Event::End(_) => in_loc = false,
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        locs
+    }
+
+    #[test]
+    fn test_generate_urlset_is_well_formed() {
+        let entries = vec![
+            SitemapEntry::new("/archive/1".to_string(), Some("2024-03-05 12:34:56")),
+            SitemapEntry::new("/threads/42".to_string(), None),
+            SitemapEntry::new("/archive/2?a=1&b=2".to_string(), Some("not a date")),
+        ];
+        let xml = generate_urlset(&entries, "https://example.com/");
+
+        assert_eq!(
+            parse_locs(&xml),
+            vec![
+                "https://example.com/archive/1",
+                "https://example.com/threads/42",
+                "https://example.com/archive/2?a=1&b=2",
+            ]
+        );
+        assert!(xml.contains("<lastmod>2024-03-05</lastmod>"));
+        assert_eq!(xml.matches("<lastmod>").count(), 1);
+    }
+
+    #[test]
+    fn test_generate_index_is_well_formed() {
+        let xml = generate_index(2, "https://example.com");
+        assert_eq!(
+            parse_locs(&xml),
+            vec![
+                "https://example.com/sitemap/1.xml",
+                "https://example.com/sitemap/2.xml",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_page_count() {
+        assert_eq!(page_count(0), 1);
+        assert_eq!(page_count(SITEMAP_MAX_URLS), 1);
+        assert_eq!(page_count(SITEMAP_MAX_URLS + 1), 2);
+    }
+
+    #[test]
+    fn test_w3c_date() {
+        assert_eq!(
+            w3c_date("2024-03-05T12:34:56+00:00").as_deref(),
+            Some("2024-03-05")
+        );
+        assert_eq!(w3c_date("2024-03"), None);
+    }
+}
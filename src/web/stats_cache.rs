@@ -42,6 +42,7 @@ impl CachedStats {
                 "thread" => "threads",
                 "playlist" => "playlists",
                 "pdf" => "PDFs",
+                "document" => "documents",
                 "mixed" => "mixed media",
                 _ => continue, // Skip unknown types
             };
@@ -0,0 +1,184 @@
+//! SSE progress stream for thread archive jobs.
+//!
+//! The job status page subscribes to `/thread-job/:id/events` and updates its
+//! progress bar live instead of reloading. The stream re-reads the
+//! `thread_archive_jobs` row on a short interval, sends a `progress` event
+//! whenever it changes, and finishes with a `done` event once the job reaches
+//! a terminal status.
+
+use std::convert::Infallible;
+use std::time::Duration;
+
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures_util::stream::Stream;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tracing::warn;
+
+use crate::db::{get_thread_archive_job, ThreadArchiveJob, ThreadArchiveJobStatus};
+
+/// How often the job row is re-read while the stream is open.
+pub const THREAD_JOB_EVENTS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Progress snapshot sent as the `data` of each event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct JobProgress {
+    status: String,
+    total_posts: Option<i64>,
+    processed_posts: i64,
+    new_links_found: i64,
+    archives_created: i64,
+    skipped_links: i64,
+    error_message: Option<String>,
+}
+
+impl From<&ThreadArchiveJob> for JobProgress {
+    fn from(job: &ThreadArchiveJob) -> Self {
+        Self {
+            status: job.status.clone(),
+            total_posts: job.total_posts,
+            processed_posts: job.processed_posts,
+            new_links_found: job.new_links_found,
+            archives_created: job.archives_created,
+            skipped_links: job.skipped_links,
+            error_message: job.error_message.clone(),
+        }
+    }
+}
+
+impl JobProgress {
+    fn is_terminal(&self) -> bool {
+        !matches!(
+            ThreadArchiveJobStatus::from_str(&self.status),
+            Some(ThreadArchiveJobStatus::Pending | ThreadArchiveJobStatus::Processing)
+        )
+    }
+
+    fn event(&self, name: &'static str) -> Event {
+        let data = serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string());
+        Event::default().event(name).data(data)
+    }
+}
+
+/// Stream progress events for a thread archive job.
+///
+/// Events emitted:
+/// - `progress` – the job row changed; `data` is the progress JSON.
+/// - `done`     – final event once the job is complete, failed or cancelled.
+/// - `error`    – the job vanished or couldn't be read; the stream ends.
+pub fn thread_job_events(
+    pool: SqlitePool,
+    job_id: i64,
+    interval: Duration,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = async_stream::stream! {
+        let mut last: Option<JobProgress> = None;
+
+        loop {
+            let progress = match get_thread_archive_job(&pool, job_id).await {
+                Ok(Some(job)) => JobProgress::from(&job),
+                Ok(None) => {
+                    yield Ok(Event::default().event("error").data("Job not found"));
+                    return;
+                }
+                Err(e) => {
+                    warn!(job_id, error = %e, "Failed to read thread archive job for SSE");
+                    yield Ok(Event::default().event("error").data("Database error"));
+                    return;
+                }
+            };
+
+            if progress.is_terminal() {
+                yield Ok(progress.event("done"));
+                return;
+            }
+
+            if last.as_ref() != Some(&progress) {
+                yield Ok(progress.event("progress"));
+                last = Some(progress);
+            }
+
+            tokio::time::sleep(interval).await;
+        }
+    };
+
+    Sse::new(stream).keep_alive(KeepAlive::new().interval(Duration::from_secs(15)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::response::IntoResponse;
+
+    use crate::db::{
+        create_user, insert_thread_archive_job, set_thread_archive_job_complete,
+        update_thread_archive_job_progress, Database, NewThreadArchiveJob,
+    };
+
+    #[tokio::test]
+    async fn test_thread_job_events_emit_done_and_close() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db = Database::new(&temp_dir.path().join("test.sqlite"))
+            .await
+            .unwrap();
+        let user_id = create_user(db.pool(), "sse_user", "hash", false)
+            .await
+            .unwrap();
+        let job_id = insert_thread_archive_job(
+            db.pool(),
+            &NewThreadArchiveJob {
+                thread_url: "https://forum.example.com/t/thread/1".to_string(),
+                rss_url: "https://forum.example.com/t/thread/1.rss".to_string(),
+                user_id,
+            },
+        )
+        .await
+        .unwrap();
+
+        // Finish the job while the stream is watching it.
+        let pool = db.pool().clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            update_thread_archive_job_progress(&pool, job_id, 3, 2, 2, 0)
+                .await
+                .unwrap();
+            set_thread_archive_job_complete(&pool, job_id)
+                .await
+                .unwrap();
+        });
+
+        let response =
+            thread_job_events(db.pool().clone(), job_id, Duration::from_millis(10)).into_response();
+        let body = tokio::time::timeout(
+            Duration::from_secs(5),
+            axum::body::to_bytes(response.into_body(), usize::MAX),
+        )
+        .await
+        .expect("stream should close after the terminal event")
+        .unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+
+        assert!(body.starts_with("event: progress\n"));
+        assert!(body.contains(r#""status":"pending""#));
+        assert!(body.trim_end().ends_with(
+            r#"data: {"status":"complete","total_posts":null,"processed_posts":3,"new_links_found":2,"archives_created":2,"skipped_links":0,"error_message":null}"#
+        ));
+        assert_eq!(body.matches("event: done").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_thread_job_events_missing_job() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db = Database::new(&temp_dir.path().join("test.sqlite"))
+            .await
+            .unwrap();
+
+        let response =
+            thread_job_events(db.pool().clone(), 999, THREAD_JOB_EVENTS_INTERVAL).into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        assert_eq!(body, "event: error\ndata: Job not found\n\n");
+    }
+}
@@ -0,0 +1,478 @@
+//! WARC export of a single archive (`GET /archive/{id}/warc`).
+//!
+//! Produces a WARC 1.0 file that web-archiving tools such as pywb and
+//! replayweb.page can load. The file starts with a `warcinfo` record followed
+//! by one `response` record per stored artifact, streamed to the client as
+//! each artifact is fetched from S3.
+//!
+//! The original HTTP exchanges aren't kept, so every response record carries
+//! a synthesized `HTTP/1.1` header built from the artifact's content type. The
+//! raw HTML is recorded under the page's own URL (with the status code seen
+//! when it was fetched); other artifacts are recorded under their `/s3/` URL
+//! on this site.
+
+use std::future::Future;
+
+use anyhow::Result;
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use chrono::{NaiveDateTime, Utc};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tokio::sync::mpsc;
+use tracing::{error, warn};
+
+use super::AppState;
+use crate::auth::MaybeUser;
+use crate::constants::ARCHIVER_HONEST_USER_AGENT;
+use crate::db::{get_archive, get_artifacts_for_archive, get_link, Archive, ArchiveArtifact, Link};
+use crate::s3::decode_stored;
+
+/// Number of finished records buffered ahead of a slow client.
+const STREAM_BUFFER_RECORDS: usize = 2;
+
+#[derive(Debug, Deserialize)]
+pub struct WarcParams {
+    /// Allow NSFW archives for unauthenticated requests.
+    #[serde(default)]
+    nsfw: bool,
+}
+
+/// Handler for `GET /archive/{id}/warc`.
+///
+/// NSFW archives require a logged-in user or `?nsfw=true`, as for the JSON API.
+pub async fn archive_warc(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Query(params): Query<WarcParams>,
+    MaybeUser(user): MaybeUser,
+) -> Response {
+    let pool = state.db.read_pool();
+    let archive = match get_archive(pool, id).await {
+        Ok(Some(a)) => a,
+        Ok(None) => return (StatusCode::NOT_FOUND, "Archive not found").into_response(),
+        Err(e) => {
+            error!(archive_id = id, error = ?e, "Failed to fetch archive for WARC export");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    if archive.is_nsfw && !(params.nsfw || user.is_some()) {
+        return (
+            StatusCode::FORBIDDEN,
+            "NSFW archive: log in or pass ?nsfw=true to download",
+        )
+            .into_response();
+    }
+
+    let link = match get_link(pool, archive.link_id).await {
+        Ok(Some(l)) => l,
+        Ok(None) => return (StatusCode::NOT_FOUND, "Link not found").into_response(),
+        Err(e) => {
+            error!(archive_id = id, error = ?e, "Failed to fetch link for WARC export");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    let artifacts: Vec<ArchiveArtifact> = match get_artifacts_for_archive(pool, id).await {
+        Ok(a) => a.into_iter().filter(|a| !a.is_internal_marker()).collect(),
+        Err(e) => {
+            error!(archive_id = id, error = ?e, "Failed to fetch artifacts for WARC export");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+        }
+    };
+
+    if artifacts.is_empty() {
+        return (StatusCode::NOT_FOUND, "Archive has no artifacts").into_response();
+    }
+
+    let filename = format!("archive-{id}.warc");
+    let source = WarcSource {
+        filename: filename.clone(),
+        public_base_url: state.config.public_base_url.clone(),
+        archive,
+        link,
+        artifacts,
+    };
+
+    let (tx, mut rx) = mpsc::channel(STREAM_BUFFER_RECORDS);
+    let s3 = state.s3.clone();
+    tokio::spawn(stream_warc(
+        source,
+        move |key: String| {
+            let s3 = s3.clone();
+            async move { s3.download_file(&key).await }
+        },
+        tx,
+    ));
+
+    let stream = futures_util::stream::poll_fn(move |cx| rx.poll_recv(cx));
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/warc"),
+            (
+                header::CONTENT_DISPOSITION,
+                &format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response()
+}
+
+/// Everything needed to write one archive's WARC file.
+struct WarcSource {
+    filename: String,
+    public_base_url: String,
+    archive: Archive,
+    link: Link,
+    artifacts: Vec<ArchiveArtifact>,
+}
+
+/// Write the `warcinfo` record, then a `response` record per artifact.
+///
+/// `fetch` returns an S3 object's bytes and content type. Artifacts that
+/// can't be fetched are left out so the file stays valid.
+async fn stream_warc<F, Fut>(
+    source: WarcSource,
+    fetch: F,
+    tx: mpsc::Sender<std::io::Result<Vec<u8>>>,
+) where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<(Vec<u8>, String)>>,
+{
+    let now = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    if tx.send(Ok(warcinfo_record(&source, &now))).await.is_err() {
+        return;
+    }
+
+    for artifact in &source.artifacts {
+        let (bytes, s3_content_type) = match fetch(artifact.s3_key.clone()).await {
+            Ok(object) => object,
+            Err(e) => {
+                warn!(
+                    archive_id = source.archive.id,
+                    s3_key = %artifact.s3_key,
+                    error = ?e,
+                    "Skipping artifact in WARC export"
+                );
+                continue;
+            }
+        };
+        let payload = match decode_stored(bytes, artifact.content_encoding.as_deref()) {
+            Ok(payload) => payload,
+            Err(e) => {
+                warn!(
+                    archive_id = source.archive.id,
+                    s3_key = %artifact.s3_key,
+                    error = ?e,
+                    "Skipping undecodable artifact in WARC export"
+                );
+                continue;
+            }
+        };
+
+        let is_page = artifact.kind == "raw_html";
+        let target_uri = if is_page {
+            page_url(&source.link).to_string()
+        } else {
+            format!(
+                "{}/s3/{}",
+                source.public_base_url.trim_end_matches('/'),
+                artifact.s3_key
+            )
+        };
+        let status = is_page
+            .then_some(source.archive.http_status_code)
+            .flatten()
+            .and_then(|code| u16::try_from(code).ok())
+            .unwrap_or(200);
+        let content_type = artifact.content_type.as_deref().unwrap_or(&s3_content_type);
+
+        let record = response_record(
+            &target_uri,
+            &warc_date(&artifact.created_at),
+            status,
+            content_type,
+            &payload,
+        );
+        if tx.send(Ok(record)).await.is_err() {
+            // Client went away
+            return;
+        }
+    }
+}
+
+/// The URL the page was archived from, after redirects if known.
+fn page_url(link: &Link) -> &str {
+    link.final_url.as_deref().unwrap_or(&link.original_url)
+}
+
+/// A `warcinfo` record describing this export.
+fn warcinfo_record(source: &WarcSource, date: &str) -> Vec<u8> {
+    let fields = format!(
+        "software: {ARCHIVER_HONEST_USER_AGENT}\r\n\
+         format: WARC File Format 1.0\r\n\
+         description: Archive {} of {}\r\n\
+         isPartOf: {}/archive/{}\r\n",
+        source.archive.id,
+        page_url(&source.link),
+        source.public_base_url.trim_end_matches('/'),
+        source.archive.id,
+    );
+    warc_record(
+        &[
+            ("WARC-Type", "warcinfo"),
+            ("WARC-Date", date),
+            ("WARC-Filename", &source.filename),
+            (
+                "WARC-Record-ID",
+                &record_id(&format!("warcinfo:{}", source.archive.id)),
+            ),
+            ("Content-Type", "application/warc-fields"),
+        ],
+        fields.as_bytes(),
+    )
+}
+
+/// A `response` record for `payload`, with a synthesized HTTP header.
+fn response_record(
+    target_uri: &str,
+    date: &str,
+    status: u16,
+    content_type: &str,
+    payload: &[u8],
+) -> Vec<u8> {
+    let reason = StatusCode::from_u16(status)
+        .ok()
+        .and_then(|s| s.canonical_reason())
+        .unwrap_or("");
+    let mut block = format!(
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\r\n",
+        payload.len()
+    )
+    .into_bytes();
+    block.extend_from_slice(payload);
+
+    warc_record(
+        &[
+            ("WARC-Type", "response"),
+            ("WARC-Target-URI", target_uri),
+            ("WARC-Date", date),
+            (
+                "WARC-Record-ID",
+                &record_id(&format!("response:{target_uri}:{date}")),
+            ),
+            ("Content-Type", "application/http; msgtype=response"),
+        ],
+        &block,
+    )
+}
+
+/// Serialize a record: version line, named fields, `Content-Length`, block.
+fn warc_record(fields: &[(&str, &str)], block: &[u8]) -> Vec<u8> {
+    let mut record = String::from("WARC/1.0\r\n");
+    for (name, value) in fields {
+        record.push_str(&format!("{name}: {value}\r\n"));
+    }
+    record.push_str(&format!("Content-Length: {}\r\n\r\n", block.len()));
+
+    let mut record = record.into_bytes();
+    record.extend_from_slice(block);
+    record.extend_from_slice(b"\r\n\r\n");
+    record
+}
+
+/// A stable `<urn:uuid:...>` record ID derived from `seed`.
+///
+/// Uses the UUIDv8 (custom) layout over a SHA-256 of the seed, so exporting
+/// the same archive twice gives the same IDs.
+fn record_id(seed: &str) -> String {
+    let mut bytes: [u8; 16] = Sha256::digest(seed.as_bytes())[..16]
+        .try_into()
+        .expect("SHA-256 digest has at least 16 bytes");
+    bytes[6] = (bytes[6] & 0x0f) | 0x80;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = hex::encode(bytes);
+    format!(
+        "<urn:uuid:{}-{}-{}-{}-{}>",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// A stored `YYYY-MM-DD HH:MM:SS` (UTC) timestamp in WARC's ISO 8601 form.
+fn warc_date(timestamp: &str) -> String {
+    NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S")
+        .unwrap_or_else(|_| Utc::now().naive_utc())
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source() -> WarcSource {
+        let archive = Archive {
+            id: 7,
+            link_id: 3,
+            status: "complete".to_string(),
+            archived_at: Some("2024-01-15 12:00:10".to_string()),
+            content_title: None,
+            content_author: None,
+            content_text: None,
+            content_type: Some("text".to_string()),
+            s3_key_primary: None,
+            s3_key_thumb: None,
+            s3_keys_extra: None,
+            wayback_url: None,
+            archive_today_url: None,
+            ipfs_cid: None,
+            error_message: None,
+            retry_count: 0,
+            created_at: "2024-01-15 12:00:00".to_string(),
+            is_nsfw: false,
+            nsfw_source: None,
+            next_retry_at: None,
+            last_attempt_at: None,
+            http_status_code: Some(200),
+            redirect_chain: None,
+            post_date: None,
+            quoted_archive_id: None,
+            reply_to_archive_id: None,
+            submitted_by_user_id: None,
+            progress_percent: None,
+            progress_details: None,
+            last_progress_update: None,
+            og_title: None,
+            og_description: None,
+            og_image: None,
+            og_type: None,
+            og_extracted_at: None,
+            og_extraction_attempted: false,
+            transcript_text: None,
+            full_text: None,
+            view_count: None,
+            like_count: None,
+            repost_count: None,
+            platform_comment_count: None,
+            save_count: None,
+            metrics_backfill_version: None,
+            short_code: None,
+            error_kind: None,
+            skip_reason: None,
+        };
+        let link = Link {
+            id: 3,
+            original_url: "https://example.com/post".to_string(),
+            normalized_url: "https://example.com/post".to_string(),
+            canonical_url: None,
+            final_url: None,
+            domain: "example.com".to_string(),
+            first_seen_at: "2024-01-15 12:00:00".to_string(),
+            last_archived_at: None,
+        };
+        let artifact = |id: i64, kind: &str, s3_key: &str, content_type: &str| ArchiveArtifact {
+            id,
+            archive_id: 7,
+            kind: kind.to_string(),
+            s3_key: s3_key.to_string(),
+            content_type: Some(content_type.to_string()),
+            size_bytes: None,
+            sha256: None,
+            created_at: "2024-01-15 12:00:05".to_string(),
+            perceptual_hash: None,
+            duplicate_of_artifact_id: None,
+            video_file_id: None,
+            metadata: None,
+            placeholder_color: None,
+            width: None,
+            height: None,
+            content_encoding: None,
+        };
+        WarcSource {
+            filename: "archive-7.warc".to_string(),
+            public_base_url: "https://archiver.example/".to_string(),
+            archive,
+            link,
+            artifacts: vec![
+                artifact(1, "raw_html", "archives/7/raw.html", "text/html"),
+                artifact(2, "screenshot", "archives/7/screenshot.webp", "image/webp"),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_record_id_is_stable_uuid() {
+        let id = record_id("warcinfo:7");
+        assert_eq!(id, record_id("warcinfo:7"));
+        assert_ne!(id, record_id("warcinfo:8"));
+        assert!(id.starts_with("<urn:uuid:") && id.ends_with('>'));
+        assert_eq!(id.len(), "<urn:uuid:>".len() + 36);
+        assert_eq!(&id[24..25], "8");
+    }
+
+    #[test]
+    fn test_warc_date() {
+        assert_eq!(warc_date("2024-01-15 12:00:05"), "2024-01-15T12:00:05Z");
+    }
+
+    #[tokio::test]
+    async fn test_stream_warc_writes_record_per_artifact() {
+        let (tx, mut rx) = mpsc::channel(8);
+        stream_warc(
+            source(),
+            |key: String| async move { Ok((format!("body of {key}").into_bytes(), String::new())) },
+            tx,
+        )
+        .await;
+
+        let mut output = Vec::new();
+        while let Some(chunk) = rx.recv().await {
+            output.extend(chunk.unwrap());
+        }
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.starts_with("WARC/1.0\r\nWARC-Type: warcinfo\r\n"));
+        assert_eq!(output.matches("WARC/1.0\r\n").count(), 3);
+        assert_eq!(output.matches("WARC-Type: response\r\n").count(), 2);
+        assert!(output.contains("WARC-Target-URI: https://example.com/post\r\n"));
+        assert!(output.contains(
+            "WARC-Target-URI: https://archiver.example/s3/archives/7/screenshot.webp\r\n"
+        ));
+        assert!(output.contains("WARC-Date: 2024-01-15T12:00:05Z\r\n"));
+        assert!(output.contains(
+            "HTTP/1.1 200 OK\r\nContent-Type: image/webp\r\nContent-Length: 34\r\n\r\nbody of archives/7/screenshot.webp"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stream_warc_skips_unfetchable_artifacts() {
+        let (tx, mut rx) = mpsc::channel(8);
+        stream_warc(
+            source(),
+            |key: String| async move {
+                if key.ends_with("raw.html") {
+                    anyhow::bail!("missing")
+                }
+                Ok((b"img".to_vec(), "image/webp".to_string()))
+            },
+            tx,
+        )
+        .await;
+
+        let mut records = 0;
+        while let Some(chunk) = rx.recv().await {
+            assert!(chunk.unwrap().starts_with(b"WARC/1.0\r\n"));
+            records += 1;
+        }
+        assert_eq!(records, 2);
+    }
+}
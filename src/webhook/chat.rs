@@ -0,0 +1,612 @@
+//! Discord/Slack notifications for newly completed archives.
+//!
+//! Completed archives are queued and posted to a chat incoming webhook as a
+//! formatted message (title, thumbnail, source domain, link to the archive
+//! page). Archives that complete within the batch window are folded into a
+//! single summary message so a burst doesn't flood the channel. Rate limit
+//! responses (HTTP 429 with `Retry-After`, Discord's `X-RateLimit-*` headers)
+//! pause delivery instead of dropping messages.
+
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use crate::config::{ChatFormat, Config};
+use crate::db::{Archive, Link};
+
+/// Maximum number of archives waiting to be posted.
+const QUEUE_CAPACITY: usize = 256;
+/// Maximum number of archives folded into one message.
+const MAX_BATCH_ITEMS: usize = 100;
+/// Archives listed individually in a summary message.
+const MAX_SUMMARY_LINES: usize = 10;
+/// Delivery attempts per message before giving up.
+const MAX_ATTEMPTS: u32 = 4;
+/// Delay before retrying a failed (non rate-limited) delivery; doubles each time.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(2);
+/// Longest rate-limit pause we honour, in case a server sends something absurd.
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(300);
+/// Timeout for a single delivery request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Discord embed title limit.
+const DISCORD_TITLE_MAX: usize = 256;
+/// Discord embed description limit.
+const DISCORD_DESCRIPTION_MAX: usize = 4096;
+/// Slack section text limit.
+const SLACK_TEXT_MAX: usize = 3000;
+
+/// One completed archive, ready to be posted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatItem {
+    pub archive_id: i64,
+    pub title: String,
+    pub domain: String,
+    pub source_url: String,
+    pub archive_url: String,
+    pub thumbnail_url: Option<String>,
+}
+
+impl ChatItem {
+    /// Build an item from an archive and its link.
+    ///
+    /// Archive page and thumbnail URLs are absolute, rooted at `public_base_url`.
+    #[must_use]
+    pub fn from_archive(archive: &Archive, link: &Link, public_base_url: &str) -> Self {
+        let base = public_base_url.trim_end_matches('/');
+        let title = archive
+            .content_title
+            .as_deref()
+            .or(archive.og_title.as_deref())
+            .filter(|t| !t.trim().is_empty())
+            .unwrap_or(&link.normalized_url)
+            .to_string();
+
+        Self {
+            archive_id: archive.id,
+            title,
+            domain: link.domain.clone(),
+            source_url: link.original_url.clone(),
+            archive_url: format!("{base}/archive/{}", archive.id),
+            thumbnail_url: archive
+                .s3_key_thumb
+                .as_deref()
+                .map(|key| format!("{base}/s3/{key}")),
+        }
+    }
+}
+
+/// Build the JSON body for a batch in the given chat format.
+#[must_use]
+pub fn format_message(format: ChatFormat, items: &[ChatItem]) -> Value {
+    match format {
+        ChatFormat::Discord => discord_message(items),
+        ChatFormat::Slack => slack_message(items),
+    }
+}
+
+/// Discord webhook body: a rich embed for one archive, or a summary embed
+/// listing the batch.
+#[must_use]
+pub fn discord_message(items: &[ChatItem]) -> Value {
+    if let [item] = items {
+        let mut embed = json!({
+            "title": truncate(&item.title, DISCORD_TITLE_MAX),
+            "url": item.archive_url,
+            "description": format!(
+                "Archived from [{}]({})",
+                escape_discord(&item.domain),
+                item.source_url
+            ),
+            "footer": { "text": format!("Archive #{}", item.archive_id) },
+        });
+        if let Some(thumb) = &item.thumbnail_url {
+            embed["thumbnail"] = json!({ "url": thumb });
+        }
+        return json!({ "embeds": [embed] });
+    }
+
+    let lines = summary_lines(items, |item| {
+        format!(
+            "• [{}]({}) — {}",
+            escape_discord(&truncate(&item.title, 100)),
+            item.archive_url,
+            escape_discord(&item.domain)
+        )
+    });
+    json!({
+        "embeds": [{
+            "title": summary_title(items.len()),
+            "description": truncate(&lines, DISCORD_DESCRIPTION_MAX),
+        }]
+    })
+}
+
+/// Slack webhook body: a section with thumbnail accessory for one archive,
+/// or a summary section listing the batch.
+#[must_use]
+pub fn slack_message(items: &[ChatItem]) -> Value {
+    if let [item] = items {
+        let text = format!(
+            "*<{}|{}>*\nArchived from <{}|{}>",
+            item.archive_url,
+            escape_slack(&truncate(&item.title, 200)),
+            item.source_url,
+            escape_slack(&item.domain)
+        );
+        let mut section = json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": truncate(&text, SLACK_TEXT_MAX) },
+        });
+        if let Some(thumb) = &item.thumbnail_url {
+            section["accessory"] = json!({
+                "type": "image",
+                "image_url": thumb,
+                "alt_text": truncate(&item.title, 200),
+            });
+        }
+        return json!({
+            "text": format!("New archive: {}", item.title),
+            "blocks": [section],
+        });
+    }
+
+    let title = summary_title(items.len());
+    let lines = summary_lines(items, |item| {
+        format!(
+            "• <{}|{}> — {}",
+            item.archive_url,
+            escape_slack(&truncate(&item.title, 100)),
+            escape_slack(&item.domain)
+        )
+    });
+    json!({
+        "text": title,
+        "blocks": [{
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": truncate(&format!("*{title}*\n{lines}"), SLACK_TEXT_MAX),
+            },
+        }],
+    })
+}
+
+fn summary_title(count: usize) -> String {
+    format!("{count} new archives")
+}
+
+/// One line per archive (up to [`MAX_SUMMARY_LINES`]) plus an overflow note.
+fn summary_lines(items: &[ChatItem], line: impl Fn(&ChatItem) -> String) -> String {
+    let mut out = items
+        .iter()
+        .take(MAX_SUMMARY_LINES)
+        .map(line)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if items.len() > MAX_SUMMARY_LINES {
+        let _ = write!(out, "\n…and {} more", items.len() - MAX_SUMMARY_LINES);
+    }
+    out
+}
+
+/// Truncate to at most `max` characters, marking the cut with an ellipsis.
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut out: String = text.chars().take(max.saturating_sub(1)).collect();
+    out.push('…');
+    out
+}
+
+/// Escape characters that would break a Discord markdown link label.
+fn escape_discord(text: &str) -> String {
+    text.replace('[', "\\[").replace(']', "\\]")
+}
+
+/// Escape the characters Slack treats as control sequences in mrkdwn.
+fn escape_slack(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Wait for the next item, then collect everything that arrives within
+/// `window` (up to `max` items).
+///
+/// Returns `None` once the channel is closed and drained.
+pub async fn next_batch(
+    receiver: &mut mpsc::Receiver<ChatItem>,
+    window: Duration,
+    max: usize,
+) -> Option<Vec<ChatItem>> {
+    let first = receiver.recv().await?;
+    let mut batch = vec![first];
+    let deadline = tokio::time::Instant::now() + window;
+    while batch.len() < max {
+        match tokio::time::timeout_at(deadline, receiver.recv()).await {
+            Ok(Some(item)) => batch.push(item),
+            Ok(None) | Err(_) => break,
+        }
+    }
+    Some(batch)
+}
+
+/// How long a response asks us to wait before sending again, if at all.
+///
+/// Uses `Retry-After` (seconds) on 429 responses, and Discord's
+/// `X-RateLimit-Reset-After` when the bucket is exhausted.
+#[must_use]
+pub fn rate_limit_wait(status: StatusCode, headers: &HeaderMap) -> Option<Duration> {
+    let seconds = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .filter(|s| s.is_finite() && *s >= 0.0)
+            .map(|s| Duration::from_secs_f64(s).min(MAX_RATE_LIMIT_WAIT))
+    };
+
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        return Some(
+            seconds("retry-after")
+                .or_else(|| seconds("x-ratelimit-reset-after"))
+                .unwrap_or(INITIAL_BACKOFF),
+        );
+    }
+    let exhausted = headers
+        .get("x-ratelimit-remaining")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.trim() == "0");
+    if exhausted {
+        return seconds("x-ratelimit-reset-after");
+    }
+    None
+}
+
+/// Handle for queueing chat notifications.
+#[derive(Clone, Default)]
+pub struct ChatNotifier {
+    sender: Option<mpsc::Sender<ChatItem>>,
+    public_base_url: String,
+    notify_feed: bool,
+    notify_submissions: bool,
+}
+
+impl ChatNotifier {
+    /// A notifier that never posts anything.
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Create a notifier from configuration.
+    ///
+    /// Spawns the background delivery task when `chat_webhook_url` is set, so
+    /// this must be called from within a Tokio runtime.
+    #[must_use]
+    pub fn new(config: &Config) -> Self {
+        let Some(url) = config.chat_webhook_url.clone() else {
+            return Self::disabled();
+        };
+        let (sender, receiver) = mpsc::channel(QUEUE_CAPACITY);
+        let delivery = ChatDelivery {
+            http: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .expect("Failed to create HTTP client"),
+            url,
+            format: config.chat_webhook_format,
+            window: Duration::from_secs(config.chat_batch_window_secs),
+        };
+        tokio::spawn(delivery.run(receiver));
+
+        Self {
+            sender: Some(sender),
+            public_base_url: config.public_base_url.clone(),
+            notify_feed: config.chat_notify_feed,
+            notify_submissions: config.chat_notify_submissions,
+        }
+    }
+
+    /// Whether any archives will be posted.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.sender.is_some() && (self.notify_feed || self.notify_submissions)
+    }
+
+    /// Whether this archive's source is toggled on: the RSS feed, or manual
+    /// submissions (archives with a submitting user).
+    #[must_use]
+    pub fn wants(&self, archive: &Archive) -> bool {
+        if archive.submitted_by_user_id.is_some() {
+            self.notify_submissions
+        } else {
+            self.notify_feed
+        }
+    }
+
+    /// Queue a completed archive without waiting for delivery.
+    pub fn notify_archive(&self, archive: &Archive, link: &Link) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+        if !self.wants(archive) {
+            return;
+        }
+        let item = ChatItem::from_archive(archive, link, &self.public_base_url);
+        if sender.try_send(item).is_err() {
+            warn!(
+                archive_id = archive.id,
+                "Chat notification queue full or closed, dropping notification"
+            );
+        }
+    }
+}
+
+/// Background task state for posting batched chat messages.
+struct ChatDelivery {
+    http: reqwest::Client,
+    url: String,
+    format: ChatFormat,
+    window: Duration,
+}
+
+impl ChatDelivery {
+    async fn run(self, mut receiver: mpsc::Receiver<ChatItem>) {
+        while let Some(batch) = next_batch(&mut receiver, self.window, MAX_BATCH_ITEMS).await {
+            self.deliver(&batch).await;
+        }
+    }
+
+    async fn deliver(&self, batch: &[ChatItem]) {
+        let body = format_message(self.format, batch);
+        let mut backoff = INITIAL_BACKOFF;
+        for attempt in 1..=MAX_ATTEMPTS {
+            match self.send(&body).await {
+                Ok(wait) => {
+                    debug!(archives = batch.len(), attempt, "Chat notification posted");
+                    // Bucket exhausted: hold off before the next batch
+                    if let Some(wait) = wait {
+                        tokio::time::sleep(wait).await;
+                    }
+                    return;
+                }
+                Err(SendError::RateLimited(wait)) => {
+                    debug!(wait_secs = wait.as_secs_f64(), "Chat webhook rate limited");
+                    tokio::time::sleep(wait).await;
+                }
+                Err(SendError::Failed(e)) if attempt < MAX_ATTEMPTS => {
+                    debug!(attempt, "Chat notification failed, retrying: {e:#}");
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                Err(SendError::Failed(e)) => {
+                    warn!(archives = batch.len(), "Chat notification failed: {e:#}");
+                    return;
+                }
+            }
+        }
+        warn!(
+            archives = batch.len(),
+            "Chat notification dropped after {MAX_ATTEMPTS} attempts"
+        );
+    }
+
+    /// Post once; on success returns any pause the server asked for.
+    async fn send(&self, body: &Value) -> Result<Option<Duration>, SendError> {
+        let response = self
+            .http
+            .post(&self.url)
+            .json(body)
+            .send()
+            .await
+            .context("Failed to send chat webhook request")
+            .map_err(SendError::Failed)?;
+        let status = response.status();
+        let wait = rate_limit_wait(status, response.headers());
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(SendError::RateLimited(wait.unwrap_or(INITIAL_BACKOFF)));
+        }
+        if !status.is_success() {
+            return Err(SendError::Failed(anyhow::anyhow!(
+                "Chat webhook returned {status}"
+            )));
+        }
+        Ok(wait)
+    }
+}
+
+enum SendError {
+    RateLimited(Duration),
+    Failed(anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn item(id: i64, title: &str) -> ChatItem {
+        ChatItem {
+            archive_id: id,
+            title: title.to_string(),
+            domain: "example.com".to_string(),
+            source_url: format!("https://example.com/{id}"),
+            archive_url: format!("https://archiver.test/archive/{id}"),
+            thumbnail_url: Some(format!("https://archiver.test/s3/archives/{id}/thumb.jpg")),
+        }
+    }
+
+    #[test]
+    fn test_discord_single_embed() {
+        let message = discord_message(&[item(5, "A [great] video")]);
+        assert_eq!(
+            message,
+            json!({
+                "embeds": [{
+                    "title": "A [great] video",
+                    "url": "https://archiver.test/archive/5",
+                    "description": "Archived from [example.com](https://example.com/5)",
+                    "footer": { "text": "Archive #5" },
+                    "thumbnail": { "url": "https://archiver.test/s3/archives/5/thumb.jpg" },
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_discord_single_without_thumbnail() {
+        let mut it = item(5, "Article");
+        it.thumbnail_url = None;
+        let message = discord_message(&[it]);
+        assert!(message["embeds"][0].get("thumbnail").is_none());
+    }
+
+    #[test]
+    fn test_discord_title_truncated() {
+        let long = "x".repeat(300);
+        let message = discord_message(&[item(1, &long)]);
+        let title = message["embeds"][0]["title"].as_str().unwrap();
+        assert_eq!(title.chars().count(), DISCORD_TITLE_MAX);
+        assert!(title.ends_with('…'));
+    }
+
+    #[test]
+    fn test_discord_summary_embed() {
+        let message = discord_message(&[item(1, "First [one]"), item(2, "Second")]);
+        assert_eq!(
+            message,
+            json!({
+                "embeds": [{
+                    "title": "2 new archives",
+                    "description": "• [First \\[one\\]](https://archiver.test/archive/1) — example.com\n\
+                                    • [Second](https://archiver.test/archive/2) — example.com",
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_summary_overflow_note() {
+        let items: Vec<ChatItem> = (1..=13).map(|i| item(i, "Item")).collect();
+        let message = discord_message(&items);
+        let description = message["embeds"][0]["description"].as_str().unwrap();
+        assert_eq!(description.lines().count(), MAX_SUMMARY_LINES + 1);
+        assert!(description.ends_with("…and 3 more"));
+        assert_eq!(message["embeds"][0]["title"], "13 new archives");
+    }
+
+    #[test]
+    fn test_slack_single_message() {
+        let message = slack_message(&[item(5, "Q&A <live>")]);
+        assert_eq!(message["text"], "New archive: Q&A <live>");
+        let section = &message["blocks"][0];
+        assert_eq!(
+            section["text"]["text"],
+            "*<https://archiver.test/archive/5|Q&amp;A &lt;live&gt;>*\nArchived from <https://example.com/5|example.com>"
+        );
+        assert_eq!(
+            section["accessory"]["image_url"],
+            "https://archiver.test/s3/archives/5/thumb.jpg"
+        );
+    }
+
+    #[test]
+    fn test_slack_summary_message() {
+        let message = slack_message(&[item(1, "One"), item(2, "Two")]);
+        assert_eq!(message["text"], "2 new archives");
+        assert_eq!(
+            message["blocks"][0]["text"]["text"],
+            "*2 new archives*\n• <https://archiver.test/archive/1|One> — example.com\n\
+             • <https://archiver.test/archive/2|Two> — example.com"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_next_batch_collects_burst() {
+        let (tx, mut rx) = mpsc::channel(16);
+        for i in 1..=3 {
+            tx.send(item(i, "Burst")).await.unwrap();
+        }
+        let batch = next_batch(&mut rx, Duration::from_millis(50), 10)
+            .await
+            .unwrap();
+        assert_eq!(batch.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_next_batch_respects_window() {
+        let (tx, mut rx) = mpsc::channel(16);
+        tx.send(item(1, "Early")).await.unwrap();
+        let late = tx.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            late.send(item(2, "Late")).await.unwrap();
+        });
+
+        let first = next_batch(&mut rx, Duration::from_millis(50), 10)
+            .await
+            .unwrap();
+        assert_eq!(first, vec![item(1, "Early")]);
+
+        let second = next_batch(&mut rx, Duration::from_millis(50), 10)
+            .await
+            .unwrap();
+        assert_eq!(second, vec![item(2, "Late")]);
+    }
+
+    #[tokio::test]
+    async fn test_next_batch_caps_size_and_ends_on_close() {
+        let (tx, mut rx) = mpsc::channel(16);
+        for i in 1..=5 {
+            tx.send(item(i, "Item")).await.unwrap();
+        }
+        drop(tx);
+
+        let batch = next_batch(&mut rx, Duration::from_secs(5), 3)
+            .await
+            .unwrap();
+        assert_eq!(batch.len(), 3);
+        let rest = next_batch(&mut rx, Duration::from_secs(5), 3)
+            .await
+            .unwrap();
+        assert_eq!(rest.len(), 2);
+        assert!(next_batch(&mut rx, Duration::from_secs(5), 3)
+            .await
+            .is_none());
+    }
+
+    #[test]
+    fn test_rate_limit_wait() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(rate_limit_wait(StatusCode::OK, &headers), None);
+        assert_eq!(
+            rate_limit_wait(StatusCode::TOO_MANY_REQUESTS, &headers),
+            Some(INITIAL_BACKOFF)
+        );
+
+        headers.insert("retry-after", HeaderValue::from_static("7"));
+        assert_eq!(
+            rate_limit_wait(StatusCode::TOO_MANY_REQUESTS, &headers),
+            Some(Duration::from_secs(7))
+        );
+
+        let mut discord = HeaderMap::new();
+        discord.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        discord.insert("x-ratelimit-reset-after", HeaderValue::from_static("1.5"));
+        assert_eq!(
+            rate_limit_wait(StatusCode::NO_CONTENT, &discord),
+            Some(Duration::from_millis(1500))
+        );
+        discord.insert("x-ratelimit-remaining", HeaderValue::from_static("4"));
+        assert_eq!(rate_limit_wait(StatusCode::NO_CONTENT, &discord), None);
+    }
+}
@@ -0,0 +1,547 @@
+//! Webhook notifications for archive state changes.
+//!
+//! When `WEBHOOK_URL` is set, the archive worker POSTs a JSON payload each
+//! time an archive completes or fails. Deliveries go through a bounded queue
+//! drained by a background task, so a slow or unreachable endpoint never
+//! stalls archiving; when the queue is full the event is dropped and logged.
+//!
+//! If `WEBHOOK_SECRET` is set, each request carries an
+//! `X-Webhook-Signature: sha256=<hex>` header holding the HMAC-SHA256 of the
+//! raw request body.
+//!
+//! [`chat`] builds on the same completion hook to post formatted messages to
+//! Discord or Slack.
+
+pub mod chat;
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use crate::config::Config;
+use crate::db::{Archive, ArchiveArtifact};
+
+/// Header carrying the HMAC-SHA256 signature of the body.
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+/// Header carrying the event name, so receivers can route without parsing.
+pub const EVENT_HEADER: &str = "X-Webhook-Event";
+
+/// Maximum number of notifications waiting for delivery.
+const QUEUE_CAPACITY: usize = 256;
+/// Delivery attempts per notification before giving up.
+const MAX_ATTEMPTS: u32 = 4;
+/// Delay before the first retry; doubles after each failed attempt.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(2);
+/// Timeout for a single delivery request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Archive events that can be delivered to a webhook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// The archive finished successfully.
+    ArchiveComplete,
+    /// An archive attempt failed (including permanent and auth-required failures).
+    ArchiveFailed,
+}
+
+impl WebhookEvent {
+    /// Every event, in the order used when no subscription list is configured.
+    pub const ALL: [Self; 2] = [Self::ArchiveComplete, Self::ArchiveFailed];
+
+    /// Event name as it appears in payloads and `WEBHOOK_EVENTS`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::ArchiveComplete => "archive_complete",
+            Self::ArchiveFailed => "archive_failed",
+        }
+    }
+
+    /// Parse an event name from configuration.
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.as_str() == name)
+    }
+}
+
+/// JSON body sent to the webhook endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WebhookPayload {
+    pub event: WebhookEvent,
+    pub archive_id: i64,
+    pub url: String,
+    pub status: String,
+    pub content_type: Option<String>,
+    pub s3_keys: Vec<String>,
+    pub ipfs_cid: Option<String>,
+}
+
+impl WebhookPayload {
+    /// Build a payload from an archive, its link URL and its artifacts.
+    ///
+    /// `s3_keys` lists the primary and thumbnail keys first, followed by any
+    /// other artifact keys, without duplicates.
+    #[must_use]
+    pub fn from_archive(
+        event: WebhookEvent,
+        archive: &Archive,
+        url: &str,
+        artifacts: &[ArchiveArtifact],
+    ) -> Self {
+        let mut s3_keys: Vec<String> = Vec::new();
+        let candidates = archive
+            .s3_key_primary
+            .iter()
+            .chain(archive.s3_key_thumb.iter())
+            .chain(artifacts.iter().map(|a| &a.s3_key));
+        for key in candidates {
+            if !s3_keys.contains(key) {
+                s3_keys.push(key.clone());
+            }
+        }
+
+        Self {
+            event,
+            archive_id: archive.id,
+            url: url.to_string(),
+            status: archive.status.clone(),
+            content_type: archive.content_type.clone(),
+            s3_keys,
+            ipfs_cid: archive.ipfs_cid.clone(),
+        }
+    }
+}
+
+/// Compute the signature header value for a request body.
+///
+/// Returns `sha256=<hex>` where `<hex>` is the HMAC-SHA256 of `body` keyed
+/// with `secret`.
+#[must_use]
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Handle for queueing webhook notifications.
+///
+/// Cloning is cheap; all clones feed the same delivery task.
+#[derive(Clone, Default)]
+pub struct WebhookNotifier {
+    sender: Option<mpsc::Sender<WebhookPayload>>,
+    events: Vec<WebhookEvent>,
+}
+
+impl WebhookNotifier {
+    /// A notifier that never sends anything.
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Create a notifier from configuration.
+    ///
+    /// Spawns the background delivery task when `webhook_url` is set, so this
+    /// must be called from within a Tokio runtime.
+    #[must_use]
+    pub fn new(config: &Config) -> Self {
+        let Some(url) = config.webhook_url.clone() else {
+            return Self::disabled();
+        };
+        let events = config
+            .webhook_events
+            .iter()
+            .filter_map(|name| WebhookEvent::parse(name))
+            .collect();
+        Self::spawn(url, config.webhook_secret.clone(), events, QUEUE_CAPACITY)
+    }
+
+    fn spawn(
+        url: String,
+        secret: Option<String>,
+        events: Vec<WebhookEvent>,
+        capacity: usize,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel(capacity);
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("Failed to create HTTP client");
+        let delivery = Delivery { http, url, secret };
+        tokio::spawn(delivery.run(receiver));
+
+        Self {
+            sender: Some(sender),
+            events,
+        }
+    }
+
+    /// Whether notifications for `event` will be delivered.
+    #[must_use]
+    pub fn is_subscribed(&self, event: WebhookEvent) -> bool {
+        self.sender.is_some() && self.events.contains(&event)
+    }
+
+    /// Queue a notification without waiting for delivery.
+    ///
+    /// Unsubscribed events are ignored; if the queue is full the notification
+    /// is dropped with a warning rather than blocking the caller.
+    pub fn notify(&self, payload: WebhookPayload) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+        if !self.events.contains(&payload.event) {
+            return;
+        }
+        let archive_id = payload.archive_id;
+        match sender.try_send(payload) {
+            Ok(()) => {}
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                warn!(archive_id, "Webhook queue full, dropping notification");
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                warn!(
+                    archive_id,
+                    "Webhook delivery task stopped, dropping notification"
+                );
+            }
+        }
+    }
+}
+
+/// Background task state for delivering queued notifications.
+struct Delivery {
+    http: reqwest::Client,
+    url: String,
+    secret: Option<String>,
+}
+
+impl Delivery {
+    async fn run(self, mut receiver: mpsc::Receiver<WebhookPayload>) {
+        while let Some(payload) = receiver.recv().await {
+            self.deliver(&payload).await;
+        }
+    }
+
+    /// Deliver one notification, retrying with exponential backoff.
+    async fn deliver(&self, payload: &WebhookPayload) {
+        let body = match serde_json::to_vec(payload) {
+            Ok(body) => body,
+            Err(e) => {
+                warn!(
+                    archive_id = payload.archive_id,
+                    "Failed to serialize webhook payload: {e}"
+                );
+                return;
+            }
+        };
+
+        let mut backoff = INITIAL_BACKOFF;
+        for attempt in 1..=MAX_ATTEMPTS {
+            match self.send(payload.event, &body).await {
+                Ok(()) => {
+                    debug!(
+                        archive_id = payload.archive_id,
+                        event = payload.event.as_str(),
+                        attempt,
+                        "Webhook delivered"
+                    );
+                    return;
+                }
+                Err(e) if attempt < MAX_ATTEMPTS => {
+                    debug!(
+                        archive_id = payload.archive_id,
+                        attempt, "Webhook delivery failed, retrying: {e:#}"
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                Err(e) => {
+                    warn!(
+                        archive_id = payload.archive_id,
+                        event = payload.event.as_str(),
+                        "Webhook delivery failed after {MAX_ATTEMPTS} attempts: {e:#}"
+                    );
+                }
+            }
+        }
+    }
+
+    async fn send(&self, event: WebhookEvent, body: &[u8]) -> Result<()> {
+        let mut request = self
+            .http
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, event.as_str())
+            .body(body.to_vec());
+        if let Some(secret) = &self.secret {
+            request = request.header(SIGNATURE_HEADER, sign_payload(secret, body));
+        }
+
+        let response = request
+            .send()
+            .await
+            .context("Failed to send webhook request")?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("Webhook endpoint returned {status}");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Bytes, http::HeaderMap, routing::post, Router};
+    use serde_json::json;
+
+    fn archive(id: i64, status: &str) -> Archive {
+        Archive {
+            id,
+            link_id: 7,
+            status: status.to_string(),
+            archived_at: None,
+            content_title: None,
+            content_author: None,
+            content_text: None,
+            content_type: None,
+            s3_key_primary: None,
+            s3_key_thumb: None,
+            s3_keys_extra: None,
+            wayback_url: None,
+            archive_today_url: None,
+            ipfs_cid: None,
+            error_message: None,
+            retry_count: 0,
+            created_at: "2024-01-15 10:00:00".to_string(),
+            is_nsfw: false,
+            nsfw_source: None,
+            next_retry_at: None,
+            last_attempt_at: None,
+            http_status_code: None,
+            redirect_chain: None,
+            post_date: None,
+            quoted_archive_id: None,
+            reply_to_archive_id: None,
+            submitted_by_user_id: None,
+            progress_percent: None,
+            progress_details: None,
+            last_progress_update: None,
+            og_title: None,
+            og_description: None,
+            og_image: None,
+            og_type: None,
+            og_extracted_at: None,
+            og_extraction_attempted: false,
+            transcript_text: None,
+            full_text: None,
+            view_count: None,
+            like_count: None,
+            repost_count: None,
+            platform_comment_count: None,
+            save_count: None,
+            metrics_backfill_version: None,
+            short_code: None,
+            error_kind: None,
+            skip_reason: None,
+        }
+    }
+
+    fn sample_archive() -> Archive {
+        Archive {
+            content_type: Some("video".to_string()),
+            s3_key_primary: Some("archives/42/media/video.mp4".to_string()),
+            s3_key_thumb: Some("archives/42/thumb/thumb.jpg".to_string()),
+            ipfs_cid: Some("bafytest".to_string()),
+            ..archive(42, "complete")
+        }
+    }
+
+    fn sample_artifact(s3_key: &str) -> ArchiveArtifact {
+        ArchiveArtifact {
+            id: 1,
+            archive_id: 42,
+            kind: "screenshot".to_string(),
+            s3_key: s3_key.to_string(),
+            content_type: None,
+            size_bytes: None,
+            sha256: None,
+            created_at: "2024-01-15 12:00:00".to_string(),
+            perceptual_hash: None,
+            duplicate_of_artifact_id: None,
+            video_file_id: None,
+            metadata: None,
+            placeholder_color: None,
+            width: None,
+            height: None,
+            content_encoding: None,
+        }
+    }
+
+    #[test]
+    fn test_event_names_round_trip() {
+        for event in WebhookEvent::ALL {
+            assert_eq!(WebhookEvent::parse(event.as_str()), Some(event));
+        }
+        assert_eq!(WebhookEvent::parse("archive_deleted"), None);
+    }
+
+    #[test]
+    fn test_sign_payload_known_vector() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign_payload("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_sign_payload_depends_on_secret_and_body() {
+        let body = br#"{"archive_id":1}"#;
+        assert_ne!(sign_payload("a", body), sign_payload("b", body));
+        assert_ne!(
+            sign_payload("a", body),
+            sign_payload("a", br#"{"archive_id":2}"#)
+        );
+    }
+
+    #[test]
+    fn test_payload_shape() {
+        let artifacts = [
+            sample_artifact("archives/42/media/video.mp4"),
+            sample_artifact("archives/42/render/screenshot.webp"),
+        ];
+        let payload = WebhookPayload::from_archive(
+            WebhookEvent::ArchiveComplete,
+            &sample_archive(),
+            "https://example.com/video",
+            &artifacts,
+        );
+
+        assert_eq!(
+            serde_json::to_value(&payload).unwrap(),
+            json!({
+                "event": "archive_complete",
+                "archive_id": 42,
+                "url": "https://example.com/video",
+                "status": "complete",
+                "content_type": "video",
+                "s3_keys": [
+                    "archives/42/media/video.mp4",
+                    "archives/42/thumb/thumb.jpg",
+                    "archives/42/render/screenshot.webp",
+                ],
+                "ipfs_cid": "bafytest",
+            })
+        );
+    }
+
+    #[test]
+    fn test_failed_payload_has_nulls() {
+        let archive = archive(3, "failed");
+        let payload = WebhookPayload::from_archive(
+            WebhookEvent::ArchiveFailed,
+            &archive,
+            "https://example.com",
+            &[],
+        );
+
+        assert_eq!(
+            serde_json::to_value(&payload).unwrap(),
+            json!({
+                "event": "archive_failed",
+                "archive_id": 3,
+                "url": "https://example.com",
+                "status": "failed",
+                "content_type": null,
+                "s3_keys": [],
+                "ipfs_cid": null,
+            })
+        );
+    }
+
+    #[test]
+    fn test_disabled_notifier_is_not_subscribed() {
+        let notifier = WebhookNotifier::disabled();
+        assert!(!notifier.is_subscribed(WebhookEvent::ArchiveComplete));
+        assert!(!notifier.is_subscribed(WebhookEvent::ArchiveFailed));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_endpoint_does_not_block() {
+        // Port 9 (discard) is not listening; deliveries fail and retry in the background
+        let notifier = WebhookNotifier::spawn(
+            "http://127.0.0.1:9/hook".to_string(),
+            None,
+            vec![WebhookEvent::ArchiveFailed],
+            1,
+        );
+        assert!(notifier.is_subscribed(WebhookEvent::ArchiveFailed));
+        assert!(!notifier.is_subscribed(WebhookEvent::ArchiveComplete));
+
+        let archive = archive(1, "failed");
+        let start = std::time::Instant::now();
+        // Far more than the queue holds: extra notifications are dropped, not awaited
+        for _ in 0..100 {
+            notifier.notify(WebhookPayload::from_archive(
+                WebhookEvent::ArchiveFailed,
+                &archive,
+                "https://example.com",
+                &[],
+            ));
+        }
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_delivery_is_signed() {
+        let (tx, mut rx) = mpsc::channel::<(HeaderMap, Bytes)>(1);
+        let app = Router::new().route(
+            "/hook",
+            post(move |headers: HeaderMap, body: Bytes| {
+                let tx = tx.clone();
+                async move {
+                    let _ = tx.send((headers, body)).await;
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let notifier = WebhookNotifier::spawn(
+            format!("http://{addr}/hook"),
+            Some("s3cret".to_string()),
+            WebhookEvent::ALL.to_vec(),
+            4,
+        );
+        notifier.notify(WebhookPayload::from_archive(
+            WebhookEvent::ArchiveComplete,
+            &sample_archive(),
+            "https://example.com/video",
+            &[],
+        ));
+
+        let (headers, body) = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("webhook not delivered")
+            .unwrap();
+        assert_eq!(
+            headers.get(SIGNATURE_HEADER).unwrap(),
+            sign_payload("s3cret", &body).as_str()
+        );
+        assert_eq!(headers.get(EVENT_HEADER).unwrap(), "archive_complete");
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value["archive_id"], 42);
+    }
+}
@@ -240,6 +240,7 @@ function renderComment(comment, depth, platform, childrenMap) {
     const indentClass = depth > 0 ? `comment-depth-${Math.min(depth, 3)}` : '';
     const pinnedClass = comment.is_pinned ? 'comment-pinned' : '';
     const creatorClass = comment.is_creator ? 'comment-creator' : '';
+    const removedClass = comment.is_removed ? 'comment-removed' : '';
 
     // Generate avatar initial
     const avatarInitial = comment.author ? comment.author.charAt(0).toUpperCase() : '?';
@@ -249,7 +250,7 @@ function renderComment(comment, depth, platform, childrenMap) {
     const replies = (childrenMap && childrenMap.get(comment.id)) || comment.replies || [];
 
     let html = `
-        <div class="platform-comment ${indentClass} ${pinnedClass} ${creatorClass}"
+        <div class="platform-comment ${indentClass} ${pinnedClass} ${creatorClass} ${removedClass}"
              data-platform="${platform}"
              data-comment-id="${escapeHtml(String(commentId))}">
             <div class="comment-header">
@@ -3,8 +3,10 @@
 use discourse_link_archiver::archiver::CookieOptions;
 use discourse_link_archiver::config::Config;
 use discourse_link_archiver::db::{
-    create_pending_archive, get_archive, get_pending_archives, insert_link, set_archive_complete,
-    set_archive_failed, set_archive_processing, Database, NewLink,
+    count_archives_needing_og_reextraction, create_pending_archive, get_archive,
+    get_pending_archives, insert_link, mark_og_extraction_attempted, reset_failed_og_extractions,
+    reset_og_extraction, set_archive_complete, set_archive_failed, set_archive_processing,
+    update_archive_og_metadata, Database, NewLink,
 };
 use discourse_link_archiver::handlers::HANDLERS;
 use tempfile::TempDir;
@@ -147,7 +149,7 @@ async fn test_archive_failure() {
         .expect("Failed to set processing");
 
     // Mark as failed
-    set_archive_failed(db.pool(), archive_id, "HTTP 404 Not Found")
+    set_archive_failed(db.pool(), archive_id, "HTTP 404 Not Found", 3)
         .await
         .expect("Failed to set failed");
 
@@ -432,3 +434,64 @@ async fn test_archive_with_metadata_extraction() {
         .as_ref()
         .is_some_and(|t| t.contains("guide to testing")));
 }
+
+#[tokio::test]
+async fn test_reset_og_extraction() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    let mut archive_ids = Vec::new();
+    for i in 0..3 {
+        let url = format!("https://example.com/og-{i}");
+        let link_id = insert_link(
+            pool,
+            &NewLink {
+                original_url: url.clone(),
+                normalized_url: url,
+                canonical_url: None,
+                domain: "example.com".to_string(),
+            },
+        )
+        .await
+        .expect("Failed to insert link");
+        archive_ids.push(
+            create_pending_archive(pool, link_id, None)
+                .await
+                .expect("Failed to create archive"),
+        );
+    }
+
+    // Two extractions found nothing, one found a title
+    mark_og_extraction_attempted(pool, archive_ids[0])
+        .await
+        .unwrap();
+    mark_og_extraction_attempted(pool, archive_ids[1])
+        .await
+        .unwrap();
+    update_archive_og_metadata(pool, archive_ids[2], Some("Title"), None, None, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        count_archives_needing_og_reextraction(pool).await.unwrap(),
+        2
+    );
+
+    assert!(reset_og_extraction(pool, archive_ids[0]).await.unwrap());
+    assert!(!reset_og_extraction(pool, 999_999).await.unwrap());
+    let archive = get_archive(pool, archive_ids[0]).await.unwrap().unwrap();
+    assert!(!archive.og_extraction_attempted);
+    assert_eq!(
+        count_archives_needing_og_reextraction(pool).await.unwrap(),
+        1
+    );
+
+    // Bulk reset leaves archives with metadata alone
+    assert_eq!(reset_failed_og_extractions(pool).await.unwrap(), 1);
+    assert_eq!(
+        count_archives_needing_og_reextraction(pool).await.unwrap(),
+        0
+    );
+    let archive = get_archive(pool, archive_ids[2]).await.unwrap().unwrap();
+    assert!(archive.og_extraction_attempted);
+    assert_eq!(archive.og_title.as_deref(), Some("Title"));
+}
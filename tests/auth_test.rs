@@ -5,10 +5,12 @@ use discourse_link_archiver::auth::{
     verify_password,
 };
 use discourse_link_archiver::db::{
-    count_users, create_session, create_user, delete_session, delete_user_sessions,
-    get_session_by_token, get_user_by_id, get_user_by_username, increment_failed_login_attempts,
-    lock_user_until, reset_failed_login_attempts, update_user_active, update_user_admin,
-    update_user_approval, update_user_password, update_user_profile, Database,
+    count_audit_events, count_audit_events_filtered, count_users, create_audit_event,
+    create_session, create_user, delete_session, delete_user_sessions, get_all_users,
+    get_audit_events, get_audit_events_filtered, get_session_by_token, get_user_by_id,
+    get_user_by_username, increment_failed_login_attempts, lock_user_until,
+    reset_failed_login_attempts, update_user_active, update_user_admin, update_user_approval,
+    update_user_password, update_user_profile, Database,
 };
 use serial_test::serial;
 use tempfile::TempDir;
@@ -567,3 +569,132 @@ async fn test_delete_user_sessions() {
         .unwrap()
         .is_none());
 }
+
+#[tokio::test]
+async fn test_audit_events_filtered() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = db.pool();
+
+    let password_hash = hash_password("SecureP@ssw0rd123").unwrap();
+    let alice = create_user(pool, "alice", &password_hash, true)
+        .await
+        .unwrap();
+    let bob = create_user(pool, "bob", &password_hash, false)
+        .await
+        .unwrap();
+
+    for (user_id, event_type) in [
+        (Some(alice), "login"),
+        (Some(alice), "user_approved"),
+        (Some(bob), "login"),
+        (None, "login"),
+        (Some(alice), "login"),
+    ] {
+        create_audit_event(
+            pool, user_id, event_type, None, None, None, None, None, None,
+        )
+        .await
+        .unwrap();
+    }
+
+    // No filters returns everything
+    let all = get_audit_events_filtered(pool, None, None, 50, 0)
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 5);
+    assert_eq!(
+        count_audit_events_filtered(pool, None, None).await.unwrap(),
+        5
+    );
+
+    // Single filters
+    assert_eq!(
+        count_audit_events_filtered(pool, Some("login"), None)
+            .await
+            .unwrap(),
+        4
+    );
+    assert_eq!(
+        count_audit_events_filtered(pool, None, Some(alice))
+            .await
+            .unwrap(),
+        3
+    );
+
+    // Combined filter
+    let events = get_audit_events_filtered(pool, Some("login"), Some(alice), 50, 0)
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 2);
+    assert!(events
+        .iter()
+        .all(|e| e.event_type == "login" && e.user_id == Some(alice)));
+    assert_eq!(
+        count_audit_events_filtered(pool, Some("login"), Some(alice))
+            .await
+            .unwrap(),
+        2
+    );
+
+    // Pagination
+    let page = get_audit_events_filtered(pool, Some("login"), None, 3, 3)
+        .await
+        .unwrap();
+    assert_eq!(page.len(), 1);
+}
+
+#[tokio::test]
+async fn test_admin_list_pages() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = db.pool();
+
+    let password_hash = hash_password("SecureP@ssw0rd123").unwrap();
+    let mut user_ids = Vec::new();
+    for i in 0..7 {
+        user_ids.push(
+            create_user(pool, &format!("user{i}"), &password_hash, i == 0)
+                .await
+                .unwrap(),
+        );
+    }
+    let mut event_ids = Vec::new();
+    for _ in 0..11 {
+        event_ids.push(
+            create_audit_event(pool, None, "login", None, None, None, None, None, None)
+                .await
+                .unwrap(),
+        );
+    }
+
+    // Newest first; users created in the same second fall back to id order
+    let per_page = 3;
+    let total_users = count_users(pool).await.unwrap();
+    assert_eq!((total_users + per_page - 1) / per_page, 3);
+    let page: Vec<i64> = get_all_users(pool, per_page, per_page)
+        .await
+        .unwrap()
+        .iter()
+        .map(|u| u.id)
+        .collect();
+    assert_eq!(page, vec![user_ids[3], user_ids[2], user_ids[1]]);
+
+    let per_page = 4;
+    let total_events = count_audit_events(pool).await.unwrap();
+    assert_eq!((total_events + per_page - 1) / per_page, 3);
+    let page: Vec<i64> = get_audit_events(pool, per_page, per_page)
+        .await
+        .unwrap()
+        .iter()
+        .map(|e| e.id)
+        .collect();
+    assert_eq!(
+        page,
+        vec![event_ids[6], event_ids[5], event_ids[4], event_ids[3]]
+    );
+
+    // The last page holds the remainder
+    let last = get_audit_events(pool, per_page, 2 * per_page)
+        .await
+        .unwrap();
+    assert_eq!(last.len(), 3);
+}
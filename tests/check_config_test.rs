@@ -0,0 +1,82 @@
+//! Tests for the `--check-config` dry validation mode.
+
+use std::process::{Command, Output};
+
+use tempfile::TempDir;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Run the binary with `--check-config` and only the given environment.
+///
+/// Runs from an empty directory so a developer's `.env` isn't picked up.
+fn check_config(env: &[(&str, &str)]) -> (Output, String) {
+    let dir = TempDir::new().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_discourse-link-archiver"))
+        .arg("--check-config")
+        .current_dir(dir.path())
+        .env_clear()
+        .env("HOME", dir.path())
+        .env("AWS_EC2_METADATA_DISABLED", "true")
+        .envs(env.iter().copied())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    (output, stdout)
+}
+
+#[tokio::test]
+async fn test_check_config_valid_env() {
+    // A HEAD for the probe key returning 404 means the bucket is reachable
+    let s3 = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1..)
+        .mount(&s3)
+        .await;
+
+    let endpoint = s3.uri();
+    let (output, stdout) = check_config(&[
+        ("RSS_URL", "https://forum.example.com/posts.rss"),
+        ("S3_BUCKET", "archives"),
+        ("S3_ENDPOINT", &endpoint),
+        ("AWS_ACCESS_KEY_ID", "test"),
+        ("AWS_SECRET_ACCESS_KEY", "test"),
+    ]);
+
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("[ok  ] config"), "{stdout}");
+    assert!(stdout.contains("[ok  ] validate"), "{stdout}");
+    assert!(stdout.contains("[ok  ] s3: bucket archives"), "{stdout}");
+    assert!(stdout.contains("[skip] ipfs: disabled"), "{stdout}");
+    assert!(stdout.contains("Enabled: "), "{stdout}");
+    assert!(stdout.ends_with("Result: OK\n"), "{stdout}");
+}
+
+#[test]
+fn test_check_config_missing_required_value() {
+    let (output, stdout) = check_config(&[("S3_BUCKET", "archives")]);
+
+    assert_eq!(output.status.code(), Some(1), "{stdout}");
+    assert!(stdout.contains("[FAIL] config"), "{stdout}");
+    assert!(stdout.contains("RSS_URL"), "{stdout}");
+    assert!(stdout.contains("Result: FAILED"), "{stdout}");
+}
+
+#[tokio::test]
+async fn test_check_config_invalid_value_and_unreachable_s3() {
+    // Nothing listens on the S3 endpoint and the pool size is out of range
+    let (output, stdout) = check_config(&[
+        ("RSS_URL", "https://forum.example.com/posts.rss"),
+        ("S3_BUCKET", "archives"),
+        ("S3_ENDPOINT", "http://127.0.0.1:9"),
+        ("AWS_ACCESS_KEY_ID", "test"),
+        ("AWS_SECRET_ACCESS_KEY", "test"),
+        ("DB_MAX_CONNECTIONS", "0"),
+    ]);
+
+    assert_eq!(output.status.code(), Some(1), "{stdout}");
+    assert!(stdout.contains("[FAIL] validate"), "{stdout}");
+    assert!(stdout.contains("db_max_connections"), "{stdout}");
+    assert!(stdout.contains("[FAIL] s3"), "{stdout}");
+    assert!(stdout.contains("Result: FAILED (2 problem(s))"), "{stdout}");
+}
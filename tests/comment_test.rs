@@ -8,6 +8,7 @@ use discourse_link_archiver::db::{
     remove_comment_reaction, soft_delete_comment, unpin_comment, update_comment, Database, NewLink,
 };
 use serial_test::serial;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use tempfile::TempDir;
 
 async fn setup_test_db() -> (Database, TempDir) {
@@ -27,11 +28,16 @@ async fn create_test_user(db: &Database, username: &str, is_admin: bool) -> i64
         .expect("Failed to create user")
 }
 
-/// Helper to create a test archive
+/// Helper to create a test archive (each call gets its own link)
 async fn create_test_archive(db: &Database) -> i64 {
+    static NEXT_LINK: AtomicUsize = AtomicUsize::new(0);
+    let url = format!(
+        "https://example.com/test/{}",
+        NEXT_LINK.fetch_add(1, Ordering::Relaxed)
+    );
     let new_link = NewLink {
-        original_url: "https://example.com/test".to_string(),
-        normalized_url: "https://example.com/test".to_string(),
+        original_url: url.clone(),
+        normalized_url: url,
         canonical_url: None,
         domain: "example.com".to_string(),
     };
@@ -1,13 +1,35 @@
 //! Integration tests for database operations.
 
+use discourse_link_archiver::archiver::SkipReason;
 use discourse_link_archiver::db::{
-    count_archives_for_video_file, create_pending_archive, find_video_file, get_archive,
-    get_archive_by_link_id, get_link_by_normalized_url, get_nsfw_count, get_or_create_video_file,
-    get_post_by_guid, get_recent_archives, get_top_domains, get_video_file,
-    insert_artifact_with_video_file, insert_link, insert_link_occurrence, insert_post,
-    insert_video_file, link_occurrence_exists, search_archives, set_archive_complete,
-    set_archive_nsfw, update_video_file_metadata, update_video_file_metadata_key, Database,
-    NewLink, NewLinkOccurrence, NewPost,
+    add_excluded_domain, add_priority_domain, add_versioned_domain, archive_versioning_enabled,
+    clear_ipfs_pin_pending, count_archives_for_video_file, create_pending_archive, defer_archive,
+    delete_archive, delete_domain_quote_policy, delete_external_service_rule,
+    delete_priority_domain, delete_versioned_domain, external_services_for, find_duplicate_links,
+    find_video_file, forum_author_handle, get_archive, get_archive_by_link_id,
+    get_archive_by_short_code, get_archive_versions, get_archives_eligible_for_pruning,
+    get_archives_since, get_artifacts_for_archive, get_content_versions_for_link,
+    get_domain_quote_override, get_domain_stats, get_due_watched_links, get_external_service_rules,
+    get_failed_archives_for_retry, get_failure_kind_counts, get_latest_content_version, get_link,
+    get_link_by_normalized_url, get_newest_ingested_post_id, get_nsfw_count, get_or_create_link,
+    get_or_create_video_file, get_pending_archives, get_pending_counts_by_domain,
+    get_pending_ipfs_pins, get_playlist_members_display, get_post_by_guid,
+    get_posts_by_forum_author, get_previous_primary, get_priority_domains, get_random_archive_id,
+    get_recent_archives, get_recent_failed_archives, get_sitemap_archives, get_skip_reason_counts,
+    get_storage_by_domain, get_thumbnails_for_archives, get_top_domains, get_video_file,
+    get_watched_link, insert_archive_version, insert_artifact, insert_artifact_with_encoding,
+    insert_artifact_with_video_file, insert_content_version, insert_link, insert_link_occurrence,
+    insert_playlist_item, insert_post, insert_video_file, is_domain_excluded,
+    link_occurrence_exists, mark_ipfs_pin_pending, mark_watched_link_checked, merge_links,
+    next_archive_version, reset_archive_for_rearchive,
+    reset_archive_for_rearchive_preserve_metadata, reset_dry_run_archives,
+    reset_single_skipped_archive, search_archives, set_archive_complete, set_archive_dry_run,
+    set_archive_error_kind, set_archive_failed, set_archive_ipfs_cid, set_archive_keep_versions,
+    set_archive_nsfw, set_archive_nsfw_auto, set_archive_processing, set_archive_skipped,
+    set_artifact_placeholder, set_domain_quote_policy, set_external_service_rule,
+    should_archive_quote_only_link, toggle_archive_nsfw, unwatch_link, update_video_file_metadata,
+    update_video_file_metadata_key, watch_link, ArchiveQuery, ArchiveSort, Database,
+    DatabaseOptions, ExternalServiceScope, ExternalServices, NewLink, NewLinkOccurrence, NewPost,
 };
 use tempfile::TempDir;
 
@@ -28,6 +50,7 @@ async fn test_insert_and_get_post() {
         guid: "test-guid-123".to_string(),
         discourse_url: "https://forum.example.com/t/test/123".to_string(),
         author: Some("testuser".to_string()),
+        author_handle: None,
         title: Some("Test Post".to_string()),
         body_html: Some("<p>Hello world</p>".to_string()),
         content_hash: Some("abc123".to_string()),
@@ -74,6 +97,453 @@ async fn test_insert_and_get_link() {
     assert_eq!(retrieved.domain, "old.reddit.com");
 }
 
+#[tokio::test]
+async fn test_read_pool_sees_writes_and_rejects_writes() {
+    let (db, _temp_dir) = setup_db().await;
+
+    let link_id = insert_link(db.pool(), &test_link("https://example.com/read-pool"))
+        .await
+        .expect("Failed to insert link");
+
+    // Committed writes on the main pool are visible to readers
+    let link = get_link(db.read_pool(), link_id)
+        .await
+        .expect("Failed to read link")
+        .expect("Link not found");
+    assert_eq!(link.normalized_url, "https://example.com/read-pool");
+
+    let err = insert_link(db.read_pool(), &test_link("https://example.com/other"))
+        .await
+        .expect_err("Read pool accepted a write");
+    assert!(
+        format!("{err:#}").contains("readonly"),
+        "unexpected error: {err:#}"
+    );
+}
+
+#[tokio::test]
+async fn test_get_or_create_link_concurrent() {
+    let (db, _temp_dir) = setup_db().await;
+
+    let new_link = NewLink {
+        original_url: "https://example.com/race".to_string(),
+        normalized_url: "https://example.com/race".to_string(),
+        canonical_url: None,
+        domain: "example.com".to_string(),
+    };
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let pool = db.pool().clone();
+            let link = new_link.clone();
+            tokio::spawn(async move { get_or_create_link(&pool, &link).await })
+        })
+        .collect();
+
+    let mut ids = Vec::new();
+    for handle in handles {
+        ids.push(handle.await.unwrap().expect("Failed to get or create link"));
+    }
+    assert!(ids.iter().all(|id| *id == ids[0]));
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM links WHERE normalized_url = ?")
+        .bind(&new_link.normalized_url)
+        .fetch_one(db.pool())
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+
+    // A later call returns the same link
+    let again = get_or_create_link(db.pool(), &new_link).await.unwrap();
+    assert_eq!(again, ids[0]);
+}
+
+#[tokio::test]
+async fn test_get_newest_ingested_post_id() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    assert_eq!(
+        get_newest_ingested_post_id(pool, "forum.example.com")
+            .await
+            .unwrap(),
+        None
+    );
+
+    for guid in [
+        "forum.example.com-post-9",
+        "forum.example.com-post-20218",
+        "other.example.com-post-99999",
+    ] {
+        insert_post(
+            pool,
+            &NewPost {
+                guid: guid.to_string(),
+                discourse_url: format!("https://{guid}"),
+                author: None,
+                author_handle: None,
+                title: None,
+                body_html: None,
+                content_hash: None,
+                published_at: None,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    // Compared numerically, and only among the forum's own posts
+    assert_eq!(
+        get_newest_ingested_post_id(pool, "forum.example.com")
+            .await
+            .unwrap(),
+        Some(20218)
+    );
+}
+
+/// Recreate the pre-v34 state, where duplicate normalized URLs were possible.
+///
+/// Returns the kept and duplicate link IDs, the duplicate's archive and an
+/// archive quoting it.
+async fn seed_pre_v34_duplicates(db_path: &std::path::Path) -> (i64, i64, i64, i64) {
+    let db = Database::new(db_path).await.unwrap();
+    let pool = db.pool();
+    sqlx::query("DROP INDEX idx_links_normalized_url")
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE _schema_version SET version = 33")
+        .execute(pool)
+        .await
+        .unwrap();
+
+    let new_link = NewLink {
+        original_url: "https://example.com/dup".to_string(),
+        normalized_url: "https://example.com/dup".to_string(),
+        canonical_url: None,
+        domain: "example.com".to_string(),
+    };
+    let keep_id = insert_link(pool, &new_link).await.unwrap();
+    let dup_id = insert_link(pool, &new_link).await.unwrap();
+    let other_id = insert_link(
+        pool,
+        &NewLink {
+            normalized_url: "https://example.com/other".to_string(),
+            ..new_link.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    // Both copies were archived, only the duplicate's completely, and
+    // another archive quotes the duplicate's
+    let keep_archive_id = create_pending_archive(pool, keep_id, None).await.unwrap();
+    insert_artifact(
+        pool,
+        keep_archive_id,
+        "screenshot",
+        "keep/1.png",
+        None,
+        None,
+        Some("aaa"),
+    )
+    .await
+    .unwrap();
+    let dup_archive_id = create_pending_archive(pool, dup_id, None).await.unwrap();
+    set_archive_complete(pool, dup_archive_id, None, None, None, None, None, None)
+        .await
+        .unwrap();
+    for (kind, key, sha) in [
+        ("screenshot", "dup/1.png", "aaa"),
+        ("video", "dup/v.mp4", "vvv"),
+    ] {
+        insert_artifact(pool, dup_archive_id, kind, key, None, None, Some(sha))
+            .await
+            .unwrap();
+    }
+    let other_archive_id = create_pending_archive(pool, other_id, None).await.unwrap();
+    sqlx::query("UPDATE archives SET quoted_archive_id = ? WHERE id = ?")
+        .bind(dup_archive_id)
+        .bind(other_archive_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    (keep_id, dup_id, dup_archive_id, other_archive_id)
+}
+
+#[tokio::test]
+async fn test_migration_merges_duplicate_links() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.sqlite");
+
+    let (keep_id, dup_id, dup_archive_id, other_archive_id) =
+        seed_pre_v34_duplicates(&db_path).await;
+
+    let db = Database::new(&db_path).await.unwrap();
+
+    let link = get_link_by_normalized_url(db.pool(), "https://example.com/dup")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(link.id, keep_id);
+    let dup_exists: Option<i64> = sqlx::query_scalar("SELECT id FROM links WHERE id = ?")
+        .bind(dup_id)
+        .fetch_optional(db.pool())
+        .await
+        .unwrap();
+    assert!(dup_exists.is_none());
+
+    // The complete archive moves to the kept link with its artifacts, and
+    // the file it already had is recorded for cleanup instead of lost
+    let archive = get_archive_by_link_id(db.pool(), keep_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(archive.id, dup_archive_id);
+    let mut keys: Vec<String> = get_artifacts_for_archive(db.pool(), dup_archive_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|a| a.s3_key)
+        .collect();
+    keys.sort();
+    assert_eq!(keys, vec!["dup/1.png", "dup/v.mp4"]);
+    let orphaned: Vec<String> = sqlx::query_scalar("SELECT s3_key FROM orphaned_s3_objects")
+        .fetch_all(db.pool())
+        .await
+        .unwrap();
+    assert_eq!(orphaned, vec!["keep/1.png"]);
+    let other = get_archive(db.pool(), other_archive_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(other.quoted_archive_id, Some(dup_archive_id));
+
+    // Inserting the same URL again is rejected by the unique index
+    let new_link = NewLink {
+        original_url: "https://example.com/dup".to_string(),
+        normalized_url: "https://example.com/dup".to_string(),
+        canonical_url: None,
+        domain: "example.com".to_string(),
+    };
+    assert!(insert_link(db.pool(), &new_link).await.is_err());
+    assert_eq!(
+        get_or_create_link(db.pool(), &new_link).await.unwrap(),
+        keep_id
+    );
+}
+
+fn test_link(normalized_url: &str) -> NewLink {
+    NewLink {
+        original_url: normalized_url.to_string(),
+        normalized_url: normalized_url.to_string(),
+        canonical_url: None,
+        domain: "example.com".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_find_duplicate_links() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    // Stored before normalization stripped tracking params and forced HTTPS
+    let keep_id = insert_link(pool, &test_link("https://example.com/page"))
+        .await
+        .unwrap();
+    let old_id = insert_link(pool, &test_link("http://example.com/page?utm_source=rss"))
+        .await
+        .unwrap();
+    insert_link(pool, &test_link("https://example.com/other"))
+        .await
+        .unwrap();
+    // Only the Twitter handler maps twitter.com onto x.com
+    let tweet_id = insert_link(pool, &test_link("https://x.com/user/status/1"))
+        .await
+        .unwrap();
+    let old_tweet_id = insert_link(pool, &test_link("https://twitter.com/user/status/1"))
+        .await
+        .unwrap();
+
+    let scan = find_duplicate_links(pool, 0, 100).await.unwrap();
+    assert_eq!(scan.scanned, 5);
+    assert_eq!(scan.last_id, Some(old_tweet_id));
+    let groups = scan.groups;
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].normalized_url, "https://example.com/page");
+    assert_eq!(groups[0].keep().id, keep_id);
+    assert_eq!(groups[0].merge_ids(), vec![old_id]);
+    assert_eq!(groups[1].normalized_url, "https://x.com/user/status/1");
+    assert_eq!(groups[1].keep().id, tweet_id);
+    assert_eq!(groups[1].merge_ids(), vec![old_tweet_id]);
+
+    // A page holding only the stale link still finds the current one
+    let scan = find_duplicate_links(pool, old_id - 1, 1).await.unwrap();
+    assert_eq!(scan.scanned, 1);
+    assert_eq!(scan.groups.len(), 1);
+    assert_eq!(scan.groups[0].keep().id, keep_id);
+    assert_eq!(scan.groups[0].merge_ids(), vec![old_id]);
+
+    let scan = find_duplicate_links(pool, old_tweet_id, 100).await.unwrap();
+    assert_eq!(scan.scanned, 0);
+    assert_eq!(scan.last_id, None);
+}
+
+#[tokio::test]
+async fn test_merge_links_updates_kept_url() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    // The oldest link was stored before twitter.com became x.com
+    let keep_id = insert_link(pool, &test_link("https://twitter.com/user/status/2"))
+        .await
+        .unwrap();
+    let dup_id = insert_link(pool, &test_link("https://x.com/user/status/2"))
+        .await
+        .unwrap();
+
+    merge_links(pool, keep_id, &[dup_id]).await.unwrap();
+
+    let link = get_link(pool, keep_id).await.unwrap().unwrap();
+    assert_eq!(link.normalized_url, "https://x.com/user/status/2");
+    let scan = find_duplicate_links(pool, 0, 100).await.unwrap();
+    assert!(scan.groups.is_empty());
+}
+
+#[tokio::test]
+async fn test_merge_links_reparents_rows() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    let post = |guid: &str| NewPost {
+        guid: guid.to_string(),
+        discourse_url: format!("https://forum.example.com/t/{guid}"),
+        author: None,
+        author_handle: None,
+        title: None,
+        body_html: None,
+        content_hash: None,
+        published_at: None,
+    };
+    let post1 = insert_post(pool, &post("merge-1")).await.unwrap();
+    let post2 = insert_post(pool, &post("merge-2")).await.unwrap();
+
+    let keep_id = insert_link(pool, &test_link("https://example.com/video"))
+        .await
+        .unwrap();
+    let dup_id = insert_link(pool, &test_link("http://example.com/video/"))
+        .await
+        .unwrap();
+
+    // Both links appear in post 1; only the duplicate appears in post 2
+    for (link_id, post_id) in [(keep_id, post1), (dup_id, post1), (dup_id, post2)] {
+        insert_link_occurrence(
+            pool,
+            &NewLinkOccurrence {
+                link_id,
+                post_id,
+                in_quote: false,
+                context_snippet: None,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    // The kept link's archive is pending; the duplicate's is complete
+    let keep_archive = create_pending_archive(pool, keep_id, None).await.unwrap();
+    for (kind, key, sha) in [
+        ("screenshot", "a/1.png", "aaa"),
+        ("thumb", "a/thumb.jpg", "ttt"),
+    ] {
+        insert_artifact(pool, keep_archive, kind, key, None, None, Some(sha))
+            .await
+            .unwrap();
+    }
+    let dup_archive = create_pending_archive(pool, dup_id, None).await.unwrap();
+    set_archive_complete(
+        pool,
+        dup_archive,
+        None,
+        None,
+        None,
+        Some("video"),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    for (kind, key, sha) in [
+        ("video", "d/v.mp4", "vvv"),
+        ("screenshot", "d/1.png", "aaa"),
+    ] {
+        insert_artifact(pool, dup_archive, kind, key, None, None, Some(sha))
+            .await
+            .unwrap();
+    }
+
+    let summary = merge_links(pool, keep_id, &[dup_id]).await.unwrap();
+    assert_eq!(summary.links_removed, 1);
+    assert_eq!(summary.occurrences_moved, 1);
+    assert_eq!(summary.archives_merged, 1);
+    assert_eq!(summary.artifacts_moved, 1);
+    assert_eq!(summary.artifacts_deduplicated, 1);
+    assert_eq!(summary.objects_orphaned, 1);
+
+    let links: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM links")
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    assert_eq!(links, 1);
+
+    // One occurrence per post, all on the kept link
+    let occurrences: Vec<(i64, i64)> =
+        sqlx::query_as("SELECT link_id, post_id FROM link_occurrences ORDER BY post_id")
+            .fetch_all(pool)
+            .await
+            .unwrap();
+    assert_eq!(occurrences, vec![(keep_id, post1), (keep_id, post2)]);
+
+    // The complete archive is kept and gains the other archive's unique artifact
+    let archive = get_archive_by_link_id(pool, keep_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(archive.id, dup_archive);
+    assert!(get_archive(pool, keep_archive).await.unwrap().is_none());
+    let mut kinds: Vec<String> = get_artifacts_for_archive(pool, dup_archive)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|a| a.kind)
+        .collect();
+    kinds.sort();
+    assert_eq!(kinds, vec!["screenshot", "thumb", "video"]);
+
+    assert!(find_duplicate_links(pool, 0, 100)
+        .await
+        .unwrap()
+        .groups
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_merge_links_rejects_invalid_input() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+    let link_id = insert_link(pool, &test_link("https://example.com/a"))
+        .await
+        .unwrap();
+
+    assert!(merge_links(pool, link_id, &[]).await.is_err());
+    assert!(merge_links(pool, link_id, &[link_id]).await.is_err());
+    assert!(merge_links(pool, link_id, &[link_id + 100]).await.is_err());
+    assert!(get_archive_by_link_id(pool, link_id)
+        .await
+        .unwrap()
+        .is_none());
+}
+
 #[tokio::test]
 async fn test_link_occurrence() {
     let (db, _temp_dir) = setup_db().await;
@@ -83,6 +553,7 @@ async fn test_link_occurrence() {
         guid: "post-for-link".to_string(),
         discourse_url: "https://forum.example.com/t/test/1".to_string(),
         author: None,
+        author_handle: None,
         title: None,
         body_html: None,
         content_hash: None,
@@ -157,68 +628,214 @@ async fn test_archive_workflow() {
 }
 
 #[tokio::test]
-async fn test_recent_archives() {
-    let (db, _temp_dir) = setup_db().await;
-
-    // Initially no archives
-    let recent = get_recent_archives(db.pool(), 10).await.unwrap();
-    assert!(recent.is_empty());
-}
-
-#[tokio::test]
-async fn test_search_archives() {
+async fn test_defer_archive_for_live_content() {
     let (db, _temp_dir) = setup_db().await;
 
-    // Search with no archives should return empty
-    let results = search_archives(db.pool(), "test", 10).await.unwrap();
-    assert!(results.is_empty());
-}
-
-// ========== Video File Tests ==========
+    let link_id = insert_link(db.pool(), &test_link("https://x.com/i/spaces/1abc"))
+        .await
+        .unwrap();
+    let archive_id = create_pending_archive(db.pool(), link_id, None)
+        .await
+        .unwrap();
 
-#[tokio::test]
-async fn test_insert_and_get_video_file() {
-    let (db, _temp_dir) = setup_db().await;
+    // Deferred archives stay pending but aren't picked up until they're due
+    assert!(
+        defer_archive(db.pool(), archive_id, "still live", 1800, 48 * 3600)
+            .await
+            .unwrap()
+    );
+    let archive = get_archive(db.pool(), archive_id).await.unwrap().unwrap();
+    assert_eq!(archive.status, "pending");
+    assert_eq!(archive.retry_count, 0);
+    assert_eq!(archive.error_message.as_deref(), Some("still live"));
+    assert!(archive.next_retry_at.is_some());
+    assert!(get_pending_archives(db.pool(), 10)
+        .await
+        .unwrap()
+        .is_empty());
 
-    // Insert a video file
-    let video_file_id = insert_video_file(
-        db.pool(),
-        "dQw4w9WgXcQ",
-        "youtube",
-        "videos/dQw4w9WgXcQ.mp4",
-        Some("videos/dQw4w9WgXcQ.json"),
-        Some(12345678),
-        Some("video/mp4"),
-        Some(212), // 3:32 in seconds
+    // The retry never lands past the deferral window...
+    assert!(defer_archive(db.pool(), archive_id, "still live", 7200, 60)
+        .await
+        .unwrap());
+    let (next_retry_in,): (i64,) = sqlx::query_as(
+        "SELECT CAST(strftime('%s', next_retry_at) - strftime('%s', created_at) AS INTEGER) FROM archives WHERE id = ?",
     )
+    .bind(archive_id)
+    .fetch_one(db.pool())
     .await
-    .expect("Failed to insert video file");
-
-    assert!(video_file_id > 0);
+    .unwrap();
+    assert!(next_retry_in <= 60, "{next_retry_in}");
 
-    // Get by ID
-    let vf = get_video_file(db.pool(), video_file_id)
+    // ...and once the window has passed the archive is no longer deferred
+    sqlx::query("UPDATE archives SET created_at = datetime('now', '-3 days'), next_retry_at = NULL WHERE id = ?")
+        .bind(archive_id)
+        .execute(db.pool())
         .await
-        .expect("Failed to get video file")
-        .expect("Video file not found");
-
-    assert_eq!(vf.video_id, "dQw4w9WgXcQ");
-    assert_eq!(vf.platform, "youtube");
-    assert_eq!(vf.s3_key, "videos/dQw4w9WgXcQ.mp4");
-    assert_eq!(
-        vf.metadata_s3_key.as_deref(),
-        Some("videos/dQw4w9WgXcQ.json")
+        .unwrap();
+    assert!(
+        !defer_archive(db.pool(), archive_id, "still live", 1800, 48 * 3600)
+            .await
+            .unwrap()
     );
-    assert_eq!(vf.size_bytes, Some(12345678));
-    assert_eq!(vf.content_type.as_deref(), Some("video/mp4"));
-    assert_eq!(vf.duration_seconds, Some(212));
+    let pending = get_pending_archives(db.pool(), 10).await.unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, archive_id);
 }
 
 #[tokio::test]
-async fn test_find_video_file_by_platform_and_id() {
+async fn test_archive_short_codes_unique() {
     let (db, _temp_dir) = setup_db().await;
 
-    // Insert video file
+    let mut codes = std::collections::HashSet::new();
+    for i in 0..500 {
+        let url = format!("https://example.com/page-{i}");
+        let link_id = insert_link(db.pool(), &test_link(&url)).await.unwrap();
+        let archive_id = create_pending_archive(db.pool(), link_id, None)
+            .await
+            .unwrap();
+        let archive = get_archive(db.pool(), archive_id).await.unwrap().unwrap();
+        let code = archive.short_code.expect("new archives get a short code");
+        assert!(codes.insert(code.clone()), "duplicate short code {code}");
+
+        let resolved = get_archive_by_short_code(db.pool(), &code)
+            .await
+            .unwrap()
+            .expect("short code resolves");
+        assert_eq!(resolved.id, archive_id);
+    }
+
+    // Re-creating an existing archive keeps its code
+    let link_id = get_link_by_normalized_url(db.pool(), "https://example.com/page-0")
+        .await
+        .unwrap()
+        .unwrap()
+        .id;
+    let archive_id = create_pending_archive(db.pool(), link_id, None)
+        .await
+        .unwrap();
+    let archive = get_archive(db.pool(), archive_id).await.unwrap().unwrap();
+    assert!(codes.contains(archive.short_code.as_deref().unwrap()));
+
+    assert!(get_archive_by_short_code(db.pool(), "zzzzzzzz")
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_rearchive_remembers_previous_primary() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    let link_id = insert_link(pool, &test_link("https://example.com/video"))
+        .await
+        .unwrap();
+    let archive_id = create_pending_archive(pool, link_id, None).await.unwrap();
+    assert!(get_previous_primary(pool, archive_id)
+        .await
+        .unwrap()
+        .is_none());
+
+    let key = "archives/1/media/video.mp4";
+    insert_artifact(
+        pool,
+        archive_id,
+        "video",
+        key,
+        None,
+        Some(5),
+        Some("abc123"),
+    )
+    .await
+    .unwrap();
+    set_archive_complete(pool, archive_id, None, None, None, None, Some(key), None)
+        .await
+        .unwrap();
+
+    reset_archive_for_rearchive(pool, archive_id).await.unwrap();
+    assert_eq!(
+        get_previous_primary(pool, archive_id).await.unwrap(),
+        Some((key.to_string(), "abc123".to_string()))
+    );
+    assert!(get_artifacts_for_archive(pool, archive_id)
+        .await
+        .unwrap()
+        .is_empty());
+
+    // A reset with no primary forgets the old one
+    reset_archive_for_rearchive_preserve_metadata(pool, archive_id)
+        .await
+        .unwrap();
+    assert!(get_previous_primary(pool, archive_id)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_recent_archives() {
+    let (db, _temp_dir) = setup_db().await;
+
+    // Initially no archives
+    let recent = get_recent_archives(db.pool(), 10).await.unwrap();
+    assert!(recent.is_empty());
+}
+
+#[tokio::test]
+async fn test_search_archives() {
+    let (db, _temp_dir) = setup_db().await;
+
+    // Search with no archives should return empty
+    let results = search_archives(db.pool(), "test", 10).await.unwrap();
+    assert!(results.is_empty());
+}
+
+// ========== Video File Tests ==========
+
+#[tokio::test]
+async fn test_insert_and_get_video_file() {
+    let (db, _temp_dir) = setup_db().await;
+
+    // Insert a video file
+    let video_file_id = insert_video_file(
+        db.pool(),
+        "dQw4w9WgXcQ",
+        "youtube",
+        "videos/dQw4w9WgXcQ.mp4",
+        Some("videos/dQw4w9WgXcQ.json"),
+        Some(12345678),
+        Some("video/mp4"),
+        Some(212), // 3:32 in seconds
+    )
+    .await
+    .expect("Failed to insert video file");
+
+    assert!(video_file_id > 0);
+
+    // Get by ID
+    let vf = get_video_file(db.pool(), video_file_id)
+        .await
+        .expect("Failed to get video file")
+        .expect("Video file not found");
+
+    assert_eq!(vf.video_id, "dQw4w9WgXcQ");
+    assert_eq!(vf.platform, "youtube");
+    assert_eq!(vf.s3_key, "videos/dQw4w9WgXcQ.mp4");
+    assert_eq!(
+        vf.metadata_s3_key.as_deref(),
+        Some("videos/dQw4w9WgXcQ.json")
+    );
+    assert_eq!(vf.size_bytes, Some(12345678));
+    assert_eq!(vf.content_type.as_deref(), Some("video/mp4"));
+    assert_eq!(vf.duration_seconds, Some(212));
+}
+
+#[tokio::test]
+async fn test_find_video_file_by_platform_and_id() {
+    let (db, _temp_dir) = setup_db().await;
+
+    // Insert video file
     insert_video_file(
         db.pool(),
         "abc123xyz",
@@ -645,3 +1262,1451 @@ async fn test_get_nsfw_count() {
     let count = get_nsfw_count(db.pool()).await.unwrap();
     assert_eq!(count, 2);
 }
+
+#[tokio::test]
+async fn test_get_sitemap_archives_excludes_nsfw_and_incomplete() {
+    let (db, _temp_dir) = setup_db().await;
+
+    let mut archive_ids = Vec::new();
+    for name in ["public", "nsfw", "pending"] {
+        let link_id = insert_link(
+            db.pool(),
+            &test_link(&format!("https://example.com/{name}")),
+        )
+        .await
+        .unwrap();
+        archive_ids.push(
+            create_pending_archive(db.pool(), link_id, None)
+                .await
+                .unwrap(),
+        );
+    }
+    for &archive_id in &archive_ids[..2] {
+        set_archive_complete(
+            db.pool(),
+            archive_id,
+            Some("Title"),
+            None,
+            None,
+            Some("text"),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+    set_archive_nsfw(db.pool(), archive_ids[1], true, Some("test"))
+        .await
+        .unwrap();
+
+    let entries = get_sitemap_archives(db.pool()).await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].0, archive_ids[0]);
+    assert!(
+        entries[0].1.is_some(),
+        "complete archive should have archived_at"
+    );
+}
+
+#[tokio::test]
+async fn test_random_archive_only_picks_complete_sfw() {
+    let (db, _temp_dir) = setup_db().await;
+    assert_eq!(get_random_archive_id(db.pool()).await.unwrap(), None);
+
+    let mut archive_ids = Vec::new();
+    for name in ["pending", "first", "nsfw", "second", "failed"] {
+        let link_id = insert_link(
+            db.pool(),
+            &test_link(&format!("https://example.com/{name}")),
+        )
+        .await
+        .unwrap();
+        archive_ids.push(
+            create_pending_archive(db.pool(), link_id, None)
+                .await
+                .unwrap(),
+        );
+    }
+    for &archive_id in &archive_ids[1..4] {
+        set_archive_complete(
+            db.pool(),
+            archive_id,
+            Some("Title"),
+            None,
+            None,
+            Some("text"),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+    set_archive_nsfw(db.pool(), archive_ids[2], true, Some("test"))
+        .await
+        .unwrap();
+    set_archive_failed(db.pool(), archive_ids[4], "boom", 1)
+        .await
+        .unwrap();
+
+    let mut picked = std::collections::HashSet::new();
+    for _ in 0..200 {
+        picked.insert(get_random_archive_id(db.pool()).await.unwrap().unwrap());
+    }
+    assert_eq!(
+        picked,
+        std::collections::HashSet::from([archive_ids[1], archive_ids[3]])
+    );
+}
+
+#[tokio::test]
+async fn test_playlist_items_ordered_by_position() {
+    let (db, _temp_dir) = setup_db().await;
+
+    let mut archive_ids = Vec::new();
+    for url in [
+        "https://www.youtube.com/playlist?list=PLtest",
+        "https://www.youtube.com/watch?v=aaa",
+        "https://www.youtube.com/watch?v=bbb",
+        "https://www.youtube.com/watch?v=ccc",
+    ] {
+        let link_id = insert_link(
+            db.pool(),
+            &NewLink {
+                original_url: url.to_string(),
+                normalized_url: url.to_string(),
+                canonical_url: None,
+                domain: "www.youtube.com".to_string(),
+            },
+        )
+        .await
+        .expect("Failed to insert link");
+        let archive_id = create_pending_archive(db.pool(), link_id, None)
+            .await
+            .expect("Failed to create archive");
+        archive_ids.push(archive_id);
+    }
+    let playlist_id = archive_ids[0];
+
+    // Insert members out of order; the listing must follow position.
+    insert_playlist_item(db.pool(), playlist_id, archive_ids[3], 3)
+        .await
+        .expect("Failed to insert playlist item");
+    insert_playlist_item(db.pool(), playlist_id, archive_ids[1], 1)
+        .await
+        .expect("Failed to insert playlist item");
+    insert_playlist_item(db.pool(), playlist_id, archive_ids[2], 2)
+        .await
+        .expect("Failed to insert playlist item");
+
+    // Re-inserting an existing member does not duplicate it.
+    insert_playlist_item(db.pool(), playlist_id, archive_ids[1], 1)
+        .await
+        .expect("Failed to re-insert playlist item");
+
+    let members = get_playlist_members_display(db.pool(), playlist_id)
+        .await
+        .expect("Failed to get playlist members");
+    let member_ids: Vec<i64> = members.iter().map(|m| m.id).collect();
+    assert_eq!(
+        member_ids,
+        vec![archive_ids[1], archive_ids[2], archive_ids[3]]
+    );
+    assert_eq!(
+        members[0].original_url,
+        "https://www.youtube.com/watch?v=aaa"
+    );
+}
+
+#[tokio::test]
+async fn test_excluded_domain_patterns() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    add_excluded_domain(pool, "exact.example.org", "test", None)
+        .await
+        .unwrap();
+    add_excluded_domain(pool, "*.example.com", "test", None)
+        .await
+        .unwrap();
+    add_excluded_domain(pool, ".example.net", "test", None)
+        .await
+        .unwrap();
+
+    // Exact entries match only that host
+    assert!(is_domain_excluded(pool, "exact.example.org").await.unwrap());
+    assert!(!is_domain_excluded(pool, "sub.exact.example.org")
+        .await
+        .unwrap());
+    assert!(!is_domain_excluded(pool, "example.org").await.unwrap());
+
+    // Wildcard and leading-dot entries match the domain and its subdomains
+    assert!(is_domain_excluded(pool, "example.com").await.unwrap());
+    assert!(is_domain_excluded(pool, "cdn.example.com").await.unwrap());
+    assert!(is_domain_excluded(pool, "a.b.example.com").await.unwrap());
+    assert!(is_domain_excluded(pool, "static.example.net")
+        .await
+        .unwrap());
+
+    // Non-matches
+    assert!(!is_domain_excluded(pool, "notexample.com").await.unwrap());
+    assert!(!is_domain_excluded(pool, "example.com.evil.org")
+        .await
+        .unwrap());
+}
+
+#[tokio::test]
+async fn test_domain_quote_policy_overrides_global_default() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    set_domain_quote_policy(pool, "courtlistener.com", true, None)
+        .await
+        .unwrap();
+    set_domain_quote_policy(pool, "*.x.com", false, None)
+        .await
+        .unwrap();
+    set_domain_quote_policy(pool, "media.x.com", true, None)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_domain_quote_override(pool, "courtlistener.com")
+            .await
+            .unwrap(),
+        Some(true)
+    );
+    assert_eq!(
+        get_domain_quote_override(pool, "example.org")
+            .await
+            .unwrap(),
+        None
+    );
+
+    // Domain override beats the global default in both directions
+    assert!(
+        should_archive_quote_only_link(pool, "courtlistener.com", false)
+            .await
+            .unwrap()
+    );
+    assert!(!should_archive_quote_only_link(pool, "x.com", true)
+        .await
+        .unwrap());
+    assert!(!should_archive_quote_only_link(pool, "mobile.x.com", true)
+        .await
+        .unwrap());
+
+    // The most specific entry wins over a wildcard parent
+    assert!(should_archive_quote_only_link(pool, "media.x.com", false)
+        .await
+        .unwrap());
+
+    // Without an override, the global default applies
+    assert!(should_archive_quote_only_link(pool, "example.org", true)
+        .await
+        .unwrap());
+    assert!(!should_archive_quote_only_link(pool, "example.org", false)
+        .await
+        .unwrap());
+
+    // Setting again updates in place; deleting falls back to the default
+    set_domain_quote_policy(pool, "courtlistener.com", false, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        get_domain_quote_override(pool, "courtlistener.com")
+            .await
+            .unwrap(),
+        Some(false)
+    );
+    delete_domain_quote_policy(pool, "courtlistener.com")
+        .await
+        .unwrap();
+    assert_eq!(
+        get_domain_quote_override(pool, "courtlistener.com")
+            .await
+            .unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_priority_domains_jump_the_queue() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    let mut ids = Vec::new();
+    for domain in ["slow.example.com", "news.example.org", "cdn.gov.example"] {
+        let link_id = insert_link(
+            pool,
+            &NewLink {
+                original_url: format!("https://{domain}/story"),
+                normalized_url: format!("https://{domain}/story"),
+                canonical_url: None,
+                domain: domain.to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        ids.push(create_pending_archive(pool, link_id, None).await.unwrap());
+    }
+    // Same age for all of them
+    sqlx::query("UPDATE archives SET created_at = '2024-01-01 00:00:00'")
+        .execute(pool)
+        .await
+        .unwrap();
+
+    let pending_ids = |archives: Vec<discourse_link_archiver::db::Archive>| {
+        archives.into_iter().map(|a| a.id).collect::<Vec<_>>()
+    };
+
+    add_priority_domain(pool, "news.example.org", "", None)
+        .await
+        .unwrap();
+    add_priority_domain(pool, "*.gov.example", "Press releases", None)
+        .await
+        .unwrap();
+    assert_eq!(get_priority_domains(pool).await.unwrap().len(), 2);
+
+    let pending = pending_ids(get_pending_archives(pool, 10).await.unwrap());
+    assert_eq!(pending.len(), 3);
+    assert_eq!(pending[2], ids[0], "non-priority archive should come last");
+
+    // Only one slot: a priority archive takes it
+    let first = pending_ids(get_pending_archives(pool, 1).await.unwrap());
+    assert_ne!(first, vec![ids[0]]);
+
+    delete_priority_domain(pool, "news.example.org")
+        .await
+        .unwrap();
+    delete_priority_domain(pool, "*.gov.example").await.unwrap();
+    assert!(get_priority_domains(pool).await.unwrap().is_empty());
+
+    // Failed archives due for retry are ordered the same way
+    add_priority_domain(pool, "cdn.gov.example", "", None)
+        .await
+        .unwrap();
+    set_archive_failed(pool, ids[0], "boom", 5).await.unwrap();
+    set_archive_failed(pool, ids[2], "boom", 5).await.unwrap();
+    sqlx::query("UPDATE archives SET next_retry_at = NULL")
+        .execute(pool)
+        .await
+        .unwrap();
+    let retry = pending_ids(get_failed_archives_for_retry(pool, 10, 5).await.unwrap());
+    assert_eq!(retry, vec![ids[2], ids[0]]);
+}
+
+#[tokio::test]
+async fn test_database_options_applied() {
+    let temp_dir = TempDir::new().unwrap();
+    let options = DatabaseOptions {
+        max_connections: 3,
+        busy_timeout: std::time::Duration::from_secs(2),
+        cache_size_kb: 8192,
+    };
+    let db = Database::with_options(&temp_dir.path().join("test.sqlite"), &options)
+        .await
+        .unwrap();
+
+    for pool in [db.pool(), db.read_pool()] {
+        assert_eq!(pool.options().get_max_connections(), 3);
+        let cache_size: i64 = sqlx::query_scalar("PRAGMA cache_size")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert_eq!(cache_size, -8192);
+        let busy_timeout: i64 = sqlx::query_scalar("PRAGMA busy_timeout")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert_eq!(busy_timeout, 2000);
+    }
+}
+
+#[tokio::test]
+async fn test_archive_versions() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    let link_id = insert_link(
+        pool,
+        &NewLink {
+            original_url: "https://news.example.org/story".to_string(),
+            normalized_url: "https://news.example.org/story".to_string(),
+            canonical_url: None,
+            domain: "news.example.org".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    let archive_id = create_pending_archive(pool, link_id, None).await.unwrap();
+
+    // Off unless the archive or its domain opts in
+    assert!(!archive_versioning_enabled(pool, archive_id).await.unwrap());
+    set_archive_keep_versions(pool, archive_id, true)
+        .await
+        .unwrap();
+    assert!(archive_versioning_enabled(pool, archive_id).await.unwrap());
+    set_archive_keep_versions(pool, archive_id, false)
+        .await
+        .unwrap();
+    add_versioned_domain(pool, "*.example.org", "Stories get edited", None)
+        .await
+        .unwrap();
+    assert!(archive_versioning_enabled(pool, archive_id).await.unwrap());
+    delete_versioned_domain(pool, "*.example.org")
+        .await
+        .unwrap();
+    assert!(!archive_versioning_enabled(pool, archive_id).await.unwrap());
+
+    assert_eq!(next_archive_version(pool, archive_id).await.unwrap(), 1);
+    for (version, text) in [(1, "The vote passed."), (2, "The vote failed.")] {
+        insert_archive_version(
+            pool,
+            archive_id,
+            version,
+            Some("2024-01-01 00:00:00"),
+            &format!("archives/{link_id}/versions/{version}/"),
+            Some(&format!("archives/{link_id}/versions/{version}/page.html")),
+            Some("Story"),
+            Some(text),
+        )
+        .await
+        .unwrap();
+    }
+    assert_eq!(next_archive_version(pool, archive_id).await.unwrap(), 3);
+
+    // Newest first
+    let versions = get_archive_versions(pool, archive_id).await.unwrap();
+    assert_eq!(
+        versions.iter().map(|v| v.version).collect::<Vec<_>>(),
+        vec![2, 1]
+    );
+    assert_eq!(
+        versions[1].content_text.as_deref(),
+        Some("The vote passed.")
+    );
+    assert_eq!(
+        versions[0].s3_prefix,
+        format!("archives/{link_id}/versions/2/")
+    );
+
+    // Version numbers are unique per archive
+    assert!(
+        insert_archive_version(pool, archive_id, 2, None, "dup/", None, None, None)
+            .await
+            .is_err()
+    );
+
+    delete_archive(pool, archive_id).await.unwrap();
+    assert!(get_archive_versions(pool, archive_id)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_get_pending_counts_by_domain() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    // (domain, pending, processing, complete)
+    for (domain, pending, processing, complete) in [
+        ("slow.example.com", 3, 1, 1),
+        ("fast.example.org", 1, 0, 4),
+        ("busy.example.net", 0, 2, 0),
+        ("done.example.io", 0, 0, 2),
+    ] {
+        for i in 0..(pending + processing + complete) {
+            let link_id = insert_link(
+                pool,
+                &NewLink {
+                    original_url: format!("https://{domain}/{i}"),
+                    normalized_url: format!("https://{domain}/{i}"),
+                    canonical_url: None,
+                    domain: domain.to_string(),
+                },
+            )
+            .await
+            .unwrap();
+            let archive_id = create_pending_archive(pool, link_id, None).await.unwrap();
+            if i >= pending + processing {
+                set_archive_complete(pool, archive_id, None, None, None, None, None, None)
+                    .await
+                    .unwrap();
+            } else if i >= pending {
+                set_archive_processing(pool, archive_id).await.unwrap();
+            }
+        }
+    }
+
+    let counts = get_pending_counts_by_domain(pool, 10).await.unwrap();
+    let summary: Vec<(&str, i64, i64)> = counts
+        .iter()
+        .map(|c| (c.domain.as_str(), c.pending_count, c.processing_count))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("slow.example.com", 3, 1),
+            ("busy.example.net", 0, 2),
+            ("fast.example.org", 1, 0),
+        ]
+    );
+    assert!(counts[0].oldest_pending_at.is_some());
+    assert!(counts[0]
+        .oldest_pending_age_secs
+        .is_some_and(|age| age >= 0));
+    // Only processing archives: no pending age
+    assert!(counts[1].oldest_pending_at.is_none());
+    assert!(counts[1].oldest_pending_age_secs.is_none());
+
+    // Bounded to the top N domains
+    let top = get_pending_counts_by_domain(pool, 1).await.unwrap();
+    assert_eq!(top.len(), 1);
+    assert_eq!(top[0].domain, "slow.example.com");
+}
+
+#[tokio::test]
+async fn test_auto_nsfw_does_not_overwrite_manual_setting() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    let link_id = insert_link(pool, &test_link("https://example.com/photo.jpg"))
+        .await
+        .unwrap();
+    let archive_id = create_pending_archive(pool, link_id, None).await.unwrap();
+
+    // Unset status is filled in, and a later auto pass may revise it
+    assert!(set_archive_nsfw_auto(pool, archive_id, true).await.unwrap());
+    assert!(set_archive_nsfw_auto(pool, archive_id, false)
+        .await
+        .unwrap());
+    let archive = get_archive(pool, archive_id).await.unwrap().unwrap();
+    assert!(!archive.is_nsfw);
+    assert_eq!(archive.nsfw_source.as_deref(), Some("auto"));
+
+    // A manual toggle survives re-archiving and the auto pass that follows
+    assert!(toggle_archive_nsfw(pool, archive_id).await.unwrap());
+    reset_archive_for_rearchive_preserve_metadata(pool, archive_id)
+        .await
+        .unwrap();
+    assert!(!set_archive_nsfw_auto(pool, archive_id, false)
+        .await
+        .unwrap());
+    let archive = get_archive(pool, archive_id).await.unwrap().unwrap();
+    assert!(archive.is_nsfw);
+    assert_eq!(archive.nsfw_source.as_deref(), Some("manual"));
+
+    // Handler-detected status is kept too
+    set_archive_nsfw(pool, archive_id, true, Some("metadata"))
+        .await
+        .unwrap();
+    assert!(!set_archive_nsfw_auto(pool, archive_id, false)
+        .await
+        .unwrap());
+    let archive = get_archive(pool, archive_id).await.unwrap().unwrap();
+    assert!(archive.is_nsfw);
+    assert_eq!(archive.nsfw_source.as_deref(), Some("metadata"));
+}
+
+#[tokio::test]
+async fn test_external_service_rules_override_global_defaults() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+    let defaults = ExternalServices {
+        wayback: true,
+        archive_today: false,
+        ipfs: false,
+    };
+
+    set_external_service_rule(
+        pool,
+        ExternalServiceScope::ContentType,
+        "video",
+        Some(false),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    set_external_service_rule(
+        pool,
+        ExternalServiceScope::Domain,
+        "*.youtube.com",
+        Some(true),
+        Some(true),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // No rule matches
+    assert_eq!(
+        external_services_for(pool, "example.org", "text", defaults)
+            .await
+            .unwrap(),
+        defaults
+    );
+    // Content-type rule beats the global default
+    assert!(
+        !external_services_for(pool, "example.org", "video", defaults)
+            .await
+            .unwrap()
+            .wayback
+    );
+    // Domain rule beats the content-type rule
+    assert_eq!(
+        external_services_for(pool, "www.youtube.com", "video", defaults)
+            .await
+            .unwrap(),
+        ExternalServices {
+            wayback: true,
+            archive_today: true,
+            ipfs: false,
+        }
+    );
+
+    // Setting again updates in place
+    set_external_service_rule(
+        pool,
+        ExternalServiceScope::ContentType,
+        "video",
+        None,
+        None,
+        Some(true),
+        None,
+    )
+    .await
+    .unwrap();
+    let rules = get_external_service_rules(pool).await.unwrap();
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules[0].pattern, "*.youtube.com");
+    let video = rules.iter().find(|r| r.pattern == "video").unwrap();
+    assert_eq!(
+        (video.wayback, video.archive_today, video.ipfs),
+        (None, None, Some(true))
+    );
+
+    // Deleting returns the rule and falls back to the defaults
+    let deleted = delete_external_service_rule(pool, video.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(deleted.scope, "content_type");
+    assert!(delete_external_service_rule(pool, video.id)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        external_services_for(pool, "example.org", "video", defaults)
+            .await
+            .unwrap(),
+        defaults
+    );
+}
+
+#[tokio::test]
+async fn test_get_posts_by_forum_author() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    // The same forum user appears under several raw author strings
+    for (i, author) in [
+        "@alice",
+        "@Alice Alice Smith",
+        "@alicette",
+        "@bob",
+        "alice",
+        "Alice Smith (@ALICE)",
+    ]
+    .iter()
+    .enumerate()
+    {
+        insert_post(
+            pool,
+            &NewPost {
+                guid: format!("guid-{i}"),
+                discourse_url: format!("https://forum.example.com/t/topic/1/{}", i + 1),
+                author: Some((*author).to_string()),
+                author_handle: forum_author_handle(author).map(String::from),
+                title: Some("Topic".to_string()),
+                body_html: None,
+                content_hash: None,
+                published_at: Some(format!("2024-01-0{}T00:00:00Z", i + 1)),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let posts = get_posts_by_forum_author(pool, "alice", 50).await.unwrap();
+    let authors: Vec<&str> = posts.iter().filter_map(|p| p.author.as_deref()).collect();
+    assert_eq!(
+        authors,
+        vec![
+            "Alice Smith (@ALICE)",
+            "alice",
+            "@Alice Alice Smith",
+            "@alice"
+        ]
+    );
+
+    let posts = get_posts_by_forum_author(pool, "bob", 50).await.unwrap();
+    assert_eq!(posts.len(), 1);
+
+    let posts = get_posts_by_forum_author(pool, "nobody", 50).await.unwrap();
+    assert!(posts.is_empty());
+}
+
+#[tokio::test]
+async fn test_watched_link_scheduling_and_versions() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    let link_id = insert_link(
+        pool,
+        &NewLink {
+            original_url: "https://example.com/statement".to_string(),
+            normalized_url: "https://example.com/statement".to_string(),
+            canonical_url: None,
+            domain: "example.com".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+
+    watch_link(pool, link_id, 24, None).await.unwrap();
+    let due = get_due_watched_links(pool, 10).await.unwrap();
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].check_interval_hours, 24);
+
+    // Once checked, the link isn't due again until the interval passes
+    mark_watched_link_checked(pool, link_id).await.unwrap();
+    assert!(get_due_watched_links(pool, 10).await.unwrap().is_empty());
+    let watched = get_watched_link(pool, link_id).await.unwrap().unwrap();
+    assert!(watched.last_checked_at.is_some());
+    assert!(watched.next_check_at.is_some());
+
+    let first = insert_content_version(pool, link_id, None, None, "h1", "one")
+        .await
+        .unwrap();
+    let second = insert_content_version(pool, link_id, None, Some(first), "h2", "two")
+        .await
+        .unwrap();
+    let latest = get_latest_content_version(pool, link_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(latest.id, second);
+    assert_eq!(latest.previous_version_id, Some(first));
+
+    let versions = get_content_versions_for_link(pool, link_id).await.unwrap();
+    assert_eq!(
+        versions.iter().map(|v| v.id).collect::<Vec<_>>(),
+        vec![second, first]
+    );
+
+    // Unwatching keeps the recorded history
+    unwatch_link(pool, link_id).await.unwrap();
+    assert!(get_watched_link(pool, link_id).await.unwrap().is_none());
+    assert_eq!(
+        get_content_versions_for_link(pool, link_id)
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn test_archives_eligible_for_pruning() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    // (status, retry_count, days since last attempt)
+    let cases = [
+        ("failed", 3, 40),   // old, retries exhausted: eligible
+        ("failed", 3, 5),    // too recent
+        ("failed", 1, 40),   // still has retries left
+        ("skipped", 0, 40),  // only with include_skipped
+        ("skipped", 0, 5),   // too recent even with include_skipped
+        ("complete", 0, 40), // never pruned
+    ];
+    let mut ids = Vec::new();
+    for (i, (status, retries, age_days)) in cases.iter().enumerate() {
+        let url = format!("https://example.com/prune/{i}");
+        let link_id = insert_link(
+            pool,
+            &NewLink {
+                original_url: url.clone(),
+                normalized_url: url,
+                canonical_url: None,
+                domain: "example.com".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        let archive_id = create_pending_archive(pool, link_id, None).await.unwrap();
+        sqlx::query(
+            "UPDATE archives SET status = ?, retry_count = ?, last_attempt_at = datetime('now', ?) WHERE id = ?",
+        )
+        .bind(status)
+        .bind(retries)
+        .bind(format!("-{age_days} days"))
+        .bind(archive_id)
+        .execute(pool)
+        .await
+        .unwrap();
+        ids.push(archive_id);
+    }
+
+    let eligible = |include_skipped| async move {
+        get_archives_eligible_for_pruning(pool, 30, 3, include_skipped, 100)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|a| a.id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(eligible(false).await, vec![ids[0]]);
+    assert_eq!(eligible(true).await, vec![ids[0], ids[3]]);
+
+    // Never-attempted archives fall back to their creation time
+    sqlx::query(
+        "UPDATE archives SET retry_count = 3, last_attempt_at = NULL, created_at = datetime('now', '-40 days') WHERE id = ?",
+    )
+    .bind(ids[2])
+        .execute(pool)
+        .await
+        .unwrap();
+    assert_eq!(eligible(false).await, vec![ids[0], ids[2]]);
+}
+
+#[tokio::test]
+async fn test_dry_run_skips_are_requeued_and_never_pruned() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    let link_id = insert_link(pool, &test_link("https://example.com/dry"))
+        .await
+        .unwrap();
+    let dry_run = create_pending_archive(pool, link_id, None).await.unwrap();
+    set_archive_dry_run(pool, dry_run, "would fetch HTML")
+        .await
+        .unwrap();
+    let link_id = insert_link(pool, &test_link("https://example.com/excluded"))
+        .await
+        .unwrap();
+    let excluded = create_pending_archive(pool, link_id, None).await.unwrap();
+    set_archive_skipped(pool, excluded, "excluded_domain")
+        .await
+        .unwrap();
+    sqlx::query("UPDATE archives SET last_attempt_at = datetime('now', '-40 days')")
+        .execute(pool)
+        .await
+        .unwrap();
+
+    // A dry run isn't a finished archive, so retention leaves it alone
+    let eligible: Vec<i64> = get_archives_eligible_for_pruning(pool, 30, 3, true, 100)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|a| a.id)
+        .collect();
+    assert_eq!(eligible, vec![excluded]);
+
+    // Only the dry-run skip goes back to the queue
+    assert_eq!(reset_dry_run_archives(pool).await.unwrap(), 1);
+    let archive = get_archive(pool, dry_run).await.unwrap().unwrap();
+    assert_eq!(archive.status, "pending");
+    assert_eq!(archive.skip_reason, None);
+    assert_eq!(archive.error_message, None);
+    let archive = get_archive(pool, excluded).await.unwrap().unwrap();
+    assert_eq!(archive.status, "skipped");
+}
+
+#[tokio::test]
+async fn test_archive_query_combines_source_and_nsfw() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    // (domain, content_type, nsfw)
+    let cases = [
+        ("old.reddit.com", "text", false),
+        ("reddit.com", "image", true),
+        ("youtube.com", "video", false),
+        ("reddit.com", "video", false),
+    ];
+    let mut ids = Vec::new();
+    for (i, (domain, content_type, nsfw)) in cases.iter().enumerate() {
+        let url = format!("https://{domain}/query/{i}");
+        let link_id = insert_link(
+            pool,
+            &NewLink {
+                original_url: url.clone(),
+                normalized_url: url,
+                canonical_url: None,
+                domain: (*domain).to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        let archive_id = create_pending_archive(pool, link_id, None).await.unwrap();
+        set_archive_complete(
+            pool,
+            archive_id,
+            None,
+            None,
+            None,
+            Some(content_type),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        set_archive_nsfw(pool, archive_id, *nsfw, Some("test"))
+            .await
+            .unwrap();
+        ids.push(archive_id);
+    }
+
+    let ids_of = |archives: Vec<discourse_link_archiver::db::Archive>| {
+        let mut ids: Vec<_> = archives.into_iter().map(|a| a.id).collect();
+        ids.sort_unstable();
+        ids
+    };
+
+    let sfw_reddit = ArchiveQuery::new()
+        .status("complete")
+        .source(Some("reddit"))
+        .nsfw(Some(false));
+    assert_eq!(
+        ids_of(sfw_reddit.fetch_archives(pool).await.unwrap()),
+        vec![ids[0], ids[3]]
+    );
+    assert_eq!(sfw_reddit.count(pool).await.unwrap(), 2);
+
+    let reddit_video = sfw_reddit.content_type(Some("video"));
+    assert_eq!(
+        ids_of(reddit_video.fetch_archives(pool).await.unwrap()),
+        vec![ids[3]]
+    );
+
+    let display = ArchiveQuery::new()
+        .source(Some("reddit"))
+        .nsfw(Some(true))
+        .fetch_display(pool)
+        .await
+        .unwrap();
+    assert_eq!(display.len(), 1);
+    assert_eq!(display[0].id, ids[1]);
+
+    let paged = ArchiveQuery::new()
+        .sort(ArchiveSort::Id)
+        .limit(2)
+        .offset(1)
+        .fetch_archives(pool)
+        .await
+        .unwrap();
+    assert_eq!(
+        paged.iter().map(|a| a.id).collect::<Vec<_>>(),
+        vec![ids[2], ids[1]]
+    );
+}
+
+#[tokio::test]
+async fn test_thumbnail_placeholders_for_archives() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    let mut archive_ids = Vec::new();
+    for i in 0..2 {
+        let url = format!("https://example.com/thumb/{i}");
+        let link_id = insert_link(
+            pool,
+            &NewLink {
+                original_url: url.clone(),
+                normalized_url: url,
+                canonical_url: None,
+                domain: "example.com".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        archive_ids.push(create_pending_archive(pool, link_id, None).await.unwrap());
+    }
+
+    let thumb_id = insert_artifact(
+        pool,
+        archive_ids[0],
+        "thumb",
+        "archives/1/thumb.jpg",
+        Some("image/jpeg"),
+        Some(100),
+        None,
+    )
+    .await
+    .unwrap();
+    insert_artifact(
+        pool,
+        archive_ids[0],
+        "image",
+        "archives/1/a.jpg",
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    set_artifact_placeholder(pool, thumb_id, "#102030", 640, 360)
+        .await
+        .unwrap();
+
+    let thumbs = get_thumbnails_for_archives(pool, &archive_ids)
+        .await
+        .unwrap();
+    assert_eq!(thumbs.len(), 1);
+    let thumb = &thumbs[&archive_ids[0]];
+    assert_eq!(thumb.id, thumb_id);
+    assert_eq!(thumb.placeholder_color.as_deref(), Some("#102030"));
+    assert_eq!((thumb.width, thumb.height), (Some(640), Some(360)));
+
+    assert!(get_thumbnails_for_archives(pool, &[])
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_insert_artifact_with_encoding() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    let url = "https://example.com/complete".to_string();
+    let link_id = insert_link(
+        pool,
+        &NewLink {
+            original_url: url.clone(),
+            normalized_url: url,
+            canonical_url: None,
+            domain: "example.com".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    let archive_id = create_pending_archive(pool, link_id, None).await.unwrap();
+
+    insert_artifact_with_encoding(
+        pool,
+        archive_id,
+        "complete_html",
+        "archives/1/media/complete.html",
+        Some("text/html"),
+        Some(42),
+        Some("zstd"),
+    )
+    .await
+    .unwrap();
+
+    let artifacts = get_artifacts_for_archive(pool, archive_id).await.unwrap();
+    assert_eq!(artifacts.len(), 1);
+    assert_eq!(artifacts[0].content_encoding.as_deref(), Some("zstd"));
+    assert_eq!(artifacts[0].size_bytes, Some(42));
+}
+
+#[tokio::test]
+async fn test_pending_ipfs_pins_queue() {
+    let (db, _temp_dir) = setup_db().await;
+
+    let mut ids = Vec::new();
+    for n in 0..3 {
+        let link_id = get_or_create_link(
+            db.pool(),
+            &NewLink {
+                original_url: format!("https://example.com/{n}"),
+                normalized_url: format!("https://example.com/{n}"),
+                canonical_url: None,
+                domain: "example.com".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        ids.push(
+            create_pending_archive(db.pool(), link_id, None)
+                .await
+                .unwrap(),
+        );
+    }
+
+    for id in &ids {
+        mark_ipfs_pin_pending(db.pool(), *id).await.unwrap();
+    }
+    // Marking twice keeps a single entry
+    mark_ipfs_pin_pending(db.pool(), ids[0]).await.unwrap();
+
+    let pending: Vec<i64> = get_pending_ipfs_pins(db.pool(), 10)
+        .await
+        .unwrap()
+        .iter()
+        .map(|a| a.id)
+        .collect();
+    assert_eq!(pending, ids);
+    assert_eq!(get_pending_ipfs_pins(db.pool(), 1).await.unwrap().len(), 1);
+
+    // Pinning or clearing removes an archive from the queue
+    set_archive_ipfs_cid(db.pool(), ids[0], "bafyexample")
+        .await
+        .unwrap();
+    clear_ipfs_pin_pending(db.pool(), ids[1]).await.unwrap();
+    let pending = get_pending_ipfs_pins(db.pool(), 10).await.unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, ids[2]);
+}
+
+#[tokio::test]
+async fn test_archive_time_window_boundaries() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    // Post dates mix RFC 3339 (from feeds) and SQLite datetime formats
+    let post_dates = [
+        "2024-01-01T00:00:00Z",
+        "2024-01-02 00:00:00",
+        "2024-01-03T02:00:00+02:00",
+        "2024-01-04T00:00:00Z",
+    ];
+    let mut ids = Vec::new();
+    for (n, post_date) in post_dates.iter().enumerate() {
+        let link_id = get_or_create_link(
+            pool,
+            &NewLink {
+                original_url: format!("https://example.com/window/{n}"),
+                normalized_url: format!("https://example.com/window/{n}"),
+                canonical_url: None,
+                domain: "example.com".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        let id = create_pending_archive(pool, link_id, Some(post_date))
+            .await
+            .unwrap();
+        set_archive_complete(pool, id, None, None, None, Some("text"), None, None)
+            .await
+            .unwrap();
+        ids.push(id);
+    }
+
+    let ids_of = |archives: Vec<discourse_link_archiver::db::Archive>| -> Vec<i64> {
+        archives.iter().map(|a| a.id).collect()
+    };
+
+    // `since` is inclusive and results come oldest first
+    let since = get_archives_since(pool, "2024-01-02T00:00:00Z", 10)
+        .await
+        .unwrap();
+    assert_eq!(ids_of(since), vec![ids[1], ids[2], ids[3]]);
+    let since = get_archives_since(pool, "2024-01-02T00:00:01Z", 10)
+        .await
+        .unwrap();
+    assert_eq!(ids_of(since), vec![ids[2], ids[3]]);
+    let since = get_archives_since(pool, "2024-01-01T00:00:00Z", 2)
+        .await
+        .unwrap();
+    assert_eq!(ids_of(since), vec![ids[0], ids[1]]);
+
+    // `before` is exclusive
+    let window = ArchiveQuery::new()
+        .status("complete")
+        .since(Some("2024-01-01 00:00:00"))
+        .before(Some("2024-01-03T00:00:00Z"))
+        .fetch_archives(pool)
+        .await
+        .unwrap();
+    assert_eq!(ids_of(window), vec![ids[1], ids[0]]);
+}
+
+#[tokio::test]
+async fn test_set_archive_failed_stops_scheduling_at_max_retries() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    let link_id = get_or_create_link(
+        pool,
+        &NewLink {
+            original_url: "https://example.com/flaky".to_string(),
+            normalized_url: "https://example.com/flaky".to_string(),
+            canonical_url: None,
+            domain: "example.com".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    let id = create_pending_archive(pool, link_id, None).await.unwrap();
+
+    set_archive_failed(pool, id, "timeout", 2).await.unwrap();
+    let archive = get_archive(pool, id).await.unwrap().unwrap();
+    assert_eq!(archive.retry_count, 1);
+    assert!(archive.next_retry_at.is_some());
+
+    // The second failure uses up both attempts, so nothing is scheduled
+    set_archive_failed(pool, id, "timeout", 2).await.unwrap();
+    let archive = get_archive(pool, id).await.unwrap().unwrap();
+    assert_eq!(archive.retry_count, 2);
+    assert_eq!(archive.next_retry_at, None);
+    assert_eq!(archive.status, "failed");
+}
+
+#[tokio::test]
+async fn test_failure_kinds_counted_and_filtered() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    let mut ids = Vec::new();
+    for path in ["gone", "also-gone", "throttled", "legacy"] {
+        let url = format!("https://example.com/{path}");
+        let link_id = get_or_create_link(
+            pool,
+            &NewLink {
+                original_url: url.clone(),
+                normalized_url: url,
+                canonical_url: None,
+                domain: "example.com".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        let id = create_pending_archive(pool, link_id, None).await.unwrap();
+        set_archive_failed(pool, id, "failed", 1).await.unwrap();
+        ids.push(id);
+    }
+    set_archive_error_kind(pool, ids[0], "not_found")
+        .await
+        .unwrap();
+    set_archive_error_kind(pool, ids[1], "not_found")
+        .await
+        .unwrap();
+    set_archive_error_kind(pool, ids[2], "rate_limited")
+        .await
+        .unwrap();
+
+    let counts = get_failure_kind_counts(pool).await.unwrap();
+    assert_eq!(
+        counts,
+        vec![
+            (Some("not_found".to_string()), 2),
+            (None, 1),
+            (Some("rate_limited".to_string()), 1),
+        ]
+    );
+
+    assert_eq!(
+        get_recent_failed_archives(pool, None, None, 10)
+            .await
+            .unwrap()
+            .len(),
+        4
+    );
+    let not_found = get_recent_failed_archives(pool, Some("not_found"), None, 10)
+        .await
+        .unwrap();
+    assert_eq!(not_found.len(), 2);
+    assert!(not_found
+        .iter()
+        .all(|a| a.error_kind.as_deref() == Some("not_found")));
+
+    // Re-archiving clears the kind along with the message
+    reset_archive_for_rearchive(pool, ids[0]).await.unwrap();
+    let archive = get_archive(pool, ids[0]).await.unwrap().unwrap();
+    assert_eq!(archive.error_kind, None);
+}
+
+#[tokio::test]
+async fn test_skip_reasons_recorded_filtered_and_cleared() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    let mut ids = Vec::new();
+    for reason in SkipReason::ALL {
+        let url = format!("https://example.com/{}", reason.as_str());
+        let link_id = insert_link(pool, &test_link(&url)).await.unwrap();
+        let id = create_pending_archive(pool, link_id, None).await.unwrap();
+        if reason == SkipReason::DryRun {
+            set_archive_dry_run(pool, id, "would run yt-dlp")
+                .await
+                .unwrap();
+        } else {
+            set_archive_skipped(pool, id, reason.as_str())
+                .await
+                .unwrap();
+        }
+        ids.push(id);
+    }
+
+    // Each skip path records its reason
+    for (reason, id) in SkipReason::ALL.into_iter().zip(&ids) {
+        let archive = get_archive(pool, *id).await.unwrap().unwrap();
+        assert_eq!(archive.status, "skipped");
+        assert_eq!(archive.skip_reason.as_deref(), Some(reason.as_str()));
+    }
+
+    let counts = get_skip_reason_counts(pool).await.unwrap();
+    assert_eq!(counts.len(), SkipReason::ALL.len());
+    assert!(counts.iter().all(|(_, count)| *count == 1));
+
+    let excluded = get_recent_failed_archives(pool, None, Some("excluded_domain"), 10)
+        .await
+        .unwrap();
+    assert_eq!(excluded.len(), 1);
+    assert_eq!(excluded[0].id, ids[0]);
+
+    // Resetting a skipped archive clears its reason
+    assert!(reset_single_skipped_archive(pool, ids[0]).await.unwrap());
+    let archive = get_archive(pool, ids[0]).await.unwrap().unwrap();
+    assert_eq!(archive.status, "pending");
+    assert_eq!(archive.skip_reason, None);
+    assert!(
+        get_recent_failed_archives(pool, None, Some("excluded_domain"), 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_storage_by_domain_groups_and_orders() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    // (domain, artifact sizes per archive)
+    let seed: [(&str, &[&[i64]]); 3] = [
+        ("small.example.com", &[&[100], &[50]]),
+        ("big.example.com", &[&[4_000, 1_000], &[]]),
+        ("empty.example.com", &[&[]]),
+    ];
+    for (domain, archives) in seed {
+        for (n, sizes) in archives.iter().enumerate() {
+            let url = format!("https://{domain}/{n}");
+            let link_id = get_or_create_link(
+                pool,
+                &NewLink {
+                    original_url: url.clone(),
+                    normalized_url: url,
+                    canonical_url: None,
+                    domain: domain.to_string(),
+                },
+            )
+            .await
+            .unwrap();
+            let archive_id = create_pending_archive(pool, link_id, None).await.unwrap();
+            for (i, size) in sizes.iter().enumerate() {
+                insert_artifact(
+                    pool,
+                    archive_id,
+                    "html",
+                    &format!("{domain}/{n}/{i}"),
+                    None,
+                    Some(*size),
+                    None,
+                )
+                .await
+                .unwrap();
+            }
+        }
+    }
+
+    let storage = get_storage_by_domain(pool, 10).await.unwrap();
+    let rows: Vec<(&str, i64, i64)> = storage
+        .iter()
+        .map(|d| (d.domain.as_str(), d.archive_count, d.total_bytes))
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            ("big.example.com", 2, 5_000),
+            ("small.example.com", 2, 150),
+            ("empty.example.com", 1, 0),
+        ]
+    );
+
+    assert_eq!(get_storage_by_domain(pool, 1).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_domain_stats_include_subdomains() {
+    let (db, _temp_dir) = setup_db().await;
+    let pool = db.pool();
+
+    // (domain, content type once complete, artifact size)
+    let seed = [
+        ("example.com", Some("video"), 1_000),
+        ("example.com", Some("video"), 500),
+        ("media.example.com", Some("image"), 2_000),
+        ("example.com", None, 0),
+        ("notexample.com", Some("video"), 9_000),
+    ];
+    for (n, (domain, content_type, size)) in seed.into_iter().enumerate() {
+        let url = format!("https://{domain}/{n}");
+        let link_id = get_or_create_link(
+            pool,
+            &NewLink {
+                original_url: url.clone(),
+                normalized_url: url,
+                canonical_url: None,
+                domain: domain.to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        let archive_id = create_pending_archive(pool, link_id, None).await.unwrap();
+        if let Some(content_type) = content_type {
+            set_archive_complete(
+                pool,
+                archive_id,
+                None,
+                None,
+                None,
+                Some(content_type),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+            insert_artifact(
+                pool,
+                archive_id,
+                "html",
+                &format!("{domain}/{n}"),
+                None,
+                Some(size),
+                None,
+            )
+            .await
+            .unwrap();
+        }
+    }
+
+    let stats = get_domain_stats(pool, "example.com").await.unwrap();
+    let rows: Vec<(&str, i64, i64)> = stats
+        .domains
+        .iter()
+        .map(|d| (d.domain.as_str(), d.archive_count, d.total_bytes))
+        .collect();
+    // The pending archive counts but adds no storage; notexample.com is excluded
+    assert_eq!(
+        rows,
+        vec![("media.example.com", 1, 2_000), ("example.com", 3, 1_500)]
+    );
+    assert_eq!(stats.archive_count(), 4);
+    assert_eq!(stats.total_bytes(), 3_500);
+    assert_eq!(
+        stats.content_types,
+        vec![("video".to_string(), 2), ("image".to_string(), 1)]
+    );
+
+    let empty = get_domain_stats(pool, "unknown.example.org").await.unwrap();
+    assert_eq!(empty.archive_count(), 0);
+    assert!(empty.content_types.is_empty());
+}
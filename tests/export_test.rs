@@ -30,6 +30,7 @@ async fn create_test_app(db: Database, s3: Arc<S3Client>) -> Router {
         stats_cache: Arc::new(discourse_link_archiver::web::StatsCache::new(
             Duration::from_secs(60),
         )),
+        sitemap_cache: Arc::new(discourse_link_archiver::web::SitemapCache::default()),
     };
 
     // Build the router with export route
@@ -146,6 +147,7 @@ async fn test_export_rate_limiting() {
             stats_cache: Arc::new(discourse_link_archiver::web::StatsCache::new(
                 Duration::from_secs(60),
             )),
+            sitemap_cache: Arc::new(discourse_link_archiver::web::SitemapCache::default()),
         });
 
     // Try to export again from the same IP
@@ -392,7 +394,7 @@ async fn test_export_only_includes_completed_archives() {
     .unwrap();
 
     // Fail archive3
-    discourse_link_archiver::db::set_archive_failed(db.pool(), archive3_id, "Test failure")
+    discourse_link_archiver::db::set_archive_failed(db.pool(), archive3_id, "Test failure", 3)
         .await
         .unwrap();
 
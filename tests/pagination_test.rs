@@ -436,8 +436,8 @@ async fn test_search_pagination_middle_page() {
 #[tokio::test]
 async fn test_site_list_pagination() {
     let (db, _temp_dir) = setup_db().await;
-    create_test_archives(&db, 30, "youtube").await;
-    create_test_archives(&db, 10, "twitter").await;
+    create_test_archives(&db, 30, "youtube.com").await;
+    create_test_archives(&db, 10, "twitter.com").await;
 
     let app = create_test_app(db.clone());
 
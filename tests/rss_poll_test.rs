@@ -4,11 +4,15 @@ use std::time::Duration;
 
 use discourse_link_archiver::config::Config;
 use discourse_link_archiver::db::{
-    get_link_by_normalized_url, get_pending_archives, get_post_by_guid, Database,
+    add_excluded_domain, create_user, get_audit_events_filtered, get_feed_state,
+    get_link_by_normalized_url, get_pending_archives, get_post_by_guid, get_post_snapshot_archive,
+    get_thread_archive_job, insert_post, insert_thread_archive_job,
+    set_thread_archive_job_cancelled, Database, NewPost, NewThreadArchiveJob,
 };
 use discourse_link_archiver::rss::poll_once;
+use discourse_link_archiver::rss::thread_archive_worker::process_job;
 use tempfile::TempDir;
-use wiremock::matchers::{method, path};
+use wiremock::matchers::{method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 /// Create a test configuration with the given RSS URL.
@@ -282,6 +286,464 @@ async fn test_poll_once_handles_multiple_posts() {
     assert!(post2.is_some());
 }
 
+/// Build a posts.json body with `count` posts, each linking to its own page
+/// and to one page shared by every post.
+fn numbered_posts_json(count: i64) -> String {
+    let posts: Vec<_> = (1..=count)
+        .map(|id| {
+            serde_json::json!({
+                "id": id,
+                "post_number": 1,
+                "username": "testuser",
+                "topic_id": 100 + id,
+                "topic_slug": format!("topic-{id}"),
+                "topic_title": format!("Topic {id}"),
+                "created_at": format!("2024-01-01T12:{id:02}:00.000Z"),
+                "updated_at": format!("2024-01-01T12:{id:02}:00.000Z"),
+                "cooked": format!(
+                    "<p><a href=\"https://example.com/page-{id}\">Own</a> \
+                     <a href=\"https://example.com/shared\">Shared</a></p>"
+                ),
+                "post_url": format!("/t/topic-{id}/{}/1", 100 + id),
+            })
+        })
+        .collect();
+    serde_json::json!({ "latest_posts": posts }).to_string()
+}
+
+async fn row_counts(db: &Database) -> [i64; 4] {
+    let mut counts = [0; 4];
+    for (count, table) in counts
+        .iter_mut()
+        .zip(["posts", "links", "link_occurrences", "archives"])
+    {
+        *count = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {table}"))
+            .fetch_one(db.pool())
+            .await
+            .expect("Failed to count rows");
+    }
+    counts
+}
+
+#[tokio::test]
+async fn test_poll_once_ingests_batch_in_one_transaction() {
+    let (db, temp_dir) = setup_db().await;
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/posts.json"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw(numbered_posts_json(20), "application/json"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let config = create_test_config(
+        &format!("{}/posts.json", mock_server.uri()),
+        temp_dir.path(),
+    );
+    let client = reqwest::Client::new();
+
+    assert_eq!(poll_once(&client, &config, &db).await.unwrap(), 20);
+    // 20 own links plus the shared one; every post links to two pages
+    assert_eq!(row_counts(&db).await, [20, 21, 40, 21]);
+
+    // The shared link is deduplicated across posts within the batch
+    let shared = get_link_by_normalized_url(db.pool(), "https://example.com/shared")
+        .await
+        .unwrap();
+    assert!(shared.is_some());
+
+    // Re-polling the same page changes nothing
+    assert_eq!(poll_once(&client, &config, &db).await.unwrap(), 0);
+    assert_eq!(row_counts(&db).await, [20, 21, 40, 21]);
+}
+
+#[tokio::test]
+async fn test_poll_once_rolls_back_failed_batch() {
+    let (db, temp_dir) = setup_db().await;
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/posts.json"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw(numbered_posts_json(5), "application/json"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let config = create_test_config(
+        &format!("{}/posts.json", mock_server.uri()),
+        temp_dir.path(),
+    );
+    let client = reqwest::Client::new();
+
+    // Fail the insert of the third post, after two posts and their links
+    sqlx::query(
+        r"
+        CREATE TRIGGER fail_third_post BEFORE INSERT ON posts
+        WHEN NEW.guid LIKE '%-post-3'
+        BEGIN SELECT RAISE(ABORT, 'simulated failure'); END
+        ",
+    )
+    .execute(db.pool())
+    .await
+    .unwrap();
+
+    assert!(poll_once(&client, &config, &db).await.is_err());
+    assert_eq!(row_counts(&db).await, [0, 0, 0, 0]);
+
+    // Once the failure clears, the whole page is ingested
+    sqlx::query("DROP TRIGGER fail_third_post")
+        .execute(db.pool())
+        .await
+        .unwrap();
+    assert_eq!(poll_once(&client, &config, &db).await.unwrap(), 5);
+    assert_eq!(row_counts(&db).await, [5, 6, 10, 6]);
+}
+
+#[tokio::test]
+async fn test_poll_once_skips_authors_not_on_allowlist() {
+    let (db, temp_dir) = setup_db().await;
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/posts.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(SAMPLE_JSON, "application/json"))
+        .mount(&mock_server)
+        .await;
+
+    let config = Config {
+        archive_author_allowlist: vec!["someone_else".to_string()],
+        ..create_test_config(
+            &format!("{}/posts.json", mock_server.uri()),
+            temp_dir.path(),
+        )
+    };
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap();
+
+    let new_count = poll_once(&client, &config, &db)
+        .await
+        .expect("poll_once failed");
+
+    // The post itself is still stored, but none of its links are
+    assert_eq!(new_count, 1);
+    let link_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM links")
+        .fetch_one(db.pool())
+        .await
+        .unwrap();
+    assert_eq!(link_count.0, 0);
+
+    // The skip is recorded for admins
+    let events = get_audit_events_filtered(db.pool(), Some("post_author_not_allowed"), None, 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].target_type.as_deref(), Some("post"));
+    assert!(events[0].metadata.as_deref().unwrap().contains("@testuser"));
+
+    // Polling again doesn't record the same post twice
+    poll_once(&client, &config, &db)
+        .await
+        .expect("poll_once failed");
+    let events = get_audit_events_filtered(db.pool(), Some("post_author_not_allowed"), None, 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 1);
+}
+
+#[tokio::test]
+async fn test_poll_once_archives_allowlisted_author_case_insensitively() {
+    let (db, temp_dir) = setup_db().await;
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/posts.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(SAMPLE_JSON, "application/json"))
+        .mount(&mock_server)
+        .await;
+
+    let config = Config {
+        archive_author_allowlist: vec!["@TestUser".to_string()],
+        ..create_test_config(
+            &format!("{}/posts.json", mock_server.uri()),
+            temp_dir.path(),
+        )
+    };
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap();
+
+    poll_once(&client, &config, &db)
+        .await
+        .expect("poll_once failed");
+
+    let link_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM links")
+        .fetch_one(db.pool())
+        .await
+        .unwrap();
+    assert_eq!(link_count.0, 2);
+}
+
+#[tokio::test]
+async fn test_poll_once_queues_post_snapshot_despite_self_exclusion() {
+    let (db, temp_dir) = setup_db().await;
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/posts.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(SAMPLE_JSON, "application/json"))
+        .mount(&mock_server)
+        .await;
+
+    // The forum domain is self-excluded, as it is at startup
+    add_excluded_domain(db.pool(), "127.0.0.1", "Self-archive exclusion", None)
+        .await
+        .unwrap();
+
+    let rss_url = format!("{}/posts.json", mock_server.uri());
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap();
+
+    // Without the opt-in, no snapshot is queued
+    let config = create_test_config(&rss_url, temp_dir.path());
+    poll_once(&client, &config, &db)
+        .await
+        .expect("poll_once failed");
+    let post = get_post_by_guid(db.pool(), "127.0.0.1-post-123")
+        .await
+        .unwrap()
+        .expect("post stored");
+    assert!(get_post_snapshot_archive(db.pool(), post.id)
+        .await
+        .unwrap()
+        .is_none());
+
+    // With the opt-in, new posts get a pending archive of their own page
+    let (db, temp_dir) = setup_db().await;
+    add_excluded_domain(db.pool(), "127.0.0.1", "Self-archive exclusion", None)
+        .await
+        .unwrap();
+    let config = Config {
+        archive_post_snapshots: true,
+        ..create_test_config(&rss_url, temp_dir.path())
+    };
+    poll_once(&client, &config, &db)
+        .await
+        .expect("poll_once failed");
+    let post = get_post_by_guid(db.pool(), "127.0.0.1-post-123")
+        .await
+        .unwrap()
+        .expect("post stored");
+    let snapshot = get_post_snapshot_archive(db.pool(), post.id)
+        .await
+        .unwrap()
+        .expect("snapshot archive queued");
+    assert_eq!(snapshot.status, "pending");
+
+    let (original_url,): (String,) = sqlx::query_as("SELECT original_url FROM links WHERE id = ?")
+        .bind(snapshot.link_id)
+        .fetch_one(db.pool())
+        .await
+        .unwrap();
+    assert_eq!(original_url, post.discourse_url);
+
+    // The post's own links are still archived as usual
+    let pending = get_pending_archives(db.pool(), 10).await.unwrap();
+    assert_eq!(pending.len(), 3);
+}
+
+/// A `/posts.json` page holding posts with the given IDs.
+fn old_posts_page(ids: &[i64]) -> String {
+    let posts: Vec<String> = ids
+        .iter()
+        .map(|id| {
+            format!(
+                r#"{{"id": {id}, "post_number": 1, "username": "testuser", "topic_id": {id},
+                    "topic_slug": "t{id}", "topic_title": "Post {id}",
+                    "created_at": "2020-01-01T12:00:{id:02}.000Z",
+                    "updated_at": "2020-01-01T12:00:{id:02}.000Z",
+                    "cooked": "<p>No links</p>", "post_url": "/t/t{id}/{id}/1"}}"#
+            )
+        })
+        .collect();
+    format!(r#"{{"latest_posts": [{}]}}"#, posts.join(","))
+}
+
+/// Mount a three-page feed: posts 6-5, then 4-3 before 5, then 2-1 before 3.
+async fn mount_paged_feed(server: &MockServer) {
+    let pages: [(&str, &[i64]); 3] = [("5", &[4, 3]), ("3", &[2, 1]), ("1", &[])];
+    for (before, ids) in pages {
+        let body = old_posts_page(ids);
+        Mock::given(method("GET"))
+            .and(path("/posts.json"))
+            .and(query_param("before", before))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .with_priority(1)
+            .mount(server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/posts.json"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw(old_posts_page(&[6, 5]), "application/json"),
+        )
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_poll_once_stops_at_max_pages_without_backfill() {
+    let (db, temp_dir) = setup_db().await;
+    let mock_server = MockServer::start().await;
+    mount_paged_feed(&mock_server).await;
+
+    let config = Config {
+        rss_max_pages: 2,
+        ..create_test_config(
+            &format!("{}/posts.json", mock_server.uri()),
+            temp_dir.path(),
+        )
+    };
+    let client = reqwest::Client::new();
+
+    // Every page has new posts, so only RSS_MAX_PAGES stops the poll
+    let new_count = poll_once(&client, &config, &db).await.unwrap();
+    assert_eq!(new_count, 4);
+
+    let state = get_feed_state(db.pool(), &config.rss_url)
+        .await
+        .unwrap()
+        .expect("feed state recorded");
+    assert_eq!(state.newest_post_id, Some(6));
+    assert_eq!(state.backfilled_at, None);
+}
+
+#[tokio::test]
+async fn test_poll_once_backfills_full_feed_on_first_poll() {
+    let (db, temp_dir) = setup_db().await;
+    let mock_server = MockServer::start().await;
+    mount_paged_feed(&mock_server).await;
+
+    // One page per poll normally; backfill ignores that limit
+    let config = Config {
+        rss_backfill: true,
+        ..create_test_config(
+            &format!("{}/posts.json", mock_server.uri()),
+            temp_dir.path(),
+        )
+    };
+    let client = reqwest::Client::new();
+
+    let new_count = poll_once(&client, &config, &db).await.unwrap();
+    assert_eq!(new_count, 6);
+
+    let state = get_feed_state(db.pool(), &config.rss_url)
+        .await
+        .unwrap()
+        .expect("feed state recorded");
+    assert_eq!(state.newest_post_id, Some(6));
+    assert!(state.backfilled_at.is_some());
+
+    // Later polls page as usual and stop at the first page with nothing new
+    let requests_before = mock_server.received_requests().await.unwrap().len();
+    assert_eq!(poll_once(&client, &config, &db).await.unwrap(), 0);
+    let requests_after = mock_server.received_requests().await.unwrap().len();
+    assert_eq!(requests_after - requests_before, 1);
+}
+
+#[tokio::test]
+async fn test_poll_once_backfill_stops_at_page_cap() {
+    let (db, temp_dir) = setup_db().await;
+    let mock_server = MockServer::start().await;
+    mount_paged_feed(&mock_server).await;
+
+    let config = Config {
+        rss_backfill: true,
+        rss_backfill_max_pages: 2,
+        ..create_test_config(
+            &format!("{}/posts.json", mock_server.uri()),
+            temp_dir.path(),
+        )
+    };
+    let client = reqwest::Client::new();
+
+    let new_count = poll_once(&client, &config, &db).await.unwrap();
+    assert_eq!(new_count, 4);
+
+    // The cut-short backfill records where to pick up
+    let state = get_feed_state(db.pool(), &config.rss_url)
+        .await
+        .unwrap()
+        .expect("feed state recorded");
+    assert_eq!(state.newest_post_id, Some(6));
+    assert_eq!(state.backfill_cursor, Some(3));
+    assert_eq!(state.backfilled_at, None);
+
+    // The next poll checks the top of the feed, then resumes before post 3
+    assert_eq!(poll_once(&client, &config, &db).await.unwrap(), 2);
+    let state = get_feed_state(db.pool(), &config.rss_url)
+        .await
+        .unwrap()
+        .expect("feed state recorded");
+    assert_eq!(state.backfill_cursor, None);
+    assert!(state.backfilled_at.is_some());
+}
+
+#[tokio::test]
+async fn test_poll_once_does_not_backfill_feed_with_stored_posts() {
+    let (db, temp_dir) = setup_db().await;
+    let mock_server = MockServer::start().await;
+    mount_paged_feed(&mock_server).await;
+
+    let config = Config {
+        rss_backfill: true,
+        rss_max_pages: 10,
+        ..create_test_config(
+            &format!("{}/posts.json", mock_server.uri()),
+            temp_dir.path(),
+        )
+    };
+    let client = reqwest::Client::new();
+
+    // A database from before feed state was tracked, holding post 4
+    insert_post(
+        db.pool(),
+        &NewPost {
+            guid: "127.0.0.1-post-4".to_string(),
+            discourse_url: format!("{}/t/t4/4/1", mock_server.uri()),
+            author: None,
+            author_handle: None,
+            title: None,
+            body_html: None,
+            content_hash: None,
+            published_at: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    // Paging stops at the already-seen post instead of backfilling
+    assert_eq!(poll_once(&client, &config, &db).await.unwrap(), 3);
+    let state = get_feed_state(db.pool(), &config.rss_url)
+        .await
+        .unwrap()
+        .expect("feed state recorded");
+    assert_eq!(state.newest_post_id, Some(6));
+    assert_eq!(state.backfilled_at, None);
+    assert_eq!(state.backfill_cursor, None);
+    assert!(get_post_by_guid(db.pool(), "127.0.0.1-post-1")
+        .await
+        .unwrap()
+        .is_none());
+}
+
 #[tokio::test]
 async fn test_poll_once_idempotent() {
     let (db, temp_dir) = setup_db().await;
@@ -401,3 +863,109 @@ async fn test_poll_once_handles_invalid_json() {
     let result = poll_once(&client, &config, &db).await;
     assert!(result.is_err(), "Should fail on invalid RSS");
 }
+
+/// Thread JSON page with two posts, as returned by `/t/{topic_id}/{post_number}.json`.
+const THREAD_PAGE_JSON: &str = r#"{
+  "post_stream": {
+    "posts": [
+      {
+        "id": 501,
+        "post_number": 1,
+        "username": "testuser",
+        "topic_id": 500,
+        "topic_slug": "thread",
+        "created_at": "2024-01-01T12:00:00.000Z",
+        "updated_at": "2024-01-01T12:00:00.000Z",
+        "cooked": "<p><a href=\"https://www.youtube.com/watch?v=abc\">video</a></p>",
+        "posts_count": 2
+      },
+      {
+        "id": 502,
+        "post_number": 2,
+        "username": "testuser",
+        "topic_id": 500,
+        "topic_slug": "thread",
+        "created_at": "2024-01-01T12:05:00.000Z",
+        "updated_at": "2024-01-01T12:05:00.000Z",
+        "cooked": "<p><a href=\"https://www.reddit.com/r/test/comments/xyz\">reddit</a></p>"
+      }
+    ]
+  }
+}"#;
+
+#[tokio::test]
+async fn test_thread_archive_job_stops_when_cancelled() {
+    let (db, temp_dir) = setup_db().await;
+    let mock_server = MockServer::start().await;
+
+    // Slow first page so the cancellation lands while the worker is mid-run.
+    Mock::given(method("GET"))
+        .and(path("/t/500/1.json"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(THREAD_PAGE_JSON)
+                .insert_header("content-type", "application/json")
+                .set_delay(Duration::from_millis(1500)),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/t/500/9999.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(THREAD_PAGE_JSON))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let config = create_test_config(&format!("{}/posts.rss", mock_server.uri()), temp_dir.path());
+    let user_id = create_user(db.pool(), "thread_user", "hash", false)
+        .await
+        .unwrap();
+    let job_id = insert_thread_archive_job(
+        db.pool(),
+        &NewThreadArchiveJob {
+            thread_url: format!("{}/t/thread/500", mock_server.uri()),
+            rss_url: format!("{}/t/thread/500.rss", mock_server.uri()),
+            user_id,
+        },
+    )
+    .await
+    .unwrap();
+    let job = get_thread_archive_job(db.pool(), job_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let worker = {
+        let config = config.clone();
+        let db = db.clone();
+        tokio::spawn(async move { process_job(&config, &db, &job).await })
+    };
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(set_thread_archive_job_cancelled(db.pool(), job_id)
+        .await
+        .unwrap());
+
+    tokio::time::timeout(Duration::from_secs(10), worker)
+        .await
+        .expect("worker should stop after cancellation")
+        .unwrap();
+
+    let job = get_thread_archive_job(db.pool(), job_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(job.status, "cancelled");
+    assert!(job.completed_at.is_some());
+    assert_eq!(job.processed_posts, 0);
+    assert!(get_post_by_guid(db.pool(), "topic-500-post-501")
+        .await
+        .unwrap()
+        .is_none());
+
+    // A finished job can't be cancelled again.
+    assert!(!set_thread_archive_job_cancelled(db.pool(), job_id)
+        .await
+        .unwrap());
+}
@@ -7,8 +7,9 @@ use axum::http::{Request, StatusCode};
 use axum::Router;
 use discourse_link_archiver::config::Config;
 use discourse_link_archiver::db::{
-    count_submissions_from_ip_last_hour, get_link_by_normalized_url, get_pending_archives,
-    insert_submission, submission_exists_for_url, Database, NewSubmission,
+    count_submissions_from_ip_last_hour, get_archive_by_link_id, get_link_by_normalized_url,
+    get_pending_archives, get_submission, insert_submission, set_submission_complete,
+    submission_exists_for_url, submission_retry_after_secs, Database, NewSubmission,
 };
 use tempfile::TempDir;
 use tower::ServiceExt;
@@ -63,7 +64,7 @@ async fn submit_url(
     axum::extract::Form(form): axum::extract::Form<SubmitForm>,
 ) -> axum::response::Response {
     use axum::response::IntoResponse;
-    use discourse_link_archiver::db::{create_pending_archive, insert_link, NewLink};
+    use discourse_link_archiver::db::{create_pending_archive, get_or_create_link, NewLink};
     use discourse_link_archiver::handlers::normalize_url;
 
     if !state.config.submission_enabled {
@@ -104,6 +105,28 @@ async fn submit_url(
     let normalized = normalize_url(url);
     let domain = parsed_url.host_str().unwrap_or("unknown").to_string();
 
+    // Short-circuit to an existing completed archive
+    if let Ok(Some(link)) = get_link_by_normalized_url(state.db.pool(), &normalized).await {
+        if let Ok(Some(archive)) = get_archive_by_link_id(state.db.pool(), link.id).await {
+            if archive.status == "complete" {
+                let submission = NewSubmission {
+                    url: url.to_string(),
+                    normalized_url: normalized.clone(),
+                    submitted_by_ip: client_ip,
+                    submitted_by_user_id: None,
+                };
+                if let Ok(id) = insert_submission(state.db.pool(), &submission).await {
+                    let _ = set_submission_complete(state.db.pool(), id, link.id).await;
+                }
+                return axum::response::Redirect::to(&format!(
+                    "/archive/{}?already_archived=true",
+                    archive.id
+                ))
+                .into_response();
+            }
+        }
+    }
+
     // Check for duplicate submission
     match submission_exists_for_url(state.db.pool(), &normalized).await {
         Ok(true) => {
@@ -131,26 +154,14 @@ async fn submit_url(
     }
 
     // Create or find link
-    let link_id = match get_link_by_normalized_url(state.db.pool(), &normalized).await {
-        Ok(Some(link)) => link.id,
-        Ok(None) => {
-            let new_link = NewLink {
-                original_url: url.to_string(),
-                normalized_url: normalized.clone(),
-                canonical_url: None,
-                domain,
-            };
-            match insert_link(state.db.pool(), &new_link).await {
-                Ok(id) => id,
-                Err(_) => {
-                    return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create link")
-                        .into_response();
-                }
-            }
-        }
-        Err(_) => {
-            return (StatusCode::INTERNAL_SERVER_ERROR, "Error").into_response();
-        }
+    let new_link = NewLink {
+        original_url: url.to_string(),
+        normalized_url: normalized.clone(),
+        canonical_url: None,
+        domain,
+    };
+    let Ok(link_id) = get_or_create_link(state.db.pool(), &new_link).await else {
+        return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create link").into_response();
     };
 
     // Create pending archive
@@ -384,6 +395,50 @@ async fn test_submit_rate_limiting() {
     assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
 }
 
+#[tokio::test]
+async fn test_submission_retry_after_secs() {
+    let (db, _temp_dir) = setup_db().await;
+
+    assert_eq!(
+        submission_retry_after_secs(db.pool(), "127.0.0.1")
+            .await
+            .unwrap(),
+        None
+    );
+
+    for (i, age) in ["-50 minutes", "-10 minutes", "-2 hours"]
+        .iter()
+        .enumerate()
+    {
+        let submission = NewSubmission {
+            url: format!("https://example.com/page{i}"),
+            normalized_url: format!("https://example.com/page{i}"),
+            submitted_by_ip: "127.0.0.1".to_string(),
+            submitted_by_user_id: None,
+        };
+        let id = insert_submission(db.pool(), &submission).await.unwrap();
+        sqlx::query("UPDATE submissions SET created_at = datetime('now', ?) WHERE id = ?")
+            .bind(age)
+            .bind(id)
+            .execute(db.pool())
+            .await
+            .unwrap();
+    }
+
+    // The oldest submission inside the window frees a slot in ~10 minutes
+    let secs = submission_retry_after_secs(db.pool(), "127.0.0.1")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!((595..=600).contains(&secs), "{secs}");
+    assert_eq!(
+        submission_retry_after_secs(db.pool(), "10.0.0.1")
+            .await
+            .unwrap(),
+        None
+    );
+}
+
 #[tokio::test]
 async fn test_submit_when_disabled() {
     let (db, _temp_dir) = setup_db().await;
@@ -505,3 +560,85 @@ async fn test_submission_exists_check() {
         .expect("DB error");
     assert!(exists);
 }
+
+#[tokio::test]
+async fn test_submit_already_archived_url_short_circuits() {
+    use discourse_link_archiver::db::{
+        create_pending_archive, insert_link, set_archive_complete, NewLink,
+    };
+
+    let (db, _temp_dir) = setup_db().await;
+    let url = "https://example.com/archived-long-ago";
+
+    let link_id = insert_link(
+        db.pool(),
+        &NewLink {
+            original_url: url.to_string(),
+            normalized_url: url.to_string(),
+            canonical_url: None,
+            domain: "example.com".to_string(),
+        },
+    )
+    .await
+    .expect("Failed to insert link");
+    let archive_id = create_pending_archive(db.pool(), link_id, None)
+        .await
+        .expect("Failed to create archive");
+    set_archive_complete(db.pool(), archive_id, None, None, None, None, None, None)
+        .await
+        .expect("Failed to complete archive");
+
+    let app = create_test_app(db.clone(), true, 10);
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/submit")
+                .header("content-type", "application/x-www-form-urlencoded")
+                .body(Body::from(format!("url={url}")))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert!(response.status().is_redirection());
+    let location = response.headers()["location"].to_str().unwrap();
+    assert_eq!(
+        location,
+        format!("/archive/{archive_id}?already_archived=true")
+    );
+
+    // Nothing new was queued
+    let pending = get_pending_archives(db.pool(), 10).await.expect("DB error");
+    assert!(pending.is_empty());
+
+    // Submission was recorded as complete against the existing link
+    let submission = get_submission(db.pool(), 1)
+        .await
+        .expect("DB error")
+        .expect("Submission should be recorded");
+    assert_eq!(submission.status, "complete");
+    assert_eq!(submission.link_id, Some(link_id));
+}
+
+#[tokio::test]
+async fn test_submit_new_url_is_queued() {
+    let (db, _temp_dir) = setup_db().await;
+    let app = create_test_app(db.clone(), true, 10);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/submit")
+                .header("content-type", "application/x-www-form-urlencoded")
+                .body(Body::from("url=https://example.com/brand-new"))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let pending = get_pending_archives(db.pool(), 10).await.expect("DB error");
+    assert_eq!(pending.len(), 1);
+}
@@ -7,8 +7,8 @@ use axum::http::{Request, StatusCode};
 use axum::Router;
 use discourse_link_archiver::config::Config;
 use discourse_link_archiver::db::{
-    create_pending_archive, insert_link, insert_post, set_archive_complete, Database, NewLink,
-    NewPost,
+    create_pending_archive, get_archive, insert_link, insert_post, set_archive_complete, Database,
+    NewLink, NewPost,
 };
 use tempfile::TempDir;
 use tower::ServiceExt;
@@ -42,6 +42,8 @@ fn create_test_app(db: Database) -> Router {
         .route("/stats", axum::routing::get(stats))
         .route("/healthz", axum::routing::get(health))
         .route("/archive/:id", axum::routing::get(archive_detail))
+        .route("/a/:code", axum::routing::get(archive_permalink))
+        .route("/random", axum::routing::get(random_archive))
         .route("/archive/:id/rearchive", axum::routing::post(rearchive))
         .route("/post/:guid", axum::routing::get(post_detail))
         .layer(CompressionLayer::new())
@@ -147,6 +149,40 @@ async fn archive_detail(
     axum::response::Html(html).into_response()
 }
 
+/// Handler for archive permalinks (GET /a/:code).
+async fn archive_permalink(
+    axum::extract::State(state): axum::extract::State<AppState>,
+    axum::extract::Path(code): axum::extract::Path<String>,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+    use discourse_link_archiver::db::{get_archive_by_short_code, is_valid_short_code};
+
+    if !is_valid_short_code(&code) {
+        return (StatusCode::NOT_FOUND, "Archive not found").into_response();
+    }
+    match get_archive_by_short_code(state.db.pool(), &code).await {
+        Ok(Some(archive)) => {
+            axum::response::Redirect::to(&format!("/archive/{}", archive.id)).into_response()
+        }
+        Ok(None) => (StatusCode::NOT_FOUND, "Archive not found").into_response(),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    }
+}
+
+/// Handler for a random archive (GET /random).
+async fn random_archive(
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> axum::response::Response {
+    use axum::response::{IntoResponse, Redirect};
+    use discourse_link_archiver::db::get_random_archive_id;
+
+    match get_random_archive_id(state.db.pool()).await {
+        Ok(Some(id)) => Redirect::to(&format!("/archive/{id}")).into_response(),
+        Ok(None) => Redirect::to("/").into_response(),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    }
+}
+
 /// Handler for re-archiving an archive (POST /archive/:id/rearchive).
 ///
 /// Mirrors the real web route behavior: resets the archive to pending state.
@@ -232,7 +268,7 @@ async fn test_rearchive_failed_archive_with_no_artifacts() {
     let archive_id = create_pending_archive(db.pool(), link_id, None)
         .await
         .unwrap();
-    set_archive_failed(db.pool(), archive_id, "boom")
+    set_archive_failed(db.pool(), archive_id, "boom", 3)
         .await
         .unwrap();
 
@@ -476,6 +512,95 @@ async fn test_archive_detail_not_found() {
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
 }
 
+#[tokio::test]
+async fn test_archive_permalink() {
+    let (db, _temp_dir) = setup_db().await;
+
+    let new_link = NewLink {
+        original_url: "https://example.com/permalink".to_string(),
+        normalized_url: "https://example.com/permalink".to_string(),
+        canonical_url: None,
+        domain: "example.com".to_string(),
+    };
+    let link_id = insert_link(db.pool(), &new_link).await.unwrap();
+    let archive_id = create_pending_archive(db.pool(), link_id, None)
+        .await
+        .unwrap();
+    let code = get_archive(db.pool(), archive_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .short_code
+        .unwrap();
+
+    let app = create_test_app(db);
+    let get = |uri: String| Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+    let response = app
+        .clone()
+        .oneshot(get(format!("/a/{code}")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    assert_eq!(
+        response.headers()["location"],
+        format!("/archive/{archive_id}").as_str()
+    );
+
+    // Unknown and malformed codes 404
+    for uri in ["/a/zzzzzzzz", "/a/not-a-code"] {
+        let response = app.clone().oneshot(get(uri.to_string())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND, "{uri}");
+    }
+}
+
+#[tokio::test]
+async fn test_random_archive_redirect() {
+    let (db, _temp_dir) = setup_db().await;
+    let app = create_test_app(db.clone());
+    let get = || {
+        Request::builder()
+            .uri("/random")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    // Nothing archived yet: back to the home page
+    let response = app.clone().oneshot(get()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    assert_eq!(response.headers()["location"], "/");
+
+    let new_link = NewLink {
+        original_url: "https://example.com/random".to_string(),
+        normalized_url: "https://example.com/random".to_string(),
+        canonical_url: None,
+        domain: "example.com".to_string(),
+    };
+    let link_id = insert_link(db.pool(), &new_link).await.unwrap();
+    let archive_id = create_pending_archive(db.pool(), link_id, None)
+        .await
+        .unwrap();
+    set_archive_complete(
+        db.pool(),
+        archive_id,
+        Some("Random"),
+        None,
+        None,
+        Some("text"),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let response = app.oneshot(get()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    assert_eq!(
+        response.headers()["location"],
+        format!("/archive/{archive_id}").as_str()
+    );
+}
+
 #[tokio::test]
 async fn test_archive_detail_found() {
     let (db, _temp_dir) = setup_db().await;
@@ -562,6 +687,7 @@ async fn test_post_detail_found() {
         guid: "test-post-guid-12345".to_string(),
         discourse_url: "https://forum.example.com/t/test/1".to_string(),
         author: Some("test_user".to_string()),
+        author_handle: None,
         title: Some("Test Post Title".to_string()),
         body_html: Some("<p>Test body content</p>".to_string()),
         content_hash: Some("abc123".to_string()),
@@ -606,6 +732,7 @@ async fn test_stats_with_data() {
         guid: "stats-test-post".to_string(),
         discourse_url: "https://forum.example.com/t/stats/1".to_string(),
         author: Some("user".to_string()),
+        author_handle: None,
         title: Some("Stats Test".to_string()),
         body_html: None,
         content_hash: None,